@@ -49,6 +49,7 @@ const Configuration = {
             'papyrus_sync',
             'papyrus_test_utils',
             'release',
+            'sequencer_client',
             'sequencing',
             'starknet_api',
             'starknet_batcher',
@@ -0,0 +1,8 @@
+// A paymaster sponsoring every transaction, without any validations.
+
+%lang starknet
+
+@external
+func __validate_paymaster__(paymaster_data: felt) {
+    return ();
+}
@@ -0,0 +1,104 @@
+//! Executes sequences of invoke transactions of a single account with the transaction executor,
+//! and checks the invariants of `check_executor_invariants`. Run with:
+//! cargo +nightly fuzz run transaction_executor
+//! from the blockifier crate directory.
+
+#![no_main]
+
+use blockifier::test_utils::executor_fuzzing::{
+    check_executor_invariants,
+    FuzzedCall,
+    FuzzedFeeFields,
+    FuzzedInvoke,
+};
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use starknet_api::test_utils::{
+    DEFAULT_L1_DATA_GAS_MAX_AMOUNT,
+    DEFAULT_L1_GAS_AMOUNT,
+    DEFAULT_L2_GAS_MAX_AMOUNT,
+    MAX_FEE,
+};
+use starknet_api::transaction::fields::Fee;
+
+const MAX_TXS_PER_INPUT: usize = 8;
+const MAX_UNKNOWN_ENTRY_POINT_ARGS: usize = 4;
+
+#[derive(Arbitrary, Debug)]
+enum Call {
+    StorageWrite { key: u8, value: u64 },
+    ReturnResult(u64),
+    WriteAndRevert,
+    Fail,
+    UnknownEntryPoint(Vec<u64>),
+}
+
+#[derive(Arbitrary, Debug)]
+enum PricePercentage {
+    Half,
+    Default,
+    Double,
+}
+
+#[derive(Arbitrary, Debug)]
+enum FeeFields {
+    V1 { max_fee: u128 },
+    V3 { l1_gas: u64, l2_gas: u64, l1_data_gas: u64, price_percentage: PricePercentage },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Invoke {
+    call: Call,
+    fee_fields: FeeFields,
+    // Mapped to the offset from the account nonce; see `FuzzedInvoke::nonce_offset`.
+    nonce_offset: u8,
+}
+
+impl From<Invoke> for FuzzedInvoke {
+    fn from(invoke: Invoke) -> Self {
+        let call = match invoke.call {
+            Call::StorageWrite { key, value } => FuzzedCall::StorageWrite { key, value },
+            Call::ReturnResult(num) => FuzzedCall::ReturnResult(num),
+            Call::WriteAndRevert => FuzzedCall::WriteAndRevert,
+            Call::Fail => FuzzedCall::Fail,
+            Call::UnknownEntryPoint(mut args) => {
+                args.truncate(MAX_UNKNOWN_ENTRY_POINT_ARGS);
+                FuzzedCall::UnknownEntryPoint(args)
+            }
+        };
+        // The fee fields are limited to twice their defaults, as in the property tests.
+        let fee_fields = match invoke.fee_fields {
+            FeeFields::V1 { max_fee } => {
+                FuzzedFeeFields::V1 { max_fee: Fee(max_fee % (2 * MAX_FEE.0 + 1)) }
+            }
+            FeeFields::V3 { l1_gas, l2_gas, l1_data_gas, price_percentage } => {
+                FuzzedFeeFields::V3 {
+                    l1_gas: l1_gas % (2 * DEFAULT_L1_GAS_AMOUNT.0 + 1),
+                    l2_gas: l2_gas % (2 * DEFAULT_L2_GAS_MAX_AMOUNT.0 + 1),
+                    l1_data_gas: l1_data_gas % (2 * DEFAULT_L1_DATA_GAS_MAX_AMOUNT.0 + 1),
+                    price_percentage: match price_percentage {
+                        PricePercentage::Half => 50,
+                        PricePercentage::Default => 100,
+                        PricePercentage::Double => 200,
+                    },
+                }
+            }
+        };
+        // A nonzero offset (1 or 2) in a tenth of the transactions.
+        let nonce_offset = match invoke.nonce_offset % 10 {
+            8 => 1,
+            9 => 2,
+            _ => 0,
+        };
+        Self { call, fee_fields, nonce_offset }
+    }
+}
+
+fuzz_target!(|invokes: Vec<Invoke>| {
+    let invokes: Vec<FuzzedInvoke> =
+        invokes.into_iter().take(MAX_TXS_PER_INPUT).map(FuzzedInvoke::from).collect();
+    if invokes.is_empty() {
+        return;
+    }
+    check_executor_invariants(&invokes);
+});
@@ -23,8 +23,10 @@ pub const CONSUMED_MSG_TO_L2_ENCODED_DATA_SIZE: usize =
 pub const L1_GAS_USAGE: &str = "gas_weight";
 pub const N_STEPS_RESOURCE: &str = "n_steps";
 pub const N_EVENTS: &str = "n_events";
+pub const N_MESSAGES: &str = "n_messages";
 pub const MESSAGE_SEGMENT_LENGTH: &str = "message_segment_length";
 pub const STATE_DIFF_SIZE: &str = "state_diff_size";
+pub const COMPRESSED_STATE_DIFF_SIZE: &str = "compressed_state_diff_size";
 pub const N_MEMORY_HOLES: &str = "n_memory_holes";
 pub const SIERRA_GAS: &str = "sierra_gas";
 
@@ -0,0 +1,58 @@
+use std::env;
+use std::process::ExitCode;
+
+use blockifier::test_utils::feature_contracts_fixtures::{
+    verify_feature_contracts_fixtures,
+    FixtureMismatch,
+    FIX_FEATURE_CONTRACTS_COMMAND,
+};
+use blockifier::test_utils::{CairoVersion, RunnableCairo1};
+use infra_utils::compile_time_cargo_manifest_dir;
+
+const USAGE: &str = "Usage: feature_contracts_fixtures <verify|fix> [cairo0|cairo1]";
+
+/// Recompiles the feature contracts, and verifies that the committed artifacts match (`verify`), or
+/// regenerates them (`fix`). Compiles both Cairo versions, unless one is given:
+/// cargo run -p blockifier --bin feature_contracts_fixtures --features testing -- verify cairo1
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let fix = match args.first().map(String::as_str) {
+        Some("verify") => false,
+        Some("fix") => true,
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let cairo_versions = match args.get(1).map(String::as_str) {
+        None => vec![CairoVersion::Cairo0, CairoVersion::Cairo1(RunnableCairo1::Casm)],
+        Some("cairo0") => vec![CairoVersion::Cairo0],
+        Some("cairo1") => vec![CairoVersion::Cairo1(RunnableCairo1::Casm)],
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // The feature contract paths are relative to the crate directory.
+    env::set_current_dir(compile_time_cargo_manifest_dir!())
+        .expect("Failed to enter the blockifier crate directory.");
+
+    let mismatches: Vec<_> = cairo_versions
+        .into_iter()
+        .flat_map(|cairo_version| verify_feature_contracts_fixtures(cairo_version, fix))
+        .collect();
+    for FixtureMismatch { source_path, artifact_path } in &mismatches {
+        if fix {
+            println!("Regenerated {artifact_path} from {source_path}.");
+        } else {
+            println!("{artifact_path} does not match the compilation of {source_path}.");
+        }
+    }
+
+    if fix || mismatches.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+    eprintln!("Run `{FIX_FEATURE_CONTRACTS_COMMAND}` to regenerate the mismatching artifacts.");
+    ExitCode::FAILURE
+}
@@ -1,5 +1,9 @@
 pub mod block;
+pub mod block_bundle;
 pub mod config;
+pub mod differential_execution;
+pub mod execution_replay;
+pub mod side_effects;
 pub mod stateful_validator;
 pub mod transaction_executor;
 #[cfg(test)]
@@ -1,13 +1,23 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
 use log::warn;
 use starknet_api::block::{
+    BlockHash,
     BlockHashAndNumber,
+    BlockInfo,
     BlockNumber,
     GasPrice,
     GasPriceVector,
     GasPrices,
     NonzeroGasPrice,
+    StarknetVersion,
 };
+use starknet_api::core::{ascii_as_felt, ContractAddress};
+use starknet_api::crypto::utils::Signature;
 use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
 
 use crate::abi::constants;
 use crate::state::errors::StateError;
@@ -18,6 +28,33 @@ use crate::versioned_constants::{OsConstants, VersionedConstants};
 #[path = "block_test.rs"]
 pub mod block_test;
 
+// Domain separators of the randomness hashes, as Cairo short strings.
+const RANDOMNESS_SEED_PREFIX: &[u8] = b"STARKNET_RANDOMNESS_SEED";
+const RANDOMNESS_MESSAGE_PREFIX: &[u8] = b"STARKNET_RANDOMNESS_MESSAGE";
+
+// The storage layout of the block metadata system contract.
+pub const SEQUENCER_ADDRESS_STORAGE_KEY: u8 = 0;
+pub const STARKNET_VERSION_STORAGE_KEY: u8 = 1;
+// The gas prices are stored in consecutive keys, in the order: L1 gas, L1 data gas and L2 gas in
+// wei, followed by the same in fri.
+pub const GAS_PRICES_STORAGE_KEY: u8 = 2;
+
+/// Resolves the hashes of blocks missing from the storage of the block hash contract, e.g., blocks
+/// older than the state an execution starts from in fork or simulation mode, for the
+/// `get_block_hash` syscall; see `BlockContext::with_block_hash_provider`.
+pub trait BlockHashProvider: Send + Sync {
+    /// Returns the hash of the given block, or `None` if it is unknown.
+    fn get_block_hash(&self, block_number: BlockNumber) -> StateResult<Option<BlockHash>>;
+}
+
+pub type SharedBlockHashProvider = Arc<dyn BlockHashProvider>;
+
+impl Debug for dyn BlockHashProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("BlockHashProvider")
+    }
+}
+
 /// Warns if the submitted gas prices do not match the expected gas prices.
 fn validate_l2_gas_price(gas_prices: &GasPrices) {
     // TODO(Aner): fix backwards compatibility.
@@ -92,3 +129,97 @@ pub fn pre_process_block(
 
     Ok(())
 }
+
+/// The inputs of the randomness seed of a block that are not part of its block info.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockRandomness {
+    /// The system contract the seed is written to.
+    pub contract_address: ContractAddress,
+    /// The signature of the block's proposer over the `randomness_message_hash` of the block.
+    pub proposer_signature: Signature,
+}
+
+/// The message the proposer of a block signs to contribute to the randomness seed of the block.
+pub fn randomness_message_hash(block_number: BlockNumber) -> Felt {
+    Poseidon::hash_array(&[
+        Felt::from_bytes_be_slice(RANDOMNESS_MESSAGE_PREFIX),
+        Felt::from(block_number.0),
+    ])
+}
+
+/// Derives the pseudo-random seed of a block from the block number, the sequencer address, the
+/// hash of the block `STORED_BLOCK_HASH_BUFFER` blocks back and the proposer's signature over the
+/// `randomness_message_hash` of the block number.
+///
+/// Without the signature, anyone could compute the seed once the old block is known, i.e., about
+/// `STORED_BLOCK_HASH_BUFFER` (10) blocks ahead. With it, only the proposer knows the seed before
+/// proposing the block. The proposer can still bias the seed: by withholding its proposal, or by
+/// choosing among the many valid ECDSA signatures of the message. Validators don't verify the
+/// signature either, as no public keys of the proposers are known. Hence the seed must not secure
+/// high stakes.
+pub fn randomness_seed(
+    block_info: &BlockInfo,
+    old_block_number_and_hash: Option<BlockHashAndNumber>,
+    proposer_signature: &Signature,
+) -> Felt {
+    let old_block_hash = old_block_number_and_hash.map_or(Felt::ZERO, |block| block.hash.0);
+    Poseidon::hash_array(&[
+        Felt::from_bytes_be_slice(RANDOMNESS_SEED_PREFIX),
+        Felt::from(block_info.block_number.0),
+        *block_info.sequencer_address.0.key(),
+        old_block_hash,
+        proposer_signature.r,
+        proposer_signature.s,
+    ])
+}
+
+// Block pre-processing of chains that expose randomness to contracts.
+// Writes the randomness seed of the block under its block number in the storage of the randomness
+// system contract, from which contracts read it during the block.
+// NOTE: like `pre_process_block`, this function is idempotent.
+pub fn write_randomness_seed(
+    state: &mut dyn State,
+    randomness: BlockRandomness,
+    block_info: &BlockInfo,
+    old_block_number_and_hash: Option<BlockHashAndNumber>,
+) -> StateResult<()> {
+    let seed =
+        randomness_seed(block_info, old_block_number_and_hash, &randomness.proposer_signature);
+    let block_number_as_storage_key = StorageKey::from(block_info.block_number.0);
+    state.set_storage_at(randomness.contract_address, block_number_as_storage_key, seed)
+}
+
+// Block pre-processing of Starknet versions that define a block metadata system contract.
+// Writes the sequencer address, the Starknet version and the gas prices of the block to the storage
+// of the system contract, from which contracts read them during the block.
+// NOTE: like `pre_process_block`, this function is idempotent.
+pub fn write_block_metadata(
+    state: &mut dyn State,
+    block_info: &BlockInfo,
+    starknet_version: StarknetVersion,
+    os_constants: &OsConstants,
+) -> StateResult<()> {
+    let Some(block_metadata_contract_address) =
+        os_constants.os_contract_addresses.block_metadata_contract_address()
+    else {
+        return Ok(());
+    };
+    let starknet_version = ascii_as_felt(&starknet_version.to_string())?;
+    let GasPrices { eth_gas_prices, strk_gas_prices } = &block_info.gas_prices;
+    let gas_prices = [eth_gas_prices, strk_gas_prices].into_iter().flat_map(|prices| {
+        [prices.l1_gas_price, prices.l1_data_gas_price, prices.l2_gas_price]
+            .map(|price| Felt::from(price.get().0))
+    });
+
+    let entries = [
+        (SEQUENCER_ADDRESS_STORAGE_KEY, *block_info.sequencer_address.0.key()),
+        (STARKNET_VERSION_STORAGE_KEY, starknet_version),
+    ]
+    .into_iter()
+    .chain((GAS_PRICES_STORAGE_KEY..).zip(gas_prices));
+    for (key, value) in entries {
+        state.set_storage_at(block_metadata_contract_address, StorageKey::from(key), value)?;
+    }
+
+    Ok(())
+}
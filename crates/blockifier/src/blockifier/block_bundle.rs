@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockHashAndNumber, BlockInfo, StarknetVersion};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::executable_transaction::Transaction as ApiTransaction;
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+
+use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::transaction_executor::{
+    TransactionExecutor,
+    TransactionExecutorError,
+    TransactionExecutorResult,
+};
+use crate::bouncer::BouncerConfig;
+use crate::context::{BlockContext, ChainInfo};
+use crate::execution::contract_class::RunnableCompiledClass;
+use crate::state::cached_state::{CommitmentStateDiff, StateMaps};
+use crate::state::errors::StateError;
+use crate::state::state_api::{StateReader, StateResult};
+use crate::transaction::objects::TransactionExecutionInfo;
+use crate::transaction::transaction_execution::Transaction;
+use crate::versioned_constants::{
+    VersionedConstants,
+    VersionedConstantsError,
+    VersionedConstantsOverrides,
+};
+
+#[cfg(test)]
+#[path = "block_bundle_test.rs"]
+mod block_bundle_test;
+
+/// The reads of a block's parent state performed while executing the block.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockStateReads {
+    pub state_maps: StateMaps,
+    /// The classes loaded during execution, including the ones declared in the block.
+    pub class_hashes: HashSet<ClassHash>,
+}
+
+#[derive(Debug, Error)]
+pub enum BlockBundleError {
+    #[error(transparent)]
+    ExecutorError(#[from] TransactionExecutorError),
+    #[error("Malformed block bundle archive: {0}")]
+    MalformedArchive(#[from] serde_json::Error),
+    #[error(transparent)]
+    StateError(#[from] StateError),
+    #[error(transparent)]
+    VersionedConstantsError(#[from] VersionedConstantsError),
+}
+
+pub type BlockBundleResult<T> = Result<T, BlockBundleError>;
+
+/// The execution results of the transactions of a replayed block, and the block's state diff.
+pub type BlockReplayOutput =
+    (Vec<TransactionExecutorResult<TransactionExecutionInfo>>, CommitmentStateDiff);
+
+/// Everything needed to re-execute a block offline: the block context, the transactions, and the
+/// parts of the parent state the execution touched.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BlockBundle {
+    pub block_info: BlockInfo,
+    pub chain_info: ChainInfo,
+    pub starknet_version: StarknetVersion,
+    pub versioned_constants_overrides: VersionedConstantsOverrides,
+    pub bouncer_config: BouncerConfig,
+    pub retrospective_block_hash: Option<BlockHashAndNumber>,
+    pub transactions: Vec<ApiTransaction>,
+    pub state_reads: BundleStateReads,
+    /// The parent state classes loaded during execution; classes declared in the block are part of
+    /// their declare transactions.
+    pub classes: Vec<(ClassHash, ContractClass)>,
+}
+
+impl BlockBundle {
+    /// Serializes the bundle into a single self-contained archive.
+    pub fn to_archive(&self) -> BlockBundleResult<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    pub fn from_archive(archive: &[u8]) -> BlockBundleResult<Self> {
+        Ok(serde_json::from_slice(archive)?)
+    }
+
+    pub fn block_context(&self) -> BlockBundleResult<BlockContext> {
+        let versioned_constants = VersionedConstants::get(&self.starknet_version)?
+            .clone()
+            .with_overrides(self.versioned_constants_overrides.clone());
+        Ok(BlockContext::new(
+            self.block_info.clone(),
+            self.chain_info.clone(),
+            versioned_constants,
+            self.bouncer_config.clone(),
+        ))
+    }
+
+    /// Re-executes the block on top of the bundled state reads. Reading state outside the bundle
+    /// fails, as it means the replay diverged from the original execution.
+    pub fn replay(&self) -> BlockBundleResult<BlockReplayOutput> {
+        let state_reader = BundleStateReader::try_from(self)?;
+        let mut executor = TransactionExecutor::pre_process_and_create(
+            state_reader,
+            self.block_context()?,
+            self.retrospective_block_hash,
+            TransactionExecutorConfig::default(),
+        )?;
+        let txs: Vec<Transaction> =
+            self.transactions.iter().cloned().map(Transaction::new_for_sequencing).collect();
+        let execution_results = executor.execute_txs(&txs);
+        let (state_diff, _, _) = executor.finalize()?;
+        Ok((execution_results, state_diff))
+    }
+}
+
+/// A serializable form of [StateMaps]; entries are sorted so that exporting the same block always
+/// yields the same archive.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BundleStateReads {
+    pub nonces: Vec<(ContractAddress, Nonce)>,
+    pub class_hashes: Vec<(ContractAddress, ClassHash)>,
+    pub storage: Vec<(ContractAddress, StorageKey, Felt)>,
+    pub compiled_class_hashes: Vec<(ClassHash, CompiledClassHash)>,
+    pub declared_contracts: Vec<(ClassHash, bool)>,
+}
+
+fn sorted<T: Ord>(entries: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut entries: Vec<T> = entries.into_iter().collect();
+    entries.sort();
+    entries
+}
+
+impl From<StateMaps> for BundleStateReads {
+    fn from(state_maps: StateMaps) -> Self {
+        Self {
+            nonces: sorted(state_maps.nonces),
+            class_hashes: sorted(state_maps.class_hashes),
+            storage: sorted(
+                state_maps.storage.into_iter().map(|((address, key), value)| (address, key, value)),
+            ),
+            compiled_class_hashes: sorted(state_maps.compiled_class_hashes),
+            declared_contracts: sorted(state_maps.declared_contracts),
+        }
+    }
+}
+
+impl From<BundleStateReads> for StateMaps {
+    fn from(state_reads: BundleStateReads) -> Self {
+        Self {
+            nonces: state_reads.nonces.into_iter().collect(),
+            class_hashes: state_reads.class_hashes.into_iter().collect(),
+            storage: state_reads
+                .storage
+                .into_iter()
+                .map(|(address, key, value)| ((address, key), value))
+                .collect(),
+            compiled_class_hashes: state_reads.compiled_class_hashes.into_iter().collect(),
+            declared_contracts: state_reads.declared_contracts.into_iter().collect(),
+        }
+    }
+}
+
+/// Serves the state reads of a block bundle, or of an execution replay.
+pub(crate) struct BundleStateReader {
+    state_maps: StateMaps,
+    classes: HashMap<ClassHash, RunnableCompiledClass>,
+}
+
+impl BundleStateReader {
+    pub(crate) fn new(
+        state_reads: &BundleStateReads,
+        classes: &[(ClassHash, ContractClass)],
+    ) -> StateResult<Self> {
+        let classes = classes
+            .iter()
+            .map(|(class_hash, class)| -> StateResult<(ClassHash, RunnableCompiledClass)> {
+                Ok((*class_hash, class.clone().try_into()?))
+            })
+            .collect::<StateResult<_>>()?;
+        Ok(Self { state_maps: state_reads.clone().into(), classes })
+    }
+}
+
+impl TryFrom<&BlockBundle> for BundleStateReader {
+    type Error = StateError;
+
+    fn try_from(bundle: &BlockBundle) -> StateResult<Self> {
+        Self::new(&bundle.state_reads, &bundle.classes)
+    }
+}
+
+fn missing_read(description: String) -> StateError {
+    StateError::StateReadError(format!("{description} is not part of the bundled state reads."))
+}
+
+impl StateReader for BundleStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.state_maps.storage.get(&(contract_address, key)).copied().ok_or_else(|| {
+            missing_read(format!("Storage key {key:?} of contract {contract_address}"))
+        })
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.state_maps
+            .nonces
+            .get(&contract_address)
+            .copied()
+            .ok_or_else(|| missing_read(format!("Nonce of contract {contract_address}")))
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.state_maps
+            .class_hashes
+            .get(&contract_address)
+            .copied()
+            .ok_or_else(|| missing_read(format!("Class hash of contract {contract_address}")))
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.classes.get(&class_hash).cloned().ok_or(StateError::UndeclaredClassHash(class_hash))
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.state_maps
+            .compiled_class_hashes
+            .get(&class_hash)
+            .copied()
+            .ok_or_else(|| missing_read(format!("Compiled class hash of class {class_hash}")))
+    }
+}
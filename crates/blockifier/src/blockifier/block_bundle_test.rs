@@ -0,0 +1,107 @@
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::block::{BlockInfo, StarknetVersion};
+use starknet_api::executable_transaction::Transaction as ApiTransaction;
+use starknet_api::invoke_tx_args;
+use starknet_api::test_utils::invoke::executable_invoke_tx;
+
+use crate::blockifier::block_bundle::{BlockBundle, BlockBundleError};
+use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::transaction_executor::TransactionExecutor;
+use crate::bouncer::BouncerConfig;
+use crate::context::ChainInfo;
+use crate::state::cached_state::CommitmentStateDiff;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{
+    create_calldata,
+    maybe_dummy_block_hash_and_number,
+    CairoVersion,
+    RunnableCairo1,
+    BALANCE,
+};
+use crate::transaction::transaction_execution::Transaction;
+use crate::versioned_constants::VersionedConstantsOverrides;
+
+/// Executes an invoke transaction and returns the bundle of its block, and the block's original
+/// state diff.
+fn execute_and_bundle_block() -> (BlockBundle, CommitmentStateDiff) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let account_contract =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let chain_info = ChainInfo::create_for_testing();
+    let state = test_state(&chain_info, BALANCE, &[(test_contract, 1), (account_contract, 1)]);
+
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_storage_read_write",
+        &[1.into(), 2.into()],
+    );
+    let invoke_tx = executable_invoke_tx(invoke_tx_args! {
+        sender_address: account_contract.get_instance_address(0),
+        calldata,
+    });
+    let block_info = BlockInfo::create_for_testing();
+    let retrospective_block_hash = maybe_dummy_block_hash_and_number(block_info.block_number);
+    let mut bundle = BlockBundle {
+        block_info,
+        chain_info,
+        starknet_version: StarknetVersion::LATEST,
+        versioned_constants_overrides: VersionedConstantsOverrides::default(),
+        bouncer_config: BouncerConfig::max(),
+        retrospective_block_hash,
+        transactions: vec![ApiTransaction::Account(invoke_tx.clone())],
+        state_reads: Default::default(),
+        classes: vec![],
+    };
+
+    let mut executor = TransactionExecutor::pre_process_and_create(
+        state,
+        bundle.block_context().unwrap(),
+        retrospective_block_hash,
+        TransactionExecutorConfig::default(),
+    )
+    .unwrap();
+    executor.execute(&Transaction::new_for_sequencing(ApiTransaction::Account(invoke_tx))).unwrap();
+    let block_state_reads = executor.get_block_state_reads().unwrap();
+    let (state_diff, _, _) = executor.finalize().unwrap();
+
+    bundle.state_reads = block_state_reads.state_maps.into();
+    bundle.classes = [test_contract, account_contract]
+        .iter()
+        .map(|contract| (contract.get_class_hash(), contract.get_class()))
+        .filter(|(class_hash, _)| block_state_reads.class_hashes.contains(class_hash))
+        .collect();
+    (bundle, state_diff)
+}
+
+#[test]
+fn replay_reproduces_block() {
+    let (bundle, expected_state_diff) = execute_and_bundle_block();
+
+    let archive = bundle.to_archive().unwrap();
+    let restored_bundle = BlockBundle::from_archive(&archive).unwrap();
+    assert_eq!(restored_bundle, bundle);
+
+    let (execution_results, state_diff) = restored_bundle.replay().unwrap();
+    assert_eq!(execution_results.len(), 1);
+    assert!(execution_results[0].as_ref().is_ok_and(|info| !info.is_reverted()));
+    assert_eq!(state_diff, expected_state_diff);
+}
+
+#[test]
+fn replay_fails_on_reads_outside_the_bundle() {
+    let (mut bundle, _) = execute_and_bundle_block();
+    bundle.state_reads.nonces.clear();
+
+    let (execution_results, _) = bundle.replay().unwrap();
+    assert!(execution_results[0].is_err());
+}
+
+#[test]
+fn malformed_archive() {
+    assert_matches!(
+        BlockBundle::from_archive(b"not a bundle"),
+        Err(BlockBundleError::MalformedArchive(_))
+    );
+}
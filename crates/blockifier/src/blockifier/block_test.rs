@@ -1,9 +1,24 @@
-use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
-use starknet_api::felt;
+use std::collections::HashMap;
+
+use pretty_assertions::assert_eq;
+use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockInfo, BlockNumber, StarknetVersion};
+use starknet_api::core::ascii_as_felt;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::state::StorageKey;
+use starknet_api::{contract_address, felt};
+use starknet_types_core::felt::Felt;
 
 use crate::abi::constants;
-use crate::blockifier::block::pre_process_block;
+use crate::blockifier::block::{
+    pre_process_block,
+    randomness_seed,
+    write_block_metadata,
+    write_randomness_seed,
+    BlockRandomness,
+    GAS_PRICES_STORAGE_KEY,
+    SEQUENCER_ADDRESS_STORAGE_KEY,
+    STARKNET_VERSION_STORAGE_KEY,
+};
 use crate::context::ChainInfo;
 use crate::state::state_api::StateReader;
 use crate::test_utils::contracts::FeatureContract;
@@ -49,3 +64,86 @@ fn test_pre_process_block() {
         format!("{}", error.unwrap_err())
     );
 }
+
+#[test]
+fn test_write_randomness_seed() {
+    let mut state = test_state(&ChainInfo::create_for_testing(), BALANCE, &[]);
+    let randomness = BlockRandomness {
+        contract_address: contract_address!("0x3"),
+        proposer_signature: Signature { r: felt!(30_u8), s: felt!(31_u8) },
+    };
+    let old_block = BlockHashAndNumber { hash: BlockHash(felt!(20_u8)), number: BlockNumber(0) };
+    let block_info = BlockInfo {
+        block_number: BlockNumber(constants::STORED_BLOCK_HASH_BUFFER),
+        ..BlockInfo::create_for_testing()
+    };
+
+    write_randomness_seed(&mut state, randomness, &block_info, Some(old_block)).unwrap();
+    let signature = randomness.proposer_signature;
+    let seed = randomness_seed(&block_info, Some(old_block), &signature);
+    let written_seed = state
+        .get_storage_at(randomness.contract_address, StorageKey::from(block_info.block_number.0));
+    assert_eq!(written_seed.unwrap(), seed);
+
+    // The seed depends on the block, on the old block hash and on the proposer's signature.
+    let next_block_info =
+        BlockInfo { block_number: block_info.block_number.next().unwrap(), ..block_info.clone() };
+    assert_ne!(randomness_seed(&next_block_info, Some(old_block), &signature), seed);
+    let other_old_block = BlockHashAndNumber { hash: BlockHash(felt!(21_u8)), ..old_block };
+    assert_ne!(randomness_seed(&block_info, Some(other_old_block), &signature), seed);
+    let other_signature = Signature { r: felt!(32_u8), ..signature };
+    assert_ne!(randomness_seed(&block_info, Some(old_block), &other_signature), seed);
+}
+
+#[test]
+fn test_write_block_metadata() {
+    let mut state = test_state(&ChainInfo::create_for_testing(), BALANCE, &[]);
+    let block_info = BlockInfo::create_for_testing();
+    let starknet_version = StarknetVersion::V0_13_5;
+    let os_constants = &VersionedConstants::get(&starknet_version).unwrap().os_constants;
+    let block_metadata_contract_address =
+        os_constants.os_contract_addresses.block_metadata_contract_address().unwrap();
+
+    write_block_metadata(&mut state, &block_info, starknet_version, os_constants).unwrap();
+    // Writing the metadata again leaves the state unchanged.
+    write_block_metadata(&mut state, &block_info, starknet_version, os_constants).unwrap();
+
+    let eth_gas_prices = &block_info.gas_prices.eth_gas_prices;
+    let strk_gas_prices = &block_info.gas_prices.strk_gas_prices;
+    let gas_prices = [
+        eth_gas_prices.l1_gas_price,
+        eth_gas_prices.l1_data_gas_price,
+        eth_gas_prices.l2_gas_price,
+        strk_gas_prices.l1_gas_price,
+        strk_gas_prices.l1_data_gas_price,
+        strk_gas_prices.l2_gas_price,
+    ]
+    .map(|price| Felt::from(price.get().0));
+    let expected_metadata = [
+        (SEQUENCER_ADDRESS_STORAGE_KEY, *block_info.sequencer_address.0.key()),
+        (STARKNET_VERSION_STORAGE_KEY, ascii_as_felt("0.13.5").unwrap()),
+    ]
+    .into_iter()
+    .chain((GAS_PRICES_STORAGE_KEY..).zip(gas_prices))
+    .map(|(key, value)| ((block_metadata_contract_address, StorageKey::from(key)), value));
+    assert_eq!(
+        state.to_state_diff().unwrap().state_maps.storage,
+        HashMap::from_iter(expected_metadata)
+    );
+}
+
+#[test]
+fn test_write_block_metadata_before_system_contract() {
+    let mut state = test_state(&ChainInfo::create_for_testing(), BALANCE, &[]);
+    let starknet_version = StarknetVersion::V0_13_4;
+    let os_constants = &VersionedConstants::get(&starknet_version).unwrap().os_constants;
+
+    write_block_metadata(
+        &mut state,
+        &BlockInfo::create_for_testing(),
+        starknet_version,
+        os_constants,
+    )
+    .unwrap();
+    assert!(state.to_state_diff().unwrap().state_maps.storage.is_empty());
+}
@@ -1,25 +1,82 @@
 use std::collections::BTreeMap;
 
+use itertools::Itertools;
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
-use serde::{Deserialize, Serialize};
+use serde::de::Error as DeserializationError;
+use serde::{Deserialize, Deserializer, Serialize};
+use starknet_api::core::ClassHash;
+use starknet_types_core::felt::Felt;
 
-use crate::state::global_cache::GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST;
+use crate::state::global_cache::{
+    GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST,
+    GLOBAL_CONTRACT_CACHE_SIZE_IN_BYTES_FOR_TEST,
+};
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct TransactionExecutorConfig {
     pub concurrency_config: ConcurrencyConfig,
+    // Accrues the fees paid to the sequencer in sequential execution, and settles them with a
+    // single balance update (concurrent execution always works this way).
+    pub deferred_fee_settlement: bool,
+    // Aggregates the resources used by each entry point executed in the block; see
+    // `TransactionExecutor::execution_profile`.
+    pub profile_entry_points: bool,
+    // Indexes the events and L2 to L1 messages of the block; see
+    // `TransactionExecutor::side_effects`.
+    pub index_side_effects: bool,
+    // Also executes each transaction by the Cairo VM, and reports where its execution diverges
+    // from the Cairo native one; see `TransactionExecutor::execution_divergences`.
+    pub shadow_vm_execution: bool,
 }
 impl TransactionExecutorConfig {
     #[cfg(any(test, feature = "testing", feature = "native_blockifier"))]
     pub fn create_for_testing(concurrency_enabled: bool) -> Self {
-        Self { concurrency_config: ConcurrencyConfig::create_for_testing(concurrency_enabled) }
+        Self {
+            concurrency_config: ConcurrencyConfig::create_for_testing(concurrency_enabled),
+            deferred_fee_settlement: false,
+            profile_entry_points: false,
+            index_side_effects: false,
+            shadow_vm_execution: false,
+        }
     }
 }
 
 impl SerializeConfig for TransactionExecutorConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        append_sub_config_name(self.concurrency_config.dump(), "concurrency_config")
+        let mut dump = append_sub_config_name(self.concurrency_config.dump(), "concurrency_config");
+        dump.append(&mut BTreeMap::from([
+            ser_param(
+                "deferred_fee_settlement",
+                &self.deferred_fee_settlement,
+                "Accrues the fees paid to the sequencer during sequential execution and settles \
+                 them once per block, instead of updating the sequencer balance per transaction.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "profile_entry_points",
+                &self.profile_entry_points,
+                "Aggregates the steps, builtins, syscalls and gas used by each entry point \
+                 executed in the block, to find hot contracts.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "index_side_effects",
+                &self.index_side_effects,
+                "Indexes the events of the block by contract and keys, and the messages sent to \
+                 L1 by hash, with bloom filters over both, for storage and RPC queries.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "shadow_vm_execution",
+                &self.shadow_vm_execution,
+                "Also executes each sequentially executed transaction by the Cairo VM, discarding \
+                 the result, and logs where it diverges from the Cairo native execution in state \
+                 diff, events or gas.",
+                ParamPrivacyInput::Public,
+            ),
+        ]));
+        dump
     }
 }
 
@@ -28,14 +85,30 @@ pub struct ConcurrencyConfig {
     pub enabled: bool,
     pub n_workers: usize,
     pub chunk_size: usize,
+    // Shrinks the chunks when their transactions conflict often, and grows them back (up to
+    // `chunk_size`) when they do not.
+    pub adaptive_chunk_size: bool,
+    pub validation_strategy: ValidationStrategy,
 }
 
 impl ConcurrencyConfig {
     pub fn create_for_testing(concurrency_enabled: bool) -> Self {
         if concurrency_enabled {
-            return Self { enabled: true, n_workers: 4, chunk_size: 64 };
+            return Self {
+                enabled: true,
+                n_workers: 4,
+                chunk_size: 64,
+                adaptive_chunk_size: false,
+                validation_strategy: ValidationStrategy::Optimistic,
+            };
+        }
+        Self {
+            enabled: false,
+            n_workers: 0,
+            chunk_size: 0,
+            adaptive_chunk_size: false,
+            validation_strategy: ValidationStrategy::Optimistic,
         }
-        Self { enabled: false, n_workers: 0, chunk_size: 0 }
     }
 }
 
@@ -60,23 +133,70 @@ impl SerializeConfig for ConcurrencyConfig {
                 "The size of the transaction chunk executed in parallel.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "adaptive_chunk_size",
+                &self.adaptive_chunk_size,
+                "If true, the chunk size adapts to the conflict rate observed in the block, up to \
+                 chunk_size.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "validation_strategy",
+                &self.validation_strategy,
+                "How far transactions are executed ahead of their commit, to be validated later: \
+                 'Optimistic' (as far as the workers get) or 'Conservative' (one transaction per \
+                 worker).",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
 
+/// How far concurrent workers execute transactions ahead of the commit of the chunk; a transaction
+/// executed ahead of its predecessors is validated against their writes, and re-executed if it read
+/// a value they changed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ValidationStrategy {
+    /// Workers execute any transaction of the chunk, maximizing parallelism; best for blocks whose
+    /// transactions rarely conflict.
+    #[default]
+    Optimistic,
+    /// Workers execute transactions at most one per worker ahead of the first uncommitted one,
+    /// bounding the executions wasted on conflicts; best for blocks with high contention.
+    Conservative,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ContractClassManagerConfig {
     pub run_cairo_native: bool,
     pub wait_on_native_compilation: bool,
     pub contract_cache_size: usize,
+    pub contract_cache_size_in_bytes: usize,
+    // Classes that are never compiled to, nor executed by, Cairo native.
+    #[serde(deserialize_with = "deserialize_class_hashes")]
+    pub native_execution_disabled_class_hashes: Vec<ClassHash>,
+    // The stack space guaranteed to each native execution; native code runs on the real stack.
+    pub native_execution_stack_size: usize,
+    // The maximal growth of the resident memory of the process during a native execution; beyond
+    // it, the execution is discarded and rerun by the Cairo VM. Zero disables the check.
+    pub native_execution_max_memory_growth: usize,
 }
 
+// Native executions should run out of gas before exhausting this much stack, even on extremely
+// deep recursions with the maximal gas.
+pub const DEFAULT_NATIVE_EXECUTION_STACK_SIZE: usize = 160 * 1024 * 1024;
+pub const DEFAULT_NATIVE_EXECUTION_MAX_MEMORY_GROWTH: usize = 1 << 30;
+
 impl Default for ContractClassManagerConfig {
     fn default() -> Self {
         Self {
             run_cairo_native: false,
             wait_on_native_compilation: false,
             contract_cache_size: GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST,
+            contract_cache_size_in_bytes: GLOBAL_CONTRACT_CACHE_SIZE_IN_BYTES_FOR_TEST,
+            native_execution_disabled_class_hashes: Vec::new(),
+            native_execution_stack_size: DEFAULT_NATIVE_EXECUTION_STACK_SIZE,
+            native_execution_max_memory_growth: DEFAULT_NATIVE_EXECUTION_MAX_MEMORY_GROWTH,
         }
     }
 }
@@ -102,6 +222,55 @@ impl SerializeConfig for ContractClassManagerConfig {
                 "The size of the global contract cache.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "contract_cache_size_in_bytes",
+                &self.contract_cache_size_in_bytes,
+                "The maximal estimated size, in bytes, of the classes in the global contract \
+                 cache; the least recently used classes are evicted beyond it.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "native_execution_disabled_class_hashes",
+                &serialize_class_hashes(&self.native_execution_disabled_class_hashes),
+                "Space-separated hex class hashes that are always executed by the Cairo VM, even \
+                 when Cairo native execution is enabled.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "native_execution_stack_size",
+                &self.native_execution_stack_size,
+                "The stack space, in bytes, guaranteed to each Cairo native execution.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "native_execution_max_memory_growth",
+                &self.native_execution_max_memory_growth,
+                "The maximal growth, in bytes, of the resident memory of the process during a \
+                 Cairo native execution; beyond it, the execution is rerun by the Cairo VM and \
+                 the class is no longer executed natively. Zero disables the check.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
+
+/// Serializes class hashes to a "0x1 0x2" string structure.
+fn serialize_class_hashes(class_hashes: &[ClassHash]) -> String {
+    class_hashes.iter().map(|class_hash| class_hash.0.to_hex_string()).join(" ")
+}
+
+/// Deserializes class hashes from a "0x1 0x2" string structure.
+fn deserialize_class_hashes<'de, D>(de: D) -> Result<Vec<ClassHash>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_str = String::deserialize(de)?;
+    raw_str
+        .split_whitespace()
+        .map(|raw_class_hash| {
+            Felt::from_hex(raw_class_hash).map(ClassHash).map_err(|error| {
+                D::Error::custom(format!("Invalid class hash {raw_class_hash}: {error}"))
+            })
+        })
+        .collect()
+}
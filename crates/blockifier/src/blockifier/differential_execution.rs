@@ -0,0 +1,150 @@
+use starknet_api::execution_resources::GasVector;
+use starknet_api::transaction::Event;
+
+use crate::context::BlockContext;
+use crate::state::cached_state::{CachedState, StateMaps, TransactionalState};
+use crate::state::state_api::{StateReader, StateResult};
+use crate::transaction::objects::{TransactionExecutionInfo, TransactionExecutionResult};
+use crate::transaction::rpc_receipt::ordered_events;
+use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transactions::ExecutableTransaction;
+
+#[cfg(test)]
+#[path = "differential_execution_test.rs"]
+mod test;
+
+/// The result of executing a transaction, and the changes it made to the state (none if it
+/// failed).
+pub type BackendExecution = (TransactionExecutionResult<TransactionExecutionInfo>, StateMaps);
+type BackendExecutionRef<'a> =
+    (&'a TransactionExecutionResult<TransactionExecutionInfo>, &'a StateMaps);
+
+/// A difference between the executions of a transaction by Cairo native and by the Cairo VM.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExecutionDivergence {
+    /// Exactly one of the executions failed or reverted; holds the error of each execution, if
+    /// any.
+    Outcome {
+        native_error: Option<String>,
+        vm_error: Option<String>,
+    },
+    StateDiff {
+        native: StateMaps,
+        vm: StateMaps,
+    },
+    Events {
+        native: Vec<Event>,
+        vm: Vec<Event>,
+    },
+    Gas {
+        native: GasVector,
+        vm: GasVector,
+    },
+}
+
+/// The execution of a transaction by Cairo native, and its divergences from the execution by the
+/// Cairo VM.
+#[derive(Debug)]
+pub struct DifferentialExecutionOutput {
+    pub execution_result: TransactionExecutionResult<TransactionExecutionInfo>,
+    pub divergences: Vec<ExecutionDivergence>,
+}
+
+/// Executes the given transaction by Cairo native, committing it to the state, and by the Cairo
+/// VM, on top of the same state, discarding its changes; returns the native execution, and where
+/// the VM execution diverged from it. Classes not compiled to Cairo native run by the VM in both
+/// executions.
+pub fn execute_differentially<S: StateReader>(
+    tx: &Transaction,
+    state: &mut CachedState<S>,
+    block_context: &BlockContext,
+) -> StateResult<DifferentialExecutionOutput> {
+    let concurrency_mode = false;
+    let vm_execution = execute_on_vm(tx, state, block_context, concurrency_mode)?;
+
+    let mut transactional_state = TransactionalState::create_transactional(state);
+    let execution_result =
+        tx.execute_raw(&mut transactional_state, block_context, concurrency_mode);
+    let state_diff = match execution_result {
+        Ok(_) => transactional_state.to_state_diff()?.state_maps,
+        Err(_) => StateMaps::default(),
+    };
+    let (vm_execution_result, vm_state_diff) = &vm_execution;
+    let divergences =
+        compare_executions((&execution_result, &state_diff), (vm_execution_result, vm_state_diff));
+
+    match execution_result {
+        Ok(_) => transactional_state.commit(),
+        Err(_) => transactional_state.abort(),
+    }
+    Ok(DifferentialExecutionOutput { execution_result, divergences })
+}
+
+/// Executes the given transaction by the Cairo VM on top of the given state, without changing it
+/// or the given block context.
+pub fn execute_on_vm<S: StateReader>(
+    tx: &Transaction,
+    state: &mut CachedState<S>,
+    block_context: &BlockContext,
+    concurrency_mode: bool,
+) -> StateResult<BackendExecution> {
+    let vm_block_context = block_context.to_shadow_vm_context();
+    let mut transactional_state = TransactionalState::create_transactional(state);
+    let execution_result =
+        tx.execute_raw(&mut transactional_state, &vm_block_context, concurrency_mode);
+    let state_diff = match execution_result {
+        Ok(_) => transactional_state.to_state_diff()?.state_maps,
+        Err(_) => StateMaps::default(),
+    };
+    transactional_state.abort();
+    Ok((execution_result, state_diff))
+}
+
+/// Compares the executions of a transaction by Cairo native and by the Cairo VM. Errors are
+/// compared by presence only, as their traces differ between the backends.
+pub fn compare_executions(
+    native: BackendExecutionRef<'_>,
+    vm: BackendExecutionRef<'_>,
+) -> Vec<ExecutionDivergence> {
+    let (native_result, native_state_diff) = native;
+    let (vm_result, vm_state_diff) = vm;
+    let (native_error, vm_error) = (execution_error(native_result), execution_error(vm_result));
+    if native_error.is_some() != vm_error.is_some() {
+        return vec![ExecutionDivergence::Outcome { native_error, vm_error }];
+    }
+
+    let mut divergences = vec![];
+    if native_state_diff != vm_state_diff {
+        divergences.push(ExecutionDivergence::StateDiff {
+            native: native_state_diff.clone(),
+            vm: vm_state_diff.clone(),
+        });
+    }
+    if let (Ok(native_execution_info), Ok(vm_execution_info)) = (native_result, vm_result) {
+        let native_events = events(native_execution_info);
+        let vm_events = events(vm_execution_info);
+        if native_events != vm_events {
+            divergences.push(ExecutionDivergence::Events { native: native_events, vm: vm_events });
+        }
+        let native_gas = native_execution_info.receipt.gas;
+        let vm_gas = vm_execution_info.receipt.gas;
+        if native_gas != vm_gas {
+            divergences.push(ExecutionDivergence::Gas { native: native_gas, vm: vm_gas });
+        }
+    }
+    divergences
+}
+
+/// Returns the error of a failed execution, or the revert error of a reverted one.
+fn execution_error(
+    execution_result: &TransactionExecutionResult<TransactionExecutionInfo>,
+) -> Option<String> {
+    match execution_result {
+        Ok(execution_info) => execution_info.revert_error.as_ref().map(ToString::to_string),
+        Err(error) => Some(error.to_string()),
+    }
+}
+
+fn events(execution_info: &TransactionExecutionInfo) -> Vec<Event> {
+    execution_info.non_optional_call_infos().flat_map(ordered_events).collect()
+}
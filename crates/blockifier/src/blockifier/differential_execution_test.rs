@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::ClassHash;
+use starknet_api::execution_resources::{GasAmount, GasVector};
+use starknet_api::test_utils::invoke::executable_invoke_tx;
+use starknet_api::transaction::fields::ValidResourceBounds;
+use starknet_api::{contract_address, felt, invoke_tx_args, nonce, storage_key};
+
+use crate::blockifier::differential_execution::{
+    compare_executions,
+    execute_differentially,
+    ExecutionDivergence,
+};
+use crate::context::BlockContext;
+use crate::state::cached_state::StateMaps;
+use crate::state::state_api::StateReader;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, RunnableCairo1, BALANCE};
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::objects::{TransactionExecutionInfo, TransactionExecutionResult};
+use crate::transaction::test_utils::{block_context, default_all_resource_bounds};
+use crate::transaction::transaction_execution::Transaction;
+
+#[test]
+fn test_compare_executions() {
+    let succeeded: TransactionExecutionResult<_> = Ok(TransactionExecutionInfo::default());
+    let mut execution_info = TransactionExecutionInfo::default();
+    execution_info.receipt.gas = GasVector { l2_gas: GasAmount(1), ..Default::default() };
+    let charged_more: TransactionExecutionResult<_> = Ok(execution_info);
+    let failed: TransactionExecutionResult<_> =
+        Err(TransactionExecutionError::DeclareTransactionError {
+            class_hash: ClassHash::default(),
+        });
+    let error_message = failed.as_ref().unwrap_err().to_string();
+    let empty_state_diff = StateMaps::default();
+    let state_diff = StateMaps {
+        nonces: HashMap::from([(contract_address!("0x1"), nonce!(1_u8))]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        compare_executions((&succeeded, &empty_state_diff), (&succeeded, &empty_state_diff)),
+        vec![]
+    );
+    assert_eq!(
+        compare_executions((&succeeded, &empty_state_diff), (&charged_more, &state_diff)),
+        vec![
+            ExecutionDivergence::StateDiff { native: empty_state_diff.clone(), vm: state_diff },
+            ExecutionDivergence::Gas {
+                native: GasVector::default(),
+                vm: GasVector { l2_gas: GasAmount(1), ..Default::default() },
+            },
+        ]
+    );
+    assert_eq!(
+        compare_executions((&failed, &empty_state_diff), (&succeeded, &empty_state_diff)),
+        vec![ExecutionDivergence::Outcome { native_error: Some(error_message), vm_error: None }]
+    );
+}
+
+#[rstest]
+#[cfg_attr(feature = "cairo_native", case::native(CairoVersion::Cairo1(RunnableCairo1::Native)))]
+#[case::vm(CairoVersion::Cairo1(RunnableCairo1::Casm))]
+fn test_execute_differentially(
+    #[case] cairo_version: CairoVersion,
+    block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let account_contract =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let mut state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(test_contract, 1), (account_contract, 1)],
+    );
+    let sender_address = account_contract.get_instance_address(0);
+    let contract_address = test_contract.get_instance_address(0);
+    let tx: Transaction =
+        AccountTransaction::new_for_sequencing(executable_invoke_tx(invoke_tx_args! {
+            sender_address,
+            calldata: create_calldata(
+                contract_address,
+                "test_storage_read_write",
+                &[felt!(15_u8), felt!(17_u8)],
+            ),
+            resource_bounds: default_all_resource_bounds,
+            nonce: nonce!(0_u8),
+        }))
+        .into();
+
+    let output = execute_differentially(&tx, &mut state, &block_context).unwrap();
+
+    assert_eq!(output.divergences, vec![]);
+    assert!(!output.execution_result.unwrap().is_reverted());
+    // Only the native execution is committed.
+    assert_eq!(state.get_nonce_at(sender_address).unwrap(), nonce!(1_u8));
+    assert_eq!(state.get_storage_at(contract_address, storage_key!(15_u8)).unwrap(), felt!(17_u8));
+}
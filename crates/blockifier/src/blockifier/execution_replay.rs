@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockInfo, StarknetVersion};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::ClassHash;
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::executable_transaction::Transaction as ApiTransaction;
+use thiserror::Error;
+
+use crate::blockifier::block_bundle::{BundleStateReader, BundleStateReads};
+use crate::bouncer::BouncerConfig;
+use crate::context::{BlockContext, ChainInfo};
+use crate::state::cached_state::{CachedState, CommitmentStateDiff, StateMaps};
+use crate::state::errors::StateError;
+use crate::transaction::objects::{TransactionExecutionInfo, TransactionExecutionResult};
+use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transactions::ExecutableTransaction;
+use crate::versioned_constants::{
+    VersionedConstants,
+    VersionedConstantsError,
+    VersionedConstantsOverrides,
+};
+
+#[cfg(test)]
+#[path = "execution_replay_test.rs"]
+mod execution_replay_test;
+
+#[derive(Debug, Error)]
+pub enum ExecutionReplayError {
+    #[error("Failed to access the execution replay file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed execution replay file: {0}")]
+    MalformedFile(#[from] serde_json::Error),
+    #[error("The block context must set its Starknet version to capture an execution replay.")]
+    MissingStarknetVersion,
+    #[error(transparent)]
+    StateError(#[from] StateError),
+    #[error(transparent)]
+    VersionedConstantsError(#[from] VersionedConstantsError),
+}
+
+pub type ExecutionReplayResult<T> = Result<T, ExecutionReplayError>;
+
+/// The result of a replayed transaction execution, and the state diff of the transaction.
+pub type TxReplayOutput =
+    (TransactionExecutionResult<TransactionExecutionInfo>, CommitmentStateDiff);
+
+/// Everything needed to reproduce the execution of a single transaction offline: the transaction,
+/// the block context it was executed in, and the state it read. Captured executions can be
+/// attached to bug reports, and added as regression fixtures.
+/// NOTE: the Starknet version of the block selects the versioned constants of the replay; a custom
+/// versioned constants file is not captured.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExecutionReplay {
+    pub block_info: BlockInfo,
+    pub chain_info: ChainInfo,
+    pub starknet_version: StarknetVersion,
+    pub versioned_constants_overrides: VersionedConstantsOverrides,
+    pub bouncer_config: BouncerConfig,
+    pub state_diff_da_mode: Option<DataAvailabilityMode>,
+    pub enforce_l1_handler_fee: bool,
+    pub transaction: ApiTransaction,
+    pub state_reads: BundleStateReads,
+    /// The classes loaded during execution; a declared class is part of its declare transaction.
+    pub classes: Vec<(ClassHash, ContractClass)>,
+}
+
+impl ExecutionReplay {
+    /// Captures the execution of the given transaction in the given block context, from the values
+    /// it read from the state, e.g., its `TransactionExecutionInfo::read_set`, and the classes it
+    /// loaded.
+    pub fn capture(
+        tx: &ApiTransaction,
+        state_reads: StateMaps,
+        classes: impl IntoIterator<Item = (ClassHash, ContractClass)>,
+        block_context: &BlockContext,
+    ) -> ExecutionReplayResult<Self> {
+        let starknet_version =
+            block_context.starknet_version().ok_or(ExecutionReplayError::MissingStarknetVersion)?;
+        let versioned_constants = &block_context.versioned_constants;
+        let mut classes: Vec<_> = classes.into_iter().collect();
+        classes.sort_by_key(|(class_hash, _)| *class_hash);
+
+        Ok(Self {
+            block_info: block_context.block_info.clone(),
+            chain_info: block_context.chain_info.clone(),
+            starknet_version,
+            versioned_constants_overrides: VersionedConstantsOverrides {
+                validate_max_n_steps: versioned_constants.validate_max_n_steps,
+                max_recursion_depth: versioned_constants.max_recursion_depth,
+                invoke_tx_max_n_steps: versioned_constants.invoke_tx_max_n_steps,
+                execution_quotas: versioned_constants.execution_quotas.clone(),
+                versioned_constants_path: None,
+            },
+            bouncer_config: block_context.bouncer_config.clone(),
+            state_diff_da_mode: block_context.state_diff_da_mode(),
+            enforce_l1_handler_fee: block_context.enforces_l1_handler_fee(),
+            transaction: tx.clone(),
+            state_reads: state_reads.into(),
+            classes,
+        })
+    }
+
+    /// Writes the replay into a single self-contained file.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> ExecutionReplayResult<()> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    pub fn read_from_file(path: impl AsRef<Path>) -> ExecutionReplayResult<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Reproduces the transaction execution captured in the given file.
+    pub fn run(path: impl AsRef<Path>) -> ExecutionReplayResult<TxReplayOutput> {
+        Self::read_from_file(path)?.replay()
+    }
+
+    pub fn block_context(&self) -> ExecutionReplayResult<BlockContext> {
+        let versioned_constants = VersionedConstants::get(&self.starknet_version)?
+            .clone()
+            .with_overrides(self.versioned_constants_overrides.clone());
+        let mut block_context = BlockContext::new(
+            self.block_info.clone(),
+            self.chain_info.clone(),
+            versioned_constants,
+            self.bouncer_config.clone(),
+        );
+        if let Some(state_diff_da_mode) = self.state_diff_da_mode {
+            block_context = block_context.with_state_diff_da_mode(state_diff_da_mode);
+        }
+        if self.enforce_l1_handler_fee {
+            block_context = block_context.with_l1_handler_fee_enforcement();
+        }
+        Ok(block_context)
+    }
+
+    /// Re-executes the transaction on top of the captured state reads. Reading state outside the
+    /// captured reads fails the transaction, as it means the replay diverged from the original
+    /// execution.
+    pub fn replay(&self) -> ExecutionReplayResult<TxReplayOutput> {
+        let mut state = CachedState::new(BundleStateReader::new(&self.state_reads, &self.classes)?);
+        let tx = Transaction::new_for_sequencing(self.transaction.clone());
+        let execution_result = tx.execute(&mut state, &self.block_context()?);
+        let state_diff = state.to_state_diff()?.state_maps.into();
+        Ok((execution_result, state_diff))
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs;
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::block::{BlockInfo, StarknetVersion};
+use starknet_api::executable_transaction::Transaction as ApiTransaction;
+use starknet_api::invoke_tx_args;
+use starknet_api::test_utils::invoke::executable_invoke_tx;
+use tempfile::tempdir;
+
+use crate::blockifier::execution_replay::{ExecutionReplay, ExecutionReplayError};
+use crate::bouncer::BouncerConfig;
+use crate::context::{BlockContext, ChainInfo};
+use crate::state::cached_state::{CommitmentStateDiff, StateMaps};
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, RunnableCairo1, BALANCE};
+use crate::transaction::objects::TransactionExecutionInfo;
+use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transactions::ExecutableTransaction;
+use crate::versioned_constants::VersionedConstants;
+
+fn block_context() -> BlockContext {
+    BlockContext::new(
+        BlockInfo::create_for_testing(),
+        ChainInfo::create_for_testing(),
+        VersionedConstants::get(&StarknetVersion::LATEST).unwrap().clone(),
+        BouncerConfig::max(),
+    )
+    .with_read_set_recording()
+}
+
+/// Executes an invoke transaction and captures its execution; returns the replay, and the original
+/// execution info and state diff.
+fn execute_and_capture() -> (ExecutionReplay, TransactionExecutionInfo, CommitmentStateDiff) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let account_contract =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let block_context = block_context().with_starknet_version(StarknetVersion::LATEST);
+    let mut state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(test_contract, 1), (account_contract, 1)],
+    );
+
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_storage_read_write",
+        &[1.into(), 2.into()],
+    );
+    let invoke_tx = ApiTransaction::Account(executable_invoke_tx(invoke_tx_args! {
+        sender_address: account_contract.get_instance_address(0),
+        calldata,
+    }));
+    let execution_info = Transaction::new_for_sequencing(invoke_tx.clone())
+        .execute(&mut state, &block_context)
+        .unwrap();
+    let state_diff = state.to_state_diff().unwrap().state_maps.into();
+
+    let classes = [test_contract, account_contract]
+        .iter()
+        .map(|contract| (contract.get_class_hash(), contract.get_class()));
+    let replay = ExecutionReplay::capture(
+        &invoke_tx,
+        execution_info.read_set.clone().unwrap(),
+        classes,
+        &block_context,
+    )
+    .unwrap();
+    (replay, execution_info, state_diff)
+}
+
+#[test]
+fn replay_reproduces_execution() {
+    let (replay, expected_execution_info, expected_state_diff) = execute_and_capture();
+    let replay_dir = tempdir().unwrap();
+    let replay_path = replay_dir.path().join("replay.json");
+
+    replay.write_to_file(&replay_path).unwrap();
+    assert_eq!(ExecutionReplay::read_from_file(&replay_path).unwrap(), replay);
+
+    let (execution_result, state_diff) = ExecutionReplay::run(&replay_path).unwrap();
+    let execution_info = execution_result.unwrap();
+    assert!(!execution_info.is_reverted());
+    assert_eq!(execution_info.execute_call_info, expected_execution_info.execute_call_info);
+    assert_eq!(execution_info.receipt, expected_execution_info.receipt);
+    assert_eq!(state_diff, expected_state_diff);
+}
+
+#[test]
+fn replay_fails_on_reads_outside_the_capture() {
+    let (mut replay, _, _) = execute_and_capture();
+    replay.state_reads.nonces.clear();
+
+    let (execution_result, _) = replay.replay().unwrap();
+    assert!(execution_result.is_err());
+}
+
+#[test]
+fn capture_requires_starknet_version() {
+    let tx = ApiTransaction::Account(executable_invoke_tx(invoke_tx_args! {}));
+    assert_matches!(
+        ExecutionReplay::capture(&tx, StateMaps::default(), [], &block_context()),
+        Err(ExecutionReplayError::MissingStarknetVersion)
+    );
+}
+
+#[test]
+fn malformed_file() {
+    let replay_dir = tempdir().unwrap();
+    let replay_path = replay_dir.path().join("replay.json");
+    fs::write(&replay_path, "not a replay").unwrap();
+
+    assert_matches!(
+        ExecutionReplay::run(&replay_path),
+        Err(ExecutionReplayError::MalformedFile(_))
+    );
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use starknet_api::core::ContractAddress;
+use starknet_api::hash::keccak256;
+use starknet_api::transaction::{EventData, EventKey, MessageToL1, TransactionHash};
+use starknet_types_core::felt::Felt;
+
+use crate::transaction::objects::TransactionExecutionInfo;
+use crate::transaction::rpc_receipt::{ordered_events, ordered_messages};
+
+#[cfg(test)]
+#[path = "side_effects_test.rs"]
+mod test;
+
+// The size of a bloom filter in bits, and the number of bits set for each inserted value.
+const BLOOM_FILTER_N_BITS: usize = 2048;
+const BLOOM_FILTER_N_HASHES: usize = 3;
+
+/// An event emitted in the block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmittedEvent {
+    pub tx_index: usize,
+    pub tx_hash: TransactionHash,
+    // The position of the event among the events of its transaction.
+    pub event_index: usize,
+    pub data: EventData,
+}
+
+/// A message sent to L1 in the block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SentMessage {
+    pub tx_index: usize,
+    pub tx_hash: TransactionHash,
+    pub message: MessageToL1,
+    pub hash: L2ToL1MessageHash,
+}
+
+/// The hash under which a message sent to L1 is consumed on L1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct L2ToL1MessageHash(pub [u8; 32]);
+
+impl L2ToL1MessageHash {
+    /// Computes the Keccak256 hash of the message: its sender, recipient, payload length and
+    /// payload, each encoded as 32 bytes.
+    pub fn new(message: &MessageToL1) -> Self {
+        let encoded_message: Vec<u8> = [
+            *message.from_address.0.key(),
+            Felt::from(message.to_address),
+            Felt::from(message.payload.0.len()),
+        ]
+        .iter()
+        .chain(&message.payload.0)
+        .flat_map(Felt::to_bytes_be)
+        .collect();
+
+        Self(keccak256(&encoded_message))
+    }
+}
+
+/// A bloom filter over felts: may report a value that was not inserted, but never misses one that
+/// was.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloomFilter([u8; BLOOM_FILTER_N_BITS / 8]);
+
+impl Default for BloomFilter {
+    fn default() -> Self {
+        Self([0; BLOOM_FILTER_N_BITS / 8])
+    }
+}
+
+impl BloomFilter {
+    pub fn insert(&mut self, value: &Felt) {
+        for bit in Self::bits(value) {
+            self.0[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    pub fn may_contain(&self, value: &Felt) -> bool {
+        Self::bits(value).all(|bit| self.0[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    // As in the Ethereum logs bloom, each of the first byte pairs of the value's Keccak256 hash
+    // selects a bit.
+    fn bits(value: &Felt) -> impl Iterator<Item = usize> {
+        let hash = keccak256(&value.to_bytes_be());
+        (0..BLOOM_FILTER_N_HASHES).map(move |i| {
+            usize::from(u16::from_be_bytes([hash[2 * i], hash[2 * i + 1]])) % BLOOM_FILTER_N_BITS
+        })
+    }
+}
+
+/// The events and L2 to L1 messages of a block, indexed for storage and RPC queries, so that
+/// these need not walk the call infos of every transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockSideEffects {
+    /// The events of the block, by their emitting contract and keys, in emission order.
+    pub events: HashMap<(ContractAddress, Vec<EventKey>), Vec<EmittedEvent>>,
+    /// The messages sent to L1 in the block, in sending order.
+    pub messages: Vec<SentMessage>,
+    /// A filter over the emitting contracts and the event keys of the block.
+    pub events_filter: BloomFilter,
+    /// A filter over the senders and recipients of the messages sent to L1 in the block.
+    pub messages_filter: BloomFilter,
+    n_txs: usize,
+}
+
+impl BlockSideEffects {
+    /// Adds the side effects of the next transaction of the block.
+    pub fn add_tx(&mut self, tx_hash: TransactionHash, execution_info: &TransactionExecutionInfo) {
+        let tx_index = self.n_txs;
+        self.n_txs += 1;
+
+        let events = execution_info.non_optional_call_infos().flat_map(ordered_events);
+        for (event_index, event) in events.enumerate() {
+            self.events_filter.insert(event.from_address.0.key());
+            for key in &event.content.keys {
+                self.events_filter.insert(&key.0);
+            }
+            let emitted_event =
+                EmittedEvent { tx_index, tx_hash, event_index, data: event.content.data };
+            self.events
+                .entry((event.from_address, event.content.keys))
+                .or_default()
+                .push(emitted_event);
+        }
+
+        for message in execution_info.non_optional_call_infos().flat_map(ordered_messages) {
+            self.messages_filter.insert(message.from_address.0.key());
+            self.messages_filter.insert(&Felt::from(message.to_address));
+            let hash = L2ToL1MessageHash::new(&message);
+            self.messages.push(SentMessage { tx_index, tx_hash, message, hash });
+        }
+    }
+
+    /// Returns the number of transactions added so far.
+    pub fn n_txs(&self) -> usize {
+        self.n_txs
+    }
+}
@@ -0,0 +1,115 @@
+use pretty_assertions::assert_eq;
+use starknet_api::core::EthAddress;
+use starknet_api::transaction::{
+    EventContent,
+    EventData,
+    EventKey,
+    L2ToL1Payload,
+    MessageToL1,
+    TransactionHash,
+};
+use starknet_api::{contract_address, felt};
+use starknet_types_core::felt::Felt;
+
+use crate::blockifier::side_effects::{BlockSideEffects, BloomFilter, L2ToL1MessageHash};
+use crate::execution::call_info::{
+    CallExecution,
+    CallInfo,
+    MessageToL1 as BlockifierMessageToL1,
+    OrderedEvent,
+    OrderedL2ToL1Message,
+};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::transaction::objects::TransactionExecutionInfo;
+
+#[test]
+fn test_add_tx() {
+    let emitter = contract_address!("0x100");
+    let sender = contract_address!("0x1234");
+    let recipient = EthAddress::try_from(felt!("0xabcd")).unwrap();
+    let key = EventKey(felt!("0x7"));
+    // The inner call emits the first event and sends a message.
+    let execute_call_info = CallInfo {
+        call: CallEntryPoint { storage_address: emitter, ..Default::default() },
+        execution: CallExecution {
+            events: vec![OrderedEvent {
+                order: 1,
+                event: EventContent { keys: vec![key.clone()], data: EventData(vec![Felt::ONE]) },
+            }],
+            ..Default::default()
+        },
+        inner_calls: vec![CallInfo {
+            call: CallEntryPoint { storage_address: sender, ..Default::default() },
+            execution: CallExecution {
+                events: vec![OrderedEvent { order: 0, event: EventContent::default() }],
+                l2_to_l1_messages: vec![OrderedL2ToL1Message {
+                    order: 0,
+                    message: BlockifierMessageToL1 {
+                        to_address: recipient,
+                        payload: L2ToL1Payload(vec![Felt::ONE, Felt::TWO]),
+                    },
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(execute_call_info),
+        ..Default::default()
+    };
+
+    let mut side_effects = BlockSideEffects::default();
+    side_effects.add_tx(TransactionHash(felt!("0x1")), &TransactionExecutionInfo::default());
+    side_effects.add_tx(TransactionHash(felt!("0x2")), &execution_info);
+
+    assert_eq!(side_effects.n_txs(), 2);
+    let emitter_event = &side_effects.events[&(emitter, vec![key.clone()])][0];
+    assert_eq!((emitter_event.tx_index, emitter_event.event_index), (1, 1));
+    assert_eq!(emitter_event.data, EventData(vec![Felt::ONE]));
+    let sender_event = &side_effects.events[&(sender, vec![])][0];
+    assert_eq!((sender_event.tx_index, sender_event.event_index), (1, 0));
+    for value in [emitter.0.key(), sender.0.key(), &key.0] {
+        assert!(side_effects.events_filter.may_contain(value));
+    }
+
+    let [sent_message] = side_effects.messages.as_slice() else {
+        panic!("Expected a single message, got {:?}.", side_effects.messages);
+    };
+    assert_eq!(sent_message.tx_hash, TransactionHash(felt!("0x2")));
+    assert_eq!(
+        sent_message.message,
+        MessageToL1 {
+            from_address: sender,
+            to_address: recipient,
+            payload: L2ToL1Payload(vec![Felt::ONE, Felt::TWO]),
+        }
+    );
+    assert_eq!(sent_message.hash, L2ToL1MessageHash::new(&sent_message.message));
+    assert!(side_effects.messages_filter.may_contain(sender.0.key()));
+    assert!(side_effects.messages_filter.may_contain(&Felt::from(recipient)));
+}
+
+#[test]
+fn test_l2_to_l1_message_hash() {
+    let message = MessageToL1 {
+        from_address: contract_address!("0x1234"),
+        to_address: EthAddress::try_from(felt!("0xabcd")).unwrap(),
+        payload: L2ToL1Payload(vec![Felt::ONE, Felt::TWO]),
+    };
+    let hash: String =
+        L2ToL1MessageHash::new(&message).0.iter().map(|byte| format!("{byte:02x}")).collect();
+    assert_eq!(hash, "5ad3ecbb5ddf39c85be973f23ca2ad9bd01f3c766c22ca0655182285d3891235");
+}
+
+#[test]
+fn test_bloom_filter() {
+    let mut filter = BloomFilter::default();
+    assert!(filter.as_bytes().iter().all(|byte| *byte == 0));
+    assert!(!filter.may_contain(&Felt::ONE));
+
+    filter.insert(&Felt::ONE);
+    assert!(filter.may_contain(&Felt::ONE));
+    assert!(!filter.may_contain(&Felt::TWO));
+}
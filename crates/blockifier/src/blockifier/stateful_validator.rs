@@ -13,12 +13,14 @@ use crate::blockifier::transaction_executor::{
 use crate::context::{BlockContext, TransactionContext};
 use crate::execution::call_info::CallInfo;
 use crate::fee::fee_checks::PostValidationReport;
+use crate::fee::fee_utils::{get_committed_fee_balance, CommittedFeeBalance};
 use crate::fee::receipt::TransactionReceipt;
 use crate::state::cached_state::CachedState;
 use crate::state::errors::StateError;
 use crate::state::state_api::StateReader;
 use crate::transaction::account_transaction::AccountTransaction;
 use crate::transaction::errors::{TransactionExecutionError, TransactionPreValidationError};
+use crate::transaction::objects::TransactionExecutionInfo;
 use crate::transaction::transaction_execution::Transaction;
 use crate::transaction::transactions::ValidatableTransaction;
 
@@ -52,39 +54,47 @@ impl<S: StateReader> StatefulValidator<S> {
         Self { tx_executor }
     }
 
+    pub fn block_context(&self) -> &BlockContext {
+        &self.tx_executor.block_context
+    }
+
+    /// Performs the validations of the transaction, returning the call info of its validation
+    /// entry point, if it was run.
     pub fn perform_validations(
         &mut self,
         tx: AccountTransaction,
         skip_validate: bool,
-    ) -> StatefulValidatorResult<()> {
+    ) -> StatefulValidatorResult<Option<CallInfo>> {
         // Deploy account transactions should be fully executed, since the constructor must run
         // before `__validate_deploy__`. The execution already includes all necessary validations,
         // so they are skipped here.
         if let ApiTransaction::DeployAccount(_) = tx.tx {
-            self.execute(tx)?;
-            return Ok(());
+            let execution_info = self.execute(tx)?;
+            return Ok(execution_info.validate_call_info);
         }
 
         let tx_context = self.tx_executor.block_context.to_tx_context(&tx);
         self.perform_pre_validation_stage(&tx, &tx_context)?;
 
         if skip_validate {
-            return Ok(());
+            return Ok(None);
         }
 
         // `__validate__` call.
-        let (_optional_call_info, actual_cost) =
+        let (optional_call_info, actual_cost) =
             self.validate(&tx, tx_context.initial_sierra_gas().0)?;
 
         // Post validations.
         PostValidationReport::verify(&tx_context, &actual_cost)?;
 
-        Ok(())
+        Ok(optional_call_info)
     }
 
-    fn execute(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<()> {
-        self.tx_executor.execute(&Transaction::Account(tx))?;
-        Ok(())
+    fn execute(
+        &mut self,
+        tx: AccountTransaction,
+    ) -> StatefulValidatorResult<TransactionExecutionInfo> {
+        Ok(self.tx_executor.execute(&Transaction::Account(tx))?)
     }
 
     fn perform_pre_validation_stage(
@@ -117,6 +127,14 @@ impl<S: StateReader> StatefulValidator<S> {
             &mut remaining_gas,
             limit_steps_by_resources,
         )?;
+        // `__validate_paymaster__` call, if the transaction is sponsored.
+        let mut remaining_paymaster_gas = remaining_gas
+            .min(tx_context.block_context.chain_info.paymaster.validate_max_sierra_gas.0);
+        let validate_paymaster_call_info = tx.validate_paymaster(
+            self.tx_executor.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR),
+            tx_context.clone(),
+            &mut remaining_paymaster_gas,
+        )?;
 
         let tx_receipt = TransactionReceipt::from_account_tx(
             tx,
@@ -128,7 +146,7 @@ impl<S: StateReader> StatefulValidator<S> {
                 .expect(BLOCK_STATE_ACCESS_ERR)
                 .get_actual_state_changes()?,
             CallInfo::summarize_many(
-                validate_call_info.iter(),
+                validate_call_info.iter().chain(validate_paymaster_call_info.iter()),
                 &tx_context.block_context.versioned_constants,
             ),
             0,
@@ -137,6 +155,20 @@ impl<S: StateReader> StatefulValidator<S> {
         Ok((validate_call_info, tx_receipt))
     }
 
+    /// Returns the fee token balance of the transaction's fee payer, compared to the maximal fee
+    /// the transaction may be charged.
+    pub fn get_committed_fee_balance(
+        &mut self,
+        tx: &AccountTransaction,
+    ) -> StatefulValidatorResult<CommittedFeeBalance> {
+        let tx_context = self.tx_executor.block_context.to_tx_context(tx);
+        get_committed_fee_balance(
+            self.tx_executor.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR),
+            &tx_context,
+        )
+        .map_err(|error| TransactionPreValidationError::from(error).into())
+    }
+
     pub fn get_nonce(
         &mut self,
         account_address: ContractAddress,
@@ -76,6 +76,8 @@ fn test_tx_validator(
     let skip_validate = false;
     let result = stateful_validator.perform_validations(account_tx, skip_validate);
     assert!(result.is_ok(), "Validation failed: {:?}", result.unwrap_err());
+    // The validation entry point was run.
+    assert!(result.unwrap().is_some());
 }
 
 #[rstest]
@@ -101,5 +103,5 @@ fn test_tx_validator_skip_validate(
     let mut stateful_validator = StatefulValidator::create(state, block_context);
     // The transaction validations should be skipped and the function should return Ok.
     let result = stateful_validator.perform_validations(tx, true);
-    assert_matches!(result, Ok(()));
+    assert_matches!(result, Ok(None));
 }
@@ -2,18 +2,37 @@ use std::collections::{HashMap, HashSet};
 use std::panic::{self, catch_unwind, AssertUnwindSafe};
 use std::sync::{Arc, Mutex};
 
-use itertools::FoldWhile::{Continue, Done};
-use itertools::Itertools;
+use num_traits::ToPrimitive;
 use starknet_api::block::BlockHashAndNumber;
-use starknet_api::core::ClassHash;
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::error_context::ErrorContext;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::TransactionHash;
+use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
-use crate::blockifier::block::pre_process_block;
-use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::block::{pre_process_block, write_block_metadata, write_randomness_seed};
+use crate::blockifier::block_bundle::BlockStateReads;
+use crate::blockifier::config::{ConcurrencyConfig, TransactionExecutorConfig, ValidationStrategy};
+use crate::blockifier::differential_execution::{
+    compare_executions,
+    execute_on_vm,
+    ExecutionDivergence,
+};
+use crate::blockifier::side_effects::BlockSideEffects;
 use crate::bouncer::{Bouncer, BouncerWeights};
+use crate::concurrency::conflict_stats::ConflictStats;
+use crate::concurrency::dependency_hints::TxDependencyHints;
+use crate::concurrency::fee_utils::{
+    add_fee_to_balance,
+    deferred_fee_transfers,
+    fill_sequencer_balance_reads,
+};
 use crate::concurrency::worker_logic::WorkerExecutor;
-use crate::context::BlockContext;
-use crate::state::cached_state::{CachedState, CommitmentStateDiff, TransactionalState};
+use crate::context::{BlockContext, TransactionContext};
+use crate::execution::entry_point::{BlockExecutionProfile, ExecutionProfiler};
+use crate::fee::fee_utils::get_address_balance_keys;
+use crate::state::cached_state::{CachedState, CommitmentStateDiff, StateMaps, TransactionalState};
 use crate::state::errors::StateError;
 use crate::state::state_api::{StateReader, StateResult};
 use crate::state::stateful_compression::state_diff_with_alias_allocation;
@@ -26,12 +45,25 @@ use crate::transaction::transactions::ExecutableTransaction;
 #[path = "transaction_executor_test.rs"]
 pub mod transaction_executor_test;
 
+#[cfg(test)]
+#[path = "transaction_executor_fuzz_test.rs"]
+mod fuzz_test;
+
 pub const BLOCK_STATE_ACCESS_ERR: &str = "Error: The block state should be `Some`.";
 
+// The conflict percentages (re-executions per 100 transactions) of a chunk above which the adaptive
+// chunk size is halved, and below which it is doubled.
+const HIGH_CONFLICT_PERCENTAGE: usize = 20;
+const LOW_CONFLICT_PERCENTAGE: usize = 5;
+// The number of most conflicting storage keys logged at the end of the block.
+const N_LOGGED_CONFLICTING_STORAGE_KEYS: usize = 5;
+
 #[derive(Debug, Error)]
 pub enum TransactionExecutorError {
     #[error("Transaction cannot be added to the current block, block capacity reached.")]
     BlockFull,
+    #[error("The execution of the block was cancelled.")]
+    Cancelled,
     #[error(transparent)]
     StateError(#[from] StateError),
     #[error(transparent)]
@@ -54,6 +86,20 @@ pub struct TransactionExecutor<S: StateReader> {
     // committing the chunk. The block state is wrapped with an Option<_> to allow setting it to
     // `None` while it is moved to the worker executor.
     pub block_state: Option<CachedState<S>>,
+    // The fees paid to the sequencer and to the fee distribution recipients and not yet added to
+    // their balances, per (fee token, recipient); see
+    // `TransactionExecutorConfig::deferred_fee_settlement`.
+    accrued_fees: HashMap<(ContractAddress, ContractAddress), Fee>,
+    // The conflicts between transactions executed concurrently in the block.
+    conflict_stats: ConflictStats,
+    // The size of the next concurrently executed chunk.
+    chunk_size: usize,
+    // The indexed events and messages of the transactions executed so far in the block; see
+    // `TransactionExecutorConfig::index_side_effects`.
+    side_effects: Option<BlockSideEffects>,
+    // The divergences of the Cairo VM executions from the Cairo native executions of the
+    // transactions of the block; see `TransactionExecutorConfig::shadow_vm_execution`.
+    execution_divergences: Vec<(TransactionHash, Vec<ExecutionDivergence>)>,
 }
 
 impl<S: StateReader> TransactionExecutor<S> {
@@ -71,6 +117,22 @@ impl<S: StateReader> TransactionExecutor<S> {
             block_context.block_info().block_number,
             &block_context.versioned_constants.os_constants,
         )?;
+        if let Some(randomness) = block_context.randomness() {
+            write_randomness_seed(
+                &mut block_state,
+                randomness,
+                block_context.block_info(),
+                old_block_number_and_hash,
+            )?;
+        }
+        if let Some(starknet_version) = block_context.starknet_version() {
+            write_block_metadata(
+                &mut block_state,
+                block_context.block_info(),
+                starknet_version,
+                &block_context.versioned_constants.os_constants,
+            )?;
+        }
         Ok(Self::new(block_state, block_context, config))
     }
 
@@ -83,49 +145,146 @@ impl<S: StateReader> TransactionExecutor<S> {
         let bouncer_config = block_context.bouncer_config.clone();
         // Note: the state might not be empty even at this point; it is the creator's
         // responsibility to tune the bouncer according to pre and post block process.
+        let chunk_size = config.concurrency_config.chunk_size;
+        let block_context = if config.profile_entry_points {
+            block_context.with_execution_profiler(ExecutionProfiler::default())
+        } else {
+            block_context
+        };
+        let side_effects = config.index_side_effects.then(BlockSideEffects::default);
         Self {
             block_context,
             bouncer: Bouncer::new(bouncer_config),
             config,
             block_state: Some(block_state),
+            accrued_fees: HashMap::new(),
+            conflict_stats: ConflictStats::default(),
+            chunk_size,
+            side_effects,
+            execution_divergences: Vec::new(),
         }
     }
 
     /// Executes the given transaction on the state maintained by the executor.
     /// Returns the execution result (info or error) if there is room for the transaction;
-    /// Otherwise, returns BlockFull error.
+    /// Otherwise, returns BlockFull error. Returns Cancelled error if the execution of the block
+    /// is cancelled before or while executing the transaction.
     pub fn execute(
         &mut self,
         tx: &Transaction,
     ) -> TransactionExecutorResult<TransactionExecutionInfo> {
+        if self.block_context.is_execution_cancelled() {
+            return Err(TransactionExecutorError::Cancelled);
+        }
+        let deferred_fee_tx_context =
+            self.config.deferred_fee_settlement.then(|| self.block_context.to_tx_context(tx));
+        if deferred_fee_tx_context.as_ref().is_some_and(|ctx| ctx.is_sequencer_the_sender()) {
+            // The sequencer pays with a full fee transfer, which requires its actual balance.
+            self.settle_accrued_fees()?;
+        }
+
+        // Executing a single transaction cannot be done in a concurrent mode; the concurrent fee
+        // transfer flow is used to defer the fee settlement, though.
+        let concurrency_mode = deferred_fee_tx_context.is_some();
+        let vm_execution = if self.config.shadow_vm_execution {
+            let block_state = self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR);
+            Some(execute_on_vm(tx, block_state, &self.block_context, concurrency_mode)?)
+        } else {
+            None
+        };
         let mut transactional_state = TransactionalState::create_transactional(
             self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR),
         );
 
-        // Executing a single transaction cannot be done in a concurrent mode.
-        let concurrency_mode = false;
         let tx_execution_result =
             tx.execute_raw(&mut transactional_state, &self.block_context, concurrency_mode);
+        if self.block_context.is_execution_cancelled() {
+            // The execution may have stopped midway, e.g., reverting the transaction.
+            transactional_state.abort();
+            return Err(TransactionExecutorError::Cancelled);
+        }
+        let divergences = match vm_execution {
+            Some((vm_execution_result, vm_state_diff)) => {
+                let state_diff = match &tx_execution_result {
+                    Ok(_) => transactional_state.to_state_diff()?.state_maps,
+                    Err(_) => StateMaps::default(),
+                };
+                compare_executions(
+                    (&tx_execution_result, &state_diff),
+                    (&vm_execution_result, &vm_state_diff),
+                )
+            }
+            None => vec![],
+        };
         match tx_execution_result {
-            Ok(tx_execution_info) => {
-                let tx_state_changes_keys =
+            Ok(mut tx_execution_info) => {
+                let mut tx_state_changes_keys =
                     transactional_state.get_actual_state_changes()?.state_maps.into_keys();
+                if let Some(tx_context) = &deferred_fee_tx_context {
+                    if Self::read_unsettled_balance(&self.accrued_fees, &transactional_state) {
+                        // The transaction observed a stale recipient balance; settle and retry.
+                        transactional_state.abort();
+                        self.settle_accrued_fees()?;
+                        // The divergences are those of the retried execution.
+                        return self.execute(tx);
+                    }
+                    // The deferred sequencer balance update is part of the transaction's changes.
+                    tx_state_changes_keys.update_sequencer_key_in_storage(
+                        tx_context,
+                        &tx_execution_info,
+                        concurrency_mode,
+                    );
+                }
+                Self::record_divergences(&mut self.execution_divergences, tx, divergences);
                 self.bouncer.try_update(
                     &transactional_state,
                     &tx_state_changes_keys,
                     &tx_execution_info.summarize(&self.block_context.versioned_constants),
                     &tx_execution_info.receipt.resources,
+                    tx.into(),
                 )?;
                 transactional_state.commit();
+                self.block_context.block_call_counter.commit(&tx_execution_info.quota_call_counts);
+                if let Some(tx_context) = &deferred_fee_tx_context {
+                    self.accrue_fee(tx_context, &mut tx_execution_info)?;
+                }
+                self.index_side_effects(tx, &tx_execution_info);
                 Ok(tx_execution_info)
             }
             Err(error) => {
                 transactional_state.abort();
+                Self::record_divergences(&mut self.execution_divergences, tx, divergences);
+                log::debug!("{}", self.error_context(tx).wrap(&error));
                 Err(TransactionExecutorError::TransactionExecutionError(error))
             }
         }
     }
 
+    /// Records where the Cairo VM execution of the given transaction diverged from its actual
+    /// execution, if it did.
+    fn record_divergences(
+        execution_divergences: &mut Vec<(TransactionHash, Vec<ExecutionDivergence>)>,
+        tx: &Transaction,
+        divergences: Vec<ExecutionDivergence>,
+    ) {
+        if divergences.is_empty() {
+            return;
+        }
+        let tx_hash = Transaction::tx_hash(tx);
+        log::warn!(
+            "The Cairo VM execution of transaction {tx_hash} diverged from its Cairo native \
+             execution: {divergences:?}."
+        );
+        execution_divergences.push((tx_hash, divergences));
+    }
+
+    /// The context of errors occurring while executing the given transaction in this block.
+    pub fn error_context(&self, tx: &Transaction) -> ErrorContext {
+        ErrorContext::new("blockifier")
+            .with_block_number(self.block_context.block_info.block_number)
+            .with_tx_hash(Transaction::tx_hash(tx))
+    }
+
     pub fn execute_txs_sequentially(
         &mut self,
         txs: &[Transaction],
@@ -135,12 +294,144 @@ impl<S: StateReader> TransactionExecutor<S> {
             match self.execute(tx) {
                 Ok(tx_execution_info) => results.push(Ok(tx_execution_info)),
                 Err(TransactionExecutorError::BlockFull) => break,
+                Err(TransactionExecutorError::Cancelled) => {
+                    results.push(Err(TransactionExecutorError::Cancelled));
+                    break;
+                }
                 Err(error) => results.push(Err(error)),
             }
         }
         results
     }
 
+    /// Returns the balance (low, high) of the given fee recipient in the given fee token,
+    /// including the accrued fees.
+    fn recipient_balance(
+        &self,
+        fee_token_address: ContractAddress,
+        recipient: ContractAddress,
+    ) -> StateResult<(Felt, Felt)> {
+        let block_state = self.block_state.as_ref().expect(BLOCK_STATE_ACCESS_ERR);
+        let (low, high) = block_state.get_fee_token_balance(recipient, fee_token_address)?;
+        let Some(accrued_fee) = self.accrued_fees.get(&(fee_token_address, recipient)) else {
+            return Ok((low, high));
+        };
+        let low = low.to_u128().expect("Recipient balance low should be u128.");
+        let (new_low, carry) = low.overflowing_add(accrued_fee.0);
+        Ok((Felt::from(new_low), high + Felt::from(u128::from(carry))))
+    }
+
+    /// Records the fees paid by an executed transaction to the sequencer and to the fee
+    /// distribution recipients, and completes its fee transfer call infos, which were executed
+    /// without the recipient balances.
+    fn accrue_fee(
+        &mut self,
+        tx_context: &TransactionContext,
+        tx_execution_info: &mut TransactionExecutionInfo,
+    ) -> StateResult<()> {
+        let fee_token_address = tx_context.fee_token_address();
+        for (fee_transfer_call_info, recipient, amount) in
+            deferred_fee_transfers(tx_context, tx_execution_info)
+        {
+            fill_sequencer_balance_reads(
+                fee_transfer_call_info,
+                self.recipient_balance(fee_token_address, recipient)?,
+            );
+            let accrued_fee = self.accrued_fees.entry((fee_token_address, recipient)).or_default();
+            *accrued_fee = accrued_fee
+                .checked_add(amount)
+                .expect("The accrued fees overflowed. This should not happen.");
+        }
+        Ok(())
+    }
+
+    /// Whether the given transaction state read a recipient balance that has unsettled fees.
+    fn read_unsettled_balance(
+        accrued_fees: &HashMap<(ContractAddress, ContractAddress), Fee>,
+        transactional_state: &TransactionalState<'_, CachedState<S>>,
+    ) -> bool {
+        let cache = transactional_state.cache.borrow();
+        accrued_fees.keys().any(|(fee_token_address, recipient)| {
+            let (balance_key_low, balance_key_high) = get_address_balance_keys(*recipient);
+            [balance_key_low, balance_key_high]
+                .into_iter()
+                .any(|key| cache.initial_reads.storage.contains_key(&(*fee_token_address, key)))
+        })
+    }
+
+    /// Adds the accrued fees to the recipient balances in the block state.
+    fn settle_accrued_fees(&mut self) -> StateResult<()> {
+        for ((fee_token_address, recipient), accrued_fee) in std::mem::take(&mut self.accrued_fees)
+        {
+            let block_state = self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR);
+            let recipient_balance =
+                block_state.get_fee_token_balance(recipient, fee_token_address)?;
+            add_fee_to_balance(
+                fee_token_address,
+                block_state,
+                accrued_fee,
+                recipient,
+                recipient_balance,
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds the events and messages of the given executed transaction to the block side effects, if
+    /// indexing is enabled.
+    fn index_side_effects(
+        &mut self,
+        tx: &Transaction,
+        tx_execution_info: &TransactionExecutionInfo,
+    ) {
+        if let Some(side_effects) = self.side_effects.as_mut() {
+            side_effects.add_tx(Transaction::tx_hash(tx), tx_execution_info);
+        }
+    }
+
+    /// Returns the indexed events and messages of the transactions executed so far in the block,
+    /// if indexing is enabled.
+    pub fn side_effects(&self) -> Option<&BlockSideEffects> {
+        self.side_effects.as_ref()
+    }
+
+    /// Returns the transactions executed so far in the block whose Cairo VM execution diverged from
+    /// their Cairo native execution, with their divergences, if shadow VM execution is enabled.
+    /// Note: only sequentially executed transactions are executed by both backends.
+    pub fn execution_divergences(&self) -> &[(TransactionHash, Vec<ExecutionDivergence>)] {
+        &self.execution_divergences
+    }
+
+    /// Returns the conflicts between the transactions executed concurrently so far in the block.
+    pub fn conflict_stats(&self) -> &ConflictStats {
+        &self.conflict_stats
+    }
+
+    /// Returns the resources used so far in the block per entry point, if profiling is enabled.
+    /// Note: in concurrent execution, the executions of re-executed transactions are all counted.
+    pub fn execution_profile(&self) -> Option<BlockExecutionProfile> {
+        self.block_context.execution_profiler().map(ExecutionProfiler::profile)
+    }
+
+    /// Returns the reads of the parent state and the classes loaded so far in the block; together
+    /// with the block context and transactions, they suffice to re-execute the block offline.
+    pub fn get_block_state_reads(&self) -> TransactionExecutorResult<BlockStateReads> {
+        let block_state = self.block_state.as_ref().expect(BLOCK_STATE_ACCESS_ERR);
+        Ok(BlockStateReads {
+            state_maps: block_state.get_initial_reads()?,
+            class_hashes: block_state.get_loaded_class_hashes(),
+        })
+    }
+
+    /// Returns the storage updates and nonces of the given block state diff that were made only by
+    /// transactions publishing their state diff to an alternative data availability layer.
+    pub fn alternative_da_state_diff(
+        &self,
+        state_diff: &CommitmentStateDiff,
+    ) -> CommitmentStateDiff {
+        state_diff.storage_and_nonces_of(&self.bouncer.alternative_da_state_changes_keys())
+    }
+
     /// Returns the state diff, a list of contract class hash with the corresponding list of
     /// visited segment values and the block weights.
     // TODO(Yoav): Consume "self".
@@ -167,7 +458,18 @@ impl<S: StateReader> TransactionExecutor<S> {
             })
             .collect::<TransactionExecutorResult<_>>()?;
 
+        self.settle_accrued_fees()?;
         log::debug!("Final block weights: {:?}.", self.bouncer.get_accumulated_weights());
+        if self.config.concurrency_config.enabled {
+            log::debug!(
+                "Concurrent execution conflicts: {} executions of {} transactions, most \
+                 conflicting storage keys: {:?}.",
+                self.conflict_stats.n_executions,
+                self.conflict_stats.n_committed_txs,
+                self.conflict_stats
+                    .most_conflicting_storage_keys(N_LOGGED_CONFLICTING_STORAGE_KEYS)
+            );
+        }
         let mut block_state = self.block_state.take().expect(BLOCK_STATE_ACCESS_ERR);
         let state_diff = if self.block_context.versioned_constants.enable_stateful_compression {
             state_diff_with_alias_allocation(
@@ -188,11 +490,28 @@ impl<S: StateReader> TransactionExecutor<S> {
 impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
     /// Executes the given transactions on the state maintained by the executor.
     /// Stops if and when there is no more room in the block, and returns the executed transactions'
-    /// results.
+    /// results. If the execution of the block is cancelled, the last result is a Cancelled error.
     pub fn execute_txs(
         &mut self,
         txs: &[Transaction],
     ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>> {
+        self.execute_txs_with_dependency_hints(txs, None)
+    }
+
+    /// Same as `execute_txs`, where the given dependency hints (one per transaction) are used to
+    /// schedule the concurrent execution of the transactions; see `TxDependencyHints`.
+    pub fn execute_txs_with_dependency_hints(
+        &mut self,
+        txs: &[Transaction],
+        dependency_hints: Option<&[TxDependencyHints]>,
+    ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>> {
+        if let Some(dependency_hints) = dependency_hints {
+            assert_eq!(
+                dependency_hints.len(),
+                txs.len(),
+                "Each transaction must have exactly one dependency hints entry."
+            );
+        }
         if !self.config.concurrency_config.enabled {
             log::debug!("Executing transactions sequentially.");
             self.execute_txs_sequentially(txs)
@@ -212,36 +531,59 @@ impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
                  than 0. It equals {:?} ",
                 n_workers
             );
-            txs.chunks(chunk_size)
-                .fold_while(Vec::new(), |mut results, chunk| {
-                    let chunk_results = self.execute_chunk(chunk);
-                    if chunk_results.len() < chunk.len() {
-                        // Block is full.
-                        results.extend(chunk_results);
-                        Done(results)
-                    } else {
-                        results.extend(chunk_results);
-                        Continue(results)
-                    }
-                })
-                .into_inner()
+            let mut results = Vec::new();
+            let mut remaining_txs = txs;
+            while !remaining_txs.is_empty() {
+                let chunk = &remaining_txs[..self.chunk_size.min(remaining_txs.len())];
+                let chunk_start = txs.len() - remaining_txs.len();
+                let chunk_dependency_hints = dependency_hints.map(|dependency_hints| {
+                    &dependency_hints[chunk_start..chunk_start + chunk.len()]
+                });
+                let chunk_results = self.execute_chunk(chunk, chunk_dependency_hints);
+                if self.block_context.is_execution_cancelled() {
+                    // The chunk may have been cancelled midway; its results are discarded.
+                    results.push(Err(TransactionExecutorError::Cancelled));
+                    break;
+                }
+                let block_full = chunk_results.len() < chunk.len();
+                results.extend(chunk_results);
+                if block_full {
+                    break;
+                }
+                remaining_txs = &remaining_txs[chunk.len()..];
+            }
+            results
         }
     }
 
     pub fn execute_chunk(
         &mut self,
         chunk: &[Transaction],
+        chunk_dependency_hints: Option<&[TxDependencyHints]>,
     ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>> {
         use crate::concurrency::utils::AbortIfPanic;
 
+        // Concurrent execution settles each fee on commit, so it must see the accrued fees.
+        self.settle_accrued_fees().unwrap_or_else(|error| {
+            panic!("Access to storage failed. Probably due to a bug in Papyrus. {error:?}: {error}")
+        });
         let block_state = self.block_state.take().expect("The block state should be `Some`.");
 
-        let worker_executor = Arc::new(WorkerExecutor::initialize(
+        let ConcurrencyConfig { n_workers, validation_strategy, .. } =
+            self.config.concurrency_config;
+        let mut worker_executor = WorkerExecutor::initialize(
             block_state,
             chunk,
             &self.block_context,
             Mutex::new(&mut self.bouncer),
-        ));
+        );
+        if validation_strategy == ValidationStrategy::Conservative {
+            worker_executor = worker_executor.with_execution_window(n_workers);
+        }
+        if let Some(chunk_dependency_hints) = chunk_dependency_hints {
+            worker_executor = worker_executor.with_dependency_hints(chunk_dependency_hints);
+        }
+        let worker_executor = Arc::new(worker_executor);
 
         // No thread pool implementation is needed here since we already have our scheduler. The
         // initialized threads below will "busy wait" for new tasks using the `run` method until the
@@ -249,7 +591,7 @@ impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
         // TODO(barak, 01/07/2024): Consider using tokio and spawn tasks that will be served by some
         // upper level tokio thread pool (Runtime in tokio terminology).
         std::thread::scope(|s| {
-            for _ in 0..self.config.concurrency_config.n_workers {
+            for _ in 0..n_workers {
                 let worker_executor = Arc::clone(&worker_executor);
                 s.spawn(move || {
                     // Making sure that the program will abort if a panic accured while halting the
@@ -275,6 +617,12 @@ impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
         });
 
         let n_committed_txs = worker_executor.scheduler.get_n_committed_txs();
+        let mut chunk_conflict_stats = std::mem::take(
+            &mut *worker_executor.conflict_stats.lock().expect("Conflict stats lock failed."),
+        );
+        chunk_conflict_stats.n_committed_txs = n_committed_txs;
+        self.adapt_chunk_size(&chunk_conflict_stats);
+        self.conflict_stats.extend(&chunk_conflict_stats);
         let mut tx_execution_results = Vec::new();
         let mut visited_pcs: HashMap<ClassHash, HashSet<usize>> = HashMap::new();
         for execution_output in worker_executor.execution_outputs.iter() {
@@ -303,7 +651,34 @@ impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
             })
             .commit_chunk_and_recover_block_state(n_committed_txs, visited_pcs);
         self.block_state.replace(block_state_after_commit);
+        for (tx, tx_execution_info) in chunk.iter().zip(&tx_execution_results) {
+            if let Ok(tx_execution_info) = tx_execution_info {
+                self.index_side_effects(tx, tx_execution_info);
+            }
+        }
 
         tx_execution_results
     }
+
+    /// Halves the chunk size if the transactions of the last chunk conflicted often, and doubles it
+    /// (up to the configured size) if they rarely did.
+    fn adapt_chunk_size(&mut self, chunk_conflict_stats: &ConflictStats) {
+        let ConcurrencyConfig {
+            n_workers, chunk_size: max_chunk_size, adaptive_chunk_size, ..
+        } = self.config.concurrency_config;
+        if !adaptive_chunk_size {
+            return;
+        }
+        // Smaller chunks cannot keep all the workers busy.
+        let min_chunk_size = n_workers.min(max_chunk_size);
+        let conflict_percentage = chunk_conflict_stats.conflict_percentage();
+        let chunk_size = if conflict_percentage > HIGH_CONFLICT_PERCENTAGE {
+            self.chunk_size / 2
+        } else if conflict_percentage < LOW_CONFLICT_PERCENTAGE {
+            self.chunk_size * 2
+        } else {
+            self.chunk_size
+        };
+        self.chunk_size = chunk_size.clamp(min_chunk_size, max_chunk_size);
+    }
 }
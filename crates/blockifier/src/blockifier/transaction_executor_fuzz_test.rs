@@ -0,0 +1,61 @@
+use proptest::collection::vec;
+use proptest::prelude::*;
+use starknet_api::test_utils::{
+    DEFAULT_L1_DATA_GAS_MAX_AMOUNT,
+    DEFAULT_L1_GAS_AMOUNT,
+    DEFAULT_L2_GAS_MAX_AMOUNT,
+    MAX_FEE,
+};
+use starknet_api::transaction::fields::Fee;
+
+use crate::test_utils::executor_fuzzing::{
+    check_executor_invariants,
+    FuzzedCall,
+    FuzzedFeeFields,
+    FuzzedInvoke,
+};
+
+const N_CASES: u32 = 16;
+const MAX_TXS_PER_CASE: usize = 8;
+
+fn fuzzed_call() -> impl Strategy<Value = FuzzedCall> {
+    prop_oneof![
+        (any::<u8>(), any::<u64>())
+            .prop_map(|(key, value)| FuzzedCall::StorageWrite { key, value }),
+        any::<u64>().prop_map(FuzzedCall::ReturnResult),
+        Just(FuzzedCall::WriteAndRevert),
+        Just(FuzzedCall::Fail),
+        vec(any::<u64>(), 0..4).prop_map(FuzzedCall::UnknownEntryPoint),
+    ]
+}
+
+fn fuzzed_fee_fields() -> impl Strategy<Value = FuzzedFeeFields> {
+    prop_oneof![
+        (0..=2 * MAX_FEE.0).prop_map(|max_fee| FuzzedFeeFields::V1 { max_fee: Fee(max_fee) }),
+        (
+            0..=2 * DEFAULT_L1_GAS_AMOUNT.0,
+            0..=2 * DEFAULT_L2_GAS_MAX_AMOUNT.0,
+            0..=2 * DEFAULT_L1_DATA_GAS_MAX_AMOUNT.0,
+            prop_oneof![Just(50_u128), Just(100), Just(200)],
+        )
+            .prop_map(|(l1_gas, l2_gas, l1_data_gas, price_percentage)| {
+                FuzzedFeeFields::V3 { l1_gas, l2_gas, l1_data_gas, price_percentage }
+            }),
+    ]
+}
+
+fn fuzzed_invoke() -> impl Strategy<Value = FuzzedInvoke> {
+    let nonce_offset = prop_oneof![8 => Just(0_u8), 1 => Just(1), 1 => Just(2)];
+    (fuzzed_call(), fuzzed_fee_fields(), nonce_offset).prop_map(
+        |(call, fee_fields, nonce_offset)| FuzzedInvoke { call, fee_fields, nonce_offset },
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(N_CASES))]
+
+    #[test]
+    fn fuzz_executor_invariants(invokes in vec(fuzzed_invoke(), 1..=MAX_TXS_PER_CASE)) {
+        check_executor_invariants(&invokes);
+    }
+}
@@ -1,28 +1,60 @@
+use std::collections::{HashMap, HashSet};
+
 use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use rstest::rstest;
+use starknet_api::abi::abi_utils::{get_fee_token_var_address, selector_from_name};
+use starknet_api::block::{FeeType, StarknetVersion};
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::execution_resources::GasVector;
 use starknet_api::test_utils::declare::executable_declare_tx;
 use starknet_api::test_utils::deploy_account::executable_deploy_account_tx;
 use starknet_api::test_utils::invoke::executable_invoke_tx;
 use starknet_api::test_utils::{NonceManager, DEFAULT_STRK_L1_GAS_PRICE};
+use starknet_api::transaction::constants::EXECUTE_ENTRY_POINT_NAME;
 use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::TransactionVersion;
-use starknet_api::{declare_tx_args, deploy_account_tx_args, felt, invoke_tx_args, nonce};
+use starknet_api::{
+    contract_address,
+    declare_tx_args,
+    deploy_account_tx_args,
+    felt,
+    invoke_tx_args,
+    nonce,
+    storage_key,
+};
 use starknet_types_core::felt::Felt;
 
-use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::config::{ConcurrencyConfig, TransactionExecutorConfig};
+#[cfg(feature = "cairo_native")]
+use crate::blockifier::transaction_executor::VisitedSegmentsMapping;
 use crate::blockifier::transaction_executor::{
     TransactionExecutor,
     TransactionExecutorError,
     BLOCK_STATE_ACCESS_ERR,
 };
 use crate::bouncer::{Bouncer, BouncerWeights};
-use crate::context::BlockContext;
+use crate::concurrency::conflict_stats::ConflictStats;
+use crate::context::{BlockContext, FeeDistribution};
+use crate::execution::cancellation::CancellationToken;
+#[cfg(feature = "cairo_native")]
+use crate::execution::entry_point::BlockExecutionProfile;
+use crate::execution::execution_quotas::{ExecutionQuotas, QuotaSubject};
+#[cfg(feature = "cairo_native")]
+use crate::execution::execution_trace::ExecutionTrace;
+use crate::execution::execution_trace::ExecutionTracer;
+#[cfg(feature = "cairo_native")]
+use crate::execution::execution_utils::FAIL_NATIVE_EXECUTIONS;
+#[cfg(feature = "cairo_native")]
+use crate::execution::native::execution_guard::NATIVE_EXECUTION_GUARD;
+use crate::execution::syscalls::SyscallSelector;
 use crate::state::cached_state::CachedState;
+#[cfg(feature = "cairo_native")]
+use crate::state::cached_state::CommitmentStateDiff;
 use crate::state::state_api::StateReader;
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::initial_test_state::test_state;
-use crate::test_utils::l1_handler::l1handler_tx;
+use crate::test_utils::l1_handler::{l1handler_tx, l1handler_tx_with_nonce};
 use crate::test_utils::{
     create_calldata,
     maybe_dummy_block_hash_and_number,
@@ -32,10 +64,13 @@ use crate::test_utils::{
 };
 use crate::transaction::account_transaction::AccountTransaction;
 use crate::transaction::errors::TransactionExecutionError;
+#[cfg(feature = "cairo_native")]
+use crate::transaction::objects::TransactionExecutionInfo;
 use crate::transaction::test_utils::{
     block_context,
     calculate_class_info_for_testing,
     create_test_init_data,
+    default_all_resource_bounds,
     emit_n_events_tx,
     l1_resource_bounds,
     TestInitData,
@@ -373,3 +408,596 @@ fn test_execute_txs_bouncing(#[values(true, false)] concurrency_enabled: bool) {
         nonce!(4_u32)
     );
 }
+
+/// Only the calls of committed transactions count against the call quotas of the block.
+#[rstest]
+fn test_call_quotas_count_committed_txs(#[values(true, false)] concurrency_enabled: bool) {
+    let config = TransactionExecutorConfig::create_for_testing(concurrency_enabled);
+    let max_n_events_in_block = 10;
+    let mut block_context = BlockContext::create_for_bouncer_testing(max_n_events_in_block);
+    let TestInitData { state, account_address, contract_address, .. } = create_test_init_data(
+        &block_context.chain_info,
+        CairoVersion::Cairo1(RunnableCairo1::Casm),
+    );
+    block_context.versioned_constants.execution_quotas =
+        ExecutionQuotas::try_from(format!("address:{:#x}:calls=1", contract_address.0.key()))
+            .unwrap();
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let fail_tx = executable_invoke_tx(invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_calldata(contract_address, "fail", &[]),
+        nonce: nonce!(0_u32),
+    });
+    let txs: Vec<Transaction> = [
+        // Rejected: too large for the block.
+        emit_n_events_tx(
+            max_n_events_in_block + 1,
+            account_address,
+            contract_address,
+            nonce!(0_u32),
+        ),
+        // Reverted.
+        AccountTransaction::new_for_sequencing(fail_tx),
+        emit_n_events_tx(1, account_address, contract_address, nonce!(1_u32)),
+        // Reverted: the contract exhausted its quota.
+        emit_n_events_tx(1, account_address, contract_address, nonce!(2_u32)),
+    ]
+    .into_iter()
+    .map(Transaction::Account)
+    .collect();
+
+    let results = tx_executor.execute_txs(&txs);
+
+    assert_eq!(results.len(), 4);
+    assert_matches!(
+        results[0].as_ref().unwrap_err(),
+        TransactionExecutorError::TransactionExecutionError(
+            TransactionExecutionError::TransactionTooLarge { .. }
+        )
+    );
+    let execution_infos: Vec<_> =
+        results[1..].iter().map(|result| result.as_ref().unwrap()).collect();
+    assert!(execution_infos[0].is_reverted());
+    assert!(execution_infos[0].quota_call_counts.is_empty());
+    assert!(!execution_infos[1].is_reverted());
+    assert!(execution_infos[2].is_reverted());
+    assert_eq!(
+        tx_executor
+            .block_context
+            .block_call_counter()
+            .n_calls(QuotaSubject::Address(contract_address)),
+        1
+    );
+}
+
+#[rstest]
+fn test_deferred_fee_settlement(
+    mut block_context: BlockContext,
+    #[values(false, true)] with_fee_distribution: bool,
+) {
+    if with_fee_distribution {
+        block_context.chain_info.fee_distribution = FeeDistribution {
+            treasury_address: contract_address!("0x1234"),
+            treasury_percentage: 20,
+            burn_address: contract_address!("0x5678"),
+            burn_percentage: 10,
+        };
+    }
+    let execute_block = |deferred_fee_settlement: bool| {
+        let cairo_version = CairoVersion::Cairo1(RunnableCairo1::Casm);
+        let test_contract = FeatureContract::TestContract(cairo_version);
+        let account_contract = FeatureContract::AccountWithoutValidations(cairo_version);
+        let state = test_state(
+            &block_context.chain_info,
+            BALANCE,
+            &[(test_contract, 1), (account_contract, 2)],
+        );
+        let config = TransactionExecutorConfig {
+            deferred_fee_settlement,
+            ..TransactionExecutorConfig::default()
+        };
+        let mut tx_executor = TransactionExecutor::new(state, block_context.clone(), config);
+
+        let mut nonce_manager = NonceManager::default();
+        let calldata = create_calldata(
+            test_contract.get_instance_address(0),
+            "test_count_actual_storage_changes",
+            &[],
+        );
+        let txs: Vec<Transaction> = [0, 1, 0]
+            .into_iter()
+            .map(|account_instance| {
+                let sender_address = account_contract.get_instance_address(account_instance);
+                AccountTransaction::new_for_sequencing(executable_invoke_tx(invoke_tx_args! {
+                    sender_address,
+                    calldata: calldata.clone(),
+                    resource_bounds: default_all_resource_bounds(),
+                    nonce: nonce_manager.next(sender_address),
+                }))
+                .into()
+            })
+            .collect();
+
+        let execution_infos: Vec<_> = tx_executor
+            .execute_txs_sequentially(&txs)
+            .into_iter()
+            .map(|result| result.unwrap())
+            .collect();
+        let bouncer_weights = *tx_executor.bouncer.get_accumulated_weights();
+        let (state_diff, _, _) = tx_executor.finalize().unwrap();
+        (execution_infos, bouncer_weights, state_diff)
+    };
+
+    let (deferred_infos, deferred_weights, deferred_state_diff) = execute_block(true);
+    let (immediate_infos, immediate_weights, immediate_state_diff) = execute_block(false);
+
+    // Deferring the settlement must not affect the execution results.
+    assert!(deferred_infos.iter().all(|info| info.fee_transfer_call_info.is_some()));
+    assert!(deferred_infos.iter().all(|info| {
+        info.fee_distribution_call_infos.len() == if with_fee_distribution { 2 } else { 0 }
+    }));
+    assert_eq!(deferred_infos, immediate_infos);
+    assert_eq!(deferred_weights, immediate_weights);
+    assert_eq!(deferred_state_diff, immediate_state_diff);
+}
+
+#[rstest]
+fn test_execution_profile(
+    block_context: BlockContext,
+    #[values(CairoVersion::Cairo0, CairoVersion::Cairo1(RunnableCairo1::Casm))]
+    cairo_version: CairoVersion,
+) {
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let account_contract = FeatureContract::AccountWithoutValidations(cairo_version);
+    let state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(test_contract, 1), (account_contract, 1)],
+    );
+    let config = TransactionExecutorConfig {
+        profile_entry_points: true,
+        ..TransactionExecutorConfig::default()
+    };
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let mut nonce_manager = NonceManager::default();
+    let sender_address = account_contract.get_instance_address(0);
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_storage_read_write",
+        &[felt!(15_u8), felt!(17_u8)],
+    );
+    let txs: Vec<Transaction> = (0..2)
+        .map(|_| {
+            AccountTransaction::new_for_sequencing(executable_invoke_tx(invoke_tx_args! {
+                sender_address,
+                calldata: calldata.clone(),
+                resource_bounds: default_all_resource_bounds(),
+                nonce: nonce_manager.next(sender_address),
+            }))
+            .into()
+        })
+        .collect();
+    for result in tx_executor.execute_txs_sequentially(&txs) {
+        result.unwrap();
+    }
+
+    let profile = tx_executor.execution_profile().unwrap();
+    let entry_point_profile = &profile.entry_points
+        [&(test_contract.get_class_hash(), selector_from_name("test_storage_read_write"))];
+    assert_eq!(entry_point_profile.n_calls, 2);
+    assert!(entry_point_profile.n_steps > 0);
+    assert_eq!(
+        entry_point_profile.syscall_counts,
+        HashMap::from([(SyscallSelector::StorageRead, 2), (SyscallSelector::StorageWrite, 2)])
+    );
+    // The account's `__execute__` wraps the call, so the profile includes it as well.
+    assert!(profile.entry_points.contains_key(&(
+        account_contract.get_class_hash(),
+        selector_from_name(EXECUTE_ENTRY_POINT_NAME)
+    )));
+    assert_eq!(profile.hottest_entry_points().len(), profile.entry_points.len());
+}
+
+#[rstest]
+fn test_execution_profile_disabled(block_context: BlockContext) {
+    let state = test_state(&block_context.chain_info, BALANCE, &[]);
+    let tx_executor =
+        TransactionExecutor::new(state, block_context, TransactionExecutorConfig::default());
+    assert!(tx_executor.execution_profile().is_none());
+}
+
+#[rstest]
+#[cfg_attr(feature = "cairo_native", case::native(CairoVersion::Cairo1(RunnableCairo1::Native)))]
+#[case::vm(CairoVersion::Cairo1(RunnableCairo1::Casm))]
+fn test_shadow_vm_execution(block_context: BlockContext, #[case] cairo_version: CairoVersion) {
+    let TestInitData { state, account_address, contract_address, .. } =
+        create_test_init_data(&block_context.chain_info, cairo_version);
+    let config = TransactionExecutorConfig {
+        shadow_vm_execution: true,
+        ..TransactionExecutorConfig::default()
+    };
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let txs: Vec<Transaction> = [
+        emit_n_events_tx(2, account_address, contract_address, nonce!(0_u32)),
+        emit_n_events_tx(3, account_address, contract_address, nonce!(1_u32)),
+    ]
+    .into_iter()
+    .map(Transaction::Account)
+    .collect();
+    for result in tx_executor.execute_txs_sequentially(&txs) {
+        result.unwrap();
+    }
+
+    assert!(tx_executor.execution_divergences().is_empty());
+    // The VM executions are discarded.
+    let (state_diff, _, _) = tx_executor.finalize().unwrap();
+    assert_eq!(state_diff.address_to_nonce[&account_address], nonce!(2_u32));
+}
+
+#[rstest]
+fn test_shadow_vm_execution_is_not_recorded(block_context: BlockContext) {
+    let execute_block = |shadow_vm_execution: bool| {
+        let TestInitData { state, account_address, contract_address, .. } = create_test_init_data(
+            &block_context.chain_info,
+            CairoVersion::Cairo1(RunnableCairo1::Casm),
+        );
+        let execution_tracer = ExecutionTracer::default();
+        let block_context = block_context.clone().with_execution_tracer(execution_tracer.clone());
+        let config = TransactionExecutorConfig {
+            shadow_vm_execution,
+            profile_entry_points: true,
+            ..TransactionExecutorConfig::default()
+        };
+        let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+        let tx = Transaction::Account(emit_n_events_tx(
+            2,
+            account_address,
+            contract_address,
+            nonce!(0_u32),
+        ));
+        tx_executor.execute(&tx).unwrap();
+        (execution_tracer.take_trace(), tx_executor.execution_profile().unwrap())
+    };
+
+    // The VM executions are neither traced nor profiled.
+    assert_eq!(execute_block(true), execute_block(false));
+}
+
+/// Executes an invoke transaction whose call emits events from an inner call, and returns the
+/// execution info, the trace, the profile and the block summary.
+#[cfg(feature = "cairo_native")]
+fn execute_native_contract_calls(
+    block_context: BlockContext,
+) -> (
+    TransactionExecutionInfo,
+    ExecutionTrace,
+    BlockExecutionProfile,
+    (CommitmentStateDiff, VisitedSegmentsMapping, BouncerWeights),
+) {
+    let cairo_version = CairoVersion::Cairo1(RunnableCairo1::Native);
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let account_contract = FeatureContract::AccountWithoutValidations(cairo_version);
+    let state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(test_contract, 2), (account_contract, 1)],
+    );
+    let execution_tracer = ExecutionTracer::default();
+    let block_context = block_context.with_execution_tracer(execution_tracer.clone());
+    let config = TransactionExecutorConfig {
+        profile_entry_points: true,
+        ..TransactionExecutorConfig::default()
+    };
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let (n_events, keys_len, data_len) = (felt!(2_u8), felt!(0_u8), felt!(0_u8));
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_call_contract",
+        &[
+            *test_contract.get_instance_address(1).0.key(),
+            selector_from_name("test_emit_events").0,
+            felt!(3_u8),
+            n_events,
+            keys_len,
+            data_len,
+        ],
+    );
+    let tx = AccountTransaction::new_for_sequencing(executable_invoke_tx(invoke_tx_args! {
+        sender_address: account_contract.get_instance_address(0),
+        calldata,
+        resource_bounds: default_all_resource_bounds(),
+    }))
+    .into();
+    let execution_info = tx_executor.execute(&tx).unwrap();
+    assert!(!execution_info.is_reverted());
+
+    (
+        execution_info,
+        execution_tracer.take_trace(),
+        tx_executor.execution_profile().unwrap(),
+        tx_executor.finalize().unwrap(),
+    )
+}
+
+#[cfg(feature = "cairo_native")]
+#[rstest]
+fn test_native_fallback_matches_vm_execution(block_context: BlockContext) {
+    let vm_execution =
+        execute_native_contract_calls(block_context.clone().with_vm_execution_forced());
+
+    let n_vm_fallbacks = NATIVE_EXECUTION_GUARD.n_vm_fallbacks();
+    FAIL_NATIVE_EXECUTIONS.with(|fail_native_executions| fail_native_executions.set(true));
+    let fallback_execution = execute_native_contract_calls(block_context);
+    FAIL_NATIVE_EXECUTIONS.with(|fail_native_executions| fail_native_executions.set(false));
+
+    assert!(NATIVE_EXECUTION_GUARD.n_vm_fallbacks() > n_vm_fallbacks);
+    assert_eq!(fallback_execution, vm_execution);
+}
+
+#[rstest]
+fn test_side_effects(
+    block_context: BlockContext,
+    #[values(true, false)] concurrency_enabled: bool,
+) {
+    let TestInitData { state, account_address, contract_address, .. } = create_test_init_data(
+        &block_context.chain_info,
+        CairoVersion::Cairo1(RunnableCairo1::Casm),
+    );
+    let config = TransactionExecutorConfig {
+        index_side_effects: true,
+        ..TransactionExecutorConfig::create_for_testing(concurrency_enabled)
+    };
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let txs: Vec<Transaction> = [
+        emit_n_events_tx(2, account_address, contract_address, nonce!(0_u32)),
+        emit_n_events_tx(3, account_address, contract_address, nonce!(1_u32)),
+    ]
+    .into_iter()
+    .map(Transaction::Account)
+    .collect();
+    for result in tx_executor.execute_txs(&txs) {
+        result.unwrap();
+    }
+
+    let side_effects = tx_executor.side_effects().unwrap();
+    assert_eq!(side_effects.n_txs(), 2);
+    // The events are emitted without keys, before the fee transfer event of their transaction.
+    let event_locations: Vec<_> = side_effects.events[&(contract_address, vec![])]
+        .iter()
+        .map(|event| (event.tx_index, event.tx_hash, event.event_index))
+        .collect();
+    let tx_hashes: Vec<_> = txs.iter().map(Transaction::tx_hash).collect();
+    assert_eq!(
+        event_locations,
+        vec![
+            (0, tx_hashes[0], 0),
+            (0, tx_hashes[0], 1),
+            (1, tx_hashes[1], 0),
+            (1, tx_hashes[1], 1),
+            (1, tx_hashes[1], 2),
+        ]
+    );
+    assert!(side_effects.events_filter.may_contain(contract_address.0.key()));
+    assert!(side_effects.messages.is_empty());
+}
+
+#[rstest]
+fn test_l1_handlers_interleaved_with_account_txs(
+    block_context: BlockContext,
+    #[values(true, false)] concurrency_enabled: bool,
+) {
+    let TestInitData { state, account_address, contract_address, .. } = create_test_init_data(
+        &block_context.chain_info,
+        CairoVersion::Cairo1(RunnableCairo1::Casm),
+    );
+    let config = TransactionExecutorConfig::create_for_testing(concurrency_enabled);
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let l1_handler_tx = |nonce: u32| {
+        Transaction::L1Handler(l1handler_tx_with_nonce(Fee(1), contract_address, nonce!(nonce)))
+    };
+    let invoke_tx = |nonce: u32| {
+        Transaction::Account(emit_n_events_tx(1, account_address, contract_address, nonce!(nonce)))
+    };
+    let txs = [l1_handler_tx(0), invoke_tx(0), l1_handler_tx(1), invoke_tx(1)];
+    let results = tx_executor.execute_txs(&txs);
+
+    assert!(results.iter().all(Result::is_ok));
+}
+
+#[rstest]
+fn test_side_effects_disabled(block_context: BlockContext) {
+    let state = test_state(&block_context.chain_info, BALANCE, &[]);
+    let tx_executor =
+        TransactionExecutor::new(state, block_context, TransactionExecutorConfig::default());
+    assert!(tx_executor.side_effects().is_none());
+}
+
+#[rstest]
+fn test_cancelled_execution(
+    block_context: BlockContext,
+    #[values(true, false)] concurrency_enabled: bool,
+) {
+    let cancellation_token = CancellationToken::default();
+    let block_context = block_context.with_cancellation_token(cancellation_token.clone());
+    let TestInitData { state, account_address, contract_address, .. } = create_test_init_data(
+        &block_context.chain_info,
+        CairoVersion::Cairo1(RunnableCairo1::Casm),
+    );
+    let config = TransactionExecutorConfig::create_for_testing(concurrency_enabled);
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+    let txs: Vec<Transaction> = (0..2_u32)
+        .map(|nonce| {
+            Transaction::Account(emit_n_events_tx(
+                1,
+                account_address,
+                contract_address,
+                nonce!(nonce),
+            ))
+        })
+        .collect();
+
+    let results = tx_executor.execute_txs(&txs[..1]);
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_ok());
+
+    cancellation_token.cancel();
+    let results = tx_executor.execute_txs(&txs[1..]);
+    assert_eq!(results.len(), 1);
+    assert_matches!(results[0], Err(TransactionExecutorError::Cancelled));
+}
+
+#[rstest]
+fn test_adaptive_chunk_size(block_context: BlockContext) {
+    let state = test_state(&block_context.chain_info, BALANCE, &[]);
+    let config = TransactionExecutorConfig {
+        concurrency_config: ConcurrencyConfig {
+            adaptive_chunk_size: true,
+            ..ConcurrencyConfig::create_for_testing(true)
+        },
+        ..TransactionExecutorConfig::default()
+    };
+    let (n_workers, max_chunk_size) =
+        (config.concurrency_config.n_workers, config.concurrency_config.chunk_size);
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+    let chunk_stats = |n_commit_reexecutions: usize| ConflictStats {
+        n_committed_txs: 100,
+        n_commit_reexecutions,
+        ..ConflictStats::default()
+    };
+
+    // Frequent conflicts shrink the chunks, down to the number of workers.
+    tx_executor.adapt_chunk_size(&chunk_stats(50));
+    assert_eq!(tx_executor.chunk_size, max_chunk_size / 2);
+    for _ in 0..10 {
+        tx_executor.adapt_chunk_size(&chunk_stats(50));
+    }
+    assert_eq!(tx_executor.chunk_size, n_workers);
+
+    // Moderate conflicts keep the chunk size.
+    tx_executor.adapt_chunk_size(&chunk_stats(10));
+    assert_eq!(tx_executor.chunk_size, n_workers);
+
+    // Rare conflicts grow the chunks back, up to the configured size.
+    tx_executor.adapt_chunk_size(&chunk_stats(0));
+    assert_eq!(tx_executor.chunk_size, n_workers * 2);
+    for _ in 0..10 {
+        tx_executor.adapt_chunk_size(&chunk_stats(0));
+    }
+    assert_eq!(tx_executor.chunk_size, max_chunk_size);
+}
+
+#[rstest]
+fn test_alternative_da_state_diff(block_context: BlockContext) {
+    let cairo_version = CairoVersion::Cairo1(RunnableCairo1::Casm);
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let account_contract = FeatureContract::AccountWithoutValidations(cairo_version);
+    let state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(test_contract, 1), (account_contract, 2)],
+    );
+    let mut tx_executor = TransactionExecutor::new(
+        state,
+        block_context.clone(),
+        TransactionExecutorConfig::default(),
+    );
+
+    // Only the first transaction changes the test contract storage; the second one rewrites the
+    // same value.
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_count_actual_storage_changes",
+        &[],
+    );
+    let alternative_da_account = account_contract.get_instance_address(0);
+    let l1_da_account = account_contract.get_instance_address(1);
+    let txs: Vec<Transaction> = [
+        (alternative_da_account, DataAvailabilityMode::L2),
+        (l1_da_account, DataAvailabilityMode::L1),
+    ]
+    .into_iter()
+    .map(|(sender_address, da_mode)| {
+        AccountTransaction::new_for_sequencing(executable_invoke_tx(invoke_tx_args! {
+            sender_address,
+            calldata: calldata.clone(),
+            resource_bounds: default_all_resource_bounds(),
+            nonce_data_availability_mode: da_mode,
+            fee_data_availability_mode: da_mode,
+        }))
+        .into()
+    })
+    .collect();
+
+    let execution_infos: Vec<_> = tx_executor
+        .execute_txs_sequentially(&txs)
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect();
+    // Only state diffs published on L1 cost L1 data availability gas.
+    assert_eq!(execution_infos[0].receipt.da_gas, GasVector::ZERO);
+    assert_ne!(execution_infos[1].receipt.da_gas, GasVector::ZERO);
+
+    let (state_diff, _, _) = tx_executor.finalize().unwrap();
+    let alternative_da_state_diff = tx_executor.alternative_da_state_diff(&state_diff);
+    assert_eq!(
+        alternative_da_state_diff.address_to_nonce.keys().collect::<Vec<_>>(),
+        [&alternative_da_account]
+    );
+    // The sequencer balance is also changed by the L1 transaction, so it is published on L1.
+    let fee_token_address = block_context.chain_info.fee_token_address(&FeeType::Strk);
+    assert_eq!(
+        alternative_da_state_diff
+            .storage_updates
+            .iter()
+            .map(|(address, updates)| (*address, updates.keys().copied().collect::<HashSet<_>>()))
+            .collect::<HashMap<_, _>>(),
+        HashMap::from([
+            (test_contract.get_instance_address(0), HashSet::from([storage_key!(15_u8)])),
+            (fee_token_address, HashSet::from([get_fee_token_var_address(alternative_da_account)])),
+        ])
+    );
+}
+
+#[rstest]
+fn test_block_metadata_state_diff(block_context: BlockContext) {
+    let block_metadata_contract_address = block_context
+        .versioned_constants
+        .os_constants
+        .os_contract_addresses
+        .block_metadata_contract_address()
+        .unwrap();
+    let state = test_state(&block_context.chain_info, BALANCE, &[]);
+    let block_info = block_context.block_info();
+    let block_number_hash_pair = maybe_dummy_block_hash_and_number(block_info.block_number);
+    let sequencer_address = block_info.sequencer_address;
+
+    for (starknet_version, expected_metadata_written) in
+        [(None, false), (Some(StarknetVersion::LATEST), true)]
+    {
+        let mut block_context = block_context.clone();
+        if let Some(starknet_version) = starknet_version {
+            block_context = block_context.with_starknet_version(starknet_version);
+        }
+        let mut tx_executor = TransactionExecutor::pre_process_and_create(
+            state.state.clone(),
+            block_context,
+            block_number_hash_pair,
+            TransactionExecutorConfig::default(),
+        )
+        .unwrap();
+
+        let (state_diff, _, _) = tx_executor.finalize().unwrap();
+        let metadata = state_diff.storage_updates.get(&block_metadata_contract_address);
+        assert_eq!(metadata.is_some(), expected_metadata_written);
+        if let Some(metadata) = metadata {
+            assert_eq!(metadata[&storage_key!(0_u8)], *sequencer_address.0.key());
+        }
+    }
+}
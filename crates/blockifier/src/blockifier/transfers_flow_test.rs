@@ -1,6 +1,6 @@
 use rstest::rstest;
 
-use crate::blockifier::config::ConcurrencyConfig;
+use crate::blockifier::config::{ConcurrencyConfig, ValidationStrategy};
 use crate::test_utils::transfers_generator::{
     RecipientGeneratorType,
     TransfersGenerator,
@@ -8,8 +8,27 @@ use crate::test_utils::transfers_generator::{
 };
 
 #[rstest]
-#[case::concurrency_enabled(ConcurrencyConfig{enabled: true, n_workers: 4, chunk_size: 100})]
-#[case::concurrency_disabled(ConcurrencyConfig{enabled: false, n_workers: 0, chunk_size: 0})]
+#[case::concurrency_enabled(ConcurrencyConfig {
+    enabled: true,
+    n_workers: 4,
+    chunk_size: 100,
+    ..Default::default()
+})]
+#[case::adaptive_chunk_size(ConcurrencyConfig {
+    enabled: true,
+    n_workers: 4,
+    chunk_size: 100,
+    adaptive_chunk_size: true,
+    ..Default::default()
+})]
+#[case::conservative_validation(ConcurrencyConfig {
+    enabled: true,
+    n_workers: 4,
+    chunk_size: 100,
+    validation_strategy: ValidationStrategy::Conservative,
+    ..Default::default()
+})]
+#[case::concurrency_disabled(ConcurrencyConfig::default())]
 pub fn transfers_flow_test(#[case] concurrency_config: ConcurrencyConfig) {
     let transfers_generator_config = TransfersGeneratorConfig {
         recipient_generator_type: RecipientGeneratorType::DisjointFromSenders,
@@ -1,4 +1,5 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::{Add, Div, Mul, Rem};
 
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
@@ -6,6 +7,7 @@ use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::core::ClassHash;
+use starknet_api::data_availability::DataAvailabilityMode;
 use starknet_api::execution_resources::GasAmount;
 
 use crate::blockifier::transaction_executor::{
@@ -16,9 +18,11 @@ use crate::execution::call_info::ExecutionSummary;
 use crate::fee::gas_usage::get_onchain_data_segment_length;
 use crate::fee::resources::TransactionResources;
 use crate::state::cached_state::{StateChangesKeys, StorageEntry};
+use crate::state::da_encoding::{compressed_state_diff_length, FIELD_ELEMENTS_PER_BLOB};
 use crate::state::state_api::StateReader;
 use crate::transaction::errors::TransactionExecutionError;
 use crate::transaction::objects::{ExecutionResourcesTraits, TransactionExecutionResult};
+use crate::transaction::transaction_execution::Transaction;
 use crate::utils::usize_from_u64;
 
 #[cfg(test)]
@@ -54,30 +58,57 @@ pub type HashMapWrapper = HashMap<BuiltinName, usize>;
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct BouncerConfig {
     pub block_max_capacity: BouncerWeights,
+    // The percentage of the block capacity, in every dimension, that user transactions may not
+    // use; it is kept for L1 handler and system transactions.
+    pub reserved_capacity_percent: u8,
 }
 
 impl BouncerConfig {
     pub fn empty() -> Self {
-        Self { block_max_capacity: BouncerWeights::empty() }
+        Self { block_max_capacity: BouncerWeights::empty(), reserved_capacity_percent: 0 }
     }
 
     pub fn max() -> Self {
-        Self { block_max_capacity: BouncerWeights::max() }
+        Self { block_max_capacity: BouncerWeights::max(), reserved_capacity_percent: 0 }
     }
 
     pub fn has_room(&self, weights: BouncerWeights) -> bool {
         self.block_max_capacity.has_room(weights)
     }
 
+    /// Checks that the reserved capacity is a valid percentage.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.reserved_capacity_percent > 100 {
+            return Err(format!(
+                "The reserved capacity is {}%, more than the whole block.",
+                self.reserved_capacity_percent
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the block capacity available to transactions of the given class.
+    pub fn capacity_for(&self, tx_capacity_class: TxCapacityClass) -> BouncerWeights {
+        match tx_capacity_class {
+            TxCapacityClass::System => self.block_max_capacity,
+            TxCapacityClass::User => self
+                .block_max_capacity
+                .percentage(100_u8.saturating_sub(self.reserved_capacity_percent)),
+        }
+    }
+
+    /// Checks that a transaction of the given class with the given weights fits in an empty block.
     pub fn within_max_capacity_or_err(
         &self,
         weights: BouncerWeights,
+        tx_capacity_class: TxCapacityClass,
     ) -> TransactionExecutionResult<()> {
-        if self.block_max_capacity.has_room(weights) {
+        let max_capacity = self.capacity_for(tx_capacity_class);
+        if max_capacity.has_room(weights) {
             Ok(())
         } else {
             Err(TransactionExecutionError::TransactionTooLarge {
-                max_capacity: Box::new(self.block_max_capacity),
+                max_capacity: Box::new(max_capacity),
                 tx_size: Box::new(weights),
             })
         }
@@ -86,7 +117,49 @@ impl BouncerConfig {
 
 impl SerializeConfig for BouncerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        append_sub_config_name(self.block_max_capacity.dump(), "block_max_capacity")
+        let mut dump = append_sub_config_name(self.block_max_capacity.dump(), "block_max_capacity");
+        dump.append(&mut BTreeMap::from([ser_param(
+            "reserved_capacity_percent",
+            &self.reserved_capacity_percent,
+            "The percentage of the block capacity, in every dimension, reserved for L1 handler \
+             and system transactions. User transactions may only fill the rest of the block.",
+            ParamPrivacyInput::Public,
+        )]));
+        dump
+    }
+}
+
+/// Whether a transaction fits in the block; see `Bouncer::can_fit`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FitResult {
+    Fits,
+    /// The transaction does not fit in the remaining capacity of the block, in the given
+    /// dimensions; it may fit in another block.
+    ExceedsRemainingCapacity {
+        dimensions: Vec<String>,
+    },
+    /// The transaction does not fit in any block, in the given dimensions, within the capacity
+    /// available to its class.
+    ExceedsMaxCapacity {
+        dimensions: Vec<String>,
+    },
+}
+
+/// Determines which part of the block capacity a transaction may use.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TxCapacityClass {
+    /// L1 handler and system transactions, which may use the entire block capacity.
+    System,
+    /// User transactions, which may not use the capacity reserved for system transactions.
+    User,
+}
+
+impl From<&Transaction> for TxCapacityClass {
+    fn from(tx: &Transaction) -> Self {
+        match tx {
+            Transaction::L1Handler(_) => Self::System,
+            Transaction::Account(_) => Self::User,
+        }
     }
 }
 
@@ -95,9 +168,13 @@ impl SerializeConfig for BouncerConfig {
 /// Represents the execution resources counted throughout block creation.
 pub struct BouncerWeights {
     pub builtin_count: BuiltinCount,
+    // The length of the state diff published on L1 after compression, in felts; see
+    // `get_compressed_state_diff_size`.
+    pub compressed_state_diff_size: usize,
     pub l1_gas: usize,
     pub message_segment_length: usize,
     pub n_events: usize,
+    pub n_messages: usize,
     pub n_steps: usize,
     pub state_diff_size: usize,
     pub sierra_gas: GasAmount,
@@ -106,9 +183,11 @@ pub struct BouncerWeights {
 impl BouncerWeights {
     impl_checked_ops!(
         builtin_count,
+        compressed_state_diff_size,
         l1_gas,
         message_segment_length,
         n_events,
+        n_messages,
         n_steps,
         state_diff_size,
         sierra_gas
@@ -118,13 +197,65 @@ impl BouncerWeights {
         self.checked_sub(other).is_some()
     }
 
+    /// Returns the names of the dimensions in which the weights exceed the given capacity; empty
+    /// if and only if the capacity has room for them.
+    pub fn exceeding_dimensions(&self, capacity: &Self) -> Vec<String> {
+        let exceeding_builtins = self
+            .builtin_count
+            .exceeding_dimensions(&capacity.builtin_count)
+            .into_iter()
+            .map(|builtin| format!("builtin_count.{builtin}"));
+        let dimensions = [
+            (
+                "compressed_state_diff_size",
+                self.compressed_state_diff_size > capacity.compressed_state_diff_size,
+            ),
+            ("l1_gas", self.l1_gas > capacity.l1_gas),
+            (
+                "message_segment_length",
+                self.message_segment_length > capacity.message_segment_length,
+            ),
+            ("n_events", self.n_events > capacity.n_events),
+            ("n_messages", self.n_messages > capacity.n_messages),
+            ("n_steps", self.n_steps > capacity.n_steps),
+            ("state_diff_size", self.state_diff_size > capacity.state_diff_size),
+            ("sierra_gas", self.sierra_gas > capacity.sierra_gas),
+        ];
+        exceeding_builtins
+            .chain(
+                dimensions
+                    .into_iter()
+                    .filter(|(_, exceeds)| *exceeds)
+                    .map(|(dimension, _)| dimension.to_string()),
+            )
+            .collect()
+    }
+
+    /// Returns the given percentage (at most 100) of the weights, in every dimension, rounded
+    /// down.
+    pub fn percentage(&self, percent: u8) -> Self {
+        Self {
+            builtin_count: self.builtin_count.percentage(percent),
+            compressed_state_diff_size: percentage_of(self.compressed_state_diff_size, percent),
+            l1_gas: percentage_of(self.l1_gas, percent),
+            message_segment_length: percentage_of(self.message_segment_length, percent),
+            n_events: percentage_of(self.n_events, percent),
+            n_messages: percentage_of(self.n_messages, percent),
+            n_steps: percentage_of(self.n_steps, percent),
+            state_diff_size: percentage_of(self.state_diff_size, percent),
+            sierra_gas: GasAmount(percentage_of(self.sierra_gas.0, percent)),
+        }
+    }
+
     pub fn max() -> Self {
         Self {
             l1_gas: usize::MAX,
             n_steps: usize::MAX,
             message_segment_length: usize::MAX,
             state_diff_size: usize::MAX,
+            compressed_state_diff_size: usize::MAX,
             n_events: usize::MAX,
+            n_messages: usize::MAX,
             builtin_count: BuiltinCount::max(),
             sierra_gas: GasAmount::MAX,
         }
@@ -133,11 +264,13 @@ impl BouncerWeights {
     pub fn empty() -> Self {
         Self {
             n_events: 0,
+            n_messages: 0,
             builtin_count: BuiltinCount::empty(),
             l1_gas: 0,
             message_segment_length: 0,
             n_steps: 0,
             state_diff_size: 0,
+            compressed_state_diff_size: 0,
             sierra_gas: GasAmount::ZERO,
         }
     }
@@ -151,7 +284,10 @@ impl Default for BouncerWeights {
             n_steps: 2500000,
             message_segment_length: 3700,
             n_events: 5000,
+            n_messages: 1000,
             state_diff_size: 4000,
+            // The capacity of 6 blobs.
+            compressed_state_diff_size: 6 * FIELD_ELEMENTS_PER_BLOB,
             builtin_count: BuiltinCount::default(),
             sierra_gas: GasAmount(250000000),
         }
@@ -161,6 +297,13 @@ impl Default for BouncerWeights {
 impl SerializeConfig for BouncerWeights {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         let mut dump = append_sub_config_name(self.builtin_count.dump(), "builtin_count");
+        dump.append(&mut BTreeMap::from([ser_param(
+            "compressed_state_diff_size",
+            &self.compressed_state_diff_size,
+            "An upper bound on the total state diff size in a block after compression, in field \
+             elements; i.e., on the size of the blobs of the block.",
+            ParamPrivacyInput::Public,
+        )]));
         dump.append(&mut BTreeMap::from([ser_param(
             "l1_gas",
             &self.l1_gas,
@@ -179,6 +322,12 @@ impl SerializeConfig for BouncerWeights {
             "An upper bound on the total number of events generated in a block.",
             ParamPrivacyInput::Public,
         )]));
+        dump.append(&mut BTreeMap::from([ser_param(
+            "n_messages",
+            &self.n_messages,
+            "An upper bound on the total number of L2 to L1 messages sent in a block.",
+            ParamPrivacyInput::Public,
+        )]));
         dump.append(&mut BTreeMap::from([ser_param(
             "n_steps",
             &self.n_steps,
@@ -206,12 +355,15 @@ impl std::fmt::Display for BouncerWeights {
         write!(
             f,
             "BouncerWeights {{ l1_gas: {}, n_steps: {}, message_segment_length: {}, n_events: {}, \
-             state_diff_size: {}, builtin_count: {}, sierra_gas: {} }}",
+             n_messages: {}, state_diff_size: {}, compressed_state_diff_size: {}, builtin_count: \
+             {}, sierra_gas: {} }}",
             self.l1_gas,
             self.n_steps,
             self.message_segment_length,
             self.n_events,
+            self.n_messages,
             self.state_diff_size,
+            self.compressed_state_diff_size,
             self.builtin_count,
             self.sierra_gas
         )
@@ -250,13 +402,50 @@ macro_rules! impl_all_non_zero {
     };
 }
 
+macro_rules! impl_percentage {
+    ($($field:ident),+) => {
+        fn percentage(&self, percent: u8) -> Self {
+            Self {
+                $(
+                    $field: percentage_of(self.$field, percent),
+                )+
+            }
+        }
+    };
+}
+
+macro_rules! impl_exceeding_dimensions {
+    ($($field:ident),+) => {
+        pub fn exceeding_dimensions(&self, capacity: &Self) -> Vec<&'static str> {
+            let mut dimensions = Vec::new();
+            $(
+                if self.$field > capacity.$field {
+                    dimensions.push(stringify!($field));
+                }
+            )+
+            dimensions
+        }
+    };
+}
+
 macro_rules! impl_builtin_variants {
     ($($field:ident),+) => {
         impl_checked_ops!($($field),+);
         impl_all_non_zero!($($field),+);
+        impl_percentage!($($field),+);
+        impl_exceeding_dimensions!($($field),+);
     };
 }
 
+/// Computes `value * percent / 100`, rounded down, without overflowing.
+fn percentage_of<T>(value: T, percent: u8) -> T
+where
+    T: Copy + From<u8> + Add<Output = T> + Mul<Output = T> + Div<Output = T> + Rem<Output = T>,
+{
+    let (percent, hundred) = (T::from(percent), T::from(100));
+    value / hundred * percent + value % hundred * percent / hundred
+}
+
 impl BuiltinCount {
     impl_builtin_variants!(
         add_mod,
@@ -441,6 +630,9 @@ pub struct Bouncer {
     pub executed_class_hashes: HashSet<ClassHash>,
     pub visited_storage_entries: HashSet<StorageEntry>,
     pub state_changes_keys: StateChangesKeys,
+    // The state changes made by transactions whose state diff is published on L1; only these
+    // occupy the block's state diff capacity.
+    pub l1_da_state_changes_keys: StateChangesKeys,
 
     pub bouncer_config: BouncerConfig,
 
@@ -457,6 +649,7 @@ impl Bouncer {
             executed_class_hashes: HashSet::default(),
             visited_storage_entries: HashSet::default(),
             state_changes_keys: StateChangesKeys::default(),
+            l1_da_state_changes_keys: StateChangesKeys::default(),
             bouncer_config: BouncerConfig::empty(),
             accumulated_weights: BouncerWeights::empty(),
         }
@@ -473,11 +666,16 @@ impl Bouncer {
         tx_state_changes_keys: &StateChangesKeys,
         tx_execution_summary: &ExecutionSummary,
         tx_resources: &TransactionResources,
+        tx_capacity_class: TxCapacityClass,
     ) -> TransactionExecutorResult<()> {
         // The countings here should be linear in the transactional state changes and execution info
         // rather than the cumulative state attributes.
         let marginal_state_changes_keys =
             tx_state_changes_keys.difference(&self.state_changes_keys);
+        // A change previously made only by transactions using an alternative data availability is
+        // published on L1 once an L1 transaction makes it too.
+        let marginal_l1_da_state_changes_keys =
+            tx_state_changes_keys.difference(&self.l1_da_state_changes_keys);
         let marginal_executed_class_hashes = tx_execution_summary
             .executed_class_hashes
             .difference(&self.executed_class_hashes)
@@ -492,29 +690,61 @@ impl Bouncer {
             &marginal_executed_class_hashes,
             n_marginal_visited_storage_entries,
             tx_resources,
-            &marginal_state_changes_keys,
+            &marginal_l1_da_state_changes_keys,
         )?;
 
         // Check if the transaction can fit the current block available capacity.
+        match self.can_fit(&tx_weights, tx_capacity_class) {
+            FitResult::Fits => {}
+            FitResult::ExceedsRemainingCapacity { dimensions }
+            | FitResult::ExceedsMaxCapacity { dimensions } => {
+                log::debug!(
+                    "Transaction cannot be added to the current block, block capacity reached in \
+                     {dimensions:?}; transaction weights: {tx_weights:?}, block weights: {:?}.",
+                    self.accumulated_weights
+                );
+                Err(TransactionExecutorError::BlockFull)?
+            }
+        }
+
+        self.update(tx_weights, tx_execution_summary, &marginal_state_changes_keys);
+        if tx_resources.starknet_resources.state.data_availability_mode == DataAvailabilityMode::L1
+        {
+            self.l1_da_state_changes_keys.extend(&marginal_l1_da_state_changes_keys);
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether a transaction of the given weights and capacity class fits in the block,
+    /// without updating the bouncer. Reports the dimensions in which it does not.
+    pub fn can_fit(
+        &self,
+        tx_weights: &BouncerWeights,
+        tx_capacity_class: TxCapacityClass,
+    ) -> FitResult {
+        let capacity = self.bouncer_config.capacity_for(tx_capacity_class);
+        let dimensions = tx_weights.exceeding_dimensions(&capacity);
+        if !dimensions.is_empty() {
+            return FitResult::ExceedsMaxCapacity { dimensions };
+        }
+
         let err_msg = format!(
             "Addition overflow. Transaction weights: {tx_weights:?}, block weights: {:?}.",
             self.accumulated_weights
         );
-        if !self
-            .bouncer_config
-            .has_room(self.accumulated_weights.checked_add(tx_weights).expect(&err_msg))
-        {
-            log::debug!(
-                "Transaction cannot be added to the current block, block capacity reached; \
-                 transaction weights: {tx_weights:?}, block weights: {:?}.",
-                self.accumulated_weights
-            );
-            Err(TransactionExecutorError::BlockFull)?
+        let block_weights = self.accumulated_weights.checked_add(*tx_weights).expect(&err_msg);
+        let dimensions = block_weights.exceeding_dimensions(&capacity);
+        if !dimensions.is_empty() {
+            return FitResult::ExceedsRemainingCapacity { dimensions };
         }
+        FitResult::Fits
+    }
 
-        self.update(tx_weights, tx_execution_summary, &marginal_state_changes_keys);
-
-        Ok(())
+    /// Returns the state changes made only by transactions whose state diff is published to an
+    /// alternative data availability layer.
+    pub fn alternative_da_state_changes_keys(&self) -> StateChangesKeys {
+        self.state_changes_keys.difference(&self.l1_da_state_changes_keys)
     }
 
     fn update(
@@ -558,17 +788,39 @@ pub fn get_tx_weights<S: StateReader>(
 
     let vm_resources = &additional_os_resources + &tx_resources.computation.vm_resources;
 
+    let (state_diff_size, compressed_state_diff_size) =
+        match tx_resources.starknet_resources.state.data_availability_mode {
+            DataAvailabilityMode::L1 => (
+                get_onchain_data_segment_length(&state_changes_keys.count()),
+                get_compressed_state_diff_size(state_reader, state_changes_keys)?,
+            ),
+            DataAvailabilityMode::L2 => (0, 0),
+        };
+
     Ok(BouncerWeights {
         l1_gas: message_starknet_l1gas,
         message_segment_length: message_resources.message_segment_length,
         n_events: tx_resources.starknet_resources.archival_data.event_summary.n_events,
+        n_messages: message_resources.l2_to_l1_payload_lengths.len(),
         n_steps: vm_resources.total_n_steps(),
         builtin_count: BuiltinCount::from(vm_resources.prover_builtins()),
-        state_diff_size: get_onchain_data_segment_length(&state_changes_keys.count()),
+        state_diff_size,
+        compressed_state_diff_size,
         sierra_gas: tx_resources.computation.sierra_gas,
     })
 }
 
+/// Returns the contribution of the given state changes to the compressed state diff of the block:
+/// their compressed length given their values in the given state.
+/// Transactions are weighed separately, so values repeating across transactions are counted once
+/// per transaction; this bounds the compressed state diff of the block from above.
+pub fn get_compressed_state_diff_size<S: StateReader>(
+    state_reader: &S,
+    state_changes_keys: &StateChangesKeys,
+) -> TransactionExecutionResult<usize> {
+    Ok(compressed_state_diff_length(&state_changes_keys.read_values(state_reader)?))
+}
+
 /// Returns the estimated Cairo resources for Casm hash calculation (done by the OS), of the given
 /// classes.
 pub fn get_casm_hash_calculation_resources<S: StateReader>(
@@ -609,6 +861,7 @@ pub fn verify_tx_weights_within_max_capacity<S: StateReader>(
     tx_resources: &TransactionResources,
     tx_state_changes_keys: &StateChangesKeys,
     bouncer_config: &BouncerConfig,
+    tx_capacity_class: TxCapacityClass,
 ) -> TransactionExecutionResult<()> {
     let tx_weights = get_tx_weights(
         state_reader,
@@ -618,5 +871,5 @@ pub fn verify_tx_weights_within_max_capacity<S: StateReader>(
         tx_state_changes_keys,
     )?;
 
-    bouncer_config.within_max_capacity_or_err(tx_weights)
+    bouncer_config.within_max_capacity_or_err(tx_weights, tx_capacity_class)
 }
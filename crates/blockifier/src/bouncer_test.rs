@@ -4,22 +4,33 @@ use assert_matches::assert_matches;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use rstest::rstest;
+use starknet_api::data_availability::DataAvailabilityMode;
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::transaction::fields::Fee;
-use starknet_api::{class_hash, contract_address, storage_key};
+use starknet_api::{class_hash, contract_address, felt, storage_key};
 
 use super::BouncerConfig;
 use crate::blockifier::transaction_executor::TransactionExecutorError;
 use crate::bouncer::{
+    get_tx_weights,
     verify_tx_weights_within_max_capacity,
     Bouncer,
     BouncerWeights,
     BuiltinCount,
+    FitResult,
+    TxCapacityClass,
 };
 use crate::context::BlockContext;
 use crate::execution::call_info::ExecutionSummary;
-use crate::fee::resources::{ComputationResources, TransactionResources};
+use crate::fee::resources::{
+    ComputationResources,
+    MessageResources,
+    StarknetResources,
+    StateResources,
+    TransactionResources,
+};
 use crate::state::cached_state::{StateChangesKeys, TransactionalState};
+use crate::state::state_api::State;
 use crate::test_utils::initial_test_state::test_state;
 use crate::transaction::errors::TransactionExecutionError;
 
@@ -41,8 +52,10 @@ fn test_block_weights_has_room() {
         l1_gas: 10,
         message_segment_length: 10,
         n_events: 10,
+        n_messages: 10,
         n_steps: 10,
         state_diff_size: 10,
+        compressed_state_diff_size: 10,
         sierra_gas: GasAmount(10),
     };
 
@@ -63,7 +76,9 @@ fn test_block_weights_has_room() {
         message_segment_length: 10,
         n_steps: 0,
         n_events: 2,
+        n_messages: 2,
         state_diff_size: 7,
+        compressed_state_diff_size: 7,
         sierra_gas: GasAmount(7),
     };
 
@@ -86,7 +101,9 @@ fn test_block_weights_has_room() {
         message_segment_length: 5,
         n_steps: 5,
         n_events: 5,
+        n_messages: 5,
         state_diff_size: 5,
+        compressed_state_diff_size: 5,
         sierra_gas: GasAmount(5),
     };
 
@@ -104,6 +121,7 @@ fn test_block_weights_has_room() {
     state_changes_keys: StateChangesKeys::create_for_testing(HashSet::from([
         contract_address!(0_u128),
     ])),
+    l1_da_state_changes_keys: StateChangesKeys::default(),
     bouncer_config: BouncerConfig::empty(),
     accumulated_weights: BouncerWeights {
         builtin_count: BuiltinCount {
@@ -122,7 +140,9 @@ fn test_block_weights_has_room() {
         message_segment_length: 10,
         n_steps: 10,
         n_events: 10,
+        n_messages: 10,
         state_diff_size: 10,
+        compressed_state_diff_size: 10,
         sierra_gas: GasAmount(10),
     },
 })]
@@ -153,7 +173,9 @@ fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
         message_segment_length: 10,
         n_steps: 0,
         n_events: 1,
+        n_messages: 1,
         state_diff_size: 2,
+        compressed_state_diff_size: 2,
         sierra_gas: GasAmount(9),
     };
 
@@ -180,11 +202,81 @@ fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
     assert_eq!(updated_bouncer, expected_bouncer);
 }
 
+#[test]
+fn test_capacity_for_tx_class() {
+    let block_max_capacity =
+        BouncerWeights { n_events: 1000, sierra_gas: GasAmount(205), ..BouncerWeights::empty() };
+    let bouncer_config = BouncerConfig { block_max_capacity, reserved_capacity_percent: 10 };
+
+    assert_eq!(bouncer_config.capacity_for(TxCapacityClass::System), block_max_capacity);
+    assert_eq!(
+        bouncer_config.capacity_for(TxCapacityClass::User),
+        BouncerWeights { n_events: 900, sierra_gas: GasAmount(184), ..BouncerWeights::empty() }
+    );
+    assert_eq!(BouncerConfig::max().capacity_for(TxCapacityClass::User), BouncerWeights::max());
+}
+
+#[rstest]
+#[case::fits(1, 0, TxCapacityClass::User, FitResult::Fits)]
+#[case::exceeds_reserved_capacity(
+    2,
+    0,
+    TxCapacityClass::User,
+    FitResult::ExceedsRemainingCapacity { dimensions: vec!["n_events".to_string()] },
+)]
+#[case::system_tx_in_reserved_capacity(2, 0, TxCapacityClass::System, FitResult::Fits)]
+#[case::exceeds_max_user_capacity(
+    6,
+    0,
+    TxCapacityClass::User,
+    FitResult::ExceedsMaxCapacity { dimensions: vec!["n_events".to_string()] },
+)]
+#[case::exceeds_max_capacity(
+    11,
+    11,
+    TxCapacityClass::System,
+    FitResult::ExceedsMaxCapacity {
+        dimensions: vec!["builtin_count.ecdsa".to_string(), "n_events".to_string()],
+    },
+)]
+fn test_can_fit(
+    #[case] n_events: usize,
+    #[case] ecdsa: usize,
+    #[case] tx_capacity_class: TxCapacityClass,
+    #[case] expected_fit_result: FitResult,
+) {
+    let block_max_capacity = BouncerWeights {
+        builtin_count: BuiltinCount { ecdsa: 10, ..BuiltinCount::max() },
+        n_events: 10,
+        ..BouncerWeights::max()
+    };
+    let bouncer_config = BouncerConfig { block_max_capacity, reserved_capacity_percent: 50 };
+    let accumulated_weights = BouncerWeights { n_events: 4, ..BouncerWeights::empty() };
+    let bouncer = Bouncer { accumulated_weights, bouncer_config, ..Bouncer::empty() };
+    let tx_weights = BouncerWeights {
+        builtin_count: BuiltinCount { ecdsa, ..BuiltinCount::empty() },
+        n_events,
+        ..BouncerWeights::empty()
+    };
+
+    assert_eq!(bouncer.can_fit(&tx_weights, tx_capacity_class), expected_fit_result);
+    // The query does not update the bouncer.
+    assert_eq!(*bouncer.get_accumulated_weights(), accumulated_weights);
+}
+
 #[rstest]
-#[case::positive_flow(1, "ok")]
-#[case::block_full(11, "block_full")]
-#[case::transaction_too_large(21, "too_large")]
-fn test_bouncer_try_update(#[case] added_ecdsa: usize, #[case] scenario: &'static str) {
+#[case::positive_flow(1, 0, TxCapacityClass::User, "ok")]
+#[case::block_full(11, 0, TxCapacityClass::User, "block_full")]
+#[case::transaction_too_large(21, 0, TxCapacityClass::User, "too_large")]
+#[case::user_tx_in_reserved_capacity(1, 50, TxCapacityClass::User, "block_full")]
+#[case::system_tx_in_reserved_capacity(1, 50, TxCapacityClass::System, "ok")]
+#[case::user_tx_too_large_for_unreserved_capacity(11, 50, TxCapacityClass::User, "too_large")]
+fn test_bouncer_try_update(
+    #[case] added_ecdsa: usize,
+    #[case] reserved_capacity_percent: u8,
+    #[case] tx_capacity_class: TxCapacityClass,
+    #[case] scenario: &'static str,
+) {
     let state =
         &mut test_state(&BlockContext::create_for_account_testing().chain_info, Fee(0), &[]);
     let mut transactional_state = TransactionalState::create_transactional(state);
@@ -207,10 +299,12 @@ fn test_bouncer_try_update(#[case] added_ecdsa: usize, #[case] scenario: &'stati
         message_segment_length: 20,
         n_steps: 20,
         n_events: 20,
+        n_messages: 20,
         state_diff_size: 20,
+        compressed_state_diff_size: 20,
         sierra_gas: GasAmount(20),
     };
-    let bouncer_config = BouncerConfig { block_max_capacity };
+    let bouncer_config = BouncerConfig { block_max_capacity, reserved_capacity_percent };
 
     let accumulated_weights = BouncerWeights {
         builtin_count: BuiltinCount {
@@ -229,7 +323,9 @@ fn test_bouncer_try_update(#[case] added_ecdsa: usize, #[case] scenario: &'stati
         message_segment_length: 10,
         n_steps: 10,
         n_events: 10,
+        n_messages: 10,
         state_diff_size: 10,
+        compressed_state_diff_size: 10,
         sierra_gas: GasAmount(10),
     };
 
@@ -268,6 +364,7 @@ fn test_bouncer_try_update(#[case] added_ecdsa: usize, #[case] scenario: &'stati
         &tx_resources,
         &tx_state_changes_keys,
         &bouncer.bouncer_config,
+        tx_capacity_class,
     )
     .map_err(TransactionExecutorError::TransactionExecutionError);
     let expected_weights =
@@ -280,6 +377,7 @@ fn test_bouncer_try_update(#[case] added_ecdsa: usize, #[case] scenario: &'stati
             &tx_state_changes_keys,
             &execution_summary,
             &tx_resources,
+            tx_capacity_class,
         );
     }
 
@@ -290,7 +388,50 @@ fn test_bouncer_try_update(#[case] added_ecdsa: usize, #[case] scenario: &'stati
                 TransactionExecutorError::TransactionExecutionError(
                     TransactionExecutionError::TransactionTooLarge { max_capacity, tx_size }
                 )
-            ) if *max_capacity == block_max_capacity && *tx_size == expected_weights),
+            ) if *max_capacity == bouncer.bouncer_config.capacity_for(tx_capacity_class)
+                && *tx_size == expected_weights),
         _ => panic!("Unexpected scenario: {}", scenario),
     }
 }
+
+#[rstest]
+fn test_tx_weights_of_messages_and_compressed_state_diff(
+    #[values(DataAvailabilityMode::L1, DataAvailabilityMode::L2)]
+    data_availability_mode: DataAvailabilityMode,
+) {
+    let state =
+        &mut test_state(&BlockContext::create_for_account_testing().chain_info, Fee(0), &[]);
+    let mut transactional_state = TransactionalState::create_transactional(state);
+    transactional_state
+        .set_storage_at(contract_address!("0x10"), storage_key!("0x1"), felt!("0x5"))
+        .unwrap();
+    let tx_state_changes_keys =
+        transactional_state.get_actual_state_changes().unwrap().state_maps.into_keys();
+    let tx_resources = TransactionResources {
+        starknet_resources: StarknetResources {
+            messages: MessageResources::new(vec![2, 3], None),
+            state: StateResources { data_availability_mode, ..Default::default() },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let tx_weights = get_tx_weights(
+        &transactional_state,
+        &HashSet::new(),
+        0,
+        &tx_resources,
+        &tx_state_changes_keys,
+    )
+    .unwrap();
+
+    assert_eq!(tx_weights.n_messages, 2);
+    // The serialized state diff (the contract count, address, header, key and value, and the
+    // class count) is compressed into a bucket of unique values, a repeating value pointer and
+    // the bucket indices.
+    let expected_compressed_state_diff_size = match data_availability_mode {
+        DataAvailabilityMode::L1 => 3,
+        DataAvailabilityMode::L2 => 0,
+    };
+    assert_eq!(tx_weights.compressed_state_diff_size, expected_compressed_state_diff_size);
+}
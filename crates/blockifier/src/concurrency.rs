@@ -1,3 +1,5 @@
+pub mod conflict_stats;
+pub mod dependency_hints;
 pub mod fee_utils;
 pub mod scheduler;
 #[cfg(any(feature = "testing", test))]
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::state::cached_state::StorageEntry;
+
+#[cfg(test)]
+#[path = "conflict_stats_test.rs"]
+mod test;
+
+/// Statistics of the conflicts between transactions executed concurrently; a conflict occurs when
+/// a transaction read a value that a preceding transaction in the chunk wrote afterwards, forcing
+/// its re-execution.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConflictStats {
+    /// The number of transactions committed.
+    pub n_committed_txs: usize,
+    /// The number of transaction executions, including re-executions.
+    pub n_executions: usize,
+    /// The number of executions aborted by a failed validation.
+    pub n_validation_aborts: usize,
+    /// The number of transactions re-executed during their commit.
+    pub n_commit_reexecutions: usize,
+    /// How many conflicts each storage cell caused.
+    pub storage_conflicts: HashMap<StorageEntry, usize>,
+}
+
+impl ConflictStats {
+    pub fn record_conflict(
+        &mut self,
+        conflicting_storage_keys: impl IntoIterator<Item = StorageEntry>,
+    ) {
+        for storage_key in conflicting_storage_keys {
+            *self.storage_conflicts.entry(storage_key).or_default() += 1;
+        }
+    }
+
+    /// The number of re-executions per 100 committed transactions.
+    pub fn conflict_percentage(&self) -> usize {
+        if self.n_committed_txs == 0 {
+            return 0;
+        }
+        (self.n_validation_aborts + self.n_commit_reexecutions) * 100 / self.n_committed_txs
+    }
+
+    /// The storage cells that caused the most conflicts, with their conflict counts, most
+    /// conflicting first.
+    pub fn most_conflicting_storage_keys(&self, n_keys: usize) -> Vec<(StorageEntry, usize)> {
+        self.storage_conflicts
+            .iter()
+            .map(|(storage_key, n_conflicts)| (*storage_key, *n_conflicts))
+            .sorted_by(|(key_a, count_a), (key_b, count_b)| {
+                count_b.cmp(count_a).then_with(|| key_a.cmp(key_b))
+            })
+            .take(n_keys)
+            .collect()
+    }
+
+    pub fn extend(&mut self, other: &Self) {
+        self.n_committed_txs += other.n_committed_txs;
+        self.n_executions += other.n_executions;
+        self.n_validation_aborts += other.n_validation_aborts;
+        self.n_commit_reexecutions += other.n_commit_reexecutions;
+        for (storage_key, n_conflicts) in &other.storage_conflicts {
+            *self.storage_conflicts.entry(*storage_key).or_default() += n_conflicts;
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use starknet_api::{contract_address, storage_key};
+
+use crate::concurrency::conflict_stats::ConflictStats;
+
+#[test]
+fn test_conflict_stats() {
+    let hot_key = (contract_address!("0x1"), storage_key!("0x10"));
+    let cold_key = (contract_address!("0x2"), storage_key!("0x20"));
+
+    let mut chunk_stats = ConflictStats {
+        n_committed_txs: 4,
+        n_executions: 6,
+        n_validation_aborts: 1,
+        n_commit_reexecutions: 1,
+        ..Default::default()
+    };
+    chunk_stats.record_conflict([hot_key, cold_key]);
+    chunk_stats.record_conflict([hot_key]);
+    assert_eq!(chunk_stats.conflict_percentage(), 50);
+
+    let mut block_stats = ConflictStats::default();
+    assert_eq!(block_stats.conflict_percentage(), 0);
+    block_stats.extend(&chunk_stats);
+    block_stats.extend(&chunk_stats);
+    assert_eq!(block_stats.n_executions, 12);
+    assert_eq!(block_stats.most_conflicting_storage_keys(1), vec![(hot_key, 4)]);
+    assert_eq!(block_stats.most_conflicting_storage_keys(3), vec![(hot_key, 4), (cold_key, 2)]);
+}
@@ -0,0 +1,79 @@
+use std::collections::{HashMap, HashSet};
+
+use starknet_api::core::ContractAddress;
+
+use crate::concurrency::TxIndex;
+use crate::context::BlockContext;
+use crate::fee::fee_utils::get_address_balance_keys;
+use crate::state::cached_state::StorageEntry;
+use crate::transaction::transaction_execution::Transaction;
+
+#[cfg(test)]
+#[path = "dependency_hints_test.rs"]
+mod test;
+
+/// The state a transaction is declared to access, passed to the concurrent executor alongside the
+/// transaction. Hints only affect the scheduling of the transactions: a transaction whose hints
+/// conflict with a preceding transaction is executed only after it. Inaccurate hints cost
+/// performance, not correctness, as every execution is still validated.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TxDependencyHints {
+    /// The contracts whose nonce the transaction increments.
+    pub nonce_writes: HashSet<ContractAddress>,
+    pub storage_reads: HashSet<StorageEntry>,
+    pub storage_writes: HashSet<StorageEntry>,
+}
+
+impl TxDependencyHints {
+    /// The hints known without executing the transaction: the nonce of its sender and the balance
+    /// of its fee payer in the fee token.
+    pub fn from_tx(tx: &Transaction, block_context: &BlockContext) -> Self {
+        match tx {
+            Transaction::Account(_) => {
+                let sender_address = tx.sender_address();
+                let tx_context = block_context.to_tx_context(tx);
+                let fee_token_address = tx_context.fee_token_address();
+                let (balance_key_low, balance_key_high) =
+                    get_address_balance_keys(tx_context.fee_payer_address());
+                Self {
+                    nonce_writes: HashSet::from([sender_address]),
+                    storage_reads: HashSet::new(),
+                    storage_writes: HashSet::from([
+                        (fee_token_address, balance_key_low),
+                        (fee_token_address, balance_key_high),
+                    ]),
+                }
+            }
+            // L1 handlers neither increment a nonce nor pay a fee.
+            Transaction::L1Handler(_) => Self::default(),
+        }
+    }
+}
+
+/// Returns, for each transaction, the last preceding transaction that its hints conflict with;
+/// i.e., one that writes a storage cell or a nonce that the transaction accesses.
+pub fn find_hinted_dependencies(hints: &[TxDependencyHints]) -> Vec<Option<TxIndex>> {
+    let mut last_nonce_writer: HashMap<ContractAddress, TxIndex> = HashMap::new();
+    let mut last_storage_writer: HashMap<StorageEntry, TxIndex> = HashMap::new();
+    hints
+        .iter()
+        .enumerate()
+        .map(|(tx_index, tx_hints)| {
+            let nonce_dependencies =
+                tx_hints.nonce_writes.iter().filter_map(|address| last_nonce_writer.get(address));
+            let storage_dependencies = tx_hints
+                .storage_reads
+                .union(&tx_hints.storage_writes)
+                .filter_map(|storage_entry| last_storage_writer.get(storage_entry));
+            let dependency = nonce_dependencies.chain(storage_dependencies).max().copied();
+
+            for address in &tx_hints.nonce_writes {
+                last_nonce_writer.insert(*address, tx_index);
+            }
+            for storage_entry in &tx_hints.storage_writes {
+                last_storage_writer.insert(*storage_entry, tx_index);
+            }
+            dependency
+        })
+        .collect()
+}
@@ -0,0 +1,24 @@
+use std::collections::HashSet;
+
+use starknet_api::{contract_address, storage_key};
+
+use crate::concurrency::dependency_hints::{find_hinted_dependencies, TxDependencyHints};
+
+#[test]
+fn test_find_hinted_dependencies() {
+    let (sender_a, sender_b) = (contract_address!("0x1"), contract_address!("0x2"));
+    let hot_key = (contract_address!("0x10"), storage_key!("0x100"));
+    let sender_hints =
+        |sender| TxDependencyHints { nonce_writes: HashSet::from([sender]), ..Default::default() };
+
+    let hints = [
+        sender_hints(sender_a),
+        sender_hints(sender_b),
+        // Reads a cell no preceding transaction writes.
+        TxDependencyHints { storage_reads: HashSet::from([hot_key]), ..Default::default() },
+        TxDependencyHints { storage_writes: HashSet::from([hot_key]), ..sender_hints(sender_a) },
+        sender_hints(sender_b),
+        TxDependencyHints { storage_reads: HashSet::from([hot_key]), ..Default::default() },
+    ];
+    assert_eq!(find_hinted_dependencies(&hints), vec![None, None, None, Some(0), Some(1), Some(3)]);
+}
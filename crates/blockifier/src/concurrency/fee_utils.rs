@@ -7,7 +7,7 @@ use starknet_types_core::felt::Felt;
 
 use crate::context::{BlockContext, TransactionContext};
 use crate::execution::call_info::CallInfo;
-use crate::fee::fee_utils::get_sequencer_balance_keys;
+use crate::fee::fee_utils::get_address_balance_keys;
 use crate::state::cached_state::{ContractClassMapping, StateMaps};
 use crate::state::state_api::UpdatableState;
 use crate::transaction::objects::TransactionExecutionInfo;
@@ -21,49 +21,78 @@ mod test;
 // [account_balance, 0, sequencer_balance, 0]
 pub(crate) const STORAGE_READ_SEQUENCER_BALANCE_INDICES: (usize, usize) = (2, 3);
 
-// Completes the fee transfer flow if needed (if the transfer was made in concurrent mode).
+// Completes the fee transfer flow if needed (if the transfers were made in concurrent mode).
 pub fn complete_fee_transfer_flow(
     tx_context: &TransactionContext,
     tx_execution_info: &mut TransactionExecutionInfo,
     state: &mut impl UpdatableState,
 ) {
-    if tx_context.is_sequencer_the_sender() {
-        // When the sequencer is the sender, we use the sequential (full) fee transfer.
+    if tx_execution_info.fee_transfer_call_info.is_none() {
+        // Assumes we set the charge fee flag to the transaction enforce fee value.
+        let charge_fee = tx_context.tx_info.enforce_fee();
+        assert!(
+            !charge_fee,
+            "Transaction with no fee transfer info must not enforce a fee charge."
+        );
         return;
     }
 
-    if let Some(fee_transfer_call_info) = tx_execution_info.fee_transfer_call_info.as_mut() {
-        let sequencer_balance = state
-        .get_fee_token_balance(
-            tx_context.block_context.block_info.sequencer_address,
-            tx_context.fee_token_address()
-        )
-        // TODO(barak, 01/07/2024): Consider propagating the error.
-        .unwrap_or_else(|error| {
-            panic!(
-                "Access to storage failed. Probably due to a bug in Papyrus. {error:?}: {error}"
-            )
-        });
+    let fee_token_address = tx_context.fee_token_address();
+    for (fee_transfer_call_info, recipient, amount) in
+        deferred_fee_transfers(tx_context, tx_execution_info)
+    {
+        let recipient_balance = state
+            .get_fee_token_balance(recipient, fee_token_address)
+            // TODO(barak, 01/07/2024): Consider propagating the error.
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Access to storage failed. Probably due to a bug in Papyrus. {error:?}: \
+                     {error}"
+                )
+            });
 
         // Fix the transfer call info.
-        fill_sequencer_balance_reads(fee_transfer_call_info, sequencer_balance);
+        fill_sequencer_balance_reads(fee_transfer_call_info, recipient_balance);
         // Update the balance.
-        add_fee_to_sequencer_balance(
-            tx_context.fee_token_address(),
-            state,
-            tx_execution_info.receipt.fee,
-            &tx_context.block_context,
-            sequencer_balance,
-        );
-    } else {
-        // Assumes we set the charge fee flag to the transaction enforce fee value.
-        let charge_fee = tx_context.tx_info.enforce_fee();
-        assert!(!charge_fee, "Transaction with no fee transfer info must not enforce a fee charge.")
+        add_fee_to_balance(fee_token_address, state, amount, recipient, recipient_balance);
+    }
+}
+
+/// The fee transfers of the given transaction which were executed in concurrent mode, without
+/// their recipient balance, as (call info, recipient, amount), in execution order. These are the
+/// transfers to the fee distribution recipients and to the sequencer, unless the recipient pays the
+/// fee; such a transfer is a sequential (full) one.
+pub fn deferred_fee_transfers<'a>(
+    tx_context: &TransactionContext,
+    tx_execution_info: &'a mut TransactionExecutionInfo,
+) -> Vec<(&'a mut CallInfo, ContractAddress, Fee)> {
+    let sequencer_fee = tx_execution_info.receipt.sequencer_fee();
+    let fee_split = tx_execution_info.receipt.fee_split;
+    let mut transfers = vec![];
+    if let Some(fee_split) = fee_split {
+        let share_transfers =
+            tx_context.block_context.chain_info.fee_distribution.share_transfers(&fee_split);
+        for ((recipient, amount), call_info) in share_transfers
+            .into_iter()
+            .zip(tx_execution_info.fee_distribution_call_infos.iter_mut())
+        {
+            if recipient != tx_context.fee_payer_address() {
+                transfers.push((call_info, recipient, amount));
+            }
+        }
     }
+    if let Some(fee_transfer_call_info) = tx_execution_info.fee_transfer_call_info.as_mut() {
+        if !tx_context.is_sequencer_the_sender() {
+            let sequencer_address = tx_context.block_context.block_info.sequencer_address;
+            transfers.push((fee_transfer_call_info, sequencer_address, sequencer_fee));
+        }
+    }
+    transfers
 }
 
-// Fixes the fee transfer call info to have the correct sequencer balance. In concurrency mode, the
-// fee transfer is executed with a false (constant) sequencer balance. This affects the call info.
+// Fixes the fee transfer call info to have the correct recipient balance, e.g., of the sequencer.
+// In concurrency mode, the fee transfer is executed with a false (constant) recipient balance. This
+// affects the call info.
 pub fn fill_sequencer_balance_reads(
     fee_transfer_call_info: &mut CallInfo,
     sequencer_balance: (Felt, Felt),
@@ -84,7 +113,7 @@ pub fn fill_sequencer_balance_reads(
 
     let (low_index, high_index) = STORAGE_READ_SEQUENCER_BALANCE_INDICES;
     for index in [low_index, high_index] {
-        assert_eq!(storage_read_values[index], Felt::ZERO, "Sequencer balance should be zero");
+        assert_eq!(storage_read_values[index], Felt::ZERO, "Recipient balance should be zero");
     }
     let (low, high) = sequencer_balance;
     storage_read_values[low_index] = low;
@@ -98,23 +127,31 @@ pub fn add_fee_to_sequencer_balance(
     block_context: &BlockContext,
     sequencer_balance: (Felt, Felt),
 ) {
-    let (low, high) = sequencer_balance;
-    let sequencer_balance_low_as_u128 =
-        low.to_u128().expect("sequencer balance low should be u128");
-    let sequencer_balance_high_as_u128 =
-        high.to_u128().expect("sequencer balance high should be u128");
-    let (new_value_low, carry) = sequencer_balance_low_as_u128.overflowing_add(actual_fee.0);
-    let (new_value_high, carry) = sequencer_balance_high_as_u128.overflowing_add(carry.into());
+    let sequencer_address = block_context.block_info.sequencer_address;
+    add_fee_to_balance(fee_token_address, state, actual_fee, sequencer_address, sequencer_balance);
+}
+
+pub fn add_fee_to_balance(
+    fee_token_address: ContractAddress,
+    state: &mut impl UpdatableState,
+    fee: Fee,
+    recipient: ContractAddress,
+    recipient_balance: (Felt, Felt),
+) {
+    let (low, high) = recipient_balance;
+    let balance_low_as_u128 = low.to_u128().expect("recipient balance low should be u128");
+    let balance_high_as_u128 = high.to_u128().expect("recipient balance high should be u128");
+    let (new_value_low, carry) = balance_low_as_u128.overflowing_add(fee.0);
+    let (new_value_high, carry) = balance_high_as_u128.overflowing_add(carry.into());
     assert!(
         !carry,
-        "The sequencer balance overflowed when adding the fee. This should not happen."
+        "The recipient balance overflowed when adding the fee. This should not happen."
     );
-    let (sequencer_balance_key_low, sequencer_balance_key_high) =
-        get_sequencer_balance_keys(block_context);
+    let (balance_key_low, balance_key_high) = get_address_balance_keys(recipient);
     let writes = StateMaps {
         storage: HashMap::from([
-            ((fee_token_address, sequencer_balance_key_low), Felt::from(new_value_low)),
-            ((fee_token_address, sequencer_balance_key_high), Felt::from(new_value_high)),
+            ((fee_token_address, balance_key_low), Felt::from(new_value_low)),
+            ((fee_token_address, balance_key_high), Felt::from(new_value_high)),
         ]),
         ..StateMaps::default()
     };
@@ -40,6 +40,7 @@ impl<'a> TransactionCommitter<'a> {
         }
         *status = TransactionStatus::Committed;
         *self.commit_index_guard += 1;
+        self.scheduler.n_committed_txs.store(*self.commit_index_guard, Ordering::Release);
         if *self.commit_index_guard == self.scheduler.chunk_size {
             self.scheduler.done_marker.store(true, Ordering::Release);
         }
@@ -62,6 +63,16 @@ pub struct Scheduler {
     validation_index: AtomicUsize,
     // The index of the next transaction to commit.
     commit_index: Mutex<usize>,
+    // The value of the commit index, readable without taking its lock.
+    n_committed_txs: AtomicUsize,
+    // If set, transactions are executed at most this many transactions ahead of the commit index
+    // (approximately, as workers may race on the bound); see `ValidationStrategy`.
+    execution_window: Option<usize>,
+    // For each transaction, the preceding transaction it is hinted to depend on (empty if there
+    // are no hints); see `TxDependencyHints`.
+    hinted_dependencies: Box<[Option<TxIndex>]>,
+    // For each transaction, the transactions suspended until its execution finishes.
+    dependents: Box<[Mutex<Vec<TxIndex>>]>,
     chunk_size: usize,
     // TODO(Avi, 15/05/2024): Consider using RwLock instead of Mutex.
     tx_statuses: Box<[Mutex<TransactionStatus>]>,
@@ -76,6 +87,10 @@ impl Scheduler {
             execution_index: AtomicUsize::new(0),
             validation_index: AtomicUsize::new(chunk_size),
             commit_index: Mutex::new(0),
+            n_committed_txs: AtomicUsize::new(0),
+            execution_window: None,
+            hinted_dependencies: Box::default(),
+            dependents: Box::default(),
             chunk_size,
             tx_statuses: std::iter::repeat_with(|| Mutex::new(TransactionStatus::ReadyToExecute))
                 .take(chunk_size)
@@ -84,6 +99,26 @@ impl Scheduler {
         }
     }
 
+    /// Bounds the executions to the given number of transactions ahead of the commit index.
+    pub fn with_execution_window(mut self, execution_window: usize) -> Self {
+        self.execution_window = Some(execution_window);
+        self
+    }
+
+    /// Delays the execution of each transaction until the execution of the transaction it is
+    /// hinted to depend on, if any, has finished.
+    pub fn with_hinted_dependencies(mut self, hinted_dependencies: Vec<Option<TxIndex>>) -> Self {
+        assert_eq!(
+            hinted_dependencies.len(),
+            self.chunk_size,
+            "The number of hinted dependencies must equal the chunk size."
+        );
+        self.hinted_dependencies = hinted_dependencies.into_boxed_slice();
+        self.dependents =
+            std::iter::repeat_with(|| Mutex::new(Vec::new())).take(self.chunk_size).collect();
+        self
+    }
+
     pub fn next_task(&self) -> Task {
         if self.done() {
             return Task::Done;
@@ -106,6 +141,10 @@ impl Scheduler {
             return Task::ExecutionTask(tx_index);
         }
 
+        if self.is_execution_window_full(self.execution_index.load(Ordering::Acquire)) {
+            return Task::NoTaskAvailable;
+        }
+
         Task::AskForTask
     }
 
@@ -114,6 +153,7 @@ impl Scheduler {
     /// already scheduled.
     pub fn finish_execution(&self, tx_index: TxIndex) {
         self.set_executed_status(tx_index);
+        self.resume_dependents(tx_index);
         self.decrease_validation_index(tx_index);
     }
 
@@ -191,6 +231,36 @@ impl Scheduler {
         *status = TransactionStatus::ReadyToExecute;
     }
 
+    /// Suspends the given transaction, just incarnated, if the transaction it is hinted to depend
+    /// on has not been executed yet. Returns whether the transaction was suspended.
+    fn try_suspend_on_hinted_dependency(&self, tx_index: TxIndex) -> bool {
+        let Some(Some(dependency)) = self.hinted_dependencies.get(tx_index).copied() else {
+            return false;
+        };
+        // The dependency status lock is held until the transaction is registered as its
+        // dependent, so that the end of its execution cannot be missed.
+        let dependency_status = self.lock_tx_status(dependency);
+        if matches!(*dependency_status, TransactionStatus::Executed | TransactionStatus::Committed)
+        {
+            return false;
+        }
+        *self.lock_tx_status(tx_index) = TransactionStatus::ReadyToExecute;
+        lock_mutex_in_array(&self.dependents, dependency).push(tx_index);
+        true
+    }
+
+    /// Makes the transactions suspended on the given (executed) transaction available for
+    /// execution.
+    fn resume_dependents(&self, tx_index: TxIndex) {
+        if tx_index >= self.dependents.len() {
+            return;
+        }
+        let dependents = std::mem::take(&mut *lock_mutex_in_array(&self.dependents, tx_index));
+        if let Some(min_dependent) = dependents.into_iter().min() {
+            self.execution_index.fetch_min(min_dependent, Ordering::SeqCst);
+        }
+    }
+
     fn decrease_validation_index(&self, target_index: TxIndex) {
         self.validation_index.fetch_min(target_index, Ordering::SeqCst);
     }
@@ -224,16 +294,24 @@ impl Scheduler {
 
     fn next_version_to_execute(&self) -> Option<TxIndex> {
         let index_to_execute = self.execution_index.load(Ordering::Acquire);
-        if index_to_execute >= self.chunk_size {
+        if index_to_execute >= self.chunk_size || self.is_execution_window_full(index_to_execute) {
             return None;
         }
         let index_to_execute = self.execution_index.fetch_add(1, Ordering::SeqCst);
-        if self.try_incarnate(index_to_execute) {
+        if self.try_incarnate(index_to_execute)
+            && !self.try_suspend_on_hinted_dependency(index_to_execute)
+        {
             return Some(index_to_execute);
         }
         None
     }
 
+    fn is_execution_window_full(&self, index_to_execute: TxIndex) -> bool {
+        self.execution_window.is_some_and(|execution_window| {
+            index_to_execute >= self.n_committed_txs.load(Ordering::Acquire) + execution_window
+        })
+    }
+
     /// Returns the done marker.
     fn done(&self) -> bool {
         self.done_marker.load(Ordering::Acquire)
@@ -84,6 +84,34 @@ fn test_next_task(
     assert_eq!(next_task, expected_next_task);
 }
 
+#[rstest]
+fn test_next_task_with_execution_window() {
+    let scheduler = Scheduler::new(DEFAULT_CHUNK_SIZE).with_execution_window(1);
+    assert_eq!(scheduler.next_task(), Task::ExecutionTask(0));
+    scheduler.finish_execution(0);
+    assert_eq!(scheduler.next_task(), Task::ValidationTask(0));
+    // The next transaction is not executed before the previous one is committed.
+    assert_eq!(scheduler.next_task(), Task::NoTaskAvailable);
+
+    assert_eq!(scheduler.try_enter_commit_phase().unwrap().try_commit(), Some(0));
+    assert_eq!(scheduler.next_task(), Task::ExecutionTask(1));
+}
+
+#[rstest]
+fn test_next_task_with_hinted_dependencies() {
+    let scheduler = Scheduler::new(3).with_hinted_dependencies(vec![None, Some(0), None]);
+    assert_eq!(scheduler.next_task(), Task::ExecutionTask(0));
+    // The second transaction is suspended until the execution of the first one finishes.
+    assert_eq!(scheduler.next_task(), Task::AskForTask);
+    assert_eq!(*scheduler.lock_tx_status(1), TransactionStatus::ReadyToExecute);
+    assert_eq!(scheduler.next_task(), Task::ExecutionTask(2));
+
+    scheduler.finish_execution(0);
+    assert_eq!(scheduler.execution_index.load(Ordering::Acquire), 1);
+    assert_eq!(scheduler.next_task(), Task::ValidationTask(0));
+    assert_eq!(scheduler.next_task(), Task::ExecutionTask(1));
+}
+
 #[rstest]
 #[case::happy_flow(0, TransactionStatus::Executed, false)]
 #[case::happy_flow_with_halt(0, TransactionStatus::Executed, true)]
@@ -8,7 +8,7 @@ use starknet_types_core::felt::Felt;
 use crate::concurrency::versioned_storage::VersionedStorage;
 use crate::concurrency::TxIndex;
 use crate::execution::contract_class::RunnableCompiledClass;
-use crate::state::cached_state::{ContractClassMapping, StateMaps};
+use crate::state::cached_state::{ContractClassMapping, StateMaps, StorageEntry};
 use crate::state::errors::StateError;
 use crate::state::state_api::{StateReader, StateResult, UpdatableState};
 
@@ -135,6 +135,23 @@ impl<S: StateReader> VersionedState<S> {
         true
     }
 
+    /// Returns the storage cells in the read set whose values were changed by preceding
+    /// transactions since they were read.
+    fn conflicting_storage_keys(&self, tx_index: TxIndex, reads: &StateMaps) -> Vec<StorageEntry> {
+        if tx_index == 0 {
+            return vec![];
+        }
+        reads
+            .storage
+            .iter()
+            .filter(|(storage_entry, expected_value)| {
+                &self.storage.read(tx_index - 1, **storage_entry).expect(READ_ERR)
+                    != *expected_value
+            })
+            .map(|(storage_entry, _)| *storage_entry)
+            .collect()
+    }
+
     fn apply_writes(
         &mut self,
         tx_index: TxIndex,
@@ -266,6 +283,10 @@ impl<S: StateReader> VersionedStateProxy<S> {
         self.state().validate_reads(self.tx_index, reads)
     }
 
+    pub fn conflicting_storage_keys(&self, reads: &StateMaps) -> Vec<StorageEntry> {
+        self.state().conflicting_storage_keys(self.tx_index, reads)
+    }
+
     pub fn delete_writes(&self, writes: &StateMaps, class_hash_to_class: &ContractClassMapping) {
         self.state().delete_writes(self.tx_index, writes, class_hash_to_class);
     }
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 use std::thread;
 use std::time::Duration;
 
@@ -9,6 +9,8 @@ use starknet_api::core::ClassHash;
 use super::versioned_state::VersionedState;
 use crate::blockifier::transaction_executor::TransactionExecutorError;
 use crate::bouncer::Bouncer;
+use crate::concurrency::conflict_stats::ConflictStats;
+use crate::concurrency::dependency_hints::{find_hinted_dependencies, TxDependencyHints};
 use crate::concurrency::fee_utils::complete_fee_transfer_flow;
 use crate::concurrency::scheduler::{Scheduler, Task};
 use crate::concurrency::utils::lock_mutex_in_array;
@@ -35,6 +37,10 @@ pub struct ExecutionTaskOutput {
     pub contract_classes: ContractClassMapping,
     pub visited_pcs: HashMap<ClassHash, HashSet<usize>>,
     pub result: TransactionExecutionResult<TransactionExecutionInfo>,
+    // The number of updates of the block call counter when the execution started; if the counter
+    // was updated since, the execution may have seen call quota counts other than those of the
+    // transactions preceding it.
+    pub n_block_call_updates: usize,
 }
 
 pub struct WorkerExecutor<'a, S: StateReader> {
@@ -44,6 +50,7 @@ pub struct WorkerExecutor<'a, S: StateReader> {
     pub execution_outputs: Box<[Mutex<Option<ExecutionTaskOutput>>]>,
     pub block_context: &'a BlockContext,
     pub bouncer: Mutex<&'a mut Bouncer>,
+    pub conflict_stats: Mutex<ConflictStats>,
 }
 impl<'a, S: StateReader> WorkerExecutor<'a, S> {
     pub fn new(
@@ -56,7 +63,15 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
         let execution_outputs =
             std::iter::repeat_with(|| Mutex::new(None)).take(chunk.len()).collect();
 
-        WorkerExecutor { scheduler, state, chunk, execution_outputs, block_context, bouncer }
+        WorkerExecutor {
+            scheduler,
+            state,
+            chunk,
+            execution_outputs,
+            block_context,
+            bouncer,
+            conflict_stats: Mutex::new(ConflictStats::default()),
+        }
     }
 
     // TODO(barak, 01/08/2024): Remove the `new` method or move it to test utils.
@@ -79,9 +94,26 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
             execution_outputs,
             block_context,
             bouncer,
+            conflict_stats: Mutex::new(ConflictStats::default()),
         }
     }
 
+    /// Bounds the executions to the given number of transactions ahead of the commit index; see
+    /// `ValidationStrategy::Conservative`.
+    pub fn with_execution_window(mut self, execution_window: usize) -> Self {
+        self.scheduler =
+            std::mem::take(&mut self.scheduler).with_execution_window(execution_window);
+        self
+    }
+
+    /// Delays the execution of transactions whose dependency hints conflict with those of a
+    /// preceding transaction in the chunk until that transaction is executed.
+    pub fn with_dependency_hints(mut self, dependency_hints: &[TxDependencyHints]) -> Self {
+        self.scheduler = std::mem::take(&mut self.scheduler)
+            .with_hinted_dependencies(find_hinted_dependencies(dependency_hints));
+        self
+    }
+
     pub fn run(&self) {
         let mut task = Task::AskForTask;
         loop {
@@ -121,10 +153,12 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
     }
 
     fn execute_tx(&self, tx_index: TxIndex) {
+        self.conflict_stats().n_executions += 1;
         let mut tx_versioned_state = self.state.pin_version(tx_index);
         let tx = &self.chunk[tx_index];
         let mut transactional_state =
             TransactionalState::create_transactional(&mut tx_versioned_state);
+        let n_block_call_updates = self.block_context.block_call_counter.n_updates();
         let concurrency_mode = true;
         let execution_result =
             tx.execute_raw(&mut transactional_state, self.block_context, concurrency_mode);
@@ -144,6 +178,7 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
                     contract_classes,
                     visited_pcs,
                     result: execution_result,
+                    n_block_call_updates,
                 }
             }
             Err(_) => ExecutionTaskOutput {
@@ -153,6 +188,7 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
                 contract_classes: HashMap::default(),
                 visited_pcs: HashMap::default(),
                 result: execution_result,
+                n_block_call_updates,
             },
         };
         let mut execution_output = lock_mutex_in_array(&self.execution_outputs, tx_index);
@@ -168,6 +204,11 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
 
         let aborted = !reads_valid && self.scheduler.try_validation_abort(tx_index);
         if aborted {
+            let conflicting_storage_keys = tx_versioned_state.conflicting_storage_keys(reads);
+            let mut conflict_stats = self.conflict_stats();
+            conflict_stats.n_validation_aborts += 1;
+            conflict_stats.record_conflict(conflicting_storage_keys);
+            drop(conflict_stats);
             tx_versioned_state
                 .delete_writes(&execution_output.writes, &execution_output.contract_classes);
             self.scheduler.finish_abort(tx_index)
@@ -177,17 +218,22 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
     }
 
     /// Commits a transaction. The commit process is as follows:
-    /// 1) Validate the read set.
+    /// 1) Validate the read set, and that the block call counter was not updated since the
+    ///    execution.
     ///     * If validation failed, delete the transaction writes and (re-)execute it.
     ///     * Else (validation succeeded), no need to re-execute.
     /// 2) Execution is final.
     ///     * If execution succeeded, ask the bouncer if there is room for the transaction in the
     ///       block.
-    ///         - If there is room, fix the call info, update the sequencer balance and commit the
-    ///           transaction.
+    ///         - If there is room, add its calls to the block call counter, fix the call info,
+    ///           update the sequencer balance and commit the transaction.
     ///         - Else (no room), do not commit. The block should be closed without the transaction.
     ///     * Else (execution failed), commit the transaction without fixing the call info or
     ///       updating the sequencer balance.
+    fn conflict_stats(&self) -> MutexGuard<'_, ConflictStats> {
+        self.conflict_stats.lock().expect("Conflict stats lock failed.")
+    }
+
     fn commit_tx(&self, tx_index: TxIndex) -> bool {
         let execution_output = lock_mutex_in_array(&self.execution_outputs, tx_index);
         let execution_output_ref = execution_output.as_ref().expect(EXECUTION_OUTPUTS_UNWRAP_ERROR);
@@ -195,9 +241,17 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
 
         let mut tx_versioned_state = self.state.pin_version(tx_index);
         let reads_valid = tx_versioned_state.validate_reads(reads);
+        // The call quota counts are final once the preceding transactions are committed.
+        let block_call_counts_valid = execution_output_ref.n_block_call_updates
+            == self.block_context.block_call_counter.n_updates();
 
         // First, re-validate the transaction.
-        if !reads_valid {
+        if !reads_valid || !block_call_counts_valid {
+            let conflicting_storage_keys = tx_versioned_state.conflicting_storage_keys(reads);
+            let mut conflict_stats = self.conflict_stats();
+            conflict_stats.n_commit_reexecutions += 1;
+            conflict_stats.record_conflict(conflicting_storage_keys);
+            drop(conflict_stats);
             // Revalidate failed: re-execute the transaction.
             tx_versioned_state.delete_writes(
                 &execution_output_ref.writes,
@@ -241,6 +295,7 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
                 &tx_state_changes_keys,
                 &tx_execution_info.summarize(&self.block_context.versioned_constants),
                 &tx_execution_info.receipt.resources,
+                (&self.chunk[tx_index]).into(),
             );
             if let Err(error) = bouncer_result {
                 match error {
@@ -251,6 +306,7 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
                     }
                 }
             }
+            self.block_context.block_call_counter.commit(&tx_execution_info.quota_call_counts);
             complete_fee_transfer_flow(&tx_context, tx_execution_info, &mut tx_versioned_state);
             // Optimization: changing the sequencer balance storage cell does not trigger
             // (re-)validation of the next transactions.
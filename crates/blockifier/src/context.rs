@@ -1,19 +1,40 @@
+use std::cmp::min;
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockInfo, BlockNumber, BlockTimestamp, FeeType, GasPriceVector};
+use starknet_api::block::{
+    BlockInfo,
+    BlockNumber,
+    BlockTimestamp,
+    FeeType,
+    GasPriceVector,
+    StarknetVersion,
+};
 use starknet_api::core::{ChainId, ContractAddress};
-use starknet_api::execution_resources::GasAmount;
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::execution_resources::{GasAmount, GasVector};
 use starknet_api::transaction::fields::{
     AllResourceBounds,
+    Fee,
     GasVectorComputationMode,
     ValidResourceBounds,
 };
+use starknet_api::transaction::TransactionVersions;
+use starknet_types_core::felt::Felt;
 
+use crate::blockifier::block::{BlockRandomness, SharedBlockHashProvider};
 use crate::bouncer::BouncerConfig;
 use crate::execution::call_info::CallInfo;
+use crate::execution::cancellation::CancellationToken;
+use crate::execution::entry_point::ExecutionProfiler;
+use crate::execution::execution_quotas::{BlockCallCounter, TransactionCallCounter};
+use crate::execution::execution_trace::ExecutionTracer;
+use crate::execution::native_contracts::NativeContractRegistry;
+use crate::execution::syscall_hooks::SharedSyscallHook;
+use crate::fee::receipt::FeeSplit;
 use crate::transaction::objects::{
     CurrentTransactionInfo,
     HasRelatedFeeType,
@@ -26,14 +47,35 @@ use crate::versioned_constants::VersionedConstants;
 pub struct TransactionContext {
     pub block_context: BlockContext,
     pub tx_info: TransactionInfo,
+    // Counts the calls of the transaction subject to call quotas; added to the block call counter
+    // once the transaction is committed.
+    pub(crate) call_counter: TransactionCallCounter,
 }
 
 impl TransactionContext {
     pub fn fee_token_address(&self) -> ContractAddress {
         self.block_context.chain_info.fee_token_address(&self.tx_info.fee_type())
     }
+    /// Whether the sequencer pays the fee of the transaction, transferring it to itself.
     pub fn is_sequencer_the_sender(&self) -> bool {
-        self.tx_info.sender_address() == self.block_context.block_info.sequencer_address
+        self.fee_payer_address() == self.block_context.block_info.sequencer_address
+    }
+    /// The paymaster sponsoring the transaction: the first element of its paymaster data, if
+    /// paymasters are enabled on the chain. [None] if the data does not start with an address;
+    /// such transactions fail pre-validation.
+    pub fn paymaster_address(&self) -> Option<ContractAddress> {
+        if !self.block_context.chain_info.paymaster.enabled {
+            return None;
+        }
+        let TransactionInfo::Current(context) = &self.tx_info else {
+            return None;
+        };
+        let paymaster = context.paymaster_data.0.first()?;
+        ContractAddress::try_from(*paymaster).ok()
+    }
+    /// The account charged for the transaction fee: its paymaster, if sponsored, or its sender.
+    pub fn fee_payer_address(&self) -> ContractAddress {
+        self.paymaster_address().unwrap_or_else(|| self.tx_info.sender_address())
     }
     pub fn get_gas_vector_computation_mode(&self) -> GasVectorComputationMode {
         self.tx_info.gas_mode()
@@ -41,6 +83,21 @@ impl TransactionContext {
     pub fn get_gas_prices(&self) -> &GasPriceVector {
         self.block_context.block_info.gas_prices.gas_price_vector(&self.tx_info.fee_type())
     }
+    /// The data availability mode of the transaction's state diff; a block-wide mode takes
+    /// precedence over the transaction's own.
+    pub fn state_diff_da_mode(&self) -> DataAvailabilityMode {
+        self.block_context.state_diff_da_mode.unwrap_or_else(|| self.tx_info.state_diff_da_mode())
+    }
+
+    /// Returns the part of the fee paid as a tip, for the given gas vector; see
+    /// [TransactionInfo::get_tip_by_gas_vector]. No tip is charged before the Starknet version
+    /// that introduced tips.
+    pub fn get_tip_by_gas_vector(&self, gas_vector: GasVector) -> Fee {
+        if !self.block_context.versioned_constants.charge_tip {
+            return Fee(0);
+        }
+        self.tx_info.get_tip_by_gas_vector(&self.block_context.block_info, gas_vector)
+    }
 
     /// Returns the initial Sierra gas of the transaction.
     /// This value is used to limit the transaction's run.
@@ -94,6 +151,35 @@ pub struct BlockContext {
     pub(crate) chain_info: ChainInfo,
     pub(crate) versioned_constants: VersionedConstants,
     pub(crate) bouncer_config: BouncerConfig,
+    // If set, overrides the state diff data availability mode of all transactions in the block.
+    pub(crate) state_diff_da_mode: Option<DataAvailabilityMode>,
+    // If set, the randomness seed of the block is written to a system contract at block start.
+    pub(crate) randomness: Option<BlockRandomness>,
+    // If set, the block metadata is written to the system contract defined by the versioned
+    // constants at block start.
+    pub(crate) starknet_version: Option<StarknetVersion>,
+    // If set, the execution of the block's transactions is traced step by step into this tracer.
+    pub(crate) execution_tracer: Option<ExecutionTracer>,
+    // If set, the resources of the entry points executed in the block are aggregated into this
+    // profiler.
+    pub(crate) execution_profiler: Option<ExecutionProfiler>,
+    // If set, the executions of the block's transactions stop once this token is cancelled.
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    // If set, resolves the block hashes missing from the storage of the block hash contract.
+    pub(crate) block_hash_provider: Option<SharedBlockHashProvider>,
+    // Called around every syscall executed in the block, in order.
+    pub(crate) syscall_hooks: Vec<SharedSyscallHook>,
+    // If set, calls to the contracts of this registry run natively instead of loading a class.
+    pub(crate) native_contract_registry: Option<Arc<NativeContractRegistry>>,
+    // Counts the calls of the committed transactions of the block subject to the call quotas of
+    // the versioned constants; shared by the clones of the context.
+    pub(crate) block_call_counter: BlockCallCounter,
+    // If set, the read set of each transaction is recorded into its execution info.
+    pub(crate) record_read_sets: bool,
+    // If set, L1 handler transactions must have paid at least their actual fee on L1.
+    pub(crate) enforce_l1_handler_fee: bool,
+    // If set, classes compiled to Cairo native are executed by the Cairo VM.
+    pub(crate) force_vm_execution: bool,
 }
 
 impl BlockContext {
@@ -103,7 +189,191 @@ impl BlockContext {
         versioned_constants: VersionedConstants,
         bouncer_config: BouncerConfig,
     ) -> Self {
-        BlockContext { block_info, chain_info, versioned_constants, bouncer_config }
+        BlockContext {
+            block_info,
+            chain_info,
+            versioned_constants,
+            bouncer_config,
+            state_diff_da_mode: None,
+            randomness: None,
+            starknet_version: None,
+            execution_tracer: None,
+            execution_profiler: None,
+            cancellation_token: None,
+            block_hash_provider: None,
+            syscall_hooks: vec![],
+            native_contract_registry: None,
+            block_call_counter: BlockCallCounter::default(),
+            record_read_sets: false,
+            enforce_l1_handler_fee: false,
+            force_vm_execution: false,
+        }
+    }
+
+    /// Publishes the state diffs of all transactions in the block in the given data availability
+    /// mode, regardless of the modes the transactions chose.
+    pub fn with_state_diff_da_mode(mut self, state_diff_da_mode: DataAvailabilityMode) -> Self {
+        self.state_diff_da_mode = Some(state_diff_da_mode);
+        self
+    }
+
+    pub fn state_diff_da_mode(&self) -> Option<DataAvailabilityMode> {
+        self.state_diff_da_mode
+    }
+
+    /// Exposes a per-block randomness seed to contracts through the storage of a system contract;
+    /// see `blockifier::block::write_randomness_seed`.
+    pub fn with_randomness(mut self, randomness: BlockRandomness) -> Self {
+        self.randomness = Some(randomness);
+        self
+    }
+
+    pub fn randomness(&self) -> Option<BlockRandomness> {
+        self.randomness
+    }
+
+    /// Sets the Starknet version of the block, which must match its versioned constants. Versions
+    /// that define a block metadata system contract expose the block metadata to contracts through
+    /// it; see `blockifier::block::write_block_metadata`.
+    pub fn with_starknet_version(mut self, starknet_version: StarknetVersion) -> Self {
+        self.starknet_version = Some(starknet_version);
+        self
+    }
+
+    pub fn starknet_version(&self) -> Option<StarknetVersion> {
+        self.starknet_version
+    }
+
+    /// Records the VM steps and syscalls of every entry point executed under this context into the
+    /// given tracer; see `ExecutableTransaction::execute_traced`.
+    pub fn with_execution_tracer(mut self, execution_tracer: ExecutionTracer) -> Self {
+        self.execution_tracer = Some(execution_tracer);
+        self
+    }
+
+    pub fn execution_tracer(&self) -> Option<&ExecutionTracer> {
+        self.execution_tracer.as_ref()
+    }
+
+    /// Records the values each transaction executed under this context reads from the state into
+    /// its execution info, e.g., for provers to fetch the witnesses of a dry run; see
+    /// `TransactionExecutionInfo::read_set`.
+    pub fn with_read_set_recording(mut self) -> Self {
+        self.record_read_sets = true;
+        self
+    }
+
+    pub fn records_read_sets(&self) -> bool {
+        self.record_read_sets
+    }
+
+    /// Fails L1 handler transactions whose fee paid on L1 is lower than their actual fee. By
+    /// default, any positive paid fee is accepted.
+    pub fn with_l1_handler_fee_enforcement(mut self) -> Self {
+        self.enforce_l1_handler_fee = true;
+        self
+    }
+
+    pub fn enforces_l1_handler_fee(&self) -> bool {
+        self.enforce_l1_handler_fee
+    }
+
+    /// Executes the classes compiled to Cairo native by the Cairo VM, running their CASM; e.g., to
+    /// compare the two backends.
+    pub fn with_vm_execution_forced(mut self) -> Self {
+        self.force_vm_execution = true;
+        self
+    }
+
+    pub fn forces_vm_execution(&self) -> bool {
+        self.force_vm_execution
+    }
+
+    /// Returns a context for executing the transactions of this block by the Cairo VM alongside
+    /// their actual execution, without affecting it. The tracer, the profiler and the syscall hooks
+    /// of this context are not set, and the calls subject to call quotas are counted apart from
+    /// the block.
+    pub(crate) fn to_shadow_vm_context(&self) -> Self {
+        BlockContext {
+            execution_tracer: None,
+            execution_profiler: None,
+            syscall_hooks: vec![],
+            block_call_counter: self.block_call_counter.detached(),
+            ..self.clone()
+        }
+        .with_vm_execution_forced()
+    }
+
+    /// Aggregates the steps, builtins, syscalls and gas of every entry point executed under this
+    /// context into the given profiler, per class hash and selector.
+    pub fn with_execution_profiler(mut self, execution_profiler: ExecutionProfiler) -> Self {
+        self.execution_profiler = Some(execution_profiler);
+        self
+    }
+
+    pub fn execution_profiler(&self) -> Option<&ExecutionProfiler> {
+        self.execution_profiler.as_ref()
+    }
+
+    /// Stops the executions under this context once the given token is cancelled, e.g., when the
+    /// proposal of the block is aborted.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// Resolves the block hashes requested through the `get_block_hash` syscall which are missing
+    /// from the storage of the block hash contract through the given provider, e.g., from storage
+    /// or RPC.
+    pub fn with_block_hash_provider(
+        mut self,
+        block_hash_provider: SharedBlockHashProvider,
+    ) -> Self {
+        self.block_hash_provider = Some(block_hash_provider);
+        self
+    }
+
+    pub fn block_hash_provider(&self) -> Option<&SharedBlockHashProvider> {
+        self.block_hash_provider.as_ref()
+    }
+
+    /// Registers a hook called around every syscall executed under this context, after the
+    /// previously registered hooks; see `SyscallHook`.
+    pub fn with_syscall_hook(mut self, syscall_hook: SharedSyscallHook) -> Self {
+        self.syscall_hooks.push(syscall_hook);
+        self
+    }
+
+    pub fn syscall_hooks(&self) -> &[SharedSyscallHook] {
+        &self.syscall_hooks
+    }
+
+    /// Runs the calls to the addresses of the given registry natively, with the gas cost defined
+    /// by their native contracts; see `NativeContract`.
+    pub fn with_native_contract_registry(
+        mut self,
+        native_contract_registry: Arc<NativeContractRegistry>,
+    ) -> Self {
+        self.native_contract_registry = Some(native_contract_registry);
+        self
+    }
+
+    pub fn native_contract_registry(&self) -> Option<&NativeContractRegistry> {
+        self.native_contract_registry.as_deref()
+    }
+
+    /// The calls of the committed transactions of the block subject to call quotas; see
+    /// `ExecutionQuotas`.
+    pub fn block_call_counter(&self) -> &BlockCallCounter {
+        &self.block_call_counter
+    }
+
+    pub(crate) fn is_execution_cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled)
     }
 
     pub fn block_info(&self) -> &BlockInfo {
@@ -125,6 +395,7 @@ impl BlockContext {
         TransactionContext {
             block_context: self.clone(),
             tx_info: tx_info_creator.create_tx_info(),
+            call_counter: TransactionCallCounter::default(),
         }
     }
 
@@ -156,6 +427,22 @@ impl BlockContext {
 pub struct ChainInfo {
     pub chain_id: ChainId,
     pub fee_token_addresses: FeeTokenAddresses,
+    /// Fee tokens charged instead of the default fee token of their fee type.
+    #[serde(default)]
+    pub custom_fee_tokens: CustomFeeTokens,
+    /// Account transaction versions that are neither accepted nor executed on the chain.
+    #[serde(default)]
+    pub disabled_tx_versions: TransactionVersions,
+    /// Whether transactions run entirely under Sierra gas accounting: Cairo 0 classes may not be
+    /// executed, and transactions must bound all resources, including L2 gas.
+    #[serde(default)]
+    pub sierra_gas_only: bool,
+    /// How the fee of each transaction is split between the sequencer, a treasury and burning.
+    #[serde(default)]
+    pub fee_distribution: FeeDistribution,
+    /// Whether transactions may be sponsored by a paymaster, and its limits.
+    #[serde(default)]
+    pub paymaster: PaymasterConfig,
 }
 
 impl ChainInfo {
@@ -163,7 +450,17 @@ impl ChainInfo {
     // TransactionContext, which has both the chain_info (through BlockContext) and the tx_info.
     // That is, add to BlockContext with the signature `pub fn fee_token_address(&self)`.
     pub fn fee_token_address(&self, fee_type: &FeeType) -> ContractAddress {
-        self.fee_token_addresses.get_by_fee_type(fee_type)
+        match self.custom_fee_tokens.get_by_fee_type(fee_type) {
+            Some(custom_fee_token) => custom_fee_token.address,
+            None => self.fee_token_addresses.get_by_fee_type(fee_type),
+        }
+    }
+
+    /// The number of decimals of the fee token charged for the given fee type.
+    pub fn fee_token_decimals(&self, fee_type: &FeeType) -> u8 {
+        self.custom_fee_tokens
+            .get_by_fee_type(fee_type)
+            .map_or(DEFAULT_FEE_TOKEN_DECIMALS, |custom_fee_token| custom_fee_token.decimals)
     }
 }
 
@@ -172,22 +469,52 @@ impl Default for ChainInfo {
         ChainInfo {
             chain_id: ChainId::Other("0x0".to_string()),
             fee_token_addresses: FeeTokenAddresses::default(),
+            custom_fee_tokens: CustomFeeTokens::default(),
+            disabled_tx_versions: TransactionVersions::default(),
+            sierra_gas_only: false,
+            fee_distribution: FeeDistribution::default(),
+            paymaster: PaymasterConfig::default(),
         }
     }
 }
 
 impl SerializeConfig for ChainInfo {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        let members = BTreeMap::from_iter([ser_param(
-            "chain_id",
-            &self.chain_id,
-            "The chain ID of the StarkNet chain.",
-            ParamPrivacyInput::Public,
-        )]);
+        let members = BTreeMap::from_iter([
+            ser_param(
+                "chain_id",
+                &self.chain_id,
+                "The chain ID of the StarkNet chain.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "custom_fee_tokens",
+                &self.custom_fee_tokens,
+                "Space-separated <fee_type>:<address>:<decimals> fee tokens charged instead of \
+                 the default fee token of their fee type, e.g. \"strk:0x1234:6\".",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "disabled_tx_versions",
+                &self.disabled_tx_versions,
+                "Space-separated account transaction versions that are neither accepted nor \
+                 executed on the chain, e.g. \"0 1\".",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "sierra_gas_only",
+                &self.sierra_gas_only,
+                "If true, transactions run entirely under Sierra gas accounting: Cairo 0 classes \
+                 may not be executed, and transactions must bound all resources, including L2 gas.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
 
         vec![
             members,
             append_sub_config_name(self.fee_token_addresses.dump(), "fee_token_addresses"),
+            append_sub_config_name(self.fee_distribution.dump(), "fee_distribution"),
+            append_sub_config_name(self.paymaster.dump(), "paymaster"),
         ]
         .into_iter()
         .flatten()
@@ -228,3 +555,216 @@ impl SerializeConfig for FeeTokenAddresses {
         ])
     }
 }
+
+/// Shares, in percents, of each transaction fee transferred to a treasury and to a burn address,
+/// e.g., for revenue sharing or fee burning by an appchain; the sequencer receives the rest. The
+/// shares are transferred in the fee token, like the fee itself.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FeeDistribution {
+    pub treasury_address: ContractAddress,
+    pub treasury_percentage: u8,
+    // The fee token rejects transfers to the zero address, so burned fees are sent to an address
+    // no one controls.
+    pub burn_address: ContractAddress,
+    pub burn_percentage: u8,
+}
+
+impl FeeDistribution {
+    pub fn is_enabled(&self) -> bool {
+        self.treasury_percentage > 0 || self.burn_percentage > 0
+    }
+
+    /// Checks that the shares do not exceed the whole fee.
+    pub fn validate(&self) -> Result<(), String> {
+        let total_percentage =
+            u16::from(self.treasury_percentage) + u16::from(self.burn_percentage);
+        if total_percentage > 100 {
+            return Err(format!(
+                "The treasury and burn shares of the fee add up to {total_percentage}%, more than \
+                 the whole fee."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Splits the given fee by the configured shares, rounding down; the sequencer receives the
+    /// remainder.
+    pub fn split(&self, fee: Fee) -> FeeSplit {
+        // Avoids overflowing on the multiplication by the percentage.
+        let share = |percentage: u8| {
+            let percentage = u128::from(percentage);
+            Fee(fee.0 / 100 * percentage + fee.0 % 100 * percentage / 100)
+        };
+        let treasury = min(share(self.treasury_percentage), fee);
+        let burned = min(share(self.burn_percentage), Fee(fee.0 - treasury.0));
+        FeeSplit { sequencer: Fee(fee.0 - treasury.0 - burned.0), treasury, burned }
+    }
+
+    /// The transfers of the treasury and burned shares of the given split, in execution order, as
+    /// (recipient, amount). Empty shares are not transferred.
+    pub fn share_transfers(&self, fee_split: &FeeSplit) -> Vec<(ContractAddress, Fee)> {
+        [(self.treasury_address, fee_split.treasury), (self.burn_address, fee_split.burned)]
+            .into_iter()
+            .filter(|(_, amount)| *amount > Fee(0))
+            .collect()
+    }
+}
+
+impl SerializeConfig for FeeDistribution {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "treasury_address",
+                &self.treasury_address,
+                "The address receiving the treasury share of each transaction fee.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "treasury_percentage",
+                &self.treasury_percentage,
+                "The percentage of each transaction fee transferred to the treasury.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "burn_address",
+                &self.burn_address,
+                "The address, controlled by no one, receiving the burned share of each \
+                 transaction fee.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "burn_percentage",
+                &self.burn_percentage,
+                "The percentage of each transaction fee burned.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Sponsored transactions: a V3 transaction whose paymaster data is not empty names a paymaster
+/// account in its first element. After the sender's validation, the paymaster's
+/// `__validate_paymaster__` entry point is called with the rest of the data, under its own gas
+/// limit; if it fails, the transaction is rejected. The paymaster, rather than the sender, is then
+/// charged the fee.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PaymasterConfig {
+    pub enabled: bool,
+    pub validate_max_sierra_gas: GasAmount,
+}
+
+impl Default for PaymasterConfig {
+    fn default() -> Self {
+        Self { enabled: false, validate_max_sierra_gas: GasAmount(10_000_000) }
+    }
+}
+
+impl SerializeConfig for PaymasterConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, transactions with paymaster data are sponsored by the paymaster it \
+                 names, which validates them and pays their fee.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "validate_max_sierra_gas",
+                &self.validate_max_sierra_gas,
+                "The maximal amount of Sierra gas the validation of a transaction by its \
+                 paymaster may consume.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The number of decimals of the ETH and STRK fee tokens.
+pub const DEFAULT_FEE_TOKEN_DECIMALS: u8 = 18;
+
+/// A fee token registered by the chain, e.g., by an appchain charging fees in its native token.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CustomFeeToken {
+    /// The transactions charged in this token: those whose fees are of this type.
+    pub fee_type: FeeType,
+    pub address: ContractAddress,
+    pub decimals: u8,
+}
+
+/// The custom fee tokens of a chain, at most one per fee type.
+/// Serialized as space-separated `<fee_type>:<address>:<decimals>` triplets, where the fee type is
+/// `eth` or `strk`, e.g. `"strk:0x1234:6"`.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CustomFeeTokens(Vec<CustomFeeToken>);
+
+impl CustomFeeTokens {
+    pub fn new(
+        custom_fee_tokens: impl IntoIterator<Item = CustomFeeToken>,
+    ) -> Result<Self, String> {
+        let custom_fee_tokens: Vec<CustomFeeToken> = custom_fee_tokens.into_iter().collect();
+        for (i, custom_fee_token) in custom_fee_tokens.iter().enumerate() {
+            let fee_type = custom_fee_token.fee_type;
+            if custom_fee_tokens[..i].iter().any(|other| other.fee_type == fee_type) {
+                return Err(format!("Multiple custom fee tokens for fee type {fee_type:?}."));
+            }
+        }
+        Ok(Self(custom_fee_tokens))
+    }
+
+    pub fn get_by_fee_type(&self, fee_type: &FeeType) -> Option<&CustomFeeToken> {
+        self.0.iter().find(|custom_fee_token| custom_fee_token.fee_type == *fee_type)
+    }
+}
+
+impl From<CustomFeeTokens> for String {
+    fn from(custom_fee_tokens: CustomFeeTokens) -> Self {
+        custom_fee_tokens
+            .0
+            .iter()
+            .map(|custom_fee_token| {
+                let fee_type = match custom_fee_token.fee_type {
+                    FeeType::Eth => "eth",
+                    FeeType::Strk => "strk",
+                };
+                format!(
+                    "{fee_type}:{:#x}:{}",
+                    custom_fee_token.address.0.key(),
+                    custom_fee_token.decimals
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl TryFrom<String> for CustomFeeTokens {
+    type Error = String;
+
+    fn try_from(custom_fee_tokens: String) -> Result<Self, String> {
+        let custom_fee_tokens = custom_fee_tokens
+            .split_whitespace()
+            .map(|custom_fee_token| {
+                let invalid_token = || format!("Invalid custom fee token: {custom_fee_token}.");
+                let [fee_type, address, decimals] =
+                    custom_fee_token.split(':').collect::<Vec<_>>()[..]
+                else {
+                    return Err(invalid_token());
+                };
+                let fee_type = match fee_type {
+                    "eth" => FeeType::Eth,
+                    "strk" => FeeType::Strk,
+                    _ => return Err(invalid_token()),
+                };
+                let address = Felt::from_hex(address)
+                    .ok()
+                    .and_then(|address| ContractAddress::try_from(address).ok())
+                    .ok_or_else(invalid_token)?;
+                let decimals = decimals.parse().map_err(|_| invalid_token())?;
+                Ok(CustomFeeToken { fee_type, address, decimals })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::new(custom_fee_tokens)
+    }
+}
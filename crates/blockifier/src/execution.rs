@@ -1,4 +1,5 @@
 pub mod call_info;
+pub mod cancellation;
 pub mod common_hints;
 pub mod contract_address;
 pub mod contract_class;
@@ -7,11 +8,15 @@ pub mod deprecated_syscalls;
 pub mod entry_point;
 pub mod entry_point_execution;
 pub mod errors;
+pub mod execution_quotas;
+pub mod execution_trace;
 pub mod execution_utils;
 pub mod hint_code;
 pub mod secp;
 
 #[cfg(feature = "cairo_native")]
 pub mod native;
+pub mod native_contracts;
 pub mod stack_trace;
+pub mod syscall_hooks;
 pub mod syscalls;
@@ -4,18 +4,24 @@ use std::ops::{Add, AddAssign};
 
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use serde::Serialize;
-use starknet_api::core::{ClassHash, ContractAddress, EthAddress};
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, EthAddress};
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::state::StorageKey;
+use starknet_api::transaction::fields::GasVectorComputationMode;
 use starknet_api::transaction::{EventContent, L2ToL1Payload};
 use starknet_types_core::felt::Felt;
 
 use crate::execution::contract_class::TrackedResource;
 use crate::execution::entry_point::CallEntryPoint;
+use crate::fee::fee_utils::get_vm_resources_cost;
 use crate::state::cached_state::StorageEntry;
 use crate::utils::u64_from_usize;
 use crate::versioned_constants::VersionedConstants;
 
+#[cfg(test)]
+#[path = "call_info_test.rs"]
+pub mod test;
+
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct Retdata(pub Vec<Felt>);
@@ -117,6 +123,19 @@ impl ChargedResources {
     pub fn from_gas(gas_for_fee: GasAmount) -> Self {
         Self { gas_for_fee, ..Default::default() }
     }
+
+    /// The charged resources in units of L2 gas. The VM resources are converted as done for fee
+    /// charge, i.e., by their heaviest component.
+    pub fn to_l2_gas(&self, versioned_constants: &VersionedConstants) -> GasAmount {
+        let vm_resources_gas = get_vm_resources_cost(
+            versioned_constants,
+            &self.vm_resources,
+            0,
+            &GasVectorComputationMode::All,
+        )
+        .l2_gas;
+        self.gas_for_fee.checked_add(vm_resources_gas).expect("Gas for fee overflowed.")
+    }
 }
 
 impl Add<&ChargedResources> for &ChargedResources {
@@ -137,6 +156,17 @@ impl AddAssign<&ChargedResources> for ChargedResources {
     }
 }
 
+/// An edge of the entry point call graph, from the caller of an entry point to the contract
+/// executing it, attributed with the L2 gas charged for the execution of the entry point itself,
+/// excluding its inner calls.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CallGasEdge {
+    pub caller: ContractAddress,
+    pub callee: ContractAddress,
+    pub selector: EntryPointSelector,
+    pub gas: GasAmount,
+}
+
 /// Represents the full effects of executing an entry point, including the inner calls it invoked.
 #[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
@@ -229,6 +259,43 @@ impl CallInfo {
         call_infos.map(|call_info| call_info.summarize(versioned_constants)).sum()
     }
 
+    /// The resources charged for the execution of this call itself, excluding its inner calls.
+    pub fn charged_resources_without_inner_calls(&self) -> ChargedResources {
+        let inner_calls_resources = Self::summarize_charged_resources(self.inner_calls.iter());
+        ChargedResources {
+            vm_resources: &self.charged_resources.vm_resources
+                - &inner_calls_resources.vm_resources,
+            gas_for_fee: self
+                .charged_resources
+                .gas_for_fee
+                .checked_sub(inner_calls_resources.gas_for_fee)
+                .expect("The charged gas of a call should cover its inner calls."),
+        }
+    }
+
+    /// The L2 gas charged for the execution of this call itself, excluding its inner calls.
+    pub fn gas_for_fee_without_inner_calls(
+        &self,
+        versioned_constants: &VersionedConstants,
+    ) -> GasAmount {
+        self.charged_resources_without_inner_calls().to_l2_gas(versioned_constants)
+    }
+
+    /// Iterates over the edges of the call graph rooted at this call, in the order of `iter`, each
+    /// attributed with the L2 gas charged for its callee's execution itself; i.e., shows where the
+    /// fee of the call went.
+    pub fn gas_edges<'a>(
+        &'a self,
+        versioned_constants: &'a VersionedConstants,
+    ) -> impl Iterator<Item = CallGasEdge> + 'a {
+        self.iter().map(|call_info| CallGasEdge {
+            caller: call_info.call.caller_address,
+            callee: call_info.call.storage_address,
+            selector: call_info.call.entry_point_selector,
+            gas: call_info.gas_for_fee_without_inner_calls(versioned_constants),
+        })
+    }
+
     pub fn summarize_charged_resources<'a>(
         call_infos: impl Iterator<Item = &'a CallInfo>,
     ) -> ChargedResources {
@@ -0,0 +1,92 @@
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use pretty_assertions::assert_eq;
+use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::contract_address;
+use starknet_api::core::ContractAddress;
+use starknet_api::execution_resources::GasAmount;
+
+use crate::execution::call_info::{CallGasEdge, CallInfo, ChargedResources};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::versioned_constants::VersionedConstants;
+
+fn call_info(
+    caller: ContractAddress,
+    callee: ContractAddress,
+    selector_name: &str,
+    charged_resources: ChargedResources,
+    inner_calls: Vec<CallInfo>,
+) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint {
+            caller_address: caller,
+            storage_address: callee,
+            entry_point_selector: selector_from_name(selector_name),
+            ..Default::default()
+        },
+        charged_resources,
+        inner_calls,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_charged_resources_without_inner_calls() {
+    let vm_resources = |n_steps| ExecutionResources { n_steps, ..Default::default() };
+    let inner_call = call_info(
+        contract_address!("0x1"),
+        contract_address!("0x2"),
+        "inner",
+        ChargedResources { vm_resources: vm_resources(40), gas_for_fee: GasAmount(300) },
+        vec![],
+    );
+    let outer_call = call_info(
+        ContractAddress::default(),
+        contract_address!("0x1"),
+        "outer",
+        ChargedResources { vm_resources: vm_resources(100), gas_for_fee: GasAmount(1000) },
+        vec![inner_call],
+    );
+
+    assert_eq!(
+        outer_call.charged_resources_without_inner_calls(),
+        ChargedResources { vm_resources: vm_resources(60), gas_for_fee: GasAmount(700) }
+    );
+}
+
+#[test]
+fn test_gas_edges() {
+    let (account, token, library) =
+        (contract_address!("0x1"), contract_address!("0x2"), contract_address!("0x3"));
+    let charged_gas = |gas| ChargedResources::from_gas(GasAmount(gas));
+    let nested_call = call_info(token, library, "nested", charged_gas(100), vec![]);
+    let transfer_call = call_info(account, token, "transfer", charged_gas(300), vec![nested_call]);
+    let approve_call = call_info(account, token, "approve", charged_gas(150), vec![]);
+    let execute_call = call_info(
+        ContractAddress::default(),
+        account,
+        "__execute__",
+        charged_gas(1000),
+        vec![transfer_call, approve_call],
+    );
+
+    let versioned_constants = VersionedConstants::create_for_testing();
+    let edges: Vec<_> = execute_call.gas_edges(&versioned_constants).collect();
+    let edge = |caller, callee, selector_name, gas| CallGasEdge {
+        caller,
+        callee,
+        selector: selector_from_name(selector_name),
+        gas: GasAmount(gas),
+    };
+    assert_eq!(
+        edges,
+        vec![
+            edge(ContractAddress::default(), account, "__execute__", 550),
+            edge(account, token, "transfer", 200),
+            edge(token, library, "nested", 100),
+            edge(account, token, "approve", 150),
+        ]
+    );
+    // The fee of the call is fully attributed.
+    let total_gas: u64 = edges.iter().map(|edge| edge.gas.0).sum();
+    assert_eq!(total_gas, 1000);
+}
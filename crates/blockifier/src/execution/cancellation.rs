@@ -0,0 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A cooperative cancellation token of the executions under a block context, shared with their
+/// owner. Once cancelled, running executions stop at their next entry point call or syscall, and
+/// no further transactions are executed.
+///
+/// Note: a cancelled execution leaves the state of the block unspecified; the block must be
+/// discarded.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -22,6 +22,7 @@ use crate::execution::entry_point::{
     EntryPointExecutionResult,
 };
 use crate::execution::errors::{PostExecutionError, PreExecutionError};
+use crate::execution::execution_trace::collect_trace_entries;
 use crate::execution::execution_utils::{read_execution_retdata, Args, ReadOnlySegments};
 use crate::state::state_api::State;
 
@@ -62,6 +63,16 @@ pub fn execute_entry_point_call(
     // Execute.
     run_entry_point(&mut runner, &mut syscall_handler, entry_point_pc, args)?;
 
+    if let Some(execution_tracer) =
+        syscall_handler.context.tx_context.block_context.execution_tracer()
+    {
+        // Relocate the trace, putting the program segment at address 1 and the execution segment
+        // right after it.
+        let program_segment_size = runner.get_program().data_len();
+        runner.relocate_trace(&[1, 1 + program_segment_size])?;
+        execution_tracer.record_steps(collect_trace_entries(&runner, program_segment_size)?);
+    }
+
     Ok(finalize_execution(runner, syscall_handler, call, implicit_args, n_total_args)?)
 }
 
@@ -81,12 +92,14 @@ pub fn initialize_execution_context<'a>(
             unsupported_builtins.iter().map(|&item| *item).collect(),
         ));
     }
+    context.verify_builtins_allowed(program_builtins)?;
 
     // Resolve initial PC from EP indicator.
     let entry_point_pc = resolve_entry_point_pc(call, &compiled_class)?;
     // Instantiate Cairo runner.
     let proof_mode = false;
-    let trace_enabled = false;
+    // The trace is only needed when the execution is traced.
+    let trace_enabled = context.tx_context.block_context.execution_tracer().is_some();
     let allow_missing_builtins = false;
     let program_base = None;
     let mut runner =
@@ -217,7 +230,7 @@ pub fn run_entry_point(
 
 pub fn finalize_execution(
     mut runner: CairoRunner,
-    syscall_handler: DeprecatedSyscallHintProcessor<'_>,
+    mut syscall_handler: DeprecatedSyscallHintProcessor<'_>,
     call: CallEntryPoint,
     implicit_args: Vec<MaybeRelocatable>,
     n_total_args: usize,
@@ -250,6 +263,13 @@ pub fn finalize_execution(
             .get_mut(&BuiltinName::segment_arena)
             .map_or_else(|| {}, |val| *val *= SEGMENT_ARENA_BUILTIN_SIZE);
     }
+    let gas_consumed = 0;
+    syscall_handler.context.record_profile(
+        &call,
+        &vm_resources_without_inner_calls,
+        &syscall_handler.syscall_counter,
+        gas_consumed,
+    );
     // Take into account the syscall resources of the current call.
     vm_resources_without_inner_calls +=
         &versioned_constants.get_additional_os_syscall_resources(&syscall_handler.syscall_counter);
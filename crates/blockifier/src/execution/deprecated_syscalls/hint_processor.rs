@@ -118,6 +118,8 @@ pub enum DeprecatedSyscallExecutionError {
     VirtualMachineError(#[from] VirtualMachineError),
     #[error("Unauthorized syscall {syscall_name} in execution mode {execution_mode}.")]
     InvalidSyscallInExecutionMode { syscall_name: String, execution_mode: ExecutionMode },
+    #[error("Syscall {selector:?} was rejected by a syscall hook: {reason}")]
+    SyscallRejected { selector: DeprecatedSyscallSelector, reason: String },
 }
 
 // Needed for custom hint implementations (in our case, syscall hints) which must comply with the
@@ -262,43 +264,76 @@ impl<'a> DeprecatedSyscallHintProcessor<'a> {
         let selector = DeprecatedSyscallSelector::try_from(self.read_next_syscall_selector(vm)?)?;
         self.increment_syscall_count(&selector);
 
-        match selector {
-            DeprecatedSyscallSelector::CallContract => self.execute_syscall(vm, call_contract),
-            DeprecatedSyscallSelector::DelegateCall => self.execute_syscall(vm, delegate_call),
+        if self.context.tx_context.block_context.is_execution_cancelled() {
+            return Err(DeprecatedSyscallExecutionError::from(
+                EntryPointExecutionError::ExecutionCancelled,
+            )
+            .into());
+        }
+
+        let execution_tracer = self.context.tx_context.block_context.execution_tracer().cloned();
+        if let Some(execution_tracer) = &execution_tracer {
+            execution_tracer.enter_syscall(selector, vm.get_current_step());
+        }
+
+        let result = match selector {
+            DeprecatedSyscallSelector::CallContract => {
+                self.execute_syscall(vm, selector, call_contract)
+            }
+            DeprecatedSyscallSelector::DelegateCall => {
+                self.execute_syscall(vm, selector, delegate_call)
+            }
             DeprecatedSyscallSelector::DelegateL1Handler => {
-                self.execute_syscall(vm, delegate_l1_handler)
+                self.execute_syscall(vm, selector, delegate_l1_handler)
+            }
+            DeprecatedSyscallSelector::Deploy => self.execute_syscall(vm, selector, deploy),
+            DeprecatedSyscallSelector::EmitEvent => self.execute_syscall(vm, selector, emit_event),
+            DeprecatedSyscallSelector::GetBlockNumber => {
+                self.execute_syscall(vm, selector, get_block_number)
             }
-            DeprecatedSyscallSelector::Deploy => self.execute_syscall(vm, deploy),
-            DeprecatedSyscallSelector::EmitEvent => self.execute_syscall(vm, emit_event),
-            DeprecatedSyscallSelector::GetBlockNumber => self.execute_syscall(vm, get_block_number),
             DeprecatedSyscallSelector::GetBlockTimestamp => {
-                self.execute_syscall(vm, get_block_timestamp)
+                self.execute_syscall(vm, selector, get_block_timestamp)
             }
             DeprecatedSyscallSelector::GetCallerAddress => {
-                self.execute_syscall(vm, get_caller_address)
+                self.execute_syscall(vm, selector, get_caller_address)
             }
             DeprecatedSyscallSelector::GetContractAddress => {
-                self.execute_syscall(vm, get_contract_address)
+                self.execute_syscall(vm, selector, get_contract_address)
             }
             DeprecatedSyscallSelector::GetSequencerAddress => {
-                self.execute_syscall(vm, get_sequencer_address)
+                self.execute_syscall(vm, selector, get_sequencer_address)
+            }
+            DeprecatedSyscallSelector::GetTxInfo => self.execute_syscall(vm, selector, get_tx_info),
+            DeprecatedSyscallSelector::GetTxSignature => {
+                self.execute_syscall(vm, selector, get_tx_signature)
+            }
+            DeprecatedSyscallSelector::LibraryCall => {
+                self.execute_syscall(vm, selector, library_call)
             }
-            DeprecatedSyscallSelector::GetTxInfo => self.execute_syscall(vm, get_tx_info),
-            DeprecatedSyscallSelector::GetTxSignature => self.execute_syscall(vm, get_tx_signature),
-            DeprecatedSyscallSelector::LibraryCall => self.execute_syscall(vm, library_call),
             DeprecatedSyscallSelector::LibraryCallL1Handler => {
-                self.execute_syscall(vm, library_call_l1_handler)
+                self.execute_syscall(vm, selector, library_call_l1_handler)
+            }
+            DeprecatedSyscallSelector::ReplaceClass => {
+                self.execute_syscall(vm, selector, replace_class)
             }
-            DeprecatedSyscallSelector::ReplaceClass => self.execute_syscall(vm, replace_class),
             DeprecatedSyscallSelector::SendMessageToL1 => {
-                self.execute_syscall(vm, send_message_to_l1)
+                self.execute_syscall(vm, selector, send_message_to_l1)
+            }
+            DeprecatedSyscallSelector::StorageRead => {
+                self.execute_syscall(vm, selector, storage_read)
+            }
+            DeprecatedSyscallSelector::StorageWrite => {
+                self.execute_syscall(vm, selector, storage_write)
             }
-            DeprecatedSyscallSelector::StorageRead => self.execute_syscall(vm, storage_read),
-            DeprecatedSyscallSelector::StorageWrite => self.execute_syscall(vm, storage_write),
             _ => Err(HintError::UnknownHint(
                 format!("Unsupported syscall selector {selector:?}.").into(),
             )),
+        };
+        if let Some(execution_tracer) = execution_tracer {
+            execution_tracer.exit_syscall();
         }
+
+        result
     }
 
     pub fn get_or_allocate_tx_signature_segment(
@@ -332,20 +367,34 @@ impl<'a> DeprecatedSyscallHintProcessor<'a> {
     fn execute_syscall<Request, Response, ExecuteCallback>(
         &mut self,
         vm: &mut VirtualMachine,
+        selector: DeprecatedSyscallSelector,
         execute_callback: ExecuteCallback,
     ) -> HintExecutionResult
     where
-        Request: SyscallRequest,
-        Response: SyscallResponse,
+        Request: SyscallRequest + std::fmt::Debug + 'static,
+        Response: SyscallResponse + std::fmt::Debug + 'static,
         ExecuteCallback: FnOnce(
             Request,
             &mut VirtualMachine,
             &mut DeprecatedSyscallHintProcessor<'_>,
         ) -> DeprecatedSyscallResult<Response>,
     {
+        if !self.context.is_syscall_allowed(selector) {
+            return Err(DeprecatedSyscallExecutionError::InvalidSyscallInExecutionMode {
+                syscall_name: format!("{selector:?}"),
+                execution_mode: self.execution_mode(),
+            }
+            .into());
+        }
+
         let request = Request::read(vm, &mut self.syscall_ptr)?;
+        if let Err(reason) = self.context.before_syscall(self.storage_address, selector, &request) {
+            let error = DeprecatedSyscallExecutionError::SyscallRejected { selector, reason };
+            return Err(error.into());
+        }
 
         let response = execute_callback(request, vm, self)?;
+        self.context.after_syscall(self.storage_address, selector, Ok(&response));
         response.write(vm, &mut self.syscall_ptr)?;
 
         Ok(())
@@ -1,9 +1,10 @@
 use std::cell::RefCell;
 use std::cmp::min;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use cairo_vm::vm::runners::cairo_runner::{ResourceTracker, RunResources};
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::vm::runners::cairo_runner::{ExecutionResources, ResourceTracker, RunResources};
 use num_traits::{Inv, Zero};
 use serde::Serialize;
 use starknet_api::abi::abi_utils::selector_from_name;
@@ -24,19 +25,25 @@ use starknet_types_core::felt::Felt;
 use crate::context::{BlockContext, TransactionContext};
 use crate::execution::call_info::CallInfo;
 use crate::execution::common_hints::ExecutionMode;
-use crate::execution::contract_class::TrackedResource;
+use crate::execution::contract_class::{RunnableCompiledClass, TrackedResource};
 use crate::execution::errors::{
     ConstructorEntryPointExecutionError,
     EntryPointExecutionError,
     PreExecutionError,
 };
+use crate::execution::execution_quotas::QuotaCallCounts;
+use crate::execution::execution_trace::{ExecutionTracer, TraceCheckpoint};
 use crate::execution::execution_utils::execute_entry_point_call_wrapper;
+use crate::execution::native_contracts::execute_native_contract_call;
 use crate::execution::stack_trace::{extract_trailing_cairo1_revert_trace, Cairo1RevertHeader};
+use crate::execution::syscall_hooks::{SharedSyscallHook, SyscallMessage};
+use crate::execution::syscalls::hint_processor::SyscallCounter;
+use crate::execution::syscalls::SyscallSelector;
 use crate::state::state_api::{State, StateResult};
 use crate::transaction::objects::{HasRelatedFeeType, TransactionInfo};
 use crate::transaction::transaction_types::TransactionType;
 use crate::utils::usize_from_u64;
-use crate::versioned_constants::{GasCosts, VersionedConstants};
+use crate::versioned_constants::{ExecutionPhaseConfig, GasCosts, VersionedConstants};
 
 #[cfg(test)]
 #[path = "entry_point_test.rs"]
@@ -61,6 +68,8 @@ pub struct EntryPointRevertInfo {
     n_emitted_events: usize,
     // The number of sent messages to L1 before the call.
     n_sent_messages_to_l1: usize,
+    // The calls of the transaction subject to call quotas before the call.
+    quota_call_counts: QuotaCallCounts,
 }
 impl EntryPointRevertInfo {
     pub fn new(
@@ -68,6 +77,7 @@ impl EntryPointRevertInfo {
         original_class_hash: ClassHash,
         n_emitted_events: usize,
         n_sent_messages_to_l1: usize,
+        quota_call_counts: QuotaCallCounts,
     ) -> Self {
         Self {
             contract_address,
@@ -75,6 +85,7 @@ impl EntryPointRevertInfo {
             original_values: HashMap::new(),
             n_emitted_events,
             n_sent_messages_to_l1,
+            quota_call_counts,
         }
     }
 }
@@ -121,12 +132,30 @@ impl CallEntryPoint {
         remaining_gas: &mut u64,
     ) -> EntryPointExecutionResult<CallInfo> {
         let tx_context = &context.tx_context;
+        if tx_context.block_context.is_execution_cancelled() {
+            return Err(EntryPointExecutionError::ExecutionCancelled);
+        }
         let mut decrement_when_dropped = RecursionDepthGuard::new(
             context.current_recursion_depth.clone(),
             context.versioned_constants().max_recursion_depth,
         );
         decrement_when_dropped.try_increment_and_check_depth()?;
 
+        // Calls to native contracts do not load a class.
+        let native_contract = tx_context
+            .block_context
+            .native_contract_registry()
+            .filter(|_| self.call_type == CallType::Call)
+            .and_then(|registry| registry.get(self.storage_address).cloned());
+        if let Some(native_contract) = native_contract {
+            return execute_native_contract_call(
+                self,
+                native_contract.as_ref(),
+                context,
+                remaining_gas,
+            );
+        }
+
         // Validate contract is deployed.
         let storage_class_hash = state.get_class_hash_at(self.storage_address)?;
         if storage_class_hash == ClassHash::default() {
@@ -148,13 +177,22 @@ impl CallEntryPoint {
         }
         // Add class hash to the call, that will appear in the output (call info).
         self.class_hash = Some(class_hash);
+        let quota_call_counts = context.tx_context.call_counter.snapshot();
+        context.count_call(class_hash, self.storage_address)?;
         let compiled_class = state.get_compiled_class(class_hash)?;
+        // Cairo 0 classes can only be metered by steps.
+        if tx_context.block_context.chain_info.sierra_gas_only
+            && matches!(compiled_class, RunnableCompiledClass::V0(_))
+        {
+            return Err(PreExecutionError::Cairo0ExecutionInSierraGasOnlyMode { class_hash }.into());
+        }
 
         context.revert_infos.0.push(EntryPointRevertInfo::new(
             self.storage_address,
             storage_class_hash,
             context.n_emitted_events,
             context.n_sent_messages_to_l1,
+            quota_call_counts,
         ));
 
         // This is the last operation of this function.
@@ -194,6 +232,109 @@ impl CallEntryPoint {
     }
 }
 
+/// The resources used by the executions of an entry point, excluding those of its inner calls.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EntryPointProfile {
+    pub n_calls: usize,
+    pub n_steps: usize,
+    pub builtin_counts: HashMap<BuiltinName, usize>,
+    pub syscall_counts: HashMap<SyscallSelector, usize>,
+    pub gas_consumed: u64,
+}
+
+/// The resources used by the entry points executed in a block, per class hash and selector.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockExecutionProfile {
+    pub entry_points: HashMap<(ClassHash, EntryPointSelector), EntryPointProfile>,
+}
+
+impl BlockExecutionProfile {
+    /// Adds the resources used by a single execution of the given call.
+    pub fn record(
+        &mut self,
+        call: &CallEntryPoint,
+        vm_resources: &ExecutionResources,
+        syscall_counter: &SyscallCounter,
+        gas_consumed: u64,
+    ) {
+        let key = (call.class_hash.unwrap_or_default(), call.entry_point_selector);
+        let profile = self.entry_points.entry(key).or_default();
+
+        profile.n_calls += 1;
+        profile.n_steps += vm_resources.n_steps;
+        for (builtin_name, count) in &vm_resources.builtin_instance_counter {
+            *profile.builtin_counts.entry(*builtin_name).or_default() += count;
+        }
+        for (selector, count) in syscall_counter {
+            *profile.syscall_counts.entry(*selector).or_default() += count;
+        }
+        profile.gas_consumed += gas_consumed;
+    }
+
+    /// Adds the resources of the given profile.
+    pub fn merge(&mut self, other: &BlockExecutionProfile) {
+        for (key, other_profile) in &other.entry_points {
+            let profile = self.entry_points.entry(*key).or_default();
+            profile.n_calls += other_profile.n_calls;
+            profile.n_steps += other_profile.n_steps;
+            for (builtin_name, count) in &other_profile.builtin_counts {
+                *profile.builtin_counts.entry(*builtin_name).or_default() += count;
+            }
+            for (selector, count) in &other_profile.syscall_counts {
+                *profile.syscall_counts.entry(*selector).or_default() += count;
+            }
+            profile.gas_consumed += other_profile.gas_consumed;
+        }
+    }
+
+    /// Returns the profiled entry points, the ones that ran the most steps first.
+    pub fn hottest_entry_points(&self) -> Vec<(ClassHash, EntryPointSelector, &EntryPointProfile)> {
+        let mut entry_points = self
+            .entry_points
+            .iter()
+            .map(|((class_hash, selector), profile)| (*class_hash, *selector, profile))
+            .collect::<Vec<_>>();
+        entry_points.sort_by(|(_, _, first), (_, _, second)| {
+            second.n_steps.cmp(&first.n_steps).then(second.gas_consumed.cmp(&first.gas_consumed))
+        });
+        entry_points
+    }
+}
+
+/// Aggregates the resources of the entry points executed under a block context, shared by all
+/// the transactions of the block; see `BlockContext::with_execution_profiler`.
+///
+/// Note: entry points executed natively are not profiled, as they don't run on the VM.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionProfiler(Arc<Mutex<BlockExecutionProfile>>);
+
+impl ExecutionProfiler {
+    /// Adds the resources used by a single execution of the given call.
+    pub fn record(
+        &self,
+        call: &CallEntryPoint,
+        vm_resources: &ExecutionResources,
+        syscall_counter: &SyscallCounter,
+        gas_consumed: u64,
+    ) {
+        self.lock().record(call, vm_resources, syscall_counter, gas_consumed);
+    }
+
+    /// Adds the resources of the given profile.
+    pub fn merge(&self, profile: &BlockExecutionProfile) {
+        self.lock().merge(profile);
+    }
+
+    /// Returns the profile aggregated so far.
+    pub fn profile(&self) -> BlockExecutionProfile {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BlockExecutionProfile> {
+        self.0.lock().expect("Execution profiler lock is poisoned.")
+    }
+}
+
 pub struct ConstructorContext {
     pub class_hash: ClassHash,
     // Only relevant in deploy syscall.
@@ -223,15 +364,53 @@ pub struct EntryPointExecutionContext {
 
     // Information for reverting the state (inludes the revert info of the callers).
     pub revert_infos: ExecutionRevertInfo,
+    // Called around every syscall executed under this context, in order; includes the hooks of
+    // the block context.
+    syscall_hooks: Vec<SharedSyscallHook>,
+    // The limits and restrictions of the execution phase run under this context.
+    phase_config: ExecutionPhaseConfig,
+    // The profiles of the executions that may still be discarded, innermost last; see
+    // `checkpoint`.
+    pending_profiles: Vec<BlockExecutionProfile>,
+}
+
+/// The state of an execution context at the start of a call, to discard the effects of an
+/// execution of the call on the context; see `EntryPointExecutionContext::checkpoint`.
+#[derive(Debug)]
+pub(crate) struct ExecutionCheckpoint {
+    n_revert_infos: usize,
+    original_values: HashMap<StorageKey, Felt>,
+    n_emitted_events: usize,
+    n_sent_messages_to_l1: usize,
+    quota_call_counts: QuotaCallCounts,
+    n_remaining_steps: usize,
+    n_tracked_resources: usize,
+    recursion_depth: usize,
+    trace_checkpoint: Option<TraceCheckpoint>,
+    n_pending_profiles: usize,
 }
 
 impl EntryPointExecutionContext {
+    /// Creates a context for the given execution phase, with the limits and restrictions defined
+    /// for it by the versioned constants.
     pub fn new(
         tx_context: Arc<TransactionContext>,
         mode: ExecutionMode,
         limit_steps_by_resources: bool,
     ) -> Self {
-        let max_steps = Self::max_steps(&tx_context, &mode, limit_steps_by_resources);
+        let phase_config =
+            tx_context.block_context.versioned_constants.execution_phase_config(&mode);
+        Self::new_with_phase_config(tx_context, mode, phase_config, limit_steps_by_resources)
+    }
+
+    /// Creates a context for the given execution phase, with the given limits and restrictions.
+    pub fn new_with_phase_config(
+        tx_context: Arc<TransactionContext>,
+        mode: ExecutionMode,
+        phase_config: ExecutionPhaseConfig,
+        limit_steps_by_resources: bool,
+    ) -> Self {
+        let max_steps = Self::max_steps(&tx_context, &phase_config, limit_steps_by_resources);
         Self {
             vm_run_resources: RunResources::new(max_steps),
             n_emitted_events: 0,
@@ -241,9 +420,193 @@ impl EntryPointExecutionContext {
             execution_mode: mode,
             tracked_resource_stack: vec![],
             revert_infos: ExecutionRevertInfo(vec![]),
+            syscall_hooks: tx_context.block_context.syscall_hooks.clone(),
+            phase_config,
+            pending_profiles: vec![],
         }
     }
 
+    pub fn phase_config(&self) -> &ExecutionPhaseConfig {
+        &self.phase_config
+    }
+
+    pub(crate) fn is_syscall_allowed(&self, selector: SyscallSelector) -> bool {
+        self.phase_config.is_syscall_allowed(selector)
+    }
+
+    /// Returns an error if any of the given builtins is not allowed in the execution phase.
+    pub(crate) fn verify_builtins_allowed<'a>(
+        &self,
+        builtins: impl IntoIterator<Item = &'a BuiltinName>,
+    ) -> Result<(), PreExecutionError> {
+        match self.phase_config.find_disallowed_builtin(builtins) {
+            Some(builtin) => Err(PreExecutionError::BuiltinNotAllowedInExecutionMode {
+                builtin,
+                execution_mode: self.execution_mode,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Registers a hook called around every syscall executed under this context, after the
+    /// previously registered hooks.
+    pub fn with_syscall_hook(mut self, syscall_hook: SharedSyscallHook) -> Self {
+        self.syscall_hooks.push(syscall_hook);
+        self
+    }
+
+    /// Runs the hooks before a syscall of the given contract; returns the reason of the first
+    /// rejection, if any.
+    pub(crate) fn before_syscall(
+        &self,
+        storage_address: ContractAddress,
+        selector: SyscallSelector,
+        request: &dyn SyscallMessage,
+    ) -> Result<(), String> {
+        self.syscall_hooks
+            .iter()
+            .try_for_each(|hook| hook.before_syscall(storage_address, selector, request))
+    }
+
+    pub(crate) fn after_syscall(
+        &self,
+        storage_address: ContractAddress,
+        selector: SyscallSelector,
+        response: Result<&dyn SyscallMessage, &[Felt]>,
+    ) {
+        for hook in &self.syscall_hooks {
+            hook.after_syscall(storage_address, selector, response);
+        }
+    }
+
+    /// Counts a call of the given class at the given address in the calls of the transaction;
+    /// fails if the call exceeds a call quota of the block.
+    pub(crate) fn count_call(
+        &self,
+        class_hash: ClassHash,
+        storage_address: ContractAddress,
+    ) -> EntryPointExecutionResult<()> {
+        let block_context = &self.tx_context.block_context;
+        self.tx_context
+            .call_counter
+            .count_call(
+                &self.versioned_constants().execution_quotas,
+                &block_context.block_call_counter,
+                class_hash,
+                storage_address,
+            )
+            .map_err(|reason| EntryPointExecutionError::ExecutionQuotaExceeded {
+                class_hash,
+                storage_address,
+                reason,
+            })
+    }
+
+    /// Fails if the resources of the given executed call, excluding its inner calls, exceed the
+    /// quotas of its class or address.
+    pub(crate) fn check_call_resources(
+        &self,
+        call_info: &CallInfo,
+    ) -> EntryPointExecutionResult<()> {
+        self.versioned_constants()
+            .execution_quotas
+            .check_call_resources(call_info, self.versioned_constants())
+            .map_err(|reason| EntryPointExecutionError::ExecutionQuotaExceeded {
+                class_hash: call_info.call.class_hash.unwrap_or_default(),
+                storage_address: call_info.call.storage_address,
+                reason,
+            })
+    }
+
+    /// Adds the resources used by a single execution of the given call to the profile of the
+    /// block, if it is profiled; see `BlockContext::with_execution_profiler`.
+    pub(crate) fn record_profile(
+        &mut self,
+        call: &CallEntryPoint,
+        vm_resources: &ExecutionResources,
+        syscall_counter: &SyscallCounter,
+        gas_consumed: u64,
+    ) {
+        let Some(execution_profiler) = self.tx_context.block_context.execution_profiler() else {
+            return;
+        };
+        match self.pending_profiles.last_mut() {
+            Some(pending_profile) => {
+                pending_profile.record(call, vm_resources, syscall_counter, gas_consumed)
+            }
+            None => execution_profiler.record(call, vm_resources, syscall_counter, gas_consumed),
+        }
+    }
+
+    /// Takes a checkpoint of the context at the start of a call, right after its revert info is
+    /// pushed. Until the checkpoint is released or rolled back, the profiled resources are kept
+    /// aside.
+    pub(crate) fn checkpoint(&mut self) -> ExecutionCheckpoint {
+        let n_pending_profiles = self.pending_profiles.len();
+        if self.tx_context.block_context.execution_profiler().is_some() {
+            self.pending_profiles.push(BlockExecutionProfile::default());
+        }
+        ExecutionCheckpoint {
+            n_revert_infos: self.revert_infos.0.len(),
+            original_values: self
+                .revert_infos
+                .0
+                .last()
+                .map(|revert_info| revert_info.original_values.clone())
+                .unwrap_or_default(),
+            n_emitted_events: self.n_emitted_events,
+            n_sent_messages_to_l1: self.n_sent_messages_to_l1,
+            quota_call_counts: self.tx_context.call_counter.snapshot(),
+            n_remaining_steps: self.n_remaining_steps(),
+            n_tracked_resources: self.tracked_resource_stack.len(),
+            recursion_depth: *self.current_recursion_depth.borrow(),
+            trace_checkpoint: self
+                .tx_context
+                .block_context
+                .execution_tracer()
+                .map(ExecutionTracer::checkpoint),
+            n_pending_profiles,
+        }
+    }
+
+    /// Keeps the effects of the execution since the given checkpoint.
+    pub(crate) fn release(&mut self, checkpoint: ExecutionCheckpoint) {
+        while self.pending_profiles.len() > checkpoint.n_pending_profiles {
+            let pending_profile = self.pending_profiles.pop().expect("Pending profile is missing.");
+            match self.pending_profiles.last_mut() {
+                Some(outer_pending_profile) => outer_pending_profile.merge(&pending_profile),
+                None => {
+                    if let Some(execution_profiler) =
+                        self.tx_context.block_context.execution_profiler()
+                    {
+                        execution_profiler.merge(&pending_profile);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Discards the effects of the execution since the given checkpoint on the context; the
+    /// changes of the state are discarded by the caller.
+    pub(crate) fn rollback(&mut self, checkpoint: ExecutionCheckpoint) {
+        self.revert_infos.0.truncate(checkpoint.n_revert_infos);
+        if let Some(revert_info) = self.revert_infos.0.last_mut() {
+            revert_info.original_values = checkpoint.original_values;
+        }
+        self.n_emitted_events = checkpoint.n_emitted_events;
+        self.n_sent_messages_to_l1 = checkpoint.n_sent_messages_to_l1;
+        self.tx_context.call_counter.restore(checkpoint.quota_call_counts);
+        self.vm_run_resources = RunResources::new(checkpoint.n_remaining_steps);
+        self.tracked_resource_stack.truncate(checkpoint.n_tracked_resources);
+        *self.current_recursion_depth.borrow_mut() = checkpoint.recursion_depth;
+        if let (Some(execution_tracer), Some(trace_checkpoint)) =
+            (self.tx_context.block_context.execution_tracer(), checkpoint.trace_checkpoint)
+        {
+            execution_tracer.rollback(trace_checkpoint);
+        }
+        self.pending_profiles.truncate(checkpoint.n_pending_profiles);
+    }
+
     pub fn new_validate(
         tx_context: Arc<TransactionContext>,
         limit_steps_by_resources: bool,
@@ -256,27 +619,24 @@ impl EntryPointExecutionContext {
     }
 
     /// Returns the maximum number of cairo steps allowed, given the max fee, gas price and the
-    /// execution mode.
+    /// execution phase.
     /// If fee is disabled, returns the global maximum.
     /// The bound computation is saturating (no panic on overflow).
     fn max_steps(
         tx_context: &TransactionContext,
-        mode: &ExecutionMode,
+        phase_config: &ExecutionPhaseConfig,
         limit_steps_by_resources: bool,
     ) -> usize {
         let TransactionContext { block_context, tx_info } = tx_context;
-        let BlockContext { block_info, versioned_constants, .. } = block_context;
-        let block_upper_bound = match mode {
-            ExecutionMode::Validate => versioned_constants.validate_max_n_steps,
-            ExecutionMode::Execute => versioned_constants.invoke_tx_max_n_steps,
-        }
-        .try_into()
-        .unwrap_or_else(|error| {
+        let BlockContext { block_info, versioned_constants, chain_info, .. } = block_context;
+        let block_upper_bound = phase_config.max_n_steps.try_into().unwrap_or_else(|error| {
             log::warn!("Failed to convert global step limit to to usize: {error}.");
             usize::MAX
         });
 
-        if !limit_steps_by_resources {
+        // In Sierra gas only mode, the run is limited by the Sierra gas of the transaction rather
+        // than by a step bound derived from it.
+        if !limit_steps_by_resources || chain_info.sierra_gas_only {
             return block_upper_bound;
         }
 
@@ -349,19 +709,19 @@ impl EntryPointExecutionContext {
         self.n_remaining_steps()
     }
 
-    /// From the total amount of steps available for execution, deduct the steps consumed during
-    /// validation and the overhead steps required, among the rest, for fee transfer.
+    /// From the total amount of steps available for execution, deduct the steps consumed by the
+    /// validation calls (of the account and of the paymaster, if any) and the overhead steps
+    /// required, among the rest, for fee transfer.
     /// Returns the remaining steps (after the subtraction).
-    pub fn subtract_validation_and_overhead_steps(
+    pub fn subtract_validation_and_overhead_steps<'a>(
         &mut self,
-        validate_call_info: &Option<CallInfo>,
+        validate_call_infos: impl Iterator<Item = &'a CallInfo>,
         tx_type: &TransactionType,
         calldata_length: usize,
     ) -> usize {
-        let validate_steps = validate_call_info
-            .as_ref()
+        let validate_steps: usize = validate_call_infos
             .map(|call_info| call_info.charged_resources.vm_resources.n_steps)
-            .unwrap_or_default();
+            .sum();
 
         let overhead_steps =
             self.versioned_constants().os_resources_for_tx_type(tx_type, calldata_length).n_steps;
@@ -393,6 +753,7 @@ impl EntryPointExecutionContext {
 
             self.n_emitted_events = contract_revert_info.n_emitted_events;
             self.n_sent_messages_to_l1 = contract_revert_info.n_sent_messages_to_l1;
+            self.tx_context.call_counter.restore(contract_revert_info.quota_call_counts);
         }
 
         Ok(())
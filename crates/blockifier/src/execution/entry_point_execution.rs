@@ -10,6 +10,7 @@ use cairo_vm::vm::runners::builtin_runner::BuiltinRunner;
 use cairo_vm::vm::runners::cairo_runner::{CairoArg, CairoRunner, ExecutionResources};
 use cairo_vm::vm::security::verify_secure_runner;
 use num_traits::{ToPrimitive, Zero};
+use starknet_api::core::{ClassHash, EntryPointSelector};
 use starknet_api::execution_resources::GasAmount;
 use starknet_types_core::felt::Felt;
 
@@ -21,6 +22,7 @@ use crate::execution::entry_point::{
     EntryPointExecutionResult,
 };
 use crate::execution::errors::{EntryPointExecutionError, PostExecutionError, PreExecutionError};
+use crate::execution::execution_trace::collect_trace_entries;
 use crate::execution::execution_utils::{
     read_execution_retdata,
     write_felt,
@@ -88,8 +90,22 @@ pub fn execute_entry_point_call(
     // Execute.
     let bytecode_length = compiled_class.bytecode_length();
     let program_segment_size = bytecode_length + program_extra_data_length;
+    let declared_builtins = entry_point.builtins.clone();
     run_entry_point(&mut runner, &mut syscall_handler, entry_point, args, program_segment_size)?;
 
+    if syscall_handler.base.context.versioned_constants().validate_builtin_usage {
+        let vm_resources = runner
+            .get_execution_resources()
+            .map_err(VirtualMachineError::RunnerError)?
+            .filter_unused_builtins();
+        verify_used_builtins_declared(
+            &vm_resources,
+            &declared_builtins,
+            class_hash,
+            syscall_handler.base.call.entry_point_selector,
+        )?;
+    }
+
     // Collect the set PC values that were visited during the entry point execution.
     register_visited_pcs(
         &mut runner,
@@ -98,6 +114,11 @@ pub fn execute_entry_point_call(
         program_segment_size,
         bytecode_length,
     )?;
+    if let Some(execution_tracer) =
+        syscall_handler.base.context.tx_context.block_context.execution_tracer()
+    {
+        execution_tracer.record_steps(collect_trace_entries(&runner, program_segment_size)?);
+    }
 
     Ok(finalize_execution(
         runner,
@@ -112,7 +133,7 @@ pub fn execute_entry_point_call(
 fn register_visited_pcs(
     runner: &mut CairoRunner,
     state: &mut dyn State,
-    class_hash: starknet_api::core::ClassHash,
+    class_hash: ClassHash,
     program_segment_size: usize,
     bytecode_length: usize,
 ) -> EntryPointExecutionResult<()> {
@@ -140,6 +161,28 @@ fn register_visited_pcs(
     Ok(())
 }
 
+/// Verifies that the execution used only builtins declared by the executed entry point; using an
+/// undeclared builtin means the class was miscompiled, and the execution cannot be proven.
+pub fn verify_used_builtins_declared(
+    vm_resources: &ExecutionResources,
+    declared_builtins: &[BuiltinName],
+    class_hash: ClassHash,
+    selector: EntryPointSelector,
+) -> Result<(), PostExecutionError> {
+    let undeclared_builtin = vm_resources
+        .builtin_instance_counter
+        .iter()
+        .filter(|(builtin, n_instances)| **n_instances > 0 && !declared_builtins.contains(builtin))
+        .map(|(builtin, _)| *builtin)
+        .min_by_key(|builtin| builtin.to_str());
+    match undeclared_builtin {
+        Some(builtin) => {
+            Err(PostExecutionError::UndeclaredBuiltinUsed { builtin, class_hash, selector })
+        }
+        None => Ok(()),
+    }
+}
+
 pub fn initialize_execution_context<'a>(
     call: CallEntryPoint,
     compiled_class: &'a CompiledClassV1,
@@ -147,6 +190,7 @@ pub fn initialize_execution_context<'a>(
     context: &'a mut EntryPointExecutionContext,
 ) -> Result<VmExecutionContext<'a>, PreExecutionError> {
     let entry_point = compiled_class.get_entry_point(&call)?;
+    context.verify_builtins_allowed(&entry_point.builtins)?;
 
     // Instantiate Cairo runner.
     let proof_mode = false;
@@ -421,6 +465,21 @@ pub fn finalize_execution(
         TrackedResource::SierraGas => ExecutionResources::default(),
     };
 
+    if syscall_handler.base.context.tx_context.block_context.execution_profiler().is_some() {
+        let vm_resources = runner
+            .get_execution_resources()
+            .map_err(VirtualMachineError::RunnerError)?
+            .filter_unused_builtins();
+        let inner_calls_gas_consumed: u64 =
+            syscall_handler.base.inner_calls.iter().map(|call| call.execution.gas_consumed).sum();
+        syscall_handler.base.context.record_profile(
+            &syscall_handler.base.call,
+            &vm_resources,
+            &syscall_handler.syscall_counter,
+            call_result.gas_consumed.saturating_sub(inner_calls_gas_consumed),
+        );
+    }
+
     syscall_handler.finalize();
 
     let charged_resources_without_inner_calls = ChargedResources {
@@ -1,8 +1,12 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use assert_matches::assert_matches;
+use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use rstest::rstest;
 use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::class_hash;
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::transaction::fields::Calldata;
 
@@ -10,7 +14,11 @@ use crate::context::ChainInfo;
 use crate::execution::call_info::{CallExecution, CallInfo, ChargedResources};
 use crate::execution::contract_class::TrackedResource;
 use crate::execution::entry_point::CallEntryPoint;
-use crate::execution::entry_point_execution::gas_consumed_without_inner_calls;
+use crate::execution::entry_point_execution::{
+    gas_consumed_without_inner_calls,
+    verify_used_builtins_declared,
+};
+use crate::execution::errors::PostExecutionError;
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::initial_test_state::test_state;
 use crate::test_utils::syscall::build_recurse_calldata;
@@ -152,3 +160,34 @@ fn test_charged_resources_computation(
 
     assert_charged_resource_as_expected_rec(&call_info);
 }
+
+#[rstest]
+#[case::all_declared(&[BuiltinName::range_check, BuiltinName::bitwise], None)]
+#[case::unused_undeclared(&[BuiltinName::range_check, BuiltinName::pedersen], None)]
+#[case::used_undeclared(&[BuiltinName::range_check], Some(BuiltinName::bitwise))]
+fn test_verify_used_builtins_declared(
+    #[case] declared_builtins: &[BuiltinName],
+    #[case] expected_undeclared_builtin: Option<BuiltinName>,
+) {
+    let vm_resources = ExecutionResources {
+        builtin_instance_counter: HashMap::from([
+            (BuiltinName::range_check, 2),
+            (BuiltinName::bitwise, 1),
+            (BuiltinName::pedersen, 0),
+        ]),
+        ..Default::default()
+    };
+    let class_hash = class_hash!("0x1234");
+    let selector = selector_from_name("bitwise_and");
+
+    let result =
+        verify_used_builtins_declared(&vm_resources, declared_builtins, class_hash, selector);
+    match expected_undeclared_builtin {
+        None => result.unwrap(),
+        Some(expected_builtin) => assert_matches!(
+            result,
+            Err(PostExecutionError::UndeclaredBuiltinUsed { builtin, .. })
+                if builtin == expected_builtin
+        ),
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
+use assert_matches::assert_matches;
 use cairo_vm::types::builtin_name::BuiltinName;
 use num_bigint::BigInt;
 use pretty_assertions::assert_eq;
@@ -9,16 +11,29 @@ use starknet_api::execution_utils::format_panic_data;
 use starknet_api::transaction::fields::{Calldata, Fee};
 use starknet_api::{calldata, felt, storage_key};
 
-use crate::context::ChainInfo;
+use crate::context::{BlockContext, ChainInfo, TransactionContext};
 use crate::execution::call_info::{CallExecution, CallInfo};
-use crate::execution::entry_point::CallEntryPoint;
-use crate::retdata;
+use crate::execution::common_hints::ExecutionMode;
+use crate::execution::deprecated_entry_point_execution::CAIRO0_BUILTINS_NAMES;
+use crate::execution::entry_point::{
+    CallEntryPoint,
+    EntryPointExecutionContext,
+    EntryPointExecutionResult,
+};
+use crate::execution::errors::{EntryPointExecutionError, PreExecutionError};
+use crate::execution::syscalls::SyscallSelector;
 use crate::state::cached_state::CachedState;
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::dict_state_reader::DictStateReader;
 use crate::test_utils::initial_test_state::test_state;
 use crate::test_utils::{trivial_external_entry_point_new, CairoVersion, BALANCE};
-use crate::versioned_constants::VersionedConstants;
+use crate::transaction::objects::{CurrentTransactionInfo, TransactionInfo};
+use crate::versioned_constants::{
+    ExecutionPhaseConfig,
+    ExecutionPhaseRestrictions,
+    VersionedConstants,
+};
+use crate::{check_entry_point_execution_error_for_custom_hint, retdata};
 
 #[test]
 fn test_call_info_iteration() {
@@ -536,3 +551,94 @@ fn test_old_cairo1_entry_point_segment_arena() {
         2
     );
 }
+
+fn execute_in_restricted_validate_mode(
+    entry_point_call: CallEntryPoint,
+    state: &mut CachedState<DictStateReader>,
+    restrictions: ExecutionPhaseRestrictions,
+) -> EntryPointExecutionResult<CallInfo> {
+    let tx_context = TransactionContext {
+        block_context: BlockContext::create_for_testing(),
+        tx_info: TransactionInfo::Current(CurrentTransactionInfo::create_for_testing()),
+    };
+    let versioned_constants = &tx_context.block_context.versioned_constants;
+    let phase_config = ExecutionPhaseConfig {
+        restrictions,
+        ..versioned_constants.execution_phase_config(&ExecutionMode::Validate)
+    };
+    let limit_steps_by_resources = false;
+    let mut context = EntryPointExecutionContext::new_with_phase_config(
+        Arc::new(tx_context),
+        ExecutionMode::Validate,
+        phase_config,
+        limit_steps_by_resources,
+    );
+    let mut remaining_gas = entry_point_call.initial_gas;
+    entry_point_call.execute(state, &mut context, &mut remaining_gas)
+}
+
+#[test]
+fn test_syscall_not_allowed_in_execution_phase() {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let mut state = test_state(&ChainInfo::create_for_testing(), Fee(0), &[(test_contract, 1)]);
+    let entry_point_call = CallEntryPoint {
+        calldata: calldata![felt!(1234_u16), felt!(18_u8)],
+        entry_point_selector: selector_from_name("test_storage_read_write"),
+        ..trivial_external_entry_point_new(test_contract)
+    };
+    let restrictions = |allowed_syscalls: &[SyscallSelector]| ExecutionPhaseRestrictions {
+        allowed_syscalls: Some(allowed_syscalls.iter().copied().collect()),
+        ..Default::default()
+    };
+
+    let error = execute_in_restricted_validate_mode(
+        entry_point_call.clone(),
+        &mut state,
+        restrictions(&[SyscallSelector::StorageWrite]),
+    )
+    .unwrap_err();
+    check_entry_point_execution_error_for_custom_hint!(
+        &error,
+        "Unauthorized syscall StorageRead in execution mode Validate.",
+    );
+
+    let call_info = execute_in_restricted_validate_mode(
+        entry_point_call,
+        &mut state,
+        restrictions(&[SyscallSelector::StorageWrite, SyscallSelector::StorageRead]),
+    )
+    .unwrap();
+    assert_eq!(call_info.execution.retdata, retdata![felt!(18_u8)]);
+}
+
+#[test]
+fn test_builtin_not_allowed_in_execution_phase() {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let mut state = test_state(&ChainInfo::create_for_testing(), Fee(0), &[(test_contract, 1)]);
+    let entry_point_call = CallEntryPoint {
+        calldata: calldata![felt!(47_u8), felt!(31_u8)],
+        entry_point_selector: selector_from_name("bitwise_and"),
+        ..trivial_external_entry_point_new(test_contract)
+    };
+    let restrictions = ExecutionPhaseRestrictions {
+        allowed_builtins: Some(
+            CAIRO0_BUILTINS_NAMES
+                .into_iter()
+                .filter(|builtin| *builtin != BuiltinName::bitwise)
+                .collect(),
+        ),
+        ..Default::default()
+    };
+
+    let error = execute_in_restricted_validate_mode(entry_point_call, &mut state, restrictions)
+        .unwrap_err();
+    assert_matches!(
+        error,
+        EntryPointExecutionError::PreExecutionError(
+            PreExecutionError::BuiltinNotAllowedInExecutionMode {
+                builtin: BuiltinName::bitwise,
+                execution_mode: ExecutionMode::Validate,
+            }
+        )
+    );
+}
@@ -14,6 +14,7 @@ use starknet_api::contract_class::EntryPointType;
 use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector};
 use thiserror::Error;
 
+use crate::execution::common_hints::ExecutionMode;
 use crate::execution::entry_point::ConstructorContext;
 use crate::execution::stack_trace::Cairo1RevertSummary;
 #[cfg(feature = "cairo_native")]
@@ -24,6 +25,10 @@ use crate::state::errors::StateError;
 
 #[derive(Debug, Error)]
 pub enum PreExecutionError {
+    #[error("Builtin {builtin} is not allowed in execution mode {execution_mode}.")]
+    BuiltinNotAllowedInExecutionMode { builtin: BuiltinName, execution_mode: ExecutionMode },
+    #[error("Cairo 0 class {class_hash} cannot be executed in Sierra gas only mode.")]
+    Cairo0ExecutionInSierraGasOnlyMode { class_hash: ClassHash },
     #[error("Entry point {:#064x} of type {typ:?} is not unique.", .selector.0)]
     DuplicatedEntryPointSelector { selector: EntryPointSelector, typ: EntryPointType },
     #[error("Entry point {0:?} not found in contract.")]
@@ -72,6 +77,16 @@ pub enum PostExecutionError {
     VirtualMachineError(#[from] VirtualMachineError),
     #[error("Malformed return data : {error_message}.")]
     MalformedReturnData { error_message: String },
+    #[error(
+        "Entry point {:#064x} of class {class_hash} used the builtin {builtin}, which it does not \
+         declare.",
+        .selector.0
+    )]
+    UndeclaredBuiltinUsed {
+        builtin: BuiltinName,
+        class_hash: ClassHash,
+        selector: EntryPointSelector,
+    },
 }
 
 impl From<RunnerError> for PostExecutionError {
@@ -91,6 +106,15 @@ pub enum EntryPointExecutionError {
     #[error("Invalid input: {input_descriptor}; {info}")]
     InvalidExecutionInput { input_descriptor: String, info: String },
     #[cfg(feature = "cairo_native")]
+    #[error("Native execution of class {class_hash} panicked.")]
+    NativeExecutionPanicked { class_hash: ClassHash },
+    #[cfg(feature = "cairo_native")]
+    #[error(
+        "Native execution of class {class_hash} grew the resident memory by {memory_growth} \
+         bytes, beyond the limit."
+    )]
+    NativeMemoryLimitExceeded { class_hash: ClassHash, memory_growth: usize },
+    #[cfg(feature = "cairo_native")]
     #[error(transparent)]
     NativeUnexpectedError(#[from] NativeError),
     #[cfg(feature = "cairo_native")]
@@ -100,6 +124,16 @@ pub enum EntryPointExecutionError {
     PostExecutionError(#[from] PostExecutionError),
     #[error(transparent)]
     PreExecutionError(#[from] PreExecutionError),
+    #[error("Execution was cancelled.")]
+    ExecutionCancelled,
+    #[error(
+        "Execution quota exceeded by contract {storage_address:?} of class {class_hash}: {reason}"
+    )]
+    ExecutionQuotaExceeded {
+        class_hash: ClassHash,
+        storage_address: ContractAddress,
+        reason: String,
+    },
     #[error("Execution failed due to recursion depth exceeded.")]
     RecursionDepthExceeded,
     #[error(transparent)]
@@ -0,0 +1,296 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::execution_resources::GasAmount;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::call_info::CallInfo;
+use crate::versioned_constants::VersionedConstants;
+
+#[cfg(test)]
+#[path = "execution_quotas_test.rs"]
+pub mod test;
+
+/// The execution limits of a contract class or a contract address.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionQuota {
+    /// The maximal number of VM steps of a call, excluding its inner calls. Applies to calls
+    /// metered by Cairo steps.
+    pub max_steps: Option<usize>,
+    /// The maximal L2 gas charged for a call, excluding its inner calls.
+    pub max_l2_gas: Option<GasAmount>,
+    /// The maximal number of calls in a block.
+    pub max_calls_per_block: Option<usize>,
+}
+
+/// The subject of an execution quota.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum QuotaSubject {
+    Class(ClassHash),
+    Address(ContractAddress),
+}
+
+impl Display for QuotaSubject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Class(class_hash) => write!(f, "class {class_hash}"),
+            Self::Address(address) => write!(f, "address {}", address.0.key()),
+        }
+    }
+}
+
+/// Execution quotas by class hash and by contract address, with which operators may contain
+/// abusive contracts. A call is subject to the quotas of both its class and its storage address;
+/// a call exceeding a quota fails its transaction.
+/// Serialized as space-separated `<class|address>:<hex>:<limits>` entries, where the limits are
+/// comma-separated `steps=<n>`, `l2_gas=<n>` or `calls=<n>` bounds, e.g.,
+/// `"class:0x1234:steps=100000,calls=10 address:0x5678:l2_gas=5000000"`.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ExecutionQuotas {
+    pub by_class_hash: BTreeMap<ClassHash, ExecutionQuota>,
+    pub by_address: BTreeMap<ContractAddress, ExecutionQuota>,
+}
+
+impl ExecutionQuotas {
+    pub fn is_empty(&self) -> bool {
+        self.by_class_hash.is_empty() && self.by_address.is_empty()
+    }
+
+    fn quotas_of(
+        &self,
+        class_hash: ClassHash,
+        address: ContractAddress,
+    ) -> impl Iterator<Item = (QuotaSubject, &ExecutionQuota)> {
+        let class_quota = self.by_class_hash.get(&class_hash);
+        let address_quota = self.by_address.get(&address);
+        class_quota
+            .map(|quota| (QuotaSubject::Class(class_hash), quota))
+            .into_iter()
+            .chain(address_quota.map(|quota| (QuotaSubject::Address(address), quota)))
+    }
+
+    /// Counts a call of the given class at the given address in the calls of its transaction;
+    /// returns the reason of the failure if the committed calls of the block and the calls of the
+    /// transaction so far exceed a quota, in which case the call is not counted.
+    pub(crate) fn count_call(
+        &self,
+        block_call_counter: &BlockCallCounter,
+        tx_call_counts: &mut QuotaCallCounts,
+        class_hash: ClassHash,
+        address: ContractAddress,
+    ) -> Result<(), String> {
+        let limited_subjects: Vec<_> = self
+            .quotas_of(class_hash, address)
+            .filter_map(|(subject, quota)| Some((subject, quota.max_calls_per_block?)))
+            .collect();
+        if limited_subjects.is_empty() {
+            return Ok(());
+        }
+
+        for (subject, max_calls) in &limited_subjects {
+            let n_calls = block_call_counter.n_calls(*subject) + tx_call_counts.n_calls(*subject);
+            if n_calls >= *max_calls {
+                return Err(format!("The calls of {subject} in the block exceed {max_calls}."));
+            }
+        }
+        for (subject, _) in limited_subjects {
+            *tx_call_counts.0.entry(subject).or_default() += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the reason of the failure if the resources of the given executed call, excluding
+    /// its inner calls, exceed a quota.
+    pub(crate) fn check_call_resources(
+        &self,
+        call_info: &CallInfo,
+        versioned_constants: &VersionedConstants,
+    ) -> Result<(), String> {
+        let class_hash = call_info.call.class_hash.unwrap_or_default();
+        let mut quotas = self.quotas_of(class_hash, call_info.call.storage_address).peekable();
+        if quotas.peek().is_none() {
+            return Ok(());
+        }
+
+        let charged_resources = call_info.charged_resources_without_inner_calls();
+        let n_steps = charged_resources.vm_resources.n_steps;
+        let l2_gas = charged_resources.to_l2_gas(versioned_constants);
+        for (subject, quota) in quotas {
+            if let Some(max_steps) = quota.max_steps.filter(|max_steps| n_steps > *max_steps) {
+                return Err(format!("The call ran {n_steps} steps; {subject} allows {max_steps}."));
+            }
+            if let Some(max_l2_gas) = quota.max_l2_gas.filter(|max_l2_gas| l2_gas > *max_l2_gas) {
+                return Err(format!(
+                    "The call charged {l2_gas} L2 gas; {subject} allows {max_l2_gas}."
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<ExecutionQuotas> for String {
+    fn from(execution_quotas: ExecutionQuotas) -> Self {
+        let class_entries = execution_quotas
+            .by_class_hash
+            .iter()
+            .map(|(class_hash, quota)| ("class", class_hash.0, quota));
+        let address_entries = execution_quotas
+            .by_address
+            .iter()
+            .map(|(address, quota)| ("address", *address.0.key(), quota));
+        class_entries
+            .chain(address_entries)
+            .map(|(kind, key, quota)| {
+                let ExecutionQuota { max_steps, max_l2_gas, max_calls_per_block } = quota;
+                let limits = [
+                    max_steps.map(|max_steps| format!("steps={max_steps}")),
+                    max_l2_gas.map(|max_l2_gas| format!("l2_gas={}", max_l2_gas.0)),
+                    max_calls_per_block.map(|max_calls| format!("calls={max_calls}")),
+                ];
+                let limits: Vec<String> = limits.into_iter().flatten().collect();
+                format!("{kind}:{key:#x}:{}", limits.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl TryFrom<String> for ExecutionQuotas {
+    type Error = String;
+
+    fn try_from(execution_quotas: String) -> Result<Self, String> {
+        let mut quotas = Self::default();
+        for entry in execution_quotas.split_whitespace() {
+            let invalid_entry = || format!("Invalid execution quota: {entry}.");
+            let [kind, key, limits] = entry.split(':').collect::<Vec<_>>()[..] else {
+                return Err(invalid_entry());
+            };
+            let key = Felt::from_hex(key).map_err(|_| invalid_entry())?;
+
+            let mut quota = ExecutionQuota::default();
+            for limit in limits.split(',') {
+                let (name, value) = limit.split_once('=').ok_or_else(invalid_entry)?;
+                let value: u64 = value.parse().map_err(|_| invalid_entry())?;
+                let as_usize = || usize::try_from(value).map_err(|_| invalid_entry());
+                match name {
+                    "steps" => quota.max_steps = Some(as_usize()?),
+                    "l2_gas" => quota.max_l2_gas = Some(GasAmount(value)),
+                    "calls" => quota.max_calls_per_block = Some(as_usize()?),
+                    _ => return Err(invalid_entry()),
+                }
+            }
+
+            let is_duplicate = match kind {
+                "class" => quotas.by_class_hash.insert(ClassHash(key), quota).is_some(),
+                "address" => {
+                    let address = ContractAddress::try_from(key).map_err(|_| invalid_entry())?;
+                    quotas.by_address.insert(address, quota).is_some()
+                }
+                _ => return Err(invalid_entry()),
+            };
+            if is_duplicate {
+                return Err(format!("Multiple execution quotas for {kind} {key:#x}."));
+            }
+        }
+        Ok(quotas)
+    }
+}
+
+/// The calls subject to call quotas, by subject.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QuotaCallCounts(HashMap<QuotaSubject, usize>);
+
+impl QuotaCallCounts {
+    pub fn n_calls(&self, subject: QuotaSubject) -> usize {
+        self.0.get(&subject).copied().unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct CommittedCallCounts {
+    counts: QuotaCallCounts,
+    // The number of commits that changed the counts.
+    n_updates: usize,
+}
+
+/// Counts the calls of the committed transactions of the block subject to call quotas. Shared by
+/// the clones of a block context, i.e., by all the transactions of the block.
+///
+/// The calls of a transaction are counted into the block only once it is committed, so that calls
+/// of rejected transactions, of reverted calls and of discarded executions do not use the quotas.
+#[derive(Clone, Debug, Default)]
+pub struct BlockCallCounter(Arc<Mutex<CommittedCallCounts>>);
+
+impl BlockCallCounter {
+    fn lock(&self) -> std::sync::MutexGuard<'_, CommittedCallCounts> {
+        self.0.lock().expect("Block call counter lock is poisoned.")
+    }
+
+    pub fn n_calls(&self, subject: QuotaSubject) -> usize {
+        self.lock().counts.n_calls(subject)
+    }
+
+    /// The number of committed transactions that made calls subject to call quotas. An execution
+    /// that started at a different number may have seen other counts.
+    pub(crate) fn n_updates(&self) -> usize {
+        self.lock().n_updates
+    }
+
+    /// Returns a counter starting from the calls committed so far, which is not shared with this
+    /// one; e.g., for executions that are never committed to the block.
+    pub(crate) fn detached(&self) -> Self {
+        Self(Arc::new(Mutex::new(self.lock().clone())))
+    }
+
+    /// Adds the calls of a committed transaction to the calls of the block.
+    pub(crate) fn commit(&self, tx_call_counts: &QuotaCallCounts) {
+        if tx_call_counts.is_empty() {
+            return;
+        }
+        let mut committed = self.lock();
+        for (subject, n_calls) in &tx_call_counts.0 {
+            *committed.counts.0.entry(*subject).or_default() += n_calls;
+        }
+        committed.n_updates += 1;
+    }
+}
+
+/// Counts the calls of a single transaction subject to call quotas, across its execution phases.
+/// Shared by the execution contexts of the transaction.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionCallCounter(Arc<Mutex<QuotaCallCounts>>);
+
+impl TransactionCallCounter {
+    fn lock(&self) -> std::sync::MutexGuard<'_, QuotaCallCounts> {
+        self.0.lock().expect("Transaction call counter lock is poisoned.")
+    }
+
+    /// Counts a call against the given quotas; see `ExecutionQuotas::count_call`.
+    pub(crate) fn count_call(
+        &self,
+        execution_quotas: &ExecutionQuotas,
+        block_call_counter: &BlockCallCounter,
+        class_hash: ClassHash,
+        address: ContractAddress,
+    ) -> Result<(), String> {
+        execution_quotas.count_call(block_call_counter, &mut self.lock(), class_hash, address)
+    }
+
+    pub fn snapshot(&self) -> QuotaCallCounts {
+        self.lock().clone()
+    }
+
+    /// Discards the calls counted since the given snapshot was taken.
+    pub(crate) fn restore(&self, snapshot: QuotaCallCounts) {
+        *self.lock() = snapshot;
+    }
+}
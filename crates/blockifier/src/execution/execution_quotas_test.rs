@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::ClassHash;
+use starknet_api::execution_resources::GasAmount;
+use starknet_api::{class_hash, contract_address};
+
+use crate::execution::call_info::{CallInfo, ChargedResources};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::execution_quotas::{
+    BlockCallCounter,
+    ExecutionQuota,
+    ExecutionQuotas,
+    QuotaCallCounts,
+    QuotaSubject,
+};
+use crate::versioned_constants::VersionedConstants;
+
+#[test]
+fn test_execution_quotas_serialization() {
+    let execution_quotas = ExecutionQuotas {
+        by_class_hash: BTreeMap::from([(
+            class_hash!("0x1234"),
+            ExecutionQuota {
+                max_steps: Some(100000),
+                max_l2_gas: None,
+                max_calls_per_block: Some(10),
+            },
+        )]),
+        by_address: BTreeMap::from([(
+            contract_address!("0x5678"),
+            ExecutionQuota { max_l2_gas: Some(GasAmount(5000000)), ..Default::default() },
+        )]),
+    };
+    let serialized = "class:0x1234:steps=100000,calls=10 address:0x5678:l2_gas=5000000";
+
+    assert_eq!(String::from(execution_quotas.clone()), serialized);
+    assert_eq!(ExecutionQuotas::try_from(serialized.to_string()).unwrap(), execution_quotas);
+    assert_eq!(ExecutionQuotas::try_from(String::new()).unwrap(), ExecutionQuotas::default());
+}
+
+#[rstest]
+#[case::missing_limits("class:0x1234")]
+#[case::unknown_subject("contract:0x1234:calls=1")]
+#[case::unknown_limit("class:0x1234:events=1")]
+#[case::invalid_value("class:0x1234:calls=-1")]
+#[case::invalid_hash("class:1234x:calls=1")]
+#[case::duplicate_subject("class:0x1234:calls=1 class:0x1234:steps=1")]
+fn test_invalid_execution_quotas(#[case] serialized: &str) {
+    assert!(ExecutionQuotas::try_from(serialized.to_string()).is_err());
+}
+
+#[test]
+fn test_count_call() {
+    let (limited_class, other_class) = (class_hash!("0x1"), class_hash!("0x2"));
+    let (limited_address, other_address) = (contract_address!("0x10"), contract_address!("0x20"));
+    let execution_quotas = ExecutionQuotas::try_from(format!(
+        "class:{:#x}:calls=2 address:{:#x}:calls=1",
+        limited_class.0,
+        limited_address.0.key()
+    ))
+    .unwrap();
+    let block_counter = BlockCallCounter::default();
+    let mut committed_tx_counts = QuotaCallCounts::default();
+    execution_quotas
+        .count_call(&block_counter, &mut committed_tx_counts, limited_class, other_address)
+        .unwrap();
+    block_counter.commit(&committed_tx_counts);
+    assert_eq!(block_counter.n_updates(), 1);
+
+    let mut tx_counts = QuotaCallCounts::default();
+    let mut count_call = |class_hash, address| {
+        execution_quotas.count_call(&block_counter, &mut tx_counts, class_hash, address)
+    };
+    count_call(other_class, limited_address).unwrap();
+    // The address exhausted its quota; the rejected call is not counted against its class.
+    count_call(limited_class, limited_address).unwrap_err();
+    count_call(limited_class, other_address).unwrap();
+    // The committed calls and the calls of the transaction exhausted the quota of the class.
+    count_call(limited_class, other_address).unwrap_err();
+    // Calls not subject to quotas are not counted.
+    count_call(other_class, other_address).unwrap();
+
+    assert_eq!(tx_counts.n_calls(QuotaSubject::Class(limited_class)), 1);
+    assert_eq!(tx_counts.n_calls(QuotaSubject::Address(limited_address)), 1);
+    assert_eq!(tx_counts.n_calls(QuotaSubject::Class(other_class)), 0);
+    // The calls of a transaction are added to the block only once it is committed.
+    assert_eq!(block_counter.n_calls(QuotaSubject::Class(limited_class)), 1);
+    block_counter.commit(&tx_counts);
+    assert_eq!(block_counter.n_calls(QuotaSubject::Class(limited_class)), 2);
+    assert_eq!(block_counter.n_calls(QuotaSubject::Address(limited_address)), 1);
+    assert_eq!(block_counter.n_updates(), 2);
+    // Clones share the counts; transactions without limited calls do not update them.
+    block_counter.clone().commit(&QuotaCallCounts::default());
+    assert_eq!(block_counter.clone().n_calls(QuotaSubject::Class(limited_class)), 2);
+    assert_eq!(block_counter.n_updates(), 2);
+}
+
+fn call_info(class_hash: ClassHash, charged_resources: ChargedResources) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint { class_hash: Some(class_hash), ..Default::default() },
+        charged_resources,
+        ..Default::default()
+    }
+}
+
+#[rstest]
+#[case::steps_within_quota(ChargedResources::from_execution_resources(ExecutionResources {
+    n_steps: 100,
+    ..Default::default()
+}), true)]
+#[case::steps_exceeded(ChargedResources::from_execution_resources(ExecutionResources {
+    n_steps: 101,
+    ..Default::default()
+}), false)]
+#[case::l2_gas_within_quota(ChargedResources::from_gas(GasAmount(1000000)), true)]
+#[case::l2_gas_exceeded(ChargedResources::from_gas(GasAmount(1000001)), false)]
+fn test_check_call_resources(
+    #[case] charged_resources: ChargedResources,
+    #[case] expected_within_quota: bool,
+) {
+    let (limited_class, other_class) = (class_hash!("0x1"), class_hash!("0x2"));
+    let execution_quotas =
+        ExecutionQuotas::try_from(format!("class:{:#x}:steps=100,l2_gas=1000000", limited_class.0))
+            .unwrap();
+    let versioned_constants = VersionedConstants::create_for_testing();
+
+    let result = execution_quotas.check_call_resources(
+        &call_info(limited_class, charged_resources.clone()),
+        &versioned_constants,
+    );
+    assert_eq!(result.is_ok(), expected_within_quota);
+
+    // Calls of other classes are not subject to the quota.
+    execution_quotas
+        .check_call_resources(&call_info(other_class, charged_resources), &versioned_constants)
+        .unwrap();
+}
@@ -0,0 +1,284 @@
+use std::sync::{Arc, Mutex};
+
+use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
+use cairo_vm::vm::runners::cairo_runner::CairoRunner;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use num_traits::ToPrimitive;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::entry_point::{CallEntryPoint, EntryPointExecutionResult};
+use crate::execution::errors::EntryPointExecutionError;
+use crate::execution::syscalls::SyscallSelector;
+
+#[cfg(test)]
+#[path = "execution_trace_test.rs"]
+pub mod test;
+
+// The segment indices the Cairo runner assigns to the program and to the execution stack.
+const PROGRAM_SEGMENT_INDEX: isize = 0;
+const EXECUTION_SEGMENT_INDEX: isize = 1;
+
+// Layout of an encoded Cairo instruction: three biased 16-bit offsets followed by the flags.
+const OFFSET_BITS: u64 = 16;
+const OFFSET_BIAS: isize = 1 << 15;
+const FLAGS_SHIFT: u64 = 48;
+const DST_REG_FP_FLAG: u64 = 1 << 0;
+const OP0_REG_FP_FLAG: u64 = 1 << 1;
+const OP1_SRC_SHIFT: u64 = 2;
+const OP1_SRC_MASK: u64 = 0b111;
+const OP1_SRC_OP0: u64 = 0b000;
+const OP1_SRC_IMM: u64 = 0b001;
+const OP1_SRC_FP: u64 = 0b010;
+const OP1_SRC_AP: u64 = 0b100;
+
+/// A memory cell accessed by a Cairo step, with the value it holds.
+/// Cairo memory is write-once, so the value is the one the step read or wrote.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemoryAccess {
+    pub address: Relocatable,
+    pub value: Option<MaybeRelocatable>,
+}
+
+/// A single Cairo VM step: the registers before the step, the executed instruction and the
+/// memory cells of its destination and operands.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    pub pc: Relocatable,
+    pub ap: Relocatable,
+    pub fp: Relocatable,
+    pub instruction: Felt,
+    pub memory_accesses: Vec<MemoryAccess>,
+}
+
+/// A syscall invoked by a call, located by the step at which the VM stopped to execute it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyscallTrace {
+    pub selector: SyscallSelector,
+    pub step: usize,
+    /// Indices (into `ExecutionTrace::calls`) of the calls made by the syscall.
+    pub inner_calls: Vec<usize>,
+}
+
+/// The trace of a single entry point call.
+/// VM steps are only recorded for calls run in the Cairo VM; natively executed calls appear with
+/// no steps and no syscalls.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallTrace {
+    pub call: CallEntryPoint,
+    /// Index (into `ExecutionTrace::calls`) of the calling entry point; [None] for top-level
+    /// calls.
+    pub parent: Option<usize>,
+    pub steps: Vec<TraceEntry>,
+    pub syscalls: Vec<SyscallTrace>,
+}
+
+/// A step-by-step trace of a transaction execution, with calls in the order they were entered.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionTrace {
+    pub calls: Vec<CallTrace>,
+}
+
+impl ExecutionTrace {
+    /// Returns the calls made directly by the transaction (validate, execute, fee transfer, ...).
+    pub fn top_level_calls(&self) -> impl Iterator<Item = &CallTrace> {
+        self.calls.iter().filter(|call_trace| call_trace.parent.is_none())
+    }
+
+    pub fn n_steps(&self) -> usize {
+        self.calls.iter().map(|call_trace| call_trace.steps.len()).sum()
+    }
+}
+
+/// The length of a trace being recorded, to discard what is recorded after it; see
+/// `ExecutionTracer::checkpoint`.
+#[derive(Debug)]
+pub struct TraceCheckpoint {
+    n_calls: usize,
+    call_stack_depth: usize,
+    // The number of syscalls of the currently executing call.
+    n_active_call_syscalls: usize,
+}
+
+#[derive(Debug)]
+struct ActiveCall {
+    index: usize,
+    // The syscall currently being executed by the call, if any.
+    open_syscall: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct ExecutionTracerState {
+    trace: ExecutionTrace,
+    call_stack: Vec<ActiveCall>,
+}
+
+/// Records an `ExecutionTrace` while entry points are executed.
+/// Clones share the same trace, so the tracer can be handed to the block context and read back
+/// once execution is done.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionTracer(Arc<Mutex<ExecutionTracerState>>);
+
+impl ExecutionTracer {
+    pub fn enter_call(&self, call: &CallEntryPoint) {
+        let mut state = self.lock();
+        let ExecutionTracerState { trace, call_stack } = &mut *state;
+        let index = trace.calls.len();
+        let parent = call_stack.last().map(|active_call| active_call.index);
+        if let Some(ActiveCall { index: parent_index, open_syscall: Some(syscall_index) }) =
+            call_stack.last()
+        {
+            trace.calls[*parent_index].syscalls[*syscall_index].inner_calls.push(index);
+        }
+        trace.calls.push(CallTrace { call: call.clone(), parent, steps: vec![], syscalls: vec![] });
+        call_stack.push(ActiveCall { index, open_syscall: None });
+    }
+
+    pub fn exit_call(&self) {
+        self.lock().call_stack.pop().expect("Exited a call that was not entered.");
+    }
+
+    pub fn enter_syscall(&self, selector: SyscallSelector, step: usize) {
+        let mut state = self.lock();
+        let ExecutionTracerState { trace, call_stack } = &mut *state;
+        let active_call = call_stack.last_mut().expect("Syscall invoked outside of a call.");
+        let syscalls = &mut trace.calls[active_call.index].syscalls;
+        active_call.open_syscall = Some(syscalls.len());
+        syscalls.push(SyscallTrace { selector, step, inner_calls: vec![] });
+    }
+
+    pub fn exit_syscall(&self) {
+        let mut state = self.lock();
+        let active_call = state.call_stack.last_mut().expect("Syscall invoked outside of a call.");
+        active_call.open_syscall = None;
+    }
+
+    /// Sets the VM steps of the currently executing call.
+    pub fn record_steps(&self, steps: Vec<TraceEntry>) {
+        let mut state = self.lock();
+        let index = state.call_stack.last().expect("Steps recorded outside of a call.").index;
+        state.trace.calls[index].steps = steps;
+    }
+
+    /// Returns the current length of the trace.
+    pub fn checkpoint(&self) -> TraceCheckpoint {
+        let state = self.lock();
+        let n_active_call_syscalls = state
+            .call_stack
+            .last()
+            .map_or(0, |active_call| state.trace.calls[active_call.index].syscalls.len());
+        TraceCheckpoint {
+            n_calls: state.trace.calls.len(),
+            call_stack_depth: state.call_stack.len(),
+            n_active_call_syscalls,
+        }
+    }
+
+    /// Discards everything recorded since the given checkpoint.
+    pub fn rollback(&self, checkpoint: TraceCheckpoint) {
+        let mut state = self.lock();
+        let ExecutionTracerState { trace, call_stack } = &mut *state;
+        trace.calls.truncate(checkpoint.n_calls);
+        for call_trace in &mut trace.calls {
+            for syscall_trace in &mut call_trace.syscalls {
+                syscall_trace.inner_calls.retain(|index| *index < checkpoint.n_calls);
+            }
+        }
+        call_stack.truncate(checkpoint.call_stack_depth);
+        if let Some(active_call) = call_stack.last_mut() {
+            let call_trace = &mut trace.calls[active_call.index];
+            call_trace.syscalls.truncate(checkpoint.n_active_call_syscalls);
+            call_trace.steps.clear();
+            active_call.open_syscall = None;
+        }
+    }
+
+    /// Returns the recorded trace, leaving the tracer empty.
+    pub fn take_trace(&self) -> ExecutionTrace {
+        std::mem::take(&mut self.lock().trace)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ExecutionTracerState> {
+        self.0.lock().expect("Execution tracer lock is poisoned.")
+    }
+}
+
+/// Builds the trace entries of a finished run from its relocated trace, in which the program
+/// segment starts at address 1 and the execution segment right after it.
+pub fn collect_trace_entries(
+    runner: &CairoRunner,
+    program_segment_size: usize,
+) -> EntryPointExecutionResult<Vec<TraceEntry>> {
+    let relocated_trace = runner.relocated_trace.as_ref().ok_or_else(|| {
+        EntryPointExecutionError::InternalError("Relocated trace not found.".into())
+    })?;
+    let execution_segment_start = 1 + program_segment_size;
+    let unrelocate = |segment_index: isize, segment_start: usize, address: usize| {
+        address.checked_sub(segment_start).map(|offset| Relocatable::from((segment_index, offset)))
+    };
+
+    relocated_trace
+        .iter()
+        .map(|trace_entry| {
+            let (Some(pc), Some(ap), Some(fp)) = (
+                unrelocate(PROGRAM_SEGMENT_INDEX, 1, trace_entry.pc),
+                unrelocate(EXECUTION_SEGMENT_INDEX, execution_segment_start, trace_entry.ap),
+                unrelocate(EXECUTION_SEGMENT_INDEX, execution_segment_start, trace_entry.fp),
+            ) else {
+                return Err(EntryPointExecutionError::InternalError(format!(
+                    "Invalid registers in trace: {trace_entry:?}."
+                )));
+            };
+            let Some(MaybeRelocatable::Int(instruction)) = runner.vm.get_maybe(&pc) else {
+                return Err(EntryPointExecutionError::InternalError(format!(
+                    "No instruction at pc={pc}."
+                )));
+            };
+            let memory_accesses = operand_addresses(&runner.vm, &instruction, pc, ap, fp)
+                .into_iter()
+                .map(|address| MemoryAccess { address, value: runner.vm.get_maybe(&address) })
+                .collect();
+
+            Ok(TraceEntry { pc, ap, fp, instruction, memory_accesses })
+        })
+        .collect()
+}
+
+/// Returns the addresses of the destination and operands of the given instruction.
+/// Addresses that cannot be computed (e.g., an operand read through a non-pointer) are omitted.
+fn operand_addresses(
+    vm: &VirtualMachine,
+    instruction: &Felt,
+    pc: Relocatable,
+    ap: Relocatable,
+    fp: Relocatable,
+) -> Vec<Relocatable> {
+    let Some(encoded_instruction) = instruction.to_u64() else {
+        return vec![];
+    };
+    let offset = |index: u64| {
+        let biased_offset = (encoded_instruction >> (index * OFFSET_BITS)) & 0xffff;
+        isize::try_from(biased_offset).expect("16-bit values fit in isize.") - OFFSET_BIAS
+    };
+    let flags = encoded_instruction >> FLAGS_SHIFT;
+    let register = |fp_flag: u64| if flags & fp_flag == 0 { ap } else { fp };
+
+    let dst_address = add_offset(register(DST_REG_FP_FLAG), offset(0));
+    let op0_address = add_offset(register(OP0_REG_FP_FLAG), offset(1));
+    let op1_base = match (flags >> OP1_SRC_SHIFT) & OP1_SRC_MASK {
+        OP1_SRC_OP0 => match op0_address.and_then(|address| vm.get_maybe(&address)) {
+            Some(MaybeRelocatable::RelocatableValue(op0)) => Some(op0),
+            _ => None,
+        },
+        OP1_SRC_IMM => Some(pc),
+        OP1_SRC_FP => Some(fp),
+        OP1_SRC_AP => Some(ap),
+        _ => None,
+    };
+    let op1_address = op1_base.and_then(|base| add_offset(base, offset(2)));
+
+    [dst_address, op0_address, op1_address].into_iter().flatten().collect()
+}
+
+fn add_offset(base: Relocatable, offset: isize) -> Option<Relocatable> {
+    Some(Relocatable::from((base.segment_index, base.offset.checked_add_signed(offset)?)))
+}
@@ -0,0 +1,153 @@
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::invoke_tx_args;
+use starknet_api::transaction::fields::ValidResourceBounds;
+use starknet_types_core::felt::Felt;
+
+use crate::context::{BlockContext, ChainInfo};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::execution_trace::{ExecutionTracer, SyscallTrace};
+use crate::execution::syscalls::SyscallSelector;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, RunnableCairo1, BALANCE};
+use crate::transaction::test_utils::{
+    block_context,
+    default_all_resource_bounds,
+    invoke_tx_with_default_flags,
+};
+use crate::transaction::transactions::ExecutableTransaction;
+
+#[test]
+fn test_tracer_links_inner_calls_to_syscalls() {
+    let execution_tracer = ExecutionTracer::default();
+    let outer_call = CallEntryPoint::default();
+    let inner_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("inner"),
+        ..CallEntryPoint::default()
+    };
+
+    execution_tracer.enter_call(&outer_call);
+    execution_tracer.enter_syscall(SyscallSelector::StorageRead, 3);
+    execution_tracer.exit_syscall();
+    execution_tracer.enter_syscall(SyscallSelector::CallContract, 7);
+    execution_tracer.enter_call(&inner_call);
+    execution_tracer.exit_call();
+    execution_tracer.exit_syscall();
+    execution_tracer.exit_call();
+
+    let trace = execution_tracer.take_trace();
+    assert_eq!(trace.calls.len(), 2);
+    assert_eq!(trace.calls[0].parent, None);
+    assert_eq!(
+        trace.calls[0].syscalls,
+        vec![
+            SyscallTrace { selector: SyscallSelector::StorageRead, step: 3, inner_calls: vec![] },
+            SyscallTrace { selector: SyscallSelector::CallContract, step: 7, inner_calls: vec![1] },
+        ]
+    );
+    assert_eq!(trace.calls[1].call, inner_call);
+    assert_eq!(trace.calls[1].parent, Some(0));
+    assert_eq!(trace.top_level_calls().count(), 1);
+
+    // The trace is handed over to the caller.
+    assert_eq!(execution_tracer.take_trace().calls.len(), 0);
+}
+
+#[test]
+fn test_tracer_rollback() {
+    let execution_tracer = ExecutionTracer::default();
+    let outer_call = CallEntryPoint::default();
+    let inner_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("inner"),
+        ..CallEntryPoint::default()
+    };
+
+    execution_tracer.enter_call(&outer_call);
+    execution_tracer.enter_syscall(SyscallSelector::CallContract, 2);
+    execution_tracer.enter_call(&inner_call);
+    execution_tracer.exit_call();
+    execution_tracer.exit_syscall();
+    execution_tracer.enter_syscall(SyscallSelector::CallContract, 5);
+    // The checkpoint is taken when the second inner call is entered, as in a native execution.
+    execution_tracer.enter_call(&inner_call);
+    let checkpoint = execution_tracer.checkpoint();
+    execution_tracer.enter_syscall(SyscallSelector::CallContract, 0);
+    execution_tracer.enter_call(&inner_call);
+    execution_tracer.enter_call(&inner_call);
+
+    execution_tracer.rollback(checkpoint);
+    execution_tracer.exit_call();
+    execution_tracer.exit_syscall();
+    execution_tracer.exit_call();
+
+    let trace = execution_tracer.take_trace();
+    assert_eq!(trace.calls.len(), 3);
+    assert_eq!(
+        trace.calls[0].syscalls,
+        vec![
+            SyscallTrace { selector: SyscallSelector::CallContract, step: 2, inner_calls: vec![1] },
+            SyscallTrace { selector: SyscallSelector::CallContract, step: 5, inner_calls: vec![2] },
+        ]
+    );
+    assert_eq!(trace.calls[2].syscalls, vec![]);
+}
+
+#[rstest]
+fn test_execute_traced(
+    block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+    #[values(CairoVersion::Cairo0, CairoVersion::Cairo1(RunnableCairo1::Casm))]
+    cairo_version: CairoVersion,
+) {
+    let chain_info = ChainInfo::create_for_testing();
+    let account = FeatureContract::AccountWithoutValidations(cairo_version);
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let mut state = test_state(&chain_info, BALANCE, &[(account, 1), (test_contract, 1)]);
+    let test_contract_address = test_contract.get_instance_address(0);
+
+    let calldata = create_calldata(
+        test_contract_address,
+        "test_storage_read_write",
+        &[Felt::from(15_u8), Felt::from(17_u8)],
+    );
+    let tx = invoke_tx_with_default_flags(invoke_tx_args! {
+        sender_address: account.get_instance_address(0),
+        calldata,
+        resource_bounds: default_all_resource_bounds,
+    });
+    let traced_execution = tx.execute_traced(&mut state, &block_context).unwrap();
+    assert!(!traced_execution.execution_info.is_reverted());
+
+    let trace = traced_execution.trace;
+    // Validate, execute and fee transfer.
+    assert_eq!(trace.top_level_calls().count(), 3);
+    assert!(trace.calls.iter().all(|call_trace| !call_trace.steps.is_empty()));
+
+    // The account's `__execute__` calls the test contract, which reads and writes its storage.
+    let execute_index = 1;
+    let call_contract = trace.calls[execute_index]
+        .syscalls
+        .iter()
+        .find(|syscall| syscall.selector == SyscallSelector::CallContract)
+        .expect("The account should call the test contract.");
+    let [inner_index] = call_contract.inner_calls.as_slice() else {
+        panic!("Expected a single inner call, got: {:?}.", call_contract.inner_calls);
+    };
+    let inner_call_trace = &trace.calls[*inner_index];
+    assert_eq!(inner_call_trace.call.storage_address, test_contract_address);
+    assert_eq!(
+        inner_call_trace.syscalls.iter().map(|syscall| syscall.selector).collect::<Vec<_>>(),
+        vec![SyscallSelector::StorageWrite, SyscallSelector::StorageRead]
+    );
+
+    // Syscalls are located within the steps of their call.
+    for syscall in &inner_call_trace.syscalls {
+        assert!(syscall.step < inner_call_trace.steps.len());
+    }
+    // The first step of a call fetches its instruction from the program segment.
+    let first_step = &inner_call_trace.steps[0];
+    assert_eq!(first_step.pc.segment_index, 0);
+    assert!(!first_step.memory_accesses.is_empty());
+}
@@ -1,3 +1,5 @@
+#[cfg(all(feature = "cairo_native", test))]
+use std::cell::Cell;
 use std::collections::HashMap;
 
 use cairo_vm::serde::deserialize_program::{
@@ -16,6 +18,7 @@ use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
 use cairo_vm::vm::runners::cairo_runner::{CairoArg, CairoRunner, ExecutionResources};
 use cairo_vm::vm::vm_core::VirtualMachine;
 use num_bigint::BigUint;
+use starknet_api::contract_class::SierraVersion;
 use starknet_api::core::ClassHash;
 use starknet_api::deprecated_contract_class::Program as DeprecatedProgram;
 use starknet_api::transaction::fields::Calldata;
@@ -38,10 +41,16 @@ use crate::execution::errors::{
     PreExecutionError,
 };
 #[cfg(feature = "cairo_native")]
+use crate::execution::native::contract_class::NativeCompiledClassV1;
+#[cfg(feature = "cairo_native")]
 use crate::execution::native::entry_point_execution as native_entry_point_execution;
+#[cfg(feature = "cairo_native")]
+use crate::execution::native::execution_guard::NATIVE_EXECUTION_GUARD;
 use crate::execution::stack_trace::{extract_trailing_cairo1_revert_trace, Cairo1RevertHeader};
 use crate::execution::syscalls::hint_processor::ENTRYPOINT_NOT_FOUND_ERROR;
 use crate::execution::{deprecated_entry_point_execution, entry_point_execution};
+#[cfg(feature = "cairo_native")]
+use crate::state::cached_state::{CachedState, MutRefState};
 use crate::state::errors::StateError;
 use crate::state::state_api::State;
 
@@ -49,6 +58,13 @@ pub type Args = Vec<CairoArg>;
 
 pub const SEGMENT_ARENA_BUILTIN_SIZE: usize = 3;
 
+#[cfg(all(feature = "cairo_native", test))]
+thread_local! {
+    /// Makes the successful native executions of the current thread fail, to test the fallback
+    /// to the Cairo VM.
+    pub(crate) static FAIL_NATIVE_EXECUTIONS: Cell<bool> = const { Cell::new(false) };
+}
+
 /// A wrapper for execute_entry_point_call that performs pre and post-processing.
 pub fn execute_entry_point_call_wrapper(
     mut call: CallEntryPoint,
@@ -57,24 +73,40 @@ pub fn execute_entry_point_call_wrapper(
     context: &mut EntryPointExecutionContext,
     remaining_gas: &mut u64,
 ) -> EntryPointExecutionResult<CallInfo> {
-    let current_tracked_resource = compiled_class.tracked_resource(
-        &context.versioned_constants().min_sierra_version_for_sierra_gas,
-        context.tracked_resource_stack.last(),
-    );
+    // In Sierra gas only mode, all (Cairo 1) classes run using Sierra gas, regardless of their
+    // Sierra version.
+    let min_sierra_version_for_sierra_gas =
+        if context.tx_context.block_context.chain_info.sierra_gas_only {
+            &SierraVersion::DEPRECATED
+        } else {
+            &context.versioned_constants().min_sierra_version_for_sierra_gas
+        };
+    let current_tracked_resource = compiled_class
+        .tracked_resource(min_sierra_version_for_sierra_gas, context.tracked_resource_stack.last());
     if current_tracked_resource == TrackedResource::CairoSteps {
         // Override the initial gas with a high value so it won't limit the run.
         call.initial_gas = context.versioned_constants().infinite_gas_for_vm_mode();
     }
     let orig_call = call.clone();
 
+    let execution_tracer = context.tx_context.block_context.execution_tracer().cloned();
+    if let Some(execution_tracer) = &execution_tracer {
+        execution_tracer.enter_call(&call);
+    }
+
     // Note: no return statements (explicit or implicit) should be added between the push and the
     // pop commands.
     context.tracked_resource_stack.push(current_tracked_resource);
     let res = execute_entry_point_call(call, compiled_class, state, context);
     context.tracked_resource_stack.pop().expect("Unexpected empty tracked resource.");
 
+    if let Some(execution_tracer) = execution_tracer {
+        execution_tracer.exit_call();
+    }
+
     match res {
         Ok(call_info) => {
+            context.check_call_resources(&call_info)?;
             if call_info.execution.failed && !context.versioned_constants().enable_reverts {
                 // Reverts are disabled.
                 return Err(EntryPointExecutionError::ExecutionFailed {
@@ -126,9 +158,25 @@ pub fn execute_entry_point_call(
         }
         #[cfg(feature = "cairo_native")]
         RunnableCompiledClass::V1Native(compiled_class) => {
-            if context.tracked_resource_stack.last() == Some(&TrackedResource::CairoSteps) {
-                // We cannot run native with cairo steps as the tracked resources (it's a vm
-                // resouorce).
+            // We cannot run native with cairo steps as the tracked resources (it's a vm
+            // resouorce). Native execution does not enforce the restrictions of the execution phase
+            // either.
+            if context.tracked_resource_stack.last() == Some(&TrackedResource::CairoSteps)
+                || !context.phase_config().restrictions.is_unrestricted()
+                || context.tx_context.block_context.forces_vm_execution()
+            {
+                entry_point_execution::execute_entry_point_call(
+                    call,
+                    compiled_class.casm(),
+                    state,
+                    context,
+                )
+            } else if call
+                .class_hash
+                .is_some_and(|class_hash| NATIVE_EXECUTION_GUARD.is_disabled(&class_hash))
+            {
+                // Native execution was disabled for this class; run its CASM instead.
+                NATIVE_EXECUTION_GUARD.record_vm_fallback();
                 entry_point_execution::execute_entry_point_call(
                     call,
                     compiled_class.casm(),
@@ -143,17 +191,66 @@ pub fn execute_entry_point_call(
                     context.tx_context.tx_info.transaction_hash(),
                     call.class_hash.expect("Missing Class Hash")
                 );
-                native_entry_point_execution::execute_entry_point_call(
-                    call,
-                    compiled_class,
-                    state,
-                    context,
-                )
+                execute_native_entry_point_call_or_fall_back(call, compiled_class, state, context)
             }
         }
     }
 }
 
+/// Executes the given call natively; if the native runtime fails, the native execution is discarded
+/// and the call is rerun by the Cairo VM. This keeps the result of the call independent of the
+/// failures of the native runtime, which may differ between nodes.
+#[cfg(feature = "cairo_native")]
+fn execute_native_entry_point_call_or_fall_back(
+    call: CallEntryPoint,
+    compiled_class: NativeCompiledClassV1,
+    state: &mut dyn State,
+    context: &mut EntryPointExecutionContext,
+) -> EntryPointExecutionResult<CallInfo> {
+    // Taken after the revert info of the call is pushed by `CallEntryPoint::execute`; a failed
+    // native execution leaves no trace in the context (quota counts, events, messages, tracer
+    // records, profiles, ...), so the result does not depend on whether it failed.
+    let checkpoint = context.checkpoint();
+    let casm = compiled_class.casm();
+
+    // Run natively on top of the given state, so that the changes of a failed execution (including
+    // those of its inner calls) can be discarded.
+    let mut native_state = CachedState::new(MutRefState::new(state));
+    let native_result = native_entry_point_execution::execute_entry_point_call(
+        call.clone(),
+        compiled_class,
+        &mut native_state,
+        context,
+    );
+    #[cfg(test)]
+    let native_result = match native_result {
+        Ok(_) if FAIL_NATIVE_EXECUTIONS.with(Cell::get) => {
+            Err(EntryPointExecutionError::NativeExecutionPanicked {
+                class_hash: call.class_hash.expect("Missing Class Hash"),
+            })
+        }
+        native_result => native_result,
+    };
+    match native_result {
+        Err(
+            error @ (EntryPointExecutionError::NativeExecutionPanicked { .. }
+            | EntryPointExecutionError::NativeMemoryLimitExceeded { .. }
+            | EntryPointExecutionError::NativeUnexpectedError(_)),
+        ) => {
+            log::warn!("Rerunning a failed native execution by the Cairo VM: {error}");
+            drop(native_state);
+            context.rollback(checkpoint);
+            NATIVE_EXECUTION_GUARD.record_vm_fallback();
+            entry_point_execution::execute_entry_point_call(call, casm, state, context)
+        }
+        result => {
+            context.release(checkpoint);
+            native_state.commit_call_changes()?;
+            result
+        }
+    }
+}
+
 pub fn update_remaining_gas(remaining_gas: &mut u64, call_info: &CallInfo) {
     *remaining_gas -= call_info.execution.gas_consumed;
 }
@@ -1,5 +1,6 @@
 pub mod contract_class;
 pub mod entry_point_execution;
+pub mod execution_guard;
 pub mod syscall_handler;
 pub mod utils;
 
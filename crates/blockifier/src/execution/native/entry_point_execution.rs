@@ -1,7 +1,10 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
 use cairo_native::execution_result::ContractExecutionResult;
 use cairo_native::utils::BuiltinCosts;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use stacker;
+use starknet_api::core::ClassHash;
 
 use crate::execution::call_info::{CallExecution, CallInfo, ChargedResources, Retdata};
 use crate::execution::contract_class::TrackedResource;
@@ -13,6 +16,7 @@ use crate::execution::entry_point::{
 use crate::execution::entry_point_execution::gas_consumed_without_inner_calls;
 use crate::execution::errors::{EntryPointExecutionError, PostExecutionError};
 use crate::execution::native::contract_class::NativeCompiledClassV1;
+use crate::execution::native::execution_guard::{resident_memory, NATIVE_EXECUTION_GUARD};
 use crate::execution::native::syscall_handler::NativeSyscallHandler;
 use crate::state::state_api::State;
 
@@ -28,6 +32,7 @@ pub fn execute_entry_point_call(
     let mut syscall_handler: NativeSyscallHandler<'_> =
         NativeSyscallHandler::new(call, state, context);
 
+    let class_hash = syscall_handler.base.call.class_hash;
     let gas_costs = &syscall_handler.base.context.gas_costs();
     let builtin_costs = BuiltinCosts {
         // todo(rodrigo): Unsure of what value `const` means, but 1 is the right value.
@@ -53,23 +58,51 @@ pub fn execute_entry_point_call(
     // The gas upper bound is MAX_POSSIBLE_SIERRA_GAS, and sequencers must not raise it without
     // adjusting the stack size.
     // This also limits multi-threading, since each thread has its own stack.
-    // TODO(Aviv/Yonatan): add these numbers to overridable VC.
-    let stack_size_red_zone = 160 * 1024 * 1024;
+    // The stack size is configured by `ContractClassManagerConfig::native_execution_stack_size`.
+    let stack_size_red_zone = NATIVE_EXECUTION_GUARD.stack_size();
     let target_stack_size = stack_size_red_zone + 10 * 1024 * 1024;
+    let resident_memory_before =
+        NATIVE_EXECUTION_GUARD.limits_memory_growth().then(resident_memory).flatten();
     // Use `maybe_grow` and not `grow` for performance, since in happy flows, only the main call
     // should trigger the growth.
-    let execution_result = stacker::maybe_grow(stack_size_red_zone, target_stack_size, || {
-        compiled_class.executor.run(
-            entry_point.selector.0,
-            &syscall_handler.base.call.calldata.0.clone(),
-            syscall_handler.base.call.initial_gas,
-            Some(builtin_costs),
-            &mut syscall_handler,
-        )
-    });
+    // A panic inside the native runtime must not take the sequencer down; it is contained here, and
+    // the call is rerun by the VM.
+    let execution_result = catch_unwind(AssertUnwindSafe(|| {
+        stacker::maybe_grow(stack_size_red_zone, target_stack_size, || {
+            compiled_class.executor.run(
+                entry_point.selector.0,
+                &syscall_handler.base.call.calldata.0.clone(),
+                syscall_handler.base.call.initial_gas,
+                Some(builtin_costs),
+                &mut syscall_handler,
+            )
+        })
+    }));
     syscall_handler.finalize();
 
-    let call_result = execution_result.map_err(EntryPointExecutionError::NativeUnexpectedError)?;
+    let execution_result = match execution_result {
+        Ok(execution_result) => execution_result,
+        Err(_) => {
+            record_native_failure(class_hash);
+            return Err(EntryPointExecutionError::NativeExecutionPanicked {
+                class_hash: class_hash.unwrap_or_default(),
+            });
+        }
+    };
+    let call_result = execution_result.map_err(|error| {
+        record_native_failure(class_hash);
+        EntryPointExecutionError::NativeUnexpectedError(error)
+    })?;
+    if let Some(before) = resident_memory_before {
+        let memory_growth = resident_memory().unwrap_or(before).saturating_sub(before);
+        if NATIVE_EXECUTION_GUARD.exceeds_memory_growth_limit(memory_growth) {
+            record_native_failure(class_hash);
+            return Err(EntryPointExecutionError::NativeMemoryLimitExceeded {
+                class_hash: class_hash.unwrap_or_default(),
+                memory_growth,
+            });
+        }
+    }
 
     if let Some(error) = syscall_handler.unrecoverable_error {
         return Err(EntryPointExecutionError::NativeUnrecoverableError(Box::new(error)));
@@ -78,6 +111,13 @@ pub fn execute_entry_point_call(
     create_callinfo(call_result, syscall_handler)
 }
 
+fn record_native_failure(class_hash: Option<ClassHash>) {
+    match class_hash {
+        Some(class_hash) => NATIVE_EXECUTION_GUARD.record_native_failure(class_hash),
+        None => log::error!("Native execution failed for a call without a class hash."),
+    }
+}
+
 fn create_callinfo(
     call_result: ContractExecutionResult,
     syscall_handler: NativeSyscallHandler<'_>,
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{LazyLock, RwLock};
+
+use starknet_api::core::ClassHash;
+
+use crate::blockifier::config::{
+    DEFAULT_NATIVE_EXECUTION_MAX_MEMORY_GROWTH,
+    DEFAULT_NATIVE_EXECUTION_STACK_SIZE,
+};
+
+#[cfg(test)]
+#[path = "execution_guard_test.rs"]
+mod test;
+
+/// The process-wide guard consulted before running natively-compiled class code.
+pub static NATIVE_EXECUTION_GUARD: LazyLock<NativeExecutionGuard> =
+    LazyLock::new(NativeExecutionGuard::default);
+
+/// Contains natively-compiled classes that misbehave: panic or fail inside the native runtime, or
+/// exceed the memory growth limit.
+///
+/// A failing native call is discarded and rerun by the Cairo VM, using the class' CASM, within the
+/// same call; its result does not depend on whether native execution failed. The class is then
+/// disabled, so that its later calls run on the VM directly; classes can also be disabled up front
+/// through the config. Disabling is an optimization only.
+#[derive(Debug)]
+pub struct NativeExecutionGuard {
+    disabled_class_hashes: RwLock<HashSet<ClassHash>>,
+    stack_size: AtomicUsize,
+    max_memory_growth: AtomicUsize,
+    n_native_failures: AtomicU64,
+    n_vm_fallbacks: AtomicU64,
+}
+
+impl Default for NativeExecutionGuard {
+    fn default() -> Self {
+        Self {
+            disabled_class_hashes: RwLock::default(),
+            stack_size: AtomicUsize::new(DEFAULT_NATIVE_EXECUTION_STACK_SIZE),
+            max_memory_growth: AtomicUsize::new(DEFAULT_NATIVE_EXECUTION_MAX_MEMORY_GROWTH),
+            n_native_failures: AtomicU64::default(),
+            n_vm_fallbacks: AtomicU64::default(),
+        }
+    }
+}
+
+impl NativeExecutionGuard {
+    /// Sets the stack space guaranteed to each native execution, and the maximal growth of the
+    /// resident memory during it (zero for no limit).
+    pub fn set_limits(&self, stack_size: usize, max_memory_growth: usize) {
+        self.stack_size.store(stack_size, Ordering::Relaxed);
+        self.max_memory_growth.store(max_memory_growth, Ordering::Relaxed);
+    }
+
+    pub fn stack_size(&self) -> usize {
+        self.stack_size.load(Ordering::Relaxed)
+    }
+
+    pub fn limits_memory_growth(&self) -> bool {
+        self.max_memory_growth.load(Ordering::Relaxed) != 0
+    }
+
+    /// Returns whether the given growth of the resident memory exceeds the limit.
+    /// The resident memory is shared by all the threads of the process, so concurrent executions
+    /// may trip the limit too; this only costs the rerun by the VM.
+    pub fn exceeds_memory_growth_limit(&self, memory_growth: usize) -> bool {
+        let max_memory_growth = self.max_memory_growth.load(Ordering::Relaxed);
+        max_memory_growth != 0 && memory_growth > max_memory_growth
+    }
+
+    /// Disables native execution for the given class; it is executed by the VM from now on.
+    pub fn disable(&self, class_hash: ClassHash) {
+        let newly_disabled = self
+            .disabled_class_hashes
+            .write()
+            .expect("Native execution guard lock is poisoned.")
+            .insert(class_hash);
+        if newly_disabled {
+            log::warn!("Native execution disabled for class hash {class_hash}.");
+        }
+    }
+
+    pub fn is_disabled(&self, class_hash: &ClassHash) -> bool {
+        self.disabled_class_hashes
+            .read()
+            .expect("Native execution guard lock is poisoned.")
+            .contains(class_hash)
+    }
+
+    /// Records a failure of native code and disables native execution for the failing class.
+    pub fn record_native_failure(&self, class_hash: ClassHash) {
+        self.n_native_failures.fetch_add(1, Ordering::Relaxed);
+        self.disable(class_hash);
+    }
+
+    /// Records a call to a natively-compiled class that was executed by the VM instead.
+    pub fn record_vm_fallback(&self) {
+        self.n_vm_fallbacks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of native executions that failed inside the native runtime.
+    pub fn n_native_failures(&self) -> u64 {
+        self.n_native_failures.load(Ordering::Relaxed)
+    }
+
+    /// The number of calls to natively-compiled classes that were executed by the VM instead.
+    pub fn n_vm_fallbacks(&self) -> u64 {
+        self.n_vm_fallbacks.load(Ordering::Relaxed)
+    }
+}
+
+/// Returns the resident memory of the process in bytes, if it can be measured on this platform.
+pub fn resident_memory() -> Option<usize> {
+    // The second field of `statm` is the number of resident pages.
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let n_resident_pages: usize = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(n_resident_pages * page_size::get())
+}
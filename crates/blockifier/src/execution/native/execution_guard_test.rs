@@ -0,0 +1,34 @@
+use starknet_api::class_hash;
+
+use crate::execution::native::execution_guard::NativeExecutionGuard;
+
+#[test]
+fn native_failure_disables_class() {
+    let guard = NativeExecutionGuard::default();
+    let failing_class_hash = class_hash!("0x1");
+    let other_class_hash = class_hash!("0x2");
+
+    guard.record_native_failure(failing_class_hash);
+    guard.record_native_failure(failing_class_hash);
+    guard.record_vm_fallback();
+
+    assert!(guard.is_disabled(&failing_class_hash));
+    assert!(!guard.is_disabled(&other_class_hash));
+    assert_eq!(guard.n_native_failures(), 2);
+    assert_eq!(guard.n_vm_fallbacks(), 1);
+}
+
+#[test]
+fn memory_growth_limit() {
+    let guard = NativeExecutionGuard::default();
+    guard.set_limits(1024, 100);
+    assert_eq!(guard.stack_size(), 1024);
+    assert!(guard.limits_memory_growth());
+    assert!(!guard.exceeds_memory_growth_limit(100));
+    assert!(guard.exceeds_memory_growth_limit(101));
+
+    // Zero disables the limit.
+    guard.set_limits(1024, 0);
+    assert!(!guard.limits_memory_growth());
+    assert!(!guard.exceeds_memory_growth_limit(usize::MAX));
+}
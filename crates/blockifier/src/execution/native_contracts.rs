@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use starknet_api::core::ContractAddress;
+use starknet_api::execution_resources::GasAmount;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::call_info::{CallExecution, CallInfo, ChargedResources, Retdata};
+use crate::execution::contract_class::TrackedResource;
+use crate::execution::entry_point::{
+    CallEntryPoint,
+    EntryPointExecutionContext,
+    EntryPointExecutionResult,
+};
+use crate::execution::errors::EntryPointExecutionError;
+use crate::execution::execution_utils::update_remaining_gas;
+use crate::execution::stack_trace::{extract_trailing_cairo1_revert_trace, Cairo1RevertHeader};
+use crate::execution::syscalls::hint_processor::OUT_OF_GAS_ERROR;
+
+#[cfg(test)]
+#[path = "native_contracts_test.rs"]
+pub mod test;
+
+/// A contract implemented in Rust rather than in Cairo, like the precompiles of Ethereum, e.g., to
+/// run heavy primitives natively. Native contracts have no storage and make no calls.
+pub trait NativeContract: Send + Sync {
+    /// The gas charged for the given call, regardless of its result; must be deterministic.
+    fn gas_cost(&self, call: &CallEntryPoint) -> GasAmount;
+
+    /// Executes the given call, returning its retdata, or the error data of its failure.
+    fn execute(&self, call: &CallEntryPoint) -> Result<Vec<Felt>, Vec<Felt>>;
+}
+
+pub type SharedNativeContract = Arc<dyn NativeContract>;
+
+impl Debug for dyn NativeContract {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("NativeContract")
+    }
+}
+
+/// The native contracts of a chain, by address. Calls to these addresses run the native contract,
+/// whether or not a class is deployed at the address.
+#[derive(Debug, Default)]
+pub struct NativeContractRegistry(HashMap<ContractAddress, SharedNativeContract>);
+
+impl NativeContractRegistry {
+    pub fn with_native_contract(
+        mut self,
+        address: ContractAddress,
+        native_contract: SharedNativeContract,
+    ) -> Self {
+        self.0.insert(address, native_contract);
+        self
+    }
+
+    pub fn get(&self, address: ContractAddress) -> Option<&SharedNativeContract> {
+        self.0.get(&address)
+    }
+}
+
+/// Executes a call to a native contract. The call fails if its gas cost exceeds its initial gas,
+/// consuming all of it.
+pub(crate) fn execute_native_contract_call(
+    call: CallEntryPoint,
+    native_contract: &dyn NativeContract,
+    context: &EntryPointExecutionContext,
+    remaining_gas: &mut u64,
+) -> EntryPointExecutionResult<CallInfo> {
+    let gas_cost = native_contract.gas_cost(&call).0;
+    let (result, gas_consumed) = if gas_cost > call.initial_gas {
+        let out_of_gas_error = Felt::from_hex(OUT_OF_GAS_ERROR).expect("Invalid out of gas error.");
+        (Err(vec![out_of_gas_error]), call.initial_gas)
+    } else {
+        (native_contract.execute(&call), gas_cost)
+    };
+    let (retdata, failed) = match result {
+        Ok(retdata) => (retdata, false),
+        Err(error_data) => (error_data, true),
+    };
+
+    let call_info = CallInfo {
+        call,
+        execution: CallExecution {
+            retdata: Retdata(retdata),
+            failed,
+            gas_consumed,
+            ..CallExecution::default()
+        },
+        tracked_resource: TrackedResource::SierraGas,
+        charged_resources: ChargedResources::from_gas(GasAmount(gas_consumed)),
+        ..CallInfo::default()
+    };
+    if failed && !context.versioned_constants().enable_reverts {
+        // Reverts are disabled.
+        return Err(EntryPointExecutionError::ExecutionFailed {
+            error_trace: extract_trailing_cairo1_revert_trace(
+                &call_info,
+                Cairo1RevertHeader::Execution,
+            ),
+        });
+    }
+    update_remaining_gas(remaining_gas, &call_info);
+    Ok(call_info)
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use pretty_assertions::assert_eq;
+use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::execution_resources::GasAmount;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::{contract_address, felt};
+use starknet_types_core::felt::Felt;
+
+use crate::context::{BlockContext, ChainInfo, TransactionContext};
+use crate::execution::call_info::{CallExecution, CallInfo, ChargedResources};
+use crate::execution::common_hints::ExecutionMode;
+use crate::execution::contract_class::TrackedResource;
+use crate::execution::entry_point::{CallEntryPoint, EntryPointExecutionContext};
+use crate::execution::native_contracts::{NativeContract, NativeContractRegistry};
+use crate::retdata;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{
+    create_calldata,
+    trivial_external_entry_point_new,
+    CairoVersion,
+    RunnableCairo1,
+};
+use crate::transaction::objects::{CurrentTransactionInfo, TransactionInfo};
+
+const SUM_GAS_COST: u64 = 1000;
+
+// Sums its calldata; fails on any other entry point.
+struct SumContract;
+
+impl NativeContract for SumContract {
+    fn gas_cost(&self, call: &CallEntryPoint) -> GasAmount {
+        GasAmount(SUM_GAS_COST + u64::try_from(call.calldata.0.len()).unwrap())
+    }
+
+    fn execute(&self, call: &CallEntryPoint) -> Result<Vec<Felt>, Vec<Felt>> {
+        if call.entry_point_selector != selector_from_name("sum") {
+            return Err(vec![felt!("0x1")]);
+        }
+        Ok(vec![call.calldata.0.iter().fold(Felt::ZERO, |sum, felt| sum + *felt)])
+    }
+}
+
+// Calls the given entry point of a native contract through the test contract, and returns the
+// call info of the native contract call.
+fn call_native_contract(entry_point_name: &str, args: &[Felt]) -> CallInfo {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let mut state = test_state(&ChainInfo::create_for_testing(), Fee(0), &[(test_contract, 1)]);
+    let native_contract_address = contract_address!("0x1234");
+
+    let registry = NativeContractRegistry::default()
+        .with_native_contract(native_contract_address, Arc::new(SumContract));
+    let tx_context = TransactionContext {
+        block_context: BlockContext::create_for_testing()
+            .with_native_contract_registry(Arc::new(registry)),
+        tx_info: TransactionInfo::Current(CurrentTransactionInfo::create_for_testing()),
+    };
+    let limit_steps_by_resources = false;
+    let mut context = EntryPointExecutionContext::new(
+        Arc::new(tx_context),
+        ExecutionMode::Execute,
+        limit_steps_by_resources,
+    );
+
+    let entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("test_call_contract"),
+        calldata: create_calldata(native_contract_address, entry_point_name, args),
+        ..trivial_external_entry_point_new(test_contract)
+    };
+    let mut remaining_gas = entry_point_call.initial_gas;
+    let call_info = entry_point_call.execute(&mut state, &mut context, &mut remaining_gas).unwrap();
+    let [native_contract_call] = &call_info.inner_calls[..] else {
+        panic!("Expected one inner call, got {:?}", call_info.inner_calls);
+    };
+    assert_eq!(native_contract_call.call.storage_address, native_contract_address);
+    native_contract_call.clone()
+}
+
+#[test]
+fn test_native_contract_call() {
+    let call_info = call_native_contract("sum", &[felt!(2_u8), felt!(3_u8)]);
+
+    let expected_gas = SUM_GAS_COST + 2;
+    assert_eq!(
+        call_info.execution,
+        CallExecution {
+            retdata: retdata![felt!(5_u8)],
+            gas_consumed: expected_gas,
+            ..CallExecution::default()
+        }
+    );
+    assert_eq!(call_info.tracked_resource, TrackedResource::SierraGas);
+    assert_eq!(call_info.charged_resources, ChargedResources::from_gas(GasAmount(expected_gas)));
+}
+
+#[test]
+fn test_failed_native_contract_call() {
+    let call_info = call_native_contract("unknown", &[]);
+
+    assert!(call_info.execution.failed);
+    assert_eq!(call_info.execution.retdata, retdata![felt!("0x1")]);
+    assert_eq!(call_info.execution.gas_consumed, SUM_GAS_COST);
+}
@@ -12,8 +12,16 @@ use starknet_types_core::felt::Felt;
 
 use crate::execution::call_info::{CallInfo, Retdata};
 use crate::execution::deprecated_syscalls::hint_processor::DeprecatedSyscallExecutionError;
-use crate::execution::errors::{ConstructorEntryPointExecutionError, EntryPointExecutionError};
-use crate::execution::syscalls::hint_processor::{SyscallExecutionError, ENTRYPOINT_FAILED_ERROR};
+use crate::execution::errors::{
+    ConstructorEntryPointExecutionError,
+    EntryPointExecutionError,
+    PreExecutionError,
+};
+use crate::execution::syscalls::hint_processor::{
+    SyscallExecutionError,
+    ENTRYPOINT_FAILED_ERROR,
+    OUT_OF_GAS_ERROR,
+};
 use crate::transaction::errors::TransactionExecutionError;
 
 #[cfg(test)]
@@ -148,12 +156,50 @@ impl Display for ErrorStackHeader {
     }
 }
 
+/// The root cause of a failed execution, classified from the innermost error of the stack.
+#[cfg_attr(feature = "transaction_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FailureReason {
+    /// The execution ran out of Cairo steps.
+    OutOfSteps,
+    /// The execution ran out of Sierra gas.
+    OutOfGas,
+    /// A Cairo 1 entry point panicked with the given data.
+    Panic(Vec<Felt>),
+    EntryPointNotFound(EntryPointSelector),
+    RecursionDepthExceeded,
+    /// Any other failure; its description is the innermost segment of the stack.
+    #[default]
+    Other,
+}
+
+impl FailureReason {
+    fn from_vm_error(vm_error: &VirtualMachineError) -> Self {
+        match vm_error {
+            VirtualMachineError::UnfinishedExecution => Self::OutOfSteps,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl From<&Cairo1RevertSummary> for FailureReason {
+    fn from(revert_summary: &Cairo1RevertSummary) -> Self {
+        let out_of_gas_felt = Felt::from_hex(OUT_OF_GAS_ERROR)
+            .unwrap_or_else(|_| panic!("{OUT_OF_GAS_ERROR} does not fit in a felt."));
+        match revert_summary.last_retdata.0.first() {
+            Some(felt) if *felt == out_of_gas_felt => Self::OutOfGas,
+            _ => Self::Panic(revert_summary.last_retdata.0.clone()),
+        }
+    }
+}
+
 #[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default, PartialEq)]
 pub struct ErrorStack {
     pub header: ErrorStackHeader,
     pub stack: Vec<ErrorStackSegment>,
+    pub failure_reason: FailureReason,
 }
 
 impl Display for ErrorStack {
@@ -446,6 +492,7 @@ pub fn gen_tx_execution_error_trace(error: &TransactionExecutionError) -> ErrorS
         TransactionExecutionError::PanicInValidate { panic_reason } => {
             let mut stack = ErrorStack::default();
             stack.push(panic_reason.clone().into());
+            stack.failure_reason = panic_reason.into();
             stack
         }
         _ => {
@@ -498,6 +545,9 @@ fn extract_cairo_run_error_into_stack_trace(
         );
         extract_virtual_machine_error_into_stack_trace(error_stack, depth, &vm_exception.inner_exc);
     } else {
+        if let CairoRunError::VirtualMachine(vm_error) = error {
+            error_stack.failure_reason = FailureReason::from_vm_error(vm_error);
+        }
         error_stack.push(error.to_string().into());
     }
 }
@@ -539,6 +589,7 @@ fn extract_virtual_machine_error_into_stack_trace(
             }
         }
         _ => {
+            error_stack.failure_reason = FailureReason::from_vm_error(vm_error);
             error_stack.push(format!("{}\n", vm_error).into());
         }
     }
@@ -710,8 +761,20 @@ fn extract_entry_point_execution_error_into_stack_trace(
             extract_cairo_run_error_into_stack_trace(error_stack, depth, cairo_run_error)
         }
         EntryPointExecutionError::ExecutionFailed { error_trace } => {
+            error_stack.failure_reason = error_trace.into();
             error_stack.push(error_trace.clone().into())
         }
-        _ => error_stack.push(format!("{}\n", entry_point_error).into()),
+        _ => {
+            error_stack.failure_reason = match entry_point_error {
+                EntryPointExecutionError::PreExecutionError(
+                    PreExecutionError::EntryPointNotFound(selector),
+                ) => FailureReason::EntryPointNotFound(*selector),
+                EntryPointExecutionError::RecursionDepthExceeded => {
+                    FailureReason::RecursionDepthExceeded
+                }
+                _ => FailureReason::Other,
+            };
+            error_stack.push(format!("{}\n", entry_point_error).into())
+        }
     }
 }
@@ -33,19 +33,22 @@ use starknet_types_core::felt::Felt;
 use crate::context::{BlockContext, ChainInfo};
 use crate::execution::call_info::{CallExecution, CallInfo, Retdata};
 use crate::execution::entry_point::CallEntryPoint;
-use crate::execution::errors::EntryPointExecutionError;
+use crate::execution::errors::{EntryPointExecutionError, PreExecutionError};
 use crate::execution::stack_trace::{
     extract_trailing_cairo1_revert_trace,
+    gen_tx_execution_error_trace,
     Cairo1RevertHeader,
     Cairo1RevertSummary,
+    FailureReason,
     MIN_CAIRO1_FRAME_LENGTH,
     TRACE_LENGTH_CAP,
 };
-use crate::execution::syscalls::hint_processor::ENTRYPOINT_FAILED_ERROR;
+use crate::execution::syscalls::hint_processor::{ENTRYPOINT_FAILED_ERROR, OUT_OF_GAS_ERROR};
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::initial_test_state::{fund_account, test_state};
 use crate::test_utils::{create_calldata, CairoVersion, RunnableCairo1, BALANCE};
 use crate::transaction::account_transaction::{AccountTransaction, ExecutionFlags};
+use crate::transaction::errors::TransactionExecutionError;
 use crate::transaction::test_utils::{
     block_context,
     create_account_tx_for_validate_test_nonce_0,
@@ -1109,3 +1112,50 @@ fn test_cairo1_stack_extraction_not_failure_fallback() {
         if stack.is_empty() && last_retdata == expected_retdata
     );
 }
+
+#[rstest]
+#[case::panic(
+    EntryPointExecutionError::ExecutionFailed {
+        error_trace: Cairo1RevertSummary {
+            header: Cairo1RevertHeader::Execution,
+            stack: vec![],
+            last_retdata: Retdata(vec![felt!("0xdead")]),
+        },
+    },
+    FailureReason::Panic(vec![felt!("0xdead")])
+)]
+#[case::out_of_gas(
+    EntryPointExecutionError::ExecutionFailed {
+        error_trace: Cairo1RevertSummary {
+            header: Cairo1RevertHeader::Execution,
+            stack: vec![],
+            last_retdata: Retdata(vec![felt!(OUT_OF_GAS_ERROR)]),
+        },
+    },
+    FailureReason::OutOfGas
+)]
+#[case::entry_point_not_found(
+    PreExecutionError::EntryPointNotFound(selector_from_name("foo")).into(),
+    FailureReason::EntryPointNotFound(selector_from_name("foo"))
+)]
+#[case::recursion_depth_exceeded(
+    EntryPointExecutionError::RecursionDepthExceeded,
+    FailureReason::RecursionDepthExceeded
+)]
+#[case::other(
+    EntryPointExecutionError::InternalError("Boom.".to_string()),
+    FailureReason::Other
+)]
+fn test_failure_reason(
+    #[case] error: EntryPointExecutionError,
+    #[case] expected_failure_reason: FailureReason,
+) {
+    let tx_execution_error = TransactionExecutionError::ExecutionError {
+        error,
+        class_hash: ClassHash::default(),
+        storage_address: ContractAddress::default(),
+        selector: selector_from_name(EXECUTE_ENTRY_POINT_NAME),
+    };
+    let error_stack = gen_tx_execution_error_trace(&tx_execution_error);
+    assert_eq!(error_stack.failure_reason, expected_failure_reason);
+}
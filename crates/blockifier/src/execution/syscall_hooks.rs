@@ -0,0 +1,56 @@
+use std::any::Any;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use starknet_api::core::ContractAddress;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::syscalls::SyscallSelector;
+
+/// A syscall request or response, as exposed to syscall hooks. Hooks may downcast it to its
+/// concrete type through `as_any`, e.g., to `syscalls::DeployRequest`, or to
+/// `deprecated_syscalls::DeployRequest` in Cairo 0 contracts.
+pub trait SyscallMessage: Any + Debug {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + Debug> SyscallMessage for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A hook called around every syscall executed by the Cairo VM, e.g., to enforce the policies of
+/// an appchain or to instrument its contracts.
+///
+/// Note: syscalls of contracts executed natively (Cairo Native) do not reach the hooks.
+pub trait SyscallHook: Send + Sync {
+    /// Called before a syscall of the given contract is executed. Rejecting the syscall, with the
+    /// given reason, fails the execution of the call.
+    fn before_syscall(
+        &self,
+        _storage_address: ContractAddress,
+        _selector: SyscallSelector,
+        _request: &dyn SyscallMessage,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called after a syscall of the given contract is executed, with its response; a failed
+    /// syscall responds with its error data.
+    fn after_syscall(
+        &self,
+        _storage_address: ContractAddress,
+        _selector: SyscallSelector,
+        _response: Result<&dyn SyscallMessage, &[Felt]>,
+    ) {
+    }
+}
+
+pub type SharedSyscallHook = Arc<dyn SyscallHook>;
+
+impl Debug for dyn SyscallHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SyscallHook")
+    }
+}
@@ -113,6 +113,8 @@ pub enum SyscallExecutionError {
     InvalidSyscallSelector(Felt),
     #[error("Unauthorized syscall {syscall_name} in execution mode {execution_mode}.")]
     InvalidSyscallInExecutionMode { syscall_name: String, execution_mode: ExecutionMode },
+    #[error("Syscall {selector:?} was rejected by a syscall hook: {reason}")]
+    SyscallRejected { selector: SyscallSelector, reason: String },
     #[error(transparent)]
     MathError(#[from] cairo_vm::types::errors::math_errors::MathError),
     #[error(transparent)]
@@ -298,96 +300,157 @@ impl<'a> SyscallHintProcessor<'a> {
             self.increment_syscall_count(&selector);
         }
 
-        match selector {
-            SyscallSelector::CallContract => {
-                self.execute_syscall(vm, call_contract, self.gas_costs().syscalls.call_contract)
-            }
+        if self.base.context.tx_context.block_context.is_execution_cancelled() {
+            return Err(
+                SyscallExecutionError::from(EntryPointExecutionError::ExecutionCancelled).into()
+            );
+        }
+
+        let execution_tracer =
+            self.base.context.tx_context.block_context.execution_tracer().cloned();
+        if let Some(execution_tracer) = &execution_tracer {
+            execution_tracer.enter_syscall(selector, vm.get_current_step());
+        }
+
+        let result = match selector {
+            SyscallSelector::CallContract => self.execute_syscall(
+                vm,
+                selector,
+                call_contract,
+                self.gas_costs().syscalls.call_contract,
+            ),
             SyscallSelector::Deploy => {
-                self.execute_syscall(vm, deploy, self.gas_costs().syscalls.deploy)
+                self.execute_syscall(vm, selector, deploy, self.gas_costs().syscalls.deploy)
             }
             SyscallSelector::EmitEvent => {
-                self.execute_syscall(vm, emit_event, self.gas_costs().syscalls.emit_event)
-            }
-            SyscallSelector::GetBlockHash => {
-                self.execute_syscall(vm, get_block_hash, self.gas_costs().syscalls.get_block_hash)
+                self.execute_syscall(vm, selector, emit_event, self.gas_costs().syscalls.emit_event)
             }
+            SyscallSelector::GetBlockHash => self.execute_syscall(
+                vm,
+                selector,
+                get_block_hash,
+                self.gas_costs().syscalls.get_block_hash,
+            ),
             SyscallSelector::GetClassHashAt => self.execute_syscall(
                 vm,
+                selector,
                 get_class_hash_at,
                 self.gas_costs().syscalls.get_class_hash_at,
             ),
             SyscallSelector::GetExecutionInfo => self.execute_syscall(
                 vm,
+                selector,
                 get_execution_info,
                 self.gas_costs().syscalls.get_execution_info,
             ),
             SyscallSelector::Keccak => {
-                self.execute_syscall(vm, keccak, self.gas_costs().syscalls.keccak)
+                self.execute_syscall(vm, selector, keccak, self.gas_costs().syscalls.keccak)
             }
             SyscallSelector::Sha256ProcessBlock => self.execute_syscall(
                 vm,
+                selector,
                 sha_256_process_block,
                 self.gas_costs().syscalls.sha256_process_block,
             ),
-            SyscallSelector::LibraryCall => {
-                self.execute_syscall(vm, library_call, self.gas_costs().syscalls.library_call)
-            }
-            SyscallSelector::ReplaceClass => {
-                self.execute_syscall(vm, replace_class, self.gas_costs().syscalls.replace_class)
-            }
-            SyscallSelector::Secp256k1Add => {
-                self.execute_syscall(vm, secp256k1_add, self.gas_costs().syscalls.secp256k1_add)
-            }
+            SyscallSelector::LibraryCall => self.execute_syscall(
+                vm,
+                selector,
+                library_call,
+                self.gas_costs().syscalls.library_call,
+            ),
+            SyscallSelector::ReplaceClass => self.execute_syscall(
+                vm,
+                selector,
+                replace_class,
+                self.gas_costs().syscalls.replace_class,
+            ),
+            SyscallSelector::Secp256k1Add => self.execute_syscall(
+                vm,
+                selector,
+                secp256k1_add,
+                self.gas_costs().syscalls.secp256k1_add,
+            ),
             SyscallSelector::Secp256k1GetPointFromX => self.execute_syscall(
                 vm,
+                selector,
                 secp256k1_get_point_from_x,
                 self.gas_costs().syscalls.secp256k1_get_point_from_x,
             ),
             SyscallSelector::Secp256k1GetXy => self.execute_syscall(
                 vm,
+                selector,
                 secp256k1_get_xy,
                 self.gas_costs().syscalls.secp256k1_get_xy,
             ),
-            SyscallSelector::Secp256k1Mul => {
-                self.execute_syscall(vm, secp256k1_mul, self.gas_costs().syscalls.secp256k1_mul)
-            }
-            SyscallSelector::Secp256k1New => {
-                self.execute_syscall(vm, secp256k1_new, self.gas_costs().syscalls.secp256k1_new)
-            }
-            SyscallSelector::Secp256r1Add => {
-                self.execute_syscall(vm, secp256r1_add, self.gas_costs().syscalls.secp256r1_add)
-            }
+            SyscallSelector::Secp256k1Mul => self.execute_syscall(
+                vm,
+                selector,
+                secp256k1_mul,
+                self.gas_costs().syscalls.secp256k1_mul,
+            ),
+            SyscallSelector::Secp256k1New => self.execute_syscall(
+                vm,
+                selector,
+                secp256k1_new,
+                self.gas_costs().syscalls.secp256k1_new,
+            ),
+            SyscallSelector::Secp256r1Add => self.execute_syscall(
+                vm,
+                selector,
+                secp256r1_add,
+                self.gas_costs().syscalls.secp256r1_add,
+            ),
             SyscallSelector::Secp256r1GetPointFromX => self.execute_syscall(
                 vm,
+                selector,
                 secp256r1_get_point_from_x,
                 self.gas_costs().syscalls.secp256r1_get_point_from_x,
             ),
             SyscallSelector::Secp256r1GetXy => self.execute_syscall(
                 vm,
+                selector,
                 secp256r1_get_xy,
                 self.gas_costs().syscalls.secp256r1_get_xy,
             ),
-            SyscallSelector::Secp256r1Mul => {
-                self.execute_syscall(vm, secp256r1_mul, self.gas_costs().syscalls.secp256r1_mul)
-            }
-            SyscallSelector::Secp256r1New => {
-                self.execute_syscall(vm, secp256r1_new, self.gas_costs().syscalls.secp256r1_new)
-            }
+            SyscallSelector::Secp256r1Mul => self.execute_syscall(
+                vm,
+                selector,
+                secp256r1_mul,
+                self.gas_costs().syscalls.secp256r1_mul,
+            ),
+            SyscallSelector::Secp256r1New => self.execute_syscall(
+                vm,
+                selector,
+                secp256r1_new,
+                self.gas_costs().syscalls.secp256r1_new,
+            ),
             SyscallSelector::SendMessageToL1 => self.execute_syscall(
                 vm,
+                selector,
                 send_message_to_l1,
                 self.gas_costs().syscalls.send_message_to_l1,
             ),
-            SyscallSelector::StorageRead => {
-                self.execute_syscall(vm, storage_read, self.gas_costs().syscalls.storage_read)
-            }
-            SyscallSelector::StorageWrite => {
-                self.execute_syscall(vm, storage_write, self.gas_costs().syscalls.storage_write)
-            }
+            SyscallSelector::StorageRead => self.execute_syscall(
+                vm,
+                selector,
+                storage_read,
+                self.gas_costs().syscalls.storage_read,
+            ),
+            SyscallSelector::StorageWrite => self.execute_syscall(
+                vm,
+                selector,
+                storage_write,
+                self.gas_costs().syscalls.storage_write,
+            ),
             _ => Err(HintError::UnknownHint(
                 format!("Unsupported syscall selector {selector:?}.").into(),
             )),
+        };
+        if let Some(execution_tracer) = execution_tracer {
+            execution_tracer.exit_syscall();
         }
+
+        result
     }
 
     pub fn get_or_allocate_execution_info_segment(
@@ -442,12 +505,13 @@ impl<'a> SyscallHintProcessor<'a> {
     fn execute_syscall<Request, Response, ExecuteCallback>(
         &mut self,
         vm: &mut VirtualMachine,
+        selector: SyscallSelector,
         execute_callback: ExecuteCallback,
         syscall_gas_cost: u64,
     ) -> HintExecutionResult
     where
-        Request: SyscallRequest + std::fmt::Debug,
-        Response: SyscallResponse + std::fmt::Debug,
+        Request: SyscallRequest + std::fmt::Debug + 'static,
+        Response: SyscallResponse + std::fmt::Debug + 'static,
         ExecuteCallback: FnOnce(
             Request,
             &mut VirtualMachine,
@@ -455,6 +519,14 @@ impl<'a> SyscallHintProcessor<'a> {
             &mut u64, // Remaining gas.
         ) -> SyscallResult<Response>,
     {
+        if !self.base.context.is_syscall_allowed(selector) {
+            return Err(SyscallExecutionError::InvalidSyscallInExecutionMode {
+                syscall_name: format!("{selector:?}"),
+                execution_mode: self.execution_mode(),
+            }
+            .into());
+        }
+
         // Refund `SYSCALL_BASE_GAS_COST` as it was pre-charged.
         let required_gas =
             syscall_gas_cost - self.base.context.gas_costs().base.syscall_base_gas_cost;
@@ -473,14 +545,21 @@ impl<'a> SyscallHintProcessor<'a> {
             return Ok(());
         }
 
+        let storage_address = self.storage_address();
+        if let Err(reason) = self.base.context.before_syscall(storage_address, selector, &request) {
+            return Err(SyscallExecutionError::SyscallRejected { selector, reason }.into());
+        }
+
         // Execute.
         let mut remaining_gas = gas_counter - required_gas;
         let original_response = execute_callback(request, vm, self, &mut remaining_gas);
         let response = match original_response {
             Ok(response) => {
+                self.base.context.after_syscall(storage_address, selector, Ok(&response));
                 SyscallResponseWrapper::Success { gas_counter: remaining_gas, response }
             }
             Err(SyscallExecutionError::Revert { error_data: data }) => {
+                self.base.context.after_syscall(storage_address, selector, Err(&data[..]));
                 SyscallResponseWrapper::Failure { gas_counter: remaining_gas, error_data: data }
             }
             Err(error) => return Err(error.into()),
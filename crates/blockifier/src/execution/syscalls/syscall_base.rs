@@ -2,6 +2,7 @@
 use std::collections::{hash_map, HashMap, HashSet};
 use std::convert::From;
 
+use starknet_api::block::BlockNumber;
 use starknet_api::core::{calculate_contract_address, ClassHash, ContractAddress};
 use starknet_api::state::StorageKey;
 use starknet_api::transaction::fields::{Calldata, ContractAddressSalt};
@@ -131,7 +132,20 @@ impl<'state> SyscallHandlerBase<'state> {
             .os_constants
             .os_contract_addresses
             .block_hash_contract_address();
-        Ok(self.state.get_storage_at(block_hash_contract_address, key)?)
+        let block_hash = self.state.get_storage_at(block_hash_contract_address, key)?;
+        if block_hash != Felt::ZERO {
+            return Ok(block_hash);
+        }
+
+        // The block hash is missing from the storage, e.g., when executing on top of a partial
+        // state; fall back to the block hash provider, if any.
+        let Some(block_hash_provider) = self.context.tx_context.block_context.block_hash_provider()
+        else {
+            return Ok(block_hash);
+        };
+        let provided_block_hash =
+            block_hash_provider.get_block_hash(BlockNumber(requested_block_number))?;
+        Ok(provided_block_hash.map_or(block_hash, |block_hash| block_hash.0))
     }
 
     pub fn storage_read(&mut self, key: StorageKey) -> SyscallResult<Felt> {
@@ -1,13 +1,19 @@
+use std::sync::{Arc, Mutex};
+
 use pretty_assertions::assert_eq;
 use starknet_api::abi::abi_utils::selector_from_name;
-use starknet_api::core::calculate_contract_address;
+use starknet_api::core::{calculate_contract_address, ClassHash, ContractAddress};
 use starknet_api::transaction::fields::{Calldata, ContractAddressSalt, Fee};
 use starknet_api::{calldata, felt};
+use starknet_types_core::felt::Felt;
 use test_case::test_case;
 
-use crate::context::ChainInfo;
+use crate::context::{BlockContext, ChainInfo, TransactionContext};
 use crate::execution::call_info::CallExecution;
-use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::common_hints::ExecutionMode;
+use crate::execution::entry_point::{CallEntryPoint, EntryPointExecutionContext};
+use crate::execution::syscall_hooks::{SyscallHook, SyscallMessage};
+use crate::execution::syscalls::{DeployRequest, SyscallSelector};
 use crate::retdata;
 use crate::state::state_api::StateReader;
 use crate::test_utils::contracts::FeatureContract;
@@ -18,6 +24,38 @@ use crate::test_utils::{
     CairoVersion,
     RunnableCairo1,
 };
+use crate::transaction::objects::{CurrentTransactionInfo, TransactionInfo};
+
+// Rejects deploying the given class, and records the executed syscalls.
+struct DeployPolicy {
+    blocked_class_hash: ClassHash,
+    executed_syscalls: Mutex<Vec<SyscallSelector>>,
+}
+
+impl SyscallHook for DeployPolicy {
+    fn before_syscall(
+        &self,
+        _storage_address: ContractAddress,
+        _selector: SyscallSelector,
+        request: &dyn SyscallMessage,
+    ) -> Result<(), String> {
+        match request.as_any().downcast_ref::<DeployRequest>() {
+            Some(request) if request.class_hash == self.blocked_class_hash => {
+                Err(format!("Class {} may not be deployed.", request.class_hash))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn after_syscall(
+        &self,
+        _storage_address: ContractAddress,
+        selector: SyscallSelector,
+        _response: Result<&dyn SyscallMessage, &[Felt]>,
+    ) {
+        self.executed_syscalls.lock().unwrap().push(selector);
+    }
+}
 
 #[test_case(RunnableCairo1::Casm;"VM")]
 #[cfg_attr(feature = "cairo_native", test_case(RunnableCairo1::Native;"Native"))]
@@ -168,3 +206,65 @@ fn to_unavailable_address(runnable_version: RunnableCairo1) {
 
     assert!(error.contains("Deployment failed:"));
 }
+
+#[test]
+fn rejected_by_syscall_hook() {
+    let runnable_version = RunnableCairo1::Casm;
+    let deployer_contract = FeatureContract::TestContract(CairoVersion::Cairo1(runnable_version));
+    let empty_contract = FeatureContract::Empty(CairoVersion::Cairo1(runnable_version));
+    let mut state = test_state(
+        &ChainInfo::create_for_testing(),
+        Fee(0),
+        &[(deployer_contract, 1), (empty_contract, 0)],
+    );
+
+    let deploy_policy = Arc::new(DeployPolicy {
+        blocked_class_hash: empty_contract.get_class_hash(),
+        executed_syscalls: Mutex::default(),
+    });
+    let tx_context = TransactionContext {
+        block_context: BlockContext::create_for_testing(),
+        tx_info: TransactionInfo::Current(CurrentTransactionInfo::create_for_testing()),
+        call_counter: Default::default(),
+    };
+    let limit_steps_by_resources = false;
+    let mut context = EntryPointExecutionContext::new(
+        Arc::new(tx_context),
+        ExecutionMode::Execute,
+        limit_steps_by_resources,
+    )
+    .with_syscall_hook(deploy_policy.clone());
+
+    // Deploying another class is allowed.
+    let constructor_calldata = [felt!(1_u8), felt!(1_u8)];
+    let entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("test_deploy"),
+        calldata: calldata_for_deploy_test(
+            deployer_contract.get_class_hash(),
+            &constructor_calldata,
+            true,
+        ),
+        ..trivial_external_entry_point_new(deployer_contract)
+    };
+    let mut remaining_gas = entry_point_call.initial_gas;
+    entry_point_call.execute(&mut state, &mut context, &mut remaining_gas).unwrap();
+    // The syscalls of the constructor reach the hook before the deploy syscall completes.
+    let executed_syscalls = deploy_policy.executed_syscalls.lock().unwrap().clone();
+    assert!(executed_syscalls.contains(&SyscallSelector::StorageWrite));
+    assert_eq!(executed_syscalls.last(), Some(&SyscallSelector::Deploy));
+
+    let entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("test_deploy"),
+        calldata: calldata_for_deploy_test(empty_contract.get_class_hash(), &[], true),
+        ..trivial_external_entry_point_new(deployer_contract)
+    };
+    let mut remaining_gas = entry_point_call.initial_gas;
+    let error = entry_point_call
+        .execute(&mut state, &mut context, &mut remaining_gas)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains(&format!(
+        "Syscall Deploy was rejected by a syscall hook: Class {} may not be deployed.",
+        empty_contract.get_class_hash()
+    )));
+}
@@ -1,5 +1,8 @@
+use std::sync::Arc;
+
 use pretty_assertions::assert_eq;
 use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::execution_utils::format_panic_data;
 use starknet_api::state::StorageKey;
 use starknet_api::test_utils::CURRENT_BLOCK_NUMBER;
@@ -8,18 +11,33 @@ use starknet_types_core::felt::Felt;
 use test_case::test_case;
 
 use crate::abi::constants;
-use crate::context::ChainInfo;
+use crate::blockifier::block::BlockHashProvider;
+use crate::context::{BlockContext, ChainInfo, TransactionContext};
 use crate::execution::call_info::CallExecution;
-use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::common_hints::ExecutionMode;
+use crate::execution::entry_point::{CallEntryPoint, EntryPointExecutionContext};
 use crate::retdata;
 use crate::state::cached_state::CachedState;
-use crate::state::state_api::State;
+use crate::state::state_api::{State, StateResult};
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::dict_state_reader::DictStateReader;
 use crate::test_utils::initial_test_state::test_state;
 use crate::test_utils::{trivial_external_entry_point_new, CairoVersion, RunnableCairo1, BALANCE};
+use crate::transaction::objects::{CurrentTransactionInfo, TransactionInfo};
 use crate::versioned_constants::VersionedConstants;
 
+// Provides the hash of a single block.
+struct SingleBlockHashProvider {
+    block_number: BlockNumber,
+    block_hash: BlockHash,
+}
+
+impl BlockHashProvider for SingleBlockHashProvider {
+    fn get_block_hash(&self, block_number: BlockNumber) -> StateResult<Option<BlockHash>> {
+        Ok((block_number == self.block_number).then_some(self.block_hash))
+    }
+}
+
 fn initialize_state(test_contract: FeatureContract) -> (CachedState<DictStateReader>, Felt, Felt) {
     let chain_info = &ChainInfo::create_for_testing();
     let mut state = test_state(chain_info, BALANCE, &[(test_contract, 1)]);
@@ -57,6 +75,42 @@ fn positive_flow(runnable_version: RunnableCairo1) {
     );
 }
 
+#[cfg_attr(feature = "cairo_native", test_case(RunnableCairo1::Native;"Native"))]
+#[test_case(RunnableCairo1::Casm;"VM")]
+fn block_hash_missing_from_storage(runnable_version: RunnableCairo1) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(runnable_version));
+    let (mut state, _, stored_block_hash) = initialize_state(test_contract);
+
+    // An older block, whose hash is not in the storage of the block hash contract.
+    let block_number = BlockNumber(CURRENT_BLOCK_NUMBER - constants::STORED_BLOCK_HASH_BUFFER - 5);
+    let block_hash = BlockHash(felt!(77_u64));
+    assert_ne!(block_hash.0, stored_block_hash);
+    let block_context = BlockContext::create_for_testing()
+        .with_block_hash_provider(Arc::new(SingleBlockHashProvider { block_number, block_hash }));
+    let tx_context = TransactionContext {
+        block_context,
+        tx_info: TransactionInfo::Current(CurrentTransactionInfo::create_for_testing()),
+        call_counter: Default::default(),
+    };
+    let limit_steps_by_resources = false;
+    let mut context = EntryPointExecutionContext::new(
+        Arc::new(tx_context),
+        ExecutionMode::Execute,
+        limit_steps_by_resources,
+    );
+
+    let entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("test_get_block_hash"),
+        calldata: calldata![felt!(block_number.0)],
+        ..trivial_external_entry_point_new(test_contract)
+    };
+    let mut remaining_gas = entry_point_call.initial_gas;
+    let call_info = entry_point_call.execute(&mut state, &mut context, &mut remaining_gas).unwrap();
+
+    assert!(!call_info.execution.failed);
+    assert_eq!(call_info.execution.retdata, retdata![block_hash.0]);
+}
+
 #[cfg_attr(feature = "cairo_native", test_case(RunnableCairo1::Native;"Native"))]
 #[test_case(RunnableCairo1::Casm;"VM")]
 fn negative_flow_block_number_out_of_range(runnable_version: RunnableCairo1) {
@@ -36,14 +36,16 @@ pub enum FeeCheckError {
 pub(crate) type FeeCheckResult<T> = Result<T, FeeCheckError>;
 
 /// This struct holds the result of fee checks: recommended fee to charge (useful in post-execution
-/// revert flow) and an error if the check failed.
+/// revert flow), the part of it paid as a tip, and an error if the check failed.
 struct FeeCheckReport {
     recommended_fee: Fee,
+    recommended_tip: Fee,
     error: Option<FeeCheckError>,
 }
 
 pub trait FeeCheckReportFields {
     fn recommended_fee(&self) -> Fee;
+    fn recommended_tip(&self) -> Fee;
     fn error(&self) -> Option<FeeCheckError>;
 }
 
@@ -52,6 +54,10 @@ impl FeeCheckReportFields for FeeCheckReport {
         self.recommended_fee
     }
 
+    fn recommended_tip(&self) -> Fee {
+        self.recommended_tip
+    }
+
     fn error(&self) -> Option<FeeCheckError> {
         self.error
     }
@@ -59,24 +65,25 @@ impl FeeCheckReportFields for FeeCheckReport {
 
 // TODO(Aner, 23/1/24): Update this struct to check data gas bounds as well as other bounds.
 impl FeeCheckReport {
-    pub fn success_report(actual_fee: Fee) -> Self {
-        Self { recommended_fee: actual_fee, error: None }
+    pub fn success_report(actual_fee: Fee, actual_tip: Fee) -> Self {
+        Self { recommended_fee: actual_fee, recommended_tip: actual_tip, error: None }
     }
 
     /// Given a fee error and the current context, constructs and returns a report.
     pub fn from_fee_check_error(
         actual_fee: Fee,
+        actual_tip: Fee,
         actual_gas: GasVector,
         error: FeeCheckError,
         tx_context: &TransactionContext,
     ) -> Self {
-        let recommended_fee = match error {
+        let (recommended_fee, recommended_tip) = match error {
             // If the error is insufficient balance, the recommended fee is the actual fee.
             // This recommendation assumes (a) the pre-validation checks were applied and pass (i.e.
             // the sender initially could cover the resource bounds), and (b) the actual resources
             // are within the resource bounds set by the sender; which ensures the (after reverting
             // execution state changes) the user *can* cover the fee.
-            FeeCheckError::InsufficientFeeTokenBalance { .. } => actual_fee,
+            FeeCheckError::InsufficientFeeTokenBalance { .. } => (actual_fee, actual_tip),
             // If max fee exceeded (deprecated tx), the recommended fee is the max fee. The
             // pre-validation phase ensures the account can cover the max fee, and after reverting
             // the execution state changes we return to this state.
@@ -84,7 +91,8 @@ impl FeeCheckReport {
                 let TransactionInfo::Deprecated(ref context) = tx_context.tx_info else {
                     panic!("MaxFeeExceeded can only originate from a deprecated transaction.");
                 };
-                context.max_fee
+                // Deprecated transactions pay no tip.
+                (context.max_fee, Fee(0))
             }
             // If the error is resource overdraft, charge for the minimum between (a) actual gas
             // used and (b) the user bound, for each gas type. Pre-validation phase ensures the
@@ -115,14 +123,13 @@ impl FeeCheckReport {
                     },
                 };
 
-                get_fee_by_gas_vector(
-                    &tx_context.block_context.block_info,
-                    gas_for_fee_charge,
-                    &FeeType::Strk,
-                )
+                let block_info = &tx_context.block_context.block_info;
+                let tip = tx_context.get_tip_by_gas_vector(gas_for_fee_charge);
+                let fee = get_fee_by_gas_vector(block_info, gas_for_fee_charge, &FeeType::Strk);
+                (fee.checked_add(tip).expect("The fee with the tip overflowed."), tip)
             }
         };
-        Self { recommended_fee, error: Some(error) }
+        Self { recommended_fee, recommended_tip, error: Some(error) }
     }
 
     /// If the actual cost exceeds the resource bounds on the transaction, returns a fee check
@@ -234,6 +241,10 @@ macro_rules! impl_report_fields {
                 self.0.recommended_fee()
             }
 
+            fn recommended_tip(&self) -> Fee {
+                self.0.recommended_tip()
+            }
+
             fn error(&self) -> Option<FeeCheckError> {
                 self.0.error()
             }
@@ -273,11 +284,11 @@ impl PostExecutionReport {
         tx_receipt: &TransactionReceipt,
         charge_fee: bool,
     ) -> TransactionExecutionResult<Self> {
-        let TransactionReceipt { fee, gas, .. } = tx_receipt;
+        let TransactionReceipt { fee, tip, gas, .. } = tx_receipt;
 
         // If fee is not enforced, no need to check post-execution.
         if !charge_fee {
-            return Ok(Self(FeeCheckReport::success_report(*fee)));
+            return Ok(Self(FeeCheckReport::success_report(*fee, *tip)));
         }
 
         // First, compare the actual resources used against the upper bound(s) defined by the
@@ -298,6 +309,7 @@ impl PostExecutionReport {
                     // current context, and return the report.
                     return Ok(Self(FeeCheckReport::from_fee_check_error(
                         *fee,
+                        *tip,
                         *gas,
                         fee_check_error,
                         tx_context,
@@ -307,6 +319,6 @@ impl PostExecutionReport {
             }
         }
 
-        Ok(Self(FeeCheckReport::success_report(*fee)))
+        Ok(Self(FeeCheckReport::success_report(*fee, *tip)))
     }
 }
@@ -88,9 +88,8 @@ pub fn get_balance_and_if_covers_fee(
     tx_context: &TransactionContext,
     fee: Fee,
 ) -> TransactionFeeResult<(Felt, Felt, bool)> {
-    let tx_info = &tx_context.tx_info;
-    let (balance_low, balance_high) =
-        state.get_fee_token_balance(tx_info.sender_address(), tx_context.fee_token_address())?;
+    let (balance_low, balance_high) = state
+        .get_fee_token_balance(tx_context.fee_payer_address(), tx_context.fee_token_address())?;
     Ok((
         balance_low,
         balance_high,
@@ -100,6 +99,44 @@ pub fn get_balance_and_if_covers_fee(
     ))
 }
 
+/// The fee token balance of a transaction's fee payer, compared to the maximal fee the transaction
+/// may be charged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommittedFeeBalance {
+    pub fee_token_address: ContractAddress,
+    pub committed_fee: Fee,
+    pub balance_low: Felt,
+    pub balance_high: Felt,
+    pub covers_committed_fee: bool,
+}
+
+/// Returns the maximal fee the transaction may be charged: the fee induced by its resource upper
+/// bounds, or its max fee for deprecated transactions.
+pub fn get_committed_fee(tx_info: &TransactionInfo) -> Fee {
+    match tx_info {
+        TransactionInfo::Current(context) => context.resource_bounds.max_possible_fee(),
+        TransactionInfo::Deprecated(context) => context.max_fee,
+    }
+}
+
+/// Returns the current fee balance of the transaction's fee payer, and whether it covers the
+/// resource upper bounds.
+pub fn get_committed_fee_balance(
+    state: &mut dyn StateReader,
+    tx_context: &TransactionContext,
+) -> TransactionFeeResult<CommittedFeeBalance> {
+    let committed_fee = get_committed_fee(&tx_context.tx_info);
+    let (balance_low, balance_high, covers_committed_fee) =
+        get_balance_and_if_covers_fee(state, tx_context, committed_fee)?;
+    Ok(CommittedFeeBalance {
+        fee_token_address: tx_context.fee_token_address(),
+        committed_fee,
+        balance_low,
+        balance_high,
+        covers_committed_fee,
+    })
+}
+
 /// Verifies that, given the current state, the account can cover the resource upper bounds.
 /// Error may indicate insufficient balance, or some other error.
 pub fn verify_can_pay_committed_bounds(
@@ -107,13 +144,9 @@ pub fn verify_can_pay_committed_bounds(
     tx_context: &TransactionContext,
 ) -> TransactionFeeResult<()> {
     let tx_info = &tx_context.tx_info;
-    let committed_fee = match tx_info {
-        TransactionInfo::Current(context) => context.resource_bounds.max_possible_fee(),
-        TransactionInfo::Deprecated(context) => context.max_fee,
-    };
-    let (balance_low, balance_high, can_pay) =
-        get_balance_and_if_covers_fee(state, tx_context, committed_fee)?;
-    if can_pay {
+    let CommittedFeeBalance { balance_low, balance_high, covers_committed_fee, .. } =
+        get_committed_fee_balance(state, tx_context)?;
+    if covers_committed_fee {
         Ok(())
     } else {
         Err(match tx_info {
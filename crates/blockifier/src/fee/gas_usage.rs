@@ -1,14 +1,24 @@
+use std::cmp::max;
+
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use starknet_api::block::{FeeType, GasPrice, NonzeroGasPrice};
 use starknet_api::executable_transaction::AccountTransaction as Transaction;
-use starknet_api::execution_resources::GasVector;
-use starknet_api::transaction::fields::GasVectorComputationMode;
+use starknet_api::execution_resources::{GasAmount, GasVector};
+use starknet_api::transaction::fields::{
+    AllResourceBounds,
+    GasVectorComputationMode,
+    ResourceBounds,
+};
 
 use super::fee_utils::get_vm_resources_cost;
 use crate::abi::constants;
 use crate::context::BlockContext;
 use crate::fee::eth_gas_constants;
 use crate::state::cached_state::StateChangesCount;
-use crate::transaction::account_transaction::AccountTransaction;
+use crate::state::state_api::UpdatableState;
+use crate::transaction::account_transaction::{AccountTransaction, SimulationFlags};
+use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::objects::TransactionExecutionResult;
 use crate::utils::u64_from_usize;
 
 #[cfg(test)]
@@ -202,3 +212,72 @@ pub fn estimate_minimal_gas_vector(
         )
     })
 }
+
+/// Safety margins, in percents, added to estimated resource bounds; they absorb changes of the
+/// state and of the gas prices between the estimation and the inclusion of the transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResourceBoundsMargin {
+    pub amount_percentage: u64,
+    pub price_percentage: u64,
+}
+
+impl Default for ResourceBoundsMargin {
+    fn default() -> Self {
+        Self { amount_percentage: 10, price_percentage: 50 }
+    }
+}
+
+impl ResourceBoundsMargin {
+    fn resource_bounds(&self, amount: GasAmount, price: NonzeroGasPrice) -> ResourceBounds {
+        let max_amount = add_percentage(amount.0.into(), self.amount_percentage);
+        ResourceBounds {
+            max_amount: GasAmount(u64::try_from(max_amount).unwrap_or(u64::MAX)),
+            max_price_per_unit: GasPrice(add_percentage(price.get().0, self.price_percentage)),
+        }
+    }
+}
+
+// Rounds up, saturating on overflow.
+fn add_percentage(value: u128, percentage: u64) -> u128 {
+    let scaled_value = value.saturating_mul(100 + u128::from(percentage));
+    scaled_value.div_ceil(100)
+}
+
+/// Returns ready-to-sign resource bounds for the given account transaction, priced in STRK: the gas
+/// it uses when simulated on top of the given state, but no less than the estimated lower bound,
+/// and the gas prices of the block, each raised by the given margins.
+/// The simulation validates the transaction but does not charge its fee, and runs with the L2 gas
+/// bound the transaction carries, which should suffice to complete it; the state is left
+/// unmodified. A transaction that reverts has no estimate.
+pub fn estimate_resource_bounds<U: UpdatableState>(
+    state: &mut U,
+    block_context: &BlockContext,
+    tx: &AccountTransaction,
+    margin: ResourceBoundsMargin,
+) -> TransactionExecutionResult<AllResourceBounds> {
+    let simulation_flags =
+        SimulationFlags { skip_validate: false, skip_fee_charge: true, skip_nonce_check: false };
+    let execution_info = tx.simulate(state, block_context, simulation_flags)?;
+    if let Some(revert_error) = execution_info.revert_error {
+        return Err(TransactionExecutionError::RevertedEstimation(revert_error.to_string()));
+    }
+
+    let simulated_gas = execution_info.receipt.gas;
+    let minimal_gas =
+        estimate_minimal_gas_vector(block_context, tx, &GasVectorComputationMode::All);
+    let gas_prices = block_context.block_info.gas_prices.gas_price_vector(&FeeType::Strk);
+    Ok(AllResourceBounds {
+        l1_gas: margin.resource_bounds(
+            max(simulated_gas.l1_gas, minimal_gas.l1_gas),
+            gas_prices.l1_gas_price,
+        ),
+        l2_gas: margin.resource_bounds(
+            max(simulated_gas.l2_gas, minimal_gas.l2_gas),
+            gas_prices.l2_gas_price,
+        ),
+        l1_data_gas: margin.resource_bounds(
+            max(simulated_gas.l1_data_gas, minimal_gas.l1_data_gas),
+            gas_prices.l1_data_gas_price,
+        ),
+    })
+}
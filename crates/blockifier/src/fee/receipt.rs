@@ -40,12 +40,31 @@ struct TransactionReceiptParameters<'a> {
 #[derive(Default, Debug, PartialEq)]
 pub struct TransactionReceipt {
     pub fee: Fee,
+    /// The part of the fee paid as a tip, on top of the cost of the gas.
+    pub tip: Fee,
     pub gas: GasVector,
     pub da_gas: GasVector,
     pub resources: TransactionResources,
+    /// How the charged fee was split between its recipients; [None] if no fee distribution is
+    /// configured, and the sequencer receives the whole fee.
+    pub fee_split: Option<FeeSplit>,
+}
+
+/// The shares of a transaction fee, in units of the fee token.
+#[cfg_attr(feature = "transaction_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FeeSplit {
+    pub sequencer: Fee,
+    pub treasury: Fee,
+    pub burned: Fee,
 }
 
 impl TransactionReceipt {
+    /// The share of the fee transferred to the sequencer.
+    pub fn sequencer_fee(&self) -> Fee {
+        self.fee_split.map_or(self.fee, |fee_split| fee_split.sequencer)
+    }
+
     fn from_params(tx_receipt_params: TransactionReceiptParameters<'_>) -> Self {
         let TransactionReceiptParameters {
             tx_context,
@@ -64,7 +83,12 @@ impl TransactionReceipt {
             calldata_length,
             signature_length,
             code_size,
-            StateResources::new(state_changes, sender_address, tx_context.fee_token_address()),
+            StateResources::new(
+                state_changes,
+                sender_address,
+                tx_context.fee_token_address(),
+                tx_context.state_diff_da_mode(),
+            ),
             l1_handler_payload_size,
             execution_summary_without_fee_transfer,
         );
@@ -95,10 +119,13 @@ impl TransactionReceipt {
             &tx_context.get_gas_vector_computation_mode(),
         );
         // Backward-compatibility.
-        let fee = if tx_type == TransactionType::Declare && tx_context.tx_info.is_v0() {
-            Fee(0)
+        let (fee, tip) = if tx_type == TransactionType::Declare && tx_context.tx_info.is_v0() {
+            (Fee(0), Fee(0))
         } else {
-            tx_context.tx_info.get_fee_by_gas_vector(&tx_context.block_context.block_info, gas)
+            let block_info = &tx_context.block_context.block_info;
+            let tip = tx_context.get_tip_by_gas_vector(gas);
+            let fee = tx_context.tx_info.get_fee_by_gas_vector(block_info, gas);
+            (fee.checked_add(tip).expect("The fee with the tip overflowed."), tip)
         };
 
         let da_gas = tx_resources
@@ -106,7 +133,7 @@ impl TransactionReceipt {
             .state
             .da_gas_vector(tx_context.block_context.block_info.use_kzg_da);
 
-        Self { resources: tx_resources, gas, da_gas, fee }
+        Self { resources: tx_resources, gas, da_gas, fee, tip, fee_split: None }
     }
 
     /// Computes the receipt of an L1 handler transaction.
@@ -144,7 +171,7 @@ impl TransactionReceipt {
             signature_length: account_tx.signature_length(),
             code_size: account_tx.declare_code_size(),
             state_changes,
-            sender_address: Some(tx_context.tx_info.sender_address()),
+            sender_address: Some(tx_context.fee_payer_address()),
             l1_handler_payload_size: None,
             execution_summary_without_fee_transfer,
             tx_type: account_tx.tx_type(),
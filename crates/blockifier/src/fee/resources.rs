@@ -1,5 +1,6 @@
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use starknet_api::core::ContractAddress;
+use starknet_api::data_availability::DataAvailabilityMode;
 use starknet_api::execution_resources::{GasAmount, GasVector};
 use starknet_api::transaction::fields::GasVectorComputationMode;
 
@@ -162,9 +163,20 @@ impl StarknetResources {
 }
 
 #[cfg_attr(feature = "transaction_serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct StateResources {
     pub state_changes_for_fee: StateChangesCountForFee,
+    // Where the state changes are published; only changes published on L1 cost L1 (data) gas.
+    pub data_availability_mode: DataAvailabilityMode,
+}
+
+impl Default for StateResources {
+    fn default() -> Self {
+        Self {
+            state_changes_for_fee: StateChangesCountForFee::default(),
+            data_availability_mode: DataAvailabilityMode::L1,
+        }
+    }
 }
 
 impl StateResources {
@@ -172,10 +184,12 @@ impl StateResources {
         state_changes: &StateChanges,
         sender_address: Option<ContractAddress>,
         fee_token_address: ContractAddress,
+        data_availability_mode: DataAvailabilityMode,
     ) -> Self {
         Self {
             state_changes_for_fee: state_changes
                 .count_for_fee_charge(sender_address, fee_token_address),
+            data_availability_mode,
         }
     }
 
@@ -189,6 +203,7 @@ impl StateResources {
                 state_changes_count,
                 n_allocated_keys,
             },
+            data_availability_mode: DataAvailabilityMode::L1,
         }
     }
 
@@ -216,12 +231,24 @@ impl StateResources {
         })
     }
 
+    /// Returns the gas cost of publishing the state changes on L1; changes published to an
+    /// alternative data availability layer are free of L1 costs.
     pub fn da_gas_vector(&self, use_kzg_da: bool) -> GasVector {
-        get_da_gas_cost(&self.state_changes_for_fee.state_changes_count, use_kzg_da)
+        match self.data_availability_mode {
+            DataAvailabilityMode::L1 => {
+                get_da_gas_cost(&self.state_changes_for_fee.state_changes_count, use_kzg_da)
+            }
+            DataAvailabilityMode::L2 => GasVector::ZERO,
+        }
     }
 
     pub fn get_onchain_data_segment_length(&self) -> usize {
-        get_onchain_data_segment_length(&self.state_changes_for_fee.state_changes_count)
+        match self.data_availability_mode {
+            DataAvailabilityMode::L1 => {
+                get_onchain_data_segment_length(&self.state_changes_for_fee.state_changes_count)
+            }
+            DataAvailabilityMode::L2 => 0,
+        }
     }
 }
 
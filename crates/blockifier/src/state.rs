@@ -1,5 +1,6 @@
 pub mod cached_state;
 pub mod contract_class_manager;
+pub mod da_encoding;
 #[cfg(test)]
 pub mod error_format_test;
 pub mod errors;
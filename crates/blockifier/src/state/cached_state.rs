@@ -1,5 +1,6 @@
 use std::cell::{Ref, RefCell};
 use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 
 use indexmap::IndexMap;
 use starknet_api::abi::abi_utils::get_fee_token_var_address;
@@ -34,6 +35,20 @@ pub struct CachedState<S: StateReader> {
     pub(crate) class_hash_to_class: RefCell<ContractClassMapping>,
     /// A map from class hash to the set of PC values that were visited in the class.
     pub visited_pcs: HashMap<ClassHash, HashSet<usize>>,
+    // The savepoints taken on the state, in the order they were taken.
+    savepoints: Vec<Savepoint>,
+}
+
+/// Identifies a savepoint taken on a [`CachedState`], to roll back to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SavepointId(usize);
+
+// The writes of a state at the time a savepoint was taken.
+#[derive(Clone, Debug)]
+struct Savepoint {
+    writes: StateMaps,
+    class_hash_to_class: ContractClassMapping,
+    visited_pcs: HashMap<ClassHash, HashSet<usize>>,
 }
 
 impl<S: StateReader> CachedState<S> {
@@ -43,9 +58,36 @@ impl<S: StateReader> CachedState<S> {
             cache: RefCell::new(StateCache::default()),
             class_hash_to_class: RefCell::new(HashMap::default()),
             visited_pcs: HashMap::default(),
+            savepoints: Vec::new(),
         }
     }
 
+    /// Takes a savepoint of the writes performed so far. Savepoints nest: rolling back to a
+    /// savepoint discards the writes performed since, while keeping those of earlier savepoints.
+    pub fn checkpoint(&mut self) -> SavepointId {
+        self.savepoints.push(Savepoint {
+            writes: self.cache.get_mut().writes.clone(),
+            class_hash_to_class: self.class_hash_to_class.get_mut().clone(),
+            visited_pcs: self.visited_pcs.clone(),
+        });
+        SavepointId(self.savepoints.len() - 1)
+    }
+
+    /// Discards the writes (including declared classes) performed since the given savepoint, and
+    /// the savepoints taken after it; the savepoint itself can be rolled back to again.
+    /// The initial reads are kept, as they do not depend on the writes.
+    pub fn rollback_to(&mut self, savepoint_id: SavepointId) {
+        let Savepoint { writes, class_hash_to_class, visited_pcs } = self
+            .savepoints
+            .get(savepoint_id.0)
+            .unwrap_or_else(|| panic!("Savepoint {savepoint_id:?} does not exist."))
+            .clone();
+        self.savepoints.truncate(savepoint_id.0 + 1);
+        self.cache.get_mut().writes = writes;
+        *self.class_hash_to_class.get_mut() = class_hash_to_class;
+        self.visited_pcs = visited_pcs;
+    }
+
     /// Returns the state diff resulting from the performed writes, with respect to the parent
     /// state.
     pub fn to_state_diff(&mut self) -> StateResult<StateChanges> {
@@ -64,6 +106,12 @@ impl<S: StateReader> CachedState<S> {
         Ok(self.cache.borrow())
     }
 
+    /// Returns the values read from the underlying state, including the initial values of the
+    /// cells that were only written.
+    pub fn read_set(&mut self) -> StateResult<StateMaps> {
+        Ok(self.borrow_updated_state_cache()?.initial_reads.clone())
+    }
+
     pub fn update_cache(
         &mut self,
         write_updates: &StateMaps,
@@ -292,6 +340,7 @@ impl Default for CachedState<crate::test_utils::dict_state_reader::DictStateRead
             cache: Default::default(),
             class_hash_to_class: Default::default(),
             visited_pcs: Default::default(),
+            savepoints: Default::default(),
         }
     }
 }
@@ -301,6 +350,11 @@ impl<S: StateReader> CachedState<S> {
     pub fn get_initial_reads(&self) -> StateResult<StateMaps> {
         Ok(self.cache.borrow().initial_reads.clone())
     }
+
+    /// Returns the hashes of the classes loaded into the state, either read or declared.
+    pub fn get_loaded_class_hashes(&self) -> HashSet<ClassHash> {
+        self.class_hash_to_class.borrow().keys().copied().collect()
+    }
 }
 
 pub type StorageEntry = (ContractAddress, StorageKey);
@@ -384,6 +438,69 @@ impl StateMaps {
             compiled_class_hash_keys: self.compiled_class_hashes.into_keys().collect(),
         }
     }
+
+    /// Squashes consecutive state diffs (e.g., of the transactions of a block) into a single one;
+    /// a value written by several diffs is taken from the last of them.
+    pub fn squash<'a>(state_diffs: impl IntoIterator<Item = &'a Self>) -> Self {
+        let mut squashed_state_diff = Self::default();
+        for state_diff in state_diffs {
+            squashed_state_diff.extend(state_diff);
+        }
+        squashed_state_diff
+    }
+
+    /// Removes the writes of values already present in the given state. Declarations are kept, as
+    /// a class cannot be declared twice.
+    pub fn remove_trivial_writes<S: StateReader>(&mut self, state: &S) -> StateResult<()> {
+        remove_present_values(&mut self.storage, |(address, key)| {
+            state.get_storage_at(address, key)
+        })?;
+        remove_present_values(&mut self.nonces, |address| state.get_nonce_at(address))?;
+        remove_present_values(&mut self.class_hashes, |address| state.get_class_hash_at(address))
+    }
+
+    /// Splits the contract attributes (nonces, class hashes and storage) of the state diff by the
+    /// modified contract. Declarations are not attributed to any contract, and are dropped.
+    pub fn split_by_contract(&self) -> HashMap<ContractAddress, Self> {
+        let mut contract_diffs: HashMap<ContractAddress, Self> = HashMap::new();
+        for (address, nonce) in &self.nonces {
+            contract_diffs.entry(*address).or_default().nonces.insert(*address, *nonce);
+        }
+        for (address, class_hash) in &self.class_hashes {
+            contract_diffs.entry(*address).or_default().class_hashes.insert(*address, *class_hash);
+        }
+        for (storage_entry, value) in &self.storage {
+            let contract_diff = contract_diffs.entry(storage_entry.0).or_default();
+            contract_diff.storage.insert(*storage_entry, *value);
+        }
+        contract_diffs
+    }
+
+    pub fn count(&self) -> StateChangesCount {
+        StateChangesCount {
+            n_storage_updates: self.storage.len(),
+            n_class_hash_updates: self.class_hashes.len(),
+            n_compiled_class_hash_updates: self.compiled_class_hashes.len(),
+            n_modified_contracts: self.get_contract_addresses().len(),
+        }
+    }
+}
+
+// Removes the entries of the given mapping whose value equals the present one.
+fn remove_present_values<K: Copy + Eq + Hash, V: PartialEq>(
+    mapping: &mut HashMap<K, V>,
+    present_value: impl Fn(K) -> StateResult<V>,
+) -> StateResult<()> {
+    let mut present_keys = Vec::new();
+    for (key, value) in mapping.iter() {
+        if present_value(*key)? == *value {
+            present_keys.push(*key);
+        }
+    }
+    for key in present_keys {
+        mapping.remove(&key);
+    }
+    Ok(())
 }
 /// Caches read and write requests.
 /// The tracked changes are needed for block state commitment.
@@ -606,6 +723,32 @@ impl<U: UpdatableState> TransactionalState<'_, U> {
     }
 }
 
+impl<S: State + ?Sized> CachedState<MutRefState<'_, S>> {
+    /// Commits the storage and class hash changes in the child state to its parent, through the
+    /// `State` API; used to run an entry point call on top of the state it is given. Calls do not
+    /// change nonces nor declare classes, so these are not committed.
+    pub fn commit_call_changes(self) -> StateResult<()> {
+        let state = self.state.0;
+        let writes = self.cache.into_inner().writes;
+        debug_assert!(
+            writes.nonces.is_empty()
+                && writes.compiled_class_hashes.is_empty()
+                && writes.declared_contracts.is_empty(),
+            "Entry point calls only change the storage and class hashes."
+        );
+        for ((contract_address, key), value) in writes.storage {
+            state.set_storage_at(contract_address, key, value)?;
+        }
+        for (contract_address, class_hash) in writes.class_hashes {
+            state.set_class_hash_at(contract_address, class_hash)?;
+        }
+        for (class_hash, pcs) in &self.visited_pcs {
+            state.add_visited_pcs(*class_hash, pcs);
+        }
+        Ok(())
+    }
+}
+
 type StorageDiff = IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>;
 
 /// Holds uncommitted changes induced on Starknet contracts.
@@ -633,6 +776,32 @@ impl From<StateMaps> for CommitmentStateDiff {
     }
 }
 
+impl CommitmentStateDiff {
+    /// Returns the storage updates and nonces of the state diff under the given keys; these are
+    /// the state changes that may be published to an alternative data availability layer.
+    pub fn storage_and_nonces_of(&self, keys: &StateChangesKeys) -> Self {
+        let storage_updates = self
+            .storage_updates
+            .iter()
+            .filter_map(|(address, updates)| {
+                let updates: IndexMap<_, _> = updates
+                    .iter()
+                    .filter(|(key, _)| keys.storage_keys.contains(&(*address, **key)))
+                    .map(|(key, value)| (*key, *value))
+                    .collect();
+                (!updates.is_empty()).then_some((*address, updates))
+            })
+            .collect();
+        let address_to_nonce = self
+            .address_to_nonce
+            .iter()
+            .filter(|(address, _)| keys.nonce_keys.contains(address))
+            .map(|(address, nonce)| (*address, *nonce))
+            .collect();
+        Self { storage_updates, address_to_nonce, ..Default::default() }
+    }
+}
+
 /// Used to track the state diff size, which is determined by the number of new keys.
 /// Also, can be used to accuratly measure the contribution of a single (say, transactional)
 /// state to a cumulative state diff - provides set-like functionallities for this porpuse.
@@ -697,6 +866,49 @@ impl StateChangesKeys {
             let sequencer_balance_low = get_fee_token_var_address(sequencer_address);
             self.storage_keys.insert((tx_context.fee_token_address(), sequencer_balance_low));
         }
+        if concurrency_mode {
+            // Likewise for the deleted balance keys of the fee distribution recipients.
+            let fee_distribution = &tx_context.block_context.chain_info.fee_distribution;
+            for (recipient, _) in tx_result
+                .receipt
+                .fee_split
+                .map(|fee_split| fee_distribution.share_transfers(&fee_split))
+                .unwrap_or_default()
+            {
+                if recipient != tx_context.fee_payer_address() {
+                    let recipient_balance_low = get_fee_token_var_address(recipient);
+                    self.storage_keys
+                        .insert((tx_context.fee_token_address(), recipient_balance_low));
+                }
+            }
+        }
+    }
+
+    /// Returns the values of the keys in the given state.
+    pub fn read_values<S: StateReader>(&self, state: &S) -> StateResult<StateMaps> {
+        Ok(StateMaps {
+            nonces: self
+                .nonce_keys
+                .iter()
+                .map(|address| Ok((*address, state.get_nonce_at(*address)?)))
+                .collect::<StateResult<_>>()?,
+            class_hashes: self
+                .class_hash_keys
+                .iter()
+                .map(|address| Ok((*address, state.get_class_hash_at(*address)?)))
+                .collect::<StateResult<_>>()?,
+            storage: self
+                .storage_keys
+                .iter()
+                .map(|(address, key)| Ok(((*address, *key), state.get_storage_at(*address, *key)?)))
+                .collect::<StateResult<_>>()?,
+            compiled_class_hashes: self
+                .compiled_class_hash_keys
+                .iter()
+                .map(|class_hash| Ok((*class_hash, state.get_compiled_class_hash(*class_hash)?)))
+                .collect::<StateResult<_>>()?,
+            declared_contracts: HashMap::new(),
+        })
     }
 
     pub fn count(&self) -> StateChangesCount {
@@ -758,6 +970,61 @@ pub struct StateChanges {
 }
 
 impl StateChanges {
+    /// Squashes consecutive state changes (e.g., of the transactions of a block) into a single one.
+    /// A key is allocated if its first write allocated it, and its last write is nonzero.
+    pub fn squash<'a>(state_changes: impl IntoIterator<Item = &'a Self>) -> Self {
+        let mut state_maps = StateMaps::default();
+        let mut allocated_by_first_write: HashMap<StorageEntry, bool> = HashMap::new();
+        for state_change in state_changes {
+            for storage_entry in state_change.state_maps.storage.keys() {
+                allocated_by_first_write
+                    .entry(*storage_entry)
+                    .or_insert_with(|| state_change.allocated_keys.0.contains(storage_entry));
+            }
+            state_maps.extend(&state_change.state_maps);
+        }
+
+        let allocated_keys = AllocatedKeys(
+            allocated_by_first_write
+                .into_iter()
+                .filter(|(storage_entry, allocated)| {
+                    *allocated && state_maps.storage[storage_entry] != Felt::ZERO
+                })
+                .map(|(storage_entry, _)| storage_entry)
+                .collect(),
+        );
+        Self { state_maps, allocated_keys }
+    }
+
+    /// Removes the writes of values already present in the given state; see
+    /// [`StateMaps::remove_trivial_writes`].
+    pub fn remove_trivial_writes<S: StateReader>(&mut self, state: &S) -> StateResult<()> {
+        self.state_maps.remove_trivial_writes(state)?;
+        let storage = &self.state_maps.storage;
+        self.allocated_keys.0.retain(|storage_entry| storage.contains_key(storage_entry));
+        Ok(())
+    }
+
+    /// Splits the contract attributes of the state changes by the modified contract; see
+    /// [`StateMaps::split_by_contract`].
+    pub fn split_by_contract(&self) -> HashMap<ContractAddress, Self> {
+        self.state_maps
+            .split_by_contract()
+            .into_iter()
+            .map(|(address, state_maps)| {
+                let allocated_keys = AllocatedKeys(
+                    self.allocated_keys
+                        .0
+                        .iter()
+                        .filter(|(allocated_address, _)| *allocated_address == address)
+                        .copied()
+                        .collect(),
+                );
+                (address, Self { state_maps, allocated_keys })
+            })
+            .collect()
+    }
+
     pub fn count_for_fee_charge(
         &self,
         sender_address: Option<ContractAddress>,
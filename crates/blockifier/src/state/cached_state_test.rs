@@ -217,6 +217,25 @@ fn cannot_set_class_hash_to_uninitialized_contract() {
     );
 }
 
+#[test]
+fn commit_call_changes() {
+    let contract_address = contract_address!("0x100");
+    let key = storage_key!(0x10_u16);
+    let class_hash = class_hash!("0x10");
+    let mut state: CachedState<DictStateReader> = CachedState::default();
+    let parent: &mut dyn State = &mut state;
+
+    let mut child_state = CachedState::new(MutRefState::new(parent));
+    child_state.set_storage_at(contract_address, key, felt!("0x1")).unwrap();
+    child_state.set_class_hash_at(contract_address, class_hash).unwrap();
+    // Changes are not visible in the parent state before the commit.
+    assert_eq!(child_state.state.0.get_storage_at(contract_address, key).unwrap(), Felt::ZERO);
+    child_state.commit_call_changes().unwrap();
+
+    assert_eq!(state.get_storage_at(contract_address, key).unwrap(), felt!("0x1"));
+    assert_eq!(state.get_class_hash_at(contract_address).unwrap(), class_hash);
+}
+
 #[test]
 fn cached_state_state_diff_conversion() {
     // This will not appear in the diff, since this mapping is immutable for the current version we
@@ -525,6 +544,51 @@ fn test_write_at_validate_and_execute(
     assert_eq!(n_allocated_keys > 0, charged);
 }
 
+#[test]
+fn test_rollback_to_savepoint() {
+    let contract_address = contract_address!(CONTRACT_ADDRESS);
+    let key = storage_key!(0x10_u16);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let class_hash = test_contract.get_class_hash();
+    let mut state: CachedState<DictStateReader> = CachedState::default();
+
+    state.set_storage_at(contract_address, key, felt!("0x1")).unwrap();
+    let first_savepoint = state.checkpoint();
+    state.increment_nonce(contract_address).unwrap();
+    state.set_contract_class(class_hash, test_contract.get_runnable_class()).unwrap();
+    let second_savepoint = state.checkpoint();
+    state.set_storage_at(contract_address, key, felt!("0x2")).unwrap();
+
+    state.rollback_to(second_savepoint);
+    assert_eq!(state.get_storage_at(contract_address, key).unwrap(), felt!("0x1"));
+    assert_eq!(state.get_nonce_at(contract_address).unwrap(), nonce!(1_u8));
+    assert!(state.get_compiled_class(class_hash).is_ok());
+
+    state.rollback_to(first_savepoint);
+    assert_eq!(state.get_storage_at(contract_address, key).unwrap(), felt!("0x1"));
+    assert_eq!(state.get_nonce_at(contract_address).unwrap(), Nonce::default());
+    assert_matches!(
+        state.get_compiled_class(class_hash).unwrap_err(),
+        StateError::UndeclaredClassHash(undeclared_class_hash) if
+        undeclared_class_hash == class_hash
+    );
+
+    // A savepoint can be rolled back to again.
+    state.set_storage_at(contract_address, key, felt!("0x3")).unwrap();
+    state.rollback_to(first_savepoint);
+    assert_eq!(state.get_storage_at(contract_address, key).unwrap(), felt!("0x1"));
+}
+
+#[test]
+#[should_panic(expected = "does not exist")]
+fn test_rollback_to_discarded_savepoint() {
+    let mut state: CachedState<DictStateReader> = CachedState::default();
+    let first_savepoint = state.checkpoint();
+    let second_savepoint = state.checkpoint();
+    state.rollback_to(first_savepoint);
+    state.rollback_to(second_savepoint);
+}
+
 #[test]
 fn test_contract_cache_is_used() {
     // Initialize the global cache with a single class, and initialize an empty state with this
@@ -715,3 +779,95 @@ fn test_state_maps() {
 
     assert_eq!(maps, empty);
 }
+
+#[test]
+fn test_squash_state_changes() {
+    let (address1, address2) = (contract_address!("0x101"), contract_address!("0x102"));
+    let (key1, key2, key3) = (storage_key!(0x1_u8), storage_key!(0x2_u8), storage_key!(0x3_u8));
+    let state_changes =
+        |storage: &[(StorageEntry, Felt)], allocated_keys: &[StorageEntry]| StateChanges {
+            state_maps: StateMaps {
+                storage: HashMap::from_iter(storage.to_vec()),
+                ..Default::default()
+            },
+            allocated_keys: AllocatedKeys(allocated_keys.iter().copied().collect()),
+        };
+
+    let first_tx_changes = state_changes(
+        &[((address1, key1), felt!("0x1")), ((address1, key2), felt!("0x2"))],
+        &[(address1, key1), (address1, key2)],
+    );
+    let second_tx_changes = state_changes(
+        // The first key is cleared, and the third was nonzero before the block.
+        &[((address1, key1), Felt::ZERO), ((address2, key3), felt!("0x3"))],
+        &[],
+    );
+    let third_tx_changes = state_changes(
+        // The first key is allocated again, within the block.
+        &[((address1, key1), felt!("0x4")), ((address2, key3), felt!("0x5"))],
+        &[(address1, key1)],
+    );
+
+    let squashed_changes =
+        StateChanges::squash([&first_tx_changes, &second_tx_changes, &third_tx_changes]);
+    assert_eq!(
+        squashed_changes,
+        state_changes(
+            &[
+                ((address1, key1), felt!("0x4")),
+                ((address1, key2), felt!("0x2")),
+                ((address2, key3), felt!("0x5")),
+            ],
+            &[(address1, key1), (address1, key2)],
+        )
+    );
+
+    let contract_changes = squashed_changes.split_by_contract();
+    assert_eq!(contract_changes.len(), 2);
+    assert_eq!(contract_changes[&address1].allocated_keys.len(), 2);
+    assert_eq!(
+        contract_changes[&address2],
+        state_changes(&[((address2, key3), felt!("0x5"))], &[])
+    );
+    assert_eq!(contract_changes[&address1].state_maps.count().n_storage_updates, 2);
+}
+
+#[test]
+fn test_remove_trivial_writes() {
+    let (address1, address2) = (contract_address!("0x101"), contract_address!("0x102"));
+    let key = storage_key!(0x1_u8);
+    let state = DictStateReader {
+        storage_view: HashMap::from([((address1, key), felt!("0x1"))]),
+        address_to_nonce: HashMap::from([(address1, nonce!(1_u8))]),
+        ..Default::default()
+    };
+
+    let mut state_changes = StateChanges {
+        state_maps: StateMaps {
+            storage: HashMap::from([
+                ((address1, key), felt!("0x1")),
+                ((address2, key), felt!("0x2")),
+            ]),
+            nonces: HashMap::from([(address1, nonce!(1_u8)), (address2, nonce!(1_u8))]),
+            ..Default::default()
+        },
+        allocated_keys: AllocatedKeys([(address2, key)].into()),
+    };
+    state_changes.remove_trivial_writes(&state).unwrap();
+
+    let expected_state_maps = StateMaps {
+        storage: HashMap::from([((address2, key), felt!("0x2"))]),
+        nonces: HashMap::from([(address2, nonce!(1_u8))]),
+        ..Default::default()
+    };
+    assert_eq!(state_changes.state_maps, expected_state_maps);
+    assert_eq!(
+        expected_state_maps.count(),
+        StateChangesCount {
+            n_storage_updates: 1,
+            n_class_hash_updates: 0,
+            n_compiled_class_hash_updates: 0,
+            n_modified_contracts: 1,
+        }
+    );
+}
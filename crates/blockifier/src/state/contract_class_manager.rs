@@ -3,8 +3,6 @@ use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
 #[cfg(feature = "cairo_native")]
 use std::sync::Arc;
 
-#[cfg(any(feature = "testing", test))]
-use cached::Cached;
 #[cfg(feature = "cairo_native")]
 use log;
 use starknet_api::core::ClassHash;
@@ -22,11 +20,14 @@ use starknet_sierra_compile::SierraToNativeCompiler;
 use crate::blockifier::config::ContractClassManagerConfig;
 #[cfg(feature = "cairo_native")]
 use crate::execution::contract_class::CompiledClassV1;
+use crate::execution::contract_class::RunnableCompiledClass;
 #[cfg(feature = "cairo_native")]
 use crate::execution::native::contract_class::NativeCompiledClassV1;
 #[cfg(feature = "cairo_native")]
+use crate::execution::native::execution_guard::NATIVE_EXECUTION_GUARD;
+#[cfg(feature = "cairo_native")]
 use crate::state::global_cache::CachedCairoNative;
-use crate::state::global_cache::{CachedCasm, ContractCaches};
+use crate::state::global_cache::{CachedCasm, ContractCacheStats, ContractCaches};
 
 #[cfg(feature = "cairo_native")]
 const CHANNEL_SIZE: usize = 1000;
@@ -67,11 +68,19 @@ impl ContractClassManager {
     /// 3. `config.wait_on_native_compilation` is `true`.
     pub fn start(config: ContractClassManagerConfig) -> ContractClassManager {
         // TODO(Avi, 15/12/2024): Add the size of the channel to the config.
-        let contract_caches = ContractCaches::new(config.contract_cache_size);
+        let contract_caches =
+            ContractCaches::new(config.contract_cache_size, config.contract_cache_size_in_bytes);
         #[cfg(not(feature = "cairo_native"))]
         return ContractClassManager { contract_caches };
         #[cfg(feature = "cairo_native")]
         {
+            for class_hash in &config.native_execution_disabled_class_hashes {
+                NATIVE_EXECUTION_GUARD.disable(*class_hash);
+            }
+            NATIVE_EXECUTION_GUARD.set_limits(
+                config.native_execution_stack_size,
+                config.native_execution_max_memory_growth,
+            );
             if !config.run_cairo_native {
                 // Native compilation is disabled - no need to start the compilation worker.
                 return ContractClassManager {
@@ -113,6 +122,10 @@ impl ContractClassManager {
     #[cfg(feature = "cairo_native")]
     pub fn send_compilation_request(&self, request: CompilationRequest) {
         assert!(self.config.run_cairo_native, "Native compilation is disabled.");
+        if NATIVE_EXECUTION_GUARD.is_disabled(&request.0) {
+            // The class is executed by the VM; compiling it to native is wasted work.
+            return;
+        }
         if self.config.wait_on_native_compilation {
             // Compilation requests are processed synchronously. No need to go through the channel.
             let compiler = self.compiler.as_ref().expect("Compiler not available.");
@@ -137,6 +150,42 @@ impl ContractClassManager {
         });
     }
 
+    /// Returns the class to run for the given class hash, if it exists in cache. When Cairo native
+    /// execution is enabled, classes that were compiled to native are run natively; the others,
+    /// including classes whose compilation failed (e.g., on features native does not support), are
+    /// run by the Cairo VM.
+    pub fn get_runnable(&self, class_hash: &ClassHash) -> Option<RunnableCompiledClass> {
+        #[cfg(feature = "cairo_native")]
+        if self.config.run_cairo_native {
+            if let Some(CachedCairoNative::Compiled(native_compiled_class)) =
+                self.get_native(class_hash)
+            {
+                return Some(RunnableCompiledClass::V1Native(native_compiled_class));
+            }
+        }
+
+        self.get_casm(class_hash).map(CachedCasm::to_runnable)
+    }
+
+    /// Caches the casm compiled class for the given class hash and, when Cairo native execution is
+    /// enabled, requests the compilation of its Sierra to native.
+    pub fn set_and_compile(&self, class_hash: ClassHash, compiled_class: CachedCasm) {
+        #[cfg(feature = "cairo_native")]
+        let compilation_request = match &compiled_class {
+            CachedCasm::WithSierra(RunnableCompiledClass::V1(casm), sierra)
+                if self.config.run_cairo_native =>
+            {
+                Some((class_hash, sierra.clone(), casm.clone()))
+            }
+            _ => None,
+        };
+        self.set_casm(class_hash, compiled_class);
+        #[cfg(feature = "cairo_native")]
+        if let Some(compilation_request) = compilation_request {
+            self.send_compilation_request(compilation_request);
+        }
+    }
+
     /// Returns the native compiled class for the given class hash, if it exists in cache.
     #[cfg(feature = "cairo_native")]
     pub fn get_native(&self, class_hash: &ClassHash) -> Option<CachedCairoNative> {
@@ -153,6 +202,28 @@ impl ContractClassManager {
         self.contract_caches.set_casm(class_hash, compiled_class);
     }
 
+    /// Caches a class read without its Sierra. When Cairo native execution is enabled, Cairo 1
+    /// classes are left for the readers that have their Sierra to cache, and compile to native.
+    pub fn set_runnable(&self, class_hash: ClassHash, runnable_class: RunnableCompiledClass) {
+        #[cfg(feature = "cairo_native")]
+        if self.config.run_cairo_native && matches!(runnable_class, RunnableCompiledClass::V1(_)) {
+            return;
+        }
+        self.set_casm(class_hash, CachedCasm::WithoutSierra(runnable_class));
+    }
+
+    /// Drops the cached classes of the given hash; e.g., when the class is declared, so that any
+    /// stale entry is re-read from the state that declares it.
+    pub fn invalidate(&self, class_hash: &ClassHash) {
+        self.contract_caches.invalidate(class_hash);
+    }
+
+    /// Returns the hit, miss and eviction counts of the casm cache, shared by all the users of
+    /// this manager.
+    pub fn cache_stats(&self) -> ContractCacheStats {
+        self.contract_caches.casm_cache.stats()
+    }
+
     /// Clear the contract caches.
     pub fn clear(&mut self) {
         self.contract_caches.clear();
@@ -160,7 +231,7 @@ impl ContractClassManager {
 
     #[cfg(any(feature = "testing", test))]
     pub fn get_casm_cache_size(&self) -> usize {
-        self.contract_caches.casm_cache.lock().cache_size()
+        self.contract_caches.casm_cache.stats().n_classes
     }
 }
 
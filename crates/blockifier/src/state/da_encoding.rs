@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use num_bigint::BigUint;
+use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce, StateDiffCommitment};
+use starknet_api::state::{StorageKey, ThinStateDiff};
+use starknet_types_core::felt::Felt;
+
+use super::cached_state::{CommitmentStateDiff, StateMaps};
+use super::state_api::StateReader;
+use super::stateful_compression::{compress, CompressionResult};
+
+#[cfg(test)]
+#[path = "da_encoding_test.rs"]
+pub mod da_encoding_test;
+
+// Stateless compression.
+const COMPRESSION_VERSION: u8 = 0;
+const MAX_N_BITS: usize = 251;
+// The bit lengths of the buckets of unique values, by bucket index.
+const N_BITS_PER_BUCKET: [usize; 6] = [252, 125, 83, 62, 31, 15];
+const N_UNIQUE_VALUE_BUCKETS: usize = N_BITS_PER_BUCKET.len();
+// The unique value buckets and the repeating value bucket.
+const TOTAL_N_BUCKETS: usize = N_UNIQUE_VALUE_BUCKETS + 1;
+const REPEATING_VALUE_BUCKET_INDEX: usize = N_UNIQUE_VALUE_BUCKETS;
+const HEADER_ELM_N_BITS: usize = 20;
+// Version, data length, unique value bucket lengths and the number of repeating values.
+const HEADER_LEN: usize = 1 + 1 + N_UNIQUE_VALUE_BUCKETS + 1;
+
+// EIP-4844 blobs.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+const BYTES_PER_FIELD_ELEMENT: usize = 32;
+const BLS_PRIME_HEX: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+const BLS_PRIMITIVE_ROOT: u8 = 7;
+
+static BLS_PRIME: LazyLock<BigUint> = LazyLock::new(|| {
+    BigUint::parse_bytes(BLS_PRIME_HEX.as_bytes(), 16).expect("Invalid BLS prime.")
+});
+
+// A root of unity of order `FIELD_ELEMENTS_PER_BLOB` in the BLS field.
+static BLS_ROOT_OF_UNITY: LazyLock<BigUint> = LazyLock::new(|| {
+    let bls_prime = &*BLS_PRIME;
+    let order = BigUint::from(FIELD_ELEMENTS_PER_BLOB);
+    BigUint::from(BLS_PRIMITIVE_ROOT).modpow(&((bls_prime - 1_u8) / order), bls_prime)
+});
+
+/// The data availability encoding of the state diff of a block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateDiffDaEncoding {
+    /// The state diff, serialized as in the output of the Starknet OS.
+    pub serialized_state_diff: Vec<Felt>,
+    /// The serialized state diff after stateless compression; the data published on L1.
+    pub compressed_state_diff: Vec<Felt>,
+    /// The compressed state diff as EIP-4844 blobs of `FIELD_ELEMENTS_PER_BLOB` 32-byte
+    /// big-endian words each.
+    pub blobs: Vec<Vec<u8>>,
+    /// The commitment to the state diff, as in the block header.
+    pub state_diff_commitment: StateDiffCommitment,
+}
+
+/// Encodes the state diff of a block for data availability, given the state before the block.
+/// If the chain uses stateful compression, the aliases of the state diff must have been allocated
+/// in the given state, see `state_diff_with_alias_allocation`.
+///
+/// Note: the commitment does not cover Cairo 0 classes, which are not part of the state diff.
+pub fn encode_state_diff<S: StateReader>(
+    state_diff: &StateMaps,
+    state: &S,
+    alias_contract_address: Option<ContractAddress>,
+) -> CompressionResult<StateDiffDaEncoding> {
+    let state_diff_commitment = calculate_state_diff_hash(&thin_state_diff(state_diff));
+
+    // The nonces of all the modified contracts are published, not just the updated ones.
+    let mut published_state_diff = state_diff.clone();
+    for contract_address in state_diff.get_contract_addresses() {
+        if !published_state_diff.nonces.contains_key(&contract_address) {
+            let nonce = state.get_nonce_at(contract_address)?;
+            published_state_diff.nonces.insert(contract_address, nonce);
+        }
+    }
+    if let Some(alias_contract_address) = alias_contract_address {
+        published_state_diff = compress(&published_state_diff, state, alias_contract_address)?;
+    }
+
+    let serialized_state_diff = serialize_state_diff(&published_state_diff);
+    let compressed_state_diff = stateless_compress(&serialized_state_diff);
+    let blobs = encode_blobs(&compressed_state_diff);
+    Ok(StateDiffDaEncoding {
+        serialized_state_diff,
+        compressed_state_diff,
+        blobs,
+        state_diff_commitment,
+    })
+}
+
+fn thin_state_diff(state_diff: &StateMaps) -> ThinStateDiff {
+    let CommitmentStateDiff {
+        address_to_class_hash,
+        address_to_nonce,
+        storage_updates,
+        class_hash_to_compiled_class_hash,
+    } = CommitmentStateDiff::from(state_diff.clone());
+    ThinStateDiff {
+        // Deployed contracts and replaced classes are committed to alike.
+        deployed_contracts: address_to_class_hash,
+        storage_diffs: storage_updates,
+        declared_classes: class_hash_to_compiled_class_hash,
+        deprecated_declared_classes: Vec::new(),
+        nonces: address_to_nonce,
+        replaced_classes: Default::default(),
+    }
+}
+
+/// Serializes the state diff as in the output of the Starknet OS:
+/// [n_contracts, (contract_address, header, [class_hash], (key, value) * n_updates) * n_contracts,
+///  n_classes, (class_hash, compiled_class_hash) * n_classes], sorted by address, key and class
+/// hash. The header packs n_updates + nonce * 2^64 + class_updated * 2^128.
+pub fn serialize_state_diff(state_diff: &StateMaps) -> Vec<Felt> {
+    let contract_diffs: BTreeMap<ContractAddress, StateMaps> =
+        state_diff.split_by_contract().into_iter().collect();
+
+    let mut serialized = vec![Felt::from(contract_diffs.len())];
+    for (address, contract_diff) in contract_diffs {
+        let storage_diff: BTreeMap<StorageKey, Felt> =
+            contract_diff.storage.iter().map(|((_, key), value)| (*key, *value)).collect();
+        let Nonce(nonce) = contract_diff.nonces.get(&address).copied().unwrap_or_default();
+        let class_hash = contract_diff.class_hashes.get(&address);
+        let class_updated = if class_hash.is_some() { Felt::ONE } else { Felt::ZERO };
+        let header = Felt::from(storage_diff.len())
+            + nonce * Felt::TWO.pow(64_u128)
+            + class_updated * Felt::TWO.pow(128_u128);
+        serialized.extend([*address.0.key(), header]);
+        if let Some(ClassHash(class_hash)) = class_hash {
+            serialized.push(*class_hash);
+        }
+        for (key, value) in storage_diff {
+            serialized.extend([*key.0.key(), value]);
+        }
+    }
+
+    let declared_classes: BTreeMap<_, _> = state_diff.compiled_class_hashes.iter().collect();
+    serialized.push(Felt::from(declared_classes.len()));
+    for (class_hash, compiled_class_hash) in declared_classes {
+        serialized.extend([class_hash.0, compiled_class_hash.0]);
+    }
+    serialized
+}
+
+/// Returns the length of the given state diff, serialized and compressed without state as when
+/// published, excluding the compression header.
+pub fn compressed_state_diff_length(state_diff: &StateMaps) -> usize {
+    if state_diff.get_contract_addresses().is_empty() && state_diff.compiled_class_hashes.is_empty()
+    {
+        return 0;
+    }
+    let compressed_length = stateless_compress(&serialize_state_diff(state_diff)).len();
+    compressed_length - stateless_compress(&[]).len()
+}
+
+/// Compresses the given data without state, as done by the Starknet OS before publishing it: the
+/// unique values are packed into buckets by their bit length, and repeating values are replaced by
+/// pointers to their first occurrence.
+pub fn stateless_compress(data: &[Felt]) -> Vec<Felt> {
+    assert!(data.len() < 1 << HEADER_ELM_N_BITS, "Data is too long to compress.");
+
+    // The buckets, sorted by their bit length.
+    let mut sorted_buckets: Vec<(usize, usize)> =
+        N_BITS_PER_BUCKET.iter().copied().zip(0..N_UNIQUE_VALUE_BUCKETS).collect();
+    sorted_buckets.sort();
+
+    let mut unique_value_buckets: [Vec<Felt>; N_UNIQUE_VALUE_BUCKETS] = Default::default();
+    let mut value_to_location: BTreeMap<Felt, (usize, usize)> = BTreeMap::new();
+    let mut repeating_value_locations = Vec::new();
+    let mut bucket_index_per_elm = Vec::with_capacity(data.len());
+    for value in data {
+        if let Some(location) = value_to_location.get(value) {
+            repeating_value_locations.push(*location);
+            bucket_index_per_elm.push(REPEATING_VALUE_BUCKET_INDEX);
+            continue;
+        }
+        let n_bits =
+            usize::try_from(value.to_biguint().bits()).expect("Bit length should fit in usize.");
+        let (_, bucket_index) = *sorted_buckets
+            .iter()
+            .find(|(bucket_n_bits, _)| n_bits <= *bucket_n_bits)
+            .expect("A felt should fit in the largest bucket.");
+        let bucket = &mut unique_value_buckets[bucket_index];
+        value_to_location.insert(*value, (bucket_index, bucket.len()));
+        bucket.push(*value);
+        bucket_index_per_elm.push(bucket_index);
+    }
+
+    let bucket_lengths: Vec<usize> = unique_value_buckets.iter().map(Vec::len).collect();
+    let n_unique_values: usize = bucket_lengths.iter().sum();
+    let bucket_offsets: Vec<usize> = bucket_lengths
+        .iter()
+        .scan(0, |offset, length| {
+            let bucket_offset = *offset;
+            *offset += length;
+            Some(bucket_offset)
+        })
+        .collect();
+    let repeating_value_pointers: Vec<Felt> = repeating_value_locations
+        .iter()
+        .map(|(bucket_index, index_in_bucket)| {
+            Felt::from(bucket_offsets[*bucket_index] + index_in_bucket)
+        })
+        .collect();
+
+    let header: Vec<Felt> = [COMPRESSION_VERSION.into(), data.len()]
+        .into_iter()
+        .chain(bucket_lengths.iter().copied())
+        .chain([repeating_value_locations.len()])
+        .map(Felt::from)
+        .collect();
+    let mut compressed = pack_in_felts(&header, &BigUint::from(1_u64 << HEADER_ELM_N_BITS));
+    for (bucket, n_bits) in unique_value_buckets.iter().zip(N_BITS_PER_BUCKET) {
+        compressed.extend(pack_in_felts(bucket, &(BigUint::from(1_u8) << n_bits)));
+    }
+    compressed.extend(pack_in_felts(&repeating_value_pointers, &BigUint::from(n_unique_values)));
+    let bucket_index_per_elm: Vec<Felt> =
+        bucket_index_per_elm.into_iter().map(Felt::from).collect();
+    compressed.extend(pack_in_felts(&bucket_index_per_elm, &BigUint::from(TOTAL_N_BUCKETS)));
+    compressed
+}
+
+/// Reverses `stateless_compress`.
+pub fn stateless_decompress(compressed: &[Felt]) -> Vec<Felt> {
+    let mut compressed = compressed.iter();
+    let header_elm_bound = BigUint::from(1_u64 << HEADER_ELM_N_BITS);
+    let header: Vec<usize> = unpack_felts(&mut compressed, HEADER_LEN, &header_elm_bound)
+        .iter()
+        .map(|elm| usize::try_from(elm.to_biguint()).expect("Header element should fit in usize."))
+        .collect();
+    let data_len = header[1];
+    let bucket_lengths = &header[2..2 + N_UNIQUE_VALUE_BUCKETS];
+    let n_repeating_values = header[HEADER_LEN - 1];
+
+    let mut buckets: Vec<Vec<Felt>> = bucket_lengths
+        .iter()
+        .zip(N_BITS_PER_BUCKET)
+        .map(|(length, n_bits)| {
+            unpack_felts(&mut compressed, *length, &(BigUint::from(1_u8) << n_bits))
+        })
+        .collect();
+    let unique_values: Vec<Felt> = buckets.iter().flatten().copied().collect();
+    let repeating_value_pointers =
+        unpack_felts(&mut compressed, n_repeating_values, &BigUint::from(unique_values.len()));
+    buckets.push(
+        repeating_value_pointers
+            .iter()
+            .map(|pointer| {
+                let pointer = usize::try_from(pointer.to_biguint()).expect("Invalid pointer.");
+                unique_values[pointer]
+            })
+            .collect(),
+    );
+    let bucket_index_per_elm =
+        unpack_felts(&mut compressed, data_len, &BigUint::from(TOTAL_N_BUCKETS));
+
+    let mut buckets: Vec<_> = buckets.into_iter().map(Vec::into_iter).collect();
+    bucket_index_per_elm
+        .iter()
+        .map(|bucket_index| {
+            let bucket_index =
+                usize::try_from(bucket_index.to_biguint()).expect("Invalid bucket index.");
+            buckets[bucket_index].next().expect("Bucket should not be exhausted.")
+        })
+        .collect()
+}
+
+// The number of elements smaller than `elm_bound` packed into a single felt.
+fn n_elms_per_felt(elm_bound: &BigUint) -> usize {
+    if *elm_bound <= BigUint::from(1_u8) {
+        return MAX_N_BITS;
+    }
+    let n_bits = usize::try_from((elm_bound - 1_u8).bits()).expect("Bit length should fit.");
+    (MAX_N_BITS / n_bits).max(1)
+}
+
+// Packs each chunk of elements into a felt, as sum(elm_i * elm_bound^i).
+fn pack_in_felts(elms: &[Felt], elm_bound: &BigUint) -> Vec<Felt> {
+    elms.chunks(n_elms_per_felt(elm_bound))
+        .map(|chunk| {
+            let packed = chunk
+                .iter()
+                .rev()
+                .fold(BigUint::default(), |packed, elm| packed * elm_bound + elm.to_biguint());
+            Felt::from(&packed)
+        })
+        .collect()
+}
+
+// Unpacks `n_elms` elements packed by `pack_in_felts`, advancing the given iterator.
+fn unpack_felts<'a>(
+    packed: &mut impl Iterator<Item = &'a Felt>,
+    n_elms: usize,
+    elm_bound: &BigUint,
+) -> Vec<Felt> {
+    let n_elms_per_felt = n_elms_per_felt(elm_bound);
+    let mut elms = Vec::with_capacity(n_elms);
+    while elms.len() < n_elms {
+        let mut packed_felt = packed.next().expect("Compressed data is too short.").to_biguint();
+        for _ in 0..n_elms_per_felt.min(n_elms - elms.len()) {
+            elms.push(Felt::from(&(&packed_felt % elm_bound)));
+            packed_felt /= elm_bound;
+        }
+    }
+    elms
+}
+
+/// Encodes the given data as EIP-4844 blobs: each chunk of `FIELD_ELEMENTS_PER_BLOB` felts is
+/// taken as the coefficients of a polynomial over the BLS field, which is evaluated on the roots
+/// of unity in bit-reversal order.
+pub fn encode_blobs(data: &[Felt]) -> Vec<Vec<u8>> {
+    data.chunks(FIELD_ELEMENTS_PER_BLOB)
+        .map(|chunk| {
+            let mut coefficients: Vec<BigUint> = chunk.iter().map(Felt::to_biguint).collect();
+            coefficients.resize(FIELD_ELEMENTS_PER_BLOB, BigUint::default());
+            let evaluations = fft(&coefficients, &BLS_ROOT_OF_UNITY);
+
+            let n_bits = FIELD_ELEMENTS_PER_BLOB.trailing_zeros();
+            let mut blob = Vec::with_capacity(FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT);
+            for i in 0..FIELD_ELEMENTS_PER_BLOB {
+                let bytes = evaluations[i.reverse_bits() >> (usize::BITS - n_bits)].to_bytes_be();
+                blob.resize(blob.len() + BYTES_PER_FIELD_ELEMENT - bytes.len(), 0);
+                blob.extend(bytes);
+            }
+            blob
+        })
+        .collect()
+}
+
+// Evaluates the polynomial of the given coefficients on the powers of the given root of unity,
+// whose order is the number of coefficients.
+fn fft(coefficients: &[BigUint], root_of_unity: &BigUint) -> Vec<BigUint> {
+    if coefficients.len() == 1 {
+        return coefficients.to_vec();
+    }
+    let bls_prime = &*BLS_PRIME;
+    let squared_root = root_of_unity * root_of_unity % bls_prime;
+    let (even, odd): (Vec<_>, Vec<_>) =
+        coefficients.chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).unzip();
+    let even = fft(&even, &squared_root);
+    let odd = fft(&odd, &squared_root);
+
+    let half = coefficients.len() / 2;
+    let mut evaluations = vec![BigUint::default(); coefficients.len()];
+    let mut x = BigUint::from(1_u8);
+    for (i, (even_evaluation, odd_evaluation)) in even.iter().zip(&odd).enumerate() {
+        let odd_term = &x * odd_evaluation % bls_prime;
+        evaluations[i] = (even_evaluation + &odd_term) % bls_prime;
+        evaluations[i + half] = (even_evaluation + bls_prime - odd_term) % bls_prime;
+        x = x * root_of_unity % bls_prime;
+    }
+    evaluations
+}
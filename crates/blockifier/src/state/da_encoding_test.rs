@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use indexmap::indexmap;
+use pretty_assertions::assert_eq;
+use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::core::Nonce;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::{class_hash, compiled_class_hash, contract_address, felt, nonce, storage_key};
+use starknet_types_core::felt::Felt;
+
+use super::{
+    compressed_state_diff_length,
+    encode_blobs,
+    encode_state_diff,
+    stateless_compress,
+    stateless_decompress,
+    FIELD_ELEMENTS_PER_BLOB,
+};
+use crate::state::cached_state::StateMaps;
+use crate::test_utils::dict_state_reader::DictStateReader;
+
+#[test]
+fn test_encode_state_diff() {
+    let (contract_0, contract_1) = (contract_address!("0x10"), contract_address!("0x20"));
+    let (class_hash, compiled_class_hash) = (class_hash!("0x99"), compiled_class_hash!(0x77_u8));
+    let state_diff = StateMaps {
+        storage: HashMap::from([
+            ((contract_0, storage_key!("0x2")), felt!("0x7")),
+            ((contract_0, storage_key!("0x1")), felt!("0x5")),
+        ]),
+        nonces: HashMap::from([(contract_1, nonce!(1_u8))]),
+        class_hashes: HashMap::from([(contract_1, class_hash)]),
+        compiled_class_hashes: HashMap::from([(class_hash, compiled_class_hash)]),
+        ..Default::default()
+    };
+    let state = DictStateReader {
+        address_to_nonce: HashMap::from([(contract_0, nonce!(3_u8))]),
+        ..Default::default()
+    };
+
+    let encoding = encode_state_diff(&state_diff, &state, None).unwrap();
+
+    // The nonce of the first contract is published although it was not updated.
+    let expected_serialized_state_diff = vec![
+        felt!("0x2"),
+        *contract_0.0.key(),
+        felt!("0x2") + felt!("0x3") * Felt::TWO.pow(64_u128),
+        felt!("0x1"),
+        felt!("0x5"),
+        felt!("0x2"),
+        felt!("0x7"),
+        *contract_1.0.key(),
+        Felt::TWO.pow(64_u128) + Felt::TWO.pow(128_u128),
+        felt!("0x99"),
+        felt!("0x1"),
+        felt!("0x99"),
+        felt!("0x77"),
+    ];
+    assert_eq!(encoding.serialized_state_diff, expected_serialized_state_diff);
+    assert_eq!(
+        stateless_decompress(&encoding.compressed_state_diff),
+        encoding.serialized_state_diff
+    );
+    assert_eq!(encoding.blobs, encode_blobs(&encoding.compressed_state_diff));
+
+    // The commitment covers the updated nonces only.
+    let expected_commitment = calculate_state_diff_hash(&ThinStateDiff {
+        deployed_contracts: indexmap! { contract_1 => class_hash },
+        storage_diffs: indexmap! {
+            contract_0 => indexmap! {
+                storage_key!("0x1") => felt!("0x5"),
+                storage_key!("0x2") => felt!("0x7"),
+            },
+        },
+        declared_classes: indexmap! { class_hash => compiled_class_hash },
+        deprecated_declared_classes: vec![],
+        nonces: indexmap! { contract_1 => Nonce(Felt::ONE) },
+        replaced_classes: indexmap! {},
+    });
+    assert_eq!(encoding.state_diff_commitment, expected_commitment);
+}
+
+#[test]
+fn test_stateless_compression_round_trip() {
+    // Values of all the buckets, with repetitions.
+    let data: Vec<Felt> = (0..300_u64)
+        .map(|i| match i % 4 {
+            0 => Felt::from(i % 50),
+            1 => Felt::MAX - Felt::from(i % 7),
+            2 => Felt::TWO.pow(100_u128) + Felt::from(i),
+            _ => Felt::from(u64::MAX - i % 3),
+        })
+        .collect();
+
+    let compressed = stateless_compress(&data);
+
+    assert!(compressed.len() < data.len());
+    assert_eq!(stateless_decompress(&compressed), data);
+}
+
+#[test]
+fn test_stateless_compression_of_empty_data() {
+    assert_eq!(stateless_decompress(&stateless_compress(&[])), Vec::<Felt>::new());
+}
+
+#[test]
+fn test_compressed_state_diff_length() {
+    assert_eq!(compressed_state_diff_length(&StateMaps::default()), 0);
+
+    // [1, 0x10, 1, 0x1, 0x5, 0]: a bucket of 4 unique values, a felt of pointers to the repeating
+    // values and a felt of bucket indices, besides the header.
+    let state_diff = StateMaps {
+        storage: HashMap::from([((contract_address!("0x10"), storage_key!("0x1")), felt!("0x5"))]),
+        ..Default::default()
+    };
+    assert_eq!(compressed_state_diff_length(&state_diff), 3);
+}
+
+#[test]
+fn test_encode_blobs() {
+    // The evaluations of a constant polynomial are all the constant.
+    let data = [felt!("0x1234")];
+    let blobs = encode_blobs(&data);
+    assert_eq!(blobs.len(), 1);
+    let mut expected_word = [0_u8; 32];
+    expected_word[30..].copy_from_slice(&[0x12, 0x34]);
+    assert_eq!(blobs[0], expected_word.repeat(FIELD_ELEMENTS_PER_BLOB));
+
+    let data = vec![Felt::ONE; FIELD_ELEMENTS_PER_BLOB + 1];
+    assert_eq!(encode_blobs(&data).len(), 2);
+}
@@ -1,14 +1,20 @@
+use std::mem::size_of;
 use std::sync::{Arc, Mutex, MutexGuard};
 
-use cached::{Cached, SizedCache};
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use lru::LruCache;
 use starknet_api::core::ClassHash;
 use starknet_api::state::SierraContractClass;
+use starknet_types_core::felt::Felt;
 
 use crate::execution::contract_class::RunnableCompiledClass;
 #[cfg(feature = "cairo_native")]
 use crate::execution::native::contract_class::NativeCompiledClassV1;
 
-type ContractLRUCache<T> = SizedCache<ClassHash, T>;
+#[cfg(test)]
+#[path = "global_cache_test.rs"]
+mod test;
+
 pub type LockedClassCache<'a, T> = MutexGuard<'a, ContractLRUCache<T>>;
 #[derive(Debug, Clone)]
 // Thread-safe LRU cache for contract classes (Seirra or compiled Casm/Native), optimized for
@@ -16,12 +22,118 @@ pub type LockedClassCache<'a, T> = MutexGuard<'a, ContractLRUCache<T>>;
 // TODO(Yoni, 1/1/2025): consider defining CachedStateReader.
 pub struct GlobalContractCache<T: Clone>(pub Arc<Mutex<ContractLRUCache<T>>>);
 
+/// A cached class whose size in memory bounds the size of the cache.
+pub trait CachedClassSize {
+    /// The estimated size of the class in memory, in bytes.
+    fn size_in_bytes(&self) -> usize;
+}
+
+/// Hit, miss and eviction counts of a contract cache, since its creation, and its current size.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ContractCacheStats {
+    pub n_hits: usize,
+    pub n_misses: usize,
+    /// The number of classes evicted to bound the size of the cache.
+    pub n_evictions: usize,
+    /// The number of classes explicitly dropped from the cache; e.g., on their declaration.
+    pub n_invalidations: usize,
+    pub n_classes: usize,
+    pub size_in_bytes: usize,
+}
+
+/// An LRU cache of contract classes, bounded by both the number of classes and their estimated
+/// size in bytes; the least recently used classes are evicted once either bound is exceeded.
+#[derive(Debug)]
+pub struct ContractLRUCache<T> {
+    classes: LruCache<ClassHash, (T, usize)>,
+    max_n_classes: usize,
+    max_size_in_bytes: usize,
+    size_in_bytes: usize,
+    stats: ContractCacheStats,
+}
+
+impl<T: CachedClassSize> ContractLRUCache<T> {
+    pub fn new(max_n_classes: usize, max_size_in_bytes: usize) -> Self {
+        Self {
+            classes: LruCache::unbounded(),
+            max_n_classes,
+            max_size_in_bytes,
+            size_in_bytes: 0,
+            stats: ContractCacheStats::default(),
+        }
+    }
+
+    /// Returns the class of the given hash, if cached, and marks it as the most recently used.
+    pub fn get(&mut self, class_hash: &ClassHash) -> Option<&T> {
+        match self.classes.get(class_hash) {
+            Some((class, _)) => {
+                self.stats.n_hits += 1;
+                Some(class)
+            }
+            None => {
+                self.stats.n_misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches the class, evicting the least recently used classes as needed. A class larger than
+    /// the cache is not kept.
+    pub fn set(&mut self, class_hash: ClassHash, class: T) {
+        let class_size = class.size_in_bytes();
+        if let Some((_, (_, replaced_class_size))) =
+            self.classes.push(class_hash, (class, class_size))
+        {
+            self.size_in_bytes -= replaced_class_size;
+        }
+        self.size_in_bytes += class_size;
+
+        while self.classes.len() > self.max_n_classes || self.size_in_bytes > self.max_size_in_bytes
+        {
+            let Some((_, (_, evicted_class_size))) = self.classes.pop_lru() else {
+                break;
+            };
+            self.size_in_bytes -= evicted_class_size;
+            self.stats.n_evictions += 1;
+        }
+    }
+
+    /// Drops the class of the given hash from the cache, if cached.
+    pub fn invalidate(&mut self, class_hash: &ClassHash) {
+        if let Some((_, class_size)) = self.classes.pop(class_hash) {
+            self.size_in_bytes -= class_size;
+            self.stats.n_invalidations += 1;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.classes.clear();
+        self.size_in_bytes = 0;
+    }
+
+    pub fn stats(&self) -> ContractCacheStats {
+        ContractCacheStats {
+            n_classes: self.classes.len(),
+            size_in_bytes: self.size_in_bytes,
+            ..self.stats
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum CachedCasm {
     WithoutSierra(RunnableCompiledClass),
     WithSierra(RunnableCompiledClass, Arc<SierraContractClass>),
 }
 
+impl CachedCasm {
+    pub fn to_runnable(self) -> RunnableCompiledClass {
+        match self {
+            CachedCasm::WithoutSierra(casm) | CachedCasm::WithSierra(casm, _) => casm,
+        }
+    }
+}
+
 #[cfg(feature = "cairo_native")]
 #[derive(Debug, Clone)]
 pub enum CachedCairoNative {
@@ -29,9 +141,45 @@ pub enum CachedCairoNative {
     CompilationFailed,
 }
 
+/// The estimated size of a runnable class: its bytecode, loaded to memory cells.
+fn runnable_class_size(class: &RunnableCompiledClass) -> usize {
+    let bytecode_length = match class {
+        RunnableCompiledClass::V0(class) => class.bytecode_length(),
+        RunnableCompiledClass::V1(class) => class.bytecode_length(),
+        #[cfg(feature = "cairo_native")]
+        RunnableCompiledClass::V1Native(class) => class.casm().bytecode_length(),
+    };
+    bytecode_length * size_of::<MaybeRelocatable>()
+}
+
+impl CachedClassSize for CachedCasm {
+    fn size_in_bytes(&self) -> usize {
+        match self {
+            CachedCasm::WithoutSierra(casm) => runnable_class_size(casm),
+            CachedCasm::WithSierra(casm, sierra) => {
+                runnable_class_size(casm) + sierra.sierra_program.len() * size_of::<Felt>()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "cairo_native")]
+impl CachedClassSize for CachedCairoNative {
+    // The native code itself is mapped from a shared library, outside the estimate.
+    fn size_in_bytes(&self) -> usize {
+        match self {
+            CachedCairoNative::Compiled(native_compiled_class) => {
+                native_compiled_class.casm().bytecode_length() * size_of::<MaybeRelocatable>()
+            }
+            CachedCairoNative::CompilationFailed => 0,
+        }
+    }
+}
+
 pub const GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST: usize = 400;
+pub const GLOBAL_CONTRACT_CACHE_SIZE_IN_BYTES_FOR_TEST: usize = 1 << 30;
 
-impl<T: Clone> GlobalContractCache<T> {
+impl<T: Clone + CachedClassSize> GlobalContractCache<T> {
     /// Locks the cache for atomic access. Although conceptually shared, writing to this cache is
     /// only possible for one writer at a time.
     pub fn lock(&self) -> LockedClassCache<'_, T> {
@@ -39,19 +187,27 @@ impl<T: Clone> GlobalContractCache<T> {
     }
 
     pub fn get(&self, class_hash: &ClassHash) -> Option<T> {
-        self.lock().cache_get(class_hash).cloned()
+        self.lock().get(class_hash).cloned()
     }
 
     pub fn set(&self, class_hash: ClassHash, contract_class: T) {
-        self.lock().cache_set(class_hash, contract_class);
+        self.lock().set(class_hash, contract_class);
+    }
+
+    pub fn invalidate(&self, class_hash: &ClassHash) {
+        self.lock().invalidate(class_hash);
+    }
+
+    pub fn stats(&self) -> ContractCacheStats {
+        self.lock().stats()
     }
 
     pub fn clear(&mut self) {
-        self.lock().cache_clear();
+        self.lock().clear();
     }
 
-    pub fn new(cache_size: usize) -> Self {
-        Self(Arc::new(Mutex::new(ContractLRUCache::<T>::with_size(cache_size))))
+    pub fn new(cache_size: usize, cache_size_in_bytes: usize) -> Self {
+        Self(Arc::new(Mutex::new(ContractLRUCache::new(cache_size, cache_size_in_bytes))))
     }
 }
 
@@ -81,11 +237,18 @@ impl ContractCaches {
         self.native_cache.set(class_hash, contract_executor);
     }
 
-    pub fn new(cache_size: usize) -> Self {
+    /// Drops the classes of the given hash from all caches.
+    pub fn invalidate(&self, class_hash: &ClassHash) {
+        self.casm_cache.invalidate(class_hash);
+        #[cfg(feature = "cairo_native")]
+        self.native_cache.invalidate(class_hash);
+    }
+
+    pub fn new(cache_size: usize, cache_size_in_bytes: usize) -> Self {
         Self {
-            casm_cache: GlobalContractCache::new(cache_size),
+            casm_cache: GlobalContractCache::new(cache_size, cache_size_in_bytes),
             #[cfg(feature = "cairo_native")]
-            native_cache: GlobalContractCache::new(cache_size),
+            native_cache: GlobalContractCache::new(cache_size, cache_size_in_bytes),
         }
     }
 
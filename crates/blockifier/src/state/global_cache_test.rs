@@ -0,0 +1,76 @@
+use rstest::rstest;
+use starknet_api::class_hash;
+
+use crate::state::global_cache::{CachedClassSize, ContractCacheStats, ContractLRUCache};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct TestClass(usize);
+
+impl CachedClassSize for TestClass {
+    fn size_in_bytes(&self) -> usize {
+        self.0
+    }
+}
+
+#[test]
+fn test_eviction_by_size() {
+    let mut cache = ContractLRUCache::new(10, 100);
+    cache.set(class_hash!("0x1"), TestClass(40));
+    cache.set(class_hash!("0x2"), TestClass(40));
+    // Marks the first class as the most recently used.
+    assert_eq!(cache.get(&class_hash!("0x1")), Some(&TestClass(40)));
+
+    cache.set(class_hash!("0x3"), TestClass(40));
+    assert_eq!(cache.get(&class_hash!("0x2")), None);
+    assert_eq!(cache.get(&class_hash!("0x1")), Some(&TestClass(40)));
+    assert_eq!(cache.get(&class_hash!("0x3")), Some(&TestClass(40)));
+    assert_eq!(
+        cache.stats(),
+        ContractCacheStats {
+            n_hits: 3,
+            n_misses: 1,
+            n_evictions: 1,
+            n_invalidations: 0,
+            n_classes: 2,
+            size_in_bytes: 80,
+        }
+    );
+}
+
+#[rstest]
+#[case::by_number_of_classes(2, 100)]
+#[case::by_size(10, 50)]
+fn test_eviction_bounds(#[case] max_n_classes: usize, #[case] max_size_in_bytes: usize) {
+    let mut cache = ContractLRUCache::new(max_n_classes, max_size_in_bytes);
+    for class_hash in [class_hash!("0x1"), class_hash!("0x2"), class_hash!("0x3")] {
+        cache.set(class_hash, TestClass(20));
+    }
+    let stats = cache.stats();
+    assert_eq!((stats.n_classes, stats.size_in_bytes, stats.n_evictions), (2, 40, 1));
+}
+
+#[test]
+fn test_class_larger_than_cache_is_not_kept() {
+    let mut cache = ContractLRUCache::new(10, 100);
+    cache.set(class_hash!("0x1"), TestClass(40));
+    cache.set(class_hash!("0x2"), TestClass(200));
+    assert_eq!(cache.get(&class_hash!("0x2")), None);
+    // Evicting the older classes did not make room for it.
+    assert_eq!(cache.get(&class_hash!("0x1")), None);
+    assert_eq!(cache.stats().size_in_bytes, 0);
+}
+
+#[test]
+fn test_replace_and_invalidate() {
+    let mut cache = ContractLRUCache::new(10, 100);
+    cache.set(class_hash!("0x1"), TestClass(40));
+    cache.set(class_hash!("0x1"), TestClass(30));
+    assert_eq!(cache.stats().size_in_bytes, 30);
+
+    cache.invalidate(&class_hash!("0x1"));
+    // Invalidating a class that is not cached is a no-op.
+    cache.invalidate(&class_hash!("0x2"));
+    assert_eq!(cache.get(&class_hash!("0x1")), None);
+    let stats = cache.stats();
+    assert_eq!((stats.n_classes, stats.size_in_bytes, stats.n_invalidations), (0, 0, 1));
+}
@@ -1,6 +1,8 @@
 pub mod cairo_compile;
 pub mod contracts;
 pub mod dict_state_reader;
+pub mod executor_fuzzing;
+pub mod feature_contracts_fixtures;
 pub mod initial_test_state;
 pub mod l1_handler;
 pub mod prices;
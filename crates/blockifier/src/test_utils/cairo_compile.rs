@@ -6,13 +6,22 @@ use std::{env, fs};
 use cached::proc_macro::cached;
 use infra_utils::compile_time_cargo_manifest_dir;
 use serde::{Deserialize, Serialize};
+use starknet_api::hash::keccak256;
 use tempfile::NamedTempFile;
 
 use crate::test_utils::contracts::TagAndToolchain;
 
+#[cfg(test)]
+#[path = "cairo_compile_test.rs"]
+mod cairo_compile_test;
+
 const CAIRO0_PIP_REQUIREMENTS_FILE: &str = "tests/requirements.txt";
 const CAIRO1_REPO_RELATIVE_PATH_OVERRIDE_ENV_VAR: &str = "CAIRO1_REPO_RELATIVE_PATH";
 const DEFAULT_CAIRO1_REPO_RELATIVE_PATH: &str = "../../../cairo";
+const CAIRO1_COMPILATION_CACHE_DIR_OVERRIDE_ENV_VAR: &str = "CAIRO1_COMPILATION_CACHE_DIR";
+const DEFAULT_CAIRO1_COMPILATION_CACHE_DIR_NAME: &str = "blockifier_cairo1_compilation_cache";
+const CACHED_SIERRA_FILE_NAME: &str = "contract.sierra.json";
+const CACHED_CASM_FILE_NAME: &str = "contract.casm.json";
 
 /// Objects for simple deserialization of Cargo.toml to fetch the Cairo1 compiler version.
 /// The compiler itself isn't actually a dependency, so we compile by using the version of the
@@ -96,13 +105,12 @@ fn run_and_verify_output(command: &mut Command) -> Output {
     output
 }
 
-/// Compiles a Cairo0 program using the deprecated compiler.
+/// Compiles a Cairo0 program using the deprecated compiler; see `prepare_cairo0_compiler_deps`.
 pub fn cairo0_compile(
     path: String,
     extra_arg: Option<String>,
     debug_info: bool,
 ) -> CompilationArtifacts {
-    verify_cairo0_compiler_deps();
     let mut command = Command::new("starknet-compile-deprecated");
     command.arg(&path);
     if let Some(extra_arg) = extra_arg {
@@ -118,10 +126,46 @@ pub fn cairo0_compile(
 }
 
 /// Compiles a Cairo1 program using the compiler version set in the Cargo.toml.
+/// The artifacts are cached by the source of the program and the compiler used, so recompiling
+/// an unchanged program with the same compiler does not invoke the compiler.
 pub fn cairo1_compile(
     path: String,
     git_tag_override: Option<String>,
     cargo_nightly_arg: Option<String>,
+) -> CompilationArtifacts {
+    let (tag, cairo_repo_path) = get_tag_and_repo_file_path(git_tag_override.clone());
+    let compiler_version = checked_out_cairo1_compiler_commit(&cairo_repo_path);
+    let cache_key = cairo1_compilation_cache_key(
+        &fs::read(&path).unwrap(),
+        &cairo_repo_path,
+        &tag,
+        &compiler_version,
+        &cargo_nightly_arg.clone().unwrap_or_default(),
+    );
+
+    get_or_compile_cairo1_artifacts(&cairo1_compilation_cache_dir().join(cache_key), || {
+        cairo1_compile_uncached(path, git_tag_override, cargo_nightly_arg)
+    })
+}
+
+/// Returns the cached artifacts in the given cache entry; on a miss, compiles them and caches them.
+fn get_or_compile_cairo1_artifacts(
+    cache_entry_path: &Path,
+    compile: impl FnOnce() -> CompilationArtifacts,
+) -> CompilationArtifacts {
+    if let Some(artifacts) = read_cached_cairo1_artifacts(cache_entry_path) {
+        return artifacts;
+    }
+
+    let artifacts = compile();
+    write_cached_cairo1_artifacts(cache_entry_path, &artifacts);
+    artifacts
+}
+
+fn cairo1_compile_uncached(
+    path: String,
+    git_tag_override: Option<String>,
+    cargo_nightly_arg: Option<String>,
 ) -> CompilationArtifacts {
     let mut base_compile_args = vec![];
 
@@ -146,6 +190,65 @@ pub fn cairo1_compile(
     CompilationArtifacts::Cairo1 { casm: casm_output.stdout, sierra: sierra_output }
 }
 
+/// Returns the directory of the cached artifacts. It can be overridden by the environment variable
+/// (otherwise, it is under the system's temporary directory).
+fn cairo1_compilation_cache_dir() -> PathBuf {
+    env::var(CAIRO1_COMPILATION_CACHE_DIR_OVERRIDE_ENV_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| env::temp_dir().join(DEFAULT_CAIRO1_COMPILATION_CACHE_DIR_NAME))
+}
+
+/// Returns the commit checked out in the local Cairo1 compiler repository, which is the version of
+/// the compiler that is actually run (the expected tag is only verified to exist).
+fn checked_out_cairo1_compiler_commit(cairo_repo_path: &Path) -> String {
+    let output = run_and_verify_output(Command::new("git").args([
+        "-C",
+        cairo_repo_path.to_str().unwrap(),
+        "rev-parse",
+        "HEAD",
+    ]));
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Returns the name of the cache entry of a program: the hash of its source and of everything that
+/// determines the compiler run on it - the compiler repository, tag, version and toolchain.
+fn cairo1_compilation_cache_key(
+    source: &[u8],
+    cairo_repo_path: &Path,
+    tag: &str,
+    compiler_version: &str,
+    cargo_nightly_arg: &str,
+) -> String {
+    let cairo_repo_path = cairo_repo_path.to_string_lossy();
+    let mut cache_key_preimage = source.to_vec();
+    for key_part in [cairo_repo_path.as_ref(), tag, compiler_version, cargo_nightly_arg] {
+        cache_key_preimage.push(0);
+        cache_key_preimage.extend(key_part.as_bytes());
+    }
+    keccak256(&cache_key_preimage).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn read_cached_cairo1_artifacts(cache_entry_path: &Path) -> Option<CompilationArtifacts> {
+    let sierra = fs::read(cache_entry_path.join(CACHED_SIERRA_FILE_NAME)).ok()?;
+    let casm = fs::read(cache_entry_path.join(CACHED_CASM_FILE_NAME)).ok()?;
+    Some(CompilationArtifacts::Cairo1 { casm, sierra })
+}
+
+/// Caches the artifacts of a compilation; each file is moved into place atomically, so concurrent
+/// compilations of the same program never observe a partially written file.
+fn write_cached_cairo1_artifacts(cache_entry_path: &Path, artifacts: &CompilationArtifacts) {
+    let CompilationArtifacts::Cairo1 { casm, sierra } = artifacts else {
+        panic!("Expected Cairo1 compilation artifacts.");
+    };
+    fs::create_dir_all(cache_entry_path).unwrap();
+    let files = [(CACHED_SIERRA_FILE_NAME, sierra), (CACHED_CASM_FILE_NAME, casm)];
+    for (file_name, contents) in files {
+        let mut temp_file = NamedTempFile::new_in(cache_entry_path).unwrap();
+        temp_file.write_all(contents).unwrap();
+        temp_file.persist(cache_entry_path.join(file_name)).unwrap();
+    }
+}
+
 /// Compile Cairo1 Contract into their Sierra version using the compiler version set in the
 /// Cargo.toml
 pub fn starknet_compile(
@@ -185,8 +288,9 @@ pub fn starknet_compile(
     sierra_output.stdout
 }
 
-/// Verifies that the required dependencies are available before compiling; panics if unavailable.
-fn verify_cairo0_compiler_deps() {
+/// Verifies that the required dependencies are available before compiling Cairo0 programs; panics
+/// if unavailable.
+pub fn prepare_cairo0_compiler_deps() {
     // Python compiler. Verify correct version.
     let cairo_lang_version_output =
         Command::new("sh").arg("-c").arg("pip freeze | grep cairo-lang").output().unwrap().stdout;
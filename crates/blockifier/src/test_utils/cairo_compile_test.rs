@@ -0,0 +1,72 @@
+use std::cell::Cell;
+use std::path::Path;
+
+use tempfile::tempdir;
+
+use crate::test_utils::cairo_compile::{
+    cairo1_compilation_cache_key,
+    get_or_compile_cairo1_artifacts,
+    CompilationArtifacts,
+};
+
+fn cairo1_artifacts(casm: &[u8], sierra: &[u8]) -> CompilationArtifacts {
+    CompilationArtifacts::Cairo1 { casm: casm.to_vec(), sierra: sierra.to_vec() }
+}
+
+fn assert_cairo1_artifacts(
+    artifacts: CompilationArtifacts,
+    expected_casm: &[u8],
+    expected_sierra: &[u8],
+) {
+    let CompilationArtifacts::Cairo1 { casm, sierra } = artifacts else {
+        panic!("Expected Cairo1 compilation artifacts.");
+    };
+    assert_eq!(casm, expected_casm);
+    assert_eq!(sierra, expected_sierra);
+}
+
+#[test]
+fn compilation_cache_hit_and_miss() {
+    let cache_dir = tempdir().unwrap();
+    let n_compilations = Cell::new(0);
+    let compile = |casm: &'static [u8], sierra: &'static [u8]| {
+        let n_compilations = &n_compilations;
+        move || {
+            n_compilations.set(n_compilations.get() + 1);
+            cairo1_artifacts(casm, sierra)
+        }
+    };
+
+    // Miss: the program is compiled and cached.
+    let entry_path = cache_dir.path().join("entry");
+    let artifacts = get_or_compile_cairo1_artifacts(&entry_path, compile(b"casm", b"sierra"));
+    assert_cairo1_artifacts(artifacts, b"casm", b"sierra");
+    assert_eq!(n_compilations.get(), 1);
+
+    // Hit: the cached artifacts are returned without compiling.
+    let artifacts = get_or_compile_cairo1_artifacts(&entry_path, compile(b"other", b"other"));
+    assert_cairo1_artifacts(artifacts, b"casm", b"sierra");
+    assert_eq!(n_compilations.get(), 1);
+
+    // Miss on another entry.
+    let other_entry_path = cache_dir.path().join("other_entry");
+    let artifacts =
+        get_or_compile_cairo1_artifacts(&other_entry_path, compile(b"casm2", b"sierra2"));
+    assert_cairo1_artifacts(artifacts, b"casm2", b"sierra2");
+    assert_eq!(n_compilations.get(), 2);
+}
+
+#[test]
+fn compilation_cache_key_depends_on_compiler() {
+    let key = |source: &[u8], repo_path: &str, tag: &str, version: &str, toolchain: &str| {
+        cairo1_compilation_cache_key(source, Path::new(repo_path), tag, version, toolchain)
+    };
+    let base_key = key(b"source", "../../../cairo", "v2.9.0", "abc", "");
+
+    assert_eq!(key(b"source", "../../../cairo", "v2.9.0", "abc", ""), base_key);
+    assert_ne!(key(b"other source", "../../../cairo", "v2.9.0", "abc", ""), base_key);
+    assert_ne!(key(b"source", "../../../other_cairo", "v2.9.0", "abc", ""), base_key);
+    assert_ne!(key(b"source", "../../../cairo", "v2.8.0", "abc", ""), base_key);
+    assert_ne!(key(b"source", "../../../cairo", "v2.9.0", "def", ""), base_key);
+    assert_ne!(key(b"source", "../../../cairo", "v2.9.0", "abc", "2024-10-01"), base_key);
+}
@@ -62,6 +62,7 @@ const TEST_CONTRACT_BASE: u32 = 7 * CLASS_HASH_BASE;
 const ERC20_CONTRACT_BASE: u32 = 8 * CLASS_HASH_BASE;
 const CAIRO_STEPS_TEST_CONTRACT_BASE: u32 = 9 * CLASS_HASH_BASE;
 const SIERRA_EXECUTION_INFO_V1_CONTRACT_BASE: u32 = 10 * CLASS_HASH_BASE;
+const PAYMASTER_BASE: u32 = 11 * CLASS_HASH_BASE;
 
 // Contract names.
 const ACCOUNT_LONG_VALIDATE_NAME: &str = "account_with_long_validate";
@@ -73,6 +74,7 @@ const SECURITY_TEST_CONTRACT_NAME: &str = "security_tests_contract";
 const TEST_CONTRACT_NAME: &str = "test_contract";
 const CAIRO_STEPS_TEST_CONTRACT_NAME: &str = "cairo_steps_test_contract";
 const EXECUTION_INFO_V1_CONTRACT_NAME: &str = "test_contract_execution_info_v1";
+const PAYMASTER_NAME: &str = "paymaster";
 
 // ERC20 contract is in a unique location.
 const ERC20_CAIRO0_CONTRACT_SOURCE_PATH: &str =
@@ -108,6 +110,7 @@ pub enum FeatureContract {
     TestContract(CairoVersion),
     CairoStepsTestContract,
     SierraExecutionInfoV1Contract(RunnableCairo1),
+    Paymaster,
 }
 
 impl FeatureContract {
@@ -119,7 +122,7 @@ impl FeatureContract {
             | Self::FaultyAccount(version)
             | Self::TestContract(version)
             | Self::ERC20(version) => *version,
-            Self::SecurityTests => CairoVersion::Cairo0,
+            Self::SecurityTests | Self::Paymaster => CairoVersion::Cairo0,
             Self::LegacyTestContract | Self::CairoStepsTestContract => {
                 CairoVersion::Cairo1(RunnableCairo1::Casm)
             }
@@ -141,7 +144,10 @@ impl FeatureContract {
                 CairoVersion::Cairo0 => panic!("SierraExecutionInfoV1Contract must be Cairo1"),
                 CairoVersion::Cairo1(runnable) => *rv = runnable,
             },
-            Self::SecurityTests | Self::CairoStepsTestContract | Self::LegacyTestContract => {
+            Self::SecurityTests
+            | Self::CairoStepsTestContract
+            | Self::LegacyTestContract
+            | Self::Paymaster => {
                 panic!("{self:?} contract has no configurable version.")
             }
         }
@@ -252,6 +258,7 @@ impl FeatureContract {
                 Self::TestContract(_) => TEST_CONTRACT_BASE,
                 Self::CairoStepsTestContract => CAIRO_STEPS_TEST_CONTRACT_BASE,
                 Self::SierraExecutionInfoV1Contract(_) => SIERRA_EXECUTION_INFO_V1_CONTRACT_BASE,
+                Self::Paymaster => PAYMASTER_BASE,
             }
     }
 
@@ -266,6 +273,7 @@ impl FeatureContract {
             Self::TestContract(_) => TEST_CONTRACT_NAME,
             Self::CairoStepsTestContract => CAIRO_STEPS_TEST_CONTRACT_NAME,
             Self::SierraExecutionInfoV1Contract(_) => EXECUTION_INFO_V1_CONTRACT_NAME,
+            Self::Paymaster => PAYMASTER_NAME,
             Self::ERC20(_) => unreachable!(),
         }
     }
@@ -359,7 +367,8 @@ impl FeatureContract {
                     | FeatureContract::TestContract(_)
                     | FeatureContract::LegacyTestContract
                     | FeatureContract::CairoStepsTestContract
-                    | FeatureContract::SierraExecutionInfoV1Contract(_) => None,
+                    | FeatureContract::SierraExecutionInfoV1Contract(_)
+                    | FeatureContract::Paymaster => None,
                     FeatureContract::ERC20(_) => unreachable!(),
                 };
                 cairo0_compile(self.get_source_path(), extra_arg, false)
@@ -469,9 +478,10 @@ impl FeatureContract {
                 }
             }
 
-            Self::LegacyTestContract | Self::CairoStepsTestContract | Self::SecurityTests => {
-                vec![*self]
-            }
+            Self::LegacyTestContract
+            | Self::CairoStepsTestContract
+            | Self::SecurityTests
+            | Self::Paymaster => vec![*self],
         }
     }
 
@@ -0,0 +1,216 @@
+use starknet_api::block::GasPrice;
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::execution_resources::GasAmount;
+use starknet_api::test_utils::invoke::executable_invoke_tx;
+use starknet_api::test_utils::{
+    DEFAULT_STRK_L1_DATA_GAS_PRICE,
+    DEFAULT_STRK_L1_GAS_PRICE,
+    DEFAULT_STRK_L2_GAS_PRICE,
+};
+use starknet_api::transaction::fields::{Calldata, Fee, ValidResourceBounds};
+use starknet_api::transaction::TransactionVersion;
+use starknet_api::{felt, invoke_tx_args};
+use starknet_types_core::felt::Felt;
+
+use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::transaction_executor::{TransactionExecutor, BLOCK_STATE_ACCESS_ERR};
+use crate::context::BlockContext;
+use crate::state::cached_state::StateMaps;
+use crate::state::state_api::StateReader;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::dict_state_reader::DictStateReader;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, RunnableCairo1, BALANCE};
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::test_utils::create_all_resource_bounds;
+use crate::transaction::transaction_execution::Transaction;
+use crate::utils::subtract_mappings;
+
+/// The call an invoke transaction makes to the test contract.
+#[derive(Clone, Debug)]
+pub enum FuzzedCall {
+    StorageWrite { key: u8, value: u64 },
+    ReturnResult(u64),
+    // Writes to storage, emits an event and sends a message, then panics.
+    WriteAndRevert,
+    Fail,
+    UnknownEntryPoint(Vec<u64>),
+}
+
+/// The fee fields of an invoke transaction, by version.
+#[derive(Clone, Debug)]
+pub enum FuzzedFeeFields {
+    V1 { max_fee: Fee },
+    // The gas amounts, and a factor (in percents) of the default gas prices.
+    V3 { l1_gas: u64, l2_gas: u64, l1_data_gas: u64, price_percentage: u128 },
+}
+
+/// An invoke transaction of the fuzzed account, generated by the property tests of the transaction
+/// executor and by its fuzz target.
+#[derive(Clone, Debug)]
+pub struct FuzzedInvoke {
+    pub call: FuzzedCall,
+    pub fee_fields: FuzzedFeeFields,
+    // Added to the account nonce; a nonzero offset makes the transaction invalid.
+    pub nonce_offset: u8,
+}
+
+impl FuzzedInvoke {
+    fn calldata(&self, test_contract: FeatureContract) -> Calldata {
+        let contract_address = test_contract.get_instance_address(0);
+        let (entry_point_name, args) = match &self.call {
+            FuzzedCall::StorageWrite { key, value } => {
+                ("test_storage_read_write", vec![Felt::from(*key), Felt::from(*value)])
+            }
+            FuzzedCall::ReturnResult(num) => ("return_result", vec![Felt::from(*num)]),
+            FuzzedCall::WriteAndRevert => {
+                ("test_revert_helper", vec![test_contract.get_class_hash().0])
+            }
+            FuzzedCall::Fail => ("fail", vec![]),
+            FuzzedCall::UnknownEntryPoint(args) => {
+                ("no_such_entry_point", args.iter().copied().map(Felt::from).collect())
+            }
+        };
+        create_calldata(contract_address, entry_point_name, &args)
+    }
+
+    /// The resource bounds of a V3 transaction.
+    fn resource_bounds(&self) -> Option<ValidResourceBounds> {
+        let FuzzedFeeFields::V3 { l1_gas, l2_gas, l1_data_gas, price_percentage } = self.fee_fields
+        else {
+            return None;
+        };
+        let price = |default_price: GasPrice| GasPrice(default_price.0 * price_percentage / 100);
+        Some(create_all_resource_bounds(
+            GasAmount(l1_gas),
+            price(DEFAULT_STRK_L1_GAS_PRICE.into()),
+            GasAmount(l2_gas),
+            price(DEFAULT_STRK_L2_GAS_PRICE.into()),
+            GasAmount(l1_data_gas),
+            price(DEFAULT_STRK_L1_DATA_GAS_PRICE.into()),
+        ))
+    }
+
+    /// The maximal fee the transaction may be charged.
+    fn fee_bound(&self) -> Fee {
+        match self.fee_fields {
+            FuzzedFeeFields::V1 { max_fee } => max_fee,
+            FuzzedFeeFields::V3 { .. } => self
+                .resource_bounds()
+                .expect("V3 transactions have resource bounds.")
+                .max_possible_fee(),
+        }
+    }
+
+    fn to_transaction(
+        &self,
+        sender_address: ContractAddress,
+        nonce: Nonce,
+        test_contract: FeatureContract,
+    ) -> Transaction {
+        let nonce = Nonce(nonce.0 + Felt::from(self.nonce_offset));
+        let calldata = self.calldata(test_contract);
+        let tx = match self.resource_bounds() {
+            Some(resource_bounds) => executable_invoke_tx(invoke_tx_args! {
+                sender_address,
+                calldata,
+                nonce,
+                resource_bounds,
+                version: TransactionVersion::THREE,
+            }),
+            None => executable_invoke_tx(invoke_tx_args! {
+                sender_address,
+                calldata,
+                nonce,
+                max_fee: self.fee_bound(),
+                version: TransactionVersion::ONE,
+            }),
+        };
+        AccountTransaction::new_for_sequencing(tx).into()
+    }
+}
+
+fn account_nonce(
+    tx_executor: &TransactionExecutor<DictStateReader>,
+    account_address: ContractAddress,
+) -> Nonce {
+    tx_executor
+        .block_state
+        .as_ref()
+        .expect(BLOCK_STATE_ACCESS_ERR)
+        .get_nonce_at(account_address)
+        .unwrap()
+}
+
+/// Returns the state changes of the block so far.
+fn block_state_diff(tx_executor: &mut TransactionExecutor<DictStateReader>) -> StateMaps {
+    tx_executor
+        .block_state
+        .as_mut()
+        .expect(BLOCK_STATE_ACCESS_ERR)
+        .to_state_diff()
+        .unwrap()
+        .state_maps
+}
+
+/// Returns the changes between two state diffs of the block.
+fn state_diff_changes(before: &StateMaps, after: &StateMaps) -> StateMaps {
+    StateMaps {
+        nonces: subtract_mappings(&after.nonces, &before.nonces),
+        class_hashes: subtract_mappings(&after.class_hashes, &before.class_hashes),
+        storage: subtract_mappings(&after.storage, &before.storage),
+        compiled_class_hashes: subtract_mappings(
+            &after.compiled_class_hashes,
+            &before.compiled_class_hashes,
+        ),
+        declared_contracts: subtract_mappings(
+            &after.declared_contracts,
+            &before.declared_contracts,
+        ),
+    }
+}
+
+/// Executes the given invoke transactions of a single account in a block, and panics unless:
+/// * The fee of an executed transaction does not exceed its bounds.
+/// * The account nonce increases by one with each executed transaction, and is unchanged by a
+///   rejected one.
+/// * A rejected transaction does not change the state, and a reverted one changes only its sender
+///   nonce and the fee token balances.
+pub fn check_executor_invariants(invokes: &[FuzzedInvoke]) {
+    let account =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let block_context = BlockContext::create_for_account_testing();
+    let state = test_state(&block_context.chain_info, BALANCE, &[(account, 1), (test_contract, 1)]);
+    let sender_address = account.get_instance_address(0);
+    let config = TransactionExecutorConfig::default();
+    let mut tx_executor = TransactionExecutor::new(state, block_context.clone(), config);
+
+    for invoke in invokes {
+        let nonce = account_nonce(&tx_executor, sender_address);
+        let state_diff_before = block_state_diff(&mut tx_executor);
+        let tx = invoke.to_transaction(sender_address, nonce, test_contract);
+
+        let execution_result = tx_executor.execute(&tx);
+
+        let state_diff_after = block_state_diff(&mut tx_executor);
+        let changes = state_diff_changes(&state_diff_before, &state_diff_after);
+        let nonce_after = account_nonce(&tx_executor, sender_address);
+        let Ok(execution_info) = execution_result else {
+            assert_eq!(nonce_after, nonce);
+            assert_eq!(changes, StateMaps::default());
+            continue;
+        };
+
+        assert!(execution_info.receipt.fee <= invoke.fee_bound());
+        assert_eq!(nonce_after, Nonce(nonce.0 + felt!(1_u8)));
+        if execution_info.is_reverted() {
+            let fee_token_address = block_context.to_tx_context(&tx).fee_token_address();
+            assert_eq!(changes.nonces.keys().collect::<Vec<_>>(), vec![&sender_address]);
+            assert!(changes.storage.keys().all(|(address, _)| *address == fee_token_address));
+            assert!(changes.class_hashes.is_empty());
+            assert!(changes.compiled_class_hashes.is_empty());
+            assert!(changes.declared_contracts.is_empty());
+        }
+    }
+}
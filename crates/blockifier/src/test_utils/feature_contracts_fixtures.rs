@@ -0,0 +1,93 @@
+use std::fs;
+
+use crate::test_utils::cairo_compile::{
+    prepare_cairo0_compiler_deps,
+    prepare_group_tag_compiler_deps,
+    CompilationArtifacts,
+};
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::{CairoVersion, RunnableCairo1};
+
+/// The command that regenerates the committed feature contract artifacts; run from the blockifier
+/// crate directory.
+pub const FIX_FEATURE_CONTRACTS_COMMAND: &str =
+    "cargo run -p blockifier --bin feature_contracts_fixtures --features testing -- fix";
+
+/// A committed artifact that does not match the compilation of its feature contract source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FixtureMismatch {
+    pub source_path: String,
+    pub artifact_path: String,
+}
+
+/// Recompiles the feature contracts of the given Cairo version, and returns the committed artifacts
+/// that do not match their compilation. If `fix` is set, the mismatching artifacts are overwritten
+/// with the compilation output.
+/// Panics if the compiler of the Cairo version is not set up; see `prepare_cairo0_compiler_deps`
+/// and `prepare_group_tag_compiler_deps`.
+pub fn verify_feature_contracts_fixtures(
+    cairo_version: CairoVersion,
+    fix: bool,
+) -> Vec<FixtureMismatch> {
+    let mut mismatches = vec![];
+    match cairo_version {
+        CairoVersion::Cairo0 => {
+            prepare_cairo0_compiler_deps();
+            for contract in FeatureContract::all_feature_contracts()
+                .filter(|contract| contract.cairo_version() == cairo_version)
+            {
+                mismatches.extend(verify_contract_artifacts(&contract, fix));
+            }
+        }
+        CairoVersion::Cairo1(RunnableCairo1::Casm) => {
+            for (tag_and_tool_chain, feature_contracts) in
+                FeatureContract::cairo1_feature_contracts_by_tag()
+            {
+                prepare_group_tag_compiler_deps(&tag_and_tool_chain);
+                // TODO(Meshi 01/01/2025) Make this loop concurrent
+                for contract in feature_contracts
+                    .into_iter()
+                    .filter(|contract| contract.cairo_version() == cairo_version)
+                {
+                    mismatches.extend(verify_contract_artifacts(&contract, fix));
+                }
+            }
+        }
+        #[cfg(feature = "cairo_native")]
+        CairoVersion::Cairo1(RunnableCairo1::Native) => {
+            panic!("Native contracts share the artifacts of the Casm contracts.")
+        }
+    }
+    mismatches
+}
+
+/// Compares the compilation of the given contract with its committed artifacts, and returns the
+/// mismatching ones; overwrites them if `fix` is set.
+fn verify_contract_artifacts(contract: &FeatureContract, fix: bool) -> Vec<FixtureMismatch> {
+    let artifacts = match contract.compile() {
+        CompilationArtifacts::Cairo0 { casm } => vec![(contract.get_compiled_path(), casm)],
+        // TODO(Aviv): Remove this if after fixing sierra file of cairo steps contract.
+        CompilationArtifacts::Cairo1 { .. }
+            if matches!(contract, FeatureContract::CairoStepsTestContract) && !fix =>
+        {
+            vec![]
+        }
+        CompilationArtifacts::Cairo1 { casm, sierra } => {
+            vec![(contract.get_compiled_path(), casm), (contract.get_sierra_path(), sierra)]
+        }
+    };
+
+    let mut mismatches = vec![];
+    for (artifact_path, compiled_contents) in artifacts {
+        let existing_contents = fs::read(&artifact_path).ok();
+        if existing_contents.as_ref() == Some(&compiled_contents) {
+            continue;
+        }
+        if fix {
+            fs::write(&artifact_path, compiled_contents)
+                .unwrap_or_else(|error| panic!("Cannot write {artifact_path}: {error}."));
+        }
+        mismatches.push(FixtureMismatch { source_path: contract.get_source_path(), artifact_path });
+    }
+    mismatches
+}
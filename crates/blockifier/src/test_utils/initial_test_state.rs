@@ -37,7 +37,7 @@ pub fn fund_account(
 /// * Makes the Cairo0 account privileged (minter on both tokens, funded in both tokens).
 /// * "Declares" the input list of contracts.
 /// * "Deploys" the requested number of instances of each input contract.
-/// * Makes each input account contract privileged.
+/// * Makes each input account and paymaster contract privileged.
 pub fn test_state_inner(
     chain_info: &ChainInfo,
     initial_balances: Fee,
@@ -75,7 +75,8 @@ pub fn test_state_inner(
             match contract {
                 FeatureContract::AccountWithLongValidate(_)
                 | FeatureContract::AccountWithoutValidations(_)
-                | FeatureContract::FaultyAccount(_) => {
+                | FeatureContract::FaultyAccount(_)
+                | FeatureContract::Paymaster => {
                     fund_account(chain_info, instance_address, initial_balances, &mut state_reader);
                 }
                 _ => (),
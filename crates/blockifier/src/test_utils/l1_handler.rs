@@ -1,12 +1,22 @@
 use starknet_api::abi::abi_utils::selector_from_name;
 use starknet_api::calldata;
-use starknet_api::core::ContractAddress;
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::L1HandlerTransaction;
 use starknet_api::test_utils::l1_handler::{executable_l1_handler_tx, L1HandlerTxArgs};
 use starknet_api::transaction::fields::Fee;
 use starknet_types_core::felt::Felt;
 
 pub fn l1handler_tx(l1_fee: Fee, contract_address: ContractAddress) -> L1HandlerTransaction {
+    l1handler_tx_with_nonce(l1_fee, contract_address, Nonce::default())
+}
+
+/// An L1 handler transaction whose message is distinct from those of transactions with other
+/// nonces, e.g., for blocks that handle several messages.
+pub fn l1handler_tx_with_nonce(
+    l1_fee: Fee,
+    contract_address: ContractAddress,
+    nonce: Nonce,
+) -> L1HandlerTransaction {
     let calldata = calldata![
         Felt::from(0x123), // from_address.
         Felt::from(0x876), // key.
@@ -18,6 +28,7 @@ pub fn l1handler_tx(l1_fee: Fee, contract_address: ContractAddress) -> L1Handler
         entry_point_selector: selector_from_name("l1_handler_set_value"),
         calldata,
         paid_fee_on_l1: l1_fee,
+        nonce,
         ..Default::default()
     })
 }
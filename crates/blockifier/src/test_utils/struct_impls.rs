@@ -17,9 +17,18 @@ use starknet_api::contract_class::SierraVersion;
 use starknet_api::core::{ChainId, ClassHash};
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::test_utils::{TEST_ERC20_CONTRACT_ADDRESS, TEST_ERC20_CONTRACT_ADDRESS2};
+use starknet_api::transaction::TransactionVersions;
 
 use crate::bouncer::{BouncerConfig, BouncerWeights, BuiltinCount};
-use crate::context::{BlockContext, ChainInfo, FeeTokenAddresses, TransactionContext};
+use crate::context::{
+    BlockContext,
+    ChainInfo,
+    CustomFeeTokens,
+    FeeDistribution,
+    FeeTokenAddresses,
+    PaymasterConfig,
+    TransactionContext,
+};
 use crate::execution::call_info::{CallExecution, CallInfo, Retdata};
 use crate::execution::common_hints::ExecutionMode;
 #[cfg(feature = "cairo_native")]
@@ -66,8 +75,11 @@ impl CallEntryPoint {
         limit_steps_by_resources: bool,
         execution_mode: ExecutionMode,
     ) -> EntryPointExecutionResult<CallInfo> {
-        let tx_context =
-            TransactionContext { block_context: BlockContext::create_for_testing(), tx_info };
+        let tx_context = TransactionContext {
+            block_context: BlockContext::create_for_testing(),
+            tx_info,
+            call_counter: Default::default(),
+        };
         let mut context = EntryPointExecutionContext::new(
             Arc::new(tx_context),
             execution_mode,
@@ -130,27 +142,32 @@ impl ChainInfo {
                 eth_fee_token_address: contract_address!(TEST_ERC20_CONTRACT_ADDRESS),
                 strk_fee_token_address: contract_address!(TEST_ERC20_CONTRACT_ADDRESS2),
             },
+            custom_fee_tokens: CustomFeeTokens::default(),
+            disabled_tx_versions: TransactionVersions::default(),
+            sierra_gas_only: false,
+            fee_distribution: FeeDistribution::default(),
+            paymaster: PaymasterConfig::default(),
         }
     }
 }
 
 impl BlockContext {
     pub fn create_for_testing() -> Self {
-        Self {
-            block_info: BlockInfo::create_for_testing(),
-            chain_info: ChainInfo::create_for_testing(),
-            versioned_constants: VersionedConstants::create_for_testing(),
-            bouncer_config: BouncerConfig::max(),
-        }
+        Self::new(
+            BlockInfo::create_for_testing(),
+            ChainInfo::create_for_testing(),
+            VersionedConstants::create_for_testing(),
+            BouncerConfig::max(),
+        )
     }
 
     pub fn create_for_account_testing() -> Self {
-        Self {
-            block_info: BlockInfo::create_for_testing(),
-            chain_info: ChainInfo::create_for_testing(),
-            versioned_constants: VersionedConstants::create_for_account_testing(),
-            bouncer_config: BouncerConfig::max(),
-        }
+        Self::new(
+            BlockInfo::create_for_testing(),
+            ChainInfo::create_for_testing(),
+            VersionedConstants::create_for_account_testing(),
+            BouncerConfig::max(),
+        )
     }
 
     pub fn create_for_bouncer_testing(max_n_events_in_block: usize) -> Self {
@@ -160,6 +177,7 @@ impl BlockContext {
                     n_events: max_n_events_in_block,
                     ..BouncerWeights::max()
                 },
+                ..BouncerConfig::max()
             },
             ..Self::create_for_account_testing()
         }
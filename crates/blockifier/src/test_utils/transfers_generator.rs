@@ -79,8 +79,10 @@ impl TransfersGenerator {
         let chain_info = block_context.chain_info().clone();
         let state =
             test_state(&chain_info, config.balance, &[(account_contract, config.n_accounts)]);
-        let executor_config =
-            TransactionExecutorConfig { concurrency_config: config.concurrency_config.clone() };
+        let executor_config = TransactionExecutorConfig {
+            concurrency_config: config.concurrency_config.clone(),
+            ..Default::default()
+        };
         let executor = TransactionExecutor::new(state, block_context, executor_config);
         let account_addresses = (0..config.n_accounts)
             .map(|instance_id| account_contract.get_instance_address(instance_id))
@@ -3,6 +3,7 @@ pub mod account_transaction;
 pub mod error_format_test;
 pub mod errors;
 pub mod objects;
+pub mod rpc_receipt;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
 pub mod transaction_execution;
@@ -13,12 +13,18 @@ use starknet_api::transaction::fields::{
     AllResourceBounds,
     Calldata,
     Fee,
+    GasVectorComputationMode,
     PaymasterData,
     Tip,
     TransactionSignature,
     ValidResourceBounds,
 };
-use starknet_api::transaction::{constants, TransactionHash, TransactionVersion};
+use starknet_api::transaction::{
+    constants,
+    TransactionHash,
+    TransactionVersion,
+    TransactionVersions,
+};
 use starknet_types_core::felt::Felt;
 
 use crate::context::{BlockContext, GasCounter, TransactionContext};
@@ -32,12 +38,12 @@ use crate::execution::stack_trace::{
 };
 use crate::fee::fee_checks::{FeeCheckReportFields, PostExecutionReport};
 use crate::fee::fee_utils::{
+    get_address_balance_keys,
     get_fee_by_gas_vector,
-    get_sequencer_balance_keys,
     verify_can_pay_committed_bounds,
 };
 use crate::fee::gas_usage::estimate_minimal_gas_vector;
-use crate::fee::receipt::TransactionReceipt;
+use crate::fee::receipt::{FeeSplit, TransactionReceipt};
 use crate::retdata;
 use crate::state::cached_state::{StateCache, TransactionalState};
 use crate::state::state_api::{State, StateReader, UpdatableState};
@@ -90,6 +96,14 @@ impl Default for ExecutionFlags {
     }
 }
 
+/// Checks to skip when simulating a transaction, see [AccountTransaction::simulate].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SimulationFlags {
+    pub skip_validate: bool,
+    pub skip_fee_charge: bool,
+    pub skip_nonce_check: bool,
+}
+
 /// Represents a paid Starknet transaction.
 #[derive(Clone, Debug, derive_more::From)]
 pub struct AccountTransaction {
@@ -211,8 +225,12 @@ impl AccountTransaction {
         self.create_tx_info().enforce_fee()
     }
 
-    fn verify_tx_version(&self, version: TransactionVersion) -> TransactionExecutionResult<()> {
-        let allowed_versions: Vec<TransactionVersion> = match &self.tx {
+    fn verify_tx_version(
+        &self,
+        version: TransactionVersion,
+        disabled_versions: &TransactionVersions,
+    ) -> TransactionExecutionResult<()> {
+        let supported_versions: Vec<TransactionVersion> = match &self.tx {
             // Support `Declare` of version 0 in order to allow bootstrapping of a new system.
             Transaction::Declare(_) => {
                 vec![
@@ -229,6 +247,10 @@ impl AccountTransaction {
                 vec![TransactionVersion::ZERO, TransactionVersion::ONE, TransactionVersion::THREE]
             }
         };
+        let allowed_versions: Vec<TransactionVersion> = supported_versions
+            .into_iter()
+            .filter(|supported_version| !disabled_versions.contains(supported_version))
+            .collect();
         if allowed_versions.contains(&version) {
             Ok(())
         } else {
@@ -247,6 +269,22 @@ impl AccountTransaction {
         let tx_info = &tx_context.tx_info;
         Self::handle_nonce(state, tx_info, strict_nonce_check)?;
 
+        self.perform_fee_pre_validation(state, tx_context)
+    }
+
+    fn perform_fee_pre_validation<S: State + StateReader>(
+        &self,
+        state: &mut S,
+        tx_context: &TransactionContext,
+    ) -> TransactionPreValidationResult<()> {
+        // In Sierra gas only mode, fees are computed in L2 gas, which must be bounded by the
+        // transaction.
+        if tx_context.block_context.chain_info.sierra_gas_only
+            && tx_context.get_gas_vector_computation_mode() != GasVectorComputationMode::All
+        {
+            return Err(TransactionPreValidationError::L2GasBoundsRequired);
+        }
+        Self::check_paymaster_address(tx_context)?;
         if self.execution_flags.charge_fee {
             self.check_fee_bounds(tx_context)?;
 
@@ -256,6 +294,24 @@ impl AccountTransaction {
         Ok(())
     }
 
+    /// A sponsored transaction must name its paymaster by a valid contract address.
+    fn check_paymaster_address(
+        tx_context: &TransactionContext,
+    ) -> TransactionPreValidationResult<()> {
+        if !tx_context.block_context.chain_info.paymaster.enabled {
+            return Ok(());
+        }
+        let TransactionInfo::Current(context) = &tx_context.tx_info else {
+            return Ok(());
+        };
+        match context.paymaster_data.0.first() {
+            Some(&paymaster) if ContractAddress::try_from(paymaster).is_err() => {
+                Err(TransactionPreValidationError::InvalidPaymasterAddress { paymaster })
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn check_fee_bounds(
         &self,
         tx_context: &TransactionContext,
@@ -368,6 +424,18 @@ impl AccountTransaction {
         })
     }
 
+    // Increments the nonce of the sender regardless of the incoming transaction nonce.
+    fn increment_nonce(
+        state: &mut dyn State,
+        tx_info: &TransactionInfo,
+    ) -> TransactionPreValidationResult<()> {
+        if tx_info.is_v0() {
+            return Ok(());
+        }
+
+        Ok(state.increment_nonce(tx_info.sender_address())?)
+    }
+
     fn handle_validate_tx(
         &self,
         state: &mut dyn State,
@@ -390,6 +458,79 @@ impl AccountTransaction {
         }
     }
 
+    fn handle_validate_paymaster(
+        &self,
+        state: &mut dyn State,
+        tx_context: Arc<TransactionContext>,
+        remaining_gas: &mut GasCounter,
+    ) -> TransactionExecutionResult<Option<CallInfo>> {
+        if !self.execution_flags.validate {
+            return Ok(None);
+        }
+        let remaining_validation_gas = &mut remaining_gas
+            .limit_usage(tx_context.block_context.chain_info.paymaster.validate_max_sierra_gas);
+        Ok(self
+            .validate_paymaster(state, tx_context, remaining_validation_gas)?
+            .inspect(|call_info| remaining_gas.subtract_used_gas(call_info)))
+    }
+
+    /// Runs the `__validate_paymaster__` entry point of the paymaster sponsoring the transaction,
+    /// if any, with the paymaster data that follows its address.
+    pub fn validate_paymaster(
+        &self,
+        state: &mut dyn State,
+        tx_context: Arc<TransactionContext>,
+        remaining_gas: &mut u64,
+    ) -> TransactionExecutionResult<Option<CallInfo>> {
+        let Some(storage_address) = tx_context.paymaster_address() else {
+            return Ok(None);
+        };
+        let limit_steps_by_resources = self.execution_flags.charge_fee;
+        let mut context =
+            EntryPointExecutionContext::new_validate(tx_context, limit_steps_by_resources);
+        let class_hash = state.get_class_hash_at(storage_address)?;
+        let selector = selector_from_name(constants::VALIDATE_PAYMASTER_ENTRY_POINT_NAME);
+        let validate_paymaster_call = CallEntryPoint {
+            entry_point_type: EntryPointType::External,
+            entry_point_selector: selector,
+            calldata: Calldata(Arc::new(self.paymaster_data().0[1..].to_vec())),
+            class_hash: None,
+            code_address: None,
+            storage_address,
+            caller_address: self.sender_address(),
+            call_type: CallType::Call,
+            initial_gas: *remaining_gas,
+        };
+
+        let validate_paymaster_call_info = validate_paymaster_call
+            .execute(state, &mut context, remaining_gas)
+            .map_err(|error| TransactionExecutionError::ValidateTransactionError {
+                error,
+                class_hash,
+                storage_address,
+                selector,
+            })?;
+
+        if is_cairo1(&state.get_compiled_class(class_hash)?) {
+            if validate_paymaster_call_info.execution.failed {
+                return Err(TransactionExecutionError::PanicInValidate {
+                    panic_reason: extract_trailing_cairo1_revert_trace(
+                        &validate_paymaster_call_info,
+                        Cairo1RevertHeader::Validation,
+                    ),
+                });
+            }
+            if validate_paymaster_call_info.execution.retdata
+                != retdata![*constants::VALIDATE_RETDATA]
+            {
+                return Err(TransactionExecutionError::InvalidValidateReturnData {
+                    actual: validate_paymaster_call_info.execution.retdata,
+                });
+            }
+        }
+        Ok(Some(validate_paymaster_call_info))
+    }
+
     fn assert_actual_fee_in_bounds(tx_context: &Arc<TransactionContext>, actual_fee: Fee) {
         match &tx_context.tx_info {
             TransactionInfo::Current(context) => {
@@ -419,34 +560,69 @@ impl AccountTransaction {
         actual_fee: Fee,
         charge_fee: bool,
         concurrency_mode: bool,
-    ) -> TransactionExecutionResult<Option<CallInfo>> {
+    ) -> TransactionExecutionResult<FeeTransfers> {
         if !charge_fee || actual_fee == Fee(0) {
             // Fee charging is not enforced in some tests.
-            return Ok(None);
+            return Ok(FeeTransfers::default());
         }
 
         Self::assert_actual_fee_in_bounds(&tx_context, actual_fee);
 
+        let fee_distribution = &tx_context.block_context.chain_info.fee_distribution;
+        let fee_split = fee_distribution.is_enabled().then(|| fee_distribution.split(actual_fee));
+        let mut distribution_call_infos = vec![];
+        for (recipient, amount) in fee_split
+            .map(|fee_split| fee_distribution.share_transfers(&fee_split))
+            .unwrap_or_default()
+        {
+            // Like the sequencer balance, the shared recipient balances are updated at commit.
+            let distribution_call_info =
+                if concurrency_mode && recipient != tx_context.fee_payer_address() {
+                    Self::concurrency_execute_fee_transfer(
+                        state,
+                        tx_context.clone(),
+                        recipient,
+                        amount,
+                    )?
+                } else {
+                    Self::execute_fee_transfer(state, tx_context.clone(), recipient, amount)?
+                };
+            distribution_call_infos.push(distribution_call_info);
+        }
+
+        let sequencer_address = tx_context.block_context.block_info.sequencer_address;
+        let sequencer_fee = fee_split.map_or(actual_fee, |fee_split| fee_split.sequencer);
         let fee_transfer_call_info = if concurrency_mode && !tx_context.is_sequencer_the_sender() {
-            Self::concurrency_execute_fee_transfer(state, tx_context, actual_fee)?
+            Self::concurrency_execute_fee_transfer(
+                state,
+                tx_context,
+                sequencer_address,
+                sequencer_fee,
+            )?
         } else {
-            Self::execute_fee_transfer(state, tx_context, actual_fee)?
+            Self::execute_fee_transfer(state, tx_context, sequencer_address, sequencer_fee)?
         };
 
-        Ok(Some(fee_transfer_call_info))
+        Ok(FeeTransfers {
+            fee_transfer_call_info: Some(fee_transfer_call_info),
+            distribution_call_infos,
+            fee_split,
+        })
     }
 
+    /// Transfers the given amount of the fee token from the fee payer to the given recipient.
     fn execute_fee_transfer(
         state: &mut dyn State,
         tx_context: Arc<TransactionContext>,
-        actual_fee: Fee,
+        recipient: ContractAddress,
+        amount: Fee,
     ) -> TransactionExecutionResult<CallInfo> {
         // The least significant 128 bits of the amount transferred.
-        let lsb_amount = Felt::from(actual_fee.0);
+        let lsb_amount = Felt::from(amount.0);
         // The most significant 128 bits of the amount transferred.
         let msb_amount = Felt::ZERO;
 
-        let TransactionContext { block_context, tx_info } = tx_context.as_ref();
+        let block_context = &tx_context.block_context;
         let storage_address = tx_context.fee_token_address();
         // The fee contains the cost of running this transfer, and the token contract is
         // well known to the sequencer, so there is no need to limit its run.
@@ -457,13 +633,9 @@ impl AccountTransaction {
             code_address: None,
             entry_point_type: EntryPointType::External,
             entry_point_selector: selector_from_name(constants::TRANSFER_ENTRY_POINT_NAME),
-            calldata: calldata![
-                *block_context.block_info.sequencer_address.0.key(), // Recipient.
-                lsb_amount,
-                msb_amount
-            ],
+            calldata: calldata![*recipient.0.key(), lsb_amount, msb_amount],
             storage_address,
-            caller_address: tx_info.sender_address(),
+            caller_address: tx_context.fee_payer_address(),
             call_type: CallType::Call,
 
             initial_gas: remaining_gas_for_fee_transfer,
@@ -477,32 +649,34 @@ impl AccountTransaction {
 
     /// Handles fee transfer in concurrent execution.
     ///
-    /// Accessing and updating the sequencer balance at this stage is a bottleneck; this function
-    /// manipulates the state to avoid that part.
+    /// Accessing and updating the balance of a fee recipient, e.g., the sequencer, at this stage is
+    /// a bottleneck, as every transaction pays it; this function manipulates the state to avoid
+    /// that part.
     /// Note: the returned transfer call info is partial, and should be completed at the commit
-    /// stage, as well as the actual sequencer balance.
+    /// stage, as well as the actual recipient balance.
     fn concurrency_execute_fee_transfer<S: StateReader>(
         state: &mut TransactionalState<'_, S>,
         tx_context: Arc<TransactionContext>,
-        actual_fee: Fee,
+        recipient: ContractAddress,
+        amount: Fee,
     ) -> TransactionExecutionResult<CallInfo> {
         let fee_address = tx_context.fee_token_address();
-        let (sequencer_balance_key_low, sequencer_balance_key_high) =
-            get_sequencer_balance_keys(&tx_context.block_context);
+        let (recipient_balance_key_low, recipient_balance_key_high) =
+            get_address_balance_keys(recipient);
         let mut transfer_state = TransactionalState::create_transactional(state);
 
-        // Set the initial sequencer balance to avoid tarnishing the read-set of the transaction.
+        // Set the initial recipient balance to avoid tarnishing the read-set of the transaction.
         let cache = transfer_state.cache.get_mut();
-        for key in [sequencer_balance_key_low, sequencer_balance_key_high] {
+        for key in [recipient_balance_key_low, recipient_balance_key_high] {
             cache.set_storage_initial_value(fee_address, key, Felt::ZERO);
         }
 
         let fee_transfer_call_info =
-            Self::execute_fee_transfer(&mut transfer_state, tx_context, actual_fee);
-        // Commit without updating the sequencer balance.
+            Self::execute_fee_transfer(&mut transfer_state, tx_context, recipient, amount);
+        // Commit without updating the recipient balance.
         let storage_writes = &mut transfer_state.cache.get_mut().writes.storage;
-        storage_writes.remove(&(fee_address, sequencer_balance_key_low));
-        storage_writes.remove(&(fee_address, sequencer_balance_key_high));
+        storage_writes.remove(&(fee_address, recipient_balance_key_low));
+        storage_writes.remove(&(fee_address, recipient_balance_key_high));
         transfer_state.commit();
         fee_transfer_call_info
     }
@@ -536,6 +710,7 @@ impl AccountTransaction {
         remaining_gas: &mut GasCounter,
     ) -> TransactionExecutionResult<ValidateExecuteCallInfo> {
         let validate_call_info: Option<CallInfo>;
+        let validate_paymaster_call_info: Option<CallInfo>;
         let execute_call_info: Option<CallInfo>;
         if matches!(&self.tx, Transaction::DeployAccount(_)) {
             // Handle `DeployAccount` transactions separately, due to different order of things.
@@ -548,6 +723,8 @@ impl AccountTransaction {
             execute_call_info = self.run_execute(state, &mut execution_context, remaining_gas)?;
             validate_call_info =
                 self.handle_validate_tx(state, tx_context.clone(), remaining_gas)?;
+            validate_paymaster_call_info =
+                self.handle_validate_paymaster(state, tx_context.clone(), remaining_gas)?;
         } else {
             let mut execution_context = EntryPointExecutionContext::new_invoke(
                 tx_context.clone(),
@@ -555,6 +732,8 @@ impl AccountTransaction {
             );
             validate_call_info =
                 self.handle_validate_tx(state, tx_context.clone(), remaining_gas)?;
+            validate_paymaster_call_info =
+                self.handle_validate_paymaster(state, tx_context.clone(), remaining_gas)?;
             execute_call_info = self.run_execute(state, &mut execution_context, remaining_gas)?;
         }
 
@@ -563,7 +742,10 @@ impl AccountTransaction {
             &tx_context,
             &state.get_actual_state_changes()?,
             CallInfo::summarize_many(
-                validate_call_info.iter().chain(execute_call_info.iter()),
+                validate_call_info
+                    .iter()
+                    .chain(validate_paymaster_call_info.iter())
+                    .chain(execute_call_info.iter()),
                 &tx_context.block_context.versioned_constants,
             ),
             0,
@@ -579,6 +761,7 @@ impl AccountTransaction {
             Some(error) => Err(error.into()),
             None => Ok(ValidateExecuteCallInfo::new_accepted(
                 validate_call_info,
+                validate_paymaster_call_info,
                 execute_call_info,
                 tx_receipt,
             )),
@@ -598,9 +781,11 @@ impl AccountTransaction {
         // Run the validation, and if execution later fails, only keep the validation diff.
         let validate_call_info =
             self.handle_validate_tx(state, tx_context.clone(), remaining_gas)?;
+        let validate_paymaster_call_info =
+            self.handle_validate_paymaster(state, tx_context.clone(), remaining_gas)?;
 
         let n_allotted_execution_steps = execution_context.subtract_validation_and_overhead_steps(
-            &validate_call_info,
+            validate_call_info.iter().chain(validate_paymaster_call_info.iter()),
             &self.tx_type(),
             self.calldata_length(),
         );
@@ -608,6 +793,8 @@ impl AccountTransaction {
         // Save the state changes resulting from running `validate_tx`, to be used later for
         // resource and fee calculation.
         let validate_state_cache = state.borrow_updated_state_cache()?.clone();
+        // The calls of a reverted execution do not count against the call quotas.
+        let validate_quota_call_counts = tx_context.call_counter.snapshot();
 
         // Create copies of state and validate_resources for the execution.
         // Both will be rolled back if the execution is reverted or committed upon success.
@@ -627,7 +814,7 @@ impl AccountTransaction {
                 &tx_context,
                 &validate_state_cache.to_state_diff(),
                 CallInfo::summarize_many(
-                    validate_call_info.iter(),
+                    validate_call_info.iter().chain(validate_paymaster_call_info.iter()),
                     &tx_context.block_context.versioned_constants,
                 ),
                 execution_steps_consumed,
@@ -649,7 +836,10 @@ impl AccountTransaction {
                         tx_context.block_context.versioned_constants.comprehensive_state_diff,
                     ),
                     CallInfo::summarize_many(
-                        validate_call_info.iter().chain(execute_call_info.iter()),
+                        validate_call_info
+                            .iter()
+                            .chain(validate_paymaster_call_info.iter())
+                            .chain(execute_call_info.iter()),
                         &tx_context.block_context.versioned_constants,
                     ),
                     0,
@@ -668,12 +858,15 @@ impl AccountTransaction {
                         // revert case, compute resources by adding consumed execution steps to
                         // validation resources).
                         execution_state.abort();
+                        tx_context.call_counter.restore(validate_quota_call_counts);
                         let tx_receipt = TransactionReceipt {
                             fee: post_execution_report.recommended_fee(),
+                            tip: post_execution_report.recommended_tip(),
                             ..get_revert_receipt()
                         };
                         Ok(ValidateExecuteCallInfo::new_reverted(
                             validate_call_info,
+                            validate_paymaster_call_info,
                             post_execution_error.into(),
                             tx_receipt,
                         ))
@@ -683,6 +876,7 @@ impl AccountTransaction {
                         execution_state.commit();
                         Ok(ValidateExecuteCallInfo::new_accepted(
                             validate_call_info,
+                            validate_paymaster_call_info,
                             execute_call_info,
                             tx_receipt,
                         ))
@@ -693,6 +887,7 @@ impl AccountTransaction {
                 let revert_receipt = get_revert_receipt();
                 // Error during execution. Revert, even if the error is sequencer-related.
                 execution_state.abort();
+                tx_context.call_counter.restore(validate_quota_call_counts);
                 let post_execution_report = PostExecutionReport::new(
                     state,
                     &tx_context,
@@ -701,9 +896,11 @@ impl AccountTransaction {
                 )?;
                 Ok(ValidateExecuteCallInfo::new_reverted(
                     validate_call_info,
+                    validate_paymaster_call_info,
                     gen_tx_execution_error_trace(&execution_error).into(),
                     TransactionReceipt {
                         fee: post_execution_report.recommended_fee(),
+                        tip: post_execution_report.recommended_tip(),
                         ..revert_receipt
                     },
                 ))
@@ -743,60 +940,121 @@ impl AccountTransaction {
 
         self.run_revertible(state, tx_context, remaining_gas)
     }
-}
 
-impl<U: UpdatableState> ExecutableTransaction<U> for AccountTransaction {
-    fn execute_raw(
+    /// Simulates the transaction on top of the given state, relaxing its checks according to the
+    /// given flags. The state is left unmodified, whether the simulation succeeds or not.
+    pub fn simulate<U: UpdatableState>(
+        &self,
+        state: &mut U,
+        block_context: &BlockContext,
+        simulation_flags: SimulationFlags,
+    ) -> TransactionExecutionResult<TransactionExecutionInfo> {
+        let simulated_tx = AccountTransaction {
+            tx: self.tx.clone(),
+            execution_flags: ExecutionFlags {
+                only_query: self.execution_flags.only_query,
+                charge_fee: self.execution_flags.charge_fee && !simulation_flags.skip_fee_charge,
+                validate: self.execution_flags.validate && !simulation_flags.skip_validate,
+            },
+        };
+
+        let mut transactional_state = TransactionalState::create_transactional(state);
+        let concurrency_mode = false;
+        let execution_result = simulated_tx.execute_account_tx(
+            &mut transactional_state,
+            block_context,
+            concurrency_mode,
+            simulation_flags.skip_nonce_check,
+        );
+        transactional_state.abort();
+
+        execution_result
+    }
+
+    fn execute_account_tx<U: UpdatableState>(
         &self,
         state: &mut TransactionalState<'_, U>,
         block_context: &BlockContext,
         concurrency_mode: bool,
+        skip_nonce_check: bool,
     ) -> TransactionExecutionResult<TransactionExecutionInfo> {
         let tx_context = Arc::new(block_context.to_tx_context(self));
-        self.verify_tx_version(tx_context.tx_info.version())?;
+        self.verify_tx_version(
+            tx_context.tx_info.version(),
+            &block_context.chain_info.disabled_tx_versions,
+        )?;
 
         // Nonce and fee check should be done before running user code.
-        let strict_nonce_check = true;
-        self.perform_pre_validation_stage(state, &tx_context, strict_nonce_check)?;
+        if skip_nonce_check {
+            Self::increment_nonce(state, &tx_context.tx_info)?;
+            self.perform_fee_pre_validation(state, &tx_context)?;
+        } else {
+            let strict_nonce_check = true;
+            self.perform_pre_validation_stage(state, &tx_context, strict_nonce_check)?;
+        }
 
         // Run validation and execution.
         let initial_gas = tx_context.initial_sierra_gas();
         let ValidateExecuteCallInfo {
             validate_call_info,
+            validate_paymaster_call_info,
             execute_call_info,
             revert_error,
             final_cost:
                 TransactionReceipt {
                     fee: final_fee,
+                    tip: final_tip,
                     da_gas: final_da_gas,
                     resources: final_resources,
                     gas: total_gas,
+                    fee_split: _,
                 },
         } = self.run_or_revert(state, &mut GasCounter::new(initial_gas), tx_context.clone())?;
-        let fee_transfer_call_info = Self::handle_fee(
-            state,
-            tx_context,
-            final_fee,
-            self.execution_flags.charge_fee,
-            concurrency_mode,
-        )?;
+        let FeeTransfers { fee_transfer_call_info, distribution_call_infos, fee_split } =
+            Self::handle_fee(
+                state,
+                tx_context.clone(),
+                final_fee,
+                self.execution_flags.charge_fee,
+                concurrency_mode,
+            )?;
+        let read_set =
+            if block_context.records_read_sets() { Some(state.read_set()?) } else { None };
 
         let tx_execution_info = TransactionExecutionInfo {
             validate_call_info,
+            validate_paymaster_call_info,
             execute_call_info,
             fee_transfer_call_info,
+            fee_distribution_call_infos: distribution_call_infos,
             receipt: TransactionReceipt {
                 fee: final_fee,
+                tip: final_tip,
                 da_gas: final_da_gas,
                 resources: final_resources,
                 gas: total_gas,
+                fee_split,
             },
             revert_error,
+            read_set,
+            quota_call_counts: tx_context.call_counter.snapshot(),
         };
         Ok(tx_execution_info)
     }
 }
 
+impl<U: UpdatableState> ExecutableTransaction<U> for AccountTransaction {
+    fn execute_raw(
+        &self,
+        state: &mut TransactionalState<'_, U>,
+        block_context: &BlockContext,
+        concurrency_mode: bool,
+    ) -> TransactionExecutionResult<TransactionExecutionInfo> {
+        let skip_nonce_check = false;
+        self.execute_account_tx(state, block_context, concurrency_mode, skip_nonce_check)
+    }
+}
+
 impl TransactionInfoCreator for AccountTransaction {
     fn create_tx_info(&self) -> TransactionInfo {
         self.tx.create_tx_info(self.execution_flags.only_query)
@@ -806,6 +1064,7 @@ impl TransactionInfoCreator for AccountTransaction {
 /// Represents a bundle of validate-execute stage execution effects.
 struct ValidateExecuteCallInfo {
     validate_call_info: Option<CallInfo>,
+    validate_paymaster_call_info: Option<CallInfo>,
     execute_call_info: Option<CallInfo>,
     revert_error: Option<RevertError>,
     final_cost: TransactionReceipt,
@@ -814,19 +1073,28 @@ struct ValidateExecuteCallInfo {
 impl ValidateExecuteCallInfo {
     pub fn new_accepted(
         validate_call_info: Option<CallInfo>,
+        validate_paymaster_call_info: Option<CallInfo>,
         execute_call_info: Option<CallInfo>,
         final_cost: TransactionReceipt,
     ) -> Self {
-        Self { validate_call_info, execute_call_info, revert_error: None, final_cost }
+        Self {
+            validate_call_info,
+            validate_paymaster_call_info,
+            execute_call_info,
+            revert_error: None,
+            final_cost,
+        }
     }
 
     pub fn new_reverted(
         validate_call_info: Option<CallInfo>,
+        validate_paymaster_call_info: Option<CallInfo>,
         revert_error: RevertError,
         final_cost: TransactionReceipt,
     ) -> Self {
         Self {
             validate_call_info,
+            validate_paymaster_call_info,
             execute_call_info: None,
             revert_error: Some(revert_error),
             final_cost,
@@ -834,6 +1102,15 @@ impl ValidateExecuteCallInfo {
     }
 }
 
+/// The fee transfers of a transaction: to the sequencer, and to the recipients of the configured
+/// fee distribution, if any.
+#[derive(Default)]
+struct FeeTransfers {
+    fee_transfer_call_info: Option<CallInfo>,
+    distribution_call_infos: Vec<CallInfo>,
+    fee_split: Option<FeeSplit>,
+}
+
 impl ValidatableTransaction for AccountTransaction {
     fn validate_tx(
         &self,
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use assert_matches::assert_matches;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ResourceTracker;
 use num_traits::Inv;
@@ -12,6 +13,7 @@ use starknet_api::abi::abi_utils::{
     selector_from_name,
 };
 use starknet_api::block::{FeeType, GasPrice};
+use starknet_api::contract_class::SierraVersion;
 use starknet_api::core::{calculate_contract_address, ClassHash, ContractAddress};
 use starknet_api::executable_transaction::{
     AccountTransaction as ApiExecutableTransaction,
@@ -32,15 +34,20 @@ use starknet_api::test_utils::{
     DEFAULT_STRK_L2_GAS_PRICE,
     MAX_FEE,
 };
-use starknet_api::transaction::constants::TRANSFER_ENTRY_POINT_NAME;
+use starknet_api::transaction::constants::{
+    TRANSFER_ENTRY_POINT_NAME,
+    VALIDATE_PAYMASTER_ENTRY_POINT_NAME,
+};
 use starknet_api::transaction::fields::{
     AllResourceBounds,
     Calldata,
     ContractAddressSalt,
     Fee,
     GasVectorComputationMode,
+    PaymasterData,
     Resource,
     ResourceBounds,
+    Tip,
     ValidResourceBounds,
 };
 use starknet_api::transaction::{
@@ -48,6 +55,7 @@ use starknet_api::transaction::{
     DeclareTransactionV2,
     TransactionHash,
     TransactionVersion,
+    TransactionVersions,
 };
 use starknet_api::{
     calldata,
@@ -63,17 +71,24 @@ use starknet_api::{
 use starknet_types_core::felt::Felt;
 
 use crate::check_tx_execution_error_for_invalid_scenario;
-use crate::context::{BlockContext, TransactionContext};
+use crate::context::{
+    BlockContext,
+    CustomFeeToken,
+    CustomFeeTokens,
+    FeeDistribution,
+    TransactionContext,
+};
 use crate::execution::call_info::CallInfo;
 use crate::execution::contract_class::TrackedResource;
 use crate::execution::entry_point::EntryPointExecutionContext;
+use crate::execution::stack_trace::FailureReason;
 use crate::execution::syscalls::SyscallSelector;
 use crate::fee::fee_utils::{get_fee_by_gas_vector, get_sequencer_balance_keys};
 use crate::fee::gas_usage::estimate_minimal_gas_vector;
 use crate::state::cached_state::{StateChangesCount, StateChangesCountForFee, TransactionalState};
 use crate::state::state_api::{State, StateReader};
 use crate::test_utils::contracts::FeatureContract;
-use crate::test_utils::initial_test_state::{fund_account, test_state};
+use crate::test_utils::initial_test_state::{fund_account, test_state, test_state_inner};
 use crate::test_utils::syscall::build_recurse_calldata;
 use crate::test_utils::{
     create_calldata,
@@ -89,6 +104,11 @@ use crate::transaction::account_transaction::{
     AccountTransaction,
     ExecutionFlags as AccountExecutionFlags,
 };
+use crate::transaction::errors::{
+    TransactionExecutionError,
+    TransactionFeeError,
+    TransactionPreValidationError,
+};
 use crate::transaction::objects::{HasRelatedFeeType, TransactionInfoCreator};
 use crate::transaction::test_utils::{
     all_resource_bounds,
@@ -385,6 +405,449 @@ fn test_invoke_tx_from_non_deployed_account(
     }
 }
 
+#[rstest]
+fn test_disabled_tx_version(
+    mut block_context: BlockContext,
+    max_fee: Fee,
+    default_all_resource_bounds: ValidResourceBounds,
+    #[values(TransactionVersion::ONE, TransactionVersion::THREE)] tx_version: TransactionVersion,
+) {
+    block_context.chain_info.disabled_tx_versions = TransactionVersions::new([tx_version]);
+    let TestInitData { mut state, account_address, contract_address, mut nonce_manager } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo0);
+
+    let tx_result = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            max_fee,
+            sender_address: account_address,
+            calldata: create_trivial_calldata(contract_address),
+            resource_bounds: default_all_resource_bounds,
+            version: tx_version,
+            nonce: nonce_manager.next(account_address),
+        },
+    );
+    assert_matches!(
+        tx_result,
+        Err(TransactionExecutionError::InvalidVersion { version, allowed_versions })
+        if version == tx_version && !allowed_versions.contains(&tx_version)
+    );
+}
+
+#[rstest]
+fn test_custom_fee_token(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let TestInitData { mut state, account_address, contract_address, mut nonce_manager } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo0);
+
+    // Charge STRK fees in the ETH fee token, which is deployed and funded in the test state.
+    let eth_fee_token_address = block_context.chain_info.fee_token_address(&FeeType::Eth);
+    let strk_fee_token_address = block_context.chain_info.fee_token_address(&FeeType::Strk);
+    block_context.chain_info.custom_fee_tokens = CustomFeeTokens::new([CustomFeeToken {
+        fee_type: FeeType::Strk,
+        address: eth_fee_token_address,
+        decimals: 6,
+    }])
+    .unwrap();
+    assert_eq!(block_context.chain_info.fee_token_address(&FeeType::Strk), eth_fee_token_address);
+    assert_eq!(block_context.chain_info.fee_token_decimals(&FeeType::Strk), 6);
+    assert_eq!(block_context.chain_info.fee_token_decimals(&FeeType::Eth), 18);
+
+    let initial_eth_balance =
+        state.get_fee_token_balance(account_address, eth_fee_token_address).unwrap();
+    let initial_strk_balance =
+        state.get_fee_token_balance(account_address, strk_fee_token_address).unwrap();
+
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata: create_trivial_calldata(contract_address),
+            resource_bounds: default_all_resource_bounds,
+            version: TransactionVersion::THREE,
+            nonce: nonce_manager.next(account_address),
+        },
+    )
+    .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    let actual_fee = tx_execution_info.receipt.fee;
+    assert_ne!(actual_fee, Fee(0));
+    assert_eq!(
+        tx_execution_info.fee_transfer_call_info.unwrap().call.storage_address,
+        eth_fee_token_address
+    );
+    assert_eq!(
+        state.get_fee_token_balance(account_address, eth_fee_token_address).unwrap(),
+        (initial_eth_balance.0 - Felt::from(actual_fee.0), initial_eth_balance.1)
+    );
+    assert_eq!(
+        state.get_fee_token_balance(account_address, strk_fee_token_address).unwrap(),
+        initial_strk_balance
+    );
+}
+
+#[rstest]
+fn test_fee_distribution(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let TestInitData { mut state, account_address, contract_address, mut nonce_manager } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo0);
+    let treasury_address = contract_address!("0x1234");
+    let burn_address = contract_address!("0x5678");
+    block_context.chain_info.fee_distribution = FeeDistribution {
+        treasury_address,
+        treasury_percentage: 20,
+        burn_address,
+        burn_percentage: 10,
+    };
+
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata: create_trivial_calldata(contract_address),
+            resource_bounds: default_all_resource_bounds,
+            version: TransactionVersion::THREE,
+            nonce: nonce_manager.next(account_address),
+        },
+    )
+    .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    let actual_fee = tx_execution_info.receipt.fee;
+    let fee_split = tx_execution_info.receipt.fee_split.unwrap();
+    assert_eq!(fee_split, block_context.chain_info.fee_distribution.split(actual_fee));
+    assert_eq!(fee_split.sequencer.0 + fee_split.treasury.0 + fee_split.burned.0, actual_fee.0);
+    assert_eq!(tx_execution_info.receipt.sequencer_fee(), fee_split.sequencer);
+    assert_eq!(tx_execution_info.fee_distribution_call_infos.len(), 2);
+
+    let fee_token_address = block_context.chain_info.fee_token_address(&FeeType::Strk);
+    for (recipient, share) in
+        [(treasury_address, fee_split.treasury), (burn_address, fee_split.burned)]
+    {
+        assert_ne!(share, Fee(0));
+        assert_eq!(
+            state.get_fee_token_balance(recipient, fee_token_address).unwrap(),
+            (Felt::from(share.0), Felt::ZERO)
+        );
+    }
+}
+
+#[rstest]
+fn test_tip(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+    #[values(true, false)] charge_tip: bool,
+) {
+    block_context.versioned_constants.charge_tip = charge_tip;
+    let TestInitData { mut state, account_address, contract_address, mut nonce_manager } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo0);
+    let ValidResourceBounds::AllResources(mut resource_bounds) = default_all_resource_bounds else {
+        panic!("Expected all resource bounds.");
+    };
+    // Leave room for only part of the tip on top of the L2 gas price.
+    let l2_gas_price = block_context.block_info.gas_prices.strk_gas_prices.l2_gas_price.get();
+    resource_bounds.l2_gas.max_price_per_unit = GasPrice(l2_gas_price.0 + 3);
+
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata: create_trivial_calldata(contract_address),
+            resource_bounds: ValidResourceBounds::AllResources(resource_bounds),
+            tip: Tip(5),
+            version: TransactionVersion::THREE,
+            nonce: nonce_manager.next(account_address),
+        },
+    )
+    .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    let receipt = tx_execution_info.receipt;
+    assert_ne!(receipt.gas.l2_gas, GasAmount(0));
+    // Tips are charged only from the Starknet version that introduced them.
+    let expected_tip =
+        if charge_tip { receipt.gas.l2_gas.saturating_mul(GasPrice(3)) } else { Fee(0) };
+    assert_eq!(receipt.tip, expected_tip);
+    let gas_cost = get_fee_by_gas_vector(&block_context.block_info, receipt.gas, &FeeType::Strk);
+    assert_eq!(receipt.fee, Fee(gas_cost.0 + receipt.tip.0));
+}
+
+#[rstest]
+fn test_paymaster(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let paymaster = FeatureContract::Paymaster;
+    let mut state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account, 1), (test_contract, 1), (paymaster, 1)],
+    );
+    let account_address = account.get_instance_address(0);
+    let contract_address = test_contract.get_instance_address(0);
+    let mut nonce_manager = NonceManager::default();
+    // The test contract, which has no `__validate_paymaster__` entry point, acts as paymaster.
+    let paymaster_address = contract_address;
+    let invoke_args = invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_trivial_calldata(contract_address),
+        resource_bounds: default_all_resource_bounds,
+        version: TransactionVersion::THREE,
+        paymaster_data: PaymasterData(vec![*paymaster_address.0.key(), felt!(7_u8)]),
+    };
+
+    // When paymasters are disabled, the sender pays the fee.
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs { nonce: nonce_manager.next(account_address), ..invoke_args.clone() },
+    )
+    .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    assert!(tx_execution_info.validate_paymaster_call_info.is_none());
+    let fee_transfer_call_info = tx_execution_info.fee_transfer_call_info.unwrap();
+    assert_eq!(fee_transfer_call_info.call.caller_address, account_address);
+
+    // The paymaster must cover the resource bounds.
+    block_context.chain_info.paymaster.enabled = true;
+    let tx_result = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs { nonce: nonce_manager.next(account_address), ..invoke_args.clone() },
+    );
+    assert_matches!(
+        tx_result,
+        Err(TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::TransactionFeeError(
+                TransactionFeeError::ResourcesBoundsExceedBalance { .. }
+            )
+        ))
+    );
+    nonce_manager.rollback(account_address);
+
+    // A failing paymaster validation rejects the transaction.
+    let fee_token_address = block_context.chain_info.fee_token_address(&FeeType::Strk);
+    state
+        .set_storage_at(
+            fee_token_address,
+            get_fee_token_var_address(paymaster_address),
+            felt!(BALANCE.0),
+        )
+        .unwrap();
+    let tx_result = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs { nonce: nonce_manager.next(account_address), ..invoke_args.clone() },
+    );
+    assert_matches!(
+        tx_result,
+        Err(TransactionExecutionError::ValidateTransactionError { storage_address, selector, .. })
+            if storage_address == paymaster_address
+            && selector == selector_from_name(VALIDATE_PAYMASTER_ENTRY_POINT_NAME)
+    );
+    nonce_manager.rollback(account_address);
+
+    // A paymaster whose validation passes pays the fee instead of the sender.
+    let sponsor_address = paymaster.get_instance_address(0);
+    let sender_balance_key = get_fee_token_var_address(account_address);
+    let sender_balance = state.get_storage_at(fee_token_address, sender_balance_key).unwrap();
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs {
+            nonce: nonce_manager.next(account_address),
+            paymaster_data: PaymasterData(vec![*sponsor_address.0.key(), felt!(7_u8)]),
+            ..invoke_args.clone()
+        },
+    )
+    .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    let validate_paymaster_call_info = tx_execution_info.validate_paymaster_call_info.unwrap();
+    assert_eq!(validate_paymaster_call_info.call.storage_address, sponsor_address);
+    assert_eq!(validate_paymaster_call_info.call.calldata, calldata![felt!(7_u8)]);
+    let fee_transfer_call_info = tx_execution_info.fee_transfer_call_info.unwrap();
+    assert_eq!(fee_transfer_call_info.call.caller_address, sponsor_address);
+    assert_eq!(
+        state.get_storage_at(fee_token_address, sender_balance_key).unwrap(),
+        sender_balance
+    );
+    let fee = tx_execution_info.receipt.fee;
+    assert_ne!(fee, Fee(0));
+    let sponsor_balance_key = get_fee_token_var_address(sponsor_address);
+    assert_eq!(
+        state.get_storage_at(fee_token_address, sponsor_balance_key).unwrap(),
+        felt!(BALANCE.0 - fee.0)
+    );
+
+    // The paymaster data must start with a contract address.
+    let tx_result = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs {
+            nonce: nonce_manager.next(account_address),
+            paymaster_data: PaymasterData(vec![Felt::MAX]),
+            ..invoke_args
+        },
+    );
+    assert_matches!(
+        tx_result,
+        Err(TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::InvalidPaymasterAddress { paymaster }
+        )) if paymaster == Felt::MAX
+    );
+}
+
+/// The steps of the paymaster validation are deducted from the execution budget, like those of the
+/// account validation, so a sponsored transaction cannot exceed the steps limit.
+#[rstest]
+fn test_paymaster_validation_reduces_execution_steps(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let paymaster = FeatureContract::Paymaster;
+    let mut state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account, 1), (test_contract, 1), (paymaster, 1)],
+    );
+    let account_address = account.get_instance_address(0);
+    let contract_address = test_contract.get_instance_address(0);
+    let mut nonce_manager = NonceManager::default();
+    block_context.chain_info.paymaster.enabled = true;
+    // Limit the number of execution steps (so we quickly hit the limit).
+    block_context.versioned_constants.invoke_tx_max_n_steps = 4200;
+    let invoke_args = invoke_tx_args! {
+        sender_address: account_address,
+        calldata: recursive_function_calldata(&contract_address, 1000, false),
+        resource_bounds: default_all_resource_bounds,
+        version: TransactionVersion::THREE,
+    };
+
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs { nonce: nonce_manager.next(account_address), ..invoke_args.clone() },
+    )
+    .unwrap();
+    assert!(tx_execution_info.is_reverted());
+    let n_steps = tx_execution_info.receipt.resources.computation.total_charged_steps();
+
+    let sponsor_address = paymaster.get_instance_address(0);
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        InvokeTxArgs {
+            nonce: nonce_manager.next(account_address),
+            paymaster_data: PaymasterData(vec![*sponsor_address.0.key()]),
+            ..invoke_args
+        },
+    )
+    .unwrap();
+    assert!(tx_execution_info.is_reverted());
+    let validate_paymaster_steps = tx_execution_info
+        .validate_paymaster_call_info
+        .as_ref()
+        .unwrap()
+        .charged_resources
+        .vm_resources
+        .n_steps;
+    assert!(validate_paymaster_steps > 0);
+    // Both transactions ran out of steps; the sponsored one had fewer steps left for its execution.
+    assert_eq!(tx_execution_info.receipt.resources.computation.total_charged_steps(), n_steps);
+}
+
+#[rstest]
+fn test_sierra_gas_only_mode(
+    mut block_context: BlockContext,
+    default_l1_resource_bounds: ValidResourceBounds,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let cairo1_version = CairoVersion::Cairo1(RunnableCairo1::Casm);
+    let account = FeatureContract::AccountWithoutValidations(cairo1_version);
+    let test_contract = FeatureContract::TestContract(cairo1_version);
+    let cairo0_test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let mut state = test_state_inner(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account, 1), (test_contract, 1), (cairo0_test_contract, 1)],
+        cairo1_version,
+    );
+    let account_address = account.get_instance_address(0);
+    let mut nonce_manager = NonceManager::default();
+
+    block_context.chain_info.sierra_gas_only = true;
+    // Outside of Sierra gas only mode, all classes would run using Cairo steps.
+    block_context.versioned_constants.min_sierra_version_for_sierra_gas =
+        SierraVersion::new(u64::MAX, 0, 0);
+
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata: create_trivial_calldata(test_contract.get_instance_address(0)),
+            resource_bounds: default_all_resource_bounds,
+            version: TransactionVersion::THREE,
+            nonce: nonce_manager.next(account_address),
+        },
+    )
+    .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    for call_info in tx_execution_info.non_optional_call_infos() {
+        assert!(call_info.iter().all(|call| call.tracked_resource == TrackedResource::SierraGas));
+    }
+
+    // Cairo 0 classes may not be executed.
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata: create_trivial_calldata(cairo0_test_contract.get_instance_address(0)),
+            resource_bounds: default_all_resource_bounds,
+            version: TransactionVersion::THREE,
+            nonce: nonce_manager.next(account_address),
+        },
+    )
+    .unwrap();
+    assert!(
+        tx_execution_info
+            .revert_error
+            .unwrap()
+            .to_string()
+            .contains("cannot be executed in Sierra gas only mode")
+    );
+
+    // Transactions must bound L2 gas.
+    let tx_result = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata: create_trivial_calldata(test_contract.get_instance_address(0)),
+            resource_bounds: default_l1_resource_bounds,
+            version: TransactionVersion::THREE,
+            nonce: nonce_manager.next(account_address),
+        },
+    );
+    assert_matches!(
+        tx_result,
+        Err(TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::L2GasBoundsRequired
+        ))
+    );
+}
+
 #[rstest]
 // Try two runs for each recursion type: one short run (success), and one that reverts due to step
 // limit.
@@ -552,6 +1015,7 @@ fn test_max_fee_limit_validate(
                     let tx_context = TransactionContext {
                         block_context: block_context.clone(),
                         tx_info: account_tx.create_tx_info(),
+                        call_counter: Default::default(),
                     };
                     let gas_prices = tx_context.get_gas_prices();
                     l1_resource_bounds(
@@ -1297,7 +1761,10 @@ fn test_insufficient_max_fee_reverts(
     assert!(tx_execution_info3.is_reverted());
     assert_eq!(tx_execution_info3.receipt.da_gas, tx_execution_info1.receipt.da_gas);
     assert_eq!(tx_execution_info3.receipt.fee, tx_execution_info1.receipt.fee);
-    assert!(tx_execution_info3.revert_error.unwrap().to_string().contains("no remaining steps"));
+    assert_eq!(
+        tx_execution_info3.revert_error.unwrap().failure_reason(),
+        Some(&FailureReason::OutOfSteps)
+    );
 }
 
 #[rstest]
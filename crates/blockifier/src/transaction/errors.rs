@@ -6,7 +6,7 @@ use starknet_api::execution_resources::GasAmount;
 use starknet_api::transaction::fields::{AllResourceBounds, Fee, Resource};
 use starknet_api::transaction::TransactionVersion;
 use starknet_api::StarknetApiError;
-use starknet_types_core::felt::FromStrError;
+use starknet_types_core::felt::{Felt, FromStrError};
 use thiserror::Error;
 
 use crate::bouncer::BouncerWeights;
@@ -94,6 +94,8 @@ pub enum TransactionExecutionError {
          {:?}.", **version, allowed_versions.iter().map(|v| **v).collect::<Vec<_>>()
     )]
     InvalidVersion { version: TransactionVersion, allowed_versions: Vec<TransactionVersion> },
+    #[error("The transaction reverted, so its resource bounds cannot be estimated: {0}")]
+    RevertedEstimation(String),
     #[error(transparent)]
     StarknetApiError(#[from] StarknetApiError),
     #[error(transparent)]
@@ -132,6 +134,10 @@ pub enum TransactionPreValidationError {
          {:#064x}; got: {:#064x}.", ***address, **account_nonce, **incoming_tx_nonce
     )]
     InvalidNonce { address: ContractAddress, account_nonce: Nonce, incoming_tx_nonce: Nonce },
+    #[error("Transactions must bound all resources, including L2 gas, in Sierra gas only mode.")]
+    L2GasBoundsRequired,
+    #[error("Invalid paymaster address: {paymaster:#064x}.")]
+    InvalidPaymasterAddress { paymaster: Felt },
     #[error(transparent)]
     StateError(#[from] StateError),
     #[error(transparent)]
@@ -24,6 +24,7 @@ use starknet_api::{felt, invoke_tx_args, nonce};
 use starknet_types_core::felt::Felt;
 
 use crate::context::{BlockContext, ChainInfo};
+use crate::execution::stack_trace::FailureReason;
 use crate::execution::syscalls::SyscallSelector;
 use crate::fee::fee_utils::get_fee_by_gas_vector;
 use crate::state::cached_state::CachedState;
@@ -39,13 +40,21 @@ use crate::test_utils::{
     CairoVersion,
     BALANCE,
 };
-use crate::transaction::account_transaction::{AccountTransaction, ExecutionFlags};
+use crate::transaction::account_transaction::{
+    AccountTransaction,
+    ExecutionFlags,
+    SimulationFlags,
+};
 use crate::transaction::errors::{
     TransactionExecutionError,
     TransactionFeeError,
     TransactionPreValidationError,
 };
-use crate::transaction::objects::{TransactionExecutionInfo, TransactionExecutionResult};
+use crate::transaction::objects::{
+    HasRelatedFeeType,
+    TransactionExecutionInfo,
+    TransactionExecutionResult,
+};
 use crate::transaction::test_utils::{
     default_l1_resource_bounds,
     invoke_tx_with_default_flags,
@@ -240,6 +249,50 @@ fn test_invalid_nonce_pre_validate(
         (account_address, account_nonce, invalid_nonce)
     );
 }
+/// Test that simulating with relaxed checks succeeds where execution fails, without modifying
+/// the state.
+#[rstest]
+fn test_simulation_flags(
+    #[values(true, false)] skip_validate: bool,
+    #[values(TransactionVersion::ONE, TransactionVersion::THREE)] version: TransactionVersion,
+) {
+    let (block_context, mut state, pre_validation_base_args, _) =
+        get_pre_validate_test_args(CairoVersion::Cairo0, version);
+    let account_address = pre_validation_base_args.sender_address;
+    let chain_info = &block_context.chain_info;
+
+    let invalid_nonce = nonce!(7_u8);
+    let account_nonce = state.get_nonce_at(account_address).unwrap();
+    let tx =
+        executable_invoke_tx(invoke_tx_args! {nonce: invalid_nonce, ..pre_validation_base_args});
+    let account_tx = AccountTransaction::new_with_default_flags(tx);
+    let fee_type = account_tx.fee_type();
+    let (initial_balance, _) = state
+        .get_fee_token_balance(account_address, chain_info.fee_token_address(&fee_type))
+        .unwrap();
+
+    // The nonce check is not skipped.
+    let simulation_flags =
+        SimulationFlags { skip_validate, skip_fee_charge: true, skip_nonce_check: false };
+    assert_matches!(
+        account_tx.simulate(&mut state, &block_context, simulation_flags).unwrap_err(),
+        TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::InvalidNonce { .. }
+        )
+    );
+
+    let simulation_flags = SimulationFlags { skip_nonce_check: true, ..simulation_flags };
+    let tx_execution_info =
+        account_tx.simulate(&mut state, &block_context, simulation_flags).unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    assert_eq!(tx_execution_info.validate_call_info.is_none(), skip_validate);
+    assert!(tx_execution_info.fee_transfer_call_info.is_none());
+
+    // The state is left unmodified.
+    assert_eq!(state.get_nonce_at(account_address).unwrap(), account_nonce);
+    check_balance(initial_balance, &state, account_address, chain_info, &fee_type, false);
+}
+
 // Pre-validation scenarios.
 // 1. Not enough resource bounds for minimal fee.
 // 2. Not enough balance for resource bounds.
@@ -717,8 +770,9 @@ fn test_simulate_validate_charge_fee_mid_execution(
         execution_flags: ExecutionFlags { only_query, charge_fee, validate },
     };
     let tx_execution_info = account_tx.execute(&mut state, &low_step_block_context).unwrap();
-    assert!(
-        tx_execution_info.revert_error.clone().unwrap().to_string().contains("no remaining steps")
+    assert_eq!(
+        tx_execution_info.revert_error.as_ref().unwrap().failure_reason(),
+        Some(&FailureReason::OutOfSteps)
     );
     // Complete resources used are reported as receipt.resources; but only the charged
     // final fee is shown in actual_fee. As a sanity check, verify that the fee derived directly
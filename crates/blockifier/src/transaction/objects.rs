@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
-use starknet_api::block::{BlockInfo, FeeType};
+use starknet_api::block::{BlockInfo, FeeType, GasPrice};
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::data_availability::DataAvailabilityMode;
 use starknet_api::execution_resources::GasVector;
@@ -26,10 +26,13 @@ use starknet_api::transaction::{
 
 use crate::abi::constants as abi_constants;
 use crate::execution::call_info::{CallInfo, ExecutionSummary};
-use crate::execution::stack_trace::ErrorStack;
+use crate::execution::execution_quotas::QuotaCallCounts;
+use crate::execution::execution_trace::ExecutionTrace;
+use crate::execution::stack_trace::{ErrorStack, FailureReason};
 use crate::fee::fee_checks::FeeCheckError;
 use crate::fee::fee_utils::get_fee_by_gas_vector;
 use crate::fee::receipt::TransactionReceipt;
+use crate::state::cached_state::StateMaps;
 use crate::transaction::errors::{TransactionExecutionError, TransactionPreValidationError};
 use crate::versioned_constants::VersionedConstants;
 
@@ -100,12 +103,41 @@ impl TransactionInfo {
         }
     }
 
+    /// Returns the part of the fee paid as a tip, for the given gas vector: the effective tip
+    /// is paid per unit of L2 gas, on top of its price. Deprecated transactions pay no tip.
+    pub fn get_tip_by_gas_vector(&self, block_info: &BlockInfo, gas_vector: GasVector) -> Fee {
+        match self {
+            TransactionInfo::Current(context) => {
+                let l2_gas_price =
+                    block_info.gas_prices.gas_price_vector(&self.fee_type()).l2_gas_price.get();
+                let effective_tip =
+                    context.resource_bounds.effective_tip(context.tip, l2_gas_price);
+                gas_vector.l2_gas.saturating_mul(GasPrice(effective_tip.0.into()))
+            }
+            TransactionInfo::Deprecated(_) => Fee(0),
+        }
+    }
+
     pub fn max_fee_for_execution_info_syscall(&self) -> Fee {
         match self {
             Self::Current(_) => Fee(0),
             Self::Deprecated(context) => context.max_fee,
         }
     }
+
+    /// The data availability mode of the transaction's state diff: it is published to an
+    /// alternative data availability layer (L2 mode) only if both the nonce and fee data
+    /// availability modes of the transaction are L2.
+    pub fn state_diff_da_mode(&self) -> DataAvailabilityMode {
+        match self {
+            TransactionInfo::Current(CurrentTransactionInfo {
+                nonce_data_availability_mode: DataAvailabilityMode::L2,
+                fee_data_availability_mode: DataAvailabilityMode::L2,
+                ..
+            }) => DataAvailabilityMode::L2,
+            _ => DataAvailabilityMode::L1,
+        }
+    }
 }
 
 impl HasRelatedFeeType for TransactionInfo {
@@ -178,6 +210,17 @@ pub enum RevertError {
     PostExecution(FeeCheckError),
 }
 
+impl RevertError {
+    /// The root cause of an execution revert; [None] for post-execution reverts, which are
+    /// described by their [FeeCheckError].
+    pub fn failure_reason(&self) -> Option<&FailureReason> {
+        match self {
+            Self::Execution(error_stack) => Some(&error_stack.failure_reason),
+            Self::PostExecution(_) => None,
+        }
+    }
+}
+
 impl From<ErrorStack> for RevertError {
     fn from(stack: ErrorStack) -> Self {
         Self::Execution(stack)
@@ -197,10 +240,15 @@ impl From<FeeCheckError> for RevertError {
 pub struct TransactionExecutionInfo {
     /// Transaction validation call info; [None] for `L1Handler`.
     pub validate_call_info: Option<CallInfo>,
+    /// Paymaster validation call info; [None] unless the transaction is sponsored by a paymaster.
+    pub validate_paymaster_call_info: Option<CallInfo>,
     /// Transaction execution call info; [None] for `Declare`.
     pub execute_call_info: Option<CallInfo>,
     /// Fee transfer call info; [None] for `L1Handler`.
     pub fee_transfer_call_info: Option<CallInfo>,
+    /// The transfers of the fee shares of the treasury and of burning; empty unless a fee
+    /// distribution is configured.
+    pub fee_distribution_call_infos: Vec<CallInfo>,
     pub revert_error: Option<RevertError>,
     /// The receipt of the transaction.
     /// Including the actual fee that was charged (in units of the relevant fee token),
@@ -209,14 +257,25 @@ pub struct TransactionExecutionInfo {
     /// (including L1 gas and additional OS resources estimation),
     /// and total gas consumed.
     pub receipt: TransactionReceipt,
+    /// The values the transaction read from the state, including the previous values of the
+    /// storage cells it wrote; [None] unless read set recording is enabled in the block context.
+    // Storage entries are not valid keys in serialized maps.
+    #[cfg_attr(feature = "transaction_serde", serde(skip))]
+    pub read_set: Option<StateMaps>,
+    /// The calls of the transaction subject to call quotas; added to the calls of the block when
+    /// the transaction is committed.
+    #[cfg_attr(feature = "transaction_serde", serde(skip))]
+    pub quota_call_counts: QuotaCallCounts,
 }
 
 impl TransactionExecutionInfo {
     pub fn non_optional_call_infos(&self) -> impl Iterator<Item = &CallInfo> {
         self.validate_call_info
             .iter()
+            .chain(self.validate_paymaster_call_info.iter())
             .chain(self.execute_call_info.iter())
             .chain(self.fee_transfer_call_info.iter())
+            .chain(self.fee_distribution_call_infos.iter())
     }
 
     pub fn is_reverted(&self) -> bool {
@@ -229,6 +288,14 @@ impl TransactionExecutionInfo {
         CallInfo::summarize_many(self.non_optional_call_infos(), versioned_constants)
     }
 }
+
+/// The result of a traced transaction execution; see `ExecutableTransaction::execute_traced`.
+#[derive(Debug, PartialEq)]
+pub struct TracedExecution {
+    pub execution_info: TransactionExecutionInfo,
+    pub trace: ExecutionTrace,
+}
+
 pub trait ExecutionResourcesTraits {
     fn total_n_steps(&self) -> usize;
     fn prover_builtins(&self) -> HashMap<BuiltinName, usize>;
@@ -0,0 +1,226 @@
+use serde::{Serialize, Serializer};
+use starknet_api::block::{BlockHash, BlockNumber, FeeType};
+use starknet_api::core::ContractAddress;
+use starknet_api::executable_transaction::AccountTransaction as ApiAccountTransaction;
+use starknet_api::execution_resources::GasVector;
+use starknet_api::hash::keccak256;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{
+    Event,
+    L1HandlerTransaction,
+    MessageToL1,
+    RevertedTransactionExecutionStatus,
+    TransactionExecutionStatus,
+    TransactionHash,
+};
+use starknet_types_core::felt::Felt;
+
+use crate::execution::call_info::CallInfo;
+use crate::transaction::objects::{
+    HasRelatedFeeType,
+    TransactionExecutionInfo,
+    TransactionInfoCreator,
+};
+use crate::transaction::transaction_execution::Transaction;
+
+#[cfg(test)]
+#[path = "rpc_receipt_test.rs"]
+mod test;
+
+/// The type of a transaction, as named by the JSON-RPC specification.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RpcTransactionType {
+    Declare,
+    DeployAccount,
+    Invoke,
+    L1Handler,
+}
+
+/// The unit of a fee: Wei for ETH, Fri for STRK.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PriceUnit {
+    Wei,
+    Fri,
+}
+
+impl From<FeeType> for PriceUnit {
+    fn from(fee_type: FeeType) -> Self {
+        match fee_type {
+            FeeType::Eth => PriceUnit::Wei,
+            FeeType::Strk => PriceUnit::Fri,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FeePayment {
+    pub amount: Fee,
+    pub unit: PriceUnit,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub enum TransactionFinalityStatus {
+    #[serde(rename = "ACCEPTED_ON_L2")]
+    #[default]
+    AcceptedOnL2,
+    #[serde(rename = "ACCEPTED_ON_L1")]
+    AcceptedOnL1,
+}
+
+/// The hash of the L1 to L2 message consumed by an L1 handler transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct L1ToL2MessageHash(pub [u8; 32]);
+
+impl L1ToL2MessageHash {
+    /// Computes the Keccak256 hash of the message: its sender, recipient, nonce, selector, payload
+    /// length and payload, each encoded as 32 bytes.
+    pub fn new(tx: &L1HandlerTransaction) -> Self {
+        let (from_address, payload) =
+            tx.calldata.0.split_first().expect("L1 handler calldata must start with the sender.");
+        let encoded_message: Vec<u8> = [
+            *from_address,
+            *tx.contract_address.0.key(),
+            tx.nonce.0,
+            tx.entry_point_selector.0,
+            Felt::from(payload.len()),
+        ]
+        .iter()
+        .chain(payload)
+        .flat_map(Felt::to_bytes_be)
+        .collect();
+
+        Self(keccak256(&encoded_message))
+    }
+}
+
+impl Serialize for L1ToL2MessageHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex: String = self.0.iter().map(|byte| format!("{byte:02x}")).collect();
+        serializer.serialize_str(&format!("0x{hex}"))
+    }
+}
+
+/// The block a receipt belongs to. The hash is [None] while the block is pending, in which case
+/// it is omitted from the receipt.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ReceiptBlockInfo {
+    pub block_number: BlockNumber,
+    pub block_hash: Option<BlockHash>,
+    pub finality_status: TransactionFinalityStatus,
+}
+
+/// A transaction receipt, serialized as the JSON-RPC `TXN_RECEIPT_WITH_BLOCK_INFO` object.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct RpcTransactionReceipt {
+    #[serde(rename = "type")]
+    pub tx_type: RpcTransactionType,
+    pub transaction_hash: TransactionHash,
+    pub actual_fee: FeePayment,
+    pub finality_status: TransactionFinalityStatus,
+    #[serde(flatten)]
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<BlockHash>,
+    pub block_number: BlockNumber,
+    pub messages_sent: Vec<MessageToL1>,
+    pub events: Vec<Event>,
+    pub execution_resources: GasVector,
+    /// The address of the deployed account; only in `DEPLOY_ACCOUNT` receipts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<ContractAddress>,
+    /// The hash of the consumed L1 to L2 message; only in `L1_HANDLER` receipts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_hash: Option<L1ToL2MessageHash>,
+}
+
+impl RpcTransactionReceipt {
+    /// Builds the receipt of the given transaction from the result of its execution in the given
+    /// block.
+    pub fn new(
+        tx: &Transaction,
+        execution_info: &TransactionExecutionInfo,
+        block_info: ReceiptBlockInfo,
+    ) -> Self {
+        let (tx_type, contract_address, message_hash) = match tx {
+            Transaction::Account(account_tx) => match &account_tx.tx {
+                ApiAccountTransaction::Declare(_) => (RpcTransactionType::Declare, None, None),
+                ApiAccountTransaction::DeployAccount(deploy_account_tx) => (
+                    RpcTransactionType::DeployAccount,
+                    Some(deploy_account_tx.contract_address),
+                    None,
+                ),
+                ApiAccountTransaction::Invoke(_) => (RpcTransactionType::Invoke, None, None),
+            },
+            Transaction::L1Handler(l1_handler_tx) => (
+                RpcTransactionType::L1Handler,
+                None,
+                Some(L1ToL2MessageHash::new(&l1_handler_tx.tx)),
+            ),
+        };
+        let execution_status = match &execution_info.revert_error {
+            Some(revert_error) => {
+                TransactionExecutionStatus::Reverted(RevertedTransactionExecutionStatus {
+                    revert_reason: revert_error.to_string(),
+                })
+            }
+            None => TransactionExecutionStatus::Succeeded,
+        };
+        let ReceiptBlockInfo { block_number, block_hash, finality_status } = block_info;
+
+        Self {
+            tx_type,
+            transaction_hash: Transaction::tx_hash(tx),
+            actual_fee: FeePayment {
+                amount: execution_info.receipt.fee,
+                unit: tx.create_tx_info().fee_type().into(),
+            },
+            finality_status,
+            execution_status,
+            block_hash,
+            block_number,
+            messages_sent: execution_info
+                .non_optional_call_infos()
+                .flat_map(ordered_messages)
+                .collect(),
+            events: execution_info.non_optional_call_infos().flat_map(ordered_events).collect(),
+            execution_resources: execution_info.receipt.gas,
+            contract_address,
+            message_hash,
+        }
+    }
+}
+
+/// Returns the events emitted by the given call and its inner calls, in emission order.
+pub(crate) fn ordered_events(call_info: &CallInfo) -> Vec<Event> {
+    sort_by_order(call_info.iter().flat_map(|call| {
+        call.execution.events.iter().map(move |ordered_event| {
+            let event = Event {
+                from_address: call.call.storage_address,
+                content: ordered_event.event.clone(),
+            };
+            (ordered_event.order, event)
+        })
+    }))
+}
+
+/// Returns the messages sent to L1 by the given call and its inner calls, in sending order.
+pub(crate) fn ordered_messages(call_info: &CallInfo) -> Vec<MessageToL1> {
+    sort_by_order(call_info.iter().flat_map(|call| {
+        call.execution.l2_to_l1_messages.iter().map(move |ordered_message| {
+            let message = MessageToL1 {
+                from_address: call.call.storage_address,
+                to_address: ordered_message.message.to_address,
+                payload: ordered_message.message.payload.clone(),
+            };
+            (ordered_message.order, message)
+        })
+    }))
+}
+
+fn sort_by_order<T>(ordered_items: impl Iterator<Item = (usize, T)>) -> Vec<T> {
+    let mut ordered_items: Vec<_> = ordered_items.collect();
+    ordered_items.sort_by_key(|(order, _)| *order);
+    ordered_items.into_iter().map(|(_, item)| item).collect()
+}
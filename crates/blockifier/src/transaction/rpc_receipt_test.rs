@@ -0,0 +1,198 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ContractAddress, EntryPointSelector, EthAddress};
+use starknet_api::execution_resources::{GasAmount, GasVector};
+use starknet_api::test_utils::l1_handler::{executable_l1_handler_tx, L1HandlerTxArgs};
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{
+    EventContent,
+    EventData,
+    L2ToL1Payload,
+    TransactionHash,
+    TransactionVersion,
+};
+use starknet_api::{calldata, contract_address, felt, invoke_tx_args, nonce};
+use starknet_types_core::felt::Felt;
+
+use crate::execution::call_info::{
+    CallExecution,
+    CallInfo,
+    MessageToL1 as BlockifierMessageToL1,
+    OrderedEvent,
+    OrderedL2ToL1Message,
+};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::fee::fee_checks::FeeCheckError;
+use crate::fee::receipt::TransactionReceipt;
+use crate::transaction::objects::{RevertError, TransactionExecutionInfo};
+use crate::transaction::rpc_receipt::{
+    FeePayment,
+    PriceUnit,
+    ReceiptBlockInfo,
+    RpcTransactionReceipt,
+};
+use crate::transaction::test_utils::invoke_tx_with_default_flags;
+use crate::transaction::transaction_execution::Transaction;
+
+/// A call that emits an event and sends a message at each of the given orders, whose data and
+/// payload are the order itself.
+fn call_info(
+    storage_address: ContractAddress,
+    event_orders: &[usize],
+    message_orders: &[usize],
+    inner_calls: Vec<CallInfo>,
+) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint { storage_address, ..Default::default() },
+        execution: CallExecution {
+            events: event_orders
+                .iter()
+                .map(|&order| OrderedEvent {
+                    order,
+                    event: EventContent { keys: vec![], data: EventData(vec![Felt::from(order)]) },
+                })
+                .collect(),
+            l2_to_l1_messages: message_orders
+                .iter()
+                .map(|&order| OrderedL2ToL1Message {
+                    order,
+                    message: BlockifierMessageToL1 {
+                        to_address: EthAddress::default(),
+                        payload: L2ToL1Payload(vec![Felt::from(order)]),
+                    },
+                })
+                .collect(),
+            ..Default::default()
+        },
+        inner_calls,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_invoke_receipt() {
+    let tx = Transaction::Account(invoke_tx_with_default_flags(invoke_tx_args! {
+        version: TransactionVersion::THREE,
+        tx_hash: TransactionHash(felt!("0x1234")),
+    }));
+    let validating_account = contract_address!("0x100");
+    let caller = contract_address!("0x200");
+    let callee = contract_address!("0x300");
+    // The callee emits the second event and sends the first message of the execution.
+    let execute_call_info =
+        call_info(caller, &[0, 2], &[1], vec![call_info(callee, &[1], &[0], vec![])]);
+    let execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info(validating_account, &[0], &[], vec![])),
+        execute_call_info: Some(execute_call_info),
+        receipt: TransactionReceipt {
+            fee: Fee(10),
+            gas: GasVector {
+                l1_gas: GasAmount(1),
+                l1_data_gas: GasAmount(2),
+                l2_gas: GasAmount(3),
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let block_info = ReceiptBlockInfo {
+        block_number: BlockNumber(7),
+        block_hash: Some(BlockHash(felt!("0x99"))),
+        ..Default::default()
+    };
+
+    let receipt = RpcTransactionReceipt::new(&tx, &execution_info, block_info);
+
+    let events: Vec<_> =
+        receipt.events.iter().map(|event| (event.from_address, event.content.data.0[0])).collect();
+    assert_eq!(
+        events,
+        vec![
+            (validating_account, Felt::ZERO),
+            (caller, Felt::ZERO),
+            (callee, Felt::ONE),
+            (caller, Felt::TWO),
+        ]
+    );
+    let messages: Vec<_> = receipt
+        .messages_sent
+        .iter()
+        .map(|message| (message.from_address, message.payload.0[0]))
+        .collect();
+    assert_eq!(messages, vec![(callee, Felt::ZERO), (caller, Felt::ONE)]);
+    assert_eq!(receipt.actual_fee, FeePayment { amount: Fee(10), unit: PriceUnit::Fri });
+
+    let serialized_receipt = serde_json::to_value(&receipt).unwrap();
+    assert_eq!(serialized_receipt["type"], "INVOKE");
+    assert_eq!(serialized_receipt["transaction_hash"], "0x1234");
+    assert_eq!(serialized_receipt["actual_fee"], json!({"amount": "0xa", "unit": "FRI"}));
+    assert_eq!(serialized_receipt["finality_status"], "ACCEPTED_ON_L2");
+    assert_eq!(serialized_receipt["execution_status"], "SUCCEEDED");
+    assert_eq!(serialized_receipt["block_hash"], "0x99");
+    assert_eq!(serialized_receipt["block_number"], 7);
+    assert_eq!(
+        serialized_receipt["execution_resources"],
+        json!({"l1_gas": 1, "l1_data_gas": 2, "l2_gas": 3})
+    );
+    for absent_field in ["revert_reason", "contract_address", "message_hash"] {
+        assert!(serialized_receipt.get(absent_field).is_none());
+    }
+}
+
+#[test]
+fn test_reverted_pending_receipt() {
+    let tx = Transaction::Account(invoke_tx_with_default_flags(invoke_tx_args! {
+        version: TransactionVersion::ONE,
+    }));
+    let revert_error = RevertError::PostExecution(FeeCheckError::MaxFeeExceeded {
+        max_fee: Fee(100),
+        actual_fee: Fee(101),
+    });
+    let expected_revert_reason = revert_error.to_string();
+    let execution_info =
+        TransactionExecutionInfo { revert_error: Some(revert_error), ..Default::default() };
+
+    let receipt = RpcTransactionReceipt::new(&tx, &execution_info, ReceiptBlockInfo::default());
+
+    let serialized_receipt = serde_json::to_value(&receipt).unwrap();
+    assert_eq!(serialized_receipt["execution_status"], "REVERTED");
+    assert_eq!(serialized_receipt["revert_reason"], expected_revert_reason);
+    assert_eq!(serialized_receipt["actual_fee"]["unit"], "WEI");
+    assert!(serialized_receipt.get("block_hash").is_none());
+}
+
+#[test]
+fn test_l1_handler_message_hash() {
+    let tx = executable_l1_handler_tx(L1HandlerTxArgs {
+        nonce: nonce!(0x18e94d),
+        contract_address: contract_address!(
+            "0x73314940630fd6dcda0d772d4c972c4e0a9946bef9dabf4ef84eda8ef542b82"
+        ),
+        entry_point_selector: EntryPointSelector(felt!(
+            "0x1b64b1b3b690b43b9b514fb81377518f4039cd3e4f4914d8a6bdf01d679fb19"
+        )),
+        calldata: calldata![
+            felt!("0xae0ee0a63a2ce6baeeffe56e7714fb4efe48d419"),
+            felt!("0x455448"),
+            felt!("0xc27947400e26e534e677afc2e9b2ec1bab14fc89"),
+            felt!("0x4af4754baf89f1b8b449215a8ea7ce558824a33a5393eaa3829658549f2bfa2"),
+            felt!("0x9184e72a000"),
+            felt!("0x0")
+        ],
+        ..Default::default()
+    });
+
+    let receipt = RpcTransactionReceipt::new(
+        &Transaction::L1Handler(tx),
+        &TransactionExecutionInfo::default(),
+        ReceiptBlockInfo::default(),
+    );
+
+    let serialized_receipt = serde_json::to_value(&receipt).unwrap();
+    assert_eq!(serialized_receipt["type"], "L1_HANDLER");
+    assert_eq!(
+        serialized_receipt["message_hash"],
+        "0x99b2a7830e1c860734b308d90bb05b0e09ecda0a2b243ecddb12c50bdebaa3a9"
+    );
+}
@@ -150,9 +150,11 @@ impl<U: UpdatableState> ExecutableTransaction<U> for L1HandlerTransaction {
         let l1_handler_payload_size = self.payload_size();
         let TransactionReceipt {
             fee: actual_fee,
+            tip: _,
             da_gas,
             resources: actual_resources,
             gas: total_gas,
+            fee_split: _,
         } = TransactionReceipt::from_l1_handler(
             &tx_context,
             l1_handler_payload_size,
@@ -161,23 +163,36 @@ impl<U: UpdatableState> ExecutableTransaction<U> for L1HandlerTransaction {
         );
 
         let paid_fee = self.paid_fee_on_l1;
-        // For now, assert only that any amount of fee was paid.
+        // Unless enforced, assert only that any amount of fee was paid.
         // The error message still indicates the required fee.
-        if paid_fee == Fee(0) {
+        let insufficient_fee = if block_context.enforces_l1_handler_fee() {
+            paid_fee < actual_fee
+        } else {
+            paid_fee == Fee(0)
+        };
+        if insufficient_fee {
             return Err(TransactionFeeError::InsufficientFee { paid_fee, actual_fee })?;
         }
+        let read_set =
+            if block_context.records_read_sets() { Some(state.read_set()?) } else { None };
 
         Ok(TransactionExecutionInfo {
             validate_call_info: None,
+            validate_paymaster_call_info: None,
             execute_call_info,
             fee_transfer_call_info: None,
+            fee_distribution_call_infos: vec![],
             receipt: TransactionReceipt {
                 fee: Fee::default(),
+                tip: Fee::default(),
                 da_gas,
                 resources: actual_resources,
                 gas: total_gas,
+                fee_split: None,
             },
             revert_error: None,
+            read_set,
+            quota_call_counts: tx_context.call_counter.snapshot(),
         })
     }
 }
@@ -214,6 +229,7 @@ impl<U: UpdatableState> ExecutableTransaction<U> for Transaction {
             &tx_execution_info.receipt.resources,
             &tx_state_changes_keys,
             &block_context.bouncer_config,
+            self.into(),
         )?;
 
         Ok(tx_execution_info)
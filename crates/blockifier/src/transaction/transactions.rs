@@ -31,6 +31,7 @@ use crate::execution::entry_point::{
     ConstructorContext,
     EntryPointExecutionContext,
 };
+use crate::execution::execution_trace::ExecutionTracer;
 use crate::execution::execution_utils::execute_deployment;
 use crate::state::cached_state::TransactionalState;
 use crate::state::errors::StateError;
@@ -41,6 +42,7 @@ use crate::transaction::objects::{
     CurrentTransactionInfo,
     DeprecatedTransactionInfo,
     HasRelatedFeeType,
+    TracedExecution,
     TransactionExecutionInfo,
     TransactionExecutionResult,
     TransactionInfo,
@@ -84,6 +86,20 @@ pub trait ExecutableTransaction<U: UpdatableState>: Sized {
         }
     }
 
+    /// Executes the transaction like `execute`, additionally recording a step-by-step trace of
+    /// the VM execution and syscalls of all entry points it runs.
+    fn execute_traced(
+        &self,
+        state: &mut U,
+        block_context: &BlockContext,
+    ) -> TransactionExecutionResult<TracedExecution> {
+        let execution_tracer = ExecutionTracer::default();
+        let block_context = block_context.clone().with_execution_tracer(execution_tracer.clone());
+        let execution_info = self.execute(state, &block_context)?;
+
+        Ok(TracedExecution { execution_info, trace: execution_tracer.take_trace() })
+    }
+
     /// Note: In case of execution failure, the state may become corrupted. This means that
     /// any changes made up to the point of failure will persist in the state. To revert these
     /// changes, you should call `state.abort()`. Alternatively, consider using `execute`
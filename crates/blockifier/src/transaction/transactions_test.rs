@@ -66,6 +66,7 @@ use starknet_api::{
     felt,
     invoke_tx_args,
     nonce,
+    storage_key,
 };
 use starknet_types_core::felt::Felt;
 
@@ -83,13 +84,16 @@ use crate::execution::call_info::{
 use crate::execution::contract_class::TrackedResource;
 use crate::execution::entry_point::{CallEntryPoint, CallType};
 use crate::execution::errors::{ConstructorEntryPointExecutionError, EntryPointExecutionError};
+use crate::execution::execution_quotas::QuotaCallCounts;
 use crate::execution::syscalls::hint_processor::EmitEventError;
 use crate::execution::syscalls::SyscallSelector;
 use crate::fee::fee_utils::{balance_to_big_uint, get_fee_by_gas_vector};
 use crate::fee::gas_usage::{
     estimate_minimal_gas_vector,
+    estimate_resource_bounds,
     get_da_gas_cost,
     get_onchain_data_segment_length,
+    ResourceBoundsMargin,
 };
 use crate::fee::receipt::TransactionReceipt;
 use crate::fee::resources::{
@@ -650,15 +654,21 @@ fn test_invoke_tx(
 
     let expected_execution_info = TransactionExecutionInfo {
         validate_call_info: expected_validate_call_info,
+        validate_paymaster_call_info: None,
         execute_call_info: expected_execute_call_info,
         fee_transfer_call_info: expected_fee_transfer_call_info,
+        fee_distribution_call_infos: vec![],
         receipt: TransactionReceipt {
             fee: expected_actual_fee,
+            tip: Fee(0),
             da_gas,
             resources: expected_actual_resources,
             gas: total_gas,
+            fee_split: None,
         },
         revert_error: None,
+        read_set: None,
+        quota_call_counts: QuotaCallCounts::default(),
     };
 
     // Test execution info result.
@@ -1669,15 +1679,21 @@ fn test_declare_tx(
 
     let expected_execution_info = TransactionExecutionInfo {
         validate_call_info: expected_validate_call_info,
+        validate_paymaster_call_info: None,
         execute_call_info: None,
         fee_transfer_call_info: expected_fee_transfer_call_info,
+        fee_distribution_call_infos: vec![],
         receipt: TransactionReceipt {
             fee: expected_actual_fee,
+            tip: Fee(0),
             da_gas,
             resources: expected_actual_resources,
             gas: expected_total_gas,
+            fee_split: None,
         },
         revert_error: None,
+        read_set: None,
+        quota_call_counts: QuotaCallCounts::default(),
     };
 
     // Test execution info result.
@@ -1895,15 +1911,21 @@ fn test_deploy_account_tx(
 
     let expected_execution_info = TransactionExecutionInfo {
         validate_call_info: expected_validate_call_info,
+        validate_paymaster_call_info: None,
         execute_call_info: expected_execute_call_info,
         fee_transfer_call_info: expected_fee_transfer_call_info,
+        fee_distribution_call_infos: vec![],
         receipt: TransactionReceipt {
             fee: expected_actual_fee,
+            tip: Fee(0),
             da_gas,
             resources: actual_resources,
             gas: expected_total_gas,
+            fee_split: None,
         },
         revert_error: None,
+        read_set: None,
+        quota_call_counts: QuotaCallCounts::default(),
     };
 
     // Test execution info result.
@@ -2442,15 +2464,21 @@ fn test_l1_handler(#[values(false, true)] use_kzg_da: bool) {
     // Build the expected execution info.
     let expected_execution_info = TransactionExecutionInfo {
         validate_call_info: None,
+        validate_paymaster_call_info: None,
         execute_call_info: Some(expected_call_info),
         fee_transfer_call_info: None,
+        fee_distribution_call_infos: vec![],
         receipt: TransactionReceipt {
             fee: Fee(0),
+            tip: Fee(0),
             da_gas: expected_da_gas,
             resources: expected_tx_resources,
             gas: total_gas,
+            fee_split: None,
         },
         revert_error: None,
+        read_set: None,
+        quota_call_counts: QuotaCallCounts::default(),
     };
 
     // Check the actual returned execution info.
@@ -2482,6 +2510,35 @@ fn test_l1_handler(#[values(false, true)] use_kzg_da: bool) {
     );
 }
 
+#[rstest]
+fn test_l1_handler_fee_enforcement(#[values(false, true)] enforce_l1_handler_fee: bool) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let chain_info = &ChainInfo::create_for_testing();
+    let state = &mut test_state(chain_info, BALANCE, &[(test_contract, 1)]);
+    let mut block_context = BlockContext::create_for_account_testing();
+    if enforce_l1_handler_fee {
+        block_context = block_context.with_l1_handler_fee_enforcement();
+    }
+    let contract_address = test_contract.get_instance_address(0);
+
+    // A positive fee lower than the actual fee is accepted, unless the fee is enforced.
+    let result = l1handler_tx(Fee(1), contract_address).execute(state, &block_context);
+    if enforce_l1_handler_fee {
+        assert_matches!(
+            result.unwrap_err(),
+            TransactionExecutionError::TransactionFeeError(TransactionFeeError::InsufficientFee {
+                paid_fee: Fee(1),
+                ..
+            })
+        );
+    } else {
+        result.unwrap();
+    }
+
+    // A fee of 1 ETH covers the actual fee.
+    l1handler_tx(Fee(10_u128.pow(18)), contract_address).execute(state, &block_context).unwrap();
+}
+
 #[rstest]
 fn test_execute_tx_with_invalid_tx_version(
     block_context: BlockContext,
@@ -2835,3 +2892,108 @@ fn test_deploy_max_sierra_gas_validate_execute(
     };
     assert_eq!(actual_validate_initial_gas, expected_validate_initial_gas);
 }
+
+#[rstest]
+fn test_read_set_recording(default_all_resource_bounds: ValidResourceBounds) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let account_contract =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let block_context = BlockContext::create_for_testing();
+    let chain_info = &block_context.chain_info;
+    let state = &mut test_state(chain_info, BALANCE, &[(test_contract, 1), (account_contract, 1)]);
+    let sender_address = account_contract.get_instance_address(0);
+    let contract_address = test_contract.get_instance_address(0);
+    let mut nonce_manager = NonceManager::default();
+    let mut invoke_tx = || {
+        invoke_tx_with_default_flags(invoke_tx_args! {
+            sender_address,
+            calldata: create_calldata(
+                contract_address,
+                "test_storage_read_write",
+                &[felt!(1_u8), felt!(2_u8)],
+            ),
+            resource_bounds: default_all_resource_bounds,
+            nonce: nonce_manager.next(sender_address),
+        })
+    };
+
+    let tx_execution_info = invoke_tx().execute(state, &block_context).unwrap();
+    assert_eq!(tx_execution_info.read_set, None);
+
+    let block_context = block_context.clone().with_read_set_recording();
+    let tx_execution_info = invoke_tx().execute(state, &block_context).unwrap();
+    let read_set = tx_execution_info.read_set.unwrap();
+    assert_eq!(read_set.nonces[&sender_address], nonce!(1_u8));
+    assert_eq!(read_set.class_hashes[&contract_address], test_contract.get_class_hash());
+    // Written by the previous transaction.
+    assert_eq!(read_set.storage[&(contract_address, storage_key!(1_u8))], felt!(2_u8));
+    let sender_balance_key = get_fee_token_var_address(sender_address);
+    let fee_token_address = chain_info.fee_token_address(&FeeType::Strk);
+    assert!(read_set.storage.contains_key(&(fee_token_address, sender_balance_key)));
+}
+
+#[rstest]
+fn test_estimate_resource_bounds(default_all_resource_bounds: ValidResourceBounds) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let account_contract =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let block_context = BlockContext::create_for_testing();
+    let state = &mut test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(test_contract, 1), (account_contract, 1)],
+    );
+    let sender_address = account_contract.get_instance_address(0);
+    let invoke_tx = |resource_bounds| {
+        invoke_tx_with_default_flags(invoke_tx_args! {
+            sender_address,
+            calldata: create_calldata(
+                test_contract.get_instance_address(0),
+                "test_storage_read_write",
+                &[felt!(1_u8), felt!(2_u8)],
+            ),
+            resource_bounds,
+            nonce: nonce!(0_u8),
+        })
+    };
+
+    let estimate = |state: &mut CachedState<_>, margin| {
+        estimate_resource_bounds(
+            state,
+            &block_context,
+            &invoke_tx(default_all_resource_bounds),
+            margin,
+        )
+    };
+    let no_margin = ResourceBoundsMargin { amount_percentage: 0, price_percentage: 0 };
+    let exact_resource_bounds = estimate(state, no_margin).unwrap();
+    let resource_bounds = estimate(state, ResourceBoundsMargin::default()).unwrap();
+    let gas_prices = block_context.block_info.gas_prices.gas_price_vector(&FeeType::Strk);
+    assert_eq!(exact_resource_bounds.l2_gas.max_price_per_unit, gas_prices.l2_gas_price.get());
+    assert_eq!(
+        resource_bounds.l2_gas.max_price_per_unit.0,
+        (gas_prices.l2_gas_price.get().0 * 150).div_ceil(100)
+    );
+    assert_eq!(
+        resource_bounds.l2_gas.max_amount.0,
+        (exact_resource_bounds.l2_gas.max_amount.0 * 110).div_ceil(100)
+    );
+
+    // The estimate is the gas the transaction uses, and suffices to execute it.
+    let tx_execution_info = invoke_tx(ValidResourceBounds::AllResources(resource_bounds))
+        .execute(state, &block_context)
+        .unwrap();
+    assert!(!tx_execution_info.is_reverted());
+    let gas = tx_execution_info.receipt.gas;
+    assert_eq!(gas.l2_gas, exact_resource_bounds.l2_gas.max_amount);
+    assert!(gas.l1_gas <= resource_bounds.l1_gas.max_amount);
+    assert!(gas.l1_data_gas <= resource_bounds.l1_data_gas.max_amount);
+
+    // The transaction was executed, so its nonce is no longer valid.
+    assert_matches!(
+        estimate(state, no_margin),
+        Err(TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::InvalidNonce { .. }
+        ))
+    );
+}
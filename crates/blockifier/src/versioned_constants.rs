@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, LazyLock};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::{fs, io};
 
 use cairo_vm::types::builtin_name::BuiltinName;
@@ -9,14 +9,20 @@ use indexmap::{IndexMap, IndexSet};
 use infra_utils::compile_time_cargo_manifest_dir;
 use num_rational::Ratio;
 use num_traits::Inv;
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use paste::paste;
 use semver::Version;
 use serde::de::Error as DeserializationError;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Number, Value};
-use starknet_api::block::{GasPrice, StarknetVersion};
+use starknet_api::block::{
+    BlockNumber,
+    BlockTimestamp,
+    GasPrice,
+    StarknetVersion,
+    StarknetVersionSchedule,
+};
 use starknet_api::contract_class::SierraVersion;
 use starknet_api::core::ContractAddress;
 use starknet_api::execution_resources::{GasAmount, GasVector};
@@ -26,6 +32,7 @@ use thiserror::Error;
 
 use crate::execution::common_hints::ExecutionMode;
 use crate::execution::deprecated_syscalls::hint_processor::SyscallCounter;
+use crate::execution::execution_quotas::ExecutionQuotas;
 use crate::execution::execution_utils::poseidon_hash_many_cost;
 use crate::execution::syscalls::SyscallSelector;
 use crate::fee::resources::StarknetResources;
@@ -118,6 +125,7 @@ define_versioned_constants! {
     (V0_13_2_1, "../resources/versioned_constants_0_13_2_1.json"),
     (V0_13_3, "../resources/versioned_constants_0_13_3.json"),
     (V0_13_4, "../resources/versioned_constants_0_13_4.json"),
+    (V0_13_5, "../resources/versioned_constants_0_13_5.json"),
 }
 
 pub type ResourceCost = Ratio<u64>;
@@ -191,12 +199,27 @@ pub struct VersionedConstants {
     // multiplied by 3. This offsets a bug in the old vm where the counter counted the number of
     // cells used by instances of the builtin, instead of the number of instances.
     pub segment_arena_cells: bool,
+    // Restrictions of the syscalls and builtins of each execution phase; unrestricted if absent.
+    #[serde(default)]
+    pub validate_phase_restrictions: ExecutionPhaseRestrictions,
+    #[serde(default)]
+    pub execute_phase_restrictions: ExecutionPhaseRestrictions,
+    // Operator quotas of specific contracts; not part of the versioned constants files.
+    #[serde(default)]
+    pub execution_quotas: ExecutionQuotas,
 
     // Transactions settings.
     pub disable_cairo0_redeclaration: bool,
     pub enable_stateful_compression: bool,
     pub comprehensive_state_diff: bool,
     pub ignore_inner_event_resources: bool,
+    // If true, an entry point execution that uses builtins not declared by its compiled class
+    // fails, instead of producing an unprovable block.
+    #[serde(default)]
+    pub validate_builtin_usage: bool,
+    // If true, transactions pay their effective tip per unit of L2 gas, on top of its price.
+    #[serde(default)]
+    pub charge_tip: bool,
 
     // Compiler settings.
     pub enable_reverts: bool,
@@ -224,6 +247,41 @@ impl VersionedConstants {
         Ok(serde_json::from_reader(std::fs::File::open(path)?)?)
     }
 
+    /// Loads and validates the versioned constants of the given file, once per process; later
+    /// calls with the same path return the constants loaded by the first.
+    pub fn from_custom_path(path: &Path) -> VersionedConstantsResult<Self> {
+        static CUSTOM_VERSIONED_CONSTANTS: LazyLock<Mutex<HashMap<PathBuf, VersionedConstants>>> =
+            LazyLock::new(Default::default);
+
+        let mut custom_versioned_constants =
+            CUSTOM_VERSIONED_CONSTANTS.lock().expect("Custom versioned constants lock failed.");
+        if let Some(versioned_constants) = custom_versioned_constants.get(path) {
+            return Ok(versioned_constants.clone());
+        }
+        let versioned_constants = Self::from_path(path)?;
+        versioned_constants.validate()?;
+        custom_versioned_constants.insert(path.to_path_buf(), versioned_constants.clone());
+        Ok(versioned_constants)
+    }
+
+    /// Checks that the limits allow executing transactions at all.
+    pub fn validate(&self) -> VersionedConstantsResult<()> {
+        let zero_limits: Vec<&str> = [
+            ("invoke_tx_max_n_steps", self.invoke_tx_max_n_steps == 0),
+            ("validate_max_n_steps", self.validate_max_n_steps == 0),
+            ("max_recursion_depth", self.max_recursion_depth == 0),
+            ("execute_max_sierra_gas", self.execute_max_sierra_gas == GasAmount::ZERO),
+            ("validate_max_sierra_gas", self.validate_max_sierra_gas == GasAmount::ZERO),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_zero)| is_zero.then_some(name))
+        .collect();
+        if !zero_limits.is_empty() {
+            return Err(VersionedConstantsError::InvalidLimits(zero_limits.join(", ")));
+        }
+        Ok(())
+    }
+
     /// Converts from L1 gas price to L2 gas price with **upward rounding**, based on the
     /// conversion of a Cairo step from Sierra gas to L1 gas.
     pub fn convert_l1_to_l2_gas_price_round_up(&self, l1_gas_price: GasPrice) -> GasPrice {
@@ -265,6 +323,19 @@ impl VersionedConstants {
         }
     }
 
+    /// Returns the limits and restrictions of the given execution phase.
+    pub fn execution_phase_config(&self, mode: &ExecutionMode) -> ExecutionPhaseConfig {
+        let (max_n_steps, restrictions) = match mode {
+            ExecutionMode::Validate => {
+                (self.validate_max_n_steps, &self.validate_phase_restrictions)
+            }
+            ExecutionMode::Execute => {
+                (self.invoke_tx_max_n_steps, &self.execute_phase_restrictions)
+            }
+        };
+        ExecutionPhaseConfig { max_n_steps, restrictions: restrictions.clone() }
+    }
+
     /// Returns the default initial gas for VM mode transactions.
     pub fn infinite_gas_for_vm_mode(&self) -> u64 {
         self.os_constants.gas_costs.base.default_initial_gas_cost
@@ -345,20 +416,53 @@ impl VersionedConstants {
 
     // TODO(Arni): Consider replacing each call to this function with `latest_with_overrides`, and
     // squashing the functions together.
-    /// Returns the latest versioned constants, applying the given overrides.
+    /// Returns the latest versioned constants, applying the given overrides. If the overrides name
+    /// a versioned constants file, it replaces the latest constants; the file is expected to have
+    /// been validated on startup, see [`VersionedConstantsOverrides::validate`].
     pub fn get_versioned_constants(
         versioned_constants_overrides: VersionedConstantsOverrides,
+    ) -> Self {
+        let versioned_constants = match &versioned_constants_overrides.versioned_constants_path {
+            Some(path) => Self::from_custom_path(path)
+                .unwrap_or_else(|error| panic!("Invalid versioned constants file: {error}")),
+            None => Self::latest_constants().clone(),
+        };
+        versioned_constants.with_overrides(versioned_constants_overrides)
+    }
+
+    /// Returns the versioned constants of the Starknet version scheduled for the block with the
+    /// given height and timestamp, applying the given overrides. If the overrides name a versioned
+    /// constants file, it replaces the constants of every version.
+    pub fn get_scheduled_versioned_constants(
+        starknet_version_schedule: &StarknetVersionSchedule,
+        block_number: BlockNumber,
+        block_timestamp: BlockTimestamp,
+        versioned_constants_overrides: VersionedConstantsOverrides,
+    ) -> VersionedConstantsResult<Self> {
+        if let Some(path) = &versioned_constants_overrides.versioned_constants_path {
+            return Ok(Self::from_custom_path(path)?.with_overrides(versioned_constants_overrides));
+        }
+        let starknet_version = starknet_version_schedule.version_at(block_number, block_timestamp);
+        Ok(Self::get(&starknet_version)?.clone().with_overrides(versioned_constants_overrides))
+    }
+
+    pub(crate) fn with_overrides(
+        self,
+        versioned_constants_overrides: VersionedConstantsOverrides,
     ) -> Self {
         let VersionedConstantsOverrides {
             validate_max_n_steps,
             max_recursion_depth,
             invoke_tx_max_n_steps,
+            execution_quotas,
+            versioned_constants_path: _,
         } = versioned_constants_overrides;
         Self {
             validate_max_n_steps,
             max_recursion_depth,
             invoke_tx_max_n_steps,
-            ..Self::latest_constants().clone()
+            execution_quotas,
+            ..self
         }
     }
 
@@ -415,6 +519,64 @@ pub struct ArchivalDataGasCosts {
     pub gas_per_code_byte: ResourceCost,
 }
 
+/// Restricts the syscalls and builtins available to the contracts run in an execution phase.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionPhaseRestrictions {
+    // If set, other syscalls fail.
+    #[serde(default)]
+    pub allowed_syscalls: Option<HashSet<SyscallSelector>>,
+    // If set, entry points using other builtins fail to run.
+    #[serde(default, deserialize_with = "optional_builtin_set_from_string_set")]
+    pub allowed_builtins: Option<HashSet<BuiltinName>>,
+}
+
+impl ExecutionPhaseRestrictions {
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed_syscalls.is_none() && self.allowed_builtins.is_none()
+    }
+}
+
+fn optional_builtin_set_from_string_set<'de, D: Deserializer<'de>>(
+    d: D,
+) -> Result<Option<HashSet<BuiltinName>>, D::Error> {
+    let Some(builtin_names) = Option::<HashSet<String>>::deserialize(d)? else {
+        return Ok(None);
+    };
+    builtin_names
+        .iter()
+        .map(|name| BuiltinName::from_str(name))
+        .collect::<Option<HashSet<_>>>()
+        .map(Some)
+        .ok_or(D::Error::custom("Invalid builtin name"))
+}
+
+/// The limits and restrictions of an execution phase, i.e., of the validation or the execution of
+/// a transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionPhaseConfig {
+    pub max_n_steps: u32,
+    pub restrictions: ExecutionPhaseRestrictions,
+}
+
+impl ExecutionPhaseConfig {
+    pub fn is_syscall_allowed(&self, selector: SyscallSelector) -> bool {
+        self.restrictions
+            .allowed_syscalls
+            .as_ref()
+            .is_none_or(|allowed_syscalls| allowed_syscalls.contains(&selector))
+    }
+
+    /// Returns the first of the given builtins not allowed in the phase, if any.
+    pub fn find_disallowed_builtin<'a>(
+        &self,
+        builtins: impl IntoIterator<Item = &'a BuiltinName>,
+    ) -> Option<BuiltinName> {
+        let allowed_builtins = self.restrictions.allowed_builtins.as_ref()?;
+        builtins.into_iter().find(|builtin| !allowed_builtins.contains(builtin)).copied()
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
 pub struct EventLimits {
     pub max_data_length: usize,
@@ -774,10 +936,15 @@ impl TryFrom<&OsConstantsRawJson> for GasCosts {
     fn try_from(raw_json_data: &OsConstantsRawJson) -> Result<Self, Self::Error> {
         let base_value: Value = serde_json::to_value(&raw_json_data.parse_base()?)?;
         let base: BaseGasCosts = serde_json::from_value(base_value)?;
-        let builtins_value: Value = serde_json::to_value(&raw_json_data.parse_builtin()?)?;
+        let mut builtins_map = raw_json_data.parse_builtin()?;
+        raw_json_data.gas_cost_overrides.apply_to_builtins(&mut builtins_map)?;
+        let builtins_value: Value = serde_json::to_value(&builtins_map)?;
         let builtins: BuiltinGasCosts = serde_json::from_value(builtins_value)?;
-        let syscalls_value: Value =
-            serde_json::to_value(&raw_json_data.parse_syscalls(&base, &builtins)?)?;
+        // Syscall costs are derived from the (possibly overridden) builtin costs before their own
+        // overrides are applied.
+        let mut syscalls_map = raw_json_data.parse_syscalls(&base, &builtins)?;
+        raw_json_data.gas_cost_overrides.apply_to_syscalls(&mut syscalls_map, &base)?;
+        let syscalls_value: Value = serde_json::to_value(&syscalls_map)?;
         let syscalls: SyscallGasCosts = serde_json::from_value(syscalls_value)?;
         Ok(GasCosts { base, builtins, syscalls })
     }
@@ -800,6 +967,9 @@ pub struct OsContractAddresses {
     block_hash_contract_address: u8,
     alias_contract_address: u8,
     reserved_contract_address: u8,
+    // Defined from the Starknet version that introduced the block metadata system contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    block_metadata_contract_address: Option<u8>,
 }
 
 impl OsContractAddresses {
@@ -814,6 +984,10 @@ impl OsContractAddresses {
     pub fn reserved_contract_address(&self) -> ContractAddress {
         ContractAddress::from(self.reserved_contract_address)
     }
+
+    pub fn block_metadata_contract_address(&self) -> Option<ContractAddress> {
+        self.block_metadata_contract_address.map(ContractAddress::from)
+    }
 }
 
 impl Default for OsContractAddresses {
@@ -831,6 +1005,71 @@ struct OsConstantsRawJson {
     #[serde(default)]
     validate_rounding_consts: ValidateRoundingConsts,
     os_contract_addresses: OsContractAddresses,
+    #[serde(default)]
+    gas_cost_overrides: GasCostOverrides,
+}
+
+/// Per-chain overrides of individual syscall and builtin gas costs, given in the optional
+/// `gas_cost_overrides` section of the OS constants. Allows appchains whose prover handles some
+/// syscalls or builtins cheaply to discount them.
+/// Overrides are validated to stay provable: a syscall cannot cost less than the base gas cost
+/// pre-charged by the compiler, a builtin cannot be free, and the range check cost, which is
+/// hard-coded in compiled contracts, cannot be overridden.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GasCostOverrides {
+    #[serde(default)]
+    syscalls: IndexMap<String, u64>,
+    #[serde(default)]
+    builtins: IndexMap<String, u64>,
+}
+
+impl GasCostOverrides {
+    const FIXED_BUILTINS: [&'static str; 1] = ["range_check"];
+
+    fn apply_to_builtins(
+        &self,
+        builtins: &mut IndexMap<String, u64>,
+    ) -> Result<(), OsConstantsSerdeError> {
+        for (key, &value) in &self.builtins {
+            if Self::FIXED_BUILTINS.contains(&key.as_str()) {
+                return Err(OsConstantsSerdeError::FixedGasCostOverride { key: key.clone() });
+            }
+            if value == 0 {
+                return Err(OsConstantsSerdeError::UnprovableGasCostOverride {
+                    key: key.clone(),
+                    value,
+                    min_value: 1,
+                });
+            }
+            let cost = builtins.get_mut(key).ok_or_else(|| {
+                OsConstantsSerdeError::UnknownGasCostOverride { key: key.clone() }
+            })?;
+            *cost = value;
+        }
+        Ok(())
+    }
+
+    fn apply_to_syscalls(
+        &self,
+        syscalls: &mut IndexMap<String, u64>,
+        base: &BaseGasCosts,
+    ) -> Result<(), OsConstantsSerdeError> {
+        for (key, &value) in &self.syscalls {
+            if value < base.syscall_base_gas_cost {
+                return Err(OsConstantsSerdeError::UnprovableGasCostOverride {
+                    key: key.clone(),
+                    value,
+                    min_value: base.syscall_base_gas_cost,
+                });
+            }
+            let cost = syscalls.get_mut(key).ok_or_else(|| {
+                OsConstantsSerdeError::UnknownGasCostOverride { key: key.clone() }
+            })?;
+            *cost = value;
+        }
+        Ok(())
+    }
 }
 
 impl OsConstantsRawJson {
@@ -1013,6 +1252,8 @@ pub enum VersionedConstantsError {
     InvalidVersion { version: String },
     #[error("Invalid Starknet version: {0}")]
     InvalidStarknetVersion(StarknetVersion),
+    #[error("Versioned constants limits must be positive: {0}.")]
+    InvalidLimits(String),
 }
 
 pub type VersionedConstantsResult<T> = Result<T, VersionedConstantsError>;
@@ -1030,6 +1271,12 @@ pub enum OsConstantsSerdeError {
          into u64"
     )]
     OutOfRangeFactor { key: String, value: Value },
+    #[error("Gas cost override for unknown syscall or builtin '{key}'")]
+    UnknownGasCostOverride { key: String },
+    #[error("Gas cost of '{key}' is fixed and cannot be overridden")]
+    FixedGasCostOverride { key: String },
+    #[error("Gas cost override {value} for '{key}' is below the provable minimum of {min_value}")]
+    UnprovableGasCostOverride { key: String, value: u64, min_value: u64 },
     #[error(transparent)]
     ParseError(#[from] serde_json::Error),
     #[error("Unhandled value type: {0}")]
@@ -1115,6 +1362,22 @@ pub struct VersionedConstantsOverrides {
     pub validate_max_n_steps: u32,
     pub max_recursion_depth: usize,
     pub invoke_tx_max_n_steps: u32,
+    pub execution_quotas: ExecutionQuotas,
+    // A versioned constants file replacing the built-in constants of every Starknet version, e.g.,
+    // to set the gas costs and limits of an appchain; the other overrides apply on top of it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versioned_constants_path: Option<PathBuf>,
+}
+
+impl VersionedConstantsOverrides {
+    /// Loads the versioned constants file, if set, so that an invalid file fails the node on
+    /// startup rather than on the first block.
+    pub fn validate(&self) -> VersionedConstantsResult<()> {
+        if let Some(path) = &self.versioned_constants_path {
+            VersionedConstants::from_custom_path(path)?;
+        }
+        Ok(())
+    }
 }
 
 impl Default for VersionedConstantsOverrides {
@@ -1124,13 +1387,15 @@ impl Default for VersionedConstantsOverrides {
             validate_max_n_steps: latest_versioned_constants.validate_max_n_steps,
             max_recursion_depth: latest_versioned_constants.max_recursion_depth,
             invoke_tx_max_n_steps: latest_versioned_constants.invoke_tx_max_n_steps,
+            execution_quotas: latest_versioned_constants.execution_quotas.clone(),
+            versioned_constants_path: None,
         }
     }
 }
 
 impl SerializeConfig for VersionedConstantsOverrides {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param(
                 "validate_max_n_steps",
                 &self.validate_max_n_steps,
@@ -1149,6 +1414,22 @@ impl SerializeConfig for VersionedConstantsOverrides {
                 "Maximum number of steps the invoke function is allowed to run.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "execution_quotas",
+                &self.execution_quotas,
+                "Space-separated <class|address>:<hash>:<limits> execution quotas, with \
+                 comma-separated steps, l2_gas and calls limits, e.g. \"class:0x1234:calls=10\".",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.extend(ser_optional_param(
+            &self.versioned_constants_path,
+            PathBuf::from("./versioned_constants.json"),
+            "versioned_constants_path",
+            "A versioned constants file replacing the built-in constants of every Starknet \
+             version; the other overrides apply on top of it.",
+            ParamPrivacyInput::Public,
+        ));
+        dump
     }
 }
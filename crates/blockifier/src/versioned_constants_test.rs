@@ -1,5 +1,9 @@
+use assert_matches::assert_matches;
 use glob::{glob, Paths};
 use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::block::VersionActivation;
+use tempfile::NamedTempFile;
 
 use super::*;
 
@@ -52,6 +56,8 @@ fn test_versioned_constants_overrides() {
         validate_max_n_steps: updated_validate_max_n_steps,
         max_recursion_depth: updated_max_recursion_depth,
         invoke_tx_max_n_steps: updated_invoke_tx_max_n_steps,
+        execution_quotas: ExecutionQuotas::default(),
+        versioned_constants_path: None,
     });
 
     // Assert the new values are used.
@@ -60,6 +66,100 @@ fn test_versioned_constants_overrides() {
     assert_eq!(result.max_recursion_depth, updated_max_recursion_depth);
 }
 
+#[rstest]
+#[case::before_upgrade(9, 99, true)]
+#[case::upgraded_by_height(10, 99, false)]
+#[case::upgraded_by_timestamp(9, 100, false)]
+fn test_scheduled_versioned_constants(
+    #[case] block_number: u64,
+    #[case] block_timestamp: u64,
+    #[case] segment_arena_cells: bool,
+) {
+    let schedule = StarknetVersionSchedule::new([
+        (VersionActivation::Height(BlockNumber(0)), StarknetVersion::V0_13_1),
+        (VersionActivation::Height(BlockNumber(10)), StarknetVersion::V0_13_4),
+        (VersionActivation::Timestamp(BlockTimestamp(100)), StarknetVersion::V0_13_4),
+    ])
+    .unwrap();
+    let overrides = VersionedConstantsOverrides {
+        invoke_tx_max_n_steps: 1,
+        ..VersionedConstantsOverrides::default()
+    };
+
+    let versioned_constants = VersionedConstants::get_scheduled_versioned_constants(
+        &schedule,
+        BlockNumber(block_number),
+        BlockTimestamp(block_timestamp),
+        overrides,
+    )
+    .unwrap();
+    // Segment arena cells are counted before Starknet 0.13.2.
+    assert_eq!(versioned_constants.segment_arena_cells, segment_arena_cells);
+    assert_eq!(versioned_constants.invoke_tx_max_n_steps, 1);
+}
+
+#[test]
+fn test_versioned_constants_from_custom_path() {
+    let mut raw_versioned_constants: Value =
+        serde_json::from_str(&VERSIONED_CONSTANTS_LATEST_JSON).unwrap();
+    raw_versioned_constants["execute_max_sierra_gas"] = Value::from(1234);
+    let versioned_constants_file = NamedTempFile::new().unwrap();
+    serde_json::to_writer(&versioned_constants_file, &raw_versioned_constants).unwrap();
+
+    let schedule = StarknetVersionSchedule::new([
+        (VersionActivation::Height(BlockNumber(0)), StarknetVersion::V0_13_1),
+        (VersionActivation::Height(BlockNumber(10)), StarknetVersion::V0_13_4),
+    ])
+    .unwrap();
+    let overrides = VersionedConstantsOverrides {
+        invoke_tx_max_n_steps: 1,
+        versioned_constants_path: Some(versioned_constants_file.path().to_path_buf()),
+        ..VersionedConstantsOverrides::default()
+    };
+    overrides.validate().unwrap();
+
+    // The custom constants replace those of every version, and the other overrides still apply.
+    for block_number in [BlockNumber(9), BlockNumber(10)] {
+        let versioned_constants = VersionedConstants::get_scheduled_versioned_constants(
+            &schedule,
+            block_number,
+            BlockTimestamp::default(),
+            overrides.clone(),
+        )
+        .unwrap();
+        assert_eq!(versioned_constants.execute_max_sierra_gas, GasAmount(1234));
+        assert_eq!(versioned_constants.invoke_tx_max_n_steps, 1);
+    }
+    assert_eq!(
+        VersionedConstants::get_versioned_constants(overrides).execute_max_sierra_gas,
+        GasAmount(1234)
+    );
+}
+
+#[test]
+fn test_invalid_custom_versioned_constants() {
+    let mut raw_versioned_constants: Value =
+        serde_json::from_str(&VERSIONED_CONSTANTS_LATEST_JSON).unwrap();
+    raw_versioned_constants["validate_max_sierra_gas"] = Value::from(0);
+    let versioned_constants_file = NamedTempFile::new().unwrap();
+    serde_json::to_writer(&versioned_constants_file, &raw_versioned_constants).unwrap();
+
+    let overrides = VersionedConstantsOverrides {
+        versioned_constants_path: Some(versioned_constants_file.path().to_path_buf()),
+        ..VersionedConstantsOverrides::default()
+    };
+    assert_matches!(
+        overrides.validate(),
+        Err(VersionedConstantsError::InvalidLimits(limits)) if limits == "validate_max_sierra_gas"
+    );
+
+    let overrides = VersionedConstantsOverrides {
+        versioned_constants_path: Some(PathBuf::from("/nonexistent/versioned_constants.json")),
+        ..VersionedConstantsOverrides::default()
+    };
+    assert_matches!(overrides.validate(), Err(VersionedConstantsError::IoError(_)));
+}
+
 #[test]
 fn test_string_inside_composed_field() {
     let json_data = r#"
@@ -196,3 +296,93 @@ fn test_syscall_gas_cost_calculation() {
         EXPECTED_SHA256PROCESSBLOCK_GAS_COST
     );
 }
+
+fn os_constants_with_gas_cost_overrides(
+    overrides: Value,
+) -> Result<OsConstants, serde_json::Error> {
+    let mut json_data: Value = serde_json::from_str(&VERSIONED_CONSTANTS_LATEST_JSON).unwrap();
+    let os_constants = json_data.get_mut("os_constants").unwrap().as_object_mut().unwrap();
+    os_constants.insert("gas_cost_overrides".to_string(), overrides);
+    serde_json::from_value(os_constants.clone().into())
+}
+
+#[test]
+fn test_gas_cost_overrides() {
+    let latest_gas_costs = &VersionedConstants::latest_constants().os_constants.gas_costs;
+    let syscall_base_gas_cost = latest_gas_costs.base.syscall_base_gas_cost;
+    let overrides = serde_json::json!({
+        "syscalls": { "sha256_process_block": syscall_base_gas_cost },
+        "builtins": { "poseidon": 1 }
+    });
+
+    let gas_costs = os_constants_with_gas_cost_overrides(overrides).unwrap().gas_costs;
+
+    assert_eq!(gas_costs.syscalls.sha256_process_block, syscall_base_gas_cost);
+    assert_eq!(gas_costs.builtins.poseidon, 1);
+    // Costs that are not overridden are kept.
+    assert_eq!(gas_costs.syscalls.storage_read, latest_gas_costs.syscalls.storage_read);
+    assert_eq!(gas_costs.builtins.pedersen, latest_gas_costs.builtins.pedersen);
+}
+
+#[rstest]
+#[case::unknown_syscall(
+    serde_json::json!({ "syscalls": { "meow": 1000000 } }),
+    "Gas cost override for unknown syscall or builtin 'meow'"
+)]
+#[case::syscall_below_base_cost(
+    serde_json::json!({ "syscalls": { "storage_read": 1 } }),
+    "Gas cost override 1 for 'storage_read' is below the provable minimum of 10000"
+)]
+#[case::free_builtin(
+    serde_json::json!({ "builtins": { "pedersen": 0 } }),
+    "Gas cost override 0 for 'pedersen' is below the provable minimum of 1"
+)]
+#[case::fixed_builtin(
+    serde_json::json!({ "builtins": { "range_check": 1 } }),
+    "Gas cost of 'range_check' is fixed and cannot be overridden"
+)]
+fn test_invalid_gas_cost_overrides(#[case] overrides: Value, #[case] expected_error_message: &str) {
+    let error = os_constants_with_gas_cost_overrides(overrides).unwrap_err();
+    assert_eq!(error.to_string(), expected_error_message);
+}
+
+#[test]
+fn test_execution_phase_restrictions() {
+    let restrictions: ExecutionPhaseRestrictions = serde_json::from_value(serde_json::json!({
+        "allowed_syscalls": ["StorageRead", "GetExecutionInfo"],
+        "allowed_builtins": ["range_check", "pedersen"]
+    }))
+    .unwrap();
+    assert_eq!(
+        restrictions,
+        ExecutionPhaseRestrictions {
+            allowed_syscalls: Some(HashSet::from([
+                SyscallSelector::StorageRead,
+                SyscallSelector::GetExecutionInfo,
+            ])),
+            allowed_builtins: Some(HashSet::from([
+                BuiltinName::range_check,
+                BuiltinName::pedersen,
+            ])),
+        }
+    );
+    serde_json::from_value::<ExecutionPhaseRestrictions>(
+        serde_json::json!({ "allowed_builtins": ["meow"] }),
+    )
+    .unwrap_err();
+
+    // Versioned constants without restrictions leave all phases unrestricted.
+    let versioned_constants = VersionedConstants::latest_constants();
+    let validate_config = versioned_constants.execution_phase_config(&ExecutionMode::Validate);
+    assert_eq!(validate_config.max_n_steps, versioned_constants.validate_max_n_steps);
+    assert!(validate_config.restrictions.is_unrestricted());
+    assert!(validate_config.is_syscall_allowed(SyscallSelector::Deploy));
+    assert_eq!(validate_config.find_disallowed_builtin(&[BuiltinName::bitwise]), None);
+
+    let restricted_config = ExecutionPhaseConfig { restrictions, ..validate_config };
+    assert!(!restricted_config.is_syscall_allowed(SyscallSelector::Deploy));
+    assert_eq!(
+        restricted_config.find_disallowed_builtin(&[BuiltinName::pedersen, BuiltinName::bitwise]),
+        Some(BuiltinName::bitwise)
+    );
+}
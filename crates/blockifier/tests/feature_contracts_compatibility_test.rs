@@ -1,23 +1,20 @@
 use std::fs;
 
-use blockifier::test_utils::cairo_compile::{
-    prepare_group_tag_compiler_deps,
-    CompilationArtifacts,
-};
 use blockifier::test_utils::contracts::{
     FeatureContract,
     CAIRO1_FEATURE_CONTRACTS_DIR,
     SIERRA_CONTRACTS_SUBDIR,
 };
+use blockifier::test_utils::feature_contracts_fixtures::{
+    verify_feature_contracts_fixtures,
+    FIX_FEATURE_CONTRACTS_COMMAND,
+};
 use blockifier::test_utils::{CairoVersion, RunnableCairo1};
 use pretty_assertions::assert_eq;
 use rstest::rstest;
 
 const CAIRO0_FEATURE_CONTRACTS_DIR: &str = "feature_contracts/cairo0";
 const COMPILED_CONTRACTS_SUBDIR: &str = "compiled";
-const FIX_COMMAND: &str = "FIX_FEATURE_TEST=1 cargo test -p blockifier --test \
-                           feature_contracts_compatibility_test --features testing -- \
-                           --include-ignored";
 
 pub enum FeatureContractMetadata {
     Cairo0(Cairo0FeatureContractMetadata),
@@ -59,123 +56,17 @@ pub struct Cairo1FeatureContractMetadata {
 // . tmp_venv/bin/activate
 // pip install -r crates/blockifier/tests/requirements.txt
 // ```
-// Then, run the FIX_COMMAND above.
+// Then, run the `feature_contracts_fixtures` binary, see `FIX_FEATURE_CONTRACTS_COMMAND`.
 
 // To fix Cairo1 feature contracts, first clone the Cairo repo and checkout the required tag.
 // The repo should be located next to the sequencer repo:
 // <WORKSPACE_DIR>/
 // - sequencer/
 // - cairo/
-// Then, run the FIX_COMMAND above.
-
-// Checks that:
-// 1. `TEST_CONTRACTS` dir exists and contains only `.cairo` files and the subdirectory
-// `COMPILED_CONTRACTS_SUBDIR`.
-// 2. for each `X.cairo` file in `TEST_CONTRACTS` there exists an `X_compiled.json` file in
-// `COMPILED_CONTRACTS_SUBDIR` which equals `starknet-compile-deprecated X.cairo --no_debug_info`.
-fn verify_feature_contracts_compatibility(fix: bool, cairo_version: CairoVersion) {
-    // TODO(Dori, 1/10/2024): Parallelize this test.
-    match cairo_version {
-        CairoVersion::Cairo0 => {
-            for contract in FeatureContract::all_feature_contracts()
-                .filter(|contract| contract.cairo_version() == cairo_version)
-            {
-                verify_feature_contracts_compatibility_logic(&contract, fix);
-            }
-        }
-        CairoVersion::Cairo1(RunnableCairo1::Casm) => {
-            for (tag_and_tool_chain, feature_contracts) in
-                FeatureContract::cairo1_feature_contracts_by_tag()
-            {
-                prepare_group_tag_compiler_deps(&tag_and_tool_chain);
-                // TODO(Meshi 01/01/2025) Make this loop concurrent
-                for contract in feature_contracts
-                    .into_iter()
-                    .filter(|contract| contract.cairo_version() == cairo_version)
-                {
-                    verify_feature_contracts_compatibility_logic(&contract, fix);
-                }
-            }
-        }
-        #[cfg(feature = "cairo_native")]
-        CairoVersion::Cairo1(RunnableCairo1::Native) => {
-            panic!("This test does not support native CairoVersion.")
-        }
-    }
-}
-
-fn check_compilation(
-    actual_content: Vec<u8>,
-    existing_contents: &str,
-    path: &str,
-    source_path: String,
-) {
-    if String::from_utf8(actual_content).unwrap() != existing_contents {
-        panic!(
-            "{} does not compile to {}.\nRun `{FIX_COMMAND}` to fix the existing file according \
-             to locally installed `starknet-compile-deprecated`.\n",
-            source_path, path
-        );
-    }
-}
-
-fn compare_compilation_data(contract: &FeatureContract) {
-    let expected_compiled_raw_output = contract.compile();
-    let existing_compiled_path = contract.get_compiled_path();
-    let existing_compiled_contents = fs::read_to_string(&existing_compiled_path)
-        .unwrap_or_else(|_| panic!("Cannot read {existing_compiled_path}."));
-
-    match expected_compiled_raw_output {
-        CompilationArtifacts::Cairo0 { casm } => {
-            check_compilation(
-                casm,
-                &existing_compiled_contents,
-                &existing_compiled_path,
-                contract.get_source_path(),
-            );
-        }
-        CompilationArtifacts::Cairo1 { casm, sierra } => {
-            // TODO(Aviv): Remove this if after fixing sierra file of cairo steps contract.
-            if !matches!(contract, FeatureContract::CairoStepsTestContract) {
-                check_compilation(
-                    casm,
-                    &existing_compiled_contents,
-                    &existing_compiled_path,
-                    contract.get_source_path(),
-                );
-
-                let sierra_compiled_path = contract.get_sierra_path();
-                let existing_sierra_contents = fs::read_to_string(&sierra_compiled_path)
-                    .unwrap_or_else(|_| panic!("Cannot read {sierra_compiled_path}."));
-                check_compilation(
-                    sierra,
-                    &existing_sierra_contents,
-                    &sierra_compiled_path,
-                    contract.get_source_path(),
-                );
-            }
-        }
-    }
-}
-
-fn verify_feature_contracts_compatibility_logic(contract: &FeatureContract, fix: bool) {
-    // Compare output of cairo-file on file with existing compiled file.
-    let expected_compiled_raw_output = contract.compile();
-    let existing_compiled_path = contract.get_compiled_path();
-    if fix {
-        match expected_compiled_raw_output {
-            CompilationArtifacts::Cairo0 { ref casm } => {
-                fs::write(&existing_compiled_path, casm).unwrap();
-            }
-            CompilationArtifacts::Cairo1 { ref casm, ref sierra } => {
-                fs::write(&existing_compiled_path, casm).unwrap();
-                fs::write(contract.get_sierra_path(), sierra).unwrap();
-            }
-        }
-    }
-
-    compare_compilation_data(contract);
-}
+// Then, run the `feature_contracts_fixtures` binary, see `FIX_FEATURE_CONTRACTS_COMMAND`.
+// Compiled Cairo1 artifacts are cached by source and compiler (repository path, tag and checked out
+// commit); to place the cache in a specific directory, set the CAIRO1_COMPILATION_CACHE_DIR
+// environment variable.
 
 /// Verifies that the feature contracts directory contains the expected contents, and returns
 /// the feature contracts metadata.
@@ -299,6 +190,8 @@ fn verify_feature_contracts_match_enum(
     assert_eq!(compiled_paths_from_enum, compiled_paths_on_filesystem);
 }
 
+// Checks that for each feature contract, the committed artifacts equal the compilation of its
+// source.
 // Native and Casm have the same contracts and compiled files, as we only save the sierra for
 // Native, so we exclude Native CairoVersion from this test.
 #[rstest]
@@ -307,6 +200,12 @@ fn verify_feature_contracts(
     #[values(CairoVersion::Cairo0, CairoVersion::Cairo1(RunnableCairo1::Casm))]
     cairo_version: CairoVersion,
 ) {
-    let fix_features = std::env::var("FIX_FEATURE_TEST").is_ok();
-    verify_feature_contracts_compatibility(fix_features, cairo_version)
+    let fix = false;
+    let mismatches = verify_feature_contracts_fixtures(cairo_version, fix);
+    assert!(
+        mismatches.is_empty(),
+        "Feature contract artifacts do not match their sources: {mismatches:#?}.\nRun \
+         `{FIX_FEATURE_CONTRACTS_COMMAND}` to fix them according to the locally installed \
+         compilers."
+    );
 }
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use blockifier::blockifier::block::BlockHashProvider;
+use blockifier::context::BlockContext;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader, StateResult};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_core::types::ContractClass as StarknetContractClass;
+use starknet_gateway::config::RpcStateReaderConfig;
+use starknet_types_core::felt::Felt;
+
+use crate::retry_request;
+use crate::state_reader::compile::{
+    legacy_to_contract_class_v0,
+    sierra_to_versioned_contract_class_v1,
+};
+use crate::state_reader::errors::ReexecutionResult;
+use crate::state_reader::reexecution_state_reader::ReexecutionStateReader;
+use crate::state_reader::test_state_reader::TestStateReader;
+
+/// A state reader over the state of a live Starknet node at a pinned block ("fork mode").
+/// State is fetched through JSON-RPC the first time it is read and cached, so transactions can be
+/// simulated locally on top of the pinned state, e.g., by wrapping the reader with a `CachedState`.
+pub struct ForkStateReader {
+    state_reader: TestStateReader,
+    block_number: BlockNumber,
+    storage: Mutex<HashMap<(ContractAddress, StorageKey), Felt>>,
+    nonces: Mutex<HashMap<ContractAddress, Nonce>>,
+    class_hashes: Mutex<HashMap<ContractAddress, ClassHash>>,
+    compiled_classes: Mutex<HashMap<ClassHash, RunnableCompiledClass>>,
+    compiled_class_hashes: Mutex<HashMap<ClassHash, CompiledClassHash>>,
+    block_hash_provider: Arc<RpcBlockHashProvider>,
+}
+
+impl ForkStateReader {
+    /// Forks the state of the node at the end of the given block.
+    pub fn new(
+        config: &RpcStateReaderConfig,
+        chain_id: ChainId,
+        block_number: BlockNumber,
+    ) -> Self {
+        let dump_mode = false;
+        Self {
+            state_reader: TestStateReader::new(config, chain_id.clone(), block_number, dump_mode),
+            block_number,
+            storage: Mutex::default(),
+            nonces: Mutex::default(),
+            class_hashes: Mutex::default(),
+            compiled_classes: Mutex::default(),
+            compiled_class_hashes: Mutex::default(),
+            block_hash_provider: Arc::new(RpcBlockHashProvider {
+                state_reader: TestStateReader::new(config, chain_id, block_number, dump_mode),
+                block_number,
+                block_hashes: Mutex::default(),
+            }),
+        }
+    }
+
+    pub fn block_number(&self) -> BlockNumber {
+        self.block_number
+    }
+
+    /// Returns the block context of the pinned block. Block hashes missing from the forked state
+    /// are resolved through JSON-RPC.
+    pub fn get_block_context(&self) -> ReexecutionResult<BlockContext> {
+        Ok(self
+            .state_reader
+            .get_block_context()?
+            .with_block_hash_provider(self.block_hash_provider.clone()))
+    }
+
+    /// Fetches and compiles the class of the given class hash, returning it with its compiled class
+    /// hash; Cairo 0 classes have the default compiled class hash.
+    fn fetch_compiled_class(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<(RunnableCompiledClass, CompiledClassHash)> {
+        let contract_class = retry_request!(self.state_reader.retry_config, || self
+            .state_reader
+            .get_contract_class(&class_hash))?;
+
+        let (contract_class, compiled_class_hash) = match contract_class {
+            StarknetContractClass::Sierra(sierra) => {
+                let (contract_class, _sierra_version) =
+                    sierra_to_versioned_contract_class_v1(sierra)?;
+                let compiled_class_hash = contract_class.compiled_class_hash();
+                (contract_class, compiled_class_hash)
+            }
+            StarknetContractClass::Legacy(legacy) => {
+                (legacy_to_contract_class_v0(legacy)?, CompiledClassHash::default())
+            }
+        };
+        let compiled_class =
+            RunnableCompiledClass::try_from(contract_class).map_err(StateError::ProgramError)?;
+
+        Ok((compiled_class, compiled_class_hash))
+    }
+}
+
+impl StateReader for ForkStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        get_or_fetch(&self.storage, (contract_address, key), || {
+            self.state_reader.get_storage_at(contract_address, key)
+        })
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        get_or_fetch(&self.nonces, contract_address, || {
+            self.state_reader.get_nonce_at(contract_address)
+        })
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        get_or_fetch(&self.class_hashes, contract_address, || {
+            self.state_reader.get_class_hash_at(contract_address)
+        })
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        get_or_fetch(&self.compiled_classes, class_hash, || {
+            let (compiled_class, compiled_class_hash) = self.fetch_compiled_class(class_hash)?;
+            self.compiled_class_hashes
+                .lock()
+                .expect("Compiled class hashes lock is poisoned.")
+                .insert(class_hash, compiled_class_hash);
+            Ok(compiled_class)
+        })
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        get_or_fetch(&self.compiled_class_hashes, class_hash, || {
+            let (compiled_class, compiled_class_hash) = self.fetch_compiled_class(class_hash)?;
+            self.compiled_classes
+                .lock()
+                .expect("Compiled classes lock is poisoned.")
+                .insert(class_hash, compiled_class);
+            Ok(compiled_class_hash)
+        })
+    }
+}
+
+/// Resolves the hashes of the blocks up to the pinned block through JSON-RPC, caching them.
+struct RpcBlockHashProvider {
+    state_reader: TestStateReader,
+    block_number: BlockNumber,
+    block_hashes: Mutex<HashMap<BlockNumber, BlockHash>>,
+}
+
+impl BlockHashProvider for RpcBlockHashProvider {
+    fn get_block_hash(&self, block_number: BlockNumber) -> StateResult<Option<BlockHash>> {
+        if block_number > self.block_number {
+            return Ok(None);
+        }
+        get_or_fetch(&self.block_hashes, block_number, || {
+            self.state_reader
+                .get_old_block_hash(block_number)
+                .map_err(|err| StateError::StateReadError(err.to_string()))
+        })
+        .map(Some)
+    }
+}
+
+// Returns the cached value of the given key, fetching and caching it if missing. The lock is not
+// held while fetching, so concurrent readers of a missing key may fetch it more than once.
+fn get_or_fetch<K: Eq + Hash, V: Clone>(
+    cache: &Mutex<HashMap<K, V>>,
+    key: K,
+    fetch: impl FnOnce() -> StateResult<V>,
+) -> StateResult<V> {
+    if let Some(value) = cache.lock().expect("Fork state cache lock is poisoned.").get(&key) {
+        return Ok(value.clone());
+    }
+    let value = fetch()?;
+    cache.lock().expect("Fork state cache lock is poisoned.").insert(key, value.clone());
+    Ok(value)
+}
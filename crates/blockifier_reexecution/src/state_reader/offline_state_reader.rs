@@ -29,13 +29,14 @@ use crate::state_reader::reexecution_state_reader::{
     ReexecutionStateReader,
 };
 use crate::state_reader::test_state_reader::StarknetContractClassMapping;
-use crate::state_reader::utils::{get_chain_info, ReexecutionStateMaps};
+use crate::state_reader::utils::{get_chain_info, ComparableReceipt, ReexecutionStateMaps};
 
 pub struct OfflineReexecutionData {
     offline_state_reader_prev_block: OfflineStateReader,
     block_context_next_block: BlockContext,
     transactions_next_block: Vec<BlockifierTransaction>,
     state_diff_next_block: CommitmentStateDiff,
+    receipts_next_block: Option<Vec<ComparableReceipt>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -45,6 +46,8 @@ pub struct SerializableDataNextBlock {
     pub transactions_next_block: Vec<(Transaction, TransactionHash)>,
     pub state_diff_next_block: CommitmentStateDiff,
     pub declared_classes: StarknetContractClassMapping,
+    // Missing in data recorded before receipts were verified.
+    pub receipts_next_block: Option<Vec<ComparableReceipt>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -91,6 +94,7 @@ impl From<SerializableOfflineReexecutionData> for OfflineReexecutionData {
                     transactions_next_block,
                     state_diff_next_block,
                     declared_classes,
+                    receipts_next_block,
                 },
             chain_id,
             old_block_hash,
@@ -118,6 +122,7 @@ impl From<SerializableOfflineReexecutionData> for OfflineReexecutionData {
             ),
             transactions_next_block,
             state_diff_next_block,
+            receipts_next_block,
         }
     }
 }
@@ -217,6 +222,7 @@ pub struct OfflineConsecutiveStateReaders {
     pub block_context_next_block: BlockContext,
     pub transactions_next_block: Vec<BlockifierTransaction>,
     pub state_diff_next_block: CommitmentStateDiff,
+    pub receipts_next_block: Option<Vec<ComparableReceipt>>,
 }
 
 impl OfflineConsecutiveStateReaders {
@@ -239,6 +245,7 @@ impl OfflineConsecutiveStateReaders {
             block_context_next_block,
             transactions_next_block,
             state_diff_next_block,
+            receipts_next_block,
         }
     }
 }
@@ -259,4 +266,8 @@ impl ConsecutiveReexecutionStateReaders<OfflineStateReader> for OfflineConsecuti
     fn get_next_block_state_diff(&self) -> ReexecutionResult<CommitmentStateDiff> {
         Ok(self.state_diff_next_block.clone())
     }
+
+    fn get_next_block_receipts(&self) -> ReexecutionResult<Option<Vec<ComparableReceipt>>> {
+        Ok(self.receipts_next_block.clone())
+    }
 }
@@ -6,19 +6,24 @@ use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
 use starknet_api::block::{BlockInfo, BlockNumber};
 use starknet_api::test_utils::read_json_file;
+use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::{
     DeclareTransaction,
     DeployAccountTransaction,
     InvokeTransaction,
     Transaction,
+    TransactionHash,
 };
 use starknet_api::{class_hash, compiled_class_hash, contract_address, felt, nonce, storage_key};
 use starknet_core::types::ContractClass;
 use starknet_gateway::rpc_objects::BlockHeader;
 
 use crate::state_reader::compile::legacy_to_contract_class_v0;
-use crate::state_reader::serde_utils::deserialize_transaction_json_to_starknet_api_tx;
-use crate::state_reader::utils::ReexecutionStateMaps;
+use crate::state_reader::serde_utils::{
+    deserialize_receipt_json_to_comparable_receipt,
+    deserialize_transaction_json_to_starknet_api_tx,
+};
+use crate::state_reader::utils::{ComparableReceipt, ReexecutionStateMaps};
 
 #[fixture]
 fn block_header() -> BlockHeader {
@@ -134,6 +139,40 @@ fn deserialize_l1_handler_tx() {
     assert_matches!(l1_handler_tx, Transaction::L1Handler(..));
 }
 
+#[rstest]
+#[case::succeeded(
+    "succeeded",
+    "0x3f32a1f2ad8b9a7d9e4a6e2f5b6d3c8a7e1f0b2c4d6e8f0a1b3c5d7e9f1a2b3",
+    0x1c5b5ff4e0a0,
+    false
+)]
+#[case::reverted(
+    "reverted",
+    "0x1b6e4c2a9d7f3e5b8a0c2d4f6e8a1b3c5d7f9e0a2c4b6d8f1e3a5c7b9d0f2e4",
+    0x5af3107a4000,
+    true
+)]
+fn deserialize_receipts(
+    #[case] receipt_name: &str,
+    #[case] tx_hash: &str,
+    #[case] actual_fee: u128,
+    #[case] is_reverted: bool,
+) {
+    let receipt = deserialize_receipt_json_to_comparable_receipt(
+        read_json_file("raw_rpc_json_objects/receipts.json")[receipt_name].clone(),
+    )
+    .unwrap_or_else(|_| panic!("Failed to deserialize {receipt_name} receipt"));
+
+    assert_eq!(
+        receipt,
+        ComparableReceipt {
+            tx_hash: TransactionHash(felt!(tx_hash)),
+            actual_fee: Fee(actual_fee),
+            is_reverted,
+        }
+    );
+}
+
 #[rstest]
 fn serialize_state_maps() {
     let nonces = HashMap::from([(contract_address!(1_u8), nonce!(1_u8))]);
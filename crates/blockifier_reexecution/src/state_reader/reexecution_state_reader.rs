@@ -17,6 +17,7 @@ use crate::state_reader::compile::{
     sierra_to_versioned_contract_class_v1,
 };
 use crate::state_reader::errors::{ReexecutionError, ReexecutionResult};
+use crate::state_reader::utils::ComparableReceipt;
 
 pub trait ReexecutionStateReader {
     fn get_contract_class(&self, class_hash: &ClassHash) -> StateResult<StarknetContractClass>;
@@ -106,4 +107,8 @@ pub trait ConsecutiveReexecutionStateReaders<S: StateReader> {
     fn get_next_block_txs(&self) -> ReexecutionResult<Vec<BlockifierTransaction>>;
 
     fn get_next_block_state_diff(&self) -> ReexecutionResult<CommitmentStateDiff>;
+
+    /// Returns the canonical receipts of the next block's transactions, in block order; [None] if
+    /// they are unavailable.
+    fn get_next_block_receipts(&self) -> ReexecutionResult<Option<Vec<ComparableReceipt>>>;
 }
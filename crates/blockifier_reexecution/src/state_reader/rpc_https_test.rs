@@ -13,10 +13,12 @@ use std::env;
 use std::sync::{Arc, Mutex};
 
 use assert_matches::assert_matches;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::state_api::StateReader;
 use rstest::{fixture, rstest};
 use starknet_api::block::{BlockInfo, BlockNumber};
 use starknet_api::class_hash;
-use starknet_api::core::ChainId;
+use starknet_api::core::{ChainId, CompiledClassHash};
 use starknet_api::transaction::{
     DeclareTransaction,
     DeployAccountTransaction,
@@ -31,6 +33,7 @@ use starknet_gateway::rpc_state_reader::RpcStateReader;
 use super::test_state_reader::RetryConfig;
 use super::utils::RPC_NODE_URL;
 use crate::state_reader::compile::legacy_to_contract_class_v0;
+use crate::state_reader::fork_state_reader::ForkStateReader;
 use crate::state_reader::reexecution_state_reader::ReexecutionStateReader;
 use crate::state_reader::test_state_reader::{ConsecutiveTestStateReaders, TestStateReader};
 use crate::state_reader::utils::guess_chain_id_from_node_url;
@@ -98,6 +101,7 @@ pub fn test_state_reader() -> TestStateReader {
         rpc_state_reader: RpcStateReader {
             config: get_test_rpc_config(),
             block_id: get_test_block_id(),
+            contract_class_manager: None,
         },
         retry_config: RetryConfig::default(),
         chain_id: ChainId::Mainnet,
@@ -266,3 +270,19 @@ pub fn test_get_old_block_hash(
 ) {
     test_state_reader.get_old_block_hash(last_constructed_block).unwrap();
 }
+
+#[rstest]
+pub fn test_fork_state_reader(test_block_number: BlockNumber) {
+    let fork_state_reader =
+        ForkStateReader::new(&get_test_rpc_config(), ChainId::Mainnet, test_block_number);
+    let class_hash = class_hash!(EXAMPLE_CONTACT_CLASS_HASH);
+
+    let compiled_class = fork_state_reader.get_compiled_class(class_hash).unwrap();
+    assert_matches!(compiled_class, RunnableCompiledClass::V0(_));
+    // Cached alongside the compiled class.
+    assert_eq!(
+        fork_state_reader.get_compiled_class_hash(class_hash).unwrap(),
+        CompiledClassHash::default()
+    );
+    fork_state_reader.get_block_context().unwrap();
+}
@@ -9,6 +9,7 @@ use starknet_api::transaction::{
 };
 
 use crate::state_reader::errors::ReexecutionResult;
+use crate::state_reader::utils::ComparableReceipt;
 
 /// In old transaction, the resource bounds names are lowercase.
 /// need to convert to uppercase for deserialization to work.
@@ -167,3 +168,14 @@ pub(crate) fn vec_to_nested_hashmap<
         })
         .collect()
 }
+
+pub fn deserialize_receipt_json_to_comparable_receipt(
+    raw_receipt: Value,
+) -> serde_json::Result<ComparableReceipt> {
+    let execution_status: String = serde_json::from_value(raw_receipt["execution_status"].clone())?;
+    Ok(ComparableReceipt {
+        tx_hash: serde_json::from_value(raw_receipt["transaction_hash"].clone())?,
+        actual_fee: serde_json::from_value(raw_receipt["actual_fee"]["amount"].clone())?,
+        is_reverted: execution_status == "REVERTED",
+    })
+}
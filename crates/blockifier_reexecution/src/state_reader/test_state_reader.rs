@@ -14,7 +14,7 @@ use blockifier::state::state_api::{StateReader, StateResult};
 use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
 use blockifier::versioned_constants::VersionedConstants;
 use serde::Serialize;
-use serde_json::{json, to_value};
+use serde_json::{json, to_value, Value};
 use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockInfo, BlockNumber, StarknetVersion};
 use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::state::StorageKey;
@@ -43,6 +43,7 @@ use crate::state_reader::reexecution_state_reader::{
     ReexecutionStateReader,
 };
 use crate::state_reader::serde_utils::{
+    deserialize_receipt_json_to_comparable_receipt,
     deserialize_transaction_json_to_starknet_api_tx,
     hashmap_from_raw,
     nested_hashmap_from_raw,
@@ -51,6 +52,7 @@ use crate::state_reader::utils::{
     disjoint_hashmap_union,
     get_chain_info,
     get_rpc_state_reader_config,
+    ComparableReceipt,
 };
 
 pub const DEFAULT_RETRY_COUNT: usize = 3;
@@ -248,6 +250,25 @@ impl TestStateReader {
             .collect::<Result<_, _>>()
     }
 
+    /// Get the receipts of all transactions in the current block, in block order.
+    pub fn get_tx_receipts(&self) -> ReexecutionResult<Vec<ComparableReceipt>> {
+        let raw_txs_with_receipts: Vec<Value> = serde_json::from_value(
+            retry_request!(self.retry_config, || {
+                self.rpc_state_reader.send_rpc_request(
+                    "starknet_getBlockWithReceipts",
+                    GetBlockWithTxHashesParams { block_id: self.rpc_state_reader.block_id },
+                )
+            })?["transactions"]
+                .clone(),
+        )?;
+        Ok(raw_txs_with_receipts
+            .into_iter()
+            .map(|mut raw_tx| {
+                deserialize_receipt_json_to_comparable_receipt(raw_tx["receipt"].take())
+            })
+            .collect::<Result<_, _>>()?)
+    }
+
     pub fn get_versioned_constants(&self) -> ReexecutionResult<&'static VersionedConstants> {
         Ok(VersionedConstants::get(&self.get_starknet_version()?)?)
     }
@@ -358,7 +379,10 @@ impl ReexecutionStateReader for TestStateReader {
         Ok(contract_class)
     }
 
-    fn get_old_block_hash(&self, old_block_number: BlockNumber) -> ReexecutionResult<BlockHash> {
+    pub(crate) fn get_old_block_hash(
+        &self,
+        old_block_number: BlockNumber,
+    ) -> ReexecutionResult<BlockHash> {
         let block_id = BlockId::Number(old_block_number);
         let params = GetBlockWithTxHashesParams { block_id };
         let response =
@@ -407,6 +431,7 @@ impl ConsecutiveTestStateReaders {
             transactions_next_block,
             state_diff_next_block: self.next_block_state_reader.get_state_diff()?,
             declared_classes,
+            receipts_next_block: Some(self.next_block_state_reader.get_tx_receipts()?),
         })
     }
 
@@ -453,4 +478,8 @@ impl ConsecutiveReexecutionStateReaders<TestStateReader> for ConsecutiveTestStat
     fn get_next_block_state_diff(&self) -> ReexecutionResult<CommitmentStateDiff> {
         self.next_block_state_reader.get_state_diff()
     }
+
+    fn get_next_block_receipts(&self) -> ReexecutionResult<Option<Vec<ComparableReceipt>>> {
+        Ok(Some(self.next_block_state_reader.get_tx_receipts()?))
+    }
 }
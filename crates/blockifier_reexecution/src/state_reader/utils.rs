@@ -4,9 +4,17 @@ use std::fs::read_to_string;
 use std::sync::LazyLock;
 
 use assert_matches::assert_matches;
-use blockifier::context::{ChainInfo, FeeTokenAddresses};
+use blockifier::context::{
+    ChainInfo,
+    CustomFeeTokens,
+    FeeDistribution,
+    FeeTokenAddresses,
+    PaymasterConfig,
+};
 use blockifier::state::cached_state::{CachedState, CommitmentStateDiff, StateMaps};
 use blockifier::state::state_api::StateReader;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
 use indexmap::IndexMap;
 use papyrus_execution::{ETH_FEE_CONTRACT_ADDRESS, STRK_FEE_CONTRACT_ADDRESS};
 use pretty_assertions::assert_eq;
@@ -14,6 +22,8 @@ use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
 use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::state::StorageKey;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{TransactionHash, TransactionVersions};
 use starknet_gateway::config::RpcStateReaderConfig;
 use starknet_types_core::felt::Felt;
 
@@ -67,7 +77,15 @@ pub fn get_rpc_state_reader_config() -> RpcStateReaderConfig {
 
 /// Returns the chain info of mainnet.
 pub fn get_chain_info(chain_id: &ChainId) -> ChainInfo {
-    ChainInfo { chain_id: chain_id.clone(), fee_token_addresses: get_fee_token_addresses(chain_id) }
+    ChainInfo {
+        chain_id: chain_id.clone(),
+        fee_token_addresses: get_fee_token_addresses(chain_id),
+        custom_fee_tokens: CustomFeeTokens::default(),
+        disabled_tx_versions: TransactionVersions::default(),
+        sierra_gas_only: false,
+        fee_distribution: FeeDistribution::default(),
+        paymaster: PaymasterConfig::default(),
+    }
 }
 
 // TODO(Aner): import the following functions instead, to reduce code duplication.
@@ -213,6 +231,24 @@ impl From<CommitmentStateDiff> for ComparableStateDiff {
     }
 }
 
+/// The fields of a transaction receipt which are verified by reexecution.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ComparableReceipt {
+    pub tx_hash: TransactionHash,
+    pub actual_fee: Fee,
+    pub is_reverted: bool,
+}
+
+impl ComparableReceipt {
+    pub fn new(tx: &BlockifierTransaction, execution_info: &TransactionExecutionInfo) -> Self {
+        Self {
+            tx_hash: BlockifierTransaction::tx_hash(tx),
+            actual_fee: execution_info.receipt.fee,
+            is_reverted: execution_info.is_reverted(),
+        }
+    }
+}
+
 pub fn reexecute_and_verify_correctness<
     S: StateReader + Send + Sync + Clone,
     T: ConsecutiveReexecutionStateReaders<S>,
@@ -220,6 +256,7 @@ pub fn reexecute_and_verify_correctness<
     consecutive_state_readers: T,
 ) -> Option<CachedState<S>> {
     let expected_state_diff = consecutive_state_readers.get_next_block_state_diff().unwrap();
+    let expected_receipts = consecutive_state_readers.get_next_block_receipts().unwrap();
 
     let all_txs_in_next_block = consecutive_state_readers.get_next_block_txs().unwrap();
 
@@ -232,6 +269,19 @@ pub fn reexecute_and_verify_correctness<
         assert_matches!(res, Ok(_));
     }
 
+    // Receipts are not available in offline data recorded before they were stored.
+    if let Some(expected_receipts) = expected_receipts {
+        let actual_receipts: Vec<ComparableReceipt> = all_txs_in_next_block
+            .iter()
+            .zip(&execution_results)
+            .map(|(tx, res)| ComparableReceipt::new(tx, res.as_ref().unwrap()))
+            .collect();
+        assert_eq!(
+            expected_receipts, actual_receipts,
+            "Expected and actual receipts do not match."
+        );
+    }
+
     // TODO(Yoav): Return the block state after the modifications in finalize().
     // Note that after finalizing, the block state is None.
     let block_state = transaction_executor.block_state.clone();
@@ -6,7 +6,14 @@ use blockifier::abi::constants as abi_constants;
 use blockifier::blockifier::config::{ContractClassManagerConfig, TransactionExecutorConfig};
 use blockifier::blockifier::transaction_executor::{TransactionExecutor, TransactionExecutorError};
 use blockifier::bouncer::BouncerConfig;
-use blockifier::context::{BlockContext, ChainInfo, FeeTokenAddresses};
+use blockifier::context::{
+    BlockContext,
+    ChainInfo,
+    CustomFeeTokens,
+    FeeDistribution,
+    FeeTokenAddresses,
+    PaymasterConfig,
+};
 use blockifier::execution::call_info::CallInfo;
 use blockifier::fee::receipt::TransactionReceipt;
 use blockifier::state::contract_class_manager::ContractClassManager;
@@ -22,6 +29,7 @@ use starknet_api::block::BlockNumber;
 use starknet_api::core::{ChainId, ContractAddress};
 use starknet_api::execution_resources::GasVector;
 use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{TransactionVersion, TransactionVersions};
 use starknet_types_core::felt::Felt;
 
 use crate::errors::{NativeBlockifierError, NativeBlockifierResult};
@@ -140,6 +148,7 @@ impl PyBlockExecutor {
             bouncer_config: bouncer_config.try_into().expect("Failed to parse bouncer config."),
             tx_executor_config: TransactionExecutorConfig {
                 concurrency_config: concurrency_config.into(),
+                ..Default::default()
             },
             chain_info: os_config.into_chain_info(),
             versioned_constants,
@@ -374,9 +383,11 @@ impl PyBlockExecutor {
                     state_diff_size: max_state_diff_size,
                     ..BouncerWeights::max()
                 },
+                ..BouncerConfig::max()
             },
             tx_executor_config: TransactionExecutorConfig {
                 concurrency_config: concurrency_config.into(),
+                ..Default::default()
             },
             storage: Box::new(PapyrusStorage::new_for_testing(path, &os_config.chain_id)),
             chain_info: os_config.into_chain_info(),
@@ -442,6 +453,8 @@ pub struct PyOsConfig {
     pub chain_id: ChainId,
     pub deprecated_fee_token_address: PyFelt,
     pub fee_token_address: PyFelt,
+    pub fee_distribution: PyFeeDistribution,
+    pub disabled_tx_versions: Vec<PyFelt>,
 }
 
 impl PyOsConfig {
@@ -464,6 +477,16 @@ impl TryFrom<PyOsConfig> for ChainInfo {
                     py_os_config.fee_token_address.0,
                 )?,
             },
+            custom_fee_tokens: CustomFeeTokens::default(),
+            disabled_tx_versions: TransactionVersions::new(
+                py_os_config
+                    .disabled_tx_versions
+                    .into_iter()
+                    .map(|version| TransactionVersion(version.0)),
+            ),
+            sierra_gas_only: false,
+            fee_distribution: py_os_config.fee_distribution.try_into()?,
+            paymaster: PaymasterConfig::default(),
         })
     }
 }
@@ -474,10 +497,33 @@ impl Default for PyOsConfig {
             chain_id: ChainId::Other("".to_string()),
             deprecated_fee_token_address: Default::default(),
             fee_token_address: Default::default(),
+            fee_distribution: Default::default(),
+            disabled_tx_versions: Default::default(),
         }
     }
 }
 
+#[derive(Clone, Default, FromPyObject)]
+pub struct PyFeeDistribution {
+    pub treasury_address: PyFelt,
+    pub treasury_percentage: u8,
+    pub burn_address: PyFelt,
+    pub burn_percentage: u8,
+}
+
+impl TryFrom<PyFeeDistribution> for FeeDistribution {
+    type Error = NativeBlockifierError;
+
+    fn try_from(py_fee_distribution: PyFeeDistribution) -> Result<Self, Self::Error> {
+        Ok(Self {
+            treasury_address: ContractAddress::try_from(py_fee_distribution.treasury_address.0)?,
+            treasury_percentage: py_fee_distribution.treasury_percentage,
+            burn_address: ContractAddress::try_from(py_fee_distribution.burn_address.0)?,
+            burn_percentage: py_fee_distribution.burn_percentage,
+        })
+    }
+}
+
 fn serialize_failure_reason(error: TransactionExecutorError) -> RawTransactionExecutionResult {
     // TODO(Yoni, 1/7/2024): re-consider this serialization.
     serde_json::to_vec(&format!("{}", error)).expect(RESULT_SERIALIZE_ERR)
@@ -3,13 +3,21 @@
 use std::collections::HashMap;
 
 use blockifier::abi::constants;
-use blockifier::blockifier::config::{ConcurrencyConfig, ContractClassManagerConfig};
+use blockifier::blockifier::config::{
+    ConcurrencyConfig,
+    ContractClassManagerConfig,
+    ValidationStrategy,
+    DEFAULT_NATIVE_EXECUTION_MAX_MEMORY_GROWTH,
+    DEFAULT_NATIVE_EXECUTION_STACK_SIZE,
+};
 use blockifier::bouncer::{BouncerConfig, BouncerWeights, BuiltinCount, HashMapWrapper};
+use blockifier::execution::execution_quotas::ExecutionQuotas;
 use blockifier::state::global_cache::GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST;
 use blockifier::versioned_constants::VersionedConstantsOverrides;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use pyo3::prelude::*;
+use starknet_api::core::ClassHash;
 use starknet_api::execution_resources::GasAmount;
 
 use crate::errors::{
@@ -18,6 +26,7 @@ use crate::errors::{
     NativeBlockifierInputError,
     NativeBlockifierResult,
 };
+use crate::py_utils::PyFelt;
 
 // From Rust to Python.
 
@@ -76,7 +85,13 @@ impl From<PyVersionedConstantsOverrides> for VersionedConstantsOverrides {
             max_recursion_depth,
             invoke_tx_max_n_steps,
         } = py_versioned_constants_overrides;
-        Self { validate_max_n_steps, max_recursion_depth, invoke_tx_max_n_steps }
+        Self {
+            validate_max_n_steps,
+            max_recursion_depth,
+            invoke_tx_max_n_steps,
+            execution_quotas: ExecutionQuotas::default(),
+            versioned_constants_path: None,
+        }
     }
 }
 
@@ -92,6 +107,7 @@ impl TryFrom<PyBouncerConfig> for BouncerConfig {
             block_max_capacity: hash_map_into_bouncer_weights(
                 py_bouncer_config.full_total_weights.clone(),
             )?,
+            reserved_capacity_percent: 0,
         })
     }
 }
@@ -126,6 +142,10 @@ fn hash_map_into_bouncer_weights(
     let state_diff_size =
         data.remove(constants::STATE_DIFF_SIZE).expect("state_diff_size must be present");
     let n_events = data.remove(constants::N_EVENTS).expect("n_events must be present");
+    // Newer dimensions are unbounded unless given.
+    let n_messages = data.remove(constants::N_MESSAGES).unwrap_or(usize::MAX);
+    let compressed_state_diff_size =
+        data.remove(constants::COMPRESSED_STATE_DIFF_SIZE).unwrap_or(usize::MAX);
     let sierra_gas = GasAmount(
         data.remove(constants::SIERRA_GAS)
             .expect("sierra_gas must be present")
@@ -137,7 +157,9 @@ fn hash_map_into_bouncer_weights(
         n_steps,
         message_segment_length,
         state_diff_size,
+        compressed_state_diff_size,
         n_events,
+        n_messages,
         builtin_count: hash_map_into_builtin_count(data)?,
         sierra_gas,
     })
@@ -156,15 +178,20 @@ impl From<PyConcurrencyConfig> for ConcurrencyConfig {
             enabled: py_concurrency_config.enabled,
             n_workers: py_concurrency_config.n_workers,
             chunk_size: py_concurrency_config.chunk_size,
+            adaptive_chunk_size: false,
+            validation_strategy: ValidationStrategy::Optimistic,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, FromPyObject)]
+#[derive(Debug, Clone, FromPyObject)]
 pub struct PyContractClassManagerConfig {
     pub run_cairo_native: bool,
     pub wait_on_native_compilation: bool,
     pub contract_cache_size: usize,
+    pub native_execution_disabled_class_hashes: Vec<PyFelt>,
+    pub native_execution_stack_size: usize,
+    pub native_execution_max_memory_growth: usize,
 }
 
 impl Default for PyContractClassManagerConfig {
@@ -173,6 +200,9 @@ impl Default for PyContractClassManagerConfig {
             run_cairo_native: false,
             wait_on_native_compilation: false,
             contract_cache_size: GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST,
+            native_execution_disabled_class_hashes: Vec::new(),
+            native_execution_stack_size: DEFAULT_NATIVE_EXECUTION_STACK_SIZE,
+            native_execution_max_memory_growth: DEFAULT_NATIVE_EXECUTION_MAX_MEMORY_GROWTH,
         }
     }
 }
@@ -183,6 +213,17 @@ impl From<PyContractClassManagerConfig> for ContractClassManagerConfig {
             run_cairo_native: py_contract_class_manager_config.run_cairo_native,
             wait_on_native_compilation: py_contract_class_manager_config.wait_on_native_compilation,
             contract_cache_size: py_contract_class_manager_config.contract_cache_size,
+            // Python callers bound the cache by its number of classes only.
+            contract_cache_size_in_bytes: usize::MAX,
+            native_execution_disabled_class_hashes: py_contract_class_manager_config
+                .native_execution_disabled_class_hashes
+                .into_iter()
+                .map(|class_hash| ClassHash(class_hash.0))
+                .collect(),
+            native_execution_stack_size: py_contract_class_manager_config
+                .native_execution_stack_size,
+            native_execution_max_memory_growth: py_contract_class_manager_config
+                .native_execution_max_memory_growth,
         }
     }
 }
@@ -1,10 +1,35 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use alloy_json_rpc::{ErrorPayload, RpcError};
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_eth::Log;
+use alloy_sol_types::SolEvent;
 use pretty_assertions::assert_eq;
 use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
-use starknet_api::felt;
+use starknet_api::core::EntryPointSelector;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::L1HandlerTransaction;
+use starknet_api::{calldata, contract_address, felt, nonce};
 
-use crate::ethereum_base_layer_contract::{EthereumBaseLayerConfig, EthereumBaseLayerContract};
-use crate::test_utils::get_test_ethereum_node;
-use crate::BaseLayerContract;
+use crate::ethereum_base_layer_contract::{
+    parse_event,
+    scan_logs_in_chunks,
+    EthereumBaseLayerConfig,
+    EthereumBaseLayerContract,
+    EthereumContractAddress,
+    Starknet,
+};
+use crate::test_utils::{get_test_ethereum_node, BaseLayerQuery, FakeBaseLayer};
+use crate::{
+    BaseLayerContract,
+    BaseLayerStatus,
+    Finality,
+    L1Event,
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+};
 
 // TODO: move to global test_utils crate and use everywhere instead of relying on the
 // confusing `#[ignore]` api to mark slow tests.
@@ -23,6 +48,7 @@ async fn latest_proved_block_ethereum() {
     let config = EthereumBaseLayerConfig {
         node_url: node_handle.0.endpoint().parse().unwrap(),
         starknet_contract_address,
+        subscription_node_url: None,
     };
     let contract = EthereumBaseLayerContract::new(config);
 
@@ -33,16 +59,219 @@ async fn latest_proved_block_ethereum() {
     let third_sn_state_update =
         BlockHashAndNumber { number: BlockNumber(300), hash: BlockHash(felt!("0x300")) };
 
-    type Scenario = (u64, Option<BlockHashAndNumber>);
+    type Scenario = (Finality, Option<BlockHashAndNumber>);
     let scenarios: Vec<Scenario> = vec![
-        (0, Some(third_sn_state_update)),
-        (5, Some(third_sn_state_update)),
-        (15, Some(second_sn_state_update)),
-        (25, Some(first_sn_state_update)),
-        (1000, None),
+        (Finality::Latest, Some(third_sn_state_update)),
+        (Finality::Confirmations(5), Some(third_sn_state_update)),
+        (Finality::Confirmations(15), Some(second_sn_state_update)),
+        (Finality::Confirmations(25), Some(first_sn_state_update)),
+        (Finality::Confirmations(1000), None),
     ];
     for (scenario, expected) in scenarios {
         let latest_block = contract.latest_proved_block(scenario).await.unwrap();
         assert_eq!(latest_block, expected);
     }
 }
+
+#[test]
+fn parse_log_message_to_l2_event() {
+    let l1_sender = EthereumContractAddress::repeat_byte(0x12);
+    let event = Starknet::LogMessageToL2 {
+        fromAddress: l1_sender,
+        toAddress: U256::from(0x34),
+        selector: U256::from(0x56),
+        payload: vec![U256::from(1), U256::from(2)],
+        nonce: U256::from(7),
+        fee: U256::from(100),
+    };
+    assert_eq!(Starknet::LogMessageToL2::SIGNATURE, LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER);
+    let log = Log {
+        inner: alloy_primitives::Log { address: l1_sender, data: event.encode_log_data() },
+        ..Default::default()
+    };
+
+    let expected_tx = L1HandlerTransaction {
+        version: L1HandlerTransaction::VERSION,
+        nonce: nonce!(7_u8),
+        contract_address: contract_address!(0x34_u8),
+        entry_point_selector: EntryPointSelector(felt!(0x56_u8)),
+        // The L1 sender is prepended to the payload.
+        calldata: calldata![
+            felt!("0x1212121212121212121212121212121212121212"),
+            felt!(1_u8),
+            felt!(2_u8)
+        ],
+    };
+    assert_eq!(
+        parse_event(log).unwrap(),
+        L1Event::LogMessageToL2 { tx: expected_tx, fee: Fee(100) }
+    );
+}
+
+#[test]
+fn message_cancellation_event_identifiers() {
+    assert_eq!(
+        Starknet::MessageToL2CancellationStarted::SIGNATURE,
+        MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER
+    );
+    assert_eq!(Starknet::MessageToL2Canceled::SIGNATURE, MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER);
+}
+
+#[tokio::test]
+// Note: the test requires ganache-cli installed, otherwise it is ignored.
+async fn get_price_sample_ethereum() {
+    if !in_ci() {
+        return;
+    }
+
+    let (node_handle, starknet_contract_address) = get_test_ethereum_node();
+    let config = EthereumBaseLayerConfig {
+        node_url: node_handle.0.endpoint().parse().unwrap(),
+        starknet_contract_address,
+        subscription_node_url: None,
+    };
+    let contract = EthereumBaseLayerContract::new(config);
+
+    let latest_block_number =
+        contract.latest_l1_block_number(Finality::Latest).await.unwrap().unwrap();
+    let price_sample = contract.get_price_sample(latest_block_number).await.unwrap().unwrap();
+    assert!(price_sample.base_fee_per_gas > 0);
+    assert_eq!(contract.get_price_sample(latest_block_number + 1).await.unwrap(), None);
+}
+
+fn log(block_number: u64, log_index: u64) -> Log {
+    Log {
+        block_hash: Some(B256::with_last_byte(u8::try_from(block_number).unwrap())),
+        block_number: Some(block_number),
+        log_index: Some(log_index),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn scan_logs_in_chunks_splits_chunks_with_too_many_results() {
+    let chunk_size = 10;
+    let logs = [log(2, 0), log(2, 1), log(12, 2), log(17, 3)];
+    let mut requested_ranges = Vec::new();
+    let scanned_logs = scan_logs_in_chunks(0..=24, chunk_size, |from_block, to_block| {
+        requested_ranges.push((from_block, to_block));
+        let chunk_logs: Vec<Log> = logs
+            .iter()
+            .filter(|log| (from_block..=to_block).contains(&log.block_number.unwrap()))
+            .cloned()
+            .collect();
+        async move {
+            // Nodes limit the number of logs returned by a single query.
+            if chunk_logs.len() > 1 {
+                return Err(RpcError::ErrorResp(ErrorPayload {
+                    code: -32005,
+                    message: "query returned more than 1 results".into(),
+                    data: None,
+                }));
+            }
+            Ok(chunk_logs)
+        }
+    })
+    .await;
+
+    // The logs of block 2 can't be split, so the scan fails on it.
+    assert!(scanned_logs.is_err());
+    assert_eq!(requested_ranges, [(0, 9), (0, 4), (0, 1), (2, 3), (2, 2)]);
+}
+
+#[tokio::test]
+async fn scan_logs_in_chunks_deduplicates_logs() {
+    let chunk_size = 10;
+    let logs = [log(2, 0), log(12, 1), log(17, 2)];
+    let mut requested_ranges = Vec::new();
+    let scanned_logs = scan_logs_in_chunks(0..=24, chunk_size, |from_block, to_block| {
+        requested_ranges.push((from_block, to_block));
+        let mut chunk_logs: Vec<Log> = logs
+            .iter()
+            .filter(|log| (from_block..=to_block).contains(&log.block_number.unwrap()))
+            .cloned()
+            .collect();
+        // The node returns a log of a previous chunk again.
+        if from_block == 20 {
+            chunk_logs.push(logs[2].clone());
+        }
+        async move {
+            if chunk_logs.len() > 1 {
+                return Err(RpcError::ErrorResp(ErrorPayload {
+                    code: -32005,
+                    message: "Query returned more than 1 results".into(),
+                    data: None,
+                }));
+            }
+            Ok(chunk_logs)
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(scanned_logs, logs);
+    // The second chunk has too many results, so it and the following chunks are halved.
+    assert_eq!(requested_ranges, [(0, 9), (10, 19), (10, 14), (15, 19), (20, 24)]);
+}
+
+#[tokio::test]
+async fn status_reports_sync_lag() {
+    let latest_proved_block =
+        BlockHashAndNumber { number: BlockNumber(9), hash: BlockHash(felt!("0x9")) };
+    let base_layer = FakeBaseLayer {
+        latest_l1_block: Mutex::new(100),
+        latest_proved_block: Some(latest_proved_block),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        base_layer.status(Finality::Confirmations(10), BlockNumber(15)).await,
+        BaseLayerStatus {
+            latest_l1_block: Some(90),
+            latest_proved_block: Some(latest_proved_block),
+            sync_lag: Some(5),
+            is_healthy: true,
+            error: None,
+        }
+    );
+    // The node is behind the base layer.
+    assert_eq!(
+        base_layer.status(Finality::Confirmations(10), BlockNumber(3)).await.sync_lag,
+        Some(0)
+    );
+}
+
+#[tokio::test]
+async fn status_reports_unhealthy_endpoint() {
+    let base_layer = FakeBaseLayer {
+        latest_l1_block: Mutex::new(100),
+        failing_queries: Mutex::new(HashSet::from([BaseLayerQuery::LatestProvedBlock])),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        base_layer.status(Finality::Confirmations(10), BlockNumber(15)).await,
+        BaseLayerStatus {
+            latest_l1_block: Some(90),
+            latest_proved_block: None,
+            sync_lag: None,
+            is_healthy: false,
+            error: Some("L1 subscription error: LatestProvedBlock is unreachable.".to_string()),
+        }
+    );
+}
+
+#[test]
+fn finality_serialization() {
+    let serialized_finalities = [
+        (Finality::Latest, r#""latest""#),
+        (Finality::Safe, r#""safe""#),
+        (Finality::Finalized, r#""finalized""#),
+        (Finality::Confirmations(10), r#""10""#),
+    ];
+    for (finality, serialized) in serialized_finalities {
+        assert_eq!(serde_json::to_string(&finality).unwrap(), serialized);
+        assert_eq!(serde_json::from_str::<Finality>(serialized).unwrap(), finality);
+    }
+    assert!(serde_json::from_str::<Finality>(r#""unsafe""#).is_err());
+}
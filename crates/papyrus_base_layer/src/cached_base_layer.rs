@@ -0,0 +1,197 @@
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use lru::LruCache;
+use starknet_api::block::BlockHashAndNumber;
+use starknet_api::core::GlobalRoot;
+
+use crate::reorg_monitor::RevertedRange;
+use crate::{BaseLayerContract, Finality, L1Event, PriceSample};
+
+#[cfg(test)]
+#[path = "cached_base_layer_test.rs"]
+mod cached_base_layer_test;
+
+// The L1 block range (inclusive) of an events query, with the identifiers of the queried events;
+// message events are keyed with no identifiers.
+type EventsKey = (u64, u64, Option<Vec<String>>);
+
+#[derive(Debug)]
+struct Caches {
+    // The highest L1 block known to have the required finality.
+    finalized_l1_block: Option<u64>,
+    events: LruCache<EventsKey, Vec<L1Event>>,
+    price_samples: LruCache<u64, PriceSample>,
+}
+
+impl Caches {
+    // Forgets everything read from the given L1 blocks.
+    fn invalidate(&mut self, range: RevertedRange) {
+        let overlaps = |from_block: u64, until_block: u64| {
+            from_block <= range.until_block && range.from_block <= until_block
+        };
+        let stale_events: Vec<_> = self
+            .events
+            .iter()
+            .filter(|((from_block, until_block, _), _)| overlaps(*from_block, *until_block))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale_events {
+            self.events.pop(&key);
+        }
+        for block_number in range.from_block..=range.until_block {
+            self.price_samples.pop(&block_number);
+        }
+        // The reorg was deeper than the finality, so finality has to be re-established.
+        if self.finalized_l1_block.is_some_and(|finalized| finalized >= range.from_block) {
+            self.finalized_l1_block = range.from_block.checked_sub(1);
+        }
+    }
+}
+
+/// Wraps a base layer, caching the results of queries that can't change anymore: events and price
+/// samples of L1 blocks with the given `finality`. Queries about the latest state of
+/// the base layer are always forwarded. Cached data of L1 blocks reported as reverted by
+/// [`BaseLayerContract::detect_reorg`] is dropped.
+#[derive(Debug)]
+pub struct CachedBaseLayer<B> {
+    base_layer: B,
+    finality: Finality,
+    caches: Mutex<Caches>,
+}
+
+impl<B: BaseLayerContract> CachedBaseLayer<B> {
+    pub fn new(base_layer: B, finality: Finality, cache_size: NonZeroUsize) -> Self {
+        Self {
+            base_layer,
+            finality,
+            caches: Mutex::new(Caches {
+                finalized_l1_block: None,
+                events: LruCache::new(cache_size),
+                price_samples: LruCache::new(cache_size),
+            }),
+        }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.base_layer
+    }
+
+    // Returns true if the given L1 block is final enough to be cached; the latest final
+    // L1 block is only re-read when the block is above the last one known to be final.
+    async fn is_final(&self, l1_block: u64) -> Result<bool, B::Error> {
+        let finalized_l1_block = self.caches().finalized_l1_block;
+        if finalized_l1_block.is_some_and(|finalized| finalized >= l1_block) {
+            return Ok(true);
+        }
+        let finalized_l1_block = self.base_layer.latest_l1_block_number(self.finality).await?;
+        self.caches().finalized_l1_block = finalized_l1_block;
+        Ok(finalized_l1_block.is_some_and(|finalized| finalized >= l1_block))
+    }
+
+    async fn cached_events<Fut>(&self, key: EventsKey, query: Fut) -> Result<Vec<L1Event>, B::Error>
+    where
+        Fut: Future<Output = Result<Vec<L1Event>, B::Error>>,
+    {
+        let cached_events = self.caches().events.get(&key).cloned();
+        if let Some(events) = cached_events {
+            return Ok(events);
+        }
+        let until_block = key.1;
+        let events = query.await?;
+        if self.is_final(until_block).await? {
+            self.caches().events.put(key, events.clone());
+        }
+        Ok(events)
+    }
+
+    fn caches(&self) -> MutexGuard<'_, Caches> {
+        self.caches.lock().expect("Base layer caches lock should not be poisoned.")
+    }
+}
+
+#[async_trait]
+impl<B> BaseLayerContract for CachedBaseLayer<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    B::Error: Send,
+{
+    type Error = B::Error;
+
+    async fn latest_proved_block(
+        &self,
+        finality: Finality,
+    ) -> Result<Option<BlockHashAndNumber>, Self::Error> {
+        self.base_layer.latest_proved_block(finality).await
+    }
+
+    async fn proved_state_root(
+        &self,
+        finality: Finality,
+    ) -> Result<Option<GlobalRoot>, Self::Error> {
+        self.base_layer.proved_state_root(finality).await
+    }
+
+    async fn events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+        event_identifiers: &[&str],
+    ) -> Result<Vec<L1Event>, Self::Error> {
+        let identifiers = event_identifiers.iter().map(|identifier| identifier.to_string());
+        let key = (from_block, until_block, Some(identifiers.collect()));
+        self.cached_events(key, self.base_layer.events(from_block, until_block, event_identifiers))
+            .await
+    }
+
+    async fn l1_to_l2_message_events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+    ) -> Result<Vec<L1Event>, Self::Error> {
+        let key = (from_block, until_block, None);
+        self.cached_events(key, self.base_layer.l1_to_l2_message_events(from_block, until_block))
+            .await
+    }
+
+    async fn latest_l1_block_number(&self, finality: Finality) -> Result<Option<u64>, Self::Error> {
+        let latest_l1_block_number = self.base_layer.latest_l1_block_number(finality).await?;
+        // A block with more confirmations than required is final as well.
+        let is_at_least_as_final = match (finality.confirmations(), self.finality.confirmations()) {
+            (Some(confirmations), Some(required)) => confirmations >= required,
+            _ => finality == self.finality,
+        };
+        if is_at_least_as_final {
+            let mut caches = self.caches();
+            caches.finalized_l1_block = caches.finalized_l1_block.max(latest_l1_block_number);
+        }
+        Ok(latest_l1_block_number)
+    }
+
+    async fn get_price_sample(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<PriceSample>, Self::Error> {
+        let cached_price_sample = self.caches().price_samples.get(&block_number).copied();
+        if cached_price_sample.is_some() {
+            return Ok(cached_price_sample);
+        }
+        let price_sample = self.base_layer.get_price_sample(block_number).await?;
+        if let Some(price_sample) = price_sample {
+            if self.is_final(block_number).await? {
+                self.caches().price_samples.put(block_number, price_sample);
+            }
+        }
+        Ok(price_sample)
+    }
+
+    async fn detect_reorg(&self) -> Result<Option<RevertedRange>, Self::Error> {
+        let reverted_range = self.base_layer.detect_reorg().await?;
+        if let Some(range) = reverted_range {
+            self.caches().invalidate(range);
+        }
+        Ok(reverted_range)
+    }
+}
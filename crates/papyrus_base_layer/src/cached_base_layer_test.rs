@@ -0,0 +1,65 @@
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use pretty_assertions::assert_eq;
+
+use crate::cached_base_layer::CachedBaseLayer;
+use crate::reorg_monitor::RevertedRange;
+use crate::test_utils::FakeBaseLayer;
+use crate::{BaseLayerContract, Finality, LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER};
+
+const LATEST_L1_BLOCK: u64 = 20;
+const FINALITY: Finality = Finality::Confirmations(5);
+
+fn cached_base_layer(base_layer: FakeBaseLayer) -> CachedBaseLayer<FakeBaseLayer> {
+    *base_layer.latest_l1_block.lock().unwrap() = LATEST_L1_BLOCK;
+    CachedBaseLayer::new(base_layer, FINALITY, NonZeroUsize::new(10).unwrap())
+}
+
+#[tokio::test]
+async fn only_final_data_is_cached() {
+    let base_layer = cached_base_layer(FakeBaseLayer::default());
+    let identifiers = [LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER];
+
+    for _ in 0..2 {
+        base_layer.events(0, 10, &identifiers).await.unwrap();
+        base_layer.l1_to_l2_message_events(0, 10).await.unwrap();
+        base_layer.get_price_sample(10).await.unwrap();
+        // Not final yet, so queried every time.
+        base_layer.get_price_sample(LATEST_L1_BLOCK).await.unwrap();
+    }
+    assert_eq!(
+        base_layer.inner().take_queries(),
+        [
+            "events 0-10",
+            "latest 5",
+            "messages 0-10",
+            "price 10",
+            "price 20",
+            "latest 5",
+            "price 20",
+            "latest 5",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn reorg_invalidates_cached_data() {
+    let base_layer = cached_base_layer(FakeBaseLayer {
+        reverted_range: Mutex::new(Some(RevertedRange { from_block: 8, until_block: 9 })),
+        ..Default::default()
+    });
+    base_layer.latest_l1_block_number(FINALITY).await.unwrap();
+    base_layer.l1_to_l2_message_events(0, 5).await.unwrap();
+    base_layer.l1_to_l2_message_events(6, 10).await.unwrap();
+    base_layer.get_price_sample(9).await.unwrap();
+    base_layer.inner().take_queries();
+
+    base_layer.detect_reorg().await.unwrap();
+    assert_eq!(base_layer.inner().take_queries(), ["reorg"]);
+    base_layer.l1_to_l2_message_events(0, 5).await.unwrap();
+    base_layer.l1_to_l2_message_events(6, 10).await.unwrap();
+    base_layer.get_price_sample(9).await.unwrap();
+    // The finality of the reverted blocks is re-established once.
+    assert_eq!(base_layer.inner().take_queries(), ["messages 6-10", "latest 5", "price 9"]);
+}
@@ -1,24 +1,54 @@
-use std::collections::BTreeMap;
-use std::future::IntoFuture;
+use std::collections::{BTreeMap, HashSet};
+use std::future::{Future, IntoFuture};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 use alloy_dyn_abi::SolType;
 use alloy_json_rpc::RpcError;
+use alloy_primitives::ruint::FromUintError;
 pub(crate) use alloy_primitives::Address as EthereumContractAddress;
+use alloy_primitives::{B256, U256};
 use alloy_provider::network::Ethereum;
 use alloy_provider::{Provider, ProviderBuilder, RootProvider};
-use alloy_sol_types::{sol, sol_data};
-use alloy_transport::TransportErrorKind;
+use alloy_rpc_types_eth::{BlockNumberOrTag, Filter as EthEventFilter, Log};
+use alloy_sol_types::{sol, sol_data, SolEventInterface};
+use alloy_transport::{TransportErrorKind, TransportResult};
 use alloy_transport_http::{Client, Http};
 use async_trait::async_trait;
-use papyrus_config::dumping::{ser_param, ser_required_param, SerializeConfig};
+use papyrus_config::dumping::{ser_optional_param, ser_param, ser_required_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
+use starknet_api::core::{ContractAddress, EntryPointSelector, EthAddress, GlobalRoot, Nonce};
 use starknet_api::hash::StarkHash;
-use starknet_types_core::felt;
+use starknet_api::transaction::fields::{Calldata, Fee};
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::{self, Felt};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use url::Url;
 
-use crate::{BaseLayerContract, L1Event};
+use crate::ethereum_subscription::{run_subscription, SubscribedL1Data};
+use crate::reorg_monitor::{L1BlockHash, L1ReorgMonitor, RevertedRange};
+use crate::{
+    BaseLayerContract,
+    EventData,
+    Finality,
+    L1Event,
+    PriceSample,
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+};
+
+// The number of L1 blocks tracked for reorg detection; deeper reorgs are reported from the oldest
+// tracked block.
+const REORG_MONITOR_CAPACITY: usize = 128;
+
+// The number of L1 blocks whose logs are requested at once when scanning a range of blocks.
+pub(crate) const LOG_SCAN_CHUNK_SIZE: u64 = 10_000;
+
+// Parts of the error messages nodes respond with when a logs query exceeds their result limit.
+const RESULT_LIMIT_ERROR_MESSAGES: [&str; 3] =
+    ["query returned more than", "response size exceeded", "block range"];
 
 type EthereumBaseLayerResult<T> = Result<T, EthereumBaseLayerError>;
 
@@ -34,15 +64,65 @@ sol!(
 pub struct EthereumBaseLayerContract {
     pub config: EthereumBaseLayerConfig,
     pub contract: Starknet::StarknetInstance<Http<Client>, RootProvider<Http<Client>>, Ethereum>,
+    reorg_monitor: Mutex<L1ReorgMonitor>,
+    // Set in subscription mode, see `EthereumBaseLayerConfig::subscription_node_url`.
+    subscribed_data: Option<Arc<Mutex<SubscribedL1Data>>>,
+    subscription_task: Option<JoinHandle<()>>,
 }
 
 impl EthereumBaseLayerContract {
+    /// Creates the contract; in subscription mode, must be called within a Tokio runtime, on which
+    /// the subscription task is spawned.
     pub fn new(config: EthereumBaseLayerConfig) -> Self {
         let l1_client = ProviderBuilder::new().on_http(config.node_url.clone());
         // This type is generated from `sol!` macro, and the `new` method assumes it is already
         // deployed at L1, and wraps it with a type.
         let contract = Starknet::new(config.starknet_contract_address, l1_client);
-        Self { contract, config }
+        let (subscribed_data, subscription_task) = match config.subscription_node_url.clone() {
+            Some(ws_url) => {
+                let subscribed_data = Arc::new(Mutex::new(SubscribedL1Data::default()));
+                let subscription_task = tokio::spawn(run_subscription(
+                    ws_url,
+                    contract.clone(),
+                    subscribed_data.clone(),
+                ));
+                (Some(subscribed_data), Some(subscription_task))
+            }
+            None => (None, None),
+        };
+        Self {
+            contract,
+            config,
+            reorg_monitor: Mutex::new(L1ReorgMonitor::new(REORG_MONITOR_CAPACITY)),
+            subscribed_data,
+            subscription_task,
+        }
+    }
+
+    /// Returns the logs matching the filter in the given range of L1 blocks, in the order they
+    /// were emitted. The range is scanned in chunks, and a chunk for which the node returns too
+    /// many results is split further, so the range may span the entire chain.
+    pub async fn scan_logs(
+        &self,
+        range: RangeInclusive<u64>,
+        filter: EthEventFilter,
+    ) -> EthereumBaseLayerResult<Vec<Log>> {
+        scan_logs_in_chunks(range, LOG_SCAN_CHUNK_SIZE, |from_block, to_block| {
+            let chunk_filter = filter.clone().from_block(from_block).to_block(to_block);
+            async move { self.contract.provider().get_logs(&chunk_filter).await }
+        })
+        .await
+    }
+
+    /// Returns the number and hash of the given L1 block, if it exists.
+    async fn l1_block_hash(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> EthereumBaseLayerResult<Option<(u64, L1BlockHash)>> {
+        let hydrate_transactions = false;
+        let block =
+            self.contract.provider().get_block_by_number(block, hydrate_transactions).await?;
+        Ok(block.map(|block| (block.header.number, block.header.hash.0)))
     }
 }
 
@@ -50,15 +130,20 @@ impl EthereumBaseLayerContract {
 impl BaseLayerContract for EthereumBaseLayerContract {
     type Error = EthereumBaseLayerError;
 
-    /// Returns the latest proved block on Ethereum, where finality determines how many
-    /// blocks back (0 = latest).
     async fn latest_proved_block(
         &self,
-        finality: u64,
+        finality: Finality,
     ) -> EthereumBaseLayerResult<Option<BlockHashAndNumber>> {
         let Some(ethereum_block_number) = self.latest_l1_block_number(finality).await? else {
             return Ok(None);
         };
+        if let Some(subscribed_data) = &self.subscribed_data {
+            if let Some(block) =
+                subscribed_data.lock().await.latest_proved_block(ethereum_block_number)
+            {
+                return Ok(Some(block));
+            }
+        }
 
         let call_state_block_number =
             self.contract.stateBlockNumber().block(ethereum_block_number.into());
@@ -79,24 +164,242 @@ impl BaseLayerContract for EthereumBaseLayerContract {
         }))
     }
 
+    async fn proved_state_root(
+        &self,
+        finality: Finality,
+    ) -> EthereumBaseLayerResult<Option<GlobalRoot>> {
+        let Some(ethereum_block_number) = self.latest_l1_block_number(finality).await? else {
+            return Ok(None);
+        };
+        let state_root =
+            self.contract.stateRoot().block(ethereum_block_number.into()).call_raw().await?;
+
+        let validate = true;
+        let state_root = sol_data::FixedBytes::<32>::abi_decode(&state_root, validate)?;
+        Ok(Some(GlobalRoot(StarkHash::from_bytes_be(&state_root))))
+    }
+
     async fn events(
         &self,
-        _from_block: u64,
-        _until_block: u64,
-        _event_identifiers: &[&str],
+        from_block: u64,
+        until_block: u64,
+        event_identifiers: &[&str],
+    ) -> EthereumBaseLayerResult<Vec<L1Event>> {
+        let filter = EthEventFilter::new()
+            .address(self.config.starknet_contract_address)
+            .events(event_identifiers);
+        let matching_logs = self.scan_logs(from_block..=until_block, filter).await?;
+        matching_logs.into_iter().map(parse_event).collect()
+    }
+
+    async fn l1_to_l2_message_events(
+        &self,
+        from_block: u64,
+        until_block: u64,
     ) -> EthereumBaseLayerResult<Vec<L1Event>> {
-        todo!("Implmeneted in a subsequent commit")
+        self.events(from_block, until_block, &[LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER]).await
     }
 
-    async fn latest_l1_block_number(&self, finality: u64) -> EthereumBaseLayerResult<Option<u64>> {
-        Ok(self.contract.provider().get_block_number().await?.checked_sub(finality))
+    async fn latest_l1_block_number(
+        &self,
+        finality: Finality,
+    ) -> EthereumBaseLayerResult<Option<u64>> {
+        // The node tracks which blocks are safe and finalized.
+        let tag = match finality {
+            Finality::Safe => Some(BlockNumberOrTag::Safe),
+            Finality::Finalized => Some(BlockNumberOrTag::Finalized),
+            Finality::Latest | Finality::Confirmations(_) => None,
+        };
+        if let Some(tag) = tag {
+            return Ok(self.l1_block_hash(tag).await?.map(|(block_number, _)| block_number));
+        }
+
+        let subscribed_block_number = match &self.subscribed_data {
+            Some(subscribed_data) => subscribed_data.lock().await.latest_l1_block_number(),
+            None => None,
+        };
+        let block_number = match subscribed_block_number {
+            Some(block_number) => block_number,
+            None => self.contract.provider().get_block_number().await?,
+        };
+        Ok(finality
+            .confirmations()
+            .and_then(|confirmations| block_number.checked_sub(confirmations)))
+    }
+
+    async fn get_price_sample(
+        &self,
+        block_number: u64,
+    ) -> EthereumBaseLayerResult<Option<PriceSample>> {
+        let hydrate_transactions = false;
+        let Some(block) = self
+            .contract
+            .provider()
+            .get_block_by_number(block_number.into(), hydrate_transactions)
+            .await?
+        else {
+            return Ok(None);
+        };
+        // Blocks preceding EIP-1559 (base fee) and EIP-4844 (blobs) have no such fees.
+        Ok(Some(PriceSample {
+            timestamp: block.header.timestamp,
+            base_fee_per_gas: block.header.base_fee_per_gas.unwrap_or_default(),
+            blob_base_fee: block.header.blob_fee().unwrap_or_default(),
+        }))
+    }
+
+    async fn detect_reorg(&self) -> EthereumBaseLayerResult<Option<RevertedRange>> {
+        let mut reorg_monitor = self.reorg_monitor.lock().await;
+        let reverted_range = reorg_monitor
+            .detect_reorg(|block_number| async move {
+                let block = self.l1_block_hash(block_number.into()).await?;
+                Ok::<_, EthereumBaseLayerError>(block.map(|(_, block_hash)| block_hash))
+            })
+            .await?;
+        // Observe the current head, so that the next detection covers it.
+        if let Some((block_number, block_hash)) =
+            self.l1_block_hash(BlockNumberOrTag::Latest).await?
+        {
+            reorg_monitor.observe(block_number, block_hash);
+        }
+        Ok(reverted_range)
     }
 }
 
+impl Drop for EthereumBaseLayerContract {
+    fn drop(&mut self) {
+        if let Some(subscription_task) = &self.subscription_task {
+            subscription_task.abort();
+        }
+    }
+}
+
+/// Decodes a log of the Starknet contract into an L1 event.
+pub(crate) fn parse_event(log: Log) -> EthereumBaseLayerResult<L1Event> {
+    let validate = true;
+    let event = Starknet::StarknetEvents::decode_log(&log.inner, validate)?.data;
+    match event {
+        Starknet::StarknetEvents::LogMessageToL2(event) => {
+            let fee = Fee(event.fee.try_into().map_err(EthereumBaseLayerError::FeeOutOfRange)?);
+            let event_data = event_data(
+                event.fromAddress,
+                event.toAddress,
+                event.selector,
+                &event.payload,
+                event.nonce,
+            )?;
+            Ok(L1Event::LogMessageToL2 { tx: event_data.l1_handler_tx(), fee })
+        }
+        Starknet::StarknetEvents::ConsumedMessageToL2(event) => {
+            Ok(L1Event::ConsumedMessageToL2(event_data(
+                event.fromAddress,
+                event.toAddress,
+                event.selector,
+                &event.payload,
+                event.nonce,
+            )?))
+        }
+        Starknet::StarknetEvents::MessageToL2CancellationStarted(event) => {
+            Ok(L1Event::MessageToL2CancellationStarted(event_data(
+                event.fromAddress,
+                event.toAddress,
+                event.selector,
+                &event.payload,
+                event.nonce,
+            )?))
+        }
+        Starknet::StarknetEvents::MessageToL2Canceled(event) => {
+            Ok(L1Event::MessageToL2Canceled(event_data(
+                event.fromAddress,
+                event.toAddress,
+                event.selector,
+                &event.payload,
+                event.nonce,
+            )?))
+        }
+        // Decoding succeeded, so the log has the event selector as its first topic.
+        _ => Err(EthereumBaseLayerError::UnhandledL1Event { selector: log.inner.topics()[0] }),
+    }
+}
+
+fn event_data(
+    from_address: EthereumContractAddress,
+    to_address: U256,
+    selector: U256,
+    payload: &[U256],
+    nonce: U256,
+) -> EthereumBaseLayerResult<EventData> {
+    Ok(EventData {
+        from_address: EthAddress::try_from(Felt::from_bytes_be_slice(from_address.as_slice()))?,
+        to_address: ContractAddress::try_from(felt_from_u256(to_address))?,
+        entry_point_selector: EntryPointSelector(felt_from_u256(selector)),
+        payload: Calldata(Arc::new(payload.iter().copied().map(felt_from_u256).collect())),
+        nonce: Nonce(felt_from_u256(nonce)),
+    })
+}
+
+/// Scans the logs of the given range of L1 blocks, fetching the logs of each chunk with
+/// `get_logs(from_block, to_block)`. See [`EthereumBaseLayerContract::scan_logs`].
+pub(crate) async fn scan_logs_in_chunks<F, Fut>(
+    range: RangeInclusive<u64>,
+    chunk_size: u64,
+    mut get_logs: F,
+) -> EthereumBaseLayerResult<Vec<Log>>
+where
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = TransportResult<Vec<Log>>>,
+{
+    let mut logs = Vec::new();
+    // A log may be returned twice if the node's view changes between chunks.
+    let mut scanned_logs = HashSet::new();
+    let mut chunk_size = chunk_size.max(1);
+    let mut from_block = *range.start();
+    while from_block <= *range.end() {
+        let to_block = (*range.end()).min(from_block.saturating_add(chunk_size - 1));
+        match get_logs(from_block, to_block).await {
+            Ok(chunk_logs) => {
+                for log in chunk_logs {
+                    if scanned_logs.insert((log.block_hash, log.log_index)) {
+                        logs.push(log);
+                    }
+                }
+                let Some(next_block) = to_block.checked_add(1) else {
+                    break;
+                };
+                from_block = next_block;
+            }
+            Err(err) if to_block > from_block && is_result_limit_error(&err) => {
+                // The following chunks are kept small too, as logs tend to be dense in
+                // consecutive blocks.
+                chunk_size = (to_block - from_block + 1) / 2;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(logs)
+}
+
+// Whether the node refused a logs query because it matches too many results.
+fn is_result_limit_error(err: &RpcError<TransportErrorKind>) -> bool {
+    let RpcError::ErrorResp(payload) = err else {
+        return false;
+    };
+    let message = payload.message.to_lowercase();
+    RESULT_LIMIT_ERROR_MESSAGES.iter().any(|limit_message| message.contains(limit_message))
+}
+
+// The Starknet contract only accepts message fields that are valid felts, so no reduction
+// happens here.
+fn felt_from_u256(num: U256) -> Felt {
+    Felt::from_bytes_be(&num.to_be_bytes())
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum EthereumBaseLayerError {
     #[error(transparent)]
     Contract(#[from] alloy_contract::Error),
+    #[error("The fee of a message to L2 does not fit in u128: {0:?}.")]
+    FeeOutOfRange(FromUintError<u128>),
     #[error(transparent)]
     FeltParseError(#[from] felt::FromStrError),
     #[error(transparent)]
@@ -104,13 +407,24 @@ pub enum EthereumBaseLayerError {
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
     #[error(transparent)]
+    StarknetApi(#[from] StarknetApiError),
+    #[error("L1 subscription error: {0}")]
+    Subscription(String),
+    #[error(transparent)]
     TypeError(#[from] alloy_sol_types::Error),
+    #[error("Unhandled L1 event with selector {selector}.")]
+    UnhandledL1Event { selector: B256 },
+    #[error(transparent)]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EthereumBaseLayerConfig {
     pub node_url: Url,
     pub starknet_contract_address: EthereumContractAddress,
+    // If set, the latest L1 block and proved Starknet block are pushed by the node over this
+    // WebSocket URL, instead of being polled.
+    pub subscription_node_url: Option<Url>,
 }
 
 impl SerializeConfig for EthereumBaseLayerConfig {
@@ -129,6 +443,16 @@ impl SerializeConfig for EthereumBaseLayerConfig {
                 ParamPrivacyInput::Public,
             ),
         ])
+        .into_iter()
+        .chain(ser_optional_param(
+            &self.subscription_node_url,
+            "wss://mainnet.infura.io/ws/v3/<your_api_key>".parse().unwrap(),
+            "subscription_node_url",
+            "Ethereum node WebSocket URL. If set, the latest L1 block and proved Starknet block \
+             are pushed by the node through eth_subscribe, instead of being polled.",
+            ParamPrivacyInput::Private,
+        ))
+        .collect()
     }
 }
 
@@ -140,6 +464,7 @@ impl Default for EthereumBaseLayerConfig {
         Self {
             node_url: "https://mainnet.infura.io/v3/<your_api_key>".parse().unwrap(),
             starknet_contract_address,
+            subscription_node_url: None,
         }
     }
 }
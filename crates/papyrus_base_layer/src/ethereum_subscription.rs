@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy_dyn_abi::SolType;
+use alloy_primitives::U64;
+use alloy_provider::network::Ethereum;
+use alloy_provider::RootProvider;
+use alloy_rpc_types_eth::Log;
+use alloy_sol_types::{sol_data, SolEvent};
+use alloy_transport_http::{Client, Http};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
+use starknet_api::hash::StarkHash;
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::ethereum_base_layer_contract::{EthereumBaseLayerError, Starknet};
+
+#[cfg(test)]
+#[path = "ethereum_subscription_test.rs"]
+mod ethereum_subscription_test;
+
+type StarknetContract =
+    Starknet::StarknetInstance<Http<Client>, RootProvider<Http<Client>>, Ethereum>;
+
+// How long to wait before reconnecting after the subscription fails.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+// The number of recent state updates kept; older ones are read over HTTP.
+pub(crate) const STATE_UPDATES_CAPACITY: usize = 128;
+const NEW_HEADS_REQUEST_ID: u64 = 1;
+const LOGS_REQUEST_ID: u64 = 2;
+
+/// The L1 data pushed by the Ethereum node over a WebSocket subscription. While the subscription
+/// is down the data is discarded, as notifications may have been missed.
+#[derive(Debug, Default)]
+pub(crate) struct SubscribedL1Data {
+    latest_l1_block_number: Option<u64>,
+    // The Starknet blocks proved by the state updates logged since the subscription started, by
+    // the L1 block they were logged in.
+    state_updates: BTreeMap<u64, BlockHashAndNumber>,
+}
+
+impl SubscribedL1Data {
+    pub(crate) fn latest_l1_block_number(&self) -> Option<u64> {
+        self.latest_l1_block_number
+    }
+
+    /// The latest Starknet block proved at or before the given L1 block, if it was proved since
+    /// the subscription started; otherwise, it has to be read from the contract.
+    pub(crate) fn latest_proved_block(&self, l1_block_number: u64) -> Option<BlockHashAndNumber> {
+        self.state_updates.range(..=l1_block_number).next_back().map(|(_, block)| *block)
+    }
+
+    fn observe_head(&mut self, l1_block_number: u64) {
+        self.latest_l1_block_number = Some(l1_block_number);
+    }
+
+    fn observe_state_update(&mut self, l1_block_number: u64, block: BlockHashAndNumber) {
+        self.state_updates.insert(l1_block_number, block);
+        while self.state_updates.len() > STATE_UPDATES_CAPACITY {
+            self.state_updates.pop_first();
+        }
+    }
+
+    // A state update log was removed from the canonical chain by a reorg.
+    fn remove_state_update(&mut self, l1_block_number: u64) {
+        self.state_updates.remove(&l1_block_number);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NewHead {
+    number: U64,
+}
+
+/// Subscribes to new L1 heads and to the state updates of the Starknet contract, reconnecting
+/// whenever the subscription fails; runs until aborted.
+pub(crate) async fn run_subscription(
+    ws_url: Url,
+    contract: StarknetContract,
+    data: Arc<Mutex<SubscribedL1Data>>,
+) {
+    loop {
+        match subscribe(&ws_url, &contract, &data).await {
+            Ok(()) => {
+                warn!("The node closed the L1 subscription, reconnecting in {RECONNECT_DELAY:?}.")
+            }
+            Err(err) => {
+                warn!("The L1 subscription failed, reconnecting in {RECONNECT_DELAY:?}: {err}")
+            }
+        }
+        data.lock().await.reset();
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn subscribe(
+    ws_url: &Url,
+    contract: &StarknetContract,
+    data: &Mutex<SubscribedL1Data>,
+) -> Result<(), EthereumBaseLayerError> {
+    let (mut ws_stream, _) = tokio_tungstenite::connect_async(ws_url.as_str()).await?;
+    let logs_filter = json!({
+        "address": contract.address(),
+        "topics": [Starknet::LogStateUpdate::SIGNATURE_HASH],
+    });
+    for (id, params) in [
+        (NEW_HEADS_REQUEST_ID, json!(["newHeads"])),
+        (LOGS_REQUEST_ID, json!(["logs", logs_filter])),
+    ] {
+        let request =
+            json!({ "jsonrpc": "2.0", "id": id, "method": "eth_subscribe", "params": params });
+        ws_stream.send(Message::Text(request.to_string())).await?;
+    }
+
+    let mut new_heads_subscription = None;
+    let mut logs_subscription = None;
+    while let Some(message) = ws_stream.next().await {
+        let Message::Text(message) = message? else {
+            continue;
+        };
+        let message: Value = serde_json::from_str(&message)?;
+        // A response to one of the subscription requests, carrying the subscription ID.
+        if let Some(id) = message.get("id").and_then(Value::as_u64) {
+            let subscription = message.get("result").cloned().ok_or_else(|| {
+                EthereumBaseLayerError::Subscription(format!("Failed to subscribe: {message}"))
+            })?;
+            match id {
+                NEW_HEADS_REQUEST_ID => new_heads_subscription = Some(subscription),
+                LOGS_REQUEST_ID => logs_subscription = Some(subscription),
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some(params) = message.get("params") else {
+            continue;
+        };
+        let subscription = params.get("subscription").cloned();
+        let result = params.get("result").cloned().unwrap_or_default();
+        if subscription.is_some() && subscription == new_heads_subscription {
+            let head: NewHead = serde_json::from_value(result)?;
+            data.lock().await.observe_head(head.number.to::<u64>());
+        } else if subscription.is_some() && subscription == logs_subscription {
+            let log: Log = serde_json::from_value(result)?;
+            handle_state_update_log(contract, data, log).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_state_update_log(
+    contract: &StarknetContract,
+    data: &Mutex<SubscribedL1Data>,
+    log: Log,
+) -> Result<(), EthereumBaseLayerError> {
+    let Some(l1_block_number) = log.block_number else {
+        // Pending logs are ignored, as they are not part of a block yet.
+        return Ok(());
+    };
+    if log.removed {
+        data.lock().await.remove_state_update(l1_block_number);
+        return Ok(());
+    }
+
+    let validate = true;
+    let state_update = Starknet::LogStateUpdate::decode_log(&log.inner, validate)?.data;
+    let Ok(block_number) = u64::try_from(state_update.blockNumber.into_raw()) else {
+        debug!("Ignoring a state update of block {}.", state_update.blockNumber);
+        return Ok(());
+    };
+    // The block hash is not part of the log, so it is read at the L1 block of the update.
+    let state_block_hash =
+        contract.stateBlockHash().block(l1_block_number.into()).call_raw().await?;
+    let block_hash = sol_data::FixedBytes::<32>::abi_decode(&state_block_hash, validate)?;
+    data.lock().await.observe_state_update(
+        l1_block_number,
+        BlockHashAndNumber {
+            number: BlockNumber(block_number),
+            hash: BlockHash(StarkHash::from_bytes_be(&block_hash)),
+        },
+    );
+    Ok(())
+}
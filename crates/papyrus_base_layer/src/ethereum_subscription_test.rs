@@ -0,0 +1,45 @@
+use pretty_assertions::assert_eq;
+use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
+use starknet_api::felt;
+
+use crate::ethereum_subscription::{SubscribedL1Data, STATE_UPDATES_CAPACITY};
+
+fn proved_block(number: u64) -> BlockHashAndNumber {
+    BlockHashAndNumber { number: BlockNumber(number), hash: BlockHash(felt!(number)) }
+}
+
+#[test]
+fn latest_proved_block_by_l1_block() {
+    let mut data = SubscribedL1Data::default();
+    data.observe_head(30);
+    data.observe_state_update(10, proved_block(100));
+    data.observe_state_update(20, proved_block(200));
+
+    assert_eq!(data.latest_l1_block_number(), Some(30));
+    // No state update was observed at or before L1 block 9, so it has to be read from L1.
+    assert_eq!(data.latest_proved_block(9), None);
+    assert_eq!(data.latest_proved_block(10), Some(proved_block(100)));
+    assert_eq!(data.latest_proved_block(19), Some(proved_block(100)));
+    assert_eq!(data.latest_proved_block(30), Some(proved_block(200)));
+
+    // The state update of L1 block 20 is reorged out.
+    data.remove_state_update(20);
+    assert_eq!(data.latest_proved_block(30), Some(proved_block(100)));
+
+    data.reset();
+    assert_eq!(data.latest_l1_block_number(), None);
+    assert_eq!(data.latest_proved_block(30), None);
+}
+
+#[test]
+fn only_recent_state_updates_are_kept() {
+    let mut data = SubscribedL1Data::default();
+    let n_updates = u64::try_from(STATE_UPDATES_CAPACITY).unwrap() + 1;
+    for l1_block_number in 0..n_updates {
+        data.observe_state_update(l1_block_number, proved_block(l1_block_number));
+    }
+
+    assert_eq!(data.latest_proved_block(0), None);
+    assert_eq!(data.latest_proved_block(1), Some(proved_block(1)));
+    assert_eq!(data.latest_proved_block(n_updates), Some(proved_block(n_updates - 1)));
+}
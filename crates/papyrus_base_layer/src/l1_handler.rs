@@ -0,0 +1,77 @@
+//! Conversion of the messages sent to L2 on the base layer into the L1 handler transactions that
+//! consume them, ready to be executed.
+
+use std::sync::Arc;
+
+use starknet_api::core::{ChainId, ContractAddress, EntryPointSelector, Nonce};
+use starknet_api::executable_transaction::L1HandlerTransaction as ExecutableL1HandlerTransaction;
+use starknet_api::transaction::fields::{Calldata, Fee};
+use starknet_api::transaction::{L1HandlerTransaction, TransactionHasher};
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::Felt;
+
+use crate::{EventData, L1Event};
+
+#[cfg(test)]
+#[path = "l1_handler_test.rs"]
+mod l1_handler_test;
+
+/// Returns the L1 handler transaction consuming a message sent to L2. The L1 sender is passed to
+/// the L1 handler as its first calldata argument, followed by the message payload.
+pub fn l1_handler_tx(
+    from_address: Felt,
+    to_address: ContractAddress,
+    entry_point_selector: EntryPointSelector,
+    nonce: Nonce,
+    payload: &[Felt],
+) -> L1HandlerTransaction {
+    let calldata = std::iter::once(from_address).chain(payload.iter().copied()).collect();
+    L1HandlerTransaction {
+        version: L1HandlerTransaction::VERSION,
+        nonce,
+        contract_address: to_address,
+        entry_point_selector,
+        calldata: Calldata(Arc::new(calldata)),
+    }
+}
+
+impl EventData {
+    /// The L1 handler transaction consuming this message.
+    pub fn l1_handler_tx(&self) -> L1HandlerTransaction {
+        l1_handler_tx(
+            Felt::from(self.from_address),
+            self.to_address,
+            self.entry_point_selector,
+            self.nonce,
+            &self.payload.0,
+        )
+    }
+}
+
+/// Returns the executable L1 handler transaction, with its hash on the given chain and the fee
+/// paid for the message on L1.
+pub fn executable_l1_handler_tx(
+    tx: L1HandlerTransaction,
+    paid_fee_on_l1: Fee,
+    chain_id: &ChainId,
+) -> Result<ExecutableL1HandlerTransaction, StarknetApiError> {
+    let tx_hash = tx.calculate_transaction_hash(chain_id, &tx.version)?;
+    Ok(ExecutableL1HandlerTransaction { tx, tx_hash, paid_fee_on_l1 })
+}
+
+/// Returns the executable L1 handler transactions of the messages sent to L2 among the given
+/// events, in the order the messages were sent; other events are skipped.
+pub fn executable_l1_handler_txs(
+    events: impl IntoIterator<Item = L1Event>,
+    chain_id: &ChainId,
+) -> Result<Vec<ExecutableL1HandlerTransaction>, StarknetApiError> {
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            L1Event::LogMessageToL2 { tx, fee } => {
+                Some(executable_l1_handler_tx(tx, fee, chain_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,53 @@
+use pretty_assertions::assert_eq;
+use starknet_api::core::{ChainId, EntryPointSelector, EthAddress};
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{L1HandlerTransaction, TransactionHasher};
+use starknet_api::{calldata, contract_address, felt, nonce};
+
+use crate::l1_handler::executable_l1_handler_txs;
+use crate::{EventData, L1Event};
+
+fn message(nonce: u64) -> EventData {
+    EventData {
+        from_address: EthAddress::try_from(felt!("0x12")).unwrap(),
+        to_address: contract_address!("0x34"),
+        entry_point_selector: EntryPointSelector(felt!("0x56")),
+        payload: calldata![felt!(1_u8), felt!(2_u8)],
+        nonce: nonce!(nonce),
+    }
+}
+
+#[test]
+fn l1_handler_tx_from_message() {
+    let message = message(7);
+    assert_eq!(
+        message.l1_handler_tx(),
+        L1HandlerTransaction {
+            version: L1HandlerTransaction::VERSION,
+            nonce: nonce!(7_u8),
+            contract_address: contract_address!("0x34"),
+            entry_point_selector: EntryPointSelector(felt!("0x56")),
+            calldata: calldata![felt!("0x12"), felt!(1_u8), felt!(2_u8)],
+        }
+    );
+}
+
+#[test]
+fn executable_l1_handler_txs_of_messages() {
+    let chain_id = ChainId::Mainnet;
+    let events = vec![
+        L1Event::LogMessageToL2 { tx: message(1).l1_handler_tx(), fee: Fee(10) },
+        L1Event::ConsumedMessageToL2(message(0)),
+        L1Event::LogMessageToL2 { tx: message(2).l1_handler_tx(), fee: Fee(20) },
+    ];
+
+    let txs = executable_l1_handler_txs(events, &chain_id).unwrap();
+    let summary: Vec<_> = txs.iter().map(|tx| (tx.tx.nonce, tx.paid_fee_on_l1)).collect();
+    assert_eq!(summary, [(nonce!(1_u8), Fee(10)), (nonce!(2_u8), Fee(20))]);
+    for tx in txs {
+        assert_eq!(
+            tx.tx_hash,
+            tx.tx.calculate_transaction_hash(&chain_id, &tx.tx.version).unwrap()
+        );
+    }
+}
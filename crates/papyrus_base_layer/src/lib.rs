@@ -1,11 +1,21 @@
+use std::fmt::Display;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::BlockHashAndNumber;
-use starknet_api::core::{ContractAddress, EntryPointSelector, EthAddress, Nonce};
+use starknet_api::block::{BlockHashAndNumber, BlockNumber};
+use starknet_api::core::{ContractAddress, EntryPointSelector, EthAddress, GlobalRoot, Nonce};
 use starknet_api::transaction::fields::{Calldata, Fee};
 use starknet_api::transaction::L1HandlerTransaction;
 
+use crate::reorg_monitor::RevertedRange;
+
+pub mod cached_base_layer;
 pub mod ethereum_base_layer_contract;
+mod ethereum_subscription;
+pub mod l1_handler;
+pub mod message_cancellation;
+pub mod reorg_monitor;
+pub mod starknet_base_layer_contract;
 
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
@@ -13,18 +23,104 @@ pub mod test_utils;
 #[cfg(test)]
 mod base_layer_test;
 
+/// The signature of the event the Starknet base contract emits when a message is sent to L2.
+pub const LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER: &str =
+    "LogMessageToL2(address,uint256,uint256,uint256[],uint256,uint256)";
+
+/// The signature of the event the Starknet base contract emits when the sender of a message to L2
+/// starts cancelling it.
+pub const MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER: &str =
+    "MessageToL2CancellationStarted(address,uint256,uint256,uint256[],uint256)";
+
+/// The signature of the event the Starknet base contract emits when a message to L2 is cancelled.
+pub const MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER: &str =
+    "MessageToL2Canceled(address,uint256,uint256,uint256[],uint256)";
+
+/// How final an L1 block must be for its data to be used. Serialized as `latest`, `safe`,
+/// `finalized` or a number of confirmations.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Finality {
+    /// The latest L1 block, which may still be reorged out.
+    #[default]
+    Latest,
+    /// The latest L1 block the node considers safe from reorgs, per Ethereum's `safe` tag.
+    Safe,
+    /// The latest L1 block that can't be reverted, per Ethereum's `finalized` tag.
+    Finalized,
+    /// The latest L1 block with the given number of blocks on top of it.
+    Confirmations(u64),
+}
+
+impl Finality {
+    /// The number of confirmations the finality requires, unless it relies on the base layer to
+    /// tag final blocks.
+    pub fn confirmations(&self) -> Option<u64> {
+        match self {
+            Finality::Latest => Some(0),
+            Finality::Confirmations(confirmations) => Some(*confirmations),
+            Finality::Safe | Finality::Finalized => None,
+        }
+    }
+}
+
+impl Display for Finality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Finality::Latest => write!(f, "latest"),
+            Finality::Safe => write!(f, "safe"),
+            Finality::Finalized => write!(f, "finalized"),
+            Finality::Confirmations(confirmations) => write!(f, "{confirmations}"),
+        }
+    }
+}
+
+impl From<Finality> for String {
+    fn from(finality: Finality) -> Self {
+        finality.to_string()
+    }
+}
+
+impl TryFrom<String> for Finality {
+    type Error = InvalidFinality;
+
+    fn try_from(finality: String) -> Result<Self, InvalidFinality> {
+        match finality.as_str() {
+            "latest" => Ok(Finality::Latest),
+            "safe" => Ok(Finality::Safe),
+            "finalized" => Ok(Finality::Finalized),
+            confirmations => confirmations
+                .parse()
+                .map(Finality::Confirmations)
+                .map_err(|_| InvalidFinality(finality)),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid finality {0}, expected latest, safe, finalized or a number of confirmations.")]
+pub struct InvalidFinality(pub String);
+
 /// Interface for getting data from the Starknet base contract.
 #[async_trait]
 pub trait BaseLayerContract {
     type Error;
 
-    /// Get the latest Starknet block that is proved on the base layer.
-    /// Optionally, require minimum confirmations.
+    /// Get the latest Starknet block that is proved on the base layer, as of the latest L1 block
+    /// with the given finality.
     async fn latest_proved_block(
         &self,
-        finality: u64,
+        finality: Finality,
     ) -> Result<Option<BlockHashAndNumber>, Self::Error>;
 
+    /// Get the global state root of the latest Starknet block that is proved on the base layer,
+    /// to cross-check it against the locally committed state root.
+    /// As of the latest L1 block with the given finality.
+    async fn proved_state_root(
+        &self,
+        finality: Finality,
+    ) -> Result<Option<GlobalRoot>, Self::Error>;
+
     /// Get specific events from the Starknet base contract between two L1 block numbers.
     async fn events(
         &self,
@@ -33,7 +129,81 @@ pub trait BaseLayerContract {
         event_identifiers: &[&str],
     ) -> Result<Vec<L1Event>, Self::Error>;
 
-    async fn latest_l1_block_number(&self, finality: u64) -> Result<Option<u64>, Self::Error>;
+    /// Get the messages sent to L2 between two L1 block numbers (inclusive), as L1 handler
+    /// transactions with their paid fees, in the order they were sent.
+    async fn l1_to_l2_message_events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+    ) -> Result<Vec<L1Event>, Self::Error>;
+
+    /// Get the number of the latest L1 block with the given finality.
+    async fn latest_l1_block_number(&self, finality: Finality) -> Result<Option<u64>, Self::Error>;
+
+    /// Get the L1 gas prices of the given L1 block, if it exists.
+    async fn get_price_sample(&self, block_number: u64)
+    -> Result<Option<PriceSample>, Self::Error>;
+
+    /// Checks whether recently observed L1 blocks were reorged out since the previous call, and
+    /// returns the L1 blocks whose data (e.g. proved blocks and events) is no longer valid.
+    async fn detect_reorg(&self) -> Result<Option<RevertedRange>, Self::Error>;
+
+    /// Reports the connectivity to the base layer and how far behind the locally synced blocks
+    /// (up to `local_block_marker`) its latest proved block is. Failures to query the base layer
+    /// are reported in the status rather than returned.
+    async fn status(&self, finality: Finality, local_block_marker: BlockNumber) -> BaseLayerStatus
+    where
+        Self: Sync,
+        Self::Error: Display + Send,
+    {
+        let latest_l1_block = match self.latest_l1_block_number(finality).await {
+            Ok(latest_l1_block) => latest_l1_block,
+            Err(err) => return BaseLayerStatus::unhealthy(err),
+        };
+        let latest_proved_block = match self.latest_proved_block(finality).await {
+            Ok(latest_proved_block) => latest_proved_block,
+            Err(err) => {
+                return BaseLayerStatus { latest_l1_block, ..BaseLayerStatus::unhealthy(err) };
+            }
+        };
+        let proved_block_marker = latest_proved_block.map_or(0, |block| block.number.0 + 1);
+        BaseLayerStatus {
+            latest_l1_block,
+            latest_proved_block,
+            sync_lag: Some(local_block_marker.0.saturating_sub(proved_block_marker)),
+            is_healthy: true,
+            error: None,
+        }
+    }
+}
+
+/// The state of the base layer as seen by the node, for monitoring.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BaseLayerStatus {
+    /// The latest L1 block with the requested finality.
+    pub latest_l1_block: Option<u64>,
+    /// The latest Starknet block proved on the base layer.
+    pub latest_proved_block: Option<BlockHashAndNumber>,
+    /// The number of locally synced Starknet blocks that aren't proved on the base layer yet.
+    pub sync_lag: Option<u64>,
+    /// Whether the base layer endpoint answered all the queries.
+    pub is_healthy: bool,
+    /// The error returned by the base layer endpoint, if it isn't healthy.
+    pub error: Option<String>,
+}
+
+impl BaseLayerStatus {
+    fn unhealthy(error: impl Display) -> Self {
+        Self { is_healthy: false, error: Some(error.to_string()), ..Default::default() }
+    }
+}
+
+/// The gas prices of an L1 block, in wei; used to set the L1 gas prices of Starknet blocks.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PriceSample {
+    pub timestamp: u64,
+    pub base_fee_per_gas: u128,
+    pub blob_base_fee: u128,
 }
 
 /// Wraps Starknet L1 events with Starknet API types.
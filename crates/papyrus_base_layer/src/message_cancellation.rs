@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use alloy_primitives::keccak256;
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::L1HandlerTransaction;
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    BaseLayerContract,
+    EventData,
+    Finality,
+    L1Event,
+    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+};
+
+#[cfg(test)]
+#[path = "message_cancellation_test.rs"]
+mod message_cancellation_test;
+
+/// The hash of an L1 to L2 message, by which the Starknet base contract identifies it.
+pub type L1ToL2MessageHash = [u8; 32];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MessageCancellationStatus {
+    /// The sender started cancelling the message; until the cancellation delay passes, it can
+    /// still be consumed on L2.
+    CancellationStarted,
+    /// The message was cancelled on L1, so its L1 handler transaction must not be executed.
+    Canceled,
+}
+
+/// Follows the cancellations of L1 to L2 messages on the base layer, from a given L1 block on.
+/// Only L1 blocks with enough confirmations are scanned, so cancellations are never reorged out.
+#[derive(Debug)]
+pub struct MessageCancellationTracker {
+    finality: Finality,
+    next_l1_block: u64,
+    statuses: HashMap<L1ToL2MessageHash, MessageCancellationStatus>,
+}
+
+impl MessageCancellationTracker {
+    pub fn new(start_l1_block: u64, finality: Finality) -> Self {
+        Self { finality, next_l1_block: start_l1_block, statuses: HashMap::new() }
+    }
+
+    /// Scans the L1 blocks that became final since the previous call for cancellation events.
+    pub async fn sync<B: BaseLayerContract + Sync>(
+        &mut self,
+        base_layer: &B,
+    ) -> Result<(), B::Error> {
+        let Some(latest_l1_block) = base_layer.latest_l1_block_number(self.finality).await? else {
+            return Ok(());
+        };
+        if latest_l1_block < self.next_l1_block {
+            return Ok(());
+        }
+
+        let events = base_layer
+            .events(
+                self.next_l1_block,
+                latest_l1_block,
+                &[
+                    MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+                    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+                ],
+            )
+            .await?;
+        for event in &events {
+            self.apply_event(event);
+        }
+        self.next_l1_block = latest_l1_block + 1;
+        Ok(())
+    }
+
+    /// The cancellation status of the given message, if its cancellation was started.
+    pub fn status(&self, message_hash: &L1ToL2MessageHash) -> Option<MessageCancellationStatus> {
+        self.statuses.get(message_hash).copied()
+    }
+
+    /// Returns true if the L1 message of the given L1 handler transaction was cancelled.
+    pub fn is_canceled(&self, tx: &L1HandlerTransaction) -> bool {
+        l1_handler_message_hash(tx).is_some_and(|message_hash| {
+            self.status(&message_hash) == Some(MessageCancellationStatus::Canceled)
+        })
+    }
+
+    fn apply_event(&mut self, event: &L1Event) {
+        match event {
+            L1Event::MessageToL2CancellationStarted(event_data) => {
+                // A cancellation that was already completed is not restarted.
+                self.statuses
+                    .entry(event_data.message_hash())
+                    .or_insert(MessageCancellationStatus::CancellationStarted);
+            }
+            L1Event::MessageToL2Canceled(event_data) => {
+                self.statuses
+                    .insert(event_data.message_hash(), MessageCancellationStatus::Canceled);
+            }
+            L1Event::ConsumedMessageToL2(_) | L1Event::LogMessageToL2 { .. } => {}
+        }
+    }
+}
+
+impl EventData {
+    pub fn message_hash(&self) -> L1ToL2MessageHash {
+        message_hash(
+            Felt::from(self.from_address),
+            *self.to_address.0.key(),
+            self.nonce.0,
+            self.entry_point_selector.0,
+            &self.payload.0,
+        )
+    }
+}
+
+/// Returns the hash of the L1 message an L1 handler transaction consumes; the L1 sender is the
+/// first calldata argument, followed by the message payload. Returns `None` if there is no sender.
+pub fn l1_handler_message_hash(tx: &L1HandlerTransaction) -> Option<L1ToL2MessageHash> {
+    let (from_address, payload) = tx.calldata.0.split_first()?;
+    Some(message_hash(
+        *from_address,
+        *tx.contract_address.0.key(),
+        tx.nonce.0,
+        tx.entry_point_selector.0,
+        payload,
+    ))
+}
+
+// Follows the Starknet base contract: the keccak256 of the sender, recipient, nonce, selector,
+// payload length and payload, each encoded as a 32 bytes big-endian word.
+fn message_hash(
+    from_address: Felt,
+    to_address: Felt,
+    nonce: Felt,
+    selector: Felt,
+    payload: &[Felt],
+) -> L1ToL2MessageHash {
+    let words = [from_address, to_address, nonce, selector, Felt::from(payload.len())]
+        .into_iter()
+        .chain(payload.iter().copied());
+    let encoded: Vec<u8> = words.flat_map(|word| word.to_bytes_be()).collect();
+    keccak256(encoded).0
+}
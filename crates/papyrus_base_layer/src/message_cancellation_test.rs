@@ -0,0 +1,77 @@
+use std::sync::Mutex;
+
+use pretty_assertions::assert_eq;
+use starknet_api::core::{EntryPointSelector, EthAddress};
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::L1HandlerTransaction;
+use starknet_api::{calldata, contract_address, felt, nonce};
+
+use crate::message_cancellation::{
+    l1_handler_message_hash,
+    MessageCancellationStatus,
+    MessageCancellationTracker,
+};
+use crate::test_utils::FakeBaseLayer;
+use crate::{EventData, Finality, L1Event};
+
+const LATEST_L1_BLOCK: u64 = 20;
+
+fn message(nonce: u64) -> EventData {
+    EventData {
+        from_address: EthAddress::try_from(felt!("0x12")).unwrap(),
+        to_address: contract_address!("0x34"),
+        entry_point_selector: EntryPointSelector(felt!("0x56")),
+        payload: calldata![felt!(1_u8), felt!(2_u8)],
+        nonce: nonce!(nonce),
+    }
+}
+
+#[tokio::test]
+async fn cancellation_status_follows_events() {
+    let base_layer = FakeBaseLayer {
+        latest_l1_block: Mutex::new(LATEST_L1_BLOCK),
+        events: vec![
+            (1, L1Event::MessageToL2CancellationStarted(message(1))),
+            (2, L1Event::MessageToL2CancellationStarted(message(2))),
+            // Not a cancellation event.
+            (2, L1Event::LogMessageToL2 { tx: message(3).l1_handler_tx(), fee: Fee(1) }),
+            (3, L1Event::MessageToL2Canceled(message(2))),
+        ],
+        ..Default::default()
+    };
+    let mut tracker = MessageCancellationTracker::new(0, Finality::Latest);
+    tracker.sync(&base_layer).await.unwrap();
+
+    assert_eq!(
+        tracker.status(&message(1).message_hash()),
+        Some(MessageCancellationStatus::CancellationStarted)
+    );
+    assert_eq!(
+        tracker.status(&message(2).message_hash()),
+        Some(MessageCancellationStatus::Canceled)
+    );
+    assert_eq!(tracker.status(&message(3).message_hash()), None);
+
+    // Only messages whose cancellation completed can no longer be consumed.
+    assert!(!tracker.is_canceled(&message(1).l1_handler_tx()));
+    assert!(tracker.is_canceled(&message(2).l1_handler_tx()));
+    assert!(!tracker.is_canceled(&message(3).l1_handler_tx()));
+}
+
+#[tokio::test]
+async fn only_final_blocks_are_scanned_once() {
+    let base_layer =
+        FakeBaseLayer { latest_l1_block: Mutex::new(LATEST_L1_BLOCK), ..Default::default() };
+    let mut tracker = MessageCancellationTracker::new(10, Finality::Confirmations(5));
+
+    tracker.sync(&base_layer).await.unwrap();
+    tracker.sync(&base_layer).await.unwrap();
+    assert_eq!(base_layer.take_queries(), ["latest 5", "events 10-15", "latest 5"]);
+}
+
+#[test]
+fn l1_handler_message_hash_matches_event() {
+    let message = message(1);
+    assert_eq!(l1_handler_message_hash(&message.l1_handler_tx()), Some(message.message_hash()));
+    assert_eq!(l1_handler_message_hash(&L1HandlerTransaction::default()), None);
+}
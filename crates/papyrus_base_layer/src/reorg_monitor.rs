@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+#[path = "reorg_monitor_test.rs"]
+mod reorg_monitor_test;
+
+pub type L1BlockHash = [u8; 32];
+
+/// A range of L1 block numbers (inclusive) that are no longer part of the canonical chain. Data
+/// read from these blocks, such as proved Starknet blocks and L1 events, should be re-fetched.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RevertedRange {
+    pub from_block: u64,
+    pub until_block: u64,
+}
+
+/// Keeps the hashes of the most recently observed L1 blocks, to detect when they are reorged out.
+#[derive(Debug)]
+pub struct L1ReorgMonitor {
+    capacity: usize,
+    observed_blocks: BTreeMap<u64, L1BlockHash>,
+}
+
+impl L1ReorgMonitor {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, observed_blocks: BTreeMap::new() }
+    }
+
+    /// Records the hash of an L1 block; only the `capacity` highest blocks are kept.
+    pub fn observe(&mut self, block_number: u64, block_hash: L1BlockHash) {
+        self.observed_blocks.insert(block_number, block_hash);
+        while self.observed_blocks.len() > self.capacity {
+            self.observed_blocks.pop_first();
+        }
+    }
+
+    /// Compares the observed blocks, newest first, with the canonical chain, and forgets the ones
+    /// that were reorged out. The reverted range starts right after the newest observed block that
+    /// is still canonical; if there is none, it starts at the oldest observed block, as the reorg
+    /// might be deeper than what is tracked.
+    pub async fn detect_reorg<F, Fut, E>(
+        &mut self,
+        canonical_block_hash: F,
+    ) -> Result<Option<RevertedRange>, E>
+    where
+        F: Fn(u64) -> Fut,
+        Fut: Future<Output = Result<Option<L1BlockHash>, E>>,
+    {
+        let observed_blocks: Vec<_> =
+            self.observed_blocks.iter().rev().map(|(number, hash)| (*number, *hash)).collect();
+        let mut reverted_range: Option<RevertedRange> = None;
+        for (block_number, block_hash) in observed_blocks {
+            if canonical_block_hash(block_number).await? == Some(block_hash) {
+                if let Some(range) = reverted_range.as_mut() {
+                    range.from_block = block_number + 1;
+                }
+                break;
+            }
+            let until_block = reverted_range.map_or(block_number, |range| range.until_block);
+            reverted_range = Some(RevertedRange { from_block: block_number, until_block });
+        }
+
+        if let Some(range) = reverted_range {
+            self.observed_blocks.retain(|block_number, _| *block_number < range.from_block);
+        }
+        Ok(reverted_range)
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use pretty_assertions::assert_eq;
+
+use crate::reorg_monitor::{L1BlockHash, L1ReorgMonitor, RevertedRange};
+
+const ORIGINAL_HASH: L1BlockHash = [1; 32];
+const REORGED_HASH: L1BlockHash = [2; 32];
+
+fn monitor_with_blocks(block_numbers: &[u64]) -> L1ReorgMonitor {
+    let mut monitor = L1ReorgMonitor::new(10);
+    for block_number in block_numbers {
+        monitor.observe(*block_number, ORIGINAL_HASH);
+    }
+    monitor
+}
+
+async fn detect_reorg(
+    monitor: &mut L1ReorgMonitor,
+    canonical_chain: &HashMap<u64, L1BlockHash>,
+) -> Option<RevertedRange> {
+    monitor
+        .detect_reorg(|block_number| async move {
+            Ok::<_, Infallible>(canonical_chain.get(&block_number).copied())
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn no_reorg() {
+    let mut monitor = monitor_with_blocks(&[10, 20, 30]);
+    let canonical_chain =
+        HashMap::from([(10, ORIGINAL_HASH), (20, ORIGINAL_HASH), (30, ORIGINAL_HASH)]);
+
+    assert_eq!(detect_reorg(&mut monitor, &canonical_chain).await, None);
+}
+
+#[tokio::test]
+async fn reorg_reverts_blocks_after_the_newest_canonical_one() {
+    let mut monitor = monitor_with_blocks(&[10, 20, 30]);
+    // Block 30 was replaced, and the new chain is not yet as long as the old one.
+    let canonical_chain =
+        HashMap::from([(10, ORIGINAL_HASH), (20, ORIGINAL_HASH), (25, REORGED_HASH)]);
+
+    assert_eq!(
+        detect_reorg(&mut monitor, &canonical_chain).await,
+        Some(RevertedRange { from_block: 21, until_block: 30 })
+    );
+    // The reverted blocks are forgotten.
+    assert_eq!(detect_reorg(&mut monitor, &canonical_chain).await, None);
+}
+
+#[tokio::test]
+async fn reorg_deeper_than_the_observed_blocks() {
+    let mut monitor = monitor_with_blocks(&[10, 20]);
+    let canonical_chain = HashMap::from([(10, REORGED_HASH), (20, REORGED_HASH)]);
+
+    assert_eq!(
+        detect_reorg(&mut monitor, &canonical_chain).await,
+        Some(RevertedRange { from_block: 10, until_block: 20 })
+    );
+}
+
+#[tokio::test]
+async fn only_recent_blocks_are_kept() {
+    let mut monitor = L1ReorgMonitor::new(2);
+    for block_number in [10, 20, 30] {
+        monitor.observe(block_number, ORIGINAL_HASH);
+    }
+    // Block 10 is no longer tracked, so its reorg goes unnoticed.
+    let canonical_chain =
+        HashMap::from([(10, REORGED_HASH), (20, ORIGINAL_HASH), (30, ORIGINAL_HASH)]);
+
+    assert_eq!(detect_reorg(&mut monitor, &canonical_chain).await, None);
+}
@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use num_traits::ToPrimitive;
+use papyrus_config::dumping::{ser_param, ser_required_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber, GasPrice};
+use starknet_api::core::{ContractAddress, EntryPointSelector, GlobalRoot, Nonce};
+use starknet_api::transaction::fields::Fee;
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::Felt;
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::l1_handler::l1_handler_tx;
+use crate::reorg_monitor::{L1BlockHash, L1ReorgMonitor, RevertedRange};
+use crate::{
+    BaseLayerContract,
+    Finality,
+    L1Event,
+    PriceSample,
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+};
+
+#[cfg(test)]
+#[path = "starknet_base_layer_contract_test.rs"]
+mod starknet_base_layer_contract_test;
+
+// The number of base layer blocks tracked for reorg detection.
+const REORG_MONITOR_CAPACITY: usize = 128;
+// The maximal number of events requested in a single `starknet_getEvents` call.
+const EVENTS_CHUNK_SIZE: u64 = 1000;
+const JSON_RPC_VERSION: &str = "2.0";
+
+// The interface of the appchain core contract (Piltover) deployed on the base layer Starknet
+// chain: `get_state` returns the state root, number and hash of the latest settled block, and
+// `MessageSent` is emitted for every message sent to the appchain.
+const GET_STATE_ENTRY_POINT_NAME: &str = "get_state";
+const MESSAGE_SENT_EVENT_NAME: &str = "MessageSent";
+
+type StarknetBaseLayerResult<T> = Result<T, StarknetBaseLayerError>;
+
+/// Reads the appchain core contract deployed on a Starknet chain, for appchains (L3s) that settle
+/// on Starknet rather than on Ethereum. Base layer block numbers are the Starknet block numbers.
+#[derive(Debug)]
+pub struct StarknetBaseLayerContract {
+    pub config: StarknetBaseLayerConfig,
+    client: reqwest::Client,
+    reorg_monitor: Mutex<L1ReorgMonitor>,
+}
+
+impl StarknetBaseLayerContract {
+    pub fn new(config: StarknetBaseLayerConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            reorg_monitor: Mutex::new(L1ReorgMonitor::new(REORG_MONITOR_CAPACITY)),
+        }
+    }
+
+    async fn send_rpc_request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> StarknetBaseLayerResult<T> {
+        let request_body = json!({
+            "jsonrpc": JSON_RPC_VERSION,
+            "id": 0,
+            "method": method,
+            "params": params,
+        });
+        let response = self
+            .client
+            .post(self.config.node_url.clone())
+            .json(&request_body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        match response.json::<RpcResponse<T>>().await? {
+            RpcResponse::Success { result } => Ok(result),
+            RpcResponse::Error { error } => {
+                Err(StarknetBaseLayerError::RpcError { code: error.code, message: error.message })
+            }
+        }
+    }
+
+    /// Returns the state root, number and hash of the latest block settled on the core contract,
+    /// as of the base layer block at the given finality.
+    async fn proved_state(
+        &self,
+        finality: Finality,
+    ) -> StarknetBaseLayerResult<Option<(GlobalRoot, BlockHashAndNumber)>> {
+        let Some(base_layer_block_number) = self.latest_l1_block_number(finality).await? else {
+            return Ok(None);
+        };
+
+        let request = json!({
+            "contract_address": self.config.core_contract_address,
+            "entry_point_selector": selector_from_name(GET_STATE_ENTRY_POINT_NAME),
+            "calldata": [],
+        });
+        let state: Vec<Felt> = self
+            .send_rpc_request(
+                "starknet_call",
+                json!([request, { "block_number": base_layer_block_number }]),
+            )
+            .await?;
+        let [state_root, block_number, block_hash] = state[..] else {
+            return Err(StarknetBaseLayerError::UnexpectedResponse(format!(
+                "{GET_STATE_ENTRY_POINT_NAME} returned {state:?}."
+            )));
+        };
+        let block_number = block_number.to_u64().ok_or_else(|| {
+            StarknetBaseLayerError::UnexpectedResponse(format!(
+                "Block number {block_number} is out of range."
+            ))
+        })?;
+        Ok(Some((
+            GlobalRoot(state_root),
+            BlockHashAndNumber { number: BlockNumber(block_number), hash: BlockHash(block_hash) },
+        )))
+    }
+
+    /// Returns the header of the given block, if it exists.
+    async fn block_header(&self, block_id: Value) -> StarknetBaseLayerResult<Option<BlockHeader>> {
+        match self.send_rpc_request("starknet_getBlockWithTxHashes", json!([block_id])).await {
+            Ok(block_header) => Ok(Some(block_header)),
+            Err(StarknetBaseLayerError::RpcError { code: RPC_ERROR_BLOCK_NOT_FOUND, .. }) => {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[async_trait]
+impl BaseLayerContract for StarknetBaseLayerContract {
+    type Error = StarknetBaseLayerError;
+
+    async fn latest_proved_block(
+        &self,
+        finality: Finality,
+    ) -> StarknetBaseLayerResult<Option<BlockHashAndNumber>> {
+        Ok(self.proved_state(finality).await?.map(|(_state_root, block)| block))
+    }
+
+    async fn proved_state_root(
+        &self,
+        finality: Finality,
+    ) -> StarknetBaseLayerResult<Option<GlobalRoot>> {
+        Ok(self.proved_state(finality).await?.map(|(state_root, _block)| state_root))
+    }
+
+    /// Accepts the identifiers of the Ethereum Starknet contract events, and returns their
+    /// counterparts emitted by the appchain core contract.
+    async fn events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+        event_identifiers: &[&str],
+    ) -> StarknetBaseLayerResult<Vec<L1Event>> {
+        let event_selectors = event_identifiers
+            .iter()
+            .map(|identifier| match *identifier {
+                LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER => {
+                    Ok(selector_from_name(MESSAGE_SENT_EVENT_NAME))
+                }
+                _ => Err(StarknetBaseLayerError::UnsupportedEvent(identifier.to_string())),
+            })
+            .collect::<StarknetBaseLayerResult<Vec<_>>>()?;
+
+        let mut events = vec![];
+        let mut continuation_token = None;
+        loop {
+            let filter = json!({
+                "from_block": { "block_number": from_block },
+                "to_block": { "block_number": until_block },
+                "address": self.config.core_contract_address,
+                "keys": [event_selectors],
+                "chunk_size": EVENTS_CHUNK_SIZE,
+                "continuation_token": continuation_token,
+            });
+            let chunk: EventsChunk =
+                self.send_rpc_request("starknet_getEvents", json!([filter])).await?;
+            // Events are returned in the order they were emitted.
+            events.extend(chunk.events);
+            continuation_token = chunk.continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        events.into_iter().map(parse_event).collect()
+    }
+
+    async fn l1_to_l2_message_events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+    ) -> StarknetBaseLayerResult<Vec<L1Event>> {
+        self.events(from_block, until_block, &[LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER]).await
+    }
+
+    /// Starknet has no safe and finalized block tags, so only confirmations are supported.
+    async fn latest_l1_block_number(
+        &self,
+        finality: Finality,
+    ) -> StarknetBaseLayerResult<Option<u64>> {
+        let confirmations = finality
+            .confirmations()
+            .ok_or(StarknetBaseLayerError::UnsupportedFinality(finality))?;
+        let block_number: u64 = self.send_rpc_request("starknet_blockNumber", json!([])).await?;
+        Ok(block_number.checked_sub(confirmations))
+    }
+
+    /// Transactions settling on Starknet are priced by its L1 gas prices, so these are sampled.
+    async fn get_price_sample(
+        &self,
+        block_number: u64,
+    ) -> StarknetBaseLayerResult<Option<PriceSample>> {
+        let Some(block_header) = self.block_header(json!({ "block_number": block_number })).await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(PriceSample {
+            timestamp: block_header.timestamp,
+            base_fee_per_gas: block_header.l1_gas_price.price_in_wei.0,
+            blob_base_fee: block_header.l1_data_gas_price.price_in_wei.0,
+        }))
+    }
+
+    async fn detect_reorg(&self) -> StarknetBaseLayerResult<Option<RevertedRange>> {
+        let mut reorg_monitor = self.reorg_monitor.lock().await;
+        let reverted_range = reorg_monitor
+            .detect_reorg(|block_number| async move {
+                let block_header =
+                    self.block_header(json!({ "block_number": block_number })).await?;
+                Ok::<_, StarknetBaseLayerError>(
+                    block_header.map(|block_header| block_header.l1_block_hash()),
+                )
+            })
+            .await?;
+        // Observe the current head, so that the next detection covers it.
+        if let Some(block_header) = self.block_header(json!("latest")).await? {
+            reorg_monitor.observe(block_header.block_number, block_header.l1_block_hash());
+        }
+        Ok(reverted_range)
+    }
+}
+
+/// Decodes a `MessageSent` event of the appchain core contract into an L1 event. The event keys
+/// are the event selector, the message hash, the sender and the recipient, and its data is the
+/// L1 handler selector, the nonce and the payload (prefixed by its length).
+pub(crate) fn parse_event(event: EmittedEvent) -> StarknetBaseLayerResult<L1Event> {
+    let malformed_event = || StarknetBaseLayerError::UnexpectedResponse(format!("{event:?}"));
+    let [_selector, _message_hash, from_address, to_address] = event.keys[..] else {
+        return Err(malformed_event());
+    };
+    let [entry_point_selector, nonce, payload_length, ref payload @ ..] = event.data[..] else {
+        return Err(malformed_event());
+    };
+    if Felt::from(payload.len()) != payload_length {
+        return Err(malformed_event());
+    }
+
+    let tx = l1_handler_tx(
+        from_address,
+        ContractAddress::try_from(to_address)?,
+        EntryPointSelector(entry_point_selector),
+        Nonce(nonce),
+        payload,
+    );
+    // Messages to the appchain are not paid for on the base layer.
+    Ok(L1Event::LogMessageToL2 { tx, fee: Fee(0) })
+}
+
+// Starknet spec error code.
+const RPC_ERROR_BLOCK_NOT_FOUND: i64 = 24;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum RpcResponse<T> {
+    Success { result: T },
+    Error { error: RpcErrorObject },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct ResourcePrice {
+    pub price_in_wei: GasPrice,
+    pub price_in_fri: GasPrice,
+}
+
+/// The fields of a block header used by the base layer contract; other fields are ignored.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct BlockHeader {
+    pub block_hash: Felt,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub l1_gas_price: ResourcePrice,
+    pub l1_data_gas_price: ResourcePrice,
+}
+
+impl BlockHeader {
+    fn l1_block_hash(&self) -> L1BlockHash {
+        self.block_hash.to_bytes_be()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct EmittedEvent {
+    pub keys: Vec<Felt>,
+    pub data: Vec<Felt>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct EventsChunk {
+    events: Vec<EmittedEvent>,
+    continuation_token: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StarknetBaseLayerError {
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("JSON-RPC error {code}: {message}.")]
+    RpcError { code: i64, message: String },
+    #[error(transparent)]
+    StarknetApi(#[from] StarknetApiError),
+    #[error("Unexpected response from the base layer node: {0}")]
+    UnexpectedResponse(String),
+    #[error("Finality {0} is not supported by a Starknet base layer.")]
+    UnsupportedFinality(Finality),
+    #[error("Event {0} is not emitted by the appchain core contract.")]
+    UnsupportedEvent(String),
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct StarknetBaseLayerConfig {
+    pub node_url: Url,
+    pub core_contract_address: ContractAddress,
+}
+
+impl SerializeConfig for StarknetBaseLayerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_required_param(
+                "node_url",
+                SerializationType::String,
+                "URL of a JSON-RPC node of the Starknet chain the appchain settles on.",
+                ParamPrivacyInput::Private,
+            ),
+            ser_param(
+                "core_contract_address",
+                &self.core_contract_address,
+                "Address of the appchain core contract on the base layer Starknet chain.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for StarknetBaseLayerConfig {
+    fn default() -> Self {
+        Self {
+            node_url: "https://starknet-mainnet.public.blastapi.io/rpc/v0_7".parse().unwrap(),
+            core_contract_address: ContractAddress::default(),
+        }
+    }
+}
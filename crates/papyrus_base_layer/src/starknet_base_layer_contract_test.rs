@@ -0,0 +1,148 @@
+use mockito::{Matcher, Mock, ServerGuard};
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
+use starknet_api::core::{EntryPointSelector, GlobalRoot};
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::L1HandlerTransaction;
+use starknet_api::{calldata, contract_address, felt, nonce};
+
+use crate::starknet_base_layer_contract::{
+    parse_event,
+    EmittedEvent,
+    StarknetBaseLayerConfig,
+    StarknetBaseLayerContract,
+    StarknetBaseLayerError,
+};
+use crate::{BaseLayerContract, Finality, L1Event, PriceSample};
+
+async fn mock_rpc_method(server: &mut ServerGuard, method: &str, mut response: Value) -> Mock {
+    response["jsonrpc"] = json!("2.0");
+    response["id"] = json!(0);
+    server
+        .mock("POST", "/")
+        .match_body(Matcher::PartialJson(json!({ "method": method })))
+        .with_body(response.to_string())
+        .create_async()
+        .await
+}
+
+fn contract(server: &ServerGuard) -> StarknetBaseLayerContract {
+    StarknetBaseLayerContract::new(StarknetBaseLayerConfig {
+        node_url: server.url().parse().unwrap(),
+        core_contract_address: contract_address!("0x1234"),
+    })
+}
+
+#[tokio::test]
+async fn latest_proved_block() {
+    let mut server = mockito::Server::new_async().await;
+    let _block_number =
+        mock_rpc_method(&mut server, "starknet_blockNumber", json!({ "result": 30 })).await;
+    let get_state = mock_rpc_method(
+        &mut server,
+        "starknet_call",
+        json!({ "result": ["0x5", "0x64", "0x100"] }),
+    )
+    .await;
+
+    let contract = contract(&server);
+    assert_eq!(
+        contract.latest_proved_block(Finality::Confirmations(10)).await.unwrap(),
+        Some(BlockHashAndNumber { number: BlockNumber(100), hash: BlockHash(felt!("0x100")) })
+    );
+    get_state.assert_async().await;
+
+    // Not enough base layer blocks for the requested finality.
+    assert_eq!(contract.latest_proved_block(Finality::Confirmations(31)).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn tagged_finality_is_unsupported() {
+    let server = mockito::Server::new_async().await;
+
+    let contract = contract(&server);
+    assert!(matches!(
+        contract.latest_proved_block(Finality::Safe).await,
+        Err(StarknetBaseLayerError::UnsupportedFinality(Finality::Safe))
+    ));
+}
+
+#[tokio::test]
+async fn proved_state_root() {
+    let mut server = mockito::Server::new_async().await;
+    let _block_number =
+        mock_rpc_method(&mut server, "starknet_blockNumber", json!({ "result": 30 })).await;
+    let _get_state = mock_rpc_method(
+        &mut server,
+        "starknet_call",
+        json!({ "result": ["0x5", "0x64", "0x100"] }),
+    )
+    .await;
+
+    let contract = contract(&server);
+    assert_eq!(
+        contract.proved_state_root(Finality::Confirmations(10)).await.unwrap(),
+        Some(GlobalRoot(felt!("0x5")))
+    );
+    assert_eq!(contract.proved_state_root(Finality::Confirmations(31)).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn price_sample() {
+    let mut server = mockito::Server::new_async().await;
+    let block = json!({
+        "block_hash": "0xabc",
+        "block_number": 7,
+        "timestamp": 1000,
+        "l1_gas_price": { "price_in_wei": "0x10", "price_in_fri": "0x20" },
+        "l1_data_gas_price": { "price_in_wei": "0x1", "price_in_fri": "0x2" },
+    });
+    let _block =
+        mock_rpc_method(&mut server, "starknet_getBlockWithTxHashes", json!({ "result": block }))
+            .await;
+
+    assert_eq!(
+        contract(&server).get_price_sample(7).await.unwrap(),
+        Some(PriceSample { timestamp: 1000, base_fee_per_gas: 16, blob_base_fee: 1 })
+    );
+}
+
+#[tokio::test]
+async fn price_sample_of_missing_block() {
+    let mut server = mockito::Server::new_async().await;
+    let _block = mock_rpc_method(
+        &mut server,
+        "starknet_getBlockWithTxHashes",
+        json!({ "error": { "code": 24, "message": "Block not found" } }),
+    )
+    .await;
+
+    assert_eq!(contract(&server).get_price_sample(7).await.unwrap(), None);
+}
+
+#[test]
+fn parse_message_sent_event() {
+    let event = EmittedEvent {
+        // Selector, message hash, sender and recipient.
+        keys: vec![felt!("0x1"), felt!("0x2"), felt!("0x3"), felt!("0x4")],
+        // L1 handler selector, nonce, payload length and payload.
+        data: vec![felt!("0x5"), felt!("0x6"), felt!("0x2"), felt!("0x7"), felt!("0x8")],
+    };
+
+    let expected_tx = L1HandlerTransaction {
+        version: L1HandlerTransaction::VERSION,
+        nonce: nonce!(6),
+        contract_address: contract_address!("0x4"),
+        entry_point_selector: EntryPointSelector(felt!("0x5")),
+        calldata: calldata![felt!("0x3"), felt!("0x7"), felt!("0x8")],
+    };
+    assert_eq!(
+        parse_event(event.clone()).unwrap(),
+        L1Event::LogMessageToL2 { tx: expected_tx, fee: Fee(0) }
+    );
+
+    // The payload length does not match the payload.
+    let malformed_event = EmittedEvent { data: event.data[..4].to_vec(), ..event };
+    assert!(parse_event(malformed_event).is_err());
+}
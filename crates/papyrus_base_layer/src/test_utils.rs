@@ -1,11 +1,29 @@
+use std::collections::HashSet;
 use std::fs::File;
 use std::process::Command;
+use std::sync::Mutex;
 
 pub(crate) use alloy_primitives::Address as EthereumContractAddress;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
 use ethers::utils::{Ganache, GanacheInstance};
+use starknet_api::block::BlockHashAndNumber;
+use starknet_api::core::GlobalRoot;
 use tar::Archive;
 use tempfile::{tempdir, TempDir};
 
+use crate::ethereum_base_layer_contract::{EthereumBaseLayerError, Starknet};
+use crate::reorg_monitor::RevertedRange;
+use crate::{
+    BaseLayerContract,
+    Finality,
+    L1Event,
+    PriceSample,
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+};
+
 type TestEthereumNodeHandle = (GanacheInstance, TempDir);
 
 const MINIMAL_GANACHE_VERSION: u8 = 7;
@@ -61,3 +79,157 @@ pub fn get_test_ethereum_node() -> (TestEthereumNodeHandle, EthereumContractAddr
 
     ((ganache, ganache_db), SN_CONTRACT_ADDR.to_string().parse().unwrap())
 }
+
+/// A query of a base layer contract.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BaseLayerQuery {
+    LatestProvedBlock,
+    ProvedStateRoot,
+    Events,
+    L1ToL2MessageEvents,
+    LatestL1BlockNumber,
+    PriceSample,
+    DetectReorg,
+}
+
+impl BaseLayerQuery {
+    pub const ALL: [Self; 7] = [
+        Self::LatestProvedBlock,
+        Self::ProvedStateRoot,
+        Self::Events,
+        Self::L1ToL2MessageEvents,
+        Self::LatestL1BlockNumber,
+        Self::PriceSample,
+        Self::DetectReorg,
+    ];
+}
+
+/// An in-memory base layer whose blocks and events are set by the test. It records the queries it
+/// answers, and every query of it is answered, unless set to fail.
+#[derive(Debug, Default)]
+pub struct FakeBaseLayer {
+    /// The latest L1 block. A block is final once it has the confirmations of the finality.
+    pub latest_l1_block: Mutex<u64>,
+    pub latest_proved_block: Option<BlockHashAndNumber>,
+    pub proved_state_root: Option<GlobalRoot>,
+    /// Pairs of L1 block number and an event emitted in it.
+    pub events: Vec<(u64, L1Event)>,
+    /// Returned, once, by the next reorg detection.
+    pub reverted_range: Mutex<Option<RevertedRange>>,
+    /// The queries that fail, as if the base layer was unreachable.
+    pub failing_queries: Mutex<HashSet<BaseLayerQuery>>,
+    queries: Mutex<Vec<String>>,
+}
+
+impl FakeBaseLayer {
+    /// Returns the queries answered since the previous call, e.g. "events 0-10" or "latest 5".
+    pub fn take_queries(&self) -> Vec<String> {
+        std::mem::take(&mut *self.queries.lock().unwrap())
+    }
+
+    fn query(
+        &self,
+        query: BaseLayerQuery,
+        description: String,
+    ) -> Result<(), EthereumBaseLayerError> {
+        if self.failing_queries.lock().unwrap().contains(&query) {
+            return Err(EthereumBaseLayerError::Subscription(format!("{query:?} is unreachable.")));
+        }
+        self.queries.lock().unwrap().push(description);
+        Ok(())
+    }
+
+    fn events_in_range(
+        &self,
+        from_block: u64,
+        until_block: u64,
+        event_identifiers: &[&str],
+    ) -> Vec<L1Event> {
+        self.events
+            .iter()
+            .filter(|(block_number, event)| {
+                (from_block..=until_block).contains(block_number)
+                    && event_identifiers.contains(&event_identifier(event))
+            })
+            .map(|(_, event)| event.clone())
+            .collect()
+    }
+}
+
+fn event_identifier(event: &L1Event) -> &'static str {
+    match event {
+        L1Event::ConsumedMessageToL2(_) => Starknet::ConsumedMessageToL2::SIGNATURE,
+        L1Event::LogMessageToL2 { .. } => LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+        L1Event::MessageToL2CancellationStarted(_) => {
+            MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER
+        }
+        L1Event::MessageToL2Canceled(_) => MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    }
+}
+
+#[async_trait]
+impl BaseLayerContract for FakeBaseLayer {
+    type Error = EthereumBaseLayerError;
+
+    async fn latest_proved_block(
+        &self,
+        finality: Finality,
+    ) -> Result<Option<BlockHashAndNumber>, Self::Error> {
+        self.query(BaseLayerQuery::LatestProvedBlock, format!("proved block {finality}"))?;
+        Ok(self.latest_proved_block)
+    }
+
+    async fn proved_state_root(
+        &self,
+        finality: Finality,
+    ) -> Result<Option<GlobalRoot>, Self::Error> {
+        self.query(BaseLayerQuery::ProvedStateRoot, format!("proved state root {finality}"))?;
+        Ok(self.proved_state_root)
+    }
+
+    async fn events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+        event_identifiers: &[&str],
+    ) -> Result<Vec<L1Event>, Self::Error> {
+        self.query(BaseLayerQuery::Events, format!("events {from_block}-{until_block}"))?;
+        Ok(self.events_in_range(from_block, until_block, event_identifiers))
+    }
+
+    async fn l1_to_l2_message_events(
+        &self,
+        from_block: u64,
+        until_block: u64,
+    ) -> Result<Vec<L1Event>, Self::Error> {
+        self.query(
+            BaseLayerQuery::L1ToL2MessageEvents,
+            format!("messages {from_block}-{until_block}"),
+        )?;
+        Ok(self.events_in_range(from_block, until_block, &[LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER]))
+    }
+
+    async fn latest_l1_block_number(&self, finality: Finality) -> Result<Option<u64>, Self::Error> {
+        self.query(BaseLayerQuery::LatestL1BlockNumber, format!("latest {finality}"))?;
+        // The fake does not track safe and finalized blocks.
+        let latest_l1_block = *self.latest_l1_block.lock().unwrap();
+        Ok(finality
+            .confirmations()
+            .and_then(|confirmations| latest_l1_block.checked_sub(confirmations)))
+    }
+
+    async fn get_price_sample(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<PriceSample>, Self::Error> {
+        self.query(BaseLayerQuery::PriceSample, format!("price {block_number}"))?;
+        let latest_l1_block = *self.latest_l1_block.lock().unwrap();
+        Ok((block_number <= latest_l1_block)
+            .then(|| PriceSample { timestamp: block_number, ..Default::default() }))
+    }
+
+    async fn detect_reorg(&self) -> Result<Option<RevertedRange>, Self::Error> {
+        self.query(BaseLayerQuery::DetectReorg, "reorg".to_string())?;
+        Ok(self.reverted_range.lock().unwrap().take())
+    }
+}
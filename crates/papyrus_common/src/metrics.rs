@@ -43,3 +43,23 @@ pub const PAPYRUS_CONSENSUS_HEIGHT: &str = "papyrus_consensus_height";
 
 /// The number of times consensus has progressed due to the sync protocol.
 pub const PAPYRUS_CONSENSUS_SYNC_COUNT: &str = "papyrus_consensus_sync_count";
+
+/// The total size in bytes of the transaction batches proposed by the node, before compression.
+pub const PAPYRUS_CONSENSUS_PROPOSAL_UNCOMPRESSED_BYTES: &str =
+    "papyrus_consensus_proposal_uncompressed_bytes";
+
+/// The total size in bytes of the transaction batches proposed by the node, as sent to the network.
+pub const PAPYRUS_CONSENSUS_PROPOSAL_COMPRESSED_BYTES: &str =
+    "papyrus_consensus_proposal_compressed_bytes";
+
+/// The number of proposals and votes cached for future heights that were dropped once the heights
+/// passed.
+pub const PAPYRUS_CONSENSUS_PRUNED_CACHED_MESSAGES: &str =
+    "papyrus_consensus_pruned_cached_messages";
+
+/// The number of proposals of past heights dropped by the garbage collection.
+pub const PAPYRUS_CONSENSUS_PRUNED_PROPOSALS: &str = "papyrus_consensus_pruned_proposals";
+
+/// The number of transactions in the proposals of past heights dropped by the garbage collection.
+pub const PAPYRUS_CONSENSUS_PRUNED_PROPOSAL_TRANSACTIONS: &str =
+    "papyrus_consensus_pruned_proposal_transactions";
@@ -1,5 +1,6 @@
 // TODO(shahak): Add a test for executing when there's a missing casm that's not required and when
 // there's a missing casm that is required.
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
@@ -13,9 +14,10 @@ use papyrus_storage::test_utils::get_test_storage;
 use pretty_assertions::assert_eq;
 use starknet_api::abi::abi_utils::get_storage_var_address;
 use starknet_api::block::{BlockNumber, StarknetVersion};
-use starknet_api::core::{ChainId, CompiledClassHash, EntryPointSelector};
+use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, EntryPointSelector};
 use starknet_api::state::{StateNumber, ThinStateDiff};
 use starknet_api::transaction::fields::{Calldata, Fee};
+use starknet_api::transaction::{TransactionVersion, TransactionVersions};
 use starknet_api::{calldata, class_hash, contract_address, felt, nonce};
 use starknet_types_core::felt::Felt;
 
@@ -30,8 +32,10 @@ use crate::objects::{
     TransactionSimulationOutput,
     TransactionTrace,
 };
+use crate::state_overrides::{ClassOverride, ContractStateOverride, StateOverrides};
 use crate::test_utils::{
     execute_simulate_transactions,
+    get_test_deprecated_contract_class,
     prepare_storage,
     TxsScenarioBuilder,
     ACCOUNT_ADDRESS,
@@ -50,6 +54,7 @@ use crate::{
     estimate_fee,
     execute_call,
     ExecutableTransactionInput,
+    ExecutionConfig,
     ExecutionError,
     FeeEstimationResult,
     RevertedTransaction,
@@ -76,6 +81,7 @@ fn execute_call_cairo0() {
         Calldata::default(),
         &get_test_execution_config(),
         true,
+        StateOverrides::default(),
     )
     .unwrap()
     .retdata;
@@ -93,6 +99,7 @@ fn execute_call_cairo0() {
         Calldata(Arc::new(vec![Felt::from(25u128)])),
         &get_test_execution_config(),
         true,
+        StateOverrides::default(),
     )
     .unwrap()
     .retdata;
@@ -110,6 +117,7 @@ fn execute_call_cairo0() {
         Calldata(Arc::new(vec![Felt::from(123u128)])),
         &get_test_execution_config(),
         true,
+        StateOverrides::default(),
     )
     .unwrap()
     .retdata;
@@ -127,6 +135,7 @@ fn execute_call_cairo0() {
         Calldata(Arc::new(vec![Felt::from(123u128), Felt::from(456u128)])),
         &get_test_execution_config(),
         true,
+        StateOverrides::default(),
     )
     .unwrap()
     .retdata;
@@ -155,6 +164,7 @@ fn execute_call_cairo1() {
         calldata,
         &get_test_execution_config(),
         true,
+        StateOverrides::default(),
     )
     .unwrap()
     .retdata;
@@ -162,6 +172,60 @@ fn execute_call_cairo1() {
     assert_eq!(retdata, Retdata(vec![value]));
 }
 
+// Test calling a contract that is deployed only by the state overrides.
+#[test]
+fn execute_call_with_state_overrides() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    let non_existing_contract = contract_address!("0x987");
+    let call_contract = |state_overrides: StateOverrides| {
+        execute_call(
+            storage_reader.clone(),
+            None,
+            &CHAIN_ID,
+            StateNumber::unchecked_right_after_block(BlockNumber(0)),
+            BlockNumber(0),
+            &non_existing_contract,
+            selector_from_name("return_result"),
+            calldata![felt!(123_u8)],
+            &get_test_execution_config(),
+            true,
+            state_overrides,
+        )
+    };
+
+    assert_matches!(
+        call_contract(StateOverrides::default()),
+        Err(ExecutionError::ContractNotFound { contract_address, .. })
+        if contract_address == non_existing_contract
+    );
+
+    // Deploy the deprecated test class at the address.
+    let deploy_class = |class_hash: ClassHash| {
+        BTreeMap::from([(
+            non_existing_contract,
+            ContractStateOverride { class_hash: Some(class_hash), ..Default::default() },
+        )])
+    };
+    let state_overrides =
+        StateOverrides { contracts: deploy_class(class_hash!("0x1")), ..Default::default() };
+    let retdata = call_contract(state_overrides).unwrap().retdata;
+    assert_eq!(retdata, Retdata(vec![felt!(123_u8)]));
+
+    // Deploy a class that is declared only by the state overrides.
+    let undeclared_class_hash = class_hash!("0x9999");
+    let state_overrides = StateOverrides {
+        contracts: deploy_class(undeclared_class_hash),
+        classes: BTreeMap::from([(
+            undeclared_class_hash,
+            ClassOverride::Deprecated(get_test_deprecated_contract_class()),
+        )]),
+    };
+    let retdata = call_contract(state_overrides).unwrap().retdata;
+    assert_eq!(retdata, Retdata(vec![felt!(123_u8)]));
+}
+
 // TODO(yair): Compare to the expected fee instead of asserting that it is not zero (all
 // estimate_fee tests).
 #[test]
@@ -237,6 +301,37 @@ fn estimate_fee_reverted() {
     assert_matches!(failed_estimation, RevertedTransaction { index: 1, revert_reason: _ })
 }
 
+// Test that transactions of the versions disabled in the config are rejected.
+#[test]
+fn estimate_fee_disabled_tx_version() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    let txs = TxsScenarioBuilder::default()
+        .invoke_deprecated(*ACCOUNT_ADDRESS, *DEPRECATED_CONTRACT_ADDRESS, None, false)
+        .collect();
+    let execution_config = ExecutionConfig {
+        disabled_tx_versions: TransactionVersions::new([TransactionVersion::ONE]),
+        ..get_test_execution_config()
+    };
+    let result = estimate_fee(
+        txs,
+        &CHAIN_ID,
+        storage_reader,
+        None,
+        StateNumber::unchecked_right_after_block(BlockNumber(0)),
+        BlockNumber(1),
+        &execution_config,
+        false,
+        true,
+        StateOverrides::default(),
+    );
+    assert_matches!(
+        result,
+        Err(ExecutionError::TransactionExecutionError { transaction_index: 0, .. })
+    );
+}
+
 fn estimate_fees(txs: Vec<ExecutableTransactionInput>) -> FeeEstimationResult {
     let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
     prepare_storage(storage_writer);
@@ -252,6 +347,7 @@ fn estimate_fees(txs: Vec<ExecutableTransactionInput>) -> FeeEstimationResult {
         false,
         // TODO(yair): Add test for blob fee estimation.
         true,
+        StateOverrides::default(),
     )
     .unwrap()
 }
@@ -10,6 +10,7 @@ use blockifier::execution::contract_class::{
 use blockifier::state::cached_state::{CachedState, CommitmentStateDiff, MutRefState};
 use blockifier::state::state_api::StateReader;
 use blockifier::transaction::objects::TransactionExecutionInfo;
+use blockifier::transaction::transaction_types::TransactionType;
 use cairo_vm::types::errors::program_errors::ProgramError;
 use indexmap::IndexMap;
 use papyrus_common::state::{DeployedContract, ReplacedClass, StorageEntry};
@@ -119,6 +120,22 @@ pub fn get_trace_constructor(
     }
 }
 
+/// Returns the trace of a transaction of the given type that was executed by the blockifier.
+#[allow(clippy::result_large_err)]
+pub fn transaction_trace(
+    tx_type: TransactionType,
+    execution_info: TransactionExecutionInfo,
+) -> ExecutionResult<TransactionTrace> {
+    Ok(match tx_type {
+        TransactionType::Declare => TransactionTrace::Declare(execution_info.try_into()?),
+        TransactionType::DeployAccount => {
+            TransactionTrace::DeployAccount(execution_info.try_into()?)
+        }
+        TransactionType::InvokeFunction => TransactionTrace::Invoke(execution_info.try_into()?),
+        TransactionType::L1Handler => TransactionTrace::L1Handler(execution_info.try_into()?),
+    })
+}
+
 /// Returns the state diff induced by a single transaction. If the transaction
 /// is a deprecated Declare, the user is required to pass the class hash of the deprecated class as
 /// it is not provided by the blockifier API.
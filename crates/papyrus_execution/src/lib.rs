@@ -11,6 +11,7 @@
 #[cfg(test)]
 mod execution_test;
 pub mod execution_utils;
+pub mod state_overrides;
 mod state_reader;
 #[cfg(test)]
 mod test_utils;
@@ -22,9 +23,21 @@ use std::cell::Cell;
 use std::collections::BTreeMap;
 use std::sync::{Arc, LazyLock};
 
-use blockifier::blockifier::block::{pre_process_block, validated_gas_prices};
+use blockifier::blockifier::block::{
+    pre_process_block,
+    validated_gas_prices,
+    write_block_metadata,
+};
 use blockifier::bouncer::BouncerConfig;
-use blockifier::context::{BlockContext, ChainInfo, FeeTokenAddresses, TransactionContext};
+use blockifier::context::{
+    BlockContext,
+    ChainInfo,
+    CustomFeeTokens,
+    FeeDistribution,
+    FeeTokenAddresses,
+    PaymasterConfig,
+    TransactionContext,
+};
 use blockifier::execution::call_info::CallExecution;
 use blockifier::execution::entry_point::{
     CallEntryPoint,
@@ -46,18 +59,12 @@ use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_vm::types::builtin_name::BuiltinName;
 use execution_utils::{get_trace_constructor, induced_state_diff};
 use objects::{PriceUnit, TransactionSimulationOutput};
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::{StorageError, StorageReader};
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{
-    BlockHashAndNumber,
-    BlockInfo,
-    BlockNumber,
-    NonzeroGasPrice,
-    StarknetVersion,
-};
+use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber, NonzeroGasPrice};
 use starknet_api::contract_class::{ClassInfo, EntryPointType, SierraVersion};
 use starknet_api::core::{ChainId, ClassHash, ContractAddress, EntryPointSelector};
 use starknet_api::data_availability::L1DataAvailabilityMode;
@@ -76,10 +83,12 @@ use starknet_api::transaction::{
     TransactionHash,
     TransactionOptions,
     TransactionVersion,
+    TransactionVersions,
 };
 use starknet_api::transaction_hash::get_transaction_hash;
 use starknet_api::StarknetApiError;
 use starknet_types_core::felt::Felt;
+use state_overrides::{StateOverlay, StateOverrides};
 use state_reader::ExecutionStateReader;
 use tracing::trace;
 
@@ -114,7 +123,7 @@ pub static ETH_FEE_CONTRACT_ADDRESS: LazyLock<ContractAddress> = LazyLock::new(|
     .expect("Error converting eth fee contract address from felt")
 });
 
-#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 /// Parameters that are needed for execution.
 pub struct ExecutionConfig {
     /// The strk address to receive fees
@@ -123,6 +132,14 @@ pub struct ExecutionConfig {
     pub eth_fee_contract_address: ContractAddress,
     /// The initial gas cost for a transaction
     pub default_initial_gas_cost: u64,
+    /// How the fees are split between the sequencer and the fee distribution recipients; should be
+    /// the chain's, for executions to transfer the fees as the sequencer does.
+    #[serde(default)]
+    pub fee_distribution: FeeDistribution,
+    /// Account transaction versions the chain does not execute; should be the chain's, for
+    /// executions to reject the transactions the sequencer rejects.
+    #[serde(default)]
+    pub disabled_tx_versions: TransactionVersions,
 }
 
 impl Default for ExecutionConfig {
@@ -131,13 +148,15 @@ impl Default for ExecutionConfig {
             strk_fee_contract_address: *STRK_FEE_CONTRACT_ADDRESS,
             eth_fee_contract_address: *ETH_FEE_CONTRACT_ADDRESS,
             default_initial_gas_cost: DEFAULT_INITIAL_GAS_COST,
+            fee_distribution: FeeDistribution::default(),
+            disabled_tx_versions: TransactionVersions::default(),
         }
     }
 }
 
 impl SerializeConfig for ExecutionConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param(
                 "strk_fee_contract_address",
                 &self.strk_fee_contract_address,
@@ -156,7 +175,16 @@ impl SerializeConfig for ExecutionConfig {
                 "The initial gas cost for a transaction",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "disabled_tx_versions",
+                &self.disabled_tx_versions,
+                "Space-separated account transaction versions that are neither accepted nor \
+                 executed on the chain, e.g. \"0 1\".",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.append(&mut append_sub_config_name(self.fee_distribution.dump(), "fee_distribution"));
+        dump
     }
 }
 
@@ -185,10 +213,14 @@ pub enum ExecutionError {
     ContractNotFound { contract_address: ContractAddress, state_number: StateNumber },
     #[error("Gas consumed should fit into u64")]
     GasConsumedOutOfRange,
+    #[error("Invalid state overrides: {0}")]
+    InvalidStateOverrides(StarknetApiError),
     #[error("Missing class hash in call info")]
     MissingClassHash,
     #[error("Missing compiled class with hash {class_hash} (The CASM table isn't synced)")]
     MissingCompiledClass { class_hash: ClassHash },
+    #[error("Missing the Starknet version of block {block_number} (The headers aren't synced)")]
+    MissingStarknetVersion { block_number: BlockNumber },
     #[error(transparent)]
     StateError(#[from] blockifier::state::errors::StateError),
     #[error(transparent)]
@@ -213,7 +245,7 @@ pub type OnlyQuery = bool;
 /// Gathers all the possible errors that can be returned from the blockifier.
 type BlockifierError = anyhow::Error;
 
-/// Executes a StarkNet call and returns the execution result.
+/// Executes a StarkNet call on top of the given state overrides and returns the execution result.
 #[allow(clippy::too_many_arguments)]
 // TODO(Dan, Yair): consider box large elements (because of BadDeclareTransaction) or use ID
 // instead.
@@ -229,13 +261,18 @@ pub fn execute_call(
     calldata: Calldata,
     execution_config: &ExecutionConfig,
     override_kzg_da_to_false: bool,
+    state_overrides: StateOverrides,
 ) -> ExecutionResult<CallExecution> {
-    verify_contract_exists(
-        *contract_address,
-        &storage_reader,
-        state_number,
-        maybe_pending_data.as_ref(),
-    )?;
+    let state_overlay = StateOverlay::new(state_overrides, execution_config)
+        .map_err(ExecutionError::InvalidStateOverrides)?;
+    if state_overlay.get_class_hash_at(*contract_address).is_none() {
+        verify_contract_exists(
+            *contract_address,
+            &storage_reader,
+            state_number,
+            maybe_pending_data.as_ref(),
+        )?;
+    }
 
     // TODO(yair): check if this is the correct value.
     let mut remaining_gas = execution_config.default_initial_gas_cost;
@@ -256,6 +293,7 @@ pub fn execute_call(
         state_number,
         maybe_pending_data: maybe_pending_data.clone(),
         missing_compiled_class: Cell::new(None),
+        state_overlay,
     });
 
     let block_context = create_block_context(
@@ -390,11 +428,17 @@ fn create_block_context(
             strk_fee_token_address: execution_config.strk_fee_contract_address,
             eth_fee_token_address: execution_config.eth_fee_contract_address,
         },
+        custom_fee_tokens: CustomFeeTokens::default(),
+        disabled_tx_versions: execution_config.disabled_tx_versions.clone(),
+        sierra_gas_only: false,
+        fee_distribution: execution_config.fee_distribution,
+        paymaster: PaymasterConfig::default(),
     };
+    // A pending block is executed with the version of its parent, the latest stored block.
     let starknet_version = storage_reader
         .begin_ro_txn()?
-        .get_starknet_version(block_number)?
-        .unwrap_or(StarknetVersion::LATEST);
+        .get_starknet_version(block_context_number)?
+        .ok_or(ExecutionError::MissingStarknetVersion { block_number: block_context_number })?;
     let versioned_constants = VersionedConstants::get(&starknet_version)?;
 
     let block_context = BlockContext::new(
@@ -402,7 +446,8 @@ fn create_block_context(
         chain_info,
         versioned_constants.clone(),
         BouncerConfig::max(),
-    );
+    )
+    .with_starknet_version(starknet_version);
     let next_block_number = block_context.block_info().block_number;
 
     pre_process_block(
@@ -411,6 +456,12 @@ fn create_block_context(
         next_block_number,
         &versioned_constants.os_constants,
     )?;
+    write_block_metadata(
+        cached_state,
+        block_context.block_info(),
+        starknet_version,
+        &versioned_constants.os_constants,
+    )?;
     Ok(block_context)
 }
 
@@ -610,7 +661,8 @@ pub struct RevertedTransaction {
 /// index and revert reason of the first reverted transaction.
 pub type FeeEstimationResult = Result<Vec<FeeEstimation>, RevertedTransaction>;
 
-/// Returns the fee estimation for a series of transactions.
+/// Returns the fee estimation for a series of transactions, executed on top of the given state
+/// overrides.
 #[allow(clippy::too_many_arguments)]
 // TODO(Dan, Yair): consider box large elements (because of BadDeclareTransaction) or use ID
 // instead.
@@ -625,6 +677,7 @@ pub fn estimate_fee(
     execution_config: &ExecutionConfig,
     validate: bool,
     override_kzg_da_to_false: bool,
+    state_overrides: StateOverrides,
 ) -> ExecutionResult<FeeEstimationResult> {
     let (txs_execution_info, block_context) = execute_transactions(
         txs,
@@ -638,6 +691,7 @@ pub fn estimate_fee(
         false,
         validate,
         override_kzg_da_to_false,
+        state_overrides,
     )?;
     let mut result = Vec::new();
     for (index, tx_execution_output) in txs_execution_info.into_iter().enumerate() {
@@ -679,13 +733,17 @@ fn execute_transactions(
     charge_fee: bool,
     validate: bool,
     override_kzg_da_to_false: bool,
+    state_overrides: StateOverrides,
 ) -> ExecutionResult<(Vec<TransactionExecutionOutput>, BlockContext)> {
+    let state_overlay = StateOverlay::new(state_overrides, execution_config)
+        .map_err(ExecutionError::InvalidStateOverrides)?;
     // The starknet state will be from right before the block in which the transactions should run.
     let mut cached_state = CachedState::new(ExecutionStateReader {
         storage_reader: storage_reader.clone(),
         state_number,
         maybe_pending_data: maybe_pending_data.clone(),
         missing_compiled_class: Cell::new(None),
+        state_overlay,
     });
 
     let block_context = create_block_context(
@@ -958,7 +1016,8 @@ fn to_blockifier_tx(
     }
 }
 
-/// Simulates a series of transactions and returns the transaction traces and the fee estimations.
+/// Simulates a series of transactions on top of the given state overrides and returns the
+/// transaction traces and the fee estimations.
 // TODO(yair): Return structs instead of tuples.
 // TODO(Dan, Yair): consider box large elements (because of BadDeclareTransaction) or use ID
 // instead.
@@ -976,6 +1035,7 @@ pub fn simulate_transactions(
     charge_fee: bool,
     validate: bool,
     override_kzg_da_to_false: bool,
+    state_overrides: StateOverrides,
 ) -> ExecutionResult<Vec<TransactionSimulationOutput>> {
     let trace_constructors = txs.iter().map(get_trace_constructor).collect::<Vec<_>>();
     let (execution_results, block_context) = execute_transactions(
@@ -990,6 +1050,7 @@ pub fn simulate_transactions(
         charge_fee,
         validate,
         override_kzg_da_to_false,
+        state_overrides,
     )?;
     execution_results
         .into_iter()
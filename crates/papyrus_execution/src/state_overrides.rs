@@ -0,0 +1,172 @@
+//! Overrides of the state that transactions and calls are executed on top of.
+#[cfg(test)]
+#[path = "state_overrides_test.rs"]
+mod state_overrides_test;
+
+use std::collections::{BTreeMap, HashMap};
+
+use blockifier::execution::contract_class::{
+    CompiledClassV0,
+    CompiledClassV1,
+    RunnableCompiledClass,
+};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::StateResult;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use serde::{Deserialize, Serialize};
+use starknet_api::abi::abi_utils::get_fee_token_var_address;
+use starknet_api::contract_class::SierraVersion;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use starknet_api::state::{SierraContractClass, StorageKey};
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::Felt;
+
+use crate::ExecutionConfig;
+
+/// Caller-provided overrides of the state. Execution sees the overridden values instead of the
+/// stored ones, and the stored state is never modified (similar to the state overrides of
+/// `eth_call`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct StateOverrides {
+    /// Overrides of the state of contracts, keyed by contract address.
+    #[serde(default)]
+    pub contracts: BTreeMap<ContractAddress, ContractStateOverride>,
+    /// Classes that are declared only for the execution, keyed by class hash. The hashes are not
+    /// verified against the definitions.
+    #[serde(default)]
+    pub classes: BTreeMap<ClassHash, ClassOverride>,
+}
+
+/// Overrides of the state of a single contract. Values that are not given are read from the state.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ContractStateOverride {
+    /// The class of the contract. Deploys the contract if it isn't deployed, or replaces its
+    /// class otherwise. The class must be declared, or given by the class overrides.
+    pub class_hash: Option<ClassHash>,
+    /// The nonce of the contract.
+    pub nonce: Option<Nonce>,
+    /// Storage entries of the contract. Entries that are not given keep their value.
+    #[serde(default)]
+    pub storage_diff: BTreeMap<StorageKey, Felt>,
+    /// The balance of the contract in the ETH fee token.
+    pub eth_balance: Option<u128>,
+    /// The balance of the contract in the STRK fee token.
+    pub strk_balance: Option<u128>,
+}
+
+/// The definition of a class declared by the state overrides.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassOverride {
+    /// A Cairo 0 class.
+    Deprecated(DeprecatedContractClass),
+    /// A Cairo 1 class, with the CASM it compiles to, which is what the node executes.
+    Cairo1 { sierra: SierraContractClass, casm: CasmContractClass },
+}
+
+/// The state overrides, resolved into the values the state reader looks up.
+#[derive(Debug, Default)]
+pub(crate) struct StateOverlay {
+    storage: HashMap<(ContractAddress, StorageKey), Felt>,
+    nonces: HashMap<ContractAddress, Nonce>,
+    class_hashes: HashMap<ContractAddress, ClassHash>,
+    classes: HashMap<ClassHash, ClassOverride>,
+}
+
+impl StateOverlay {
+    pub(crate) fn new(
+        state_overrides: StateOverrides,
+        execution_config: &ExecutionConfig,
+    ) -> Result<Self, StarknetApiError> {
+        let StateOverrides { contracts, classes } = state_overrides;
+        let mut overlay = Self { classes: classes.into_iter().collect(), ..Default::default() };
+        for (contract_address, contract_override) in contracts {
+            let ContractStateOverride {
+                class_hash,
+                nonce,
+                storage_diff,
+                eth_balance,
+                strk_balance,
+            } = contract_override;
+            if let Some(class_hash) = class_hash {
+                overlay.class_hashes.insert(contract_address, class_hash);
+            }
+            if let Some(nonce) = nonce {
+                overlay.nonces.insert(contract_address, nonce);
+            }
+            overlay.storage.extend(
+                storage_diff.into_iter().map(|(key, value)| ((contract_address, key), value)),
+            );
+            if let Some(balance) = eth_balance {
+                overlay.set_balance(
+                    contract_address,
+                    execution_config.eth_fee_contract_address,
+                    balance,
+                )?;
+            }
+            if let Some(balance) = strk_balance {
+                overlay.set_balance(
+                    contract_address,
+                    execution_config.strk_fee_contract_address,
+                    balance,
+                )?;
+            }
+        }
+        Ok(overlay)
+    }
+
+    // Fee token balances are u256 values, stored as (low, high) in consecutive storage keys.
+    fn set_balance(
+        &mut self,
+        contract_address: ContractAddress,
+        fee_token_address: ContractAddress,
+        balance: u128,
+    ) -> Result<(), StarknetApiError> {
+        let low_key = get_fee_token_var_address(contract_address);
+        let high_key = low_key.next_storage_key()?;
+        self.storage.insert((fee_token_address, low_key), Felt::from(balance));
+        self.storage.insert((fee_token_address, high_key), Felt::ZERO);
+        Ok(())
+    }
+
+    pub(crate) fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> Option<Felt> {
+        self.storage.get(&(contract_address, key)).copied()
+    }
+
+    pub(crate) fn get_nonce_at(&self, contract_address: ContractAddress) -> Option<Nonce> {
+        self.nonces.get(&contract_address).copied()
+    }
+
+    pub(crate) fn get_class_hash_at(&self, contract_address: ContractAddress) -> Option<ClassHash> {
+        self.class_hashes.get(&contract_address).copied()
+    }
+
+    /// Returns the compiled class of the given class, if declared by the overrides.
+    pub(crate) fn get_compiled_class(
+        &self,
+        class_hash: ClassHash,
+    ) -> Option<StateResult<RunnableCompiledClass>> {
+        let compiled_class = match self.classes.get(&class_hash)? {
+            ClassOverride::Deprecated(deprecated_class) => {
+                CompiledClassV0::try_from(deprecated_class.clone())
+                    .map(RunnableCompiledClass::V0)
+                    .map_err(StateError::ProgramError)
+            }
+            ClassOverride::Cairo1 { sierra, casm } => {
+                SierraVersion::extract_from_program(&sierra.sierra_program)
+                    .map_err(StateError::from)
+                    .and_then(|sierra_version| {
+                        CompiledClassV1::try_from((casm.clone(), sierra_version))
+                            .map(RunnableCompiledClass::V1)
+                            .map_err(StateError::ProgramError)
+                    })
+            }
+        };
+        Some(compiled_class)
+    }
+}
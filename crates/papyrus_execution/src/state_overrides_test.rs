@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use starknet_api::abi::abi_utils::get_fee_token_var_address;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_api::{class_hash, contract_address, felt, nonce, storage_key};
+use starknet_types_core::felt::Felt;
+
+use crate::state_overrides::{ContractStateOverride, StateOverlay, StateOverrides};
+use crate::ExecutionConfig;
+
+#[test]
+fn state_overlay_resolves_overrides() {
+    let execution_config = ExecutionConfig::default();
+    let address = contract_address!("0x100");
+    let state_overrides = StateOverrides {
+        contracts: BTreeMap::from([(
+            address,
+            ContractStateOverride {
+                class_hash: Some(class_hash!("0x1")),
+                nonce: Some(nonce!(7_u8)),
+                storage_diff: BTreeMap::from([(storage_key!("0x10"), felt!("0x20"))]),
+                eth_balance: None,
+                strk_balance: Some(1000),
+            },
+        )]),
+        ..Default::default()
+    };
+
+    let overlay = StateOverlay::new(state_overrides, &execution_config).unwrap();
+
+    assert_eq!(overlay.get_class_hash_at(address), Some(class_hash!("0x1")));
+    assert_eq!(overlay.get_nonce_at(address), Some(nonce!(7_u8)));
+    assert_eq!(overlay.get_storage_at(address, storage_key!("0x10")), Some(felt!("0x20")));
+    assert_eq!(overlay.get_storage_at(address, storage_key!("0x11")), None);
+
+    let balance_low_key = get_fee_token_var_address(address);
+    let balance_high_key = balance_low_key.next_storage_key().unwrap();
+    let strk_address = execution_config.strk_fee_contract_address;
+    assert_eq!(overlay.get_storage_at(strk_address, balance_low_key), Some(Felt::from(1000_u16)));
+    assert_eq!(overlay.get_storage_at(strk_address, balance_high_key), Some(Felt::ZERO));
+    let eth_address = execution_config.eth_fee_contract_address;
+    assert_eq!(overlay.get_storage_at(eth_address, balance_low_key), None);
+
+    let other_address = contract_address!("0x200");
+    assert_eq!(overlay.get_class_hash_at(other_address), None);
+    assert_eq!(overlay.get_nonce_at(other_address), None);
+}
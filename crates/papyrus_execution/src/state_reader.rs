@@ -23,6 +23,7 @@ use starknet_types_core::felt::Felt;
 use crate::execution_utils;
 use crate::execution_utils::{get_contract_class, ExecutionUtilsError};
 use crate::objects::PendingData;
+use crate::state_overrides::StateOverlay;
 
 /// A view into the state at a specific state number.
 pub struct ExecutionStateReader {
@@ -32,6 +33,8 @@ pub struct ExecutionStateReader {
     // We want to return a custom error when missing a compiled class, but we need to return
     // Blockifier's error, so we store the missing class's hash in case of error.
     pub missing_compiled_class: Cell<Option<ClassHash>>,
+    // Caller-provided overrides, taking precedence over both the storage and the pending data.
+    pub(crate) state_overlay: StateOverlay,
 }
 
 impl BlockifierStateReader for ExecutionStateReader {
@@ -40,6 +43,9 @@ impl BlockifierStateReader for ExecutionStateReader {
         contract_address: ContractAddress,
         key: StorageKey,
     ) -> StateResult<Felt> {
+        if let Some(value) = self.state_overlay.get_storage_at(contract_address, key) {
+            return Ok(value);
+        }
         execution_utils::get_storage_at(
             &self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)?,
             self.state_number,
@@ -52,6 +58,9 @@ impl BlockifierStateReader for ExecutionStateReader {
 
     // Returns the default value if the contract address is not found.
     fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        if let Some(nonce) = self.state_overlay.get_nonce_at(contract_address) {
+            return Ok(nonce);
+        }
         Ok(execution_utils::get_nonce_at(
             &self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)?,
             self.state_number,
@@ -64,6 +73,9 @@ impl BlockifierStateReader for ExecutionStateReader {
 
     // Returns the default value if the contract address is not found.
     fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        if let Some(class_hash) = self.state_overlay.get_class_hash_at(contract_address) {
+            return Ok(class_hash);
+        }
         Ok(execution_utils::get_class_hash_at(
             &self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)?,
             self.state_number,
@@ -77,6 +89,9 @@ impl BlockifierStateReader for ExecutionStateReader {
     }
 
     fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        if let Some(compiled_class) = self.state_overlay.get_compiled_class(class_hash) {
+            return compiled_class;
+        }
         if let Some(pending_classes) =
             self.maybe_pending_data.as_ref().map(|pending_data| &pending_data.classes)
         {
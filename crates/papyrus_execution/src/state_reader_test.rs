@@ -32,6 +32,7 @@ use starknet_api::{contract_address, felt, storage_key};
 use starknet_types_core::felt::Felt;
 
 use crate::objects::PendingData;
+use crate::state_overrides::StateOverlay;
 use crate::state_reader::ExecutionStateReader;
 use crate::test_utils::{get_test_casm, get_test_deprecated_contract_class};
 
@@ -162,6 +163,7 @@ fn read_state() {
         state_number: state_number0,
         maybe_pending_data: None,
         missing_compiled_class: Cell::new(None),
+        state_overlay: StateOverlay::default(),
     };
     let storage_after_block_0 = state_reader0.get_storage_at(address0, storage_key0).unwrap();
     assert_eq!(storage_after_block_0, Felt::default());
@@ -182,6 +184,7 @@ fn read_state() {
         state_number: state_number1,
         maybe_pending_data: None,
         missing_compiled_class: Cell::new(None),
+        state_overlay: StateOverlay::default(),
     };
     let storage_after_block_1 = state_reader1.get_storage_at(address0, storage_key0).unwrap();
     assert_eq!(storage_after_block_1, storage_value0);
@@ -204,6 +207,7 @@ fn read_state() {
         state_number: state_number2,
         maybe_pending_data: None,
         missing_compiled_class: Cell::new(None),
+        state_overlay: StateOverlay::default(),
     };
     let nonce_after_block_2 = state_reader2.get_nonce_at(address0).unwrap();
     assert_eq!(nonce_after_block_2, nonce0);
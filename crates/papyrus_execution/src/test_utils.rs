@@ -48,6 +48,7 @@ use starknet_types_core::felt::Felt;
 
 use crate::execution_utils::selector_from_name;
 use crate::objects::{PendingData, TransactionSimulationOutput};
+use crate::state_overrides::StateOverrides;
 use crate::testing_instances::get_test_execution_config;
 use crate::{simulate_transactions, ExecutableTransactionInput, OnlyQuery, SierraSize};
 
@@ -222,6 +223,7 @@ pub fn execute_simulate_transactions(
         validate,
         // TODO: Consider testing without overriding DA (It's already tested in the RPC)
         true,
+        StateOverrides::default(),
     )
     .unwrap()
 }
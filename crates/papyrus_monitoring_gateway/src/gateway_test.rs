@@ -34,6 +34,7 @@ fn setup_app() -> Router {
         SECRET.to_string(),
         None,
         TEST_PEER_ID.to_string(),
+        None,
     )
 }
 
@@ -162,6 +163,14 @@ async fn without_metrics() {
     assert!(body_bytes.is_empty());
 }
 
+#[tokio::test]
+async fn without_base_layer() {
+    let app = setup_app();
+    let response = request_app(app, "baseLayerStatus").await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
 #[tokio::test]
 async fn with_metrics() {
     // Creates an app with prometheus handle.
@@ -176,6 +185,7 @@ async fn with_metrics() {
         String::new(),
         Some(prometheus_handle),
         TEST_PEER_ID.to_string(),
+        None,
     );
 
     // Register a metric.
@@ -17,9 +17,12 @@ use axum::routing::get;
 use axum::{Json, Router};
 use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
 use metrics_process::Collector;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerContract;
+use papyrus_base_layer::{BaseLayerContract, Finality};
 use papyrus_config::converters::{deserialize_optional_map, serialize_optional_map};
 use papyrus_config::dumping::{ser_generated_param, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
+use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::mmap_file::MMapFileStats;
 use papyrus_storage::{DbStats, StorageError, StorageReader};
 use rand::distributions::Alphanumeric;
@@ -33,6 +36,8 @@ use validator::Validate;
 
 const MONITORING_PREFIX: &str = "monitoring";
 const PROCESS_METRICS_PREFIX: &str = "papyrus_";
+// The same finality the sync uses for the proved blocks.
+const BASE_LAYER_FINALITY: Finality = Finality::Latest;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
 pub struct MonitoringGatewayConfig {
@@ -119,6 +124,7 @@ pub struct MonitoringServer {
     version: &'static str,
     prometheus_handle: Option<PrometheusHandle>,
     own_peer_id: String,
+    base_layer: Option<Arc<EthereumBaseLayerContract>>,
 }
 
 impl MonitoringServer {
@@ -129,6 +135,7 @@ impl MonitoringServer {
         storage_reader: StorageReader,
         version: &'static str,
         own_peer_id: String,
+        base_layer: Option<Arc<EthereumBaseLayerContract>>,
     ) -> Result<Self, BuildError> {
         let prometheus_handle = if config.collect_metrics {
             let mut builder = PrometheusBuilder::new();
@@ -149,6 +156,7 @@ impl MonitoringServer {
             version,
             prometheus_handle,
             own_peer_id,
+            base_layer,
         })
     }
 
@@ -173,6 +181,7 @@ impl MonitoringServer {
             self.config.present_full_config_secret.clone(),
             self.prometheus_handle.clone(),
             self.own_peer_id.clone(),
+            self.base_layer.clone(),
         );
         debug!("Starting monitoring gateway.");
         axum::Server::bind(&server_address).serve(app.into_make_service()).await
@@ -189,6 +198,7 @@ fn app(
     present_full_config_secret: String,
     prometheus_handle: Option<PrometheusHandle>,
     own_peer_id: String,
+    base_layer: Option<Arc<EthereumBaseLayerContract>>,
 ) -> Router {
     let is_ready_retry_config =
         RetryConfig { retry_base_millis: 50, retry_max_delay_millis: 1000, max_retries: 0 };
@@ -208,6 +218,7 @@ fn app(
 
     let db_tables_stats_reader = storage_reader.clone();
     let mmap_files_stats_reader = storage_reader.clone();
+    let base_layer_status_reader = storage_reader.clone();
 
     Router::new()
         .route(
@@ -250,6 +261,10 @@ fn app(
             get(move || is_ready(starknet_client, starknet_feeder_client)),
         )
         .route(format!("/{MONITORING_PREFIX}/peer_id").as_str(), get(move || async { own_peer_id }))
+        .route(
+            format!("/{MONITORING_PREFIX}/baseLayerStatus").as_str(),
+            get(move || base_layer_status(base_layer, base_layer_status_reader)),
+        )
 }
 
 async fn is_ready<TStarknetWriter: StarknetWriter, TStarknetReader: StarknetReader>(
@@ -314,6 +329,22 @@ async fn metrics(prometheus_handle: Option<PrometheusHandle>) -> Response {
     }
 }
 
+/// Returns the status of the base layer and how far the proved blocks are behind the synced ones.
+/// In case the node doesn't track the base layer returns an empty response with status code 405:
+/// method not allowed.
+#[instrument(level = "debug", skip(base_layer, storage_reader))]
+async fn base_layer_status(
+    base_layer: Option<Arc<EthereumBaseLayerContract>>,
+    storage_reader: StorageReader,
+) -> Result<Response, ServerError> {
+    let Some(base_layer) = base_layer else {
+        return Ok(StatusCode::METHOD_NOT_ALLOWED.into_response());
+    };
+    let header_marker = storage_reader.begin_ro_txn()?.get_header_marker()?;
+    let status = base_layer.status(BASE_LAYER_FINALITY, header_marker).await;
+    Ok(Json(status).into_response())
+}
+
 /// Returns the node version.
 #[instrument(level = "debug", ret)]
 async fn node_version(version: &'static str) -> String {
@@ -8,7 +8,10 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::StreamExt;
-use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
+use papyrus_base_layer::ethereum_base_layer_contract::{
+    EthereumBaseLayerConfig,
+    EthereumBaseLayerContract,
+};
 use papyrus_common::metrics::COLLECT_PROFILING_METRICS;
 use papyrus_common::pending_classes::PendingClasses;
 use papyrus_config::presentation::get_config_presentation;
@@ -167,6 +170,9 @@ fn spawn_monitoring_server(
     local_peer_id: String,
     config: &NodeConfig,
 ) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
+    // Only the central sync tracks the base layer.
+    let base_layer =
+        config.sync.map(|_| Arc::new(EthereumBaseLayerContract::new(config.base_layer.clone())));
     let monitoring_server = MonitoringServer::new(
         config.monitoring_gateway.clone(),
         get_config_presentation(config, true)?,
@@ -174,6 +180,7 @@ fn spawn_monitoring_server(
         storage_reader,
         VERSION_FULL,
         local_peer_id,
+        base_layer,
     )?;
     Ok(tokio::spawn(async move { Ok(monitoring_server.run_server().await?) }))
 }
@@ -1,9 +1,19 @@
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::transaction::Transaction;
 
 use crate::converters::ProtobufConversionError;
 
+/// The maximal size of a decompressed transaction batch, protecting validators from proposal parts
+/// that decompress to much more than they claim.
+pub const MAX_UNCOMPRESSED_TRANSACTION_BATCH_SIZE: u64 = 1 << 26;
+
 #[derive(Debug, Default, Hash, Clone, Eq, PartialEq)]
 pub enum VoteType {
     Prevote,
@@ -44,6 +54,9 @@ pub struct ProposalInit {
     pub valid_round: Option<u32>,
     /// Address of the one who proposed the block.
     pub proposer: ContractAddress,
+    /// The proposer's signature over the randomness message of the height, which the block's
+    /// randomness seed is derived from.
+    pub randomness_signature: Option<Signature>,
 }
 
 /// A temporary constant to use as a validator ID. Zero is not a valid contract address.
@@ -57,6 +70,7 @@ impl Default for ProposalInit {
             round: Default::default(),
             valid_round: Default::default(),
             proposer: ContractAddress::from(DEFAULT_VALIDATOR_ID),
+            randomness_signature: Default::default(),
         }
     }
 }
@@ -68,6 +82,89 @@ pub struct TransactionBatch {
     pub transactions: Vec<Transaction>,
 }
 
+/// The algorithms a proposer can compress transaction batches with. The algorithm is sent along
+/// with each compressed batch, so validators decompress with whichever algorithm the proposer
+/// chose.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CompressionAlgorithm {
+    #[default]
+    Uncompressed,
+    Zstd,
+    Gzip,
+}
+
+/// An encoded [`TransactionBatch`], compressed to reduce the bandwidth of gossiping proposals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressedTransactionBatch {
+    pub algorithm: CompressionAlgorithm,
+    /// The size of the encoded batch.
+    pub uncompressed_size: u64,
+    pub data: Vec<u8>,
+}
+
+impl CompressedTransactionBatch {
+    pub fn compress(batch: TransactionBatch, algorithm: CompressionAlgorithm) -> Self {
+        let encoded: Vec<u8> = batch.into();
+        let uncompressed_size = u64::try_from(encoded.len()).expect("usize should fit in u64");
+        // Writing to memory can't fail.
+        let data = match algorithm {
+            CompressionAlgorithm::Uncompressed => encoded,
+            CompressionAlgorithm::Zstd => {
+                zstd::encode_all(&encoded[..], zstd::DEFAULT_COMPRESSION_LEVEL)
+                    .expect("Failed to compress transaction batch")
+            }
+            CompressionAlgorithm::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(&encoded)
+                    .and_then(|()| encoder.finish())
+                    .expect("Failed to compress transaction batch")
+            }
+        };
+        Self { algorithm, uncompressed_size, data }
+    }
+
+    /// Decompresses and decodes the batch, failing if it doesn't decompress to exactly its claimed
+    /// size.
+    pub fn decompress(self) -> Result<TransactionBatch, ProtobufConversionError> {
+        if self.uncompressed_size > MAX_UNCOMPRESSED_TRANSACTION_BATCH_SIZE {
+            return Err(ProtobufConversionError::OutOfRangeValue {
+                type_description: "uncompressed transaction batch size",
+                value_as_str: self.uncompressed_size.to_string(),
+            });
+        }
+        // Reading one byte beyond the claimed size detects batches that decompress to more.
+        let limit = self.uncompressed_size + 1;
+        let encoded = match self.algorithm {
+            CompressionAlgorithm::Uncompressed => self.data,
+            CompressionAlgorithm::Zstd => read_bounded(
+                zstd::Decoder::new(&self.data[..]).map_err(|error| {
+                    ProtobufConversionError::CompressionError(error.to_string())
+                })?,
+                limit,
+            )?,
+            CompressionAlgorithm::Gzip => read_bounded(GzDecoder::new(&self.data[..]), limit)?,
+        };
+        let decompressed_size = u64::try_from(encoded.len()).expect("usize should fit in u64");
+        if decompressed_size != self.uncompressed_size {
+            return Err(ProtobufConversionError::CompressionError(format!(
+                "Transaction batch decompressed to {decompressed_size} bytes instead of {}.",
+                self.uncompressed_size
+            )));
+        }
+        TransactionBatch::try_from(encoded)
+    }
+}
+
+fn read_bounded(reader: impl Read, limit: u64) -> Result<Vec<u8>, ProtobufConversionError> {
+    let mut data = Vec::new();
+    reader
+        .take(limit)
+        .read_to_end(&mut data)
+        .map_err(|error| ProtobufConversionError::CompressionError(error.to_string()))?;
+    Ok(data)
+}
+
 /// The proposal is done when receiving this fin message, which contains the block hash.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProposalFin {
@@ -83,6 +180,8 @@ pub enum ProposalPart {
     Init(ProposalInit),
     /// A part of the proposal that contains one or more transactions.
     Transactions(TransactionBatch),
+    /// Like `Transactions`, compressed.
+    CompressedTransactions(CompressedTransactionBatch),
     /// The final part of the proposal, including the block hash.
     Fin(ProposalFin),
 }
@@ -4,11 +4,13 @@ mod consensus_test;
 use std::convert::{TryFrom, TryInto};
 
 use prost::Message;
-use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::block::{BlockHash, BlockNumber, BlockSignature};
 use starknet_api::hash::StarkHash;
 use starknet_api::transaction::Transaction;
 
 use crate::consensus::{
+    CompressedTransactionBatch,
+    CompressionAlgorithm,
     ProposalFin,
     ProposalInit,
     ProposalPart,
@@ -163,7 +165,17 @@ impl TryFrom<protobuf::ProposalInit> for ProposalInit {
             .proposer
             .ok_or(ProtobufConversionError::MissingField { field_description: "proposer" })?
             .try_into()?;
-        Ok(ProposalInit { height: BlockNumber(height), round, valid_round, proposer })
+        let randomness_signature = value
+            .randomness_signature
+            .map(|signature| BlockSignature::try_from(signature).map(|signature| signature.0))
+            .transpose()?;
+        Ok(ProposalInit {
+            height: BlockNumber(height),
+            round,
+            valid_round,
+            proposer,
+            randomness_signature,
+        })
     }
 }
 
@@ -174,6 +186,9 @@ impl From<ProposalInit> for protobuf::ProposalInit {
             round: value.round,
             valid_round: value.valid_round,
             proposer: Some(value.proposer.into()),
+            randomness_signature: value
+                .randomness_signature
+                .map(|signature| BlockSignature(signature).into()),
         }
     }
 }
@@ -201,6 +216,56 @@ impl From<TransactionBatch> for protobuf::TransactionBatch {
 
 auto_impl_into_and_try_from_vec_u8!(TransactionBatch, protobuf::TransactionBatch);
 
+impl TryFrom<protobuf::CompressionAlgorithm> for CompressionAlgorithm {
+    type Error = ProtobufConversionError;
+
+    fn try_from(value: protobuf::CompressionAlgorithm) -> Result<Self, Self::Error> {
+        match value {
+            protobuf::CompressionAlgorithm::Uncompressed => Ok(CompressionAlgorithm::Uncompressed),
+            protobuf::CompressionAlgorithm::Zstd => Ok(CompressionAlgorithm::Zstd),
+            protobuf::CompressionAlgorithm::Gzip => Ok(CompressionAlgorithm::Gzip),
+        }
+    }
+}
+
+impl From<CompressionAlgorithm> for protobuf::CompressionAlgorithm {
+    fn from(value: CompressionAlgorithm) -> Self {
+        match value {
+            CompressionAlgorithm::Uncompressed => protobuf::CompressionAlgorithm::Uncompressed,
+            CompressionAlgorithm::Zstd => protobuf::CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Gzip => protobuf::CompressionAlgorithm::Gzip,
+        }
+    }
+}
+
+impl TryFrom<protobuf::CompressedTransactionBatch> for CompressedTransactionBatch {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::CompressedTransactionBatch) -> Result<Self, Self::Error> {
+        // Fails on algorithms this node doesn't know.
+        let algorithm = protobuf::CompressionAlgorithm::try_from(value.algorithm)?.try_into()?;
+        Ok(CompressedTransactionBatch {
+            algorithm,
+            uncompressed_size: value.uncompressed_size,
+            data: value.data,
+        })
+    }
+}
+
+impl From<CompressedTransactionBatch> for protobuf::CompressedTransactionBatch {
+    fn from(value: CompressedTransactionBatch) -> Self {
+        protobuf::CompressedTransactionBatch {
+            algorithm: i32::from(protobuf::CompressionAlgorithm::from(value.algorithm)),
+            uncompressed_size: value.uncompressed_size,
+            data: value.data,
+        }
+    }
+}
+
+auto_impl_into_and_try_from_vec_u8!(
+    CompressedTransactionBatch,
+    protobuf::CompressedTransactionBatch
+);
+
 impl TryFrom<protobuf::ProposalFin> for ProposalFin {
     type Error = ProtobufConversionError;
     fn try_from(value: protobuf::ProposalFin) -> Result<Self, Self::Error> {
@@ -235,6 +300,9 @@ impl TryFrom<protobuf::ProposalPart> for ProposalPart {
         match part {
             Message::Init(init) => Ok(ProposalPart::Init(init.try_into()?)),
             Message::Transactions(content) => Ok(ProposalPart::Transactions(content.try_into()?)),
+            Message::CompressedTransactions(content) => {
+                Ok(ProposalPart::CompressedTransactions(content.try_into()?))
+            }
             Message::Fin(fin) => Ok(ProposalPart::Fin(fin.try_into()?)),
         }
     }
@@ -249,6 +317,11 @@ impl From<ProposalPart> for protobuf::ProposalPart {
             ProposalPart::Transactions(content) => protobuf::ProposalPart {
                 message: Some(protobuf::proposal_part::Message::Transactions(content.into())),
             },
+            ProposalPart::CompressedTransactions(content) => protobuf::ProposalPart {
+                message: Some(protobuf::proposal_part::Message::CompressedTransactions(
+                    content.into(),
+                )),
+            },
             ProposalPart::Fin(fin) => protobuf::ProposalPart {
                 message: Some(protobuf::proposal_part::Message::Fin(fin.into())),
             },
@@ -12,6 +12,8 @@ use starknet_api::transaction::{
 };
 
 use crate::consensus::{
+    CompressedTransactionBatch,
+    CompressionAlgorithm,
     ProposalFin,
     ProposalInit,
     ProposalPart,
@@ -20,6 +22,7 @@ use crate::consensus::{
     TransactionBatch,
     Vote,
 };
+use crate::converters::ProtobufConversionError;
 
 // If all the fields of `AllResources` are 0 upon serialization,
 // then the deserialized value will be interpreted as the `L1Gas` variant.
@@ -125,6 +128,40 @@ fn convert_proposal_part_to_vec_u8_and_back() {
     assert_eq!(proposal_part, res_data);
 }
 
+#[test]
+fn compress_transaction_batch_and_back() {
+    let mut rng = get_rng();
+
+    let mut transaction_batch = TransactionBatch::get_test_instance(&mut rng);
+    add_gas_values_to_transaction(&mut transaction_batch.transactions);
+
+    for algorithm in
+        [CompressionAlgorithm::Uncompressed, CompressionAlgorithm::Zstd, CompressionAlgorithm::Gzip]
+    {
+        let compressed = CompressedTransactionBatch::compress(transaction_batch.clone(), algorithm);
+        let bytes_data: Vec<u8> = ProposalPart::CompressedTransactions(compressed).into();
+        let ProposalPart::CompressedTransactions(compressed) =
+            ProposalPart::try_from(bytes_data).unwrap()
+        else {
+            panic!("Expected compressed transactions");
+        };
+        assert_eq!(compressed.algorithm, algorithm);
+        assert_eq!(compressed.decompress().unwrap(), transaction_batch);
+    }
+}
+
+#[test]
+fn decompress_rejects_wrong_uncompressed_size() {
+    let mut rng = get_rng();
+
+    let transaction_batch = TransactionBatch::get_test_instance(&mut rng);
+    let mut compressed =
+        CompressedTransactionBatch::compress(transaction_batch, CompressionAlgorithm::Zstd);
+    compressed.uncompressed_size -= 1;
+
+    assert!(matches!(compressed.decompress(), Err(ProtobufConversionError::CompressionError(_))));
+}
+
 #[test]
 fn stream_message_display() {
     let mut rng = get_rng();
@@ -21,10 +21,11 @@ use starknet_api::transaction::{
 
 use super::ProtobufConversionError;
 use crate::auto_impl_into_and_try_from_vec_u8;
-use crate::mempool::RpcTransactionWrapper;
+use crate::mempool::{MempoolSummary, RpcTransactionWrapper};
 use crate::protobuf::{self};
 
 auto_impl_into_and_try_from_vec_u8!(RpcTransactionWrapper, protobuf::RpcTransaction);
+auto_impl_into_and_try_from_vec_u8!(MempoolSummary, protobuf::MempoolSummary);
 
 impl TryFrom<protobuf::RpcTransaction> for RpcTransactionWrapper {
     type Error = ProtobufConversionError;
@@ -38,6 +39,19 @@ impl From<RpcTransactionWrapper> for protobuf::RpcTransaction {
     }
 }
 
+impl TryFrom<protobuf::MempoolSummary> for MempoolSummary {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::MempoolSummary) -> Result<Self, Self::Error> {
+        Ok(MempoolSummary(value.short_tx_ids))
+    }
+}
+
+impl From<MempoolSummary> for protobuf::MempoolSummary {
+    fn from(value: MempoolSummary) -> Self {
+        protobuf::MempoolSummary { short_tx_ids: value.0 }
+    }
+}
+
 impl TryFrom<protobuf::RpcTransaction> for RpcTransaction {
     type Error = ProtobufConversionError;
     fn try_from(value: protobuf::RpcTransaction) -> Result<Self, Self::Error> {
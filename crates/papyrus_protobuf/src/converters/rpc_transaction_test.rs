@@ -13,7 +13,7 @@ use starknet_api::rpc_transaction::{
 };
 use starknet_api::transaction::fields::{AllResourceBounds, ResourceBounds};
 
-use crate::mempool::RpcTransactionWrapper;
+use crate::mempool::{MempoolSummary, RpcTransactionWrapper};
 
 #[test]
 fn convert_declare_transaction_v3_to_vec_u8_and_back() {
@@ -47,6 +47,14 @@ fn convert_deploy_account_transaction_v3_to_vec_u8_and_back() {
     assert_transaction_to_vec_u8_and_back(rpc_transaction);
 }
 
+#[test]
+fn convert_mempool_summary_to_vec_u8_and_back() {
+    for summary in [MempoolSummary(vec![1, u64::MAX]), MempoolSummary(vec![])] {
+        let bytes_data = Vec::<u8>::from(summary.clone());
+        assert_eq!(MempoolSummary::try_from(bytes_data).unwrap(), summary);
+    }
+}
+
 fn assert_transaction_to_vec_u8_and_back(transaction: RpcTransaction) {
     let data = RpcTransactionWrapper(transaction.clone());
     let bytes_data = Vec::<u8>::from(data);
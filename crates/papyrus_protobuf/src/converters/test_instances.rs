@@ -2,9 +2,12 @@ use papyrus_test_utils::{auto_impl_get_test_instance, get_number_of_variants, Ge
 use rand::Rng;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::transaction::Transaction;
 
 use crate::consensus::{
+    CompressedTransactionBatch,
+    CompressionAlgorithm,
     ProposalFin,
     ProposalInit,
     ProposalPart,
@@ -32,6 +35,7 @@ auto_impl_get_test_instance! {
         pub round: u32,
         pub valid_round: Option<u32>,
         pub proposer: ContractAddress,
+        pub randomness_signature: Option<Signature>,
     }
     pub struct ProposalFin {
         pub proposal_content_id: BlockHash,
@@ -39,10 +43,21 @@ auto_impl_get_test_instance! {
     pub struct TransactionBatch {
         pub transactions: Vec<Transaction>,
     }
+    pub enum CompressionAlgorithm {
+        Uncompressed = 0,
+        Zstd = 1,
+        Gzip = 2,
+    }
+    pub struct CompressedTransactionBatch {
+        pub algorithm: CompressionAlgorithm,
+        pub uncompressed_size: u64,
+        pub data: Vec<u8>,
+    }
     pub enum ProposalPart {
         Init(ProposalInit) = 0,
         Fin(ProposalFin) = 1,
         Transactions(TransactionBatch) = 2,
+        CompressedTransactions(CompressedTransactionBatch) = 3,
     }
 
 }
@@ -2,3 +2,9 @@ use starknet_api::rpc_transaction::RpcTransaction;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RpcTransactionWrapper(pub RpcTransaction);
+
+/// The query of the mempool reconciliation protocol, through which peers converge on similar
+/// mempools even when transaction gossip drops messages: the short ids of the transactions in the
+/// sender's mempool. The queried peer responds with the transactions missing from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolSummary(pub Vec<u64>);
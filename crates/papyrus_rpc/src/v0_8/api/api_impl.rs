@@ -5,7 +5,12 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
 use papyrus_common::pending_classes::{PendingClasses, PendingClassesTrait};
-use papyrus_execution::objects::{FeeEstimation, PendingData as ExecutionPendingData};
+use papyrus_execution::objects::{
+    FeeEstimation,
+    PendingData as ExecutionPendingData,
+    TransactionTrace as ExecutionTransactionTrace,
+};
+use papyrus_execution::state_overrides::StateOverrides;
 use papyrus_execution::{
     estimate_fee as exec_estimate_fee,
     execute_call,
@@ -19,6 +24,7 @@ use papyrus_storage::body::{BodyStorageReader, TransactionIndex};
 use papyrus_storage::compiled_class::CasmStorageReader;
 use papyrus_storage::db::{TransactionKind, RO};
 use papyrus_storage::state::StateStorageReader;
+use papyrus_storage::trace::{SerializedTransactionTrace, TraceStorageReader};
 use papyrus_storage::{StorageError, StorageReader, StorageTxn};
 use starknet_api::block::{BlockHash, BlockHeaderWithoutHash, BlockNumber, BlockStatus};
 use starknet_api::contract_class::SierraVersion;
@@ -32,7 +38,7 @@ use starknet_api::core::{
 };
 use starknet_api::execution_utils::format_panic_data;
 use starknet_api::hash::StarkHash;
-use starknet_api::state::{StateNumber, StorageKey};
+use starknet_api::state::{StateNumber, StorageKey, ThinStateDiff as StarknetApiThinStateDiff};
 use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::{
     EventContent,
@@ -866,8 +872,13 @@ impl JsonRpcServer for JsonRpcServerImpl {
         }))
     }
 
-    #[instrument(skip(self), level = "debug", err, ret)]
-    async fn call(&self, request: CallRequest, block_id: BlockId) -> RpcResult<Vec<Felt>> {
+    #[instrument(skip(self, state_overrides), level = "debug", err, ret)]
+    async fn call(
+        &self,
+        request: CallRequest,
+        block_id: BlockId,
+        state_overrides: Option<StateOverrides>,
+    ) -> RpcResult<Vec<Felt>> {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
@@ -881,7 +892,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_not_reverted_validator = BlockNotRevertedValidator::new(block_number, &txn)?;
         drop(txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
-        let execution_config = self.execution_config;
+        let execution_config = self.execution_config.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -899,6 +910,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 request.calldata,
                 &execution_config,
                 DONT_IGNORE_L1_DA_MODE,
+                state_overrides.unwrap_or_default(),
             )
         })
         .await
@@ -969,12 +981,13 @@ impl JsonRpcServer for JsonRpcServerImpl {
         }
     }
 
-    #[instrument(skip(self, transactions), level = "debug", err, ret)]
+    #[instrument(skip(self, transactions, state_overrides), level = "debug", err, ret)]
     async fn estimate_fee(
         &self,
         transactions: Vec<BroadcastedTransaction>,
         simulation_flags: Vec<SimulationFlag>,
         block_id: BlockId,
+        state_overrides: Option<StateOverrides>,
     ) -> RpcResult<Vec<FeeEstimation>> {
         trace!("Estimating fee of transactions: {:#?}", transactions);
         let validate = !simulation_flags.contains(&SimulationFlag::SkipValidate);
@@ -998,7 +1011,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
-        let execution_config = self.execution_config;
+        let execution_config = self.execution_config.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1014,6 +1027,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 &execution_config,
                 validate,
                 DONT_IGNORE_L1_DA_MODE,
+                state_overrides.unwrap_or_default(),
             )
         })
         .await
@@ -1035,12 +1049,13 @@ impl JsonRpcServer for JsonRpcServerImpl {
         }
     }
 
-    #[instrument(skip(self, transactions), level = "debug", err, ret)]
+    #[instrument(skip(self, transactions, state_overrides), level = "debug", err, ret)]
     async fn simulate_transactions(
         &self,
         block_id: BlockId,
         transactions: Vec<BroadcastedTransaction>,
         simulation_flags: Vec<SimulationFlag>,
+        state_overrides: Option<StateOverrides>,
     ) -> RpcResult<Vec<SimulatedTransaction>> {
         trace!("Simulating transactions: {:#?}", transactions);
         let executable_txns =
@@ -1062,7 +1077,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
-        let execution_config = self.execution_config;
+        let execution_config = self.execution_config.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1083,6 +1098,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 charge_fee,
                 validate,
                 DONT_IGNORE_L1_DA_MODE,
+                state_overrides.unwrap_or_default(),
             )
         })
         .await
@@ -1181,6 +1197,14 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 .map_err(internal_server_error)?
                 .ok_or(TRANSACTION_HASH_NOT_FOUND)?;
 
+            // Serve the trace archived when the block was committed, if there is one.
+            if let Some(trace) = storage_txn
+                .get_transaction_trace(TransactionIndex(block_number, tx_offset))
+                .map_err(internal_server_error)?
+            {
+                return archived_transaction_trace(trace);
+            }
+
             let block_transactions = storage_txn
                 .get_block_transactions(block_number)
                 .map_err(internal_server_error)?
@@ -1214,7 +1238,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
         drop(storage_txn);
 
-        let execution_config = self.execution_config;
+        let execution_config = self.execution_config.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1232,6 +1256,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 true,
                 true,
                 DONT_IGNORE_L1_DA_MODE,
+                StateOverrides::default(),
             )
         })
         .await
@@ -1260,6 +1285,33 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
         let block_number = get_accepted_block_number(&storage_txn, block_id)?;
 
+        if maybe_client_pending_data.is_none() {
+            // Serve the traces archived when the block was committed, if there are any.
+            if let Some(traces) = storage_txn
+                .get_block_transaction_traces(block_number)
+                .map_err(internal_server_error)?
+            {
+                let transaction_hashes = storage_txn
+                    .get_block_transaction_hashes(block_number)
+                    .map_err(internal_server_error)?
+                    .ok_or_else(|| {
+                        internal_server_error(StorageError::DBInconsistency {
+                            msg: format!("Missing block {block_number} transactions"),
+                        })
+                    })?;
+                return traces
+                    .into_iter()
+                    .zip(transaction_hashes)
+                    .map(|(trace, transaction_hash)| {
+                        Ok(TransactionTraceWithHash {
+                            transaction_hash,
+                            trace_root: archived_transaction_trace(trace)?,
+                        })
+                    })
+                    .collect();
+            }
+        }
+
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
 
@@ -1329,7 +1381,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
         drop(storage_txn);
 
-        let execution_config = self.execution_config;
+        let execution_config = self.execution_config.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1348,6 +1400,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 true,
                 true,
                 DONT_IGNORE_L1_DA_MODE,
+                StateOverrides::default(),
             )
         })
         .await
@@ -1398,7 +1451,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
-        let execution_config = self.execution_config;
+        let execution_config = self.execution_config.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1414,6 +1467,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 &execution_config,
                 false,
                 DONT_IGNORE_L1_DA_MODE,
+                StateOverrides::default(),
             )
         })
         .await
@@ -1491,6 +1545,14 @@ impl JsonRpcServer for JsonRpcServerImpl {
     }
 }
 
+// Converts a trace archived at commit time to the RPC format. The state diff induced by the
+// transaction is not archived.
+fn archived_transaction_trace(trace: SerializedTransactionTrace) -> RpcResult<TransactionTrace> {
+    let trace: ExecutionTransactionTrace =
+        serde_json::from_value(trace.0).map_err(internal_server_error)?;
+    Ok(TransactionTrace::from((trace, None::<StarknetApiThinStateDiff>)))
+}
+
 async fn read_pending_data<Mode: TransactionKind>(
     pending_data: &Arc<RwLock<PendingData>>,
     txn: &StorageTxn<'_, Mode>,
@@ -9,6 +9,7 @@ use jsonrpsee::types::ErrorObjectOwned;
 use papyrus_common::deprecated_class_abi::calculate_deprecated_class_abi_length;
 use papyrus_common::pending_classes::ApiContractClass;
 use papyrus_execution::objects::FeeEstimation;
+use papyrus_execution::state_overrides::StateOverrides;
 use papyrus_execution::{AbiSize, ExecutableTransactionInput, ExecutionError, SierraSize};
 use papyrus_proc_macros::versioned_rpc;
 use papyrus_storage::compiled_class::CasmStorageReader;
@@ -192,10 +193,15 @@ pub trait JsonRpc {
     #[method(name = "syncing")]
     async fn syncing(&self) -> RpcResult<SyncingState>;
 
-    /// Executes the entry point of the contract at the given address with the given calldata,
-    /// returns the result (Retdata).
+    /// Executes the entry point of the contract at the given address with the given calldata, on
+    /// top of the given state overrides, if any, and returns the result (Retdata).
     #[method(name = "call")]
-    async fn call(&self, request: CallRequest, block_id: BlockId) -> RpcResult<Vec<Felt>>;
+    async fn call(
+        &self,
+        request: CallRequest,
+        block_id: BlockId,
+        state_overrides: Option<StateOverrides>,
+    ) -> RpcResult<Vec<Felt>>;
 
     /// Submits a new invoke transaction to be added to the chain.
     #[method(name = "addInvokeTransaction")]
@@ -218,13 +224,15 @@ pub trait JsonRpc {
         declare_transaction: BroadcastedDeclareTransaction,
     ) -> RpcResult<AddDeclareOkResult>;
 
-    /// Estimates the fee of a series of transactions.
+    /// Estimates the fee of a series of transactions, executed on top of the given state
+    /// overrides, if any.
     #[method(name = "estimateFee")]
     async fn estimate_fee(
         &self,
         request: Vec<BroadcastedTransaction>,
         simulation_flags: Vec<SimulationFlag>,
         block_id: BlockId,
+        state_overrides: Option<StateOverrides>,
     ) -> RpcResult<Vec<FeeEstimation>>;
 
     /// Estimates the fee of a message from L1.
@@ -235,13 +243,15 @@ pub trait JsonRpc {
         block_id: BlockId,
     ) -> RpcResult<FeeEstimation>;
 
-    /// Simulates execution of a series of transactions.
+    /// Simulates execution of a series of transactions, on top of the given state overrides, if
+    /// any.
     #[method(name = "simulateTransactions")]
     async fn simulate_transactions(
         &self,
         block_id: BlockId,
         transactions: Vec<BroadcastedTransaction>,
         simulation_flags: Vec<SimulationFlag>,
+        state_overrides: Option<StateOverrides>,
     ) -> RpcResult<Vec<SimulatedTransaction>>;
 
     /// Calculates the transaction trace of a transaction that is already included in a block.
@@ -661,6 +671,13 @@ pub(crate) fn execution_error_to_error_object_owned(err: ExecutionError) -> Erro
             );
             BLOCK_NOT_FOUND.into()
         }
+        ExecutionError::MissingStarknetVersion { block_number } => {
+            debug!(
+                "Execution failed because it required the Starknet version of block \
+                 {block_number} and we didn't download its header yet."
+            );
+            BLOCK_NOT_FOUND.into()
+        }
         ExecutionError::ContractError(blockifier_err) => {
             let contract_err = ContractError { revert_error: blockifier_err.to_string() };
             let rpc_err: JsonRpcError<ContractError> = contract_err.into();
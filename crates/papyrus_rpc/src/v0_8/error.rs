@@ -61,6 +61,10 @@ impl From<ContractError> for JsonRpcError<ContractError> {
         Self { code: 40, message: "Contract error", data: Some(contract_error) }
     }
 }
+
+pub fn contract_error(data: String) -> JsonRpcError<String> {
+    JsonRpcError { code: 40, message: "Contract error", data: Some(data) }
+}
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct TransactionExecutionError {
     pub transaction_index: usize,
@@ -75,9 +79,17 @@ impl From<TransactionExecutionError> for JsonRpcError<TransactionExecutionError>
 pub const CLASS_ALREADY_DECLARED: JsonRpcError<String> =
     JsonRpcError { code: 51, message: "Class already declared", data: None };
 
+pub fn class_already_declared(data: String) -> JsonRpcError<String> {
+    JsonRpcError { data: Some(data), ..CLASS_ALREADY_DECLARED }
+}
+
 pub const INVALID_TRANSACTION_NONCE: JsonRpcError<String> =
     JsonRpcError { code: 52, message: "Invalid transaction nonce", data: None };
 
+pub fn invalid_transaction_nonce(data: String) -> JsonRpcError<String> {
+    JsonRpcError { data: Some(data), ..INVALID_TRANSACTION_NONCE }
+}
+
 pub const INSUFFICIENT_MAX_FEE: JsonRpcError<String> = JsonRpcError {
     code: 53,
     message: "Max fee is smaller than the minimal transaction cost (validation plus fee transfer)",
@@ -39,8 +39,9 @@ pub enum TransactionTrace {
 pub struct L1HandlerTransactionTrace {
     /// The trace of the funcion call.
     pub function_invocation: FunctionInvocation,
-    /// The state diff induced by this transaction.
-    pub state_diff: ThinStateDiff,
+    /// The state diff induced by this transaction. Not known for archived traces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<ThinStateDiff>,
     /// The total execution resources of this transaction.
     pub execution_resources: ExecutionResources,
 }
@@ -56,8 +57,9 @@ pub struct InvokeTransactionTrace {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// The trace of the __fee_transfer__ call.
     pub fee_transfer_invocation: Option<FunctionInvocation>,
-    /// The state diff induced by this transaction.
-    pub state_diff: ThinStateDiff,
+    /// The state diff induced by this transaction. Not known for archived traces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<ThinStateDiff>,
     /// The total execution resources of this transaction.
     pub execution_resources: ExecutionResources,
 }
@@ -71,8 +73,9 @@ pub struct DeclareTransactionTrace {
     /// The trace of the __fee_transfer__ call.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_transfer_invocation: Option<FunctionInvocation>,
-    /// The state diff induced by this transaction.
-    pub state_diff: ThinStateDiff,
+    /// The state diff induced by this transaction. Not known for archived traces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<ThinStateDiff>,
     /// The total execution resources of this transaction.
     pub execution_resources: ExecutionResources,
 }
@@ -88,8 +91,9 @@ pub struct DeployAccountTransactionTrace {
     /// The trace of the __fee_transfer__ call.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_transfer_invocation: Option<FunctionInvocation>,
-    /// The state diff induced by this transaction.
-    pub state_diff: ThinStateDiff,
+    /// The state diff induced by this transaction. Not known for archived traces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<ThinStateDiff>,
     /// The total execution resources of this transaction.
     pub execution_resources: ExecutionResources,
 }
@@ -132,9 +136,20 @@ pub struct FunctionInvocation {
 
 impl From<(ExecutionTransactionTrace, StarknetApiThinStateDiff)> for TransactionTrace {
     fn from((trace, state_diff): (ExecutionTransactionTrace, StarknetApiThinStateDiff)) -> Self {
-        let mut state_diff = ThinStateDiff::from(state_diff);
-        // TODO: Investigate why blockifier sometimes returns unsorted state diff
-        state_diff.sort();
+        (trace, Some(state_diff)).into()
+    }
+}
+
+impl From<(ExecutionTransactionTrace, Option<StarknetApiThinStateDiff>)> for TransactionTrace {
+    fn from(
+        (trace, state_diff): (ExecutionTransactionTrace, Option<StarknetApiThinStateDiff>),
+    ) -> Self {
+        let state_diff = state_diff.map(|state_diff| {
+            let mut state_diff = ThinStateDiff::from(state_diff);
+            // TODO: Investigate why blockifier sometimes returns unsorted state diff
+            state_diff.sort();
+            state_diff
+        });
         match trace {
             ExecutionTransactionTrace::L1Handler(trace) => {
                 let execution_resources =
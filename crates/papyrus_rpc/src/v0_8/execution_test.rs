@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::read_to_string;
 use std::sync::Arc;
 
@@ -23,7 +24,9 @@ use papyrus_execution::objects::{
     PriceUnit,
     Retdata,
     RevertReason,
+    TransactionTrace as ExecutionTransactionTrace,
 };
+use papyrus_execution::state_overrides::{ContractStateOverride, StateOverrides};
 use papyrus_execution::testing_instances::get_storage_var_address;
 use papyrus_execution::ExecutableTransactionInput;
 use papyrus_storage::body::BodyStorageWriter;
@@ -31,6 +34,7 @@ use papyrus_storage::class::ClassStorageWriter;
 use papyrus_storage::compiled_class::CasmStorageWriter;
 use papyrus_storage::header::HeaderStorageWriter;
 use papyrus_storage::state::StateStorageWriter;
+use papyrus_storage::trace::{SerializedTransactionTrace, TraceStorageWriter};
 use papyrus_storage::StorageWriter;
 use papyrus_test_utils::{
     auto_impl_get_test_instance,
@@ -249,6 +253,31 @@ async fn execution_call() {
 
     assert_matches!(err, Error::Call(err) if err == CONTRACT_NOT_FOUND.into());
 
+    // Calling a contract that is deployed only by the state overrides.
+    let state_overrides = StateOverrides {
+        contracts: BTreeMap::from([(
+            contract_address!("0x1234"),
+            ContractStateOverride { class_hash: Some(class_hash!("0x1")), ..Default::default() },
+        )]),
+        ..Default::default()
+    };
+    let retdata = module
+        .call::<_, Vec<Felt>>(
+            "starknet_V0_8_call",
+            (
+                CallRequest {
+                    contract_address: contract_address!("0x1234"),
+                    entry_point_selector: selector_from_name("test_storage_read_write"),
+                    calldata: calldata![key, value],
+                },
+                BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0))),
+                state_overrides,
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(retdata, vec![value]);
+
     // Calling a non-existent block.
     let err = module
         .call::<_, Vec<Felt>>(
@@ -756,6 +785,60 @@ async fn call_simulate_skip_fee_charge() {
     assert_matches!(invoke_trace.fee_transfer_invocation, None);
 }
 
+#[tokio::test]
+async fn trace_transactions_from_archive() {
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let tx_hash = tx_hash!(0x1234);
+    let archived_trace = ExecutionTransactionTrace::get_test_instance(&mut get_rng());
+
+    // The block can't be executed, so its traces can only be served from the archive.
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap()
+        .append_body(
+            BlockNumber(0),
+            BlockBody {
+                transactions: vec![starknet_api::transaction::Transaction::Invoke(
+                    starknet_api::transaction::InvokeTransaction::default(),
+                )],
+                transaction_outputs: vec![starknet_api::transaction::TransactionOutput::Invoke(
+                    starknet_api::transaction::InvokeTransactionOutput::default(),
+                )],
+                transaction_hashes: vec![tx_hash],
+            },
+        )
+        .unwrap()
+        .append_transaction_traces(
+            BlockNumber(0),
+            &[SerializedTransactionTrace(serde_json::to_value(&archived_trace).unwrap())],
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let expected_trace = TransactionTrace::from((archived_trace, None::<StarknetApiStateDiff>));
+    let trace = module
+        .call::<_, TransactionTrace>("starknet_V0_8_traceTransaction", [tx_hash])
+        .await
+        .unwrap();
+    assert_eq!(trace, expected_trace);
+
+    let block_traces = module
+        .call::<_, Vec<TransactionTraceWithHash>>(
+            "starknet_V0_8_traceBlockTransactions",
+            [BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)))],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        block_traces,
+        vec![TransactionTraceWithHash { transaction_hash: tx_hash, trace_root: expected_trace }]
+    );
+}
+
 // TODO(shahak): Add test for trace_transaction that doesn't depend on trace_block_transactions
 #[tokio::test]
 async fn trace_block_transactions_regular_and_pending() {
@@ -1356,7 +1439,7 @@ auto_impl_get_test_instance! {
 
     pub struct L1HandlerTransactionTrace {
         pub function_invocation: FunctionInvocation,
-        pub state_diff: ThinStateDiff,
+        pub state_diff: Option<ThinStateDiff>,
         pub execution_resources: ExecutionResources,
     }
 
@@ -1364,14 +1447,14 @@ auto_impl_get_test_instance! {
         pub validate_invocation: Option<FunctionInvocation>,
         pub execute_invocation: FunctionInvocationResult,
         pub fee_transfer_invocation: Option<FunctionInvocation>,
-        pub state_diff: ThinStateDiff,
+        pub state_diff: Option<ThinStateDiff>,
         pub execution_resources: ExecutionResources,
     }
 
     pub struct DeclareTransactionTrace {
         pub validate_invocation: Option<FunctionInvocation>,
         pub fee_transfer_invocation: Option<FunctionInvocation>,
-        pub state_diff: ThinStateDiff,
+        pub state_diff: Option<ThinStateDiff>,
         pub execution_resources: ExecutionResources,
     }
 
@@ -1379,7 +1462,7 @@ auto_impl_get_test_instance! {
         pub validate_invocation: Option<FunctionInvocation>,
         pub constructor_invocation: FunctionInvocation,
         pub fee_transfer_invocation: Option<FunctionInvocation>,
-        pub state_diff: ThinStateDiff,
+        pub state_diff: Option<ThinStateDiff>,
         pub execution_resources: ExecutionResources,
     }
 
@@ -1 +1,2 @@
 pub mod papyrus_state;
+pub mod state_reads_cache;
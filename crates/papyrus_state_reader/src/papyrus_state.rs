@@ -1,8 +1,12 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
 use blockifier::execution::contract_class::{
     CompiledClassV0,
     CompiledClassV1,
     RunnableCompiledClass,
 };
+use blockifier::state::cached_state::{StateMaps, StorageEntry};
 use blockifier::state::contract_class_manager::ContractClassManager;
 use blockifier::state::errors::{couple_casm_and_sierra, StateError};
 use blockifier::state::global_cache::CachedCasm;
@@ -15,8 +19,22 @@ use starknet_api::block::BlockNumber;
 use starknet_api::contract_class::SierraVersion;
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::state::{StateNumber, StorageKey};
+use starknet_committer::block_committer::input::StarknetStorageValue;
+use starknet_committer::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use starknet_patricia::felt::Felt as PatriciaFelt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::{
+    fetch_leaf,
+    fetch_merkle_proof,
+    MerkleProof,
+    MerkleProofResult,
+};
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia::storage::storage_trait::Storage;
 use starknet_types_core::felt::Felt;
 
+use crate::state_reads_cache::StateReadsCache;
+
 #[cfg(test)]
 #[path = "papyrus_state_test.rs"]
 mod test;
@@ -26,6 +44,8 @@ pub struct PapyrusReader {
     storage_reader: StorageReader,
     latest_block: BlockNumber,
     contract_class_manager: ContractClassManager,
+    // If set, the storage reads are cached in (and served from) this cache.
+    reads_cache: Option<StateReadsCache>,
 }
 
 impl PapyrusReader {
@@ -34,7 +54,13 @@ impl PapyrusReader {
         latest_block: BlockNumber,
         contract_class_manager: ContractClassManager,
     ) -> Self {
-        Self { storage_reader, latest_block, contract_class_manager }
+        Self { storage_reader, latest_block, contract_class_manager, reads_cache: None }
+    }
+
+    /// Shares the storage reads with the other readers of the same state using the given cache.
+    pub fn with_reads_cache(mut self, reads_cache: StateReadsCache) -> Self {
+        self.reads_cache = Some(reads_cache);
+        self
     }
 
     fn reader(&self) -> StateResult<RawPapyrusReader<'_>> {
@@ -43,12 +69,19 @@ impl PapyrusReader {
             .map_err(|error| StateError::StateReadError(error.to_string()))
     }
 
-    /// Returns a V1 contract if found, or a V0 contract if a V1 contract is not
+    fn cached_read<T>(&self, read: impl FnOnce(&StateMaps) -> Option<T>) -> Option<T> {
+        self.reads_cache.as_ref()?.read(self.latest_block, read)
+    }
+
+    fn cache_read(&self, write: impl FnOnce(&mut StateMaps)) {
+        if let Some(reads_cache) = &self.reads_cache {
+            reads_cache.write(self.latest_block, write);
+        }
+    }
+
+    /// Returns a V1 contract, with its Sierra, if found, or a V0 contract if a V1 contract is not
     /// found, or an `Error` otherwise.
-    fn get_compiled_class_inner(
-        &self,
-        class_hash: ClassHash,
-    ) -> StateResult<RunnableCompiledClass> {
+    fn get_compiled_class_inner(&self, class_hash: ClassHash) -> StateResult<CachedCasm> {
         let state_number = StateNumber(self.latest_block);
         let class_declaration_block_number = self
             .reader()?
@@ -73,7 +106,7 @@ impl PapyrusReader {
                 casm_compiled_class,
                 sierra_version,
             ))?);
-            return Ok(runnable_compiled);
+            return Ok(CachedCasm::WithSierra(runnable_compiled, Arc::new(sierra)));
         }
 
         let v0_compiled_class = self
@@ -83,8 +116,8 @@ impl PapyrusReader {
             .map_err(|err| StateError::StateReadError(err.to_string()))?;
 
         match v0_compiled_class {
-            Some(starknet_api_contract_class) => Ok(RunnableCompiledClass::V0(
-                CompiledClassV0::try_from(starknet_api_contract_class)?,
+            Some(starknet_api_contract_class) => Ok(CachedCasm::WithoutSierra(
+                RunnableCompiledClass::V0(CompiledClassV0::try_from(starknet_api_contract_class)?),
             )),
             None => Err(StateError::UndeclaredClassHash(class_hash)),
         }
@@ -98,60 +131,194 @@ impl StateReader for PapyrusReader {
         contract_address: ContractAddress,
         key: StorageKey,
     ) -> StateResult<Felt> {
+        let storage_entry = (contract_address, key);
+        if let Some(value) = self.cached_read(|reads| reads.storage.get(&storage_entry).copied()) {
+            return Ok(value);
+        }
         let state_number = StateNumber(self.latest_block);
-        self.reader()?
+        let value = self
+            .reader()?
             .get_state_reader()
             .and_then(|sr| sr.get_storage_at(state_number, &contract_address, &key))
-            .map_err(|error| StateError::StateReadError(error.to_string()))
+            .map_err(|error| StateError::StateReadError(error.to_string()))?;
+        self.cache_read(|reads| {
+            reads.storage.insert(storage_entry, value);
+        });
+        Ok(value)
     }
 
     fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        if let Some(nonce) = self.cached_read(|reads| reads.nonces.get(&contract_address).copied())
+        {
+            return Ok(nonce);
+        }
         let state_number = StateNumber(self.latest_block);
-        match self
+        let nonce = match self
             .reader()?
             .get_state_reader()
             .and_then(|sr| sr.get_nonce_at(state_number, &contract_address))
         {
-            Ok(Some(nonce)) => Ok(nonce),
-            Ok(None) => Ok(Nonce::default()),
-            Err(err) => Err(StateError::StateReadError(err.to_string())),
-        }
+            Ok(Some(nonce)) => nonce,
+            Ok(None) => Nonce::default(),
+            Err(err) => return Err(StateError::StateReadError(err.to_string())),
+        };
+        self.cache_read(|reads| {
+            reads.nonces.insert(contract_address, nonce);
+        });
+        Ok(nonce)
     }
 
     fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        if let Some(class_hash) =
+            self.cached_read(|reads| reads.class_hashes.get(&contract_address).copied())
+        {
+            return Ok(class_hash);
+        }
         let state_number = StateNumber(self.latest_block);
-        match self
+        let class_hash = match self
             .reader()?
             .get_state_reader()
             .and_then(|sr| sr.get_class_hash_at(state_number, &contract_address))
         {
-            Ok(Some(class_hash)) => Ok(class_hash),
-            Ok(None) => Ok(ClassHash::default()),
-            Err(err) => Err(StateError::StateReadError(err.to_string())),
-        }
+            Ok(Some(class_hash)) => class_hash,
+            Ok(None) => ClassHash::default(),
+            Err(err) => return Err(StateError::StateReadError(err.to_string())),
+        };
+        self.cache_read(|reads| {
+            reads.class_hashes.insert(contract_address, class_hash);
+        });
+        Ok(class_hash)
     }
 
     fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
         // Assumption: the global cache is cleared upon reverted blocks.
-        let cached_casm = self.contract_class_manager.get_casm(&class_hash);
-
-        match cached_casm {
-            None => {
-                let compiled_class_from_db = self.get_compiled_class_inner(class_hash)?;
-                self.contract_class_manager.set_casm(
-                    class_hash,
-                    CachedCasm::WithoutSierra(compiled_class_from_db.clone()),
-                );
-                Ok(compiled_class_from_db)
-            }
-            Some(CachedCasm::WithoutSierra(casm)) => Ok(casm),
-            Some(CachedCasm::WithSierra(_, _)) => {
-                todo!("Add this flow when Sierra to Native compilation is added to PapyrusReader.")
-            }
+        if let Some(runnable_class) = self.contract_class_manager.get_runnable(&class_hash) {
+            return Ok(runnable_class);
         }
+
+        let compiled_class_from_db = self.get_compiled_class_inner(class_hash)?;
+        self.contract_class_manager.set_and_compile(class_hash, compiled_class_from_db.clone());
+        // Synchronous compilation caches the native class right away, so it is run from the start.
+        Ok(self
+            .contract_class_manager
+            .get_runnable(&class_hash)
+            .unwrap_or_else(|| compiled_class_from_db.to_runnable()))
     }
 
     fn get_compiled_class_hash(&self, _class_hash: ClassHash) -> StateResult<CompiledClassHash> {
         todo!()
     }
 }
+
+/// A [PapyrusReader] that records the values it reads, and proves them against the commitment of
+/// the read state. The papyrus storage holds the state but not its Patricia tries, so the proofs
+/// are built from the trie nodes of the committer's storage.
+pub struct PapyrusStateReader<S: Storage> {
+    reader: PapyrusReader,
+    trie_storage: S,
+    // The root of the contracts trie of the read state.
+    contracts_trie_root: HashOutput,
+    reads: Mutex<StateMaps>,
+}
+
+/// The proofs of the values read from a state, against the roots of its tries.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StateProofs {
+    /// The proof of each contract whose nonce, class hash or storage was read, in the contracts
+    /// trie.
+    pub contract_proofs: HashMap<ContractAddress, MerkleProof>,
+    /// The proof of each storage cell read, in the storage trie of its contract.
+    pub storage_proofs: HashMap<StorageEntry, MerkleProof>,
+}
+
+impl<S: Storage> PapyrusStateReader<S> {
+    pub fn new(reader: PapyrusReader, trie_storage: S, contracts_trie_root: HashOutput) -> Self {
+        Self { reader, trie_storage, contracts_trie_root, reads: Mutex::new(StateMaps::default()) }
+    }
+
+    /// Returns the proofs of all the values read so far.
+    pub fn proofs(&self) -> MerkleProofResult<StateProofs> {
+        let reads = self.reads.lock().expect("State reads lock failed.").clone();
+        let contract_addresses: HashSet<ContractAddress> = reads
+            .nonces
+            .keys()
+            .chain(reads.class_hashes.keys())
+            .chain(reads.storage.keys().map(|(contract_address, _)| contract_address))
+            .copied()
+            .collect();
+
+        let mut proofs = StateProofs::default();
+        let mut storage_trie_roots = HashMap::new();
+        for contract_address in contract_addresses {
+            let contract_proof = fetch_merkle_proof::<ContractState>(
+                &self.trie_storage,
+                self.contracts_trie_root,
+                leaf_index(*contract_address.0.key()),
+            )?;
+            let storage_trie_root = match contract_proof.leaf_hash {
+                Some(leaf_hash) => {
+                    fetch_leaf::<ContractState>(&self.trie_storage, leaf_hash)?.storage_root_hash
+                }
+                // The storage of a contract absent from the contracts trie is empty.
+                None => HashOutput::default(),
+            };
+            storage_trie_roots.insert(contract_address, storage_trie_root);
+            proofs.contract_proofs.insert(contract_address, contract_proof);
+        }
+        for storage_entry @ (contract_address, key) in reads.storage.into_keys() {
+            let storage_proof = fetch_merkle_proof::<StarknetStorageValue>(
+                &self.trie_storage,
+                storage_trie_roots[&contract_address],
+                leaf_index(*key.0.key()),
+            )?;
+            proofs.storage_proofs.insert(storage_entry, storage_proof);
+        }
+        Ok(proofs)
+    }
+
+    fn record_read(&self, record: impl FnOnce(&mut StateMaps)) {
+        record(&mut self.reads.lock().expect("State reads lock failed."));
+    }
+}
+
+fn leaf_index(leaf_key: Felt) -> NodeIndex {
+    NodeIndex::from_leaf_felt(&PatriciaFelt::from(leaf_key))
+}
+
+impl<S: Storage> StateReader for PapyrusStateReader<S> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let value = self.reader.get_storage_at(contract_address, key)?;
+        self.record_read(|reads| {
+            reads.storage.insert((contract_address, key), value);
+        });
+        Ok(value)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let nonce = self.reader.get_nonce_at(contract_address)?;
+        self.record_read(|reads| {
+            reads.nonces.insert(contract_address, nonce);
+        });
+        Ok(nonce)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let class_hash = self.reader.get_class_hash_at(contract_address)?;
+        self.record_read(|reads| {
+            reads.class_hashes.insert(contract_address, class_hash);
+        });
+        Ok(class_hash)
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.reader.get_compiled_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.reader.get_compiled_class_hash(class_hash)
+    }
+}
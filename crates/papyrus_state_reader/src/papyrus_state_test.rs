@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use assert_matches::assert_matches;
 use blockifier::blockifier::config::ContractClassManagerConfig;
 use blockifier::execution::call_info::CallExecution;
@@ -7,17 +9,30 @@ use blockifier::state::cached_state::CachedState;
 use blockifier::state::contract_class_manager::ContractClassManager;
 use blockifier::state::state_api::StateReader;
 use blockifier::test_utils::contracts::FeatureContract;
-use blockifier::test_utils::{trivial_external_entry_point_new, CairoVersion};
+use blockifier::test_utils::{trivial_external_entry_point_new, CairoVersion, RunnableCairo1};
 use indexmap::IndexMap;
 use papyrus_storage::class::ClassStorageWriter;
+use papyrus_storage::compiled_class::CasmStorageWriter;
 use papyrus_storage::state::StateStorageWriter;
 use starknet_api::abi::abi_utils::selector_from_name;
 use starknet_api::block::BlockNumber;
 use starknet_api::contract_class::ContractClass;
 use starknet_api::state::{StateDiff, StorageKey};
-use starknet_api::{calldata, felt};
+use starknet_api::{calldata, contract_address, felt, storage_key};
+use starknet_committer::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use starknet_patricia::felt::Felt as PatriciaFelt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::external_test_utils::create_edge_entry;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::{MerkleProof, ProofNode};
+use starknet_patricia::patricia_merkle_tree::node_data::inner_node::{
+    EdgeData,
+    EdgePathLength,
+    PathToBottom,
+};
+use starknet_patricia::storage::db_object::DBObject;
+use starknet_patricia::storage::map_storage::MapStorage;
 
-use crate::papyrus_state::PapyrusReader;
+use crate::papyrus_state::{PapyrusReader, PapyrusStateReader, StateProofs};
 
 #[test]
 fn test_entry_point_with_papyrus_state() -> papyrus_storage::StorageResult<()> {
@@ -76,3 +91,93 @@ fn test_entry_point_with_papyrus_state() -> papyrus_storage::StorageResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_compiled_class_is_cached() -> papyrus_storage::StorageResult<()> {
+    let ((storage_reader, mut storage_writer), _) = papyrus_storage::test_utils::get_test_storage();
+
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let test_class_hash = test_contract.get_class_hash();
+    let casm = assert_matches!(test_contract.get_class(), ContractClass::V1((casm, _)) => casm);
+    let sierra = test_contract.get_sierra();
+    let state_diff = StateDiff {
+        declared_classes: IndexMap::from([(
+            test_class_hash,
+            (test_contract.get_compiled_class_hash(), sierra.clone()),
+        )]),
+        ..Default::default()
+    };
+
+    let block_number = BlockNumber::default();
+    storage_writer
+        .begin_rw_txn()?
+        .append_state_diff(block_number, state_diff.into())?
+        .append_classes(block_number, &[(test_class_hash, &sierra)], &[])?
+        .append_casm(&test_class_hash, &casm)?
+        .commit()?;
+
+    let contract_class_manager = ContractClassManager::start(ContractClassManagerConfig::default());
+    let papyrus_reader =
+        PapyrusReader::new(storage_reader, BlockNumber(1), contract_class_manager.clone());
+    assert_eq!(contract_class_manager.get_runnable(&test_class_hash), None);
+
+    // Without Cairo native, the class is run by the VM.
+    let compiled_class = papyrus_reader.get_compiled_class(test_class_hash).unwrap();
+    assert_eq!(compiled_class, test_contract.get_runnable_class());
+    assert_eq!(contract_class_manager.get_runnable(&test_class_hash), Some(compiled_class));
+
+    Ok(())
+}
+
+#[test]
+fn test_state_proofs() -> papyrus_storage::StorageResult<()> {
+    let ((storage_reader, mut storage_writer), _) = papyrus_storage::test_utils::get_test_storage();
+    let (contract_address, key) = (contract_address!("0x1"), storage_key!("0x2"));
+    let state_diff = StateDiff {
+        storage_diffs: IndexMap::from([(contract_address, IndexMap::from([(key, felt!("0x7"))]))]),
+        ..Default::default()
+    };
+    storage_writer
+        .begin_rw_txn()?
+        .append_state_diff(BlockNumber::default(), state_diff.into())?
+        .commit()?;
+
+    // Each trie holds a single leaf, under an edge from its root: the contract of hash 100 in the
+    // contracts trie (of root 352), and the storage cell of hash 50 in its storage trie (of root
+    // 303).
+    let hash = |value: u128| HashOutput(PatriciaFelt::from(value));
+    let contract_leaf = ContractState { storage_root_hash: hash(303), ..Default::default() };
+    let contract_leaf_key = contract_leaf.get_db_key(&PatriciaFelt::from(100_u128).to_bytes_be());
+    let trie_storage = MapStorage {
+        storage: HashMap::from([
+            create_edge_entry(100, 1, 251),
+            (contract_leaf_key, contract_leaf.serialize()),
+            create_edge_entry(50, 2, 251),
+        ]),
+    };
+    let papyrus_reader = PapyrusReader::new(
+        storage_reader,
+        BlockNumber(1),
+        ContractClassManager::start(ContractClassManagerConfig::default()),
+    );
+    let state_reader = PapyrusStateReader::new(papyrus_reader, trie_storage, hash(352));
+
+    assert_eq!(state_reader.get_storage_at(contract_address, key).unwrap(), felt!("0x7"));
+    let edge_proof = |bottom_hash: u128, path: u128| MerkleProof {
+        nodes: vec![ProofNode::Edge(EdgeData {
+            bottom_hash: hash(bottom_hash),
+            path_to_bottom: PathToBottom::new(path.into(), EdgePathLength::new(251).unwrap())
+                .unwrap(),
+        })],
+        leaf_hash: Some(hash(bottom_hash)),
+    };
+    assert_eq!(
+        state_reader.proofs().unwrap(),
+        StateProofs {
+            contract_proofs: HashMap::from([(contract_address, edge_proof(100, 1))]),
+            storage_proofs: HashMap::from([((contract_address, key), edge_proof(50, 2))]),
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use blockifier::state::cached_state::StateMaps;
+use starknet_api::block::BlockNumber;
+
+#[cfg(test)]
+#[path = "state_reads_cache_test.rs"]
+mod test;
+
+/// The values read from the state before a single block, shared by all the readers of that state;
+/// e.g., a speculative pre-execution of pending transactions and the building of the block that
+/// follows it. Readers of a newer state drop the cached values, and readers of an older state
+/// bypass the cache.
+#[derive(Clone, Debug, Default)]
+pub struct StateReadsCache(Arc<Mutex<CachedReads>>);
+
+#[derive(Debug, Default)]
+struct CachedReads {
+    state_number: BlockNumber,
+    reads: StateMaps,
+}
+
+impl StateReadsCache {
+    /// Returns the value `read` finds among the cached reads, if they are of the given state.
+    pub fn read<T>(
+        &self,
+        state_number: BlockNumber,
+        read: impl FnOnce(&StateMaps) -> Option<T>,
+    ) -> Option<T> {
+        let cached_reads = self.lock();
+        if cached_reads.state_number != state_number {
+            return None;
+        }
+        read(&cached_reads.reads)
+    }
+
+    /// Caches values read from the given state, unless a newer state is cached.
+    pub fn write(&self, state_number: BlockNumber, write: impl FnOnce(&mut StateMaps)) {
+        let mut cached_reads = self.lock();
+        if cached_reads.state_number < state_number {
+            *cached_reads = CachedReads { state_number, reads: StateMaps::default() };
+        }
+        if cached_reads.state_number == state_number {
+            write(&mut cached_reads.reads);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CachedReads> {
+        self.0.lock().expect("State reads cache lock failed.")
+    }
+}
@@ -0,0 +1,32 @@
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+use starknet_api::{contract_address, felt};
+
+use crate::state_reads_cache::StateReadsCache;
+
+#[test]
+fn test_state_reads_cache() {
+    let address = contract_address!("0x1");
+    let read_nonce = |cache: &StateReadsCache, state_number| {
+        cache.read(BlockNumber(state_number), |reads| reads.nonces.get(&address).copied())
+    };
+    let cache = StateReadsCache::default();
+    let shared_cache = cache.clone();
+
+    cache.write(BlockNumber(1), |reads| {
+        reads.nonces.insert(address, Nonce(felt!("0x1")));
+    });
+    assert_eq!(read_nonce(&shared_cache, 1), Some(Nonce(felt!("0x1"))));
+    assert_eq!(read_nonce(&shared_cache, 0), None);
+
+    // Reads of an older state are not cached.
+    cache.write(BlockNumber(0), |reads| {
+        reads.nonces.insert(address, Nonce(felt!("0x0")));
+    });
+    assert_eq!(read_nonce(&shared_cache, 1), Some(Nonce(felt!("0x1"))));
+
+    // Reads of a newer state replace the cached ones.
+    cache.write(BlockNumber(2), |_| {});
+    assert_eq!(read_nonce(&shared_cache, 1), None);
+    assert_eq!(read_nonce(&shared_cache, 2), None);
+}
@@ -0,0 +1,68 @@
+//! Interface for handling the configuration that governed the execution of each block.
+//!
+//! The configuration is recorded by the node that executes the blocks, at the time it commits
+//! them, so that replays and audits can reconstruct the parameters of any historical block. The
+//! storage doesn't interpret the configuration; it is kept as a JSON value.
+//!
+//! Import [`BlockConfigStorageReader`] and [`BlockConfigStorageWriter`] to read and write the
+//! configurations using a [`StorageTxn`].
+
+#[cfg(test)]
+#[path = "block_config_test.rs"]
+mod block_config_test;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+
+use crate::db::table_types::Table;
+use crate::db::{TransactionKind, RW};
+use crate::{StorageResult, StorageTxn};
+
+/// The configuration that governed the execution of a block, serialized as JSON.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SerializedBlockConfig(pub serde_json::Value);
+
+/// Interface for reading the block configurations.
+pub trait BlockConfigStorageReader {
+    /// Returns the configuration the given block was executed with, if it was recorded.
+    fn get_block_config(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<SerializedBlockConfig>>;
+}
+
+/// Interface for writing the block configurations.
+pub trait BlockConfigStorageWriter
+where
+    Self: Sized,
+{
+    /// Stores the configuration the given block was executed with.
+    // To enforce that no commit happen after a failure, we consume and return Self on success.
+    fn append_block_config(
+        self,
+        block_number: BlockNumber,
+        block_config: &SerializedBlockConfig,
+    ) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> BlockConfigStorageReader for StorageTxn<'_, Mode> {
+    fn get_block_config(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<SerializedBlockConfig>> {
+        let block_configs_table = self.open_table(&self.tables.block_configs)?;
+        Ok(block_configs_table.get(&self.txn, &block_number)?)
+    }
+}
+
+impl BlockConfigStorageWriter for StorageTxn<'_, RW> {
+    fn append_block_config(
+        self,
+        block_number: BlockNumber,
+        block_config: &SerializedBlockConfig,
+    ) -> StorageResult<Self> {
+        let block_configs_table = self.open_table(&self.tables.block_configs)?;
+        block_configs_table.upsert(&self.txn, &block_number, block_config)?;
+        Ok(self)
+    }
+}
@@ -0,0 +1,32 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use starknet_api::block::BlockNumber;
+
+use crate::block_config::{
+    BlockConfigStorageReader,
+    BlockConfigStorageWriter,
+    SerializedBlockConfig,
+};
+use crate::test_utils::get_test_storage;
+
+#[test]
+fn append_and_get_block_configs() {
+    let (reader, mut writer) = get_test_storage().0;
+    let first_config = SerializedBlockConfig(json!({ "versioned_constants_hash": "0x1" }));
+    let second_config = SerializedBlockConfig(json!({ "versioned_constants_hash": "0x2" }));
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_block_config(BlockNumber(0), &first_config)
+        .unwrap()
+        .append_block_config(BlockNumber(2), &second_config)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_block_config(BlockNumber(0)).unwrap(), Some(first_config));
+    assert_eq!(txn.get_block_config(BlockNumber(1)).unwrap(), None);
+    assert_eq!(txn.get_block_config(BlockNumber(2)).unwrap(), Some(second_config));
+}
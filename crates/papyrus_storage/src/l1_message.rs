@@ -0,0 +1,71 @@
+//! Interface for handling the L1 handler messages consumed by the blocks in the storage.
+//!
+//! Every L1 message is identified by its nonce on the base layer; recording the nonces of the
+//! messages included in each block allows rejecting a message that is replayed in a later block,
+//! also after a restart.
+//!
+//! Import [`L1MessageStorageReader`] and [`L1MessageStorageWriter`] to read and write the consumed
+//! L1 messages using a [`StorageTxn`].
+
+#[cfg(test)]
+#[path = "l1_message_test.rs"]
+mod l1_message_test;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+
+use crate::db::table_types::Table;
+use crate::db::{TransactionKind, RW};
+use crate::{StorageError, StorageResult, StorageTxn};
+
+/// Interface for reading the consumed L1 messages.
+pub trait L1MessageStorageReader {
+    /// Returns the block that consumed the L1 message with the given nonce, if any.
+    fn get_l1_message_consumption_block(&self, nonce: &Nonce)
+    -> StorageResult<Option<BlockNumber>>;
+}
+
+/// Interface for writing the consumed L1 messages.
+pub trait L1MessageStorageWriter
+where
+    Self: Sized,
+{
+    /// Marks the L1 messages with the given nonces as consumed by the given block. Fails if any of
+    /// the messages was already consumed.
+    // To enforce that no commit happen after a failure, we consume and return Self on success.
+    fn consume_l1_messages(
+        self,
+        block_number: BlockNumber,
+        nonces: &[Nonce],
+    ) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> L1MessageStorageReader for StorageTxn<'_, Mode> {
+    fn get_l1_message_consumption_block(
+        &self,
+        nonce: &Nonce,
+    ) -> StorageResult<Option<BlockNumber>> {
+        let consumed_l1_messages_table = self.open_table(&self.tables.consumed_l1_messages)?;
+        Ok(consumed_l1_messages_table.get(&self.txn, nonce)?)
+    }
+}
+
+impl L1MessageStorageWriter for StorageTxn<'_, RW> {
+    fn consume_l1_messages(
+        self,
+        block_number: BlockNumber,
+        nonces: &[Nonce],
+    ) -> StorageResult<Self> {
+        let consumed_l1_messages_table = self.open_table(&self.tables.consumed_l1_messages)?;
+        for nonce in nonces {
+            if let Some(consumption_block) = consumed_l1_messages_table.get(&self.txn, nonce)? {
+                return Err(StorageError::L1MessageAlreadyConsumed {
+                    nonce: *nonce,
+                    block_number: consumption_block,
+                });
+            }
+            consumed_l1_messages_table.insert(&self.txn, nonce, &block_number)?;
+        }
+        Ok(self)
+    }
+}
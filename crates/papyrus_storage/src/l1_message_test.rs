@@ -0,0 +1,51 @@
+use assert_matches::assert_matches;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+use starknet_types_core::felt::Felt;
+
+use crate::l1_message::{L1MessageStorageReader, L1MessageStorageWriter};
+use crate::test_utils::get_test_storage;
+use crate::StorageError;
+
+#[test]
+fn consume_l1_messages() {
+    let (reader, mut writer) = get_test_storage().0;
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .consume_l1_messages(BlockNumber(1), &[Nonce(Felt::from(0_u8)), Nonce(Felt::from(1_u8))])
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(
+        txn.get_l1_message_consumption_block(&Nonce(Felt::from(1_u8))).unwrap(),
+        Some(BlockNumber(1))
+    );
+    assert_eq!(txn.get_l1_message_consumption_block(&Nonce(Felt::from(2_u8))).unwrap(), None);
+}
+
+#[test]
+fn consume_l1_message_twice() {
+    let (_, mut writer) = get_test_storage().0;
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .consume_l1_messages(BlockNumber(1), &[Nonce(Felt::from(0_u8))])
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let result = writer
+        .begin_rw_txn()
+        .unwrap()
+        .consume_l1_messages(BlockNumber(2), &[Nonce(Felt::from(0_u8))]);
+    assert_matches!(
+        result,
+        Err(StorageError::L1MessageAlreadyConsumed { nonce, block_number: BlockNumber(1) })
+        if nonce == Nonce(Felt::from(0_u8))
+    );
+}
@@ -76,6 +76,7 @@
 //! [`libmdbx`]: https://docs.rs/libmdbx/latest/libmdbx/
 
 pub mod base_layer;
+pub mod block_config;
 pub mod body;
 pub mod class;
 pub mod compiled_class;
@@ -87,9 +88,12 @@ pub mod storage_metrics;
 pub mod compression_utils;
 pub mod db;
 pub mod header;
+pub mod l1_message;
 pub mod mmap_file;
+pub mod revenue;
 mod serialization;
 pub mod state;
+pub mod trace;
 mod version;
 
 mod deprecated;
@@ -133,6 +137,7 @@ use tracing::{debug, info, warn};
 use validator::Validate;
 use version::{StorageVersionError, Version};
 
+use crate::block_config::SerializedBlockConfig;
 use crate::body::TransactionIndex;
 use crate::db::table_types::SimpleTable;
 use crate::db::{
@@ -150,12 +155,14 @@ use crate::db::{
 };
 use crate::header::StorageBlockHeader;
 use crate::mmap_file::MMapFileStats;
+use crate::revenue::{RevenueDay, SerializedDailyRevenue};
 use crate::state::data::IndexedDeprecatedContractClass;
+use crate::trace::SerializedTransactionTrace;
 use crate::version::{VersionStorageReader, VersionStorageWriter};
 
 // For more details on the storage version, see the module documentation.
 /// The current version of the storage state code.
-pub const STORAGE_VERSION_STATE: Version = Version { major: 4, minor: 0 };
+pub const STORAGE_VERSION_STATE: Version = Version { major: 4, minor: 4 };
 /// The current version of the storage blocks code.
 pub const STORAGE_VERSION_BLOCKS: Version = Version { major: 4, minor: 0 };
 
@@ -172,10 +179,13 @@ pub fn open_storage(
 
     let (db_reader, mut db_writer) = open_env(&storage_config.db_config)?;
     let tables = Arc::new(Tables {
+        block_configs: db_writer.create_simple_table("block_configs")?,
         block_hash_to_number: db_writer.create_simple_table("block_hash_to_number")?,
         block_signatures: db_writer.create_simple_table("block_signatures")?,
         casms: db_writer.create_simple_table("casms")?,
+        consumed_l1_messages: db_writer.create_simple_table("consumed_l1_messages")?,
         contract_storage: db_writer.create_common_prefix_table("contract_storage")?,
+        daily_revenues: db_writer.create_simple_table("daily_revenues")?,
         declared_classes: db_writer.create_simple_table("declared_classes")?,
         declared_classes_block: db_writer.create_simple_table("declared_classes_block")?,
         deprecated_declared_classes: db_writer
@@ -189,6 +199,7 @@ pub fn open_storage(
         state_diffs: db_writer.create_simple_table("state_diffs")?,
         transaction_hash_to_idx: db_writer.create_simple_table("transaction_hash_to_idx")?,
         transaction_metadata: db_writer.create_simple_table("transaction_metadata")?,
+        transaction_traces: db_writer.create_simple_table("transaction_traces")?,
 
         // Version tables
         starknet_version: db_writer.create_simple_table("starknet_version")?,
@@ -517,12 +528,15 @@ pub fn table_names() -> &'static [&'static str] {
 
 struct_field_names! {
     struct Tables {
+        block_configs: TableIdentifier<BlockNumber, VersionZeroWrapper<SerializedBlockConfig>, SimpleTable>,
         block_hash_to_number: TableIdentifier<BlockHash, NoVersionValueWrapper<BlockNumber>, SimpleTable>,
         block_signatures: TableIdentifier<BlockNumber, VersionZeroWrapper<BlockSignature>, SimpleTable>,
         casms: TableIdentifier<ClassHash, VersionZeroWrapper<LocationInFile>, SimpleTable>,
+        consumed_l1_messages: TableIdentifier<Nonce, NoVersionValueWrapper<BlockNumber>, SimpleTable>,
         // Empirically, defining the common prefix as (ContractAddress, StorageKey) is better space-wise than defining the
         // common prefix only as ContractAddress.
         contract_storage: TableIdentifier<((ContractAddress, StorageKey), BlockNumber), NoVersionValueWrapper<Felt>, CommonPrefix>,
+        daily_revenues: TableIdentifier<RevenueDay, VersionZeroWrapper<SerializedDailyRevenue>, SimpleTable>,
         declared_classes: TableIdentifier<ClassHash, VersionZeroWrapper<LocationInFile>, SimpleTable>,
         declared_classes_block: TableIdentifier<ClassHash, NoVersionValueWrapper<BlockNumber>, SimpleTable>,
         deprecated_declared_classes: TableIdentifier<ClassHash, VersionZeroWrapper<IndexedDeprecatedContractClass>, SimpleTable>,
@@ -537,6 +551,7 @@ struct_field_names! {
         transaction_hash_to_idx: TableIdentifier<TransactionHash, NoVersionValueWrapper<TransactionIndex>, SimpleTable>,
         // TODO(dvir): consider not saving transaction hash and calculating it from the transaction on demand.
         transaction_metadata: TableIdentifier<TransactionIndex, VersionZeroWrapper<TransactionMetadata>, SimpleTable>,
+        transaction_traces: TableIdentifier<TransactionIndex, VersionZeroWrapper<SerializedTransactionTrace>, SimpleTable>,
 
         // Version tables
         starknet_version: TableIdentifier<BlockNumber, VersionZeroWrapper<StarknetVersion>, SimpleTable>,
@@ -610,6 +625,8 @@ pub enum StorageError {
          {block_number}."
     )]
     BlockSignatureForNonExistingBlock { block_number: BlockNumber, block_signature: BlockSignature },
+    #[error("L1 message with nonce {nonce:?} was already consumed at block {block_number}.")]
+    L1MessageAlreadyConsumed { nonce: Nonce, block_number: BlockNumber },
 }
 
 /// A type alias that maps to std::result::Result<T, StorageError>.
@@ -0,0 +1,73 @@
+//! Interface for handling the revenue collected in the blocks, aggregated per day.
+//!
+//! The daily totals are recorded by the node that executes the blocks, at the time it commits
+//! them, so that they survive restarts. The storage doesn't interpret the totals; each one is kept
+//! as a JSON value.
+//!
+//! Import [`RevenueStorageReader`] and [`RevenueStorageWriter`] to read and write the daily totals
+//! using a [`StorageTxn`].
+
+#[cfg(test)]
+#[path = "revenue_test.rs"]
+mod revenue_test;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::table_types::{DbCursorTrait, Table};
+use crate::db::{TransactionKind, RW};
+use crate::{StorageResult, StorageTxn};
+
+/// The day a revenue total is aggregated over, as the number of days since the start of the
+/// common era.
+pub type RevenueDay = u64;
+
+/// The revenue collected in the blocks of a single day, serialized as JSON.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SerializedDailyRevenue(pub serde_json::Value);
+
+/// Interface for reading the daily revenue totals.
+pub trait RevenueStorageReader {
+    /// Returns the revenue totals of all the recorded days, oldest first.
+    fn get_daily_revenues(&self) -> StorageResult<Vec<(RevenueDay, SerializedDailyRevenue)>>;
+}
+
+/// Interface for writing the daily revenue totals.
+pub trait RevenueStorageWriter
+where
+    Self: Sized,
+{
+    /// Stores the revenue total of the given day, replacing the previously stored one.
+    // To enforce that no commit happen after a failure, we consume and return Self on success.
+    fn update_daily_revenue(
+        self,
+        day: RevenueDay,
+        daily_revenue: &SerializedDailyRevenue,
+    ) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> RevenueStorageReader for StorageTxn<'_, Mode> {
+    fn get_daily_revenues(&self) -> StorageResult<Vec<(RevenueDay, SerializedDailyRevenue)>> {
+        let daily_revenues_table = self.open_table(&self.tables.daily_revenues)?;
+        let mut cursor = daily_revenues_table.cursor(&self.txn)?;
+        let mut current = cursor.lower_bound(&0)?;
+
+        let mut daily_revenues = Vec::new();
+        while let Some(entry) = current {
+            daily_revenues.push(entry);
+            current = cursor.next()?;
+        }
+        Ok(daily_revenues)
+    }
+}
+
+impl RevenueStorageWriter for StorageTxn<'_, RW> {
+    fn update_daily_revenue(
+        self,
+        day: RevenueDay,
+        daily_revenue: &SerializedDailyRevenue,
+    ) -> StorageResult<Self> {
+        let daily_revenues_table = self.open_table(&self.tables.daily_revenues)?;
+        daily_revenues_table.upsert(&self.txn, &day, daily_revenue)?;
+        Ok(self)
+    }
+}
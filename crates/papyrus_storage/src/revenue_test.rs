@@ -0,0 +1,39 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::revenue::{RevenueStorageReader, RevenueStorageWriter, SerializedDailyRevenue};
+use crate::test_utils::get_test_storage;
+
+#[test]
+fn update_and_get_daily_revenues() {
+    let (reader, mut writer) = get_test_storage().0;
+    let first_revenue = SerializedDailyRevenue(json!({ "n_blocks": 1 }));
+    let second_revenue = SerializedDailyRevenue(json!({ "n_blocks": 2 }));
+    let updated_second_revenue = SerializedDailyRevenue(json!({ "n_blocks": 3 }));
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .update_daily_revenue(7, &second_revenue)
+        .unwrap()
+        .update_daily_revenue(5, &first_revenue)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_daily_revenues().unwrap(),
+        vec![(5, first_revenue.clone()), (7, second_revenue)]
+    );
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .update_daily_revenue(7, &updated_second_revenue)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_daily_revenues().unwrap(),
+        vec![(5, first_revenue), (7, updated_second_revenue)]
+    );
+}
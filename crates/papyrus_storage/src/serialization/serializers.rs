@@ -126,6 +126,7 @@ use starknet_api::transaction::{
 use starknet_types_core::felt::Felt;
 use tracing::warn;
 
+use crate::block_config::SerializedBlockConfig;
 use crate::body::events::EventIndex;
 use crate::body::TransactionIndex;
 use crate::compression_utils::{
@@ -139,9 +140,11 @@ use crate::db::serialization::{StorageSerde, StorageSerdeError};
 use crate::db::table_types::NoValue;
 use crate::header::StorageBlockHeader;
 use crate::mmap_file::LocationInFile;
+use crate::revenue::SerializedDailyRevenue;
 #[cfg(test)]
 use crate::serialization::serializers_test::{create_storage_serde_test, StorageSerdeTest};
 use crate::state::data::IndexedDeprecatedContractClass;
+use crate::trace::SerializedTransactionTrace;
 use crate::version::Version;
 use crate::{MarkerKind, OffsetKind, TransactionMetadata};
 
@@ -412,6 +415,7 @@ auto_storage_serde! {
         V0_13_2_1 = 17,
         V0_13_3 = 18,
         V0_13_4 = 19,
+        V0_13_5 = 20,
     }
     pub struct StateDiffCommitment(pub PoseidonHash);
     pub struct Tip(pub u64);
@@ -1133,6 +1137,39 @@ impl StorageSerde for ThinStateDiff {
 #[cfg(test)]
 create_storage_serde_test!(ThinStateDiff);
 
+impl StorageSerde for SerializedBlockConfig {
+    fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.0.serialize_into(res)
+    }
+
+    fn deserialize_from(bytes: &mut impl std::io::Read) -> Option<Self> {
+        Some(Self(serde_json::Value::deserialize_from(bytes)?))
+    }
+}
+
+impl StorageSerde for SerializedDailyRevenue {
+    fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.0.serialize_into(res)
+    }
+
+    fn deserialize_from(bytes: &mut impl std::io::Read) -> Option<Self> {
+        Some(Self(serde_json::Value::deserialize_from(bytes)?))
+    }
+}
+
+// Traces are large and repetitive, so they are compressed.
+impl StorageSerde for SerializedTransactionTrace {
+    fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        serialize_and_compress(&self.0)?.serialize_into(res)
+    }
+
+    fn deserialize_from(bytes: &mut impl std::io::Read) -> Option<Self> {
+        Some(Self(serde_json::Value::deserialize_from(
+            &mut decompress_from_reader(bytes)?.as_slice(),
+        )?))
+    }
+}
+
 // The following structs are conditionally compressed based on their serialized size.
 macro_rules! auto_storage_serde_conditionally_compressed {
     () => {};
@@ -0,0 +1,127 @@
+//! Interface for handling the execution traces of the transactions in the storage.
+//!
+//! Traces are optionally archived by the node that executes the blocks, at the time it commits
+//! them, so that they can be served without re-executing the block. The storage doesn't interpret
+//! the traces; each one is kept as the JSON value of the transaction trace in the RPC format.
+//!
+//! Import [`TraceStorageReader`] and [`TraceStorageWriter`] to read and write the traces using a
+//! [`StorageTxn`].
+
+#[cfg(test)]
+#[path = "trace_test.rs"]
+mod trace_test;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::transaction::TransactionOffsetInBlock;
+
+use crate::body::TransactionIndex;
+use crate::db::table_types::{DbCursorTrait, Table};
+use crate::db::{TransactionKind, RW};
+use crate::{StorageResult, StorageTxn};
+
+/// The execution trace of a transaction, serialized in the RPC trace format.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SerializedTransactionTrace(pub serde_json::Value);
+
+/// Interface for reading the transaction traces.
+pub trait TraceStorageReader {
+    /// Returns the trace of the transaction at the given index, if it was archived.
+    fn get_transaction_trace(
+        &self,
+        transaction_index: TransactionIndex,
+    ) -> StorageResult<Option<SerializedTransactionTrace>>;
+
+    /// Returns the traces of the transactions of the given block, ordered by their offset in the
+    /// block, or `None` if they were not archived.
+    fn get_block_transaction_traces(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<Vec<SerializedTransactionTrace>>>;
+}
+
+/// Interface for writing the transaction traces.
+pub trait TraceStorageWriter
+where
+    Self: Sized,
+{
+    /// Stores the traces of the transactions of the given block, ordered by their offset in the
+    /// block.
+    // To enforce that no commit happen after a failure, we consume and return Self on success.
+    fn append_transaction_traces(
+        self,
+        block_number: BlockNumber,
+        traces: &[SerializedTransactionTrace],
+    ) -> StorageResult<Self>;
+
+    /// Deletes the traces of all the blocks before the given block.
+    fn prune_transaction_traces(self, until_block_number: BlockNumber) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> TraceStorageReader for StorageTxn<'_, Mode> {
+    fn get_transaction_trace(
+        &self,
+        transaction_index: TransactionIndex,
+    ) -> StorageResult<Option<SerializedTransactionTrace>> {
+        let transaction_traces_table = self.open_table(&self.tables.transaction_traces)?;
+        Ok(transaction_traces_table.get(&self.txn, &transaction_index)?)
+    }
+
+    fn get_block_transaction_traces(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<Vec<SerializedTransactionTrace>>> {
+        let transaction_traces_table = self.open_table(&self.tables.transaction_traces)?;
+        let mut cursor = transaction_traces_table.cursor(&self.txn)?;
+        let mut current =
+            cursor.lower_bound(&TransactionIndex(block_number, TransactionOffsetInBlock(0)))?;
+
+        let mut traces = Vec::new();
+        while let Some((TransactionIndex(current_block_number, _), trace)) = current {
+            if current_block_number != block_number {
+                break;
+            }
+            traces.push(trace);
+            current = cursor.next()?;
+        }
+        // Blocks are archived with all their traces, so a block without traces wasn't archived.
+        Ok(if traces.is_empty() { None } else { Some(traces) })
+    }
+}
+
+impl TraceStorageWriter for StorageTxn<'_, RW> {
+    fn append_transaction_traces(
+        self,
+        block_number: BlockNumber,
+        traces: &[SerializedTransactionTrace],
+    ) -> StorageResult<Self> {
+        let transaction_traces_table = self.open_table(&self.tables.transaction_traces)?;
+        for (offset, trace) in traces.iter().enumerate() {
+            let transaction_index =
+                TransactionIndex(block_number, TransactionOffsetInBlock(offset));
+            transaction_traces_table.upsert(&self.txn, &transaction_index, trace)?;
+        }
+        Ok(self)
+    }
+
+    fn prune_transaction_traces(self, until_block_number: BlockNumber) -> StorageResult<Self> {
+        let transaction_traces_table = self.open_table(&self.tables.transaction_traces)?;
+        let mut pruned_indices = Vec::new();
+        {
+            let mut cursor = transaction_traces_table.cursor(&self.txn)?;
+            let mut current = cursor
+                .lower_bound(&TransactionIndex(BlockNumber(0), TransactionOffsetInBlock(0)))?;
+            while let Some((transaction_index, _trace)) = current {
+                if transaction_index.0 >= until_block_number {
+                    break;
+                }
+                pruned_indices.push(transaction_index);
+                current = cursor.next()?;
+            }
+        }
+        for transaction_index in &pruned_indices {
+            transaction_traces_table.delete(&self.txn, transaction_index)?;
+        }
+        Ok(self)
+    }
+}
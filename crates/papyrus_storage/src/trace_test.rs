@@ -0,0 +1,57 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use starknet_api::block::BlockNumber;
+use starknet_api::transaction::TransactionOffsetInBlock;
+
+use crate::body::TransactionIndex;
+use crate::test_utils::get_test_storage;
+use crate::trace::{SerializedTransactionTrace, TraceStorageReader, TraceStorageWriter};
+
+fn trace(tx_offset: usize) -> SerializedTransactionTrace {
+    SerializedTransactionTrace(json!({ "type": "INVOKE", "offset": tx_offset }))
+}
+
+#[test]
+fn append_and_get_transaction_traces() {
+    let (reader, mut writer) = get_test_storage().0;
+    let block_traces = vec![trace(0), trace(1)];
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_transaction_traces(BlockNumber(1), &block_traces)
+        .unwrap()
+        .append_transaction_traces(BlockNumber(2), &[trace(0)])
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(
+        txn.get_transaction_trace(TransactionIndex(BlockNumber(1), TransactionOffsetInBlock(1)))
+            .unwrap(),
+        Some(trace(1))
+    );
+    assert_eq!(
+        txn.get_transaction_trace(TransactionIndex(BlockNumber(1), TransactionOffsetInBlock(2)))
+            .unwrap(),
+        None
+    );
+    assert_eq!(txn.get_block_transaction_traces(BlockNumber(1)).unwrap(), Some(block_traces));
+    assert_eq!(txn.get_block_transaction_traces(BlockNumber(0)).unwrap(), None);
+}
+
+#[test]
+fn prune_transaction_traces() {
+    let (reader, mut writer) = get_test_storage().0;
+    let mut txn = writer.begin_rw_txn().unwrap();
+    for block_number in 0..3 {
+        txn = txn.append_transaction_traces(BlockNumber(block_number), &[trace(0)]).unwrap();
+    }
+    txn.prune_transaction_traces(BlockNumber(2)).unwrap().commit().unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_block_transaction_traces(BlockNumber(0)).unwrap(), None);
+    assert_eq!(txn.get_block_transaction_traces(BlockNumber(1)).unwrap(), None);
+    assert_eq!(txn.get_block_transaction_traces(BlockNumber(2)).unwrap(), Some(vec![trace(0)]));
+}
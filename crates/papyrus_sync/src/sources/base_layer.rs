@@ -2,7 +2,7 @@ use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
 use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerContract;
-use papyrus_base_layer::BaseLayerContract;
+use papyrus_base_layer::{BaseLayerContract, Finality};
 use starknet_api::block::{BlockHash, BlockNumber};
 
 pub type EthereumBaseLayerSource = EthereumBaseLayerContract;
@@ -36,8 +36,7 @@ impl<
     async fn latest_proved_block(
         &self,
     ) -> Result<Option<(BlockNumber, BlockHash)>, BaseLayerSourceError> {
-        let finality = 0;
-        self.latest_proved_block(finality)
+        self.latest_proved_block(Finality::Latest)
             .await
             .map(|block| block.map(|block| (block.number, block.hash)))
             .map_err(|e| BaseLayerSourceError::BaseLayerContractError(Box::new(e)))
@@ -0,0 +1,22 @@
+use reqwest::StatusCode;
+use starknet_gateway_types::errors::GatewayErrorObject;
+use thiserror::Error;
+
+pub type SequencerClientResult<T> = Result<T, SequencerClientError>;
+
+#[derive(Debug, Error)]
+pub enum SequencerClientError {
+    #[error(transparent)]
+    BadUrl(#[from] url::ParseError),
+    #[error(transparent)]
+    DeserializationError(#[from] serde_json::Error),
+    /// The gateway rejected the request; the error object is the one returned by the node.
+    #[error("Gateway error {}: {}.", .0.code, .0.message)]
+    GatewayError(GatewayErrorObject),
+    #[error("Metric {metric_name} was not found.")]
+    MetricNotFound { metric_name: String },
+    #[error(transparent)]
+    RequestError(#[from] reqwest::Error),
+    #[error("Unexpected response status {status}: {body}.")]
+    UnexpectedStatus { status: StatusCode, body: String },
+}
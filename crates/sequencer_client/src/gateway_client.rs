@@ -0,0 +1,58 @@
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_batcher_types::batcher_types::GetFeeMarketInfoResponse;
+use starknet_gateway_types::errors::GatewayErrorObject;
+use starknet_gateway_types::gateway_types::{CallInput, SignedGatewayReceipt};
+use starknet_types_core::felt::Felt;
+use url::Url;
+
+use crate::errors::{SequencerClientError, SequencerClientResult};
+
+#[cfg(test)]
+#[path = "gateway_client_test.rs"]
+mod gateway_client_test;
+
+/// A client of the HTTP server through which the node accepts transactions.
+#[derive(Clone, Debug)]
+pub struct GatewayClient {
+    url: Url,
+    client: Client,
+}
+
+impl GatewayClient {
+    pub fn new(url: &str) -> SequencerClientResult<Self> {
+        Ok(Self { url: Url::parse(url)?, client: Client::new() })
+    }
+
+    /// Submits a transaction, returning the receipt the node signed on accepting it.
+    pub async fn add_tx(&self, tx: &RpcTransaction) -> SequencerClientResult<SignedGatewayReceipt> {
+        let response = self.client.post(self.url.join("add_tx")?).json(tx).send().await?;
+        parse_response(response).await
+    }
+
+    /// Calls a contract entry point without changing the state, returning its return data.
+    pub async fn call(&self, call_input: &CallInput) -> SequencerClientResult<Vec<Felt>> {
+        let response = self.client.post(self.url.join("call")?).json(call_input).send().await?;
+        parse_response(response).await
+    }
+
+    pub async fn fee_market_info(&self) -> SequencerClientResult<GetFeeMarketInfoResponse> {
+        let response = self.client.get(self.url.join("fee_market_info")?).send().await?;
+        parse_response(response).await
+    }
+}
+
+// Rejections by the gateway are JSON-RPC error objects, returned with a success status like
+// accepted requests; other failures are reported with their status.
+async fn parse_response<T: DeserializeOwned>(response: Response) -> SequencerClientResult<T> {
+    let status = response.status();
+    let body = response.text().await?;
+    if let Ok(error_object) = serde_json::from_str::<GatewayErrorObject>(&body) {
+        return Err(SequencerClientError::GatewayError(error_object));
+    }
+    if !status.is_success() {
+        return Err(SequencerClientError::UnexpectedStatus { status, body });
+    }
+    Ok(serde_json::from_str(&body)?)
+}
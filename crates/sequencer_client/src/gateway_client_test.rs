@@ -0,0 +1,67 @@
+use assert_matches::assert_matches;
+use mockito::Matcher;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::Signature;
+use starknet_api::invoke_tx_args;
+use starknet_api::test_utils::invoke::rpc_invoke_tx;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::errors::{GatewayErrorObject, GatewaySpecError};
+use starknet_gateway_types::gateway_types::{GatewayReceipt, SignedGatewayReceipt};
+use starknet_types_core::felt::Felt;
+
+use crate::errors::SequencerClientError;
+use crate::gateway_client::GatewayClient;
+
+#[tokio::test]
+async fn add_tx() {
+    let mut server = mockito::Server::new_async().await;
+    let tx = rpc_invoke_tx(invoke_tx_args!());
+    let signed_receipt = SignedGatewayReceipt {
+        receipt: GatewayReceipt {
+            tx_hash: TransactionHash(Felt::ONE),
+            received_timestamp: 0,
+            chain_id: ChainId::Mainnet,
+        },
+        signature: Signature { r: Felt::ONE, s: Felt::TWO },
+    };
+    let mock = server
+        .mock("POST", "/add_tx")
+        .match_body(Matcher::Json(serde_json::to_value(&tx).unwrap()))
+        .with_body(serde_json::to_string(&signed_receipt).unwrap())
+        .create_async()
+        .await;
+
+    let client = GatewayClient::new(&server.url()).unwrap();
+    assert_eq!(client.add_tx(&tx).await.unwrap(), signed_receipt);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn add_tx_rejected() {
+    let mut server = mockito::Server::new_async().await;
+    let error_object = GatewayErrorObject::from(GatewaySpecError::DuplicateTx);
+    server
+        .mock("POST", "/add_tx")
+        .with_body(serde_json::to_string(&error_object).unwrap())
+        .create_async()
+        .await;
+
+    let client = GatewayClient::new(&server.url()).unwrap();
+    let error = client.add_tx(&rpc_invoke_tx(invoke_tx_args!())).await.unwrap_err();
+    assert_matches!(error, SequencerClientError::GatewayError(object) if object == error_object);
+}
+
+#[tokio::test]
+async fn unexpected_status() {
+    let mut server = mockito::Server::new_async().await;
+    server.mock("GET", "/fee_market_info").with_status(500).create_async().await;
+
+    let client = GatewayClient::new(&server.url()).unwrap();
+    assert_matches!(
+        client.fee_market_info().await,
+        Err(SequencerClientError::UnexpectedStatus { status, .. })
+            if status == StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
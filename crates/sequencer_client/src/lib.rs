@@ -0,0 +1,7 @@
+//! Typed clients for the HTTP APIs of the sequencer node, for operators and test harnesses that
+//! drive a node programmatically.
+// TODO: add a client for the admin API once the node exposes one.
+
+pub mod errors;
+pub mod gateway_client;
+pub mod monitoring_client;
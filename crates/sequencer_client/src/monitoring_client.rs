@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use infra_utils::metrics::parse_numeric_metric;
+use num_traits::Num;
+use reqwest::Client;
+use url::Url;
+
+use crate::errors::{SequencerClientError, SequencerClientResult};
+
+#[cfg(test)]
+#[path = "monitoring_client_test.rs"]
+mod monitoring_client_test;
+
+const MONITORING_PREFIX: &str = "monitoring";
+
+/// A client of the monitoring endpoint of the node.
+#[derive(Clone, Debug)]
+pub struct MonitoringClient {
+    url: Url,
+    client: Client,
+}
+
+impl MonitoringClient {
+    pub fn new(url: &str) -> SequencerClientResult<Self> {
+        Ok(Self { url: Url::parse(url)?, client: Client::new() })
+    }
+
+    /// Returns whether the node is alive.
+    pub async fn is_alive(&self) -> bool {
+        self.get("alive").await.is_ok()
+    }
+
+    /// Returns whether the node is ready to serve requests.
+    pub async fn is_ready(&self) -> bool {
+        self.get("ready").await.is_ok()
+    }
+
+    pub async fn node_version(&self) -> SequencerClientResult<String> {
+        self.get("nodeVersion").await
+    }
+
+    /// Returns the metrics of the node, in the Prometheus text format.
+    pub async fn metrics(&self) -> SequencerClientResult<String> {
+        self.get("metrics").await
+    }
+
+    pub async fn metric<T: Num + FromStr>(&self, metric_name: &str) -> SequencerClientResult<T> {
+        let metrics = self.metrics().await?;
+        parse_numeric_metric(&metrics, metric_name)
+            .ok_or(SequencerClientError::MetricNotFound { metric_name: metric_name.to_string() })
+    }
+
+    async fn get(&self, method: &str) -> SequencerClientResult<String> {
+        let url = self.url.join(&format!("{MONITORING_PREFIX}/{method}"))?;
+        let response = self.client.get(url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(SequencerClientError::UnexpectedStatus { status, body });
+        }
+        Ok(body)
+    }
+}
@@ -0,0 +1,30 @@
+use pretty_assertions::assert_eq;
+
+use crate::monitoring_client::MonitoringClient;
+
+#[tokio::test]
+async fn liveness_and_version() {
+    let mut server = mockito::Server::new_async().await;
+    server.mock("GET", "/monitoring/alive").create_async().await;
+    server.mock("GET", "/monitoring/ready").with_status(503).create_async().await;
+    server.mock("GET", "/monitoring/nodeVersion").with_body("0.0.0").create_async().await;
+
+    let client = MonitoringClient::new(&server.url()).unwrap();
+    assert!(client.is_alive().await);
+    assert!(!client.is_ready().await);
+    assert_eq!(client.node_version().await.unwrap(), "0.0.0");
+}
+
+#[tokio::test]
+async fn metric() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/monitoring/metrics")
+        .with_body("# TYPE added_transactions_total counter\nadded_transactions_total 7\n")
+        .create_async()
+        .await;
+
+    let client = MonitoringClient::new(&server.url()).unwrap();
+    assert_eq!(client.metric::<u64>("added_transactions_total").await.unwrap(), 7);
+    assert!(client.metric::<u64>("missing_metric").await.is_err());
+}
@@ -15,7 +15,11 @@ use std::time::Duration;
 use futures::channel::mpsc;
 use futures::stream::FuturesUnordered;
 use futures::{Stream, StreamExt};
-use papyrus_common::metrics::{PAPYRUS_CONSENSUS_HEIGHT, PAPYRUS_CONSENSUS_SYNC_COUNT};
+use papyrus_common::metrics::{
+    PAPYRUS_CONSENSUS_HEIGHT,
+    PAPYRUS_CONSENSUS_PRUNED_CACHED_MESSAGES,
+    PAPYRUS_CONSENSUS_SYNC_COUNT,
+};
 use papyrus_network::network_manager::BroadcastTopicClientTrait;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_protobuf::consensus::{ProposalInit, Vote};
@@ -121,6 +125,11 @@ type ProposalReceiverTuple<T> = (ProposalInit, mpsc::Receiver<T>);
 
 /// Runs Tendermint repeatedly across different heights. Handles issues which are not explicitly
 /// part of the single height consensus algorithm (e.g. messages from future heights).
+///
+/// Consensus artifacts (proposals and votes) are only kept in memory. The ones cached for heights
+/// below the one being run are pruned whenever a height starts, so they don't accumulate on
+/// long-running validators. The content of the proposals is kept by the context, which is
+/// responsible for dropping the one of past heights.
 #[derive(Debug, Default)]
 struct MultiHeightManager<ContextT: ConsensusContext> {
     validator_id: ValidatorId,
@@ -169,6 +178,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     where
         SyncReceiverT: Stream<Item = BlockNumber> + Unpin,
     {
+        self.prune_past_heights(height);
         let validators = context.validators(height).await;
         let is_observer = must_observer || !validators.contains(&self.validator_id);
         info!("running consensus for height {height:?} with validator set {validators:?}");
@@ -333,6 +343,31 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         shc.handle_vote(context, message).await
     }
 
+    // Drops the proposals and votes cached for heights below `height`, which can no longer be used.
+    // Heights may be skipped (e.g. after a sync), so this can't rely on the caches being consumed
+    // one height at a time.
+    fn prune_past_heights(&mut self, height: BlockNumber) {
+        let cached_proposals = self.cached_proposals.split_off(&height.0);
+        let pruned_proposals = std::mem::replace(&mut self.cached_proposals, cached_proposals);
+        let future_votes = self.future_votes.split_off(&height.0);
+        let pruned_votes = std::mem::replace(&mut self.future_votes, future_votes);
+        let n_pruned_messages = pruned_proposals.values().map(BTreeMap::len).sum::<usize>()
+            + pruned_votes.values().map(Vec::len).sum::<usize>();
+        metrics::counter!(
+            PAPYRUS_CONSENSUS_PRUNED_CACHED_MESSAGES,
+            u64::try_from(n_pruned_messages).expect("Number of messages overflowed.")
+        );
+        if !pruned_proposals.is_empty() || !pruned_votes.is_empty() {
+            debug!(
+                "Pruned the cached proposals of {} and the cached votes of {} past heights. \
+                 current_height={}",
+                pruned_proposals.len(),
+                pruned_votes.len(),
+                height
+            );
+        }
+    }
+
     // Checks if a cached proposal already exists (with correct height)
     // - returns the proposal if it exists and removes it from the cache.
     // - returns None if no proposal exists.
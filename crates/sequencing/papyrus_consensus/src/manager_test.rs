@@ -356,3 +356,48 @@ async fn test_timeouts() {
 
     manager_handle.await.unwrap();
 }
+
+#[tokio::test]
+async fn past_heights_are_pruned() {
+    let TestSubscriberChannels { mock_network: _mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let (_proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
+
+    let mut context = MockTestContext::new();
+    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+
+    // Cache messages for heights the manager skips (e.g. due to a sync), and for a future height.
+    let mut manager = MultiHeightManager::new(*VALIDATOR_ID, TIMEOUTS.clone());
+    for height in [1, 2, 4] {
+        manager
+            .cached_proposals
+            .entry(height)
+            .or_default()
+            .insert(0, (proposal_init(height, 0, *PROPOSER_ID), mpsc::channel(CHANNEL_SIZE).1));
+        manager.future_votes.entry(height).or_default().push(prevote(
+            Some(Felt::ONE),
+            height,
+            0,
+            *PROPOSER_ID,
+        ));
+    }
+
+    // Height 3 ends through a sync.
+    let res = manager
+        .run_height(
+            &mut context,
+            BlockNumber(3),
+            false,
+            &mut subscriber_channels.into(),
+            &mut proposal_receiver_receiver,
+            &mut futures::stream::iter([BlockNumber(3)]),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(res, RunHeightRes::Sync(BlockNumber(3))));
+
+    assert_eq!(manager.cached_proposals.keys().collect::<Vec<_>>(), vec![&4]);
+    assert_eq!(manager.future_votes.keys().collect::<Vec<_>>(), vec![&4]);
+}
@@ -459,8 +459,14 @@ impl SingleHeightConsensus {
 
         // TODO: Figure out how to handle failed proposal building. I believe this should be handled
         // by applying timeoutPropose when we are the leader.
-        let init =
-            ProposalInit { height: self.height, round, proposer: self.id, valid_round: None };
+        // The context signs the randomness message of the height.
+        let init = ProposalInit {
+            height: self.height,
+            round,
+            proposer: self.id,
+            valid_round: None,
+            randomness_signature: None,
+        };
         let fin_receiver = context.build_proposal(init, self.timeouts.proposal_timeout).await;
         vec![ShcTask::BuildProposal(round, fin_receiver)]
     }
@@ -490,6 +496,7 @@ impl SingleHeightConsensus {
             round,
             proposer: self.id,
             valid_round: Some(valid_round),
+            randomness_signature: None,
         };
         context.repropose(id, init).await;
         let old = self.proposals.insert(round, Some(proposal_id));
@@ -83,18 +83,48 @@ pub struct CentralStateDiff {
     pub block_info: CentralBlockInfo,
 }
 
-impl From<(CommitmentStateDiff, BlockInfo, StarknetVersion)> for CentralStateDiff {
+/// Built from the block state diff and the part of it published to an alternative data
+/// availability layer; the storage updates and nonces of that part are keyed by the L2 data
+/// availability mode, and the rest by L1.
+impl From<(CommitmentStateDiff, CommitmentStateDiff, BlockInfo, StarknetVersion)>
+    for CentralStateDiff
+{
     fn from(
-        (state_diff, block_info, starknet_version): (
+        (state_diff, alternative_da_state_diff, block_info, starknet_version): (
+            CommitmentStateDiff,
             CommitmentStateDiff,
             BlockInfo,
             StarknetVersion,
         ),
     ) -> CentralStateDiff {
+        let mut storage_updates = state_diff.storage_updates;
+        for (address, alternative_da_updates) in &alternative_da_state_diff.storage_updates {
+            if let Some(updates) = storage_updates.get_mut(address) {
+                updates.retain(|key, _| !alternative_da_updates.contains_key(key));
+                if updates.is_empty() {
+                    storage_updates.shift_remove(address);
+                }
+            }
+        }
+        let mut nonces = state_diff.address_to_nonce;
+        nonces
+            .retain(|address, _| !alternative_da_state_diff.address_to_nonce.contains_key(address));
+
+        let mut central_nonces = indexmap!(DataAvailabilityMode::L1 => nonces);
+        let mut central_storage_updates = indexmap!(DataAvailabilityMode::L1 => storage_updates);
+        if !alternative_da_state_diff.address_to_nonce.is_empty() {
+            central_nonces
+                .insert(DataAvailabilityMode::L2, alternative_da_state_diff.address_to_nonce);
+        }
+        if !alternative_da_state_diff.storage_updates.is_empty() {
+            central_storage_updates
+                .insert(DataAvailabilityMode::L2, alternative_da_state_diff.storage_updates);
+        }
+
         CentralStateDiff {
             address_to_class_hash: state_diff.address_to_class_hash,
-            nonces: indexmap!(DataAvailabilityMode::L1=> state_diff.address_to_nonce),
-            storage_updates: indexmap!(DataAvailabilityMode::L1=> state_diff.storage_updates),
+            nonces: central_nonces,
+            storage_updates: central_storage_updates,
             declared_classes: state_diff.class_hash_to_compiled_class_hash,
             block_info: (block_info, starknet_version).into(),
         }
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use blockifier::state::cached_state::CommitmentStateDiff;
 use indexmap::indexmap;
 use rstest::rstest;
 use serde_json::Value;
 use starknet_api::block::{
+    BlockInfo,
     BlockNumber,
     BlockTimestamp,
     GasPrice,
@@ -119,3 +121,53 @@ fn serialize_central_objects(#[case] rust_json: Value, #[case] python_json_path:
 
     assert_eq!(rust_json, python_json,);
 }
+
+#[test]
+fn state_diff_by_data_availability_mode() {
+    let state_diff = CommitmentStateDiff {
+        address_to_nonce: indexmap!(
+            contract_address!(1_u8) => Nonce(felt!(1_u8)),
+            contract_address!(2_u8) => Nonce(felt!(2_u8)),
+        ),
+        storage_updates: indexmap!(
+            contract_address!(3_u8) => indexmap!(
+                storage_key!(3_u8) => felt!(3_u8),
+                storage_key!(4_u8) => felt!(4_u8),
+            ),
+        ),
+        ..Default::default()
+    };
+    let alternative_da_state_diff = CommitmentStateDiff {
+        address_to_nonce: indexmap!(contract_address!(2_u8) => Nonce(felt!(2_u8))),
+        storage_updates: indexmap!(
+            contract_address!(3_u8) => indexmap!(storage_key!(4_u8) => felt!(4_u8)),
+        ),
+        ..Default::default()
+    };
+
+    let central_state_diff = CentralStateDiff::from((
+        state_diff,
+        alternative_da_state_diff,
+        BlockInfo::create_for_testing(),
+        StarknetVersion::default(),
+    ));
+
+    assert_eq!(
+        central_state_diff.nonces,
+        indexmap!(
+            DataAvailabilityMode::L1 => indexmap!(contract_address!(1_u8) => Nonce(felt!(1_u8))),
+            DataAvailabilityMode::L2 => indexmap!(contract_address!(2_u8) => Nonce(felt!(2_u8))),
+        )
+    );
+    assert_eq!(
+        central_state_diff.storage_updates,
+        indexmap!(
+            DataAvailabilityMode::L1 => indexmap!(
+                contract_address!(3_u8) => indexmap!(storage_key!(3_u8) => felt!(3_u8)),
+            ),
+            DataAvailabilityMode::L2 => indexmap!(
+                contract_address!(3_u8) => indexmap!(storage_key!(4_u8) => felt!(4_u8)),
+            ),
+        )
+    );
+}
@@ -0,0 +1,97 @@
+//! Samples the L1 gas prices the blocks built by this node are priced with.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use papyrus_base_layer::ethereum_base_layer_contract::{
+    EthereumBaseLayerConfig,
+    EthereumBaseLayerContract,
+};
+use papyrus_base_layer::{BaseLayerContract, Finality, PriceSample};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use url::Url;
+
+/// The configuration of the L1 node the L1 gas prices are sampled from.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct L1GasPriceProviderConfig {
+    pub node_url: Url,
+}
+
+impl Default for L1GasPriceProviderConfig {
+    fn default() -> Self {
+        Self { node_url: EthereumBaseLayerConfig::default().node_url }
+    }
+}
+
+impl SerializeConfig for L1GasPriceProviderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "node_url",
+            &self.node_url,
+            "URL of the Ethereum node the L1 gas prices are sampled from.",
+            ParamPrivacyInput::Private,
+        )])
+    }
+}
+
+/// Provides the L1 gas prices to build blocks with.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait L1GasPriceProvider: Send + Sync {
+    /// Returns the latest L1 gas price sample, or `None` if no sample was ever taken.
+    async fn latest_price_sample(&self) -> Option<PriceSample>;
+}
+
+/// Samples the L1 gas prices of the latest L1 block.
+///
+/// If sampling fails, the previous sample is returned, so that the staleness of the prices shows
+/// in its timestamp instead of the prices being silently replaced.
+pub struct BaseLayerL1GasPriceProvider<B: BaseLayerContract> {
+    base_layer: B,
+    last_price_sample: Mutex<Option<PriceSample>>,
+}
+
+impl<B: BaseLayerContract> BaseLayerL1GasPriceProvider<B> {
+    pub fn new(base_layer: B) -> Self {
+        Self { base_layer, last_price_sample: Mutex::new(None) }
+    }
+}
+
+#[async_trait]
+impl<B> L1GasPriceProvider for BaseLayerL1GasPriceProvider<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    B::Error: Display + Send,
+{
+    async fn latest_price_sample(&self) -> Option<PriceSample> {
+        let price_sample = match self.base_layer.latest_l1_block_number(Finality::Latest).await {
+            Ok(Some(block_number)) => self.base_layer.get_price_sample(block_number).await,
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+        let mut last_price_sample = self.last_price_sample.lock().expect("Lock was poisoned");
+        match price_sample {
+            Ok(Some(price_sample)) => *last_price_sample = Some(price_sample),
+            Ok(None) => warn!("The latest L1 block has no gas prices."),
+            Err(e) => warn!("Failed to sample the L1 gas prices: {e}"),
+        }
+        *last_price_sample
+    }
+}
+
+pub fn create_l1_gas_price_provider(
+    config: &L1GasPriceProviderConfig,
+) -> Arc<dyn L1GasPriceProvider> {
+    let base_layer = EthereumBaseLayerContract::new(EthereumBaseLayerConfig {
+        node_url: config.node_url.clone(),
+        ..Default::default()
+    });
+    Arc::new(BaseLayerL1GasPriceProvider::new(base_layer))
+}
@@ -2,9 +2,12 @@
 //! An orchestrator for a StarkNet node.
 //! Implements the consensus context - the interface for consensus to call out to the node.
 
+#[allow(missing_docs)]
+pub mod l1_gas_price_provider;
 #[allow(missing_docs)]
 // TODO: this is test code, rename accordingly.
 pub mod papyrus_consensus_context;
+pub mod proposal_retention;
 #[allow(missing_docs)]
 pub mod sequencer_consensus_context;
 
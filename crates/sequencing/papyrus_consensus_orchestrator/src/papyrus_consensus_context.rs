@@ -187,6 +187,12 @@ impl ConsensusContext for PapyrusConsensusContext {
                                 content_transactions.push(tx);
                             }
                         }
+                        Some(ProposalPart::CompressedTransactions(compressed)) => {
+                            let batch = compressed
+                                .decompress()
+                                .expect("Failed to decompress proposal content");
+                            content_transactions.extend(batch.transactions);
+                        }
                         Some(ProposalPart::Fin(fin)) => {
                             break fin.proposal_content_id;
                         }
@@ -0,0 +1,126 @@
+//! Garbage collection of the proposals the context keeps for past heights.
+//!
+//! The context keeps the content of the valid proposals it built or received, to re-propose them
+//! and to pass the decided one to the batcher. Heights may end through a sync, in which case no
+//! decision prunes their proposals, so a background task drops the proposals of the heights that
+//! fell out of the retention window.
+
+#[cfg(test)]
+#[path = "proposal_retention_test.rs"]
+mod proposal_retention_test;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use papyrus_common::metrics::{
+    PAPYRUS_CONSENSUS_PRUNED_PROPOSALS,
+    PAPYRUS_CONSENSUS_PRUNED_PROPOSAL_TRANSACTIONS,
+};
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::sequencer_consensus_context::HeightToIdToContent;
+
+/// The retention of the proposals of past heights.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProposalRetentionConfig {
+    /// The number of heights below the current one whose proposals are kept.
+    pub n_retained_heights: u64,
+    /// The interval between the runs of the garbage collection.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub gc_interval: Duration,
+}
+
+impl Default for ProposalRetentionConfig {
+    fn default() -> Self {
+        Self { n_retained_heights: 0, gc_interval: Duration::from_secs(10) }
+    }
+}
+
+impl SerializeConfig for ProposalRetentionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "n_retained_heights",
+                &self.n_retained_heights,
+                "The number of heights below the current one whose proposals are kept.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "gc_interval",
+                &self.gc_interval.as_secs(),
+                "The interval (seconds) between the runs of the garbage collection of the \
+                 proposals of past heights.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The proposals dropped by a run of the garbage collection.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PrunedProposals {
+    pub n_proposals: usize,
+    pub n_transactions: usize,
+}
+
+/// Drops the proposals of the heights more than `n_retained_heights` below `current_height`.
+pub(crate) fn prune_proposals(
+    proposals: &Mutex<HeightToIdToContent>,
+    current_height: BlockNumber,
+    n_retained_heights: u64,
+) -> PrunedProposals {
+    let first_retained_height = BlockNumber(current_height.0.saturating_sub(n_retained_heights));
+    let pruned_heights = {
+        let mut proposals = proposals.lock().expect("valid_proposals lock was poisoned");
+        let retained_heights = proposals.split_off(&first_retained_height);
+        std::mem::replace(&mut *proposals, retained_heights)
+    };
+
+    let pruned_proposals = pruned_heights.values().flat_map(HashMap::values).fold(
+        PrunedProposals::default(),
+        |pruned, (transactions, _)| PrunedProposals {
+            n_proposals: pruned.n_proposals + 1,
+            n_transactions: pruned.n_transactions + transactions.len(),
+        },
+    );
+    if pruned_proposals != PrunedProposals::default() {
+        debug!(
+            "Pruned {} proposals with {} transactions of the heights below {}.",
+            pruned_proposals.n_proposals, pruned_proposals.n_transactions, first_retained_height
+        );
+    }
+    metrics::counter!(
+        PAPYRUS_CONSENSUS_PRUNED_PROPOSALS,
+        u64::try_from(pruned_proposals.n_proposals).expect("Number of proposals overflowed.")
+    );
+    metrics::counter!(
+        PAPYRUS_CONSENSUS_PRUNED_PROPOSAL_TRANSACTIONS,
+        u64::try_from(pruned_proposals.n_transactions).expect("Number of transactions overflowed.")
+    );
+    pruned_proposals
+}
+
+/// Spawns a task that periodically prunes the proposals of past heights, following the height
+/// consensus is working on.
+pub(crate) fn spawn_proposals_gc(
+    proposals: Arc<Mutex<HeightToIdToContent>>,
+    current_height: watch::Receiver<BlockNumber>,
+    config: ProposalRetentionConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.gc_interval);
+        loop {
+            interval.tick().await;
+            let height = *current_height.borrow();
+            prune_proposals(&proposals, height, config.n_retained_heights);
+        }
+    })
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
+use starknet_api::felt;
+use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
+use starknet_batcher_types::batcher_types::ProposalId;
+use tokio::sync::watch;
+
+use crate::proposal_retention::{
+    prune_proposals,
+    spawn_proposals_gc,
+    ProposalRetentionConfig,
+    PrunedProposals,
+};
+use crate::sequencer_consensus_context::HeightToIdToContent;
+
+// Two proposals per height, with one transaction each.
+fn proposals(heights: &[u64]) -> HeightToIdToContent {
+    let transaction = ExecutableTransaction::Account(executable_invoke_tx(InvokeTxArgs::default()));
+    heights
+        .iter()
+        .map(|height| {
+            let height_proposals = (0..2_u64)
+                .map(|id| {
+                    (
+                        BlockHash(felt!(id)),
+                        (vec![transaction.clone()], ProposalId(*height * 2 + id)),
+                    )
+                })
+                .collect::<HashMap<_, _>>();
+            (BlockNumber(*height), height_proposals)
+        })
+        .collect()
+}
+
+#[test]
+fn prunes_heights_out_of_retention_window() {
+    let valid_proposals = Mutex::new(proposals(&[1, 2, 3, 4, 5]));
+
+    let pruned = prune_proposals(&valid_proposals, BlockNumber(5), 2);
+
+    assert_eq!(pruned, PrunedProposals { n_proposals: 4, n_transactions: 4 });
+    assert_eq!(
+        valid_proposals.lock().unwrap().keys().collect::<Vec<_>>(),
+        vec![&BlockNumber(3), &BlockNumber(4), &BlockNumber(5)]
+    );
+
+    // Nothing is left to prune at the same height.
+    assert_eq!(prune_proposals(&valid_proposals, BlockNumber(5), 2), PrunedProposals::default());
+}
+
+#[test]
+fn retention_window_larger_than_height() {
+    let valid_proposals = Mutex::new(proposals(&[0, 1]));
+
+    assert_eq!(prune_proposals(&valid_proposals, BlockNumber(1), 5), PrunedProposals::default());
+    assert_eq!(valid_proposals.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn gc_follows_current_height() {
+    let valid_proposals = Arc::new(Mutex::new(proposals(&[1, 2, 3])));
+    let (height_sender, height_receiver) = watch::channel(BlockNumber(1));
+    let config =
+        ProposalRetentionConfig { n_retained_heights: 0, gc_interval: Duration::from_millis(10) };
+    let gc_handle = spawn_proposals_gc(Arc::clone(&valid_proposals), height_receiver, config);
+
+    height_sender.send_replace(BlockNumber(3));
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while valid_proposals.lock().unwrap().len() > 1 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The proposals of past heights were not pruned.");
+    assert_eq!(valid_proposals.lock().unwrap().keys().collect::<Vec<_>>(), vec![&BlockNumber(3)]);
+
+    gc_handle.abort();
+}
@@ -10,8 +10,14 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
+use blockifier::blockifier::block::randomness_message_hash;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, StreamExt};
+use papyrus_base_layer::PriceSample;
+use papyrus_common::metrics::{
+    PAPYRUS_CONSENSUS_PROPOSAL_COMPRESSED_BYTES,
+    PAPYRUS_CONSENSUS_PROPOSAL_UNCOMPRESSED_BYTES,
+};
 use papyrus_consensus::types::{
     ConsensusContext,
     ConsensusError,
@@ -21,6 +27,8 @@ use papyrus_consensus::types::{
 };
 use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use papyrus_protobuf::consensus::{
+    CompressedTransactionBatch,
+    CompressionAlgorithm,
     ProposalFin,
     ProposalInit,
     ProposalPart,
@@ -35,15 +43,18 @@ use starknet_api::block::{
     BlockInfo,
     BlockNumber,
     BlockTimestamp,
+    GasPrice,
     GasPriceVector,
     GasPrices,
     NonzeroGasPrice,
 };
 use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
 use starknet_api::transaction::{Transaction, TransactionHash};
 use starknet_batcher_types::batcher_types::{
     DecisionReachedInput,
+    DecisionReachedResponse,
     GetProposalContent,
     GetProposalContentInput,
     ProposalId,
@@ -54,14 +65,19 @@ use starknet_batcher_types::batcher_types::{
     StartHeightInput,
     ValidateBlockInput,
 };
-use starknet_batcher_types::communication::BatcherClient;
+use starknet_batcher_types::communication::{BatcherClient, BatcherClientError};
+use starknet_batcher_types::errors::BatcherError;
+use starknet_sequencer_infra::signer::SharedSigner;
 use starknet_state_sync_types::communication::SharedStateSyncClient;
 use starknet_state_sync_types::state_sync_types::SyncBlock;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, debug_span, info, instrument, trace, warn, Instrument};
 
 use crate::cende::{BlobParameters, CendeContext};
+use crate::l1_gas_price_provider::L1GasPriceProvider;
+use crate::proposal_retention::{spawn_proposals_gc, ProposalRetentionConfig};
 
 // TODO(Dan, Matan): Remove this once and replace with real gas prices.
 const TEMPORARY_GAS_PRICES: GasPrices = GasPrices {
@@ -80,9 +96,10 @@ const TEMPORARY_GAS_PRICES: GasPrices = GasPrices {
 // {height: {proposal_id: (content, [proposal_ids])}}
 // Note that multiple proposals IDs can be associated with the same content, but we only need to
 // store one of them.
-type HeightToIdToContent =
+pub(crate) type HeightToIdToContent =
     BTreeMap<BlockNumber, HashMap<ProposalContentId, (Vec<ExecutableTransaction>, ProposalId)>>;
-type ValidationParams = (BlockNumber, ValidatorId, Duration, mpsc::Receiver<ProposalPart>);
+type ValidationParams =
+    (BlockNumber, ValidatorId, Option<Signature>, Duration, mpsc::Receiver<ProposalPart>);
 
 const CHANNEL_SIZE: usize = 100;
 
@@ -126,6 +143,16 @@ pub struct SequencerConsensusContext {
     // Used to convert Transaction to ExecutableTransaction.
     chain_id: ChainId,
     cende_ambassador: Arc<dyn CendeContext>,
+    // Used to compress the transactions of the proposals this node builds.
+    proposal_compression: CompressionAlgorithm,
+    // Samples the L1 gas prices of the proposals this node builds; if unset, they are priced with
+    // temporary gas prices.
+    l1_gas_price_provider: Option<Arc<dyn L1GasPriceProvider>>,
+    // Signs the randomness messages of the proposals this node builds.
+    signer: SharedSigner,
+    // Follows the current height, for the garbage collection of the proposals of past heights.
+    height_sender: watch::Sender<BlockNumber>,
+    proposals_gc_handle: JoinHandle<()>,
 }
 
 impl SequencerConsensusContext {
@@ -137,7 +164,18 @@ impl SequencerConsensusContext {
         num_validators: u64,
         chain_id: ChainId,
         cende_ambassador: Arc<dyn CendeContext>,
+        proposal_compression: CompressionAlgorithm,
+        l1_gas_price_provider: Option<Arc<dyn L1GasPriceProvider>>,
+        signer: SharedSigner,
+        proposal_retention_config: ProposalRetentionConfig,
     ) -> Self {
+        let valid_proposals = Arc::new(Mutex::new(HeightToIdToContent::new()));
+        let (height_sender, height_receiver) = watch::channel(BlockNumber::default());
+        let proposals_gc_handle = spawn_proposals_gc(
+            Arc::clone(&valid_proposals),
+            height_receiver,
+            proposal_retention_config,
+        );
         Self {
             state_sync_client,
             batcher,
@@ -147,7 +185,7 @@ impl SequencerConsensusContext {
             validators: (0..num_validators)
                 .map(|i| ValidatorId::from(DEFAULT_VALIDATOR_ID + i))
                 .collect(),
-            valid_proposals: Arc::new(Mutex::new(HeightToIdToContent::new())),
+            valid_proposals,
             proposal_id: 0,
             current_height: None,
             current_round: 0,
@@ -155,10 +193,21 @@ impl SequencerConsensusContext {
             queued_proposals: BTreeMap::new(),
             chain_id,
             cende_ambassador,
+            proposal_compression,
+            l1_gas_price_provider,
+            signer,
+            height_sender,
+            proposals_gc_handle,
         }
     }
 }
 
+impl Drop for SequencerConsensusContext {
+    fn drop(&mut self) {
+        self.proposals_gc_handle.abort();
+    }
+}
+
 #[async_trait]
 impl ConsensusContext for SequencerConsensusContext {
     type ProposalPart = ProposalPart;
@@ -166,10 +215,15 @@ impl ConsensusContext for SequencerConsensusContext {
     #[instrument(level = "info", skip_all, fields(proposal_init))]
     async fn build_proposal(
         &mut self,
-        proposal_init: ProposalInit,
+        mut proposal_init: ProposalInit,
         timeout: Duration,
     ) -> oneshot::Receiver<ProposalContentId> {
         info!("Building proposal: timeout={timeout:?}");
+        let randomness_signature = self
+            .signer
+            .sign(randomness_message_hash(proposal_init.height))
+            .expect("Failed to sign the randomness message");
+        proposal_init.randomness_signature = Some(randomness_signature);
         let cende_write_success =
             self.cende_ambassador.write_prev_height_blob(proposal_init.height);
         // Handles interrupting an active proposal from a previous height/round
@@ -180,6 +234,8 @@ impl ConsensusContext for SequencerConsensusContext {
         let valid_proposals = Arc::clone(&self.valid_proposals);
         let proposal_id = ProposalId(self.proposal_id);
         self.proposal_id += 1;
+        let proposal_compression = self.proposal_compression;
+        let l1_gas_price_provider = self.l1_gas_price_provider.clone();
         assert!(timeout > BUILD_PROPOSAL_MARGIN);
         let (proposal_sender, proposal_receiver) = mpsc::channel(CHANNEL_SIZE);
         let stream_id = proposal_init.height.0;
@@ -199,6 +255,8 @@ impl ConsensusContext for SequencerConsensusContext {
                     valid_proposals,
                     proposal_id,
                     cende_write_success,
+                    proposal_compression,
+                    l1_gas_price_provider,
                 )
                 .await;
             }
@@ -230,7 +288,13 @@ impl ConsensusContext for SequencerConsensusContext {
                 self.queued_proposals.insert(
                     proposal_init.round,
                     (
-                        (proposal_init.height, proposal_init.proposer, timeout, content_receiver),
+                        (
+                            proposal_init.height,
+                            proposal_init.proposer,
+                            proposal_init.randomness_signature,
+                            timeout,
+                            content_receiver,
+                        ),
                         fin_sender,
                     ),
                 );
@@ -240,6 +304,7 @@ impl ConsensusContext for SequencerConsensusContext {
                 self.validate_current_round_proposal(
                     proposal_init.height,
                     proposal_init.proposer,
+                    proposal_init.randomness_signature,
                     timeout,
                     content_receiver,
                     fin_sender,
@@ -293,26 +358,24 @@ impl ConsensusContext for SequencerConsensusContext {
 
         // TODO(matan): Broadcast the decision to the network.
 
-        let proposal_id;
-        let transactions;
-        {
-            let mut proposals = self
-                .valid_proposals
-                .lock()
-                .expect("Lock on active proposals was poisoned due to a previous panic");
-            (transactions, proposal_id) =
-                proposals.get(&BlockNumber(height)).unwrap().get(&block).unwrap().clone();
-
-            proposals.retain(|&h, _| h > BlockNumber(height));
-        }
+        // The proposals of the height are dropped by the garbage collection once it falls out of
+        // the retention window.
+        let (transactions, proposal_id) = self
+            .valid_proposals
+            .lock()
+            .expect("Lock on active proposals was poisoned due to a previous panic")
+            .get(&BlockNumber(height))
+            .unwrap()
+            .get(&block)
+            .unwrap()
+            .clone();
         // TODO(dvir): return from the batcher's 'decision_reached' function the relevant data to
         // build a blob.
-        let state_diff = self
+        let DecisionReachedResponse { state_diff, starknet_version, .. } = self
             .batcher
             .decision_reached(DecisionReachedInput { proposal_id })
             .await
-            .expect("Failed to get state diff.")
-            .state_diff;
+            .expect("Failed to get state diff.");
         // TODO(dvir): pass here real `BlobParameters` info.
         // TODO(dvir): when passing here the correct `BlobParameters`, also test that
         // `prepare_blob_for_next_height` is called with the correct parameters.
@@ -320,14 +383,31 @@ impl ConsensusContext for SequencerConsensusContext {
 
         let transaction_hashes =
             transactions.iter().map(|tx| tx.tx_hash()).collect::<Vec<TransactionHash>>();
+        let consumed_l1_message_nonces = transactions
+            .iter()
+            .filter_map(|tx| match tx {
+                ExecutableTransaction::L1Handler(l1_handler_tx) => Some(l1_handler_tx.tx.nonce),
+                ExecutableTransaction::Account(_) => None,
+            })
+            .collect();
         // TODO(Asmaa/Eitan): update with the correct values.
-        let block_header_without_hash =
-            BlockHeaderWithoutHash { block_number: BlockNumber(height), ..Default::default() };
-        let sync_block = SyncBlock { state_diff, transaction_hashes, block_header_without_hash };
+        let block_number = BlockNumber(height);
+        let block_header_without_hash = BlockHeaderWithoutHash {
+            block_number,
+            // The version the batcher executed the block with.
+            starknet_version,
+            ..Default::default()
+        };
+        let sync_block = SyncBlock {
+            state_diff,
+            transaction_hashes,
+            block_header_without_hash,
+            consumed_l1_message_nonces,
+        };
         let state_sync_client = self.state_sync_client.clone();
         // `add_new_block` returns immediately, it doesn't wait for sync to fully process the block.
         state_sync_client
-            .add_new_block(BlockNumber(height), sync_block)
+            .add_new_block(block_number, sync_block)
             .await
             .expect("Failed to add new block.");
 
@@ -346,6 +426,7 @@ impl ConsensusContext for SequencerConsensusContext {
     async fn set_height_and_round(&mut self, height: BlockNumber, round: Round) {
         if self.current_height.map(|h| height > h).unwrap_or(true) {
             self.current_height = Some(height);
+            self.height_sender.send_replace(height);
             assert_eq!(round, 0);
             self.current_round = round;
             self.interrupt_active_proposal().await;
@@ -382,10 +463,20 @@ impl ConsensusContext for SequencerConsensusContext {
             }
         }
         // Validate the proposal for the current round if exists.
-        let Some(((height, validator, timeout, content), fin_sender)) = to_process else {
+        let Some(((height, validator, randomness_signature, timeout, content), fin_sender)) =
+            to_process
+        else {
             return;
         };
-        self.validate_current_round_proposal(height, validator, timeout, content, fin_sender).await;
+        self.validate_current_round_proposal(
+            height,
+            validator,
+            randomness_signature,
+            timeout,
+            content,
+            fin_sender,
+        )
+        .await;
     }
 }
 
@@ -395,6 +486,7 @@ impl SequencerConsensusContext {
         &mut self,
         height: BlockNumber,
         proposer: ValidatorId,
+        randomness_signature: Option<Signature>,
         timeout: Duration,
         content_receiver: mpsc::Receiver<ProposalPart>,
         fin_sender: oneshot::Sender<(ProposalContentId, ProposalFin)>,
@@ -415,6 +507,7 @@ impl SequencerConsensusContext {
                 batcher.as_ref(),
                 height,
                 proposer,
+                randomness_signature,
                 timeout,
                 valid_proposals,
                 content_receiver,
@@ -445,8 +538,17 @@ async fn build_proposal(
     valid_proposals: Arc<Mutex<HeightToIdToContent>>,
     proposal_id: ProposalId,
     cende_write_success: oneshot::Receiver<bool>,
+    proposal_compression: CompressionAlgorithm,
+    l1_gas_price_provider: Option<Arc<dyn L1GasPriceProvider>>,
 ) {
-    initialize_build(proposal_id, &proposal_init, timeout, batcher.as_ref()).await;
+    initialize_build(
+        proposal_id,
+        &proposal_init,
+        timeout,
+        batcher.as_ref(),
+        l1_gas_price_provider.as_deref(),
+    )
+    .await;
     debug!("Broadcasting proposal init: {proposal_init:?}");
     proposal_sender
         .send(ProposalPart::Init(proposal_init))
@@ -459,6 +561,7 @@ async fn build_proposal(
         batcher.as_ref(),
         proposal_sender,
         cende_write_success,
+        proposal_compression,
     )
     .await
     else {
@@ -483,7 +586,12 @@ async fn initialize_build(
     proposal_init: &ProposalInit,
     timeout: Duration,
     batcher: &dyn BatcherClient,
+    l1_gas_price_provider: Option<&dyn L1GasPriceProvider>,
 ) {
+    let price_sample = match l1_gas_price_provider {
+        Some(l1_gas_price_provider) => l1_gas_price_provider.latest_price_sample().await,
+        None => None,
+    };
     let batcher_timeout = chrono::Duration::from_std(timeout - BUILD_PROPOSAL_MARGIN)
         .expect("Can't convert timeout to chrono::Duration");
     let now = chrono::Utc::now();
@@ -499,13 +607,16 @@ async fn initialize_build(
         // TODO(Dan, Matan): Fill block info.
         block_info: BlockInfo {
             block_number: proposal_init.height,
-            gas_prices: TEMPORARY_GAS_PRICES,
+            gas_prices: price_sample.map_or(TEMPORARY_GAS_PRICES, sampled_gas_prices),
             block_timestamp: BlockTimestamp(
                 now.timestamp().try_into().expect("Failed to convert timestamp"),
             ),
             use_kzg_da: true,
             sequencer_address: proposal_init.proposer,
         },
+        // Lets the batcher detect stale L1 gas prices; the temporary ones are not sampled.
+        l1_gas_prices_timestamp: price_sample.map(|price_sample| price_sample.timestamp),
+        randomness_signature: proposal_init.randomness_signature,
     };
     // TODO: Should we be returning an error?
     // I think this implies defining an error type in this crate and moving the trait definition
@@ -514,6 +625,21 @@ async fn initialize_build(
     batcher.propose_block(build_proposal_input).await.expect("Failed to initiate proposal build");
 }
 
+// Prices a block with the sampled ETH L1 gas prices.
+// TODO: Convert the sampled prices to STRK once an ETH/STRK rate source exists.
+fn sampled_gas_prices(price_sample: PriceSample) -> GasPrices {
+    let wei_price =
+        |price: u128| NonzeroGasPrice::new(GasPrice(price)).unwrap_or(NonzeroGasPrice::MIN);
+    GasPrices {
+        eth_gas_prices: GasPriceVector {
+            l1_gas_price: wei_price(price_sample.base_fee_per_gas),
+            l1_data_gas_price: wei_price(price_sample.blob_base_fee),
+            ..TEMPORARY_GAS_PRICES.eth_gas_prices
+        },
+        ..TEMPORARY_GAS_PRICES
+    }
+}
+
 // 1. Receive chunks of content from the batcher.
 // 2. Forward these to the stream handler to be streamed out to the network.
 // 3. Once finished, receive the commitment from the batcher.
@@ -523,6 +649,7 @@ async fn get_proposal_content(
     batcher: &dyn BatcherClient,
     mut proposal_sender: mpsc::Sender<ProposalPart>,
     mut cende_write_success: oneshot::Receiver<bool>,
+    proposal_compression: CompressionAlgorithm,
 ) -> Option<(ProposalContentId, Vec<ExecutableTransaction>)> {
     let mut content = Vec::new();
     loop {
@@ -549,7 +676,10 @@ async fn get_proposal_content(
                 trace!("Broadcasting proposal content: {transactions:?}");
 
                 proposal_sender
-                    .send(ProposalPart::Transactions(TransactionBatch { transactions }))
+                    .send(transactions_part(
+                        TransactionBatch { transactions },
+                        proposal_compression,
+                    ))
                     .await
                     .expect("Failed to broadcast proposal content");
             }
@@ -591,6 +721,22 @@ async fn get_proposal_content(
     }
 }
 
+// Compresses the batch unless compression is disabled, and records the bandwidth it saved.
+fn transactions_part(batch: TransactionBatch, algorithm: CompressionAlgorithm) -> ProposalPart {
+    if algorithm == CompressionAlgorithm::Uncompressed {
+        return ProposalPart::Transactions(batch);
+    }
+    let compressed = CompressedTransactionBatch::compress(batch, algorithm);
+    let compressed_size = u64::try_from(compressed.data.len()).expect("usize should fit in u64");
+    trace!(
+        "Compressed proposal content with {algorithm:?} from {} to {compressed_size} bytes",
+        compressed.uncompressed_size
+    );
+    metrics::counter!(PAPYRUS_CONSENSUS_PROPOSAL_UNCOMPRESSED_BYTES, compressed.uncompressed_size);
+    metrics::counter!(PAPYRUS_CONSENSUS_PROPOSAL_COMPRESSED_BYTES, compressed_size);
+    ProposalPart::CompressedTransactions(compressed)
+}
+
 // TODO(Arni): Remove the clippy when switch to ProposalInit.
 #[allow(clippy::too_many_arguments)]
 async fn validate_proposal(
@@ -599,13 +745,18 @@ async fn validate_proposal(
     batcher: &dyn BatcherClient,
     height: BlockNumber,
     proposer: ValidatorId,
+    randomness_signature: Option<Signature>,
     timeout: Duration,
     valid_proposals: Arc<Mutex<HeightToIdToContent>>,
     mut content_receiver: mpsc::Receiver<ProposalPart>,
     fin_sender: oneshot::Sender<(ProposalContentId, ProposalFin)>,
     cancel_token: CancellationToken,
 ) {
-    initiate_validation(batcher, proposal_id, height, proposer, timeout).await;
+    if !initiate_validation(batcher, proposal_id, height, proposer, randomness_signature, timeout)
+        .await
+    {
+        return;
+    }
 
     let mut content = Vec::new();
     let (built_block, received_fin) = loop {
@@ -653,13 +804,17 @@ async fn validate_proposal(
     }
 }
 
+// Returns whether the batcher started validating the proposal; proposals the batcher rejects
+// upfront, e.g., for their L2 gas price or a missing randomness signature, are invalid.
+// TODO: Verify the randomness signature against the proposer's public key once it is known.
 async fn initiate_validation(
     batcher: &dyn BatcherClient,
     proposal_id: ProposalId,
     height: BlockNumber,
     proposer: ValidatorId,
+    randomness_signature: Option<Signature>,
     timeout: Duration,
-) {
+) -> bool {
     // Initiate the validation.
     let chrono_timeout =
         chrono::Duration::from_std(timeout).expect("Can't convert timeout to chrono::Duration");
@@ -682,9 +837,20 @@ async fn initiate_validation(
             use_kzg_da: true,
             sequencer_address: proposer,
         },
+        randomness_signature,
     };
     debug!("Initiating validate proposal: input={input:?}");
-    batcher.validate_block(input).await.expect("Failed to initiate proposal validation");
+    match batcher.validate_block(input).await {
+        Ok(()) => true,
+        Err(BatcherClientError::BatcherError(
+            err @ (BatcherError::InvalidL2GasPrice { .. }
+            | BatcherError::MissingRandomnessSignature),
+        )) => {
+            warn!("Rejecting proposal {proposal_id:?}: {err}");
+            false
+        }
+        Err(err) => panic!("Failed to initiate proposal validation: {err:?}"),
+    }
 }
 
 // Handles receiving a proposal from another node without blocking consensus:
@@ -698,6 +864,17 @@ async fn handle_proposal_part(
     content: &mut Vec<ExecutableTransaction>,
     chain_id: ChainId,
 ) -> HandledProposalPart {
+    let proposal_part = match proposal_part {
+        Some(ProposalPart::CompressedTransactions(compressed)) => match compressed.decompress() {
+            Ok(batch) => Some(ProposalPart::Transactions(batch)),
+            Err(err) => {
+                return HandledProposalPart::Failed(format!(
+                    "Failed to decompress proposal content: {err}"
+                ));
+            }
+        },
+        proposal_part => proposal_part,
+    };
     match proposal_part {
         None => HandledProposalPart::Failed("Failed to receive proposal content".to_string()),
         Some(ProposalPart::Transactions(TransactionBatch { transactions: txs })) => {
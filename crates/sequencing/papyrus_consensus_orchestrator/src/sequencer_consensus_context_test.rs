@@ -2,9 +2,11 @@ use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use std::vec;
 
+use blockifier::blockifier::block::randomness_message_hash;
 use futures::channel::{mpsc, oneshot};
 use futures::{FutureExt, SinkExt};
 use lazy_static::lazy_static;
+use papyrus_base_layer::PriceSample;
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::ConsensusContext;
 use papyrus_network::network_manager::test_utils::{
@@ -14,6 +16,7 @@ use papyrus_network::network_manager::test_utils::{
 };
 use papyrus_network::network_manager::BroadcastTopicChannels;
 use papyrus_protobuf::consensus::{
+    CompressionAlgorithm,
     ProposalFin,
     ProposalInit,
     ProposalPart,
@@ -21,7 +24,8 @@ use papyrus_protobuf::consensus::{
     TransactionBatch,
     Vote,
 };
-use starknet_api::block::{BlockHash, BlockNumber};
+use rstest::rstest;
+use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::core::{ChainId, Nonce, StateDiffCommitment};
 use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
 use starknet_api::felt;
@@ -41,17 +45,21 @@ use starknet_batcher_types::batcher_types::{
     ValidateBlockInput,
 };
 use starknet_batcher_types::communication::MockBatcherClient;
+use starknet_sequencer_infra::signer::{LocalKeySigner, SignerBackend, SignerConfig};
 use starknet_state_sync_types::communication::MockStateSyncClient;
 use starknet_types_core::felt::Felt;
 
 use crate::cende::MockCendeContext;
-use crate::sequencer_consensus_context::SequencerConsensusContext;
+use crate::l1_gas_price_provider::{L1GasPriceProvider, MockL1GasPriceProvider};
+use crate::proposal_retention::ProposalRetentionConfig;
+use crate::sequencer_consensus_context::{transactions_part, SequencerConsensusContext};
 
 const TIMEOUT: Duration = Duration::from_millis(1200);
 const CHANNEL_SIZE: usize = 5000;
 const NUM_VALIDATORS: u64 = 4;
 const STATE_DIFF_COMMITMENT: StateDiffCommitment = StateDiffCommitment(PoseidonHash(Felt::ZERO));
 const CHAIN_ID: ChainId = ChainId::Mainnet;
+const SIGNER_PRIVATE_KEY: Felt = Felt::ONE;
 
 lazy_static! {
     static ref TX_BATCH: Vec<Transaction> = (0..3).map(generate_invoke_tx).collect();
@@ -72,9 +80,21 @@ struct NetworkDependencies {
     _new_proposal_network: BroadcastNetworkMock<StreamMessage<ProposalPart>>,
 }
 
+fn signer() -> LocalKeySigner {
+    LocalKeySigner::new(&SignerConfig { private_key: SIGNER_PRIVATE_KEY })
+}
+
 fn setup(
     batcher: MockBatcherClient,
     cende_ambassador: MockCendeContext,
+) -> (SequencerConsensusContext, NetworkDependencies) {
+    setup_with_l1_gas_price_provider(batcher, cende_ambassador, None)
+}
+
+fn setup_with_l1_gas_price_provider(
+    batcher: MockBatcherClient,
+    cende_ambassador: MockCendeContext,
+    l1_gas_price_provider: Option<Arc<dyn L1GasPriceProvider>>,
 ) -> (SequencerConsensusContext, NetworkDependencies) {
     let TestSubscriberChannels { mock_network: mock_proposal_stream_network, subscriber_channels } =
         mock_register_broadcast_topic().expect("Failed to create mock network");
@@ -99,6 +119,10 @@ fn setup(
         NUM_VALIDATORS,
         CHAIN_ID,
         Arc::new(cende_ambassador),
+        CompressionAlgorithm::Uncompressed,
+        l1_gas_price_provider,
+        Arc::new(signer()),
+        ProposalRetentionConfig::default(),
     );
 
     let network_dependencies = NetworkDependencies {
@@ -118,6 +142,8 @@ async fn build_proposal_setup(
     let proposal_id_clone = Arc::clone(&proposal_id);
     batcher.expect_propose_block().returning(move |input: ProposeBlockInput| {
         proposal_id_clone.set(input.proposal_id).unwrap();
+        let expected_signature = signer().sign(randomness_message_hash(BlockNumber(0))).unwrap();
+        assert_eq!(input.randomness_signature, Some(expected_signature));
         Ok(())
     });
     batcher
@@ -159,8 +185,12 @@ fn success_cende_ammbassador() -> MockCendeContext {
     mock_cende
 }
 
+#[rstest]
+#[case::uncompressed(CompressionAlgorithm::Uncompressed)]
+#[case::zstd(CompressionAlgorithm::Zstd)]
+#[case::gzip(CompressionAlgorithm::Gzip)]
 #[tokio::test]
-async fn validate_proposal_success() {
+async fn validate_proposal_success(#[case] compression: CompressionAlgorithm) {
     let mut batcher = MockBatcherClient::new();
     let proposal_id: Arc<OnceLock<ProposalId>> = Arc::new(OnceLock::new());
     let proposal_id_clone = Arc::clone(&proposal_id);
@@ -202,7 +232,7 @@ async fn validate_proposal_success() {
 
     let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
     content_sender
-        .send(ProposalPart::Transactions(TransactionBatch { transactions: TX_BATCH.to_vec() }))
+        .send(transactions_part(TransactionBatch { transactions: TX_BATCH.to_vec() }, compression))
         .await
         .unwrap();
     content_sender
@@ -467,3 +497,39 @@ async fn build_proposal_cende_incomplete() {
     assert_eq!(fin_receiver.await, Err(oneshot::Canceled));
     drop(sender);
 }
+
+#[tokio::test]
+async fn build_proposal_with_sampled_l1_gas_prices() {
+    const PRICE_SAMPLE: PriceSample =
+        PriceSample { timestamp: 1234, base_fee_per_gas: 10, blob_base_fee: 20 };
+    let mut l1_gas_price_provider = MockL1GasPriceProvider::new();
+    l1_gas_price_provider.expect_latest_price_sample().times(1).return_const(Some(PRICE_SAMPLE));
+
+    let mut batcher = MockBatcherClient::new();
+    batcher
+        .expect_start_height()
+        .withf(|input| input.height == BlockNumber(0))
+        .return_once(|_| Ok(()));
+    batcher.expect_propose_block().times(1).returning(|input: ProposeBlockInput| {
+        assert_eq!(input.l1_gas_prices_timestamp, Some(PRICE_SAMPLE.timestamp));
+        let eth_gas_prices = input.block_info.gas_prices.eth_gas_prices;
+        assert_eq!(eth_gas_prices.l1_gas_price.get(), GasPrice(PRICE_SAMPLE.base_fee_per_gas));
+        assert_eq!(eth_gas_prices.l1_data_gas_price.get(), GasPrice(PRICE_SAMPLE.blob_base_fee));
+        Ok(())
+    });
+    batcher.expect_get_proposal_content().times(1).returning(|_| {
+        Ok(GetProposalContentResponse {
+            content: GetProposalContent::Finished(ProposalCommitment {
+                state_diff_commitment: STATE_DIFF_COMMITMENT,
+            }),
+        })
+    });
+    let (mut context, _network) = setup_with_l1_gas_price_provider(
+        batcher,
+        success_cende_ammbassador(),
+        Some(Arc::new(l1_gas_price_provider)),
+    );
+
+    let fin_receiver = context.build_proposal(ProposalInit::default(), TIMEOUT).await;
+    assert_eq!(fin_receiver.await.unwrap().0, STATE_DIFF_COMMITMENT.0.0);
+}
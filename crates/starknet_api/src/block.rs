@@ -2,6 +2,8 @@
 #[path = "block_test.rs"]
 mod block_test;
 
+use std::collections::BTreeMap;
+
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
@@ -98,7 +100,8 @@ starknet_version_enum! {
     (V0_13_2_1, 0, 13, 2, 1),
     (V0_13_3, 0, 13, 3),
     (V0_13_4, 0, 13, 4),
-    V0_13_4
+    (V0_13_5, 0, 13, 5),
+    V0_13_5
 }
 
 impl Default for StarknetVersion {
@@ -165,6 +168,166 @@ impl<'de> Deserialize<'de> for StarknetVersion {
     }
 }
 
+/// The point from which a scheduled Starknet version is activated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionActivation {
+    /// Activated from the given block height onwards.
+    Height(BlockNumber),
+    /// Activated from the first block whose timestamp is at least the given one.
+    Timestamp(BlockTimestamp),
+}
+
+impl std::fmt::Display for VersionActivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Height(block_number) => write!(f, "block {block_number}"),
+            Self::Timestamp(timestamp) => write!(f, "timestamp {}", timestamp.0),
+        }
+    }
+}
+
+/// A schedule of Starknet protocol upgrades, each activated from a given block height or block
+/// timestamp onwards. A block is executed with the highest version activated by its height or by
+/// its timestamp; the schedule must set the version of block 0, so every block has a version.
+///
+/// Timestamp activations rely on the nodes agreeing on the block timestamp, as they must for
+/// executing the block anyway.
+///
+/// Serialized as a space-separated list of `<block_number>:<starknet_version>` and
+/// `t<unix_timestamp>:<starknet_version>` pairs, e.g. `"0:0.13.3 1000:0.13.4 t1767225600:0.13.5"`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct StarknetVersionSchedule {
+    by_height: BTreeMap<BlockNumber, StarknetVersion>,
+    by_timestamp: BTreeMap<BlockTimestamp, StarknetVersion>,
+}
+
+impl StarknetVersionSchedule {
+    /// Creates a schedule from (activation, version) pairs. A version must be scheduled for block
+    /// 0, and versions must strictly increase with the activation height, and with the activation
+    /// timestamp, above the version of block 0.
+    pub fn new(
+        activations: impl IntoIterator<Item = (VersionActivation, StarknetVersion)>,
+    ) -> Result<Self, StarknetApiError> {
+        let (mut by_height, mut by_timestamp) = (BTreeMap::new(), BTreeMap::new());
+        for (activation, version) in activations {
+            let is_duplicate = match activation {
+                VersionActivation::Height(block_number) => {
+                    by_height.insert(block_number, version).is_some()
+                }
+                VersionActivation::Timestamp(timestamp) => {
+                    by_timestamp.insert(timestamp, version).is_some()
+                }
+            };
+            if is_duplicate {
+                return Err(StarknetApiError::InvalidStarknetVersionSchedule(format!(
+                    "Multiple versions are scheduled for {activation}."
+                )));
+            }
+        }
+        let Some(&genesis_version) = by_height.get(&BlockNumber(0)) else {
+            return Err(StarknetApiError::InvalidStarknetVersionSchedule(
+                "No version is scheduled for block 0.".to_string(),
+            ));
+        };
+        let height_upgrades = by_height
+            .iter()
+            .map(|(block_number, version)| (VersionActivation::Height(*block_number), *version));
+        let timestamp_upgrades = by_timestamp
+            .iter()
+            .map(|(timestamp, version)| (VersionActivation::Timestamp(*timestamp), *version));
+        verify_upgrades(height_upgrades)?;
+        verify_upgrades(
+            [(VersionActivation::Height(BlockNumber(0)), genesis_version)]
+                .into_iter()
+                .chain(timestamp_upgrades),
+        )?;
+        Ok(Self { by_height, by_timestamp })
+    }
+
+    /// Returns the version of the block with the given height and timestamp: the highest version
+    /// activated at or before either.
+    pub fn version_at(
+        &self,
+        block_number: BlockNumber,
+        block_timestamp: BlockTimestamp,
+    ) -> StarknetVersion {
+        let version_by_height = self
+            .by_height
+            .range(..=block_number)
+            .next_back()
+            .map(|(_, version)| *version)
+            .expect("A version is scheduled for block 0.");
+        let version_by_timestamp =
+            self.by_timestamp.range(..=block_timestamp).next_back().map(|(_, version)| *version);
+        version_by_timestamp.map_or(version_by_height, |version| version.max(version_by_height))
+    }
+}
+
+impl Default for StarknetVersionSchedule {
+    fn default() -> Self {
+        Self {
+            by_height: BTreeMap::from([(BlockNumber(0), StarknetVersion::LATEST)]),
+            by_timestamp: BTreeMap::new(),
+        }
+    }
+}
+
+// Verifies that each of the given upgrades, ordered by activation, upgrades its predecessor.
+fn verify_upgrades(
+    upgrades: impl Iterator<Item = (VersionActivation, StarknetVersion)>,
+) -> Result<(), StarknetApiError> {
+    if let Some(((_, previous), (activation, version))) =
+        upgrades.tuple_windows().find(|((_, previous), (_, version))| previous >= version)
+    {
+        return Err(StarknetApiError::InvalidStarknetVersionSchedule(format!(
+            "Version {version} scheduled for {activation} does not upgrade the previously \
+             scheduled version {previous}."
+        )));
+    }
+    Ok(())
+}
+
+impl From<StarknetVersionSchedule> for String {
+    fn from(schedule: StarknetVersionSchedule) -> Self {
+        let height_activations = schedule
+            .by_height
+            .iter()
+            .map(|(block_number, version)| format!("{}:{version}", block_number.0));
+        let timestamp_activations = schedule
+            .by_timestamp
+            .iter()
+            .map(|(timestamp, version)| format!("t{}:{version}", timestamp.0));
+        height_activations.chain(timestamp_activations).join(" ")
+    }
+}
+
+impl TryFrom<String> for StarknetVersionSchedule {
+    type Error = StarknetApiError;
+
+    fn try_from(schedule: String) -> Result<Self, StarknetApiError> {
+        let activations: Vec<(VersionActivation, StarknetVersion)> = schedule
+            .split_whitespace()
+            .map(|activation| -> Result<_, StarknetApiError> {
+                let (activation, version) = activation.split_once(':').ok_or_else(|| {
+                    StarknetApiError::InvalidStarknetVersionSchedule(format!(
+                        "Expected <block_number>:<starknet_version> or \
+                         t<unix_timestamp>:<starknet_version>, got {activation}."
+                    ))
+                })?;
+                let activation = match activation.strip_prefix('t') {
+                    Some(timestamp) => {
+                        VersionActivation::Timestamp(BlockTimestamp(timestamp.parse()?))
+                    }
+                    None => VersionActivation::Height(BlockNumber(activation.parse()?)),
+                };
+                Ok((activation, StarknetVersion::try_from(version)?))
+            })
+            .try_collect()?;
+        Self::new(activations)
+    }
+}
+
 /// The header of a [Block](`crate::block::Block`).
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct BlockHeader {
@@ -447,7 +610,7 @@ pub struct GasPriceVector {
     pub l2_gas_price: NonzeroGasPrice,
 }
 
-#[derive(Clone, Copy, Hash, EnumIter, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Hash, EnumIter, Eq, PartialEq)]
 pub enum FeeType {
     Strk,
     Eth,
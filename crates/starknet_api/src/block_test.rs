@@ -1,8 +1,9 @@
+use rstest::rstest;
 use serde_json::json;
 use strum::IntoEnumIterator;
 
-use super::{verify_block_signature, StarknetVersion};
-use crate::block::{BlockHash, BlockNumber, BlockSignature};
+use super::{verify_block_signature, StarknetVersion, StarknetVersionSchedule, VersionActivation};
+use crate::block::{BlockHash, BlockNumber, BlockSignature, BlockTimestamp};
 use crate::core::{GlobalRoot, SequencerPublicKey};
 use crate::crypto::utils::{PublicKey, Signature};
 use crate::felt;
@@ -80,3 +81,68 @@ fn test_latest_version() {
         assert!(version <= latest);
     }
 }
+
+#[test]
+fn test_version_schedule_serde() {
+    let schedule = StarknetVersionSchedule::try_from(
+        "t1767225600:0.13.4 2000:0.13.3 0:0.13.1 1000:0.13.2".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        schedule,
+        StarknetVersionSchedule::new([
+            (VersionActivation::Height(BlockNumber(0)), StarknetVersion::V0_13_1),
+            (VersionActivation::Height(BlockNumber(1000)), StarknetVersion::V0_13_2),
+            (VersionActivation::Height(BlockNumber(2000)), StarknetVersion::V0_13_3),
+            (VersionActivation::Timestamp(BlockTimestamp(1767225600)), StarknetVersion::V0_13_4),
+        ])
+        .unwrap()
+    );
+    assert_eq!(
+        String::from(schedule.clone()),
+        "0:0.13.1 1000:0.13.2 2000:0.13.3 t1767225600:0.13.4"
+    );
+    assert_eq!(
+        serde_json::from_value::<StarknetVersionSchedule>(json!(schedule)).unwrap(),
+        schedule
+    );
+    assert_eq!(
+        String::from(StarknetVersionSchedule::default()),
+        format!("0:{}", StarknetVersion::LATEST)
+    );
+}
+
+#[test]
+fn test_version_schedule_version_at() {
+    let schedule = StarknetVersionSchedule::new([
+        (VersionActivation::Height(BlockNumber(0)), StarknetVersion::V0_13_2),
+        (VersionActivation::Height(BlockNumber(1000)), StarknetVersion::V0_13_3),
+        (VersionActivation::Timestamp(BlockTimestamp(500)), StarknetVersion::V0_13_4),
+    ])
+    .unwrap();
+    let version_at = |block_number, timestamp| {
+        schedule.version_at(BlockNumber(block_number), BlockTimestamp(timestamp))
+    };
+
+    assert_eq!(version_at(0, 0), StarknetVersion::V0_13_2);
+    assert_eq!(version_at(999, 499), StarknetVersion::V0_13_2);
+    assert_eq!(version_at(1000, 499), StarknetVersion::V0_13_3);
+    // The highest activated version applies, whether activated by height or by timestamp.
+    assert_eq!(version_at(999, 500), StarknetVersion::V0_13_4);
+    assert_eq!(version_at(1000, 500), StarknetVersion::V0_13_4);
+}
+
+#[rstest]
+#[case::downgrade("0:0.13.2 1000:0.13.4 2000:0.13.3")]
+#[case::same_version("0:0.13.2 1000:0.13.4 2000:0.13.4")]
+#[case::timestamp_downgrade("0:0.13.2 t1000:0.13.4 t2000:0.13.3")]
+#[case::timestamp_without_upgrade("0:0.13.4 t1000:0.13.3")]
+#[case::duplicate_height("0:0.13.2 0:0.13.3")]
+#[case::missing_genesis_version("1000:0.13.4")]
+#[case::only_timestamps("t0:0.13.4")]
+#[case::empty("")]
+#[case::missing_height("0.13.4")]
+#[case::unknown_version("0:0.1.0")]
+fn test_invalid_version_schedule(#[case] schedule: &str) {
+    assert!(StarknetVersionSchedule::try_from(schedule.to_string()).is_err());
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+#[path = "error_context_test.rs"]
+mod error_context_test;
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::block::BlockNumber;
+use crate::transaction::TransactionHash;
+
+/// Identifies where an error occurred: the component that reported it and, when known, the block
+/// and the transaction it was processing. Errors surfaced in logs and client responses carry it so
+/// that the failing transaction can be found without correlating logs by hand.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ErrorContext {
+    pub component: String,
+    pub block_number: Option<BlockNumber>,
+    pub tx_hash: Option<TransactionHash>,
+}
+
+impl ErrorContext {
+    pub fn new(component: impl Into<String>) -> Self {
+        Self { component: component.into(), ..Default::default() }
+    }
+
+    pub fn with_block_number(self, block_number: BlockNumber) -> Self {
+        Self { block_number: Some(block_number), ..self }
+    }
+
+    pub fn with_tx_hash(self, tx_hash: TransactionHash) -> Self {
+        Self { tx_hash: Some(tx_hash), ..self }
+    }
+
+    /// Attaches the context to the given error.
+    pub fn wrap<E>(self, error: E) -> ContextualError<E> {
+        ContextualError { context: self, error }
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.component)?;
+        if let Some(block_number) = self.block_number {
+            write!(f, ", block {block_number}")?;
+        }
+        if let Some(tx_hash) = self.tx_hash {
+            write!(f, ", transaction {tx_hash}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error with the context it occurred in.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("[{context}] {error}")]
+pub struct ContextualError<E> {
+    pub context: ErrorContext,
+    #[source]
+    pub error: E,
+}
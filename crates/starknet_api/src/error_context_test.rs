@@ -0,0 +1,15 @@
+use crate::block::BlockNumber;
+use crate::error_context::ErrorContext;
+use crate::tx_hash;
+
+#[test]
+fn contextual_error_display() {
+    let context = ErrorContext::new("batcher");
+    assert_eq!(context.wrap("Out of gas.").to_string(), "[batcher] Out of gas.");
+
+    let context = context.with_block_number(BlockNumber(7)).with_tx_hash(tx_hash!(0x12_u8));
+    assert_eq!(
+        context.wrap("Out of gas.").to_string(),
+        "[batcher, block 7, transaction 0x12] Out of gas."
+    );
+}
@@ -11,11 +11,16 @@ pub type StarkHash = Felt;
 )]
 pub struct PoseidonHash(pub Felt);
 
-/// Computes the first 250 bits of the Keccak256 hash, in order to fit into a field element.
-pub fn starknet_keccak_hash(input: &[u8]) -> Felt {
+/// Computes the Keccak256 hash of the input.
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
     let mut keccak = Keccak256::default();
     keccak.update(input);
-    let mut hashed_bytes: [u8; 32] = keccak.finalize().into();
+    keccak.finalize().into()
+}
+
+/// Computes the first 250 bits of the Keccak256 hash, in order to fit into a field element.
+pub fn starknet_keccak_hash(input: &[u8]) -> Felt {
+    let mut hashed_bytes = keccak256(input);
     hashed_bytes[0] &= 0b00000011_u8; // Discard the six MSBs.
     Felt::from_bytes_be(&hashed_bytes)
 }
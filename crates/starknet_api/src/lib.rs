@@ -10,6 +10,7 @@ pub mod core;
 pub mod crypto;
 pub mod data_availability;
 pub mod deprecated_contract_class;
+pub mod error_context;
 pub mod executable_transaction;
 pub mod execution_resources;
 pub mod execution_utils;
@@ -50,6 +51,8 @@ pub enum StarknetApiError {
     InvalidResourceMappingInitializer(String),
     #[error("Invalid Starknet version: {0:?}")]
     InvalidStarknetVersion(Vec<u8>),
+    #[error("Invalid Starknet version schedule: {0}")]
+    InvalidStarknetVersionSchedule(String),
     #[error("NonzeroGasPrice cannot be zero.")]
     ZeroGasPrice,
     #[error(
@@ -1,5 +1,7 @@
+use std::collections::BTreeSet;
 use std::sync::LazyLock;
 
+use itertools::Itertools;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
@@ -891,6 +893,40 @@ pub fn signed_tx_version(
     }
 }
 
+/// A set of transaction versions, e.g. the versions a chain does not accept.
+/// Serialized as a space-separated list of version numbers, e.g. `"0 1"`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TransactionVersions(BTreeSet<TransactionVersion>);
+
+impl TransactionVersions {
+    pub fn new(versions: impl IntoIterator<Item = TransactionVersion>) -> Self {
+        Self(versions.into_iter().collect())
+    }
+
+    pub fn contains(&self, version: &TransactionVersion) -> bool {
+        self.0.contains(version)
+    }
+}
+
+impl From<TransactionVersions> for String {
+    fn from(versions: TransactionVersions) -> Self {
+        versions.0.iter().map(|version| version.0.to_biguint().to_string()).join(" ")
+    }
+}
+
+impl TryFrom<String> for TransactionVersions {
+    type Error = StarknetApiError;
+
+    fn try_from(versions: String) -> Result<Self, StarknetApiError> {
+        let versions: Vec<TransactionVersion> = versions
+            .split_whitespace()
+            .map(|version| Ok(TransactionVersion(Felt::from(version.parse::<u64>()?))))
+            .collect::<Result<_, StarknetApiError>>()?;
+        Ok(Self::new(versions))
+    }
+}
+
 /// An L1 to L2 message.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct MessageToL2 {
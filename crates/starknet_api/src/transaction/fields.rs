@@ -141,6 +141,15 @@ impl From<Tip> for Felt {
     }
 }
 
+impl Tip {
+    /// Returns the tip paid per unit of L2 gas at the given L2 gas price: the tip, capped so that
+    /// the price and the tip together do not exceed the max L2 gas price.
+    pub fn effective(self, max_l2_gas_price: GasPrice, l2_gas_price: GasPrice) -> Self {
+        let margin = max_l2_gas_price.0.saturating_sub(l2_gas_price.0);
+        Self(u64::try_from(margin).map_or(self.0, |margin| self.0.min(margin)))
+    }
+}
+
 /// Execution resource.
 #[derive(
     Clone,
@@ -298,6 +307,18 @@ impl ValidResourceBounds {
         }
     }
 
+    /// Returns the tip the transaction pays per unit of L2 gas at the given L2 gas price; see
+    /// [Tip::effective]. Transactions with L1 gas bounds only are not charged for L2 gas, hence
+    /// pay no tip.
+    pub fn effective_tip(&self, tip: Tip, l2_gas_price: GasPrice) -> Tip {
+        match self {
+            Self::L1Gas(_) => Tip::default(),
+            Self::AllResources(AllResourceBounds { l2_gas, .. }) => {
+                tip.effective(l2_gas.max_price_per_unit, l2_gas_price)
+            }
+        }
+    }
+
     pub fn get_gas_vector_computation_mode(&self) -> GasVectorComputationMode {
         match self {
             Self::AllResources(_) => GasVectorComputationMode::All,
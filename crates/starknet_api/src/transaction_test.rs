@@ -11,7 +11,7 @@ use crate::executable_transaction::{
 };
 use crate::execution_resources::GasAmount;
 use crate::test_utils::{read_json_file, TransactionTestData};
-use crate::transaction::Fee;
+use crate::transaction::{Fee, TransactionVersion, TransactionVersions};
 
 const CHAIN_ID: ChainId = ChainId::Mainnet;
 
@@ -90,3 +90,16 @@ fn test_l1_handler_executable_transaction_conversion(
 
     verify_transaction_conversion(&transaction_data.transaction, expected_executable_tx);
 }
+
+#[test]
+fn transaction_versions_serialization() {
+    let versions = TransactionVersions::new([TransactionVersion::ONE, TransactionVersion::ZERO]);
+    let serialized = serde_json::to_value(&versions).unwrap();
+    assert_eq!(serialized, serde_json::json!("0 1"));
+    assert_eq!(serde_json::from_value::<TransactionVersions>(serialized).unwrap(), versions);
+    assert_eq!(
+        serde_json::from_value::<TransactionVersions>(serde_json::json!("")).unwrap(),
+        TransactionVersions::default()
+    );
+    assert!(serde_json::from_value::<TransactionVersions>(serde_json::json!("0x1")).is_err());
+}
@@ -1,19 +1,48 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use blockifier::blockifier::block_bundle::BlockBundle;
+use blockifier::execution::cancellation::CancellationToken;
 use blockifier::state::contract_class_manager::ContractClassManager;
+use blockifier::utils::u64_from_usize;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 #[cfg(test)]
 use mockall::automock;
+use papyrus_state_reader::state_reads_cache::StateReadsCache;
+use papyrus_storage::block_config::{
+    BlockConfigStorageReader,
+    BlockConfigStorageWriter,
+    SerializedBlockConfig,
+};
+use papyrus_storage::compiled_class::{CasmStorageReader, CasmStorageWriter};
+use papyrus_storage::l1_message::{L1MessageStorageReader, L1MessageStorageWriter};
+use papyrus_storage::revenue::{
+    RevenueDay,
+    RevenueStorageReader,
+    RevenueStorageWriter,
+    SerializedDailyRevenue,
+};
 use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
-use starknet_api::block::{BlockHeaderWithoutHash, BlockNumber};
-use starknet_api::core::{ContractAddress, Nonce};
+use papyrus_storage::trace::{SerializedTransactionTrace, TraceStorageWriter};
+use papyrus_storage::StorageError;
+use starknet_api::block::{BlockHeaderWithoutHash, BlockInfo, BlockNumber};
+use starknet_api::contract_class::{ContractClass, SierraVersion};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
-use starknet_api::state::ThinStateDiff;
+use starknet_api::state::{StateNumber, ThinStateDiff};
 use starknet_api::transaction::TransactionHash;
 use starknet_batcher_types::batcher_types::{
     BatcherResult,
+    BlockFeeMarketInfo,
     DecisionReachedInput,
     DecisionReachedResponse,
+    ExportBlockBundleInput,
+    ExportBlockBundleResponse,
+    ExternalBlockAttestation,
+    GetBlockConfigInput,
+    GetBlockConfigResponse,
+    GetDailyRevenueResponse,
+    GetFeeMarketInfoResponse,
     GetHeightResponse,
     GetProposalContent,
     GetProposalContentInput,
@@ -25,19 +54,23 @@ use starknet_batcher_types::batcher_types::{
     SendProposalContent,
     SendProposalContentInput,
     SendProposalContentResponse,
+    SignedExternalBlockAttestation,
     StartHeightInput,
     ValidateBlockInput,
+    ValidateExternalBlockInput,
 };
 use starknet_batcher_types::errors::BatcherError;
 use starknet_l1_provider_types::SharedL1ProviderClient;
 use starknet_mempool_types::communication::SharedMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::signer::{LocalKeySigner, SharedSigner, SignerBackend};
 use starknet_state_sync_types::state_sync_types::SyncBlock;
 use tokio::sync::Mutex;
-use tracing::{debug, error, info, instrument, trace, Instrument};
+use tracing::{debug, error, info, instrument, trace, warn, Instrument};
 
 use crate::block_builder::{
+    AbortSignalSender,
     BlockBuilderError,
     BlockBuilderExecutionParams,
     BlockBuilderFactory,
@@ -46,8 +79,18 @@ use crate::block_builder::{
     BlockExecutionArtifacts,
     BlockMetadata,
 };
+use crate::block_bundles::{RetainedBlock, RetainedBlocks};
+use crate::block_config::BlockConfig;
 use crate::config::BatcherConfig;
-use crate::transaction_provider::{ProposeTransactionProvider, ValidateTransactionProvider};
+use crate::fee_market::{block_fee_market_info, GasPriceController};
+use crate::l1_gas_prices::L1GasPriceGuard;
+use crate::metrics::init_metrics;
+use crate::revenue::{to_stored_daily_revenue, RevenueLedger};
+use crate::transaction_provider::{
+    L1MessageRegistry,
+    ProposeTransactionProvider,
+    ValidateTransactionProvider,
+};
 use crate::utils::{
     deadline_as_instant,
     proposal_status_from,
@@ -91,6 +134,30 @@ pub struct Batcher {
     // Each stream is kept until SendProposalContent::Finish/Abort is received, or a new height is
     // started.
     validate_tx_streams: HashMap<ProposalId, InputStreamSender>,
+
+    // The metadata of the proposals in the current height, used to bundle the committed block.
+    proposal_metadata: HashMap<ProposalId, BlockMetadata>,
+
+    // The fees collected in the committed blocks, aggregated per day.
+    revenue_ledger: RevenueLedger,
+
+    // The bundles of the most recent blocks built by the batcher, kept for export.
+    retained_blocks: RetainedBlocks,
+
+    // The fee market signals of the latest decided block.
+    latest_fee_market_info: Option<BlockFeeMarketInfo>,
+
+    // Keeps proposed blocks from being priced off stale L1 gas prices.
+    l1_gas_price_guard: L1GasPriceGuard,
+
+    // Prices the L2 gas of each block by the fullness of the previous one, if enabled.
+    gas_price_controller: GasPriceController,
+
+    // Cancels the pre-execution of pending transactions, if one is running.
+    pre_execution_cancellation_token: Option<CancellationToken>,
+
+    // Signs the attestations of validated external blocks.
+    pub signer: SharedSigner,
 }
 
 impl Batcher {
@@ -102,6 +169,11 @@ impl Batcher {
         mempool_client: SharedMempoolClient,
         block_builder_factory: Box<dyn BlockBuilderFactoryTrait>,
     ) -> Self {
+        let signer = LocalKeySigner::new(&config.signer_config);
+        info!("Signing external block attestations with public key {:#x}.", signer.public_key());
+        let gas_price_controller = load_gas_price_controller(&config, storage_reader.as_ref());
+        let revenue_ledger =
+            RevenueLedger::load(config.revenue_retention_days, storage_reader.as_ref());
         Self {
             config: config.clone(),
             storage_reader,
@@ -115,6 +187,14 @@ impl Batcher {
             executed_proposals: Arc::new(Mutex::new(HashMap::new())),
             propose_tx_streams: HashMap::new(),
             validate_tx_streams: HashMap::new(),
+            proposal_metadata: HashMap::new(),
+            revenue_ledger,
+            retained_blocks: RetainedBlocks::new(config.block_bundle_retention),
+            latest_fee_market_info: None,
+            l1_gas_price_guard: L1GasPriceGuard::new(config.stale_l1_gas_prices_config.clone()),
+            gas_price_controller,
+            pre_execution_cancellation_token: None,
+            signer: Arc::new(signer),
         }
     }
 
@@ -157,25 +237,42 @@ impl Batcher {
             propose_block_input.block_info.block_number,
             propose_block_input.retrospective_block_hash,
         )?;
+        let mut block_info = propose_block_input.block_info;
+        let now = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or_default();
+        let gas_prices = self.l1_gas_price_guard.gas_prices(
+            block_info.gas_prices,
+            propose_block_input.l1_gas_prices_timestamp,
+            now,
+        )?;
+        block_info.gas_prices = self.gas_price_controller.gas_prices(gas_prices);
 
         self.set_active_proposal(propose_block_input.proposal_id).await?;
 
-        let tx_provider = ProposeTransactionProvider::new(
+        let mut tx_provider = ProposeTransactionProvider::new(
             self.mempool_client.clone(),
             self.l1_provider_client.clone(),
             self.config.max_l1_handler_txs_per_block_proposal,
+            L1MessageRegistry::new(self.storage_reader.clone()),
         );
+        if self.config.stream_mempool_txs {
+            tx_provider = tx_provider.with_mempool_stream(
+                self.config.block_builder_config.tx_chunk_size,
+                self.config.mempool_stream_buffer_size,
+            );
+        }
 
         // A channel to receive the transactions included in the proposed block.
         let (output_tx_sender, output_tx_receiver) = tokio::sync::mpsc::unbounded_channel();
 
+        let block_metadata = BlockMetadata {
+            block_info,
+            retrospective_block_hash: propose_block_input.retrospective_block_hash,
+            randomness_signature: propose_block_input.randomness_signature,
+        };
         let (block_builder, abort_signal_sender) = self
             .block_builder_factory
             .create_block_builder(
-                BlockMetadata {
-                    block_info: propose_block_input.block_info,
-                    retrospective_block_hash: propose_block_input.retrospective_block_hash,
-                },
+                block_metadata.clone(),
                 BlockBuilderExecutionParams {
                     deadline: deadline_as_instant(propose_block_input.deadline)?,
                     fail_on_err: false,
@@ -183,12 +280,13 @@ impl Batcher {
                 Box::new(tx_provider),
                 Some(output_tx_sender),
             )
-            .map_err(|_| BatcherError::InternalError)?;
+            .map_err(block_builder_creation_err)?;
 
         self.spawn_proposal(propose_block_input.proposal_id, block_builder, abort_signal_sender)
             .await?;
 
         self.propose_tx_streams.insert(propose_block_input.proposal_id, output_tx_receiver);
+        self.proposal_metadata.insert(propose_block_input.proposal_id, block_metadata);
         Ok(())
     }
 
@@ -204,7 +302,11 @@ impl Batcher {
             validate_block_input.retrospective_block_hash,
         )?;
 
+        self.gas_price_controller
+            .validate_gas_prices(&validate_block_input.block_info.gas_prices)?;
+
         self.set_active_proposal(validate_block_input.proposal_id).await?;
+        let block_info = validate_block_input.block_info;
 
         // A channel to send the transactions to include in the block being validated.
         let (input_tx_sender, input_tx_receiver) =
@@ -213,15 +315,18 @@ impl Batcher {
         let tx_provider = ValidateTransactionProvider {
             tx_receiver: input_tx_receiver,
             l1_provider_client: self.l1_provider_client.clone(),
+            l1_message_registry: L1MessageRegistry::new(self.storage_reader.clone()),
         };
 
+        let block_metadata = BlockMetadata {
+            block_info,
+            retrospective_block_hash: validate_block_input.retrospective_block_hash,
+            randomness_signature: validate_block_input.randomness_signature,
+        };
         let (block_builder, abort_signal_sender) = self
             .block_builder_factory
             .create_block_builder(
-                BlockMetadata {
-                    block_info: validate_block_input.block_info,
-                    retrospective_block_hash: validate_block_input.retrospective_block_hash,
-                },
+                block_metadata.clone(),
                 BlockBuilderExecutionParams {
                     deadline: deadline_as_instant(validate_block_input.deadline)?,
                     fail_on_err: true,
@@ -229,15 +334,93 @@ impl Batcher {
                 Box::new(tx_provider),
                 None,
             )
-            .map_err(|_| BatcherError::InternalError)?;
+            .map_err(block_builder_creation_err)?;
 
         self.spawn_proposal(validate_block_input.proposal_id, block_builder, abort_signal_sender)
             .await?;
 
         self.validate_tx_streams.insert(validate_block_input.proposal_id, input_tx_sender);
+        self.proposal_metadata.insert(validate_block_input.proposal_id, block_metadata);
         Ok(())
     }
 
+    /// Validates a block built outside of the consensus, e.g., by an external builder or a shared
+    /// sequencer, by re-executing it on top of the latest committed state, and attests to whether
+    /// it results in its expected commitment. The block is neither kept nor committed, and the
+    /// consensus flow is not affected.
+    #[instrument(skip(self, input), fields(block_number = %input.block_info.block_number), err)]
+    pub async fn validate_external_block(
+        &mut self,
+        input: ValidateExternalBlockInput,
+    ) -> BatcherResult<SignedExternalBlockAttestation> {
+        let storage_height = self.get_height_from_storage()?;
+        let block_number = input.block_info.block_number;
+        verify_block_input(storage_height, block_number, input.retrospective_block_hash)?;
+        // Proposals of the consensus take precedence over external blocks.
+        if let Some(active_proposal_id) = *self.active_proposal.lock().await {
+            return Err(BatcherError::ProposalInProgress { active_proposal_id });
+        }
+
+        // The whole block is known upfront, so the channel is closed once it is filled, marking the
+        // end of the block.
+        let (tx_sender, tx_receiver) = tokio::sync::mpsc::channel(input.transactions.len().max(1));
+        for tx in input.transactions {
+            tx_sender.try_send(tx).expect("The channel should have room for all the transactions.");
+        }
+        drop(tx_sender);
+        let tx_provider = ValidateTransactionProvider {
+            tx_receiver,
+            l1_provider_client: self.l1_provider_client.clone(),
+            l1_message_registry: L1MessageRegistry::new(self.storage_reader.clone()),
+        };
+
+        let block_metadata = BlockMetadata {
+            block_info: input.block_info,
+            retrospective_block_hash: input.retrospective_block_hash,
+            randomness_signature: input.randomness_signature,
+        };
+        let (mut block_builder, _abort_signal_sender) = self
+            .block_builder_factory
+            .create_block_builder(
+                block_metadata,
+                BlockBuilderExecutionParams {
+                    deadline: deadline_as_instant(input.deadline)?,
+                    fail_on_err: true,
+                },
+                Box::new(tx_provider),
+                None,
+            )
+            .map_err(block_builder_creation_err)?;
+
+        let is_valid = match block_builder.build_block().await {
+            Ok(artifacts) => artifacts.commitment() == input.expected_commitment,
+            Err(BlockBuilderError::FailOnError(cause)) => {
+                info!("External block {} is invalid: {}", block_number, cause);
+                false
+            }
+            Err(err) => {
+                error!("Failed to re-execute external block {}: {}", block_number, err);
+                return Err(BatcherError::InternalError);
+            }
+        };
+
+        let attestation = ExternalBlockAttestation {
+            chain_id: self.config.block_builder_config.chain_info.chain_id.clone(),
+            block_number,
+            state_diff_commitment: input.expected_commitment.state_diff_commitment,
+            is_valid,
+        };
+        let signature = attestation
+            .hash()
+            .map_err(|e| e.to_string())
+            .and_then(|message_hash| self.signer.sign(message_hash).map_err(|e| e.to_string()))
+            .map_err(|e| {
+                error!("Failed to sign the attestation of external block {}: {}", block_number, e);
+                BatcherError::InternalError
+            })?;
+        Ok(SignedExternalBlockAttestation { attestation, signature })
+    }
+
     // This function assumes that requests are received in order, otherwise the content could
     // be processed out of order.
     #[instrument(skip(self), err)]
@@ -263,6 +446,7 @@ impl Batcher {
         self.executed_proposals.lock().await.clear();
         self.propose_tx_streams.clear();
         self.validate_tx_streams.clear();
+        self.proposal_metadata.clear();
     }
 
     async fn handle_send_txs_request(
@@ -388,7 +572,8 @@ impl Batcher {
         let SyncBlock {
             state_diff,
             transaction_hashes,
-            block_header_without_hash: BlockHeaderWithoutHash { block_number, .. },
+            block_header_without_hash: BlockHeaderWithoutHash { block_number, l2_gas_price, .. },
+            consumed_l1_message_nonces,
         } = sync_block;
         let address_to_nonce = state_diff.nonces.iter().map(|(k, v)| (*k, *v)).collect();
         let tx_hashes = transaction_hashes.into_iter().collect();
@@ -400,7 +585,21 @@ impl Batcher {
             );
         }
 
-        self.commit_proposal_and_block(height, state_diff, address_to_nonce, tx_hashes).await
+        // The classes of synced blocks are stored by the state sync.
+        self.commit_proposal_and_block(
+            height,
+            state_diff,
+            vec![],
+            address_to_nonce,
+            tx_hashes,
+            consumed_l1_message_nonces,
+            vec![],
+            None,
+            None,
+        )
+        .await?;
+        self.gas_price_controller.record_synced_block(l2_gas_price.price_in_fri);
+        Ok(())
     }
 
     #[instrument(skip(self), err)]
@@ -415,35 +614,235 @@ impl Batcher {
         let block_execution_artifacts = proposal_result
             .ok_or(BatcherError::ExecutedProposalNotFound { proposal_id })?
             .map_err(|_| BatcherError::InternalError)?;
+        let starknet_version = self
+            .proposal_metadata
+            .get(&proposal_id)
+            .map(|BlockMetadata { block_info, .. }| {
+                self.config
+                    .block_builder_config
+                    .starknet_version_schedule
+                    .version_at(block_info.block_number, block_info.block_timestamp)
+            })
+            .ok_or(BatcherError::ExecutedProposalNotFound { proposal_id })?;
         let state_diff = block_execution_artifacts.state_diff();
+        let transaction_traces = if self.config.archive_transaction_traces {
+            block_execution_artifacts.transaction_traces().map_err(|err| {
+                error!("Failed to create the transaction traces of block {}: {}", height, err);
+                BatcherError::InternalError
+            })?
+        } else {
+            vec![]
+        };
+        let block_config = self.proposal_metadata.get(&proposal_id).map(|block_metadata| {
+            BlockConfig::new(
+                &block_metadata.block_info,
+                &self.config.block_builder_config,
+                &self.config.stale_l1_gas_prices_config,
+                block_execution_artifacts.l2_gas_used,
+            )
+            .to_serialized()
+        });
+        let daily_revenue = self
+            .revenue_ledger
+            .with_block(chrono::Utc::now().date_naive(), &block_execution_artifacts.revenue);
         self.commit_proposal_and_block(
             height,
             state_diff.clone(),
+            block_execution_artifacts.declared_casms(),
             block_execution_artifacts.address_to_nonce(),
             block_execution_artifacts.tx_hashes(),
+            block_execution_artifacts.consumed_l1_message_nonces(),
+            transaction_traces,
+            block_config,
+            Some(to_stored_daily_revenue(&daily_revenue)),
         )
         .await?;
-        Ok(DecisionReachedResponse {
-            state_diff,
-            l2_gas_used: block_execution_artifacts.l2_gas_used,
-        })
+        self.revenue_ledger.record(daily_revenue);
+        let l2_gas_used = block_execution_artifacts.l2_gas_used;
+        let fee_market_info = self.proposal_metadata.get(&proposal_id).map(|block_metadata| {
+            block_fee_market_info(
+                block_metadata.block_info.block_number,
+                block_metadata.block_info.gas_prices.strk_gas_prices.clone(),
+                l2_gas_used,
+            )
+        });
+        if let Some(info) = &fee_market_info {
+            self.gas_price_controller.record_decided_block(&info.gas_prices, l2_gas_used);
+            self.latest_fee_market_info = Some(info.clone());
+        }
+        if let Some(block_metadata) = self.proposal_metadata.get(&proposal_id) {
+            let next_block_info = BlockInfo {
+                block_number: height.unchecked_next(),
+                gas_prices: self
+                    .gas_price_controller
+                    .gas_prices(block_metadata.block_info.gas_prices.clone()),
+                ..block_metadata.block_info.clone()
+            };
+            self.pre_execute_pending_txs(next_block_info).await;
+        }
+        self.retain_block(proposal_id, block_execution_artifacts);
+        Ok(DecisionReachedResponse { state_diff, l2_gas_used, fee_market_info, starknet_version })
+    }
+
+    // Speculatively executes the transactions likely to be included in the next block, to warm up
+    // the caches of its execution, if configured to.
+    async fn pre_execute_pending_txs(&mut self, block_info: BlockInfo) {
+        let n_txs = self.config.pre_execution_n_txs;
+        if n_txs == 0 {
+            return;
+        }
+        let txs = match self.mempool_client.peek_txs(n_txs).await {
+            Ok(txs) => txs.into_iter().map(Transaction::Account).collect(),
+            Err(err) => {
+                warn!("Failed to get the pending transactions to pre-execute: {}", err);
+                return;
+            }
+        };
+        let cancellation_token = CancellationToken::default();
+        if let Err(err) =
+            self.block_builder_factory.pre_execute_txs(block_info, txs, cancellation_token.clone())
+        {
+            warn!("Failed to pre-execute the pending transactions: {}", err);
+            return;
+        }
+        self.pre_execution_cancellation_token = Some(cancellation_token);
+    }
+
+    // Keeps the bundle of a committed block, to allow exporting it for offline re-execution.
+    fn retain_block(&mut self, proposal_id: ProposalId, artifacts: BlockExecutionArtifacts) {
+        let Some(BlockMetadata { block_info, retrospective_block_hash, .. }) =
+            self.proposal_metadata.remove(&proposal_id)
+        else {
+            return;
+        };
+        let height = block_info.block_number;
+        let block_builder_config = &self.config.block_builder_config;
+        let starknet_version = block_builder_config
+            .starknet_version_schedule
+            .version_at(height, block_info.block_timestamp);
+        let bundle = BlockBundle {
+            block_info,
+            chain_info: block_builder_config.chain_info.clone(),
+            starknet_version,
+            versioned_constants_overrides: block_builder_config
+                .versioned_constants_overrides
+                .clone(),
+            bouncer_config: block_builder_config.bouncer_config.clone(),
+            retrospective_block_hash,
+            transactions: artifacts.transactions,
+            state_reads: artifacts.state_reads.state_maps.into(),
+            classes: Vec::new(),
+        };
+        self.retained_blocks.insert(
+            height,
+            RetainedBlock { bundle, class_hashes: artifacts.state_reads.class_hashes },
+        );
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn export_block_bundle(
+        &mut self,
+        input: ExportBlockBundleInput,
+    ) -> BatcherResult<ExportBlockBundleResponse> {
+        let height = input.height;
+        let RetainedBlock { bundle, class_hashes } =
+            self.retained_blocks.get(height).ok_or(BatcherError::BlockBundleNotFound { height })?;
+
+        let mut classes = Vec::new();
+        for class_hash in class_hashes {
+            // Classes declared in the block itself are part of their declare transactions.
+            let class =
+                self.storage_reader.get_contract_class(height, *class_hash).map_err(|err| {
+                    error!("Failed to read class {} from storage: {}", class_hash, err);
+                    BatcherError::InternalError
+                })?;
+            if let Some(class) = class {
+                classes.push((*class_hash, class));
+            }
+        }
+        classes.sort_by_key(|(class_hash, _)| *class_hash);
+
+        let archive = BlockBundle { classes, ..bundle.clone() }.to_archive().map_err(|err| {
+            error!("Failed to archive the bundle of block {}: {}", height, err);
+            BatcherError::InternalError
+        })?;
+        Ok(ExportBlockBundleResponse { archive })
     }
 
+    /// Returns the configuration the given committed block was executed with, serialized as JSON.
+    #[instrument(skip(self), err)]
+    pub async fn get_block_config(
+        &mut self,
+        input: GetBlockConfigInput,
+    ) -> BatcherResult<GetBlockConfigResponse> {
+        let height = input.height;
+        let block_config = self
+            .storage_reader
+            .get_block_config(height)
+            .map_err(|err| {
+                error!("Failed to read the config of block {} from storage: {}", height, err);
+                BatcherError::InternalError
+            })?
+            .ok_or(BatcherError::BlockConfigNotFound { height })?;
+        Ok(GetBlockConfigResponse { block_config: block_config.0.to_string() })
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn get_daily_revenue(&mut self) -> BatcherResult<GetDailyRevenueResponse> {
+        Ok(GetDailyRevenueResponse { daily_revenue: self.revenue_ledger.daily_revenue() })
+    }
+
+    pub async fn get_fee_market_info(&mut self) -> BatcherResult<GetFeeMarketInfoResponse> {
+        Ok(GetFeeMarketInfoResponse { fee_market_info: self.latest_fee_market_info.clone() })
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn commit_proposal_and_block(
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        casms: Vec<(ClassHash, CasmContractClass)>,
         address_to_nonce: HashMap<ContractAddress, Nonce>,
         tx_hashes: HashSet<TransactionHash>,
+        consumed_l1_message_nonces: Vec<Nonce>,
+        transaction_traces: Vec<SerializedTransactionTrace>,
+        block_config: Option<SerializedBlockConfig>,
+        daily_revenue: Option<(RevenueDay, SerializedDailyRevenue)>,
     ) -> BatcherResult<()> {
         info!("Committing block at height {} and notifying mempool of the block.", height);
         trace!("Transactions: {:#?}, State diff: {:#?}.", tx_hashes, state_diff);
-
-        // Commit the proposal to the storage and notify the mempool.
-        self.storage_writer.commit_proposal(height, state_diff).map_err(|err| {
-            error!("Failed to commit proposal to storage: {}", err);
-            BatcherError::InternalError
-        })?;
+        let declared_class_hashes: Vec<ClassHash> = state_diff
+            .declared_classes
+            .keys()
+            .chain(&state_diff.deprecated_declared_classes)
+            .copied()
+            .collect();
+
+        // Commit the proposal to the storage and notify the mempool. The storage commit is durable
+        // once it returns; a failure to notify the mempool afterwards does not roll it back.
+        self.storage_writer
+            .commit_proposal(
+                height,
+                state_diff,
+                &casms,
+                &consumed_l1_message_nonces,
+                &transaction_traces,
+                block_config,
+                daily_revenue,
+            )
+            .map_err(|err| {
+                error!("Failed to commit proposal to storage: {}", err);
+                match err {
+                    StorageError::L1MessageAlreadyConsumed { nonce, block_number } => {
+                        BatcherError::L1MessageAlreadyConsumed { nonce, block_number }
+                    }
+                    _ => BatcherError::InternalError,
+                }
+            })?;
+        self.prune_transaction_traces(height);
+        if !declared_class_hashes.is_empty() {
+            self.block_builder_factory.invalidate_declared_classes(&declared_class_hashes);
+        }
         let mempool_result =
             self.mempool_client.commit_block(CommitBlockArgs { address_to_nonce, tx_hashes }).await;
 
@@ -455,6 +854,24 @@ impl Batcher {
         Ok(())
     }
 
+    // Deletes the traces of the blocks that are out of the retention window of the given height.
+    fn prune_transaction_traces(&mut self, height: BlockNumber) {
+        let retention = self.config.transaction_traces_retention;
+        if !self.config.archive_transaction_traces || retention == 0 {
+            return;
+        }
+        let Some(until_height) = (height.0 + 1).checked_sub(u64_from_usize(retention)) else {
+            return;
+        };
+        // The traces are only a cache of the execution, so failing to prune them isn't fatal.
+        if let Err(err) = self.storage_writer.prune_transaction_traces(BlockNumber(until_height)) {
+            error!(
+                "Failed to prune the transaction traces before height {}: {}",
+                until_height, err
+            );
+        }
+    }
+
     async fn is_active(&self, proposal_id: ProposalId) -> bool {
         *self.active_proposal.lock().await == Some(proposal_id)
     }
@@ -486,9 +903,14 @@ impl Batcher {
         &mut self,
         proposal_id: ProposalId,
         mut block_builder: Box<dyn BlockBuilderTrait>,
-        abort_signal_sender: tokio::sync::oneshot::Sender<()>,
+        abort_signal_sender: AbortSignalSender,
     ) -> BatcherResult<()> {
         info!("Starting generation of a new proposal with id {}.", proposal_id);
+        // The pre-execution would compete with the proposal over the CPU.
+        if let Some(pre_execution_cancellation_token) = self.pre_execution_cancellation_token.take()
+        {
+            pre_execution_cancellation_token.cancel();
+        }
 
         let active_proposal = self.active_proposal.clone();
         let executed_proposals = self.executed_proposals.clone();
@@ -533,7 +955,7 @@ impl Batcher {
     async fn abort_active_proposal(&mut self) {
         self.active_proposal.lock().await.take();
         if let Some(proposal_task) = self.active_proposal_task.take() {
-            proposal_task.abort_signal_sender.send(()).ok();
+            proposal_task.abort_signal_sender.abort();
         }
     }
 
@@ -544,20 +966,73 @@ impl Batcher {
     }
 }
 
+// Creates the L2 gas price controller, picking up the fee market from the latest block in the
+// storage, if it was executed by this node.
+// Block builders fail to be created on internal errors only, except for blocks lacking the
+// randomness signature the chain requires, which are invalid.
+fn block_builder_creation_err(err: BlockBuilderError) -> BatcherError {
+    match err {
+        BlockBuilderError::MissingRandomnessSignature => BatcherError::MissingRandomnessSignature,
+        _ => BatcherError::InternalError,
+    }
+}
+
+fn load_gas_price_controller(
+    config: &BatcherConfig,
+    storage_reader: &dyn BatcherStorageReaderTrait,
+) -> GasPriceController {
+    let mut gas_price_controller =
+        GasPriceController::new(config.gas_price_controller_config.clone());
+    if !gas_price_controller.is_enabled() {
+        return gas_price_controller;
+    }
+    let latest_block_number = match storage_reader.height() {
+        Ok(height) => match height.prev() {
+            Some(latest_block_number) => latest_block_number,
+            None => return gas_price_controller,
+        },
+        Err(err) => {
+            warn!("Failed to read the storage height to price the L2 gas: {}", err);
+            return gas_price_controller;
+        }
+    };
+    // Blocks added by state sync have no config, and the price of the next block is then unknown.
+    match storage_reader.get_block_config(latest_block_number) {
+        Ok(Some(serialized_block_config)) => {
+            match serde_json::from_value::<BlockConfig>(serialized_block_config.0) {
+                Ok(BlockConfig { gas_price_params, .. }) => gas_price_controller
+                    .record_decided_block(
+                        &gas_price_params.gas_prices.strk_gas_prices,
+                        gas_price_params.l2_gas_used,
+                    ),
+                Err(err) => {
+                    warn!("Failed to parse the config of block {}: {}", latest_block_number, err)
+                }
+            }
+        }
+        Ok(None) => {}
+        Err(err) => {
+            warn!("Failed to read the config of block {}: {}", latest_block_number, err)
+        }
+    }
+    gas_price_controller
+}
+
 pub fn create_batcher(
     config: BatcherConfig,
     mempool_client: SharedMempoolClient,
     l1_provider_client: SharedL1ProviderClient,
+    contract_class_manager: ContractClassManager,
 ) -> Batcher {
+    init_metrics();
     let (storage_reader, storage_writer) = papyrus_storage::open_storage(config.storage.clone())
         .expect("Failed to open batcher's storage");
 
     let block_builder_factory = Box::new(BlockBuilderFactory {
         block_builder_config: config.block_builder_config.clone(),
         storage_reader: storage_reader.clone(),
-        contract_class_manager: ContractClassManager::start(
-            config.contract_class_manager_config.clone(),
-        ),
+        contract_class_manager,
+        state_reads_cache: StateReadsCache::default(),
     });
     let storage_reader = Arc::new(storage_reader);
     let storage_writer = Box::new(storage_writer);
@@ -575,20 +1050,94 @@ pub fn create_batcher(
 pub trait BatcherStorageReaderTrait: Send + Sync {
     /// Returns the next height that the batcher should work on.
     fn height(&self) -> papyrus_storage::StorageResult<BlockNumber>;
+
+    /// Returns the class with the given hash, if it was declared before the given height.
+    fn get_contract_class(
+        &self,
+        height: BlockNumber,
+        class_hash: ClassHash,
+    ) -> papyrus_storage::StorageResult<Option<ContractClass>>;
+
+    /// Returns true if the L1 message with the given nonce was consumed by a committed block.
+    fn is_l1_message_consumed(&self, nonce: Nonce) -> papyrus_storage::StorageResult<bool>;
+
+    /// Returns the configuration the given block was executed with, if it was recorded.
+    fn get_block_config(
+        &self,
+        height: BlockNumber,
+    ) -> papyrus_storage::StorageResult<Option<SerializedBlockConfig>>;
+
+    /// Returns the revenue totals of all the days blocks were committed on, oldest first.
+    fn get_daily_revenues(
+        &self,
+    ) -> papyrus_storage::StorageResult<Vec<(RevenueDay, SerializedDailyRevenue)>>;
 }
 
 impl BatcherStorageReaderTrait for papyrus_storage::StorageReader {
     fn height(&self) -> papyrus_storage::StorageResult<BlockNumber> {
         self.begin_ro_txn()?.get_state_marker()
     }
+
+    fn get_contract_class(
+        &self,
+        height: BlockNumber,
+        class_hash: ClassHash,
+    ) -> papyrus_storage::StorageResult<Option<ContractClass>> {
+        let txn = self.begin_ro_txn()?;
+        let state_reader = txn.get_state_reader()?;
+        let declaration_block_number =
+            state_reader.get_class_definition_block_number(&class_hash)?;
+        if declaration_block_number.is_some_and(|block_number| block_number < height) {
+            let (Some(casm), Some(sierra)) = txn.get_casm_and_sierra(&class_hash)? else {
+                return Ok(None);
+            };
+            let sierra_version = SierraVersion::extract_from_program(&sierra.sierra_program)
+                .map_err(|err| StorageError::DBInconsistency {
+                    msg: format!("Stored Sierra class {class_hash} has an invalid version: {err}"),
+                })?;
+            return Ok(Some(ContractClass::V1((casm, sierra_version))));
+        }
+
+        Ok(state_reader
+            .get_deprecated_class_definition_at(StateNumber(height), &class_hash)?
+            .map(ContractClass::V0))
+    }
+
+    fn is_l1_message_consumed(&self, nonce: Nonce) -> papyrus_storage::StorageResult<bool> {
+        Ok(self.begin_ro_txn()?.get_l1_message_consumption_block(&nonce)?.is_some())
+    }
+
+    fn get_block_config(
+        &self,
+        height: BlockNumber,
+    ) -> papyrus_storage::StorageResult<Option<SerializedBlockConfig>> {
+        self.begin_ro_txn()?.get_block_config(height)
+    }
+
+    fn get_daily_revenues(
+        &self,
+    ) -> papyrus_storage::StorageResult<Vec<(RevenueDay, SerializedDailyRevenue)>> {
+        self.begin_ro_txn()?.get_daily_revenues()
+    }
 }
 
 #[cfg_attr(test, automock)]
 pub trait BatcherStorageWriterTrait: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
     fn commit_proposal(
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        casms: &[(ClassHash, CasmContractClass)],
+        consumed_l1_message_nonces: &[Nonce],
+        transaction_traces: &[SerializedTransactionTrace],
+        block_config: Option<SerializedBlockConfig>,
+        daily_revenue: Option<(RevenueDay, SerializedDailyRevenue)>,
+    ) -> papyrus_storage::StorageResult<()>;
+
+    fn prune_transaction_traces(
+        &mut self,
+        until_height: BlockNumber,
     ) -> papyrus_storage::StorageResult<()>;
 }
 
@@ -597,9 +1146,36 @@ impl BatcherStorageWriterTrait for papyrus_storage::StorageWriter {
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        casms: &[(ClassHash, CasmContractClass)],
+        consumed_l1_message_nonces: &[Nonce],
+        transaction_traces: &[SerializedTransactionTrace],
+        block_config: Option<SerializedBlockConfig>,
+        daily_revenue: Option<(RevenueDay, SerializedDailyRevenue)>,
+    ) -> papyrus_storage::StorageResult<()> {
+        // The state diff, the Casms and the batcher metadata of the block are written in a single
+        // transaction; the storage commits transactions durably, so consensus never advances past a
+        // block that may be lost on a crash.
+        let mut txn = self.begin_rw_txn()?.append_state_diff(height, state_diff)?;
+        for (class_hash, casm) in casms {
+            txn = txn.append_casm(class_hash, casm)?;
+        }
+        let mut txn = txn
+            .consume_l1_messages(height, consumed_l1_message_nonces)?
+            .append_transaction_traces(height, transaction_traces)?;
+        if let Some(block_config) = &block_config {
+            txn = txn.append_block_config(height, block_config)?;
+        }
+        if let Some((day, daily_revenue)) = &daily_revenue {
+            txn = txn.update_daily_revenue(*day, daily_revenue)?;
+        }
+        txn.commit()
+    }
+
+    fn prune_transaction_traces(
+        &mut self,
+        until_height: BlockNumber,
     ) -> papyrus_storage::StorageResult<()> {
-        // TODO: write casms.
-        self.begin_rw_txn()?.append_state_diff(height, state_diff)?.commit()
+        self.begin_rw_txn()?.prune_transaction_traces(until_height)?.commit()
     }
 }
 
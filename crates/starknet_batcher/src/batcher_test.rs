@@ -3,17 +3,33 @@ use std::sync::Arc;
 
 use assert_matches::assert_matches;
 use blockifier::abi::constants;
+use blockifier::blockifier::block_bundle::BlockBundle;
+use blockifier::execution::cancellation::CancellationToken;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use chrono::NaiveDate;
 use indexmap::indexmap;
-use mockall::predicate::eq;
+use mockall::predicate::{always, eq, function};
+use papyrus_storage::block_config::SerializedBlockConfig;
+use papyrus_storage::compiled_class::CasmStorageReader;
+use papyrus_storage::revenue::{RevenueDay, SerializedDailyRevenue};
+use papyrus_storage::test_utils::get_test_storage;
+use papyrus_storage::trace::SerializedTransactionTrace;
+use papyrus_storage::{StorageError, StorageWriter};
 use rstest::rstest;
-use starknet_api::block::{BlockHeaderWithoutHash, BlockInfo, BlockNumber};
-use starknet_api::core::{ContractAddress, Nonce};
+use serde_json::json;
+use starknet_api::block::{BlockHeaderWithoutHash, BlockInfo, BlockNumber, StarknetVersion};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::execution_resources::GasAmount;
 use starknet_api::state::ThinStateDiff;
+use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
 use starknet_api::transaction::TransactionHash;
-use starknet_api::{contract_address, nonce, tx_hash};
+use starknet_api::{class_hash, contract_address, nonce, tx_hash};
 use starknet_batcher_types::batcher_types::{
+    DailyRevenue,
     DecisionReachedInput,
+    ExportBlockBundleInput,
+    GetBlockConfigInput,
     GetHeightResponse,
     GetProposalContent,
     GetProposalContentInput,
@@ -22,28 +38,43 @@ use starknet_batcher_types::batcher_types::{
     ProposalId,
     ProposalStatus,
     ProposeBlockInput,
+    RevenueBreakdown,
     SendProposalContent,
     SendProposalContentInput,
     SendProposalContentResponse,
     StartHeightInput,
     ValidateBlockInput,
+    ValidateExternalBlockInput,
 };
 use starknet_batcher_types::errors::BatcherError;
 use starknet_l1_provider_types::MockL1ProviderClient;
 use starknet_mempool_types::communication::MockMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use starknet_sequencer_infra::signer::{SignerBackend, SignerConfig};
 use starknet_state_sync_types::state_sync_types::SyncBlock;
-
-use crate::batcher::{Batcher, MockBatcherStorageReaderTrait, MockBatcherStorageWriterTrait};
+use starknet_types_core::felt::Felt;
+
+use crate::batcher::{
+    Batcher,
+    BatcherStorageReaderTrait,
+    BatcherStorageWriterTrait,
+    MockBatcherStorageReaderTrait,
+    MockBatcherStorageWriterTrait,
+};
 use crate::block_builder::{
     AbortSignalSender,
+    BlockBuilderConfig,
     BlockBuilderError,
     BlockBuilderResult,
     BlockExecutionArtifacts,
     FailOnErrorCause,
     MockBlockBuilderFactoryTrait,
 };
+use crate::block_config::BlockConfig;
 use crate::config::BatcherConfig;
+use crate::fee_market::{GasPriceControllerConfig, MAX_BLOCK_SIZE};
+use crate::l1_gas_prices::StaleL1GasPricesConfig;
+use crate::revenue::to_stored_daily_revenue;
 use crate::test_utils::{test_txs, FakeProposeBlockBuilder, FakeValidateBlockBuilder};
 
 const INITIAL_HEIGHT: BlockNumber = BlockNumber(3);
@@ -63,6 +94,8 @@ fn propose_block_input(proposal_id: ProposalId) -> ProposeBlockInput {
         retrospective_block_hash: None,
         deadline: chrono::Utc::now() + BLOCK_GENERATION_TIMEOUT,
         block_info: BlockInfo { block_number: INITIAL_HEIGHT, ..BlockInfo::create_for_testing() },
+        l1_gas_prices_timestamp: None,
+        randomness_signature: None,
     }
 }
 
@@ -72,6 +105,20 @@ fn validate_block_input(proposal_id: ProposalId) -> ValidateBlockInput {
         retrospective_block_hash: None,
         deadline: chrono::Utc::now() + BLOCK_GENERATION_TIMEOUT,
         block_info: BlockInfo { block_number: INITIAL_HEIGHT, ..BlockInfo::create_for_testing() },
+        randomness_signature: None,
+    }
+}
+
+fn validate_external_block_input(
+    expected_commitment: ProposalCommitment,
+) -> ValidateExternalBlockInput {
+    ValidateExternalBlockInput {
+        block_info: BlockInfo { block_number: INITIAL_HEIGHT, ..BlockInfo::create_for_testing() },
+        retrospective_block_hash: None,
+        transactions: test_txs(0..3),
+        expected_commitment,
+        deadline: chrono::Utc::now() + BLOCK_GENERATION_TIMEOUT,
+        randomness_signature: None,
     }
 }
 
@@ -86,6 +133,7 @@ struct MockDependencies {
 impl Default for MockDependencies {
     fn default() -> Self {
         let mut storage_reader = MockBatcherStorageReaderTrait::new();
+        storage_reader.expect_get_daily_revenues().returning(|| Ok(vec![]));
         storage_reader.expect_height().returning(|| Ok(INITIAL_HEIGHT));
         Self {
             storage_reader,
@@ -98,8 +146,19 @@ impl Default for MockDependencies {
 }
 
 fn create_batcher(mock_dependencies: MockDependencies) -> Batcher {
+    create_batcher_with_config(BatcherConfig::default(), mock_dependencies)
+}
+
+fn create_batcher_with_config(
+    config: BatcherConfig,
+    mock_dependencies: MockDependencies,
+) -> Batcher {
     Batcher::new(
-        BatcherConfig { outstream_content_buffer_size: STREAMING_CHUNK_SIZE, ..Default::default() },
+        BatcherConfig {
+            outstream_content_buffer_size: STREAMING_CHUNK_SIZE,
+            signer_config: SignerConfig { private_key: Felt::ONE },
+            ..config
+        },
         Arc::new(mock_dependencies.storage_reader),
         Box::new(mock_dependencies.storage_writer),
         Arc::new(mock_dependencies.l1_provider_client),
@@ -109,7 +168,7 @@ fn create_batcher(mock_dependencies: MockDependencies) -> Batcher {
 }
 
 fn abort_signal_sender() -> AbortSignalSender {
-    tokio::sync::oneshot::channel().0
+    AbortSignalSender::new(tokio::sync::oneshot::channel().0, CancellationToken::default())
 }
 
 fn mock_create_builder_for_validate_block(
@@ -216,6 +275,7 @@ async fn no_active_height() {
 #[tokio::test]
 async fn consecutive_heights_success() {
     let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_get_daily_revenues().returning(|| Ok(vec![]));
     storage_reader.expect_height().times(1).returning(|| Ok(INITIAL_HEIGHT));
     storage_reader.expect_height().times(1).returning(|| Ok(INITIAL_HEIGHT.unchecked_next()));
 
@@ -275,6 +335,41 @@ async fn validate_block_full_flow() {
     );
 }
 
+#[rstest]
+#[tokio::test]
+async fn validate_block_with_invalid_l2_gas_price() {
+    // The latest stored block was full, so the L2 gas price of the next block must rise.
+    let latest_block_info = BlockInfo {
+        block_number: INITIAL_HEIGHT.prev().unwrap(),
+        ..BlockInfo::create_for_testing()
+    };
+    let latest_block_config = BlockConfig::new(
+        &latest_block_info,
+        &BlockBuilderConfig::default(),
+        &StaleL1GasPricesConfig::default(),
+        GasAmount(MAX_BLOCK_SIZE),
+    );
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies
+        .storage_reader
+        .expect_get_block_config()
+        .with(eq(latest_block_info.block_number))
+        .returning(move |_| Ok(Some(latest_block_config.to_serialized())));
+    let config = BatcherConfig {
+        gas_price_controller_config: GasPriceControllerConfig {
+            enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut batcher = create_batcher_with_config(config, mock_dependencies);
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+
+    // Proposed with the price of the latest block.
+    let result = batcher.validate_block(validate_block_input(PROPOSAL_ID)).await;
+    assert_matches!(result, Err(BatcherError::InvalidL2GasPrice { .. }));
+}
+
 #[rstest]
 #[case::send_txs(SendProposalContent::Txs(test_txs(0..1)))]
 #[case::send_finish(SendProposalContent::Finish)]
@@ -307,6 +402,52 @@ async fn send_content_to_an_invalid_proposal(
     assert_eq!(result, SendProposalContentResponse { response });
 }
 
+#[rstest]
+#[case::matching_commitment(
+    Ok(BlockExecutionArtifacts::create_for_testing()),
+    proposal_commitment(),
+    true
+)]
+#[case::mismatching_commitment(
+    Ok(BlockExecutionArtifacts::create_for_testing()),
+    ProposalCommitment::default(),
+    false
+)]
+#[case::failed_execution(Err(BUILD_BLOCK_FAIL_ON_ERROR), proposal_commitment(), false)]
+#[tokio::test]
+async fn validate_external_block(
+    #[case] build_block_result: BlockBuilderResult<BlockExecutionArtifacts>,
+    #[case] expected_commitment: ProposalCommitment,
+    #[case] expected_is_valid: bool,
+) {
+    let mut block_builder_factory = MockBlockBuilderFactoryTrait::new();
+    mock_create_builder_for_validate_block(&mut block_builder_factory, build_block_result);
+    let mut batcher =
+        create_batcher(MockDependencies { block_builder_factory, ..Default::default() });
+
+    let signed_attestation = batcher
+        .validate_external_block(validate_external_block_input(expected_commitment))
+        .await
+        .unwrap();
+
+    let attestation = &signed_attestation.attestation;
+    assert_eq!(attestation.block_number, INITIAL_HEIGHT);
+    assert_eq!(attestation.state_diff_commitment, expected_commitment.state_diff_commitment);
+    assert_eq!(attestation.is_valid, expected_is_valid);
+    assert!(signed_attestation.is_signed_by(&batcher.signer.public_key()));
+}
+
+#[rstest]
+#[tokio::test]
+async fn validate_external_block_while_validating_proposal() {
+    let mut batcher =
+        batcher_with_active_validate_block(Ok(BlockExecutionArtifacts::create_for_testing())).await;
+
+    let result =
+        batcher.validate_external_block(validate_external_block_input(proposal_commitment())).await;
+    assert_eq!(result, Err(BatcherError::ProposalInProgress { active_proposal_id: PROPOSAL_ID }));
+}
+
 #[rstest]
 #[case::send_txs_after_finish(SendProposalContent::Finish, SendProposalContent::Txs(test_txs(0..1)))]
 #[case::send_finish_after_finish(SendProposalContent::Finish, SendProposalContent::Finish)]
@@ -399,6 +540,7 @@ async fn propose_block_full_flow() {
 #[tokio::test]
 async fn get_height() {
     let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_get_daily_revenues().returning(|| Ok(vec![]));
     storage_reader.expect_height().returning(|| Ok(INITIAL_HEIGHT));
 
     let mut batcher = create_batcher(MockDependencies { storage_reader, ..Default::default() });
@@ -411,6 +553,7 @@ async fn get_height() {
 #[tokio::test]
 async fn propose_block_without_retrospective_block_hash() {
     let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_get_daily_revenues().returning(|| Ok(vec![]));
     storage_reader
         .expect_height()
         .returning(|| Ok(BlockNumber(constants::STORED_BLOCK_HASH_BUFFER)));
@@ -492,8 +635,16 @@ async fn add_sync_block() {
         .storage_writer
         .expect_commit_proposal()
         .times(1)
-        .with(eq(INITIAL_HEIGHT), eq(test_state_diff()))
-        .returning(|_, _| Ok(()));
+        .with(
+            eq(INITIAL_HEIGHT),
+            eq(test_state_diff()),
+            function(|casms: &[(ClassHash, CasmContractClass)]| casms.is_empty()),
+            function(|nonces: &[Nonce]| nonces == [nonce!(1_u8)]),
+            always(),
+            eq(None),
+            eq(None),
+        )
+        .returning(|_, _, _, _, _, _, _| Ok(()));
 
     mock_dependencies
         .mempool_client
@@ -514,6 +665,126 @@ async fn add_sync_block() {
         },
         state_diff: test_state_diff(),
         transaction_hashes: test_tx_hashes().into_iter().collect(),
+        consumed_l1_message_nonces: vec![nonce!(1_u8)],
+    };
+    batcher.add_sync_block(sync_block).await.unwrap();
+}
+
+#[rstest]
+#[tokio::test]
+async fn add_sync_block_with_consumed_l1_message() {
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.storage_writer.expect_commit_proposal().times(1).returning(
+        |_, _, _, _, _, _, _| {
+            Err(StorageError::L1MessageAlreadyConsumed {
+                nonce: nonce!(1_u8),
+                block_number: BlockNumber(1),
+            })
+        },
+    );
+    mock_dependencies.mempool_client.expect_commit_block().never();
+
+    let mut batcher = create_batcher(mock_dependencies);
+
+    let sync_block = SyncBlock {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: INITIAL_HEIGHT,
+            ..Default::default()
+        },
+        state_diff: test_state_diff(),
+        transaction_hashes: test_tx_hashes().into_iter().collect(),
+        consumed_l1_message_nonces: vec![nonce!(1_u8)],
+    };
+    assert_eq!(
+        batcher.add_sync_block(sync_block).await,
+        Err(BatcherError::L1MessageAlreadyConsumed {
+            nonce: nonce!(1_u8),
+            block_number: BlockNumber(1)
+        })
+    );
+}
+
+#[test]
+fn storage_commit_of_a_block_is_atomic() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let class_hash = class_hash!("0x1");
+    let casm = CasmContractClass {
+        prime: Default::default(),
+        compiler_version: Default::default(),
+        bytecode: Default::default(),
+        bytecode_segment_lengths: Default::default(),
+        hints: Default::default(),
+        pythonic_hints: Default::default(),
+        entry_points_by_type: Default::default(),
+    };
+    let commit_block = |storage_writer: &mut StorageWriter, height, l1_message_nonce| {
+        storage_writer.commit_proposal(
+            height,
+            ThinStateDiff::default(),
+            &[(class_hash, casm.clone())],
+            &[l1_message_nonce],
+            &[],
+            None,
+            None,
+        )
+    };
+    let stored_casm = || storage_reader.begin_ro_txn().unwrap().get_casm(&class_hash).unwrap();
+    storage_writer
+        .commit_proposal(
+            BlockNumber(0),
+            ThinStateDiff::default(),
+            &[],
+            &[nonce!(1_u8)],
+            &[],
+            None,
+            None,
+        )
+        .unwrap();
+
+    // The Casms of a block are not written if any other write of the block fails.
+    let next_height = BlockNumber(1);
+    let result = commit_block(&mut storage_writer, next_height, nonce!(1_u8));
+    assert_matches!(result, Err(StorageError::L1MessageAlreadyConsumed { .. }));
+    assert_eq!(storage_reader.height().unwrap(), next_height);
+    assert_eq!(stored_casm(), None);
+
+    commit_block(&mut storage_writer, next_height, nonce!(2_u8)).unwrap();
+    assert_eq!(storage_reader.height().unwrap(), next_height.unchecked_next());
+    assert_eq!(stored_casm(), Some(casm));
+}
+
+#[rstest]
+#[tokio::test]
+async fn add_sync_block_invalidates_declared_classes() {
+    let mut mock_dependencies = MockDependencies::default();
+    let state_diff = ThinStateDiff {
+        declared_classes: indexmap! { class_hash!("0x1") => CompiledClassHash::default() },
+        deprecated_declared_classes: vec![class_hash!("0x2")],
+        ..test_state_diff()
+    };
+
+    mock_dependencies
+        .storage_writer
+        .expect_commit_proposal()
+        .returning(|_, _, _, _, _, _, _| Ok(()));
+    mock_dependencies.mempool_client.expect_commit_block().returning(|_| Ok(()));
+    mock_dependencies
+        .block_builder_factory
+        .expect_invalidate_declared_classes()
+        .times(1)
+        .withf(|class_hashes| class_hashes == [class_hash!("0x1"), class_hash!("0x2")])
+        .return_const(());
+
+    let mut batcher = create_batcher(mock_dependencies);
+
+    let sync_block = SyncBlock {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: INITIAL_HEIGHT,
+            ..Default::default()
+        },
+        state_diff,
+        transaction_hashes: test_tx_hashes().into_iter().collect(),
+        consumed_l1_message_nonces: vec![],
     };
     batcher.add_sync_block(sync_block).await.unwrap();
 }
@@ -554,8 +825,20 @@ async fn decision_reached() {
         .storage_writer
         .expect_commit_proposal()
         .times(1)
-        .with(eq(INITIAL_HEIGHT), eq(expected_artifacts.state_diff()))
-        .returning(|_, _| Ok(()));
+        .with(
+            eq(INITIAL_HEIGHT),
+            eq(expected_artifacts.state_diff()),
+            eq(expected_artifacts.declared_casms()),
+            eq(expected_artifacts.consumed_l1_message_nonces()),
+            // Traces are not archived by default.
+            eq(Vec::<SerializedTransactionTrace>::new()),
+            function(|block_config: &Option<SerializedBlockConfig>| block_config.is_some()),
+            // The revenue of the day is stored with the block.
+            function(|daily_revenue: &Option<(RevenueDay, SerializedDailyRevenue)>| {
+                daily_revenue.as_ref().is_some_and(|(_, revenue)| revenue.0["n_blocks"] == 1)
+            }),
+        )
+        .returning(|_, _, _, _, _, _, _| Ok(()));
 
     mock_create_builder_for_propose_block(
         &mut mock_dependencies.block_builder_factory,
@@ -572,6 +855,140 @@ async fn decision_reached() {
         batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
     assert_eq!(response.state_diff, expected_artifacts.state_diff());
     assert_eq!(response.l2_gas_used, expected_artifacts.l2_gas_used);
+    assert_eq!(response.starknet_version, StarknetVersion::LATEST);
+    let fee_market_info = response.fee_market_info.unwrap();
+    assert_eq!(fee_market_info.block_number, INITIAL_HEIGHT);
+    assert_eq!(batcher.get_fee_market_info().await.unwrap().fee_market_info, Some(fee_market_info));
+
+    let daily_revenue = batcher.get_daily_revenue().await.unwrap().daily_revenue;
+    assert_eq!(daily_revenue.len(), 1);
+    assert_eq!(daily_revenue[0].n_blocks, 1);
+    assert_eq!(daily_revenue[0].revenue, expected_artifacts.revenue);
+}
+
+#[tokio::test]
+async fn daily_revenue_is_restored_from_storage() {
+    let stored_daily_revenue = DailyRevenue {
+        date: NaiveDate::from_ymd_opt(2024, 11, 1).unwrap(),
+        n_blocks: 3,
+        revenue: RevenueBreakdown::default(),
+    };
+    let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_height().returning(|| Ok(INITIAL_HEIGHT));
+    let stored_daily_revenues = vec![to_stored_daily_revenue(&stored_daily_revenue)];
+    storage_reader.expect_get_daily_revenues().return_once(|| Ok(stored_daily_revenues));
+
+    let mut batcher = create_batcher(MockDependencies { storage_reader, ..Default::default() });
+    let daily_revenue = batcher.get_daily_revenue().await.unwrap().daily_revenue;
+    assert_eq!(daily_revenue, vec![stored_daily_revenue]);
+}
+
+#[tokio::test]
+async fn decision_reached_pre_executes_pending_txs() {
+    const PRE_EXECUTION_N_TXS: usize = 2;
+    let pending_tx =
+        executable_invoke_tx(InvokeTxArgs { tx_hash: tx_hash!(7), ..Default::default() });
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.mempool_client.expect_commit_block().returning(|_| Ok(()));
+    mock_dependencies
+        .storage_writer
+        .expect_commit_proposal()
+        .returning(|_, _, _, _, _, _, _| Ok(()));
+    let peeked_tx = pending_tx.clone();
+    mock_dependencies
+        .mempool_client
+        .expect_peek_txs()
+        .times(1)
+        .with(eq(PRE_EXECUTION_N_TXS))
+        .returning(move |_| Ok(vec![peeked_tx.clone()]));
+    mock_create_builder_for_propose_block(
+        &mut mock_dependencies.block_builder_factory,
+        vec![],
+        Ok(BlockExecutionArtifacts::create_for_testing()),
+    );
+    mock_dependencies
+        .block_builder_factory
+        .expect_pre_execute_txs()
+        .times(1)
+        .withf(move |block_info, txs, _| {
+            block_info.block_number == INITIAL_HEIGHT.unchecked_next()
+                && txs == &[Transaction::Account(pending_tx.clone())]
+        })
+        .returning(|_, _, _| Ok(()));
+
+    let mut batcher = create_batcher_with_config(
+        BatcherConfig { pre_execution_n_txs: PRE_EXECUTION_N_TXS, ..Default::default() },
+        mock_dependencies,
+    );
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher.propose_block(propose_block_input(PROPOSAL_ID)).await.unwrap();
+    batcher.await_active_proposal().await;
+    batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+}
+
+#[tokio::test]
+async fn export_block_bundle() {
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.mempool_client.expect_commit_block().returning(|_| Ok(()));
+    mock_dependencies
+        .storage_writer
+        .expect_commit_proposal()
+        .returning(|_, _, _, _, _, _, _| Ok(()));
+    mock_create_builder_for_propose_block(
+        &mut mock_dependencies.block_builder_factory,
+        vec![],
+        Ok(BlockExecutionArtifacts::create_for_testing()),
+    );
+
+    let mut batcher = create_batcher(mock_dependencies);
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher.propose_block(propose_block_input(PROPOSAL_ID)).await.unwrap();
+    batcher.await_active_proposal().await;
+    batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+
+    let archive = batcher
+        .export_block_bundle(ExportBlockBundleInput { height: INITIAL_HEIGHT })
+        .await
+        .unwrap()
+        .archive;
+    let bundle = BlockBundle::from_archive(&archive).unwrap();
+    assert_eq!(bundle.block_info.block_number, INITIAL_HEIGHT);
+
+    let next_height = INITIAL_HEIGHT.unchecked_next();
+    assert_eq!(
+        batcher.export_block_bundle(ExportBlockBundleInput { height: next_height }).await,
+        Err(BatcherError::BlockBundleNotFound { height: next_height })
+    );
+}
+
+#[tokio::test]
+async fn get_block_config() {
+    let mut mock_dependencies = MockDependencies::default();
+    let block_config = SerializedBlockConfig(json!({ "versioned_constants_hash": "0x1" }));
+    let stored_block_config = block_config.clone();
+    mock_dependencies
+        .storage_reader
+        .expect_get_block_config()
+        .with(eq(INITIAL_HEIGHT))
+        .returning(move |_| Ok(Some(stored_block_config.clone())));
+    let next_height = INITIAL_HEIGHT.unchecked_next();
+    mock_dependencies
+        .storage_reader
+        .expect_get_block_config()
+        .with(eq(next_height))
+        .returning(|_| Ok(None));
+
+    let mut batcher = create_batcher(mock_dependencies);
+    let response =
+        batcher.get_block_config(GetBlockConfigInput { height: INITIAL_HEIGHT }).await.unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.block_config).unwrap(),
+        block_config.0
+    );
+    assert_eq!(
+        batcher.get_block_config(GetBlockConfigInput { height: next_height }).await,
+        Err(BatcherError::BlockConfigNotFound { height: next_height })
+    );
 }
 
 #[rstest]
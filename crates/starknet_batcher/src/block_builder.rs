@@ -1,6 +1,8 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use async_trait::async_trait;
+use blockifier::blockifier::block::BlockRandomness;
+use blockifier::blockifier::block_bundle::BlockStateReads;
 use blockifier::blockifier::config::TransactionExecutorConfig;
 use blockifier::blockifier::transaction_executor::{
     TransactionExecutor,
@@ -10,31 +12,53 @@ use blockifier::blockifier::transaction_executor::{
 };
 use blockifier::bouncer::{BouncerConfig, BouncerWeights};
 use blockifier::context::{BlockContext, ChainInfo};
-use blockifier::state::cached_state::CommitmentStateDiff;
+use blockifier::execution::cancellation::CancellationToken;
+use blockifier::state::cached_state::{CachedState, CommitmentStateDiff};
 use blockifier::state::contract_class_manager::ContractClassManager;
 use blockifier::state::errors::StateError;
 use blockifier::transaction::objects::TransactionExecutionInfo;
 use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
-use blockifier::versioned_constants::{VersionedConstants, VersionedConstantsOverrides};
+use blockifier::transaction::transaction_types::TransactionType;
+use blockifier::versioned_constants::{
+    VersionedConstants,
+    VersionedConstantsError,
+    VersionedConstantsOverrides,
+};
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use indexmap::IndexMap;
 #[cfg(test)]
 use mockall::automock;
-use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use papyrus_execution::execution_utils::transaction_trace;
+use papyrus_execution::ExecutionError;
 use papyrus_state_reader::papyrus_state::PapyrusReader;
+use papyrus_state_reader::state_reads_cache::StateReadsCache;
+use papyrus_storage::trace::SerializedTransactionTrace;
 use papyrus_storage::StorageReader;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockHashAndNumber, BlockInfo};
+use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber, StarknetVersionSchedule};
 use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
-use starknet_api::core::{ContractAddress, Nonce};
-use starknet_api::executable_transaction::Transaction;
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::crypto::utils::Signature;
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::error_context::{ContextualError, ErrorContext};
+use starknet_api::executable_transaction::{AccountTransaction, Transaction};
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::TransactionHash;
-use starknet_batcher_types::batcher_types::ProposalCommitment;
+use starknet_batcher_types::batcher_types::{ProposalCommitment, RevenueBreakdown};
 use thiserror::Error;
 use tracing::{debug, error, info, trace};
 
+use crate::metrics::{record_conflict_stats, record_contract_cache_stats};
+use crate::revenue::record_tx_revenue;
 use crate::transaction_executor::TransactionExecutorTrait;
 use crate::transaction_provider::{NextTxs, TransactionProvider, TransactionProviderError};
 
@@ -49,9 +73,13 @@ pub enum BlockBuilderError {
     #[error(transparent)]
     StreamTransactionsError(#[from] tokio::sync::mpsc::error::SendError<Transaction>),
     #[error(transparent)]
+    VersionedConstantsError(#[from] VersionedConstantsError),
+    #[error(transparent)]
     FailOnError(FailOnErrorCause),
     #[error("The block builder was aborted.")]
     Aborted,
+    #[error("The block has no randomness signature, which the chain requires.")]
+    MissingRandomnessSignature,
 }
 
 pub type BlockBuilderResult<T> = Result<T, BlockBuilderError>;
@@ -63,7 +91,7 @@ pub enum FailOnErrorCause {
     #[error("Deadline has been reached")]
     DeadlineReached,
     #[error("Transaction failed: {0}")]
-    TransactionFailed(BlockifierTransactionExecutorError),
+    TransactionFailed(ContextualError<BlockifierTransactionExecutorError>),
 }
 
 #[cfg_attr(test, derive(Clone))]
@@ -71,9 +99,17 @@ pub enum FailOnErrorCause {
 pub struct BlockExecutionArtifacts {
     pub execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo>,
     pub commitment_state_diff: CommitmentStateDiff,
+    // The part of the state diff (storage updates and nonces) published to an alternative data
+    // availability layer rather than on L1.
+    pub alternative_da_state_diff: CommitmentStateDiff,
     pub visited_segments_mapping: VisitedSegmentsMapping,
     pub bouncer_weights: BouncerWeights,
     pub l2_gas_used: GasAmount,
+    pub revenue: RevenueBreakdown,
+    // The transactions included in the block, in execution order.
+    pub transactions: Vec<Transaction>,
+    // The reads of the parent state, kept to allow re-executing the block offline.
+    pub state_reads: BlockStateReads,
 }
 
 impl BlockExecutionArtifacts {
@@ -90,6 +126,60 @@ impl BlockExecutionArtifacts {
         HashSet::from_iter(self.execution_infos.keys().copied())
     }
 
+    /// Returns the nonces of the L1 messages consumed by the L1 handler transactions of the block.
+    pub fn consumed_l1_message_nonces(&self) -> Vec<Nonce> {
+        self.transactions
+            .iter()
+            .filter_map(|tx| match tx {
+                Transaction::L1Handler(l1_handler_tx) => Some(l1_handler_tx.tx.nonce),
+                Transaction::Account(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the Casms of the Cairo 1 classes declared in the block.
+    pub fn declared_casms(&self) -> Vec<(ClassHash, CasmContractClass)> {
+        self.transactions
+            .iter()
+            .filter_map(|tx| match tx {
+                Transaction::Account(AccountTransaction::Declare(declare_tx)) => {
+                    match &declare_tx.class_info.contract_class {
+                        ContractClass::V1((casm, _)) => {
+                            Some((declare_tx.class_hash(), casm.clone()))
+                        }
+                        ContractClass::V0(_) => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the execution traces of the transactions of the block, in execution order.
+    #[allow(clippy::result_large_err)]
+    pub fn transaction_traces(&self) -> Result<Vec<SerializedTransactionTrace>, ExecutionError> {
+        self.transactions
+            .iter()
+            .zip(self.execution_infos.values())
+            .map(|(tx, execution_info)| {
+                let tx_type = match tx {
+                    Transaction::Account(AccountTransaction::Declare(_)) => {
+                        TransactionType::Declare
+                    }
+                    Transaction::Account(AccountTransaction::DeployAccount(_)) => {
+                        TransactionType::DeployAccount
+                    }
+                    Transaction::Account(AccountTransaction::Invoke(_)) => {
+                        TransactionType::InvokeFunction
+                    }
+                    Transaction::L1Handler(_) => TransactionType::L1Handler,
+                };
+                let trace = transaction_trace(tx_type, execution_info.clone())?;
+                Ok(SerializedTransactionTrace(serde_json::to_value(trace)?))
+            })
+            .collect()
+    }
+
     pub fn state_diff(&self) -> ThinStateDiff {
         // TODO(Ayelet): Remove the clones.
         let storage_diffs = self.commitment_state_diff.storage_updates.clone();
@@ -130,6 +220,10 @@ pub struct BlockBuilder {
     tx_provider: Box<dyn TransactionProvider>,
     output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
     abort_signal_receiver: tokio::sync::oneshot::Receiver<()>,
+    // The height of the built block, identifying it in the errors of its transactions.
+    block_number: BlockNumber,
+    // The chain the block is built for, telling the token each collected fee was paid in.
+    chain_info: ChainInfo,
 
     // Parameters to configure the block builder behavior.
     tx_chunk_size: usize,
@@ -137,11 +231,14 @@ pub struct BlockBuilder {
 }
 
 impl BlockBuilder {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         executor: Box<dyn TransactionExecutorTrait>,
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
         abort_signal_receiver: tokio::sync::oneshot::Receiver<()>,
+        block_number: BlockNumber,
+        chain_info: ChainInfo,
         tx_chunk_size: usize,
         execution_params: BlockBuilderExecutionParams,
     ) -> Self {
@@ -150,6 +247,8 @@ impl BlockBuilder {
             tx_provider,
             output_content_sender,
             abort_signal_receiver,
+            block_number,
+            chain_info,
             tx_chunk_size,
             execution_params,
         }
@@ -162,6 +261,8 @@ impl BlockBuilderTrait for BlockBuilder {
         let mut block_is_full = false;
         let mut execution_infos = IndexMap::new();
         let mut l2_gas_used = GasAmount::ZERO;
+        let mut revenue = RevenueBreakdown::default();
+        let mut transactions = Vec::new();
         // TODO(yael 6/10/2024): delete the timeout condition once the executor has a timeout
         while !block_is_full {
             if tokio::time::Instant::now() >= self.execution_params.deadline {
@@ -199,44 +300,67 @@ impl BlockBuilderTrait for BlockBuilder {
                 next_tx_chunk,
                 results,
                 &mut l2_gas_used,
+                &mut revenue,
                 &mut execution_infos,
+                &mut transactions,
                 &self.output_content_sender,
+                self.block_number,
+                &self.chain_info,
                 self.execution_params.fail_on_err,
             )
             .await?;
         }
+        let state_reads = self.executor.get_block_state_reads()?;
         let (commitment_state_diff, visited_segments_mapping, bouncer_weights) =
             self.executor.close_block()?;
+        let alternative_da_state_diff =
+            self.executor.alternative_da_state_diff(&commitment_state_diff);
+        record_conflict_stats(&self.executor.conflict_stats());
         Ok(BlockExecutionArtifacts {
             execution_infos,
             commitment_state_diff,
+            alternative_da_state_diff,
             visited_segments_mapping,
             bouncer_weights,
             l2_gas_used,
+            revenue,
+            transactions,
+            state_reads,
         })
     }
 }
 
 /// Returns true if the block is full and should be closed, false otherwise.
+#[allow(clippy::too_many_arguments)]
 async fn collect_execution_results_and_stream_txs(
     tx_chunk: Vec<Transaction>,
     results: Vec<TransactionExecutorResult<TransactionExecutionInfo>>,
     l2_gas_used: &mut GasAmount,
+    revenue: &mut RevenueBreakdown,
     execution_infos: &mut IndexMap<TransactionHash, TransactionExecutionInfo>,
+    transactions: &mut Vec<Transaction>,
     output_content_sender: &Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
+    block_number: BlockNumber,
+    chain_info: &ChainInfo,
     fail_on_err: bool,
 ) -> BlockBuilderResult<bool> {
     for (input_tx, result) in tx_chunk.into_iter().zip(results.into_iter()) {
         match result {
             Ok(tx_execution_info) => {
                 *l2_gas_used += tx_execution_info.receipt.gas.l2_gas;
+                record_tx_revenue(revenue, chain_info, &input_tx, &tx_execution_info);
                 execution_infos.insert(input_tx.tx_hash(), tx_execution_info);
+                transactions.push(input_tx.clone());
                 if let Some(output_content_sender) = output_content_sender {
                     output_content_sender.send(input_tx)?;
                 }
             }
             // TODO(yael 18/9/2024): add timeout error handling here once this
             // feature is added.
+            Err(BlockifierTransactionExecutorError::Cancelled) => {
+                info!("Transaction execution was cancelled. Aborting block builder.");
+                return Err(BlockBuilderError::Aborted);
+            }
             Err(BlockifierTransactionExecutorError::BlockFull) => {
                 info!("Block is full");
                 if fail_on_err {
@@ -245,6 +369,10 @@ async fn collect_execution_results_and_stream_txs(
                 return Ok(true);
             }
             Err(err) => {
+                let err = ErrorContext::new("batcher")
+                    .with_block_number(block_number)
+                    .with_tx_hash(input_tx.tx_hash())
+                    .wrap(err);
                 debug!("Transaction {:?} failed with error: {}.", input_tx, err);
                 if fail_on_err {
                     return Err(BlockBuilderError::FailOnError(
@@ -257,13 +385,34 @@ async fn collect_execution_results_and_stream_txs(
     Ok(false)
 }
 
+#[derive(Clone, Debug)]
 pub struct BlockMetadata {
     pub block_info: BlockInfo,
     pub retrospective_block_hash: Option<BlockHashAndNumber>,
+    pub randomness_signature: Option<Signature>,
 }
 
-// Type definitions for the abort channel required to abort the block builder.
-pub type AbortSignalSender = tokio::sync::oneshot::Sender<()>;
+/// Aborts a block builder: signals it to stop before its next transaction chunk, and cancels the
+/// execution of the chunk in flight.
+pub struct AbortSignalSender {
+    signal_sender: tokio::sync::oneshot::Sender<()>,
+    cancellation_token: CancellationToken,
+}
+
+impl AbortSignalSender {
+    pub fn new(
+        signal_sender: tokio::sync::oneshot::Sender<()>,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        Self { signal_sender, cancellation_token }
+    }
+
+    pub fn abort(self) {
+        self.cancellation_token.cancel();
+        // The block builder may have already finished.
+        self.signal_sender.send(()).ok();
+    }
+}
 
 /// The BlockBuilderFactoryTrait is responsible for creating a new block builder.
 #[cfg_attr(test, automock)]
@@ -275,6 +424,20 @@ pub trait BlockBuilderFactoryTrait: Send + Sync {
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
     ) -> BlockBuilderResult<(Box<dyn BlockBuilderTrait>, AbortSignalSender)>;
+
+    /// Speculatively executes the given transactions on top of the latest committed state, in the
+    /// background and discarding the results, to warm up the caches of the next block's execution.
+    /// The execution stops early if the given token is cancelled.
+    fn pre_execute_txs(
+        &self,
+        block_info: BlockInfo,
+        txs: Vec<Transaction>,
+        cancellation_token: CancellationToken,
+    ) -> BlockBuilderResult<()>;
+
+    /// Drops the given classes, declared by a committed block, from the class cache shared by the
+    /// block builders.
+    fn invalidate_declared_classes(&self, class_hashes: &[ClassHash]);
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -284,6 +447,14 @@ pub struct BlockBuilderConfig {
     pub bouncer_config: BouncerConfig,
     pub tx_chunk_size: usize,
     pub versioned_constants_overrides: VersionedConstantsOverrides,
+    pub starknet_version_schedule: StarknetVersionSchedule,
+    // If true, the state diffs of all blocks are published to an alternative data availability
+    // layer, regardless of the modes chosen by their transactions.
+    pub alternative_da_blocks: bool,
+    // If set, a per-block randomness seed is written to this system contract at block start.
+    pub randomness_contract_address: Option<ContractAddress>,
+    // If true, L1 handler transactions must have paid at least their actual fee on L1.
+    pub enforce_l1_handler_fee: bool,
 }
 
 impl Default for BlockBuilderConfig {
@@ -295,6 +466,10 @@ impl Default for BlockBuilderConfig {
             bouncer_config: BouncerConfig::default(),
             tx_chunk_size: 100,
             versioned_constants_overrides: VersionedConstantsOverrides::default(),
+            starknet_version_schedule: StarknetVersionSchedule::default(),
+            alternative_da_blocks: false,
+            randomness_contract_address: None,
+            enforce_l1_handler_fee: false,
         }
     }
 }
@@ -314,6 +489,37 @@ impl SerializeConfig for BlockBuilderConfig {
             self.versioned_constants_overrides.dump(),
             "versioned_constants_overrides",
         ));
+        dump.append(&mut BTreeMap::from([ser_param(
+            "starknet_version_schedule",
+            &self.starknet_version_schedule,
+            "Space-separated <block_number>:<starknet_version> and \
+             t<unix_timestamp>:<starknet_version> pairs, including block 0; each block is \
+             executed with the versioned constants of the highest version activated by its height \
+             or timestamp.",
+            ParamPrivacyInput::Public,
+        )]));
+        dump.append(&mut BTreeMap::from([ser_param(
+            "alternative_da_blocks",
+            &self.alternative_da_blocks,
+            "If true, the state diffs of all blocks are published to an alternative data \
+             availability layer, regardless of the modes chosen by their transactions.",
+            ParamPrivacyInput::Public,
+        )]));
+        dump.append(&mut ser_optional_param(
+            &self.randomness_contract_address,
+            ContractAddress::default(),
+            "randomness_contract_address",
+            "If set, the system contract in whose storage the randomness seed of each block is \
+             written, under the block number, at block start.",
+            ParamPrivacyInput::Public,
+        ));
+        dump.append(&mut BTreeMap::from([ser_param(
+            "enforce_l1_handler_fee",
+            &self.enforce_l1_handler_fee,
+            "If true, L1 handler transactions must have paid at least their actual fee on L1; \
+             otherwise, any positive paid fee is accepted.",
+            ParamPrivacyInput::Public,
+        )]));
         dump
     }
 }
@@ -322,36 +528,69 @@ pub struct BlockBuilderFactory {
     pub block_builder_config: BlockBuilderConfig,
     pub storage_reader: StorageReader,
     pub contract_class_manager: ContractClassManager,
+    // Shares the state reads between the pre-execution of pending transactions and the building
+    // of the following block.
+    pub state_reads_cache: StateReadsCache,
 }
 
 impl BlockBuilderFactory {
-    fn preprocess_and_create_transaction_executor(
+    fn create_block_context(
         &self,
-        block_metadata: BlockMetadata,
-    ) -> BlockBuilderResult<TransactionExecutor<PapyrusReader>> {
-        let height = block_metadata.block_info.block_number;
+        block_info: BlockInfo,
+        cancellation_token: CancellationToken,
+    ) -> BlockBuilderResult<BlockContext> {
+        let (height, timestamp) = (block_info.block_number, block_info.block_timestamp);
         let block_builder_config = self.block_builder_config.clone();
-        let versioned_constants = VersionedConstants::get_versioned_constants(
+        let versioned_constants = VersionedConstants::get_scheduled_versioned_constants(
+            &block_builder_config.starknet_version_schedule,
+            height,
+            timestamp,
             block_builder_config.versioned_constants_overrides,
-        );
-        let block_context = BlockContext::new(
-            block_metadata.block_info,
+        )?;
+        let starknet_version =
+            block_builder_config.starknet_version_schedule.version_at(height, timestamp);
+        let mut block_context = BlockContext::new(
+            block_info,
             block_builder_config.chain_info,
             versioned_constants,
             block_builder_config.bouncer_config,
-        );
+        )
+        .with_starknet_version(starknet_version)
+        .with_cancellation_token(cancellation_token);
+        if block_builder_config.alternative_da_blocks {
+            block_context = block_context.with_state_diff_da_mode(DataAvailabilityMode::L2);
+        }
+        if block_builder_config.enforce_l1_handler_fee {
+            block_context = block_context.with_l1_handler_fee_enforcement();
+        }
+        Ok(block_context)
+    }
 
-        let state_reader = PapyrusReader::new(
-            self.storage_reader.clone(),
-            height,
-            self.contract_class_manager.clone(),
-        );
+    fn create_state_reader(&self, height: BlockNumber) -> PapyrusReader {
+        PapyrusReader::new(self.storage_reader.clone(), height, self.contract_class_manager.clone())
+            .with_reads_cache(self.state_reads_cache.clone())
+    }
 
+    fn preprocess_and_create_transaction_executor(
+        &self,
+        block_metadata: BlockMetadata,
+        cancellation_token: CancellationToken,
+    ) -> BlockBuilderResult<TransactionExecutor<PapyrusReader>> {
+        let height = block_metadata.block_info.block_number;
+        let mut block_context =
+            self.create_block_context(block_metadata.block_info, cancellation_token)?;
+        if let Some(contract_address) = self.block_builder_config.randomness_contract_address {
+            let proposer_signature = block_metadata
+                .randomness_signature
+                .ok_or(BlockBuilderError::MissingRandomnessSignature)?;
+            block_context = block_context
+                .with_randomness(BlockRandomness { contract_address, proposer_signature });
+        }
         let executor = TransactionExecutor::pre_process_and_create(
-            state_reader,
+            self.create_state_reader(height),
             block_context,
             block_metadata.retrospective_block_hash,
-            block_builder_config.execute_config,
+            self.block_builder_config.execute_config.clone(),
         )?;
 
         Ok(executor)
@@ -366,16 +605,53 @@ impl BlockBuilderFactoryTrait for BlockBuilderFactory {
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
     ) -> BlockBuilderResult<(Box<dyn BlockBuilderTrait>, AbortSignalSender)> {
-        let executor = self.preprocess_and_create_transaction_executor(block_metadata)?;
-        let (abort_signal_sender, abort_signal_receiver) = tokio::sync::oneshot::channel();
+        let block_number = block_metadata.block_info.block_number;
+        record_contract_cache_stats(&self.contract_class_manager.cache_stats());
+        let cancellation_token = CancellationToken::default();
+        let executor = self.preprocess_and_create_transaction_executor(
+            block_metadata,
+            cancellation_token.clone(),
+        )?;
+        let (signal_sender, abort_signal_receiver) = tokio::sync::oneshot::channel();
         let block_builder = Box::new(BlockBuilder::new(
             Box::new(executor),
             tx_provider,
             output_content_sender,
             abort_signal_receiver,
+            block_number,
+            self.block_builder_config.chain_info.clone(),
             self.block_builder_config.tx_chunk_size,
             execution_params,
         ));
-        Ok((block_builder, abort_signal_sender))
+        Ok((block_builder, AbortSignalSender::new(signal_sender, cancellation_token)))
+    }
+
+    fn pre_execute_txs(
+        &self,
+        block_info: BlockInfo,
+        txs: Vec<Transaction>,
+        cancellation_token: CancellationToken,
+    ) -> BlockBuilderResult<()> {
+        let height = block_info.block_number;
+        let block_context = self.create_block_context(block_info, cancellation_token)?;
+        // The block pre-processing is skipped, as the execution results are discarded.
+        let mut executor = TransactionExecutor::new(
+            CachedState::new(self.create_state_reader(height)),
+            block_context,
+            self.block_builder_config.execute_config.clone(),
+        );
+        tokio::task::spawn_blocking(move || {
+            let txs: Vec<_> =
+                txs.into_iter().map(BlockifierTransaction::new_for_sequencing).collect();
+            let n_executed_txs = executor.execute_txs(&txs).len();
+            debug!("Pre-executed {n_executed_txs} pending transactions of block {height}.");
+        });
+        Ok(())
+    }
+
+    fn invalidate_declared_classes(&self, class_hashes: &[ClassHash]) {
+        for class_hash in class_hashes {
+            self.contract_class_manager.invalidate(class_hash);
+        }
     }
 }
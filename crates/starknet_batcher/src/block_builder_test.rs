@@ -1,20 +1,25 @@
 use assert_matches::assert_matches;
+use blockifier::blockifier::block_bundle::BlockStateReads;
 use blockifier::blockifier::transaction_executor::TransactionExecutorError;
 use blockifier::bouncer::BouncerWeights;
+use blockifier::concurrency::conflict_stats::ConflictStats;
+use blockifier::context::ChainInfo;
 use blockifier::fee::fee_checks::FeeCheckError;
 use blockifier::fee::receipt::TransactionReceipt;
+use blockifier::state::cached_state::CommitmentStateDiff;
 use blockifier::state::errors::StateError;
 use blockifier::transaction::objects::{RevertError, TransactionExecutionInfo};
 use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
-use indexmap::{indexmap, IndexMap};
+use indexmap::IndexMap;
 use mockall::predicate::eq;
 use mockall::Sequence;
 use rstest::rstest;
+use starknet_api::block::BlockNumber;
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::execution_resources::{GasAmount, GasVector};
 use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::TransactionHash;
-use starknet_api::tx_hash;
+use starknet_batcher_types::batcher_types::RevenueBreakdown;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 use crate::block_builder::{
@@ -46,17 +51,22 @@ fn output_channel() -> (UnboundedSender<Transaction>, UnboundedReceiver<Transact
     tokio::sync::mpsc::unbounded_channel()
 }
 
-fn block_execution_artifacts(
-    execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo>,
-) -> BlockExecutionArtifacts {
+fn block_execution_artifacts(transactions: Vec<Transaction>) -> BlockExecutionArtifacts {
+    let execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo> =
+        transactions.iter().map(|tx| (tx.tx_hash(), execution_info())).collect();
     let l2_gas_used = GasAmount(execution_infos.len().try_into().unwrap());
     BlockExecutionArtifacts {
         execution_infos,
         commitment_state_diff: Default::default(),
+        alternative_da_state_diff: Default::default(),
         visited_segments_mapping: Default::default(),
         bouncer_weights: BouncerWeights { l1_gas: 100, ..BouncerWeights::empty() },
         // Each mock transaction uses 1 L2 gas so the total amount should be the number of txs.
         l2_gas_used,
+        // The mock execution infos charge no fee, so no revenue is recorded.
+        revenue: RevenueBreakdown::default(),
+        transactions,
+        state_reads: BlockStateReads::default(),
     }
 }
 
@@ -255,12 +265,12 @@ fn transaction_failed_test_expectations() -> TestExpectations {
         vec![Ok(execution_info()), Err(execution_error), Ok(execution_info())]
     });
 
-    let execution_infos_mapping = indexmap![
-        tx_hash!(0)=> execution_info(),
-        tx_hash!(2)=> execution_info(),
-    ];
-    let expected_block_artifacts = block_execution_artifacts(execution_infos_mapping);
+    let expected_block_artifacts = block_execution_artifacts(expected_txs_output.clone());
     let expected_block_artifacts_copy = expected_block_artifacts.clone();
+    mock_transaction_executor
+        .expect_get_block_state_reads()
+        .times(1)
+        .return_once(|| Ok(BlockStateReads::default()));
     mock_transaction_executor.expect_close_block().times(1).return_once(move || {
         Ok((
             expected_block_artifacts_copy.commitment_state_diff,
@@ -268,6 +278,11 @@ fn transaction_failed_test_expectations() -> TestExpectations {
             expected_block_artifacts_copy.bouncer_weights,
         ))
     });
+    mock_transaction_executor
+        .expect_alternative_da_state_diff()
+        .times(1)
+        .returning(|_| CommitmentStateDiff::default());
+    mock_transaction_executor.expect_conflict_stats().times(1).returning(ConflictStats::default);
 
     let mock_tx_provider = mock_tx_provider_limitless_calls(1, vec![input_txs]);
 
@@ -282,10 +297,7 @@ fn transaction_failed_test_expectations() -> TestExpectations {
 // Fill the executor outputs with some non-default values to make sure the block_builder uses
 // them.
 fn block_builder_expected_output(execution_info_len: usize) -> BlockExecutionArtifacts {
-    let execution_info_len_u8 = u8::try_from(execution_info_len).unwrap();
-    let execution_infos_mapping =
-        (0..execution_info_len_u8).map(|i| (tx_hash!(i), execution_info())).collect();
-    block_execution_artifacts(execution_infos_mapping)
+    block_execution_artifacts(test_txs(0..execution_info_len))
 }
 
 fn set_close_block_expectations(
@@ -294,6 +306,10 @@ fn set_close_block_expectations(
 ) -> BlockExecutionArtifacts {
     let output_block_artifacts = block_builder_expected_output(block_size);
     let output_block_artifacts_copy = output_block_artifacts.clone();
+    mock_transaction_executor
+        .expect_get_block_state_reads()
+        .times(1)
+        .return_once(|| Ok(BlockStateReads::default()));
     mock_transaction_executor.expect_close_block().times(1).return_once(move || {
         Ok((
             output_block_artifacts.commitment_state_diff,
@@ -301,6 +317,11 @@ fn set_close_block_expectations(
             output_block_artifacts.bouncer_weights,
         ))
     });
+    mock_transaction_executor
+        .expect_alternative_da_state_diff()
+        .times(1)
+        .returning(|_| CommitmentStateDiff::default());
+    mock_transaction_executor.expect_conflict_stats().times(1).returning(ConflictStats::default);
     output_block_artifacts_copy
 }
 
@@ -396,6 +417,8 @@ async fn run_build_block(
         Box::new(tx_provider),
         output_sender,
         abort_receiver,
+        BlockNumber(0),
+        ChainInfo::default(),
         TX_CHUNK_SIZE,
         BlockBuilderExecutionParams { deadline, fail_on_err },
     );
@@ -554,6 +577,37 @@ async fn test_build_block_abort_immediately() {
     );
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_build_block_cancelled_execution() {
+    let mock_tx_provider = mock_tx_provider_limitless_calls(1, vec![test_txs(0..3)]);
+
+    // The execution of the chunk is cancelled after its first transaction.
+    let mut mock_transaction_executor = MockTransactionExecutorTrait::new();
+    mock_transaction_executor
+        .expect_add_txs_to_block()
+        .return_once(|_| vec![Ok(execution_info()), Err(TransactionExecutorError::Cancelled)]);
+    mock_transaction_executor.expect_close_block().times(0);
+
+    let (output_tx_sender, mut output_tx_receiver) = output_channel();
+    let (_abort_sender, abort_receiver) = tokio::sync::oneshot::channel();
+
+    assert_matches!(
+        run_build_block(
+            mock_transaction_executor,
+            mock_tx_provider,
+            Some(output_tx_sender),
+            false,
+            abort_receiver,
+            BLOCK_GENERATION_LONG_DEADLINE_SECS,
+        )
+        .await,
+        Err(BlockBuilderError::Aborted)
+    );
+    assert_eq!(output_tx_receiver.recv().await, Some(test_txs(0..1).remove(0)));
+    assert_eq!(output_tx_receiver.recv().await, None);
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_l2_gas_used() {
@@ -0,0 +1,42 @@
+use std::collections::{BTreeMap, HashSet};
+
+use blockifier::blockifier::block_bundle::BlockBundle;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::ClassHash;
+
+#[cfg(test)]
+#[path = "block_bundles_test.rs"]
+mod block_bundles_test;
+
+/// The bundle of a committed block, without its classes; these are read from the storage when the
+/// bundle is exported.
+#[derive(Clone, Debug)]
+pub(crate) struct RetainedBlock {
+    pub bundle: BlockBundle,
+    pub class_hashes: HashSet<ClassHash>,
+}
+
+/// Keeps the bundles of the most recent blocks built by the batcher, so they can be exported for
+/// offline re-execution.
+#[derive(Debug)]
+pub(crate) struct RetainedBlocks {
+    capacity: usize,
+    blocks: BTreeMap<BlockNumber, RetainedBlock>,
+}
+
+impl RetainedBlocks {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, blocks: BTreeMap::new() }
+    }
+
+    pub fn insert(&mut self, height: BlockNumber, block: RetainedBlock) {
+        self.blocks.insert(height, block);
+        while self.blocks.len() > self.capacity {
+            self.blocks.pop_first();
+        }
+    }
+
+    pub fn get(&self, height: BlockNumber) -> Option<&RetainedBlock> {
+        self.blocks.get(&height)
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+
+use blockifier::blockifier::block_bundle::BlockBundle;
+use blockifier::bouncer::BouncerConfig;
+use blockifier::context::ChainInfo;
+use blockifier::versioned_constants::VersionedConstantsOverrides;
+use starknet_api::block::{BlockInfo, BlockNumber, StarknetVersion};
+
+use crate::block_bundles::{RetainedBlock, RetainedBlocks};
+
+fn retained_block(height: BlockNumber) -> RetainedBlock {
+    RetainedBlock {
+        bundle: BlockBundle {
+            block_info: BlockInfo { block_number: height, ..BlockInfo::create_for_testing() },
+            chain_info: ChainInfo::default(),
+            starknet_version: StarknetVersion::LATEST,
+            versioned_constants_overrides: VersionedConstantsOverrides::default(),
+            bouncer_config: BouncerConfig::max(),
+            retrospective_block_hash: None,
+            transactions: Vec::new(),
+            state_reads: Default::default(),
+            classes: Vec::new(),
+        },
+        class_hashes: HashSet::new(),
+    }
+}
+
+#[test]
+fn retained_blocks_keep_most_recent() {
+    let mut retained_blocks = RetainedBlocks::new(2);
+    for height in 0..3 {
+        retained_blocks.insert(BlockNumber(height), retained_block(BlockNumber(height)));
+    }
+
+    assert!(retained_blocks.get(BlockNumber(0)).is_none());
+    for height in 1..3 {
+        let retained_block = retained_blocks.get(BlockNumber(height)).unwrap();
+        assert_eq!(retained_block.bundle.block_info.block_number, BlockNumber(height));
+    }
+}
+
+#[test]
+fn retention_disabled() {
+    let mut retained_blocks = RetainedBlocks::new(0);
+    retained_blocks.insert(BlockNumber(0), retained_block(BlockNumber(0)));
+
+    assert!(retained_blocks.get(BlockNumber(0)).is_none());
+}
@@ -0,0 +1,88 @@
+use blockifier::bouncer::BouncerWeights;
+use blockifier::versioned_constants::VersionedConstantsOverrides;
+use papyrus_storage::block_config::SerializedBlockConfig;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockInfo, GasPrices, StarknetVersion};
+use starknet_api::execution_resources::GasAmount;
+use starknet_api::hash::{starknet_keccak_hash, StarkHash};
+
+use crate::block_builder::BlockBuilderConfig;
+use crate::fee_market::{GAS_PRICE_MAX_CHANGE_DENOMINATOR, MAX_BLOCK_SIZE, MIN_GAS_PRICE};
+use crate::l1_gas_prices::StaleL1GasPricesConfig;
+
+#[cfg(test)]
+#[path = "block_config_test.rs"]
+mod block_config_test;
+
+/// The configuration that governed the execution of a block, recorded when the block is committed
+/// so that replays and audits can reconstruct the parameters of any historical block.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BlockConfig {
+    pub starknet_version: StarknetVersion,
+    /// Identifies the versioned constants the block was executed with: those of its Starknet
+    /// version, with the configured overrides applied.
+    pub versioned_constants_hash: StarkHash,
+    /// The maximal weights of the block, enforced by the bouncer.
+    pub bouncer_weights: BouncerWeights,
+    pub gas_price_params: GasPriceParams,
+}
+
+/// The parameters that determined the gas prices of a block.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GasPriceParams {
+    /// The gas prices the block was executed with.
+    pub gas_prices: GasPrices,
+    /// The L2 gas the block used, which determines the L2 gas price of the next block.
+    pub l2_gas_used: GasAmount,
+    pub stale_l1_gas_prices_config: StaleL1GasPricesConfig,
+    pub min_l2_gas_price: u64,
+    pub gas_price_max_change_denominator: u128,
+    pub max_block_size: u64,
+}
+
+impl BlockConfig {
+    pub fn new(
+        block_info: &BlockInfo,
+        block_builder_config: &BlockBuilderConfig,
+        stale_l1_gas_prices_config: &StaleL1GasPricesConfig,
+        l2_gas_used: GasAmount,
+    ) -> Self {
+        let starknet_version = block_builder_config
+            .starknet_version_schedule
+            .version_at(block_info.block_number, block_info.block_timestamp);
+        Self {
+            starknet_version,
+            versioned_constants_hash: versioned_constants_hash(
+                starknet_version,
+                &block_builder_config.versioned_constants_overrides,
+            ),
+            bouncer_weights: block_builder_config.bouncer_config.block_max_capacity,
+            gas_price_params: GasPriceParams {
+                gas_prices: block_info.gas_prices.clone(),
+                l2_gas_used,
+                stale_l1_gas_prices_config: stale_l1_gas_prices_config.clone(),
+                min_l2_gas_price: MIN_GAS_PRICE,
+                gas_price_max_change_denominator: GAS_PRICE_MAX_CHANGE_DENOMINATOR,
+                max_block_size: MAX_BLOCK_SIZE,
+            },
+        }
+    }
+
+    pub fn to_serialized(&self) -> SerializedBlockConfig {
+        SerializedBlockConfig(
+            serde_json::to_value(self).expect("Block config should be serializable to JSON."),
+        )
+    }
+}
+
+// The versioned constants of a Starknet version are fixed, so the version and the overrides
+// determine the constants the block was executed with.
+fn versioned_constants_hash(
+    starknet_version: StarknetVersion,
+    versioned_constants_overrides: &VersionedConstantsOverrides,
+) -> StarkHash {
+    let serialized_constants =
+        serde_json::to_vec(&(starknet_version, versioned_constants_overrides))
+            .expect("Versioned constants overrides should be serializable to JSON.");
+    starknet_keccak_hash(&serialized_constants)
+}
@@ -0,0 +1,59 @@
+use blockifier::versioned_constants::VersionedConstantsOverrides;
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::execution_resources::GasAmount;
+
+use crate::block_builder::BlockBuilderConfig;
+use crate::block_config::BlockConfig;
+use crate::l1_gas_prices::StaleL1GasPricesConfig;
+
+#[test]
+fn block_config_serialization_round_trip() {
+    let block_info = BlockInfo { block_number: BlockNumber(7), ..BlockInfo::create_for_testing() };
+    let block_config = BlockConfig::new(
+        &block_info,
+        &BlockBuilderConfig::default(),
+        &StaleL1GasPricesConfig::default(),
+        GasAmount(1000),
+    );
+
+    let serialized_block_config = block_config.to_serialized();
+    assert_eq!(
+        serde_json::from_value::<BlockConfig>(serialized_block_config.0).unwrap(),
+        block_config
+    );
+}
+
+#[test]
+fn versioned_constants_hash_depends_on_overrides() {
+    let block_info = BlockInfo::create_for_testing();
+    let stale_l1_gas_prices_config = StaleL1GasPricesConfig::default();
+    let block_builder_config = BlockBuilderConfig::default();
+    let block_config = BlockConfig::new(
+        &block_info,
+        &block_builder_config,
+        &stale_l1_gas_prices_config,
+        GasAmount::default(),
+    );
+
+    let overridden_block_builder_config = BlockBuilderConfig {
+        versioned_constants_overrides: VersionedConstantsOverrides {
+            validate_max_n_steps: block_builder_config
+                .versioned_constants_overrides
+                .validate_max_n_steps
+                + 1,
+            ..block_builder_config.versioned_constants_overrides.clone()
+        },
+        ..block_builder_config
+    };
+    let overridden_block_config = BlockConfig::new(
+        &block_info,
+        &overridden_block_builder_config,
+        &stale_l1_gas_prices_config,
+        GasAmount::default(),
+    );
+
+    assert_ne!(
+        block_config.versioned_constants_hash,
+        overridden_block_config.versioned_constants_hash
+    );
+}
@@ -36,6 +36,21 @@ impl ComponentRequestHandler<BatcherRequest, BatcherResponse> for Batcher {
             BatcherRequest::AddSyncBlock(sync_block) => {
                 BatcherResponse::AddSyncBlock(self.add_sync_block(sync_block).await)
             }
+            BatcherRequest::GetDailyRevenue => {
+                BatcherResponse::GetDailyRevenue(self.get_daily_revenue().await)
+            }
+            BatcherRequest::ExportBlockBundle(input) => {
+                BatcherResponse::ExportBlockBundle(self.export_block_bundle(input).await)
+            }
+            BatcherRequest::GetBlockConfig(input) => {
+                BatcherResponse::GetBlockConfig(self.get_block_config(input).await)
+            }
+            BatcherRequest::GetFeeMarketInfo => {
+                BatcherResponse::GetFeeMarketInfo(self.get_fee_market_info().await)
+            }
+            BatcherRequest::ValidateExternalBlock(input) => {
+                BatcherResponse::ValidateExternalBlock(self.validate_external_block(input).await)
+            }
         }
     }
 }
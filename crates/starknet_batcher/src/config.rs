@@ -4,9 +4,12 @@ use blockifier::blockifier::config::ContractClassManagerConfig;
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
+use starknet_sequencer_infra::signer::SignerConfig;
 use validator::{Validate, ValidationError};
 
 use crate::block_builder::BlockBuilderConfig;
+use crate::fee_market::GasPriceControllerConfig;
+use crate::l1_gas_prices::StaleL1GasPricesConfig;
 
 /// The batcher related configuration.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
@@ -18,6 +21,17 @@ pub struct BatcherConfig {
     pub block_builder_config: BlockBuilderConfig,
     pub contract_class_manager_config: ContractClassManagerConfig,
     pub max_l1_handler_txs_per_block_proposal: usize,
+    pub revenue_retention_days: usize,
+    pub block_bundle_retention: usize,
+    pub stream_mempool_txs: bool,
+    pub mempool_stream_buffer_size: usize,
+    pub archive_transaction_traces: bool,
+    pub transaction_traces_retention: usize,
+    pub pre_execution_n_txs: usize,
+    pub stale_l1_gas_prices_config: StaleL1GasPricesConfig,
+    pub gas_price_controller_config: GasPriceControllerConfig,
+    // Signs the attestations of validated external blocks.
+    pub signer_config: SignerConfig,
 }
 
 impl SerializeConfig for BatcherConfig {
@@ -43,6 +57,54 @@ impl SerializeConfig for BatcherConfig {
                 "The maximum number of L1 handler transactions to include in a block proposal.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "revenue_retention_days",
+                &self.revenue_retention_days,
+                "The number of days for which the daily collected fees are kept.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "block_bundle_retention",
+                &self.block_bundle_retention,
+                "The number of most recent committed blocks whose bundles are kept for export and \
+                 offline re-execution.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "stream_mempool_txs",
+                &self.stream_mempool_txs,
+                "If true, mempool transactions are streamed into a buffer while building a block \
+                 proposal, instead of being fetched from the mempool chunk by chunk.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "mempool_stream_buffer_size",
+                &self.mempool_stream_buffer_size,
+                "The maximum number of streamed mempool transactions waiting to be executed.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "archive_transaction_traces",
+                &self.archive_transaction_traces,
+                "If true, the execution traces of the transactions are stored when their block is \
+                 committed, so that they can be served without re-executing the block.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "transaction_traces_retention",
+                &self.transaction_traces_retention,
+                "The number of most recent committed blocks whose transaction traces are kept. 0 \
+                 keeps the traces of all the blocks.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "pre_execution_n_txs",
+                &self.pre_execution_n_txs,
+                "The number of pending mempool transactions speculatively executed after each \
+                 decided block, to warm up the caches of the next block's execution. 0 disables \
+                 the pre-execution.",
+                ParamPrivacyInput::Public,
+            ),
         ]);
         dump.append(&mut append_sub_config_name(self.storage.dump(), "storage"));
         dump.append(&mut append_sub_config_name(
@@ -53,6 +115,15 @@ impl SerializeConfig for BatcherConfig {
             self.contract_class_manager_config.dump(),
             "contract_class_manager_config",
         ));
+        dump.append(&mut append_sub_config_name(
+            self.stale_l1_gas_prices_config.dump(),
+            "stale_l1_gas_prices_config",
+        ));
+        dump.append(&mut append_sub_config_name(
+            self.gas_price_controller_config.dump(),
+            "gas_price_controller_config",
+        ));
+        dump.append(&mut append_sub_config_name(self.signer_config.dump(), "signer_config"));
         dump
     }
 }
@@ -76,6 +147,16 @@ impl Default for BatcherConfig {
             block_builder_config: BlockBuilderConfig::default(),
             contract_class_manager_config: ContractClassManagerConfig::default(),
             max_l1_handler_txs_per_block_proposal: 3,
+            revenue_retention_days: 30,
+            block_bundle_retention: 10,
+            stream_mempool_txs: false,
+            mempool_stream_buffer_size: 400,
+            archive_transaction_traces: false,
+            transaction_traces_retention: 0,
+            pre_execution_n_txs: 0,
+            stale_l1_gas_prices_config: StaleL1GasPricesConfig::default(),
+            gas_price_controller_config: GasPriceControllerConfig::default(),
+            signer_config: SignerConfig::default(),
         }
     }
 }
@@ -88,5 +169,29 @@ fn validate_batcher_config(batcher_config: &BatcherConfig) -> Result<(), Validat
             "input_stream_content_buffer_size must be at least tx_chunk_size",
         ));
     }
+    if batcher_config.mempool_stream_buffer_size < batcher_config.block_builder_config.tx_chunk_size
+    {
+        return Err(ValidationError::new(
+            "mempool_stream_buffer_size must be at least tx_chunk_size",
+        ));
+    }
+    let versioned_constants_overrides =
+        &batcher_config.block_builder_config.versioned_constants_overrides;
+    if let Err(error) = versioned_constants_overrides.validate() {
+        let mut validation_error = ValidationError::new("Invalid versioned constants file.");
+        validation_error.message = Some(error.to_string().into());
+        return Err(validation_error);
+    }
+    if let Err(error) = batcher_config.block_builder_config.bouncer_config.validate() {
+        let mut validation_error = ValidationError::new("Invalid bouncer config.");
+        validation_error.message = Some(error.into());
+        return Err(validation_error);
+    }
+    let fee_distribution = &batcher_config.block_builder_config.chain_info.fee_distribution;
+    if let Err(error) = fee_distribution.validate() {
+        let mut validation_error = ValidationError::new("Invalid fee distribution.");
+        validation_error.message = Some(error.into());
+        return Err(validation_error);
+    }
     Ok(())
 }
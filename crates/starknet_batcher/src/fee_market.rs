@@ -1,4 +1,13 @@
-use std::cmp::max;
+use std::cmp::{max, min};
+use std::collections::BTreeMap;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockNumber, GasPrice, GasPriceVector, GasPrices, NonzeroGasPrice};
+use starknet_api::execution_resources::GasAmount;
+use starknet_batcher_types::batcher_types::{BatcherResult, BlockFeeMarketInfo};
+use starknet_batcher_types::errors::BatcherError;
 
 #[cfg(test)]
 #[path = "fee_market_test.rs"]
@@ -7,10 +16,10 @@ pub mod fee_market_test;
 //  This constant is used to calculate the base gas price for the next block according to EIP-1559
 // and serves as a sensitivity parameter that limits the maximum rate of change of the gas price
 // between consecutive blocks.
-const GAS_PRICE_MAX_CHANGE_DENOMINATOR: u128 = 48;
-const MIN_GAS_PRICE: u64 = 100000; // In fri.
+pub(crate) const GAS_PRICE_MAX_CHANGE_DENOMINATOR: u128 = 48;
+pub(crate) const MIN_GAS_PRICE: u64 = 100000; // In fri.
 // TODO(Mohammad): Check the exact value for maximum block size in StarkNet.
-const MAX_BLOCK_SIZE: u64 = 4000000000; // In gas units. It's equivalent to 40M gas steps, with 100 gas units per step.
+pub(crate) const MAX_BLOCK_SIZE: u64 = 4000000000; // In gas units. It's equivalent to 40M gas steps, with 100 gas units per step.
 
 /// Calculate the base gas price for the next block according to EIP-1559.
 ///
@@ -72,3 +81,177 @@ pub fn calculate_next_base_gas_price(price: u64, gas_used: u64, gas_target: u64)
 
     max(adjusted_price, MIN_GAS_PRICE)
 }
+
+/// Returns the fee market signals of a block, given the gas prices it was executed with (in fri)
+/// and the L2 gas it used.
+pub fn block_fee_market_info(
+    block_number: BlockNumber,
+    gas_prices: GasPriceVector,
+    l2_gas_used: GasAmount,
+) -> BlockFeeMarketInfo {
+    let gas_used = min(l2_gas_used.0, MAX_BLOCK_SIZE);
+    let fullness_percentage =
+        u8::try_from(gas_used * 100 / MAX_BLOCK_SIZE).expect("The percentage is at most 100.");
+    let next_l2_gas_price = next_l2_gas_price(l2_gas_price(&gas_prices), l2_gas_used);
+
+    BlockFeeMarketInfo {
+        block_number,
+        gas_prices,
+        fullness_percentage,
+        next_l2_gas_price: GasPrice(next_l2_gas_price.into()),
+    }
+}
+
+// The L2 gas price (in fri) of the block following one executed with the given L2 gas price (in
+// fri) that used the given amount of L2 gas.
+fn next_l2_gas_price(l2_gas_price: u64, l2_gas_used: GasAmount) -> u64 {
+    // Blocks may exceed the fee market's block size, as it is not enforced by the bouncer.
+    let gas_used = min(l2_gas_used.0, MAX_BLOCK_SIZE);
+    calculate_next_base_gas_price(max(l2_gas_price, MIN_GAS_PRICE), gas_used, MAX_BLOCK_SIZE / 2)
+}
+
+fn l2_gas_price(gas_prices: &GasPriceVector) -> u64 {
+    u64::try_from(gas_prices.l2_gas_price.get().0).unwrap_or(u64::MAX)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GasPriceControllerConfig {
+    pub enabled: bool,
+    pub initial_l2_gas_price: u64,
+}
+
+impl Default for GasPriceControllerConfig {
+    fn default() -> Self {
+        Self { enabled: false, initial_l2_gas_price: MIN_GAS_PRICE }
+    }
+}
+
+impl SerializeConfig for GasPriceControllerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, the L2 gas price of each block is derived from the fullness of the \
+                 previous one: proposers build blocks with the derived price, and validators \
+                 reject proposals with another price.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "initial_l2_gas_price",
+                &self.initial_l2_gas_price,
+                "The L2 gas price in fri of the first block built after startup, when the price \
+                 is derived from the block fullness. Raised to the minimum gas price if lower.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+// What is known of the latest committed block, from which the L2 gas price of the next block is
+// derived.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PreviousBlock {
+    // Nothing is known, e.g., no block was committed since the storage was created.
+    Unknown,
+    // Only the L2 gas price (in fri) is known, e.g., for synced blocks.
+    Priced { l2_gas_price: u64 },
+    // The block was executed by this node: its L2 gas price (in fri) and the L2 gas it used are
+    // known, and determine the price of the next block.
+    Executed { l2_gas_price: u64, l2_gas_used: GasAmount },
+}
+
+/// Prices the L2 gas of each block by the fullness of the block committed before it, according to
+/// EIP-1559: the price rises when the previous block used more L2 gas than the target, half of the
+/// maximal block size, and falls when it used less.
+#[derive(Debug)]
+pub(crate) struct GasPriceController {
+    config: GasPriceControllerConfig,
+    previous_block: PreviousBlock,
+}
+
+impl GasPriceController {
+    pub fn new(config: GasPriceControllerConfig) -> Self {
+        Self { config, previous_block: PreviousBlock::Unknown }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Returns the gas prices to build the next block with, given the ones it was proposed with.
+    /// If enabled, the L2 gas price in fri is replaced by the controlled one; the price in wei is
+    /// kept, as the fee market only prices the L2 gas in fri.
+    pub fn gas_prices(&self, gas_prices: GasPrices) -> GasPrices {
+        if !self.config.enabled {
+            return gas_prices;
+        }
+        let next_l2_gas_price = match self.previous_block {
+            PreviousBlock::Unknown => max(self.config.initial_l2_gas_price, MIN_GAS_PRICE),
+            // Keeping the price of the previous block is within the bounds of any change.
+            PreviousBlock::Priced { l2_gas_price } => max(l2_gas_price, MIN_GAS_PRICE),
+            PreviousBlock::Executed { l2_gas_price, l2_gas_used } => {
+                next_l2_gas_price(l2_gas_price, l2_gas_used)
+            }
+        };
+        let l2_gas_price = NonzeroGasPrice::new(GasPrice(next_l2_gas_price.into()))
+            .expect("The controlled L2 gas price is at least the minimum gas price.");
+        GasPrices {
+            strk_gas_prices: GasPriceVector { l2_gas_price, ..gas_prices.strk_gas_prices },
+            ..gas_prices
+        }
+    }
+
+    /// Checks the L2 gas price in fri a block was proposed with against the one derived from the
+    /// previous block. If only the price of the previous block is known, the proposed price may
+    /// differ from it by at most the maximal change between consecutive blocks.
+    pub fn validate_gas_prices(&self, gas_prices: &GasPrices) -> BatcherResult<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        let (min_l2_gas_price, max_l2_gas_price) = match self.previous_block {
+            PreviousBlock::Unknown => (MIN_GAS_PRICE, u64::MAX),
+            PreviousBlock::Priced { l2_gas_price } => {
+                let max_change = l2_gas_price
+                    / u64::try_from(GAS_PRICE_MAX_CHANGE_DENOMINATOR)
+                        .expect("The denominator fits in u64.");
+                (
+                    max(l2_gas_price.saturating_sub(max_change), MIN_GAS_PRICE),
+                    max(l2_gas_price.saturating_add(max_change), MIN_GAS_PRICE),
+                )
+            }
+            PreviousBlock::Executed { l2_gas_price, l2_gas_used } => {
+                let next_l2_gas_price = next_l2_gas_price(l2_gas_price, l2_gas_used);
+                (next_l2_gas_price, next_l2_gas_price)
+            }
+        };
+        let l2_gas_price = gas_prices.strk_gas_prices.l2_gas_price.get();
+        if l2_gas_price < GasPrice(min_l2_gas_price.into())
+            || l2_gas_price > GasPrice(max_l2_gas_price.into())
+        {
+            return Err(BatcherError::InvalidL2GasPrice {
+                l2_gas_price,
+                min_l2_gas_price: GasPrice(min_l2_gas_price.into()),
+                max_l2_gas_price: GasPrice(max_l2_gas_price.into()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Records the gas prices (in fri) a block executed by this node was committed with, and the
+    /// L2 gas it used.
+    pub fn record_decided_block(&mut self, gas_prices: &GasPriceVector, l2_gas_used: GasAmount) {
+        self.previous_block =
+            PreviousBlock::Executed { l2_gas_price: l2_gas_price(gas_prices), l2_gas_used };
+    }
+
+    /// Records the L2 gas price in fri of a synced block, whose L2 gas usage is unknown. A zero
+    /// price means the price is unknown.
+    pub fn record_synced_block(&mut self, l2_gas_price: GasPrice) {
+        self.previous_block = match u64::try_from(l2_gas_price.0) {
+            Ok(0) => PreviousBlock::Unknown,
+            Ok(l2_gas_price) => PreviousBlock::Priced { l2_gas_price },
+            Err(_) => PreviousBlock::Priced { l2_gas_price: u64::MAX },
+        };
+    }
+}
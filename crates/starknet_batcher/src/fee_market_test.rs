@@ -1,5 +1,13 @@
+use assert_matches::assert_matches;
+use starknet_api::block::{BlockNumber, GasPrice, GasPriceVector, GasPrices, NonzeroGasPrice};
+use starknet_api::execution_resources::GasAmount;
+use starknet_batcher_types::errors::BatcherError;
+
 use crate::fee_market::{
+    block_fee_market_info,
     calculate_next_base_gas_price,
+    GasPriceController,
+    GasPriceControllerConfig,
     GAS_PRICE_MAX_CHANGE_DENOMINATOR,
     MAX_BLOCK_SIZE,
     MIN_GAS_PRICE,
@@ -60,3 +68,118 @@ fn test_gas_price_with_extreme_values() {
     let gas_used = MAX_BLOCK_SIZE;
     calculate_next_base_gas_price(u64::try_from(price_u128).unwrap(), gas_used, gas_target); // Should not panic.
 }
+
+#[test]
+fn test_block_fee_market_info() {
+    let l2_gas_price = NonzeroGasPrice::new(GasPrice(MIN_GAS_PRICE.into())).unwrap();
+    let gas_prices = GasPriceVector {
+        l1_gas_price: NonzeroGasPrice::MIN,
+        l1_data_gas_price: NonzeroGasPrice::MIN,
+        l2_gas_price,
+    };
+
+    // A full block raises the price of the next block.
+    let info = block_fee_market_info(BlockNumber(1), gas_prices.clone(), GasAmount(MAX_BLOCK_SIZE));
+    assert_eq!(info.fullness_percentage, 100);
+    assert!(info.next_l2_gas_price > l2_gas_price.get());
+
+    // An empty block doesn't lower the price below the minimum.
+    let info = block_fee_market_info(BlockNumber(1), gas_prices, GasAmount(0));
+    assert_eq!(info.fullness_percentage, 0);
+    assert_eq!(info.next_l2_gas_price, l2_gas_price.get());
+}
+
+#[test]
+fn test_gas_price_controller() {
+    let proposed_gas_prices = GasPrices {
+        eth_gas_prices: GasPriceVector {
+            l1_gas_price: NonzeroGasPrice::MIN,
+            l1_data_gas_price: NonzeroGasPrice::MIN,
+            l2_gas_price: NonzeroGasPrice::MIN,
+        },
+        strk_gas_prices: GasPriceVector {
+            l1_gas_price: NonzeroGasPrice::MIN,
+            l1_data_gas_price: NonzeroGasPrice::MIN,
+            l2_gas_price: NonzeroGasPrice::MIN,
+        },
+    };
+    let strk_l2_gas_price = |gas_prices: GasPrices| gas_prices.strk_gas_prices.l2_gas_price.get();
+
+    // A disabled controller keeps the proposed prices.
+    let controller = GasPriceController::new(GasPriceControllerConfig::default());
+    assert_eq!(controller.gas_prices(proposed_gas_prices.clone()), proposed_gas_prices);
+
+    let initial_l2_gas_price = 2 * MIN_GAS_PRICE;
+    let mut controller =
+        GasPriceController::new(GasPriceControllerConfig { enabled: true, initial_l2_gas_price });
+    let gas_prices = controller.gas_prices(proposed_gas_prices.clone());
+    assert_eq!(strk_l2_gas_price(gas_prices.clone()), GasPrice(initial_l2_gas_price.into()));
+    assert_eq!(gas_prices.eth_gas_prices, proposed_gas_prices.eth_gas_prices);
+
+    // A full block raises the price of the next block, and an empty one lowers it.
+    controller.record_decided_block(&gas_prices.strk_gas_prices, GasAmount(MAX_BLOCK_SIZE));
+    let gas_prices = controller.gas_prices(proposed_gas_prices.clone());
+    let raised_l2_gas_price = strk_l2_gas_price(gas_prices.clone());
+    assert!(raised_l2_gas_price > GasPrice(initial_l2_gas_price.into()));
+
+    controller.record_decided_block(&gas_prices.strk_gas_prices, GasAmount(0));
+    assert!(strk_l2_gas_price(controller.gas_prices(proposed_gas_prices)) < raised_l2_gas_price);
+}
+
+#[test]
+fn test_gas_price_controller_validation() {
+    let with_l2_gas_price = |l2_gas_price: u64| {
+        let l2_gas_price = NonzeroGasPrice::new(GasPrice(l2_gas_price.into())).unwrap();
+        let strk_gas_prices = GasPriceVector {
+            l1_gas_price: NonzeroGasPrice::MIN,
+            l1_data_gas_price: NonzeroGasPrice::MIN,
+            l2_gas_price,
+        };
+        GasPrices { eth_gas_prices: strk_gas_prices.clone(), strk_gas_prices }
+    };
+    let l2_gas_price = 48 * MIN_GAS_PRICE;
+    let max_change = MIN_GAS_PRICE;
+
+    // A disabled controller accepts any price.
+    let controller = GasPriceController::new(GasPriceControllerConfig::default());
+    assert_eq!(controller.validate_gas_prices(&with_l2_gas_price(1)), Ok(()));
+
+    // Without a previous block, any price above the minimum is accepted.
+    let mut controller = GasPriceController::new(GasPriceControllerConfig {
+        enabled: true,
+        initial_l2_gas_price: MIN_GAS_PRICE,
+    });
+    assert_eq!(controller.validate_gas_prices(&with_l2_gas_price(l2_gas_price)), Ok(()));
+    assert_eq!(
+        controller.validate_gas_prices(&with_l2_gas_price(MIN_GAS_PRICE - 1)),
+        Err(BatcherError::InvalidL2GasPrice {
+            l2_gas_price: GasPrice((MIN_GAS_PRICE - 1).into()),
+            min_l2_gas_price: GasPrice(MIN_GAS_PRICE.into()),
+            max_l2_gas_price: GasPrice(u64::MAX.into()),
+        })
+    );
+
+    // Given only the price of a synced block, the price may change by at most the maximal change.
+    controller.record_synced_block(GasPrice(l2_gas_price.into()));
+    assert_eq!(controller.validate_gas_prices(&with_l2_gas_price(l2_gas_price)), Ok(()));
+    assert_eq!(
+        controller.validate_gas_prices(&with_l2_gas_price(l2_gas_price + max_change)),
+        Ok(())
+    );
+    assert_matches!(
+        controller.validate_gas_prices(&with_l2_gas_price(l2_gas_price + max_change + 1)),
+        Err(BatcherError::InvalidL2GasPrice { .. })
+    );
+
+    // Given a decided block, only the derived price is accepted.
+    controller.record_decided_block(
+        &with_l2_gas_price(l2_gas_price).strk_gas_prices,
+        GasAmount(MAX_BLOCK_SIZE),
+    );
+    let derived_gas_prices = controller.gas_prices(with_l2_gas_price(l2_gas_price));
+    assert_eq!(controller.validate_gas_prices(&derived_gas_prices), Ok(()));
+    assert_matches!(
+        controller.validate_gas_prices(&with_l2_gas_price(l2_gas_price)),
+        Err(BatcherError::InvalidL2GasPrice { .. })
+    );
+}
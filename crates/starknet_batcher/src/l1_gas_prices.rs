@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{GasPrice, GasPriceVector, GasPrices, NonzeroGasPrice};
+use starknet_batcher_types::batcher_types::BatcherResult;
+use starknet_batcher_types::errors::BatcherError;
+use tracing::warn;
+
+#[cfg(test)]
+#[path = "l1_gas_prices_test.rs"]
+mod l1_gas_prices_test;
+
+/// What the batcher does when asked to build a block with stale L1 gas prices.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StaleL1GasPricesPolicy {
+    /// Rejects building the block.
+    #[default]
+    Halt,
+    /// Prices the block with the L1 gas prices of the latest block built with fresh ones.
+    ReuseLast,
+    /// Prices the block with the configured floor L1 gas prices.
+    UseFloor,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StaleL1GasPricesConfig {
+    pub max_age_seconds: u64,
+    pub policy: StaleL1GasPricesPolicy,
+    pub floor_eth_l1_gas_price: u64,
+    pub floor_eth_l1_data_gas_price: u64,
+    pub floor_strk_l1_gas_price: u64,
+    pub floor_strk_l1_data_gas_price: u64,
+}
+
+impl Default for StaleL1GasPricesConfig {
+    fn default() -> Self {
+        Self {
+            max_age_seconds: 600,
+            policy: StaleL1GasPricesPolicy::default(),
+            floor_eth_l1_gas_price: 1,
+            floor_eth_l1_data_gas_price: 1,
+            floor_strk_l1_gas_price: 1,
+            floor_strk_l1_data_gas_price: 1,
+        }
+    }
+}
+
+impl SerializeConfig for StaleL1GasPricesConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "max_age_seconds",
+                &self.max_age_seconds,
+                "The age, in seconds, beyond which the L1 gas prices of a block proposal are \
+                 stale.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "policy",
+                &self.policy,
+                "What to do when asked to build a block with stale L1 gas prices: Halt rejects \
+                 the block, ReuseLast uses the prices of the latest block built with fresh prices \
+                 and UseFloor uses the floor prices.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "floor_eth_l1_gas_price",
+                &self.floor_eth_l1_gas_price,
+                "The L1 gas price in wei used by the UseFloor policy.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "floor_eth_l1_data_gas_price",
+                &self.floor_eth_l1_data_gas_price,
+                "The L1 data gas price in wei used by the UseFloor policy.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "floor_strk_l1_gas_price",
+                &self.floor_strk_l1_gas_price,
+                "The L1 gas price in fri used by the UseFloor policy.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "floor_strk_l1_data_gas_price",
+                &self.floor_strk_l1_data_gas_price,
+                "The L1 data gas price in fri used by the UseFloor policy.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Keeps blocks from being priced off outdated L1 data, e.g., after an outage of the L1 gas price
+/// source.
+#[derive(Debug)]
+pub(crate) struct L1GasPriceGuard {
+    config: StaleL1GasPricesConfig,
+    // The gas prices of the latest block built with fresh L1 gas prices.
+    last_fresh_gas_prices: Option<GasPrices>,
+}
+
+impl L1GasPriceGuard {
+    pub fn new(config: StaleL1GasPricesConfig) -> Self {
+        Self { config, last_fresh_gas_prices: None }
+    }
+
+    /// Returns the gas prices to build a block with, given the ones it was proposed with and the
+    /// time (both in seconds since the Unix epoch) their L1 gas prices were sampled at, if known.
+    /// Only the L1 gas prices are replaced when stale; the L2 gas price is kept.
+    pub fn gas_prices(
+        &mut self,
+        gas_prices: GasPrices,
+        l1_gas_prices_timestamp: Option<u64>,
+        now: u64,
+    ) -> BatcherResult<GasPrices> {
+        let Some(l1_gas_prices_timestamp) = l1_gas_prices_timestamp else {
+            return Ok(gas_prices);
+        };
+        let age_seconds = now.saturating_sub(l1_gas_prices_timestamp);
+        if age_seconds <= self.config.max_age_seconds {
+            self.last_fresh_gas_prices = Some(gas_prices.clone());
+            return Ok(gas_prices);
+        }
+
+        warn!(
+            "The L1 gas prices are {age_seconds} seconds old; applying the {:?} policy.",
+            self.config.policy
+        );
+        let stale_error = BatcherError::StaleL1GasPrices { age_seconds };
+        let l1_gas_prices = match self.config.policy {
+            StaleL1GasPricesPolicy::Halt => return Err(stale_error),
+            StaleL1GasPricesPolicy::ReuseLast => {
+                self.last_fresh_gas_prices.clone().ok_or(stale_error)?
+            }
+            StaleL1GasPricesPolicy::UseFloor => self.floor_gas_prices(),
+        };
+        Ok(with_l1_gas_prices(gas_prices, l1_gas_prices))
+    }
+
+    // Only the L1 gas prices of the result are meaningful.
+    fn floor_gas_prices(&self) -> GasPrices {
+        let floor = |price: u64| {
+            NonzeroGasPrice::new(GasPrice(price.into())).unwrap_or(NonzeroGasPrice::MIN)
+        };
+        let config = &self.config;
+        GasPrices {
+            eth_gas_prices: GasPriceVector {
+                l1_gas_price: floor(config.floor_eth_l1_gas_price),
+                l1_data_gas_price: floor(config.floor_eth_l1_data_gas_price),
+                l2_gas_price: NonzeroGasPrice::MIN,
+            },
+            strk_gas_prices: GasPriceVector {
+                l1_gas_price: floor(config.floor_strk_l1_gas_price),
+                l1_data_gas_price: floor(config.floor_strk_l1_data_gas_price),
+                l2_gas_price: NonzeroGasPrice::MIN,
+            },
+        }
+    }
+}
+
+// Replaces the L1 gas prices of the given gas prices.
+fn with_l1_gas_prices(gas_prices: GasPrices, l1_gas_prices: GasPrices) -> GasPrices {
+    let with_l1 = |prices: GasPriceVector, l1_prices: GasPriceVector| GasPriceVector {
+        l1_gas_price: l1_prices.l1_gas_price,
+        l1_data_gas_price: l1_prices.l1_data_gas_price,
+        ..prices
+    };
+    GasPrices {
+        eth_gas_prices: with_l1(gas_prices.eth_gas_prices, l1_gas_prices.eth_gas_prices),
+        strk_gas_prices: with_l1(gas_prices.strk_gas_prices, l1_gas_prices.strk_gas_prices),
+    }
+}
@@ -0,0 +1,93 @@
+use assert_matches::assert_matches;
+use rstest::rstest;
+use starknet_api::block::{GasPrice, GasPriceVector, GasPrices, NonzeroGasPrice};
+use starknet_batcher_types::errors::BatcherError;
+
+use crate::l1_gas_prices::{L1GasPriceGuard, StaleL1GasPricesConfig, StaleL1GasPricesPolicy};
+
+const MAX_AGE_SECONDS: u64 = 60;
+const NOW: u64 = 1000;
+const FRESH_TIMESTAMP: u64 = NOW - MAX_AGE_SECONDS;
+const STALE_TIMESTAMP: u64 = FRESH_TIMESTAMP - 1;
+
+fn price(price: u128) -> NonzeroGasPrice {
+    NonzeroGasPrice::new(GasPrice(price)).unwrap()
+}
+
+// Gas prices whose L1 gas prices are all `l1_price` and L2 gas prices are all `l2_price`.
+fn gas_prices(l1_price: u128, l2_price: u128) -> GasPrices {
+    let gas_price_vector = GasPriceVector {
+        l1_gas_price: price(l1_price),
+        l1_data_gas_price: price(l1_price),
+        l2_gas_price: price(l2_price),
+    };
+    GasPrices { eth_gas_prices: gas_price_vector.clone(), strk_gas_prices: gas_price_vector }
+}
+
+fn guard(policy: StaleL1GasPricesPolicy) -> L1GasPriceGuard {
+    L1GasPriceGuard::new(StaleL1GasPricesConfig {
+        max_age_seconds: MAX_AGE_SECONDS,
+        policy,
+        floor_eth_l1_gas_price: 5,
+        floor_eth_l1_data_gas_price: 5,
+        floor_strk_l1_gas_price: 5,
+        floor_strk_l1_data_gas_price: 5,
+    })
+}
+
+#[rstest]
+#[case::unknown_timestamp(None)]
+#[case::fresh(Some(FRESH_TIMESTAMP))]
+#[case::from_the_future(Some(NOW + 1))]
+fn fresh_gas_prices_are_kept(
+    #[values(
+        StaleL1GasPricesPolicy::Halt,
+        StaleL1GasPricesPolicy::ReuseLast,
+        StaleL1GasPricesPolicy::UseFloor
+    )]
+    policy: StaleL1GasPricesPolicy,
+    #[case] l1_gas_prices_timestamp: Option<u64>,
+) {
+    let mut guard = guard(policy);
+    assert_eq!(
+        guard.gas_prices(gas_prices(10, 20), l1_gas_prices_timestamp, NOW).unwrap(),
+        gas_prices(10, 20)
+    );
+}
+
+#[test]
+fn halt_rejects_stale_gas_prices() {
+    let mut guard = guard(StaleL1GasPricesPolicy::Halt);
+    guard.gas_prices(gas_prices(10, 20), Some(FRESH_TIMESTAMP), NOW).unwrap();
+
+    let result = guard.gas_prices(gas_prices(30, 40), Some(STALE_TIMESTAMP), NOW);
+    assert_matches!(
+        result,
+        Err(BatcherError::StaleL1GasPrices { age_seconds }) if age_seconds == MAX_AGE_SECONDS + 1
+    );
+}
+
+#[test]
+fn reuse_last_replaces_stale_l1_gas_prices() {
+    let mut guard = guard(StaleL1GasPricesPolicy::ReuseLast);
+    // Nothing to reuse yet.
+    assert_matches!(
+        guard.gas_prices(gas_prices(30, 40), Some(STALE_TIMESTAMP), NOW),
+        Err(BatcherError::StaleL1GasPrices { .. })
+    );
+
+    guard.gas_prices(gas_prices(10, 20), Some(FRESH_TIMESTAMP), NOW).unwrap();
+    assert_eq!(
+        guard.gas_prices(gas_prices(30, 40), Some(STALE_TIMESTAMP), NOW).unwrap(),
+        gas_prices(10, 40)
+    );
+}
+
+#[test]
+fn use_floor_replaces_stale_l1_gas_prices() {
+    let mut guard = guard(StaleL1GasPricesPolicy::UseFloor);
+    assert_eq!(
+        guard.gas_prices(gas_prices(30, 40), Some(STALE_TIMESTAMP), NOW).unwrap(),
+        gas_prices(5, 40)
+    );
+}
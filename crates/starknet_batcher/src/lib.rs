@@ -4,9 +4,14 @@ mod batcher_test;
 pub mod block_builder;
 #[cfg(test)]
 mod block_builder_test;
+mod block_bundles;
+pub mod block_config;
 pub mod communication;
 pub mod config;
 pub mod fee_market;
+pub mod l1_gas_prices;
+mod metrics;
+mod revenue;
 #[cfg(test)]
 mod test_utils;
 mod transaction_executor;
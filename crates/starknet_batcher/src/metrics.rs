@@ -0,0 +1,84 @@
+use blockifier::concurrency::conflict_stats::ConflictStats;
+use blockifier::state::global_cache::ContractCacheStats;
+use metrics::{
+    absolute_counter,
+    describe_counter,
+    describe_gauge,
+    gauge,
+    register_counter,
+    register_gauge,
+};
+use tracing::info;
+
+const CONCURRENT_TX_EXECUTIONS: (&str, &str, u64) = (
+    "CONCURRENT_TX_EXECUTIONS",
+    "Number of transaction executions in concurrent block building, including re-executions",
+    0,
+);
+const CONCURRENT_VALIDATION_ABORTS: (&str, &str, u64) = (
+    "CONCURRENT_VALIDATION_ABORTS",
+    "Number of concurrent transaction executions aborted by a conflict found in validation",
+    0,
+);
+const CONCURRENT_COMMIT_REEXECUTIONS: (&str, &str, u64) = (
+    "CONCURRENT_COMMIT_REEXECUTIONS",
+    "Number of transactions re-executed on commit due to a conflict in concurrent block building",
+    0,
+);
+const CONTRACT_CACHE_HITS: (&str, &str, u64) =
+    ("CONTRACT_CACHE_HITS", "Number of contract class reads served by the global class cache", 0);
+const CONTRACT_CACHE_MISSES: (&str, &str, u64) =
+    ("CONTRACT_CACHE_MISSES", "Number of contract class reads missing the global class cache", 0);
+const CONTRACT_CACHE_EVICTIONS: (&str, &str, u64) = (
+    "CONTRACT_CACHE_EVICTIONS",
+    "Number of contract classes evicted from the global class cache to bound its size",
+    0,
+);
+const CONTRACT_CACHE_SIZE_IN_BYTES: (&str, &str) = (
+    "CONTRACT_CACHE_SIZE_IN_BYTES",
+    "The estimated size, in bytes, of the classes in the global class cache",
+);
+
+pub(crate) fn init_metrics() {
+    info!("Initializing Batcher metrics");
+    for (name, description, initial_value) in [
+        CONCURRENT_TX_EXECUTIONS,
+        CONCURRENT_VALIDATION_ABORTS,
+        CONCURRENT_COMMIT_REEXECUTIONS,
+        CONTRACT_CACHE_HITS,
+        CONTRACT_CACHE_MISSES,
+        CONTRACT_CACHE_EVICTIONS,
+    ] {
+        register_counter!(name);
+        describe_counter!(name, description);
+        absolute_counter!(name, initial_value);
+    }
+    register_gauge!(CONTRACT_CACHE_SIZE_IN_BYTES.0);
+    describe_gauge!(CONTRACT_CACHE_SIZE_IN_BYTES.0, CONTRACT_CACHE_SIZE_IN_BYTES.1);
+}
+
+/// Records the executions, aborts and retries of the concurrent execution of a block.
+pub(crate) fn record_conflict_stats(conflict_stats: &ConflictStats) {
+    let ConflictStats { n_executions, n_validation_aborts, n_commit_reexecutions, .. } =
+        conflict_stats;
+    metrics::counter!(CONCURRENT_TX_EXECUTIONS.0, u64::try_from(*n_executions).unwrap_or(u64::MAX));
+    metrics::counter!(
+        CONCURRENT_VALIDATION_ABORTS.0,
+        u64::try_from(*n_validation_aborts).unwrap_or(u64::MAX)
+    );
+    metrics::counter!(
+        CONCURRENT_COMMIT_REEXECUTIONS.0,
+        u64::try_from(*n_commit_reexecutions).unwrap_or(u64::MAX)
+    );
+}
+
+/// Records the statistics of the global class cache, accumulated since the node started.
+#[allow(clippy::as_conversions)] // Gauges are floats.
+pub(crate) fn record_contract_cache_stats(contract_cache_stats: &ContractCacheStats) {
+    let ContractCacheStats { n_hits, n_misses, n_evictions, size_in_bytes, .. } =
+        contract_cache_stats;
+    absolute_counter!(CONTRACT_CACHE_HITS.0, u64::try_from(*n_hits).unwrap_or(u64::MAX));
+    absolute_counter!(CONTRACT_CACHE_MISSES.0, u64::try_from(*n_misses).unwrap_or(u64::MAX));
+    absolute_counter!(CONTRACT_CACHE_EVICTIONS.0, u64::try_from(*n_evictions).unwrap_or(u64::MAX));
+    gauge!(CONTRACT_CACHE_SIZE_IN_BYTES.0, *size_in_bytes as f64);
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use blockifier::context::ChainInfo;
+use blockifier::fee::receipt::TransactionReceipt;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use chrono::{Datelike, NaiveDate};
+use papyrus_storage::revenue::{RevenueDay, SerializedDailyRevenue};
+use starknet_api::block::FeeType;
+use starknet_api::executable_transaction::{AccountTransaction, Transaction};
+use starknet_api::transaction::fields::Fee;
+use starknet_batcher_types::batcher_types::{
+    DailyRevenue,
+    FeeTotals,
+    RevenueBreakdown,
+    RevenueFeeToken,
+    RevenueTxType,
+};
+use tracing::warn;
+
+use crate::batcher::BatcherStorageReaderTrait;
+
+#[cfg(test)]
+#[path = "revenue_test.rs"]
+mod revenue_test;
+
+/// Adds the fee charged for an executed transaction to the block revenue, in the token the fee was
+/// transferred in, along with the decimals of that token.
+/// L1 handler transactions are skipped, as their fee is paid on L1 and not collected by the
+/// sequencer, and so are transactions that transferred no fee.
+pub(crate) fn record_tx_revenue(
+    revenue: &mut RevenueBreakdown,
+    chain_info: &ChainInfo,
+    tx: &Transaction,
+    execution_info: &TransactionExecutionInfo,
+) {
+    let Transaction::Account(account_tx) = tx else {
+        return;
+    };
+    let Some(fee_transfer_call_info) = &execution_info.fee_transfer_call_info else {
+        return;
+    };
+    if execution_info.receipt.fee == Fee(0) {
+        return;
+    }
+
+    let fee_token_address = fee_transfer_call_info.call.storage_address;
+    let (fee_token, fee_type) = if fee_token_address == chain_info.fee_token_address(&FeeType::Strk)
+    {
+        (RevenueFeeToken::Strk, FeeType::Strk)
+    } else if fee_token_address == chain_info.fee_token_address(&FeeType::Eth) {
+        (RevenueFeeToken::Eth, FeeType::Eth)
+    } else {
+        warn!(
+            "The fee of transaction {} was transferred in an unknown token {}.",
+            tx.tx_hash(),
+            fee_token_address
+        );
+        return;
+    };
+    let tx_type = match account_tx {
+        AccountTransaction::Declare(_) => RevenueTxType::Declare,
+        AccountTransaction::DeployAccount(_) => RevenueTxType::DeployAccount,
+        AccountTransaction::Invoke(_) => RevenueTxType::Invoke,
+    };
+    let TransactionReceipt { fee, tip, .. } = execution_info.receipt;
+    let fees = FeeTotals { n_txs: 1, base_fee: Fee(fee.0 - tip.0), tip };
+    revenue.add_tx(fee_token, chain_info.fee_token_decimals(&fee_type), tx_type, fees);
+}
+
+/// Keeps the revenue of committed blocks, aggregated per day, for a bounded number of days.
+///
+/// The total of a day is stored with every block that adds to it, so that the totals are restored
+/// from the storage after a restart.
+#[derive(Debug)]
+pub(crate) struct RevenueLedger {
+    retention_days: usize,
+    daily_revenue: BTreeMap<NaiveDate, DailyRevenue>,
+}
+
+impl RevenueLedger {
+    pub fn new(retention_days: usize) -> Self {
+        Self { retention_days, daily_revenue: BTreeMap::new() }
+    }
+
+    /// Restores the daily totals stored with the committed blocks. Totals that cannot be read are
+    /// skipped, as they only serve reporting.
+    pub fn load(retention_days: usize, storage_reader: &dyn BatcherStorageReaderTrait) -> Self {
+        let mut ledger = Self::new(retention_days);
+        let stored_daily_revenues = match storage_reader.get_daily_revenues() {
+            Ok(stored_daily_revenues) => stored_daily_revenues,
+            Err(err) => {
+                warn!("Failed to read the daily revenue from storage: {}", err);
+                return ledger;
+            }
+        };
+        for (day, serialized_daily_revenue) in stored_daily_revenues {
+            match serde_json::from_value::<DailyRevenue>(serialized_daily_revenue.0) {
+                Ok(daily_revenue) => ledger.record(daily_revenue),
+                Err(err) => warn!("Failed to parse the revenue of day {}: {}", day, err),
+            }
+        }
+        ledger
+    }
+
+    /// Returns the total of the given day, including the revenue of a block committed on it.
+    pub fn with_block(&self, date: NaiveDate, block_revenue: &RevenueBreakdown) -> DailyRevenue {
+        let mut daily_revenue = self.daily_revenue.get(&date).cloned().unwrap_or(DailyRevenue {
+            date,
+            n_blocks: 0,
+            revenue: RevenueBreakdown::default(),
+        });
+        daily_revenue.n_blocks += 1;
+        daily_revenue.revenue.merge(block_revenue);
+        daily_revenue
+    }
+
+    /// Sets the total of a day, replacing its previous total.
+    pub fn record(&mut self, daily_revenue: DailyRevenue) {
+        self.daily_revenue.insert(daily_revenue.date, daily_revenue);
+        while self.daily_revenue.len() > self.retention_days {
+            self.daily_revenue.pop_first();
+        }
+    }
+
+    /// Returns the retained daily totals, oldest first.
+    pub fn daily_revenue(&self) -> Vec<DailyRevenue> {
+        self.daily_revenue.values().cloned().collect()
+    }
+}
+
+/// Returns the total of a day as stored with the blocks, keyed by the day.
+pub(crate) fn to_stored_daily_revenue(
+    daily_revenue: &DailyRevenue,
+) -> (RevenueDay, SerializedDailyRevenue) {
+    let day = RevenueDay::try_from(daily_revenue.date.num_days_from_ce())
+        .expect("Revenue dates should be in the common era.");
+    let serialized_daily_revenue = SerializedDailyRevenue(
+        serde_json::to_value(daily_revenue).expect("Daily revenue should be serializable to JSON."),
+    );
+    (day, serialized_daily_revenue)
+}
@@ -0,0 +1,223 @@
+use blockifier::context::{ChainInfo, CustomFeeToken, CustomFeeTokens, FeeTokenAddresses};
+use blockifier::execution::call_info::CallInfo;
+use blockifier::execution::entry_point::CallEntryPoint;
+use blockifier::fee::receipt::TransactionReceipt;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use chrono::NaiveDate;
+use papyrus_storage::revenue::SerializedDailyRevenue;
+use starknet_api::block::FeeType;
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::test_utils::invoke::executable_invoke_tx;
+use starknet_api::test_utils::l1_handler::executable_l1_handler_tx;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::TransactionVersion;
+use starknet_api::{contract_address, invoke_tx_args, l1_handler_tx_args};
+use starknet_batcher_types::batcher_types::{
+    DailyRevenue,
+    FeeTotals,
+    RevenueBreakdown,
+    RevenueFeeToken,
+    RevenueTxType,
+};
+
+use crate::batcher::MockBatcherStorageReaderTrait;
+use crate::revenue::{record_tx_revenue, to_stored_daily_revenue, RevenueLedger};
+
+const STRK_FEE_TOKEN_ADDRESS: &str = "0x1001";
+const ETH_FEE_TOKEN_ADDRESS: &str = "0x1002";
+
+fn chain_info() -> ChainInfo {
+    ChainInfo {
+        fee_token_addresses: FeeTokenAddresses {
+            strk_fee_token_address: contract_address!(STRK_FEE_TOKEN_ADDRESS),
+            eth_fee_token_address: contract_address!(ETH_FEE_TOKEN_ADDRESS),
+        },
+        ..Default::default()
+    }
+}
+
+// The fee is transferred in the given token.
+fn execution_info_with_fee(fee: u128, fee_token_address: &str) -> TransactionExecutionInfo {
+    TransactionExecutionInfo {
+        fee_transfer_call_info: Some(CallInfo {
+            call: CallEntryPoint {
+                storage_address: contract_address!(fee_token_address),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        receipt: TransactionReceipt { fee: Fee(fee), ..Default::default() },
+        ..Default::default()
+    }
+}
+
+fn invoke_tx(version: TransactionVersion) -> Transaction {
+    Transaction::Account(executable_invoke_tx(invoke_tx_args!(version)))
+}
+
+fn fees(n_txs: u64, base_fee: u128) -> FeeTotals {
+    FeeTotals { n_txs, base_fee: Fee(base_fee), tip: Fee(0) }
+}
+
+#[test]
+fn record_tx_revenue_by_fee_token() {
+    let chain_info = chain_info();
+    let mut revenue = RevenueBreakdown::default();
+    let tx = invoke_tx(TransactionVersion::THREE);
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info,
+        &tx,
+        &execution_info_with_fee(7, STRK_FEE_TOKEN_ADDRESS),
+    );
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info,
+        &tx,
+        &execution_info_with_fee(3, STRK_FEE_TOKEN_ADDRESS),
+    );
+    // The token is the one the fee was transferred in, regardless of the transaction version.
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info,
+        &tx,
+        &execution_info_with_fee(5, ETH_FEE_TOKEN_ADDRESS),
+    );
+
+    let strk_revenue = &revenue.by_fee_token[&RevenueFeeToken::Strk];
+    assert_eq!(strk_revenue.total, fees(2, 10));
+    assert_eq!(strk_revenue.by_tx_type[&RevenueTxType::Invoke], fees(2, 10));
+    let eth_revenue = &revenue.by_fee_token[&RevenueFeeToken::Eth];
+    assert_eq!(eth_revenue.total, fees(1, 5));
+    assert_eq!(strk_revenue.decimals, 18);
+    assert_eq!(eth_revenue.decimals, 18);
+}
+
+#[test]
+fn record_tx_revenue_custom_fee_token_decimals() {
+    let mut chain_info = chain_info();
+    chain_info.custom_fee_tokens = CustomFeeTokens::new([CustomFeeToken {
+        fee_type: FeeType::Strk,
+        address: contract_address!("0x1003"),
+        decimals: 6,
+    }])
+    .unwrap();
+    let mut revenue = RevenueBreakdown::default();
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info,
+        &invoke_tx(TransactionVersion::THREE),
+        &execution_info_with_fee(7, "0x1003"),
+    );
+
+    let strk_revenue = &revenue.by_fee_token[&RevenueFeeToken::Strk];
+    assert_eq!(strk_revenue.total, fees(1, 7));
+    assert_eq!(strk_revenue.decimals, 6);
+}
+
+#[test]
+fn record_tx_revenue_splits_tip() {
+    let mut revenue = RevenueBreakdown::default();
+    let mut execution_info = execution_info_with_fee(10, STRK_FEE_TOKEN_ADDRESS);
+    execution_info.receipt.tip = Fee(4);
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info(),
+        &invoke_tx(TransactionVersion::THREE),
+        &execution_info,
+    );
+
+    assert_eq!(
+        revenue.by_fee_token[&RevenueFeeToken::Strk].total,
+        FeeTotals { n_txs: 1, base_fee: Fee(6), tip: Fee(4) }
+    );
+}
+
+#[test]
+fn record_tx_revenue_skips_unpaid_txs() {
+    let chain_info = chain_info();
+    let mut revenue = RevenueBreakdown::default();
+    let l1_handler_tx = Transaction::L1Handler(executable_l1_handler_tx(
+        l1_handler_tx_args!(paid_fee_on_l1: Fee(9)),
+    ));
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info,
+        &l1_handler_tx,
+        &execution_info_with_fee(9, STRK_FEE_TOKEN_ADDRESS),
+    );
+    let invoke_tx = invoke_tx(TransactionVersion::THREE);
+    record_tx_revenue(
+        &mut revenue,
+        &chain_info,
+        &invoke_tx,
+        &execution_info_with_fee(0, STRK_FEE_TOKEN_ADDRESS),
+    );
+    // No fee was transferred.
+    let execution_info = TransactionExecutionInfo {
+        receipt: TransactionReceipt { fee: Fee(9), ..Default::default() },
+        ..Default::default()
+    };
+    record_tx_revenue(&mut revenue, &chain_info, &invoke_tx, &execution_info);
+    // The fee was transferred in a token the chain does not charge fees in.
+    record_tx_revenue(&mut revenue, &chain_info, &invoke_tx, &execution_info_with_fee(9, "0x1003"));
+
+    assert_eq!(revenue, RevenueBreakdown::default());
+}
+
+#[test]
+fn revenue_ledger_aggregates_per_day() {
+    let mut block_revenue = RevenueBreakdown::default();
+    block_revenue.add_tx(RevenueFeeToken::Strk, 18, RevenueTxType::Invoke, fees(1, 4));
+    let day = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+
+    let mut ledger = RevenueLedger::new(2);
+    ledger.record(ledger.with_block(day, &block_revenue));
+    ledger.record(ledger.with_block(day, &block_revenue));
+
+    let daily_revenue = ledger.daily_revenue();
+    assert_eq!(daily_revenue.len(), 1);
+    assert_eq!(daily_revenue[0].n_blocks, 2);
+    assert_eq!(daily_revenue[0].revenue.by_fee_token[&RevenueFeeToken::Strk].total, fees(2, 8));
+}
+
+#[test]
+fn revenue_ledger_retention() {
+    let block_revenue = RevenueBreakdown::default();
+    let mut ledger = RevenueLedger::new(2);
+    for day in 1..=3 {
+        ledger.record(
+            ledger.with_block(NaiveDate::from_ymd_opt(2024, 11, day).unwrap(), &block_revenue),
+        );
+    }
+
+    let retained_dates: Vec<_> = ledger.daily_revenue().iter().map(|daily| daily.date).collect();
+    assert_eq!(
+        retained_dates,
+        vec![
+            NaiveDate::from_ymd_opt(2024, 11, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 11, 3).unwrap()
+        ]
+    );
+}
+
+#[test]
+fn revenue_ledger_load() {
+    let daily_revenue = |day, n_blocks| DailyRevenue {
+        date: NaiveDate::from_ymd_opt(2024, 11, day).unwrap(),
+        n_blocks,
+        revenue: RevenueBreakdown::default(),
+    };
+    let stored_daily_revenues = vec![
+        to_stored_daily_revenue(&daily_revenue(1, 4)),
+        (0, SerializedDailyRevenue(serde_json::json!("Not a daily revenue"))),
+        to_stored_daily_revenue(&daily_revenue(2, 5)),
+        to_stored_daily_revenue(&daily_revenue(3, 6)),
+    ];
+    let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_get_daily_revenues().return_once(|| Ok(stored_daily_revenues));
+
+    // Unreadable totals are skipped, and only the latest days are retained.
+    let ledger = RevenueLedger::load(2, &storage_reader);
+    assert_eq!(ledger.daily_revenue(), vec![daily_revenue(2, 5), daily_revenue(3, 6)]);
+}
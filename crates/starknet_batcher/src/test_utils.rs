@@ -1,6 +1,7 @@
 use std::ops::Range;
 
 use async_trait::async_trait;
+use blockifier::blockifier::block_bundle::BlockStateReads;
 use blockifier::blockifier::transaction_executor::VisitedSegmentsMapping;
 use blockifier::bouncer::BouncerWeights;
 use blockifier::state::cached_state::CommitmentStateDiff;
@@ -9,6 +10,7 @@ use starknet_api::executable_transaction::Transaction;
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
 use starknet_api::{class_hash, contract_address, nonce, tx_hash};
+use starknet_batcher_types::batcher_types::RevenueBreakdown;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::block_builder::{BlockBuilderResult, BlockBuilderTrait, BlockExecutionArtifacts};
@@ -84,9 +86,13 @@ impl BlockExecutionArtifacts {
                 class_hash_to_compiled_class_hash: IndexMap::new(),
                 address_to_nonce: IndexMap::from_iter([(contract_address!("0x7"), nonce!(1_u64))]),
             },
+            alternative_da_state_diff: CommitmentStateDiff::default(),
             visited_segments_mapping: VisitedSegmentsMapping::default(),
             bouncer_weights: BouncerWeights::empty(),
             l2_gas_used: GasAmount::default(),
+            revenue: RevenueBreakdown::default(),
+            transactions: Vec::new(),
+            state_reads: BlockStateReads::default(),
         }
     }
 }
@@ -1,9 +1,12 @@
+use blockifier::blockifier::block_bundle::BlockStateReads;
 use blockifier::blockifier::transaction_executor::{
     TransactionExecutor,
     TransactionExecutorResult,
     VisitedSegmentsMapping,
 };
 use blockifier::bouncer::BouncerWeights;
+use blockifier::concurrency::conflict_stats::ConflictStats;
+use blockifier::concurrency::dependency_hints::TxDependencyHints;
 use blockifier::state::cached_state::CommitmentStateDiff;
 use blockifier::state::state_api::StateReader;
 use blockifier::transaction::objects::TransactionExecutionInfo;
@@ -17,18 +20,33 @@ pub trait TransactionExecutorTrait: Send {
         &mut self,
         txs: &[BlockifierTransaction],
     ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>>;
+    fn get_block_state_reads(&self) -> TransactionExecutorResult<BlockStateReads>;
     fn close_block(
         &mut self,
     ) -> TransactionExecutorResult<(CommitmentStateDiff, VisitedSegmentsMapping, BouncerWeights)>;
+    fn alternative_da_state_diff(&self, state_diff: &CommitmentStateDiff) -> CommitmentStateDiff;
+    fn conflict_stats(&self) -> ConflictStats;
 }
 
 impl<S: StateReader + Send + Sync> TransactionExecutorTrait for TransactionExecutor<S> {
     /// Adds the transactions to the generated block and returns the execution results.
+    /// In concurrent execution, each transaction is hinted to access its sender's nonce and fee
+    /// balance, so that transactions of the same sender are not executed optimistically in
+    /// parallel.
     fn add_txs_to_block(
         &mut self,
         txs: &[BlockifierTransaction],
     ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>> {
-        self.execute_txs(txs)
+        if !self.config.concurrency_config.enabled {
+            return self.execute_txs(txs);
+        }
+        let dependency_hints: Vec<_> =
+            txs.iter().map(|tx| TxDependencyHints::from_tx(tx, &self.block_context)).collect();
+        self.execute_txs_with_dependency_hints(txs, Some(&dependency_hints))
+    }
+    /// Returns the reads of the parent state performed so far, needed to re-execute the block.
+    fn get_block_state_reads(&self) -> TransactionExecutorResult<BlockStateReads> {
+        TransactionExecutor::get_block_state_reads(self)
     }
     /// Finalizes the block creation and returns the commitment state diff, visited
     /// segments mapping and bouncer.
@@ -38,4 +56,13 @@ impl<S: StateReader + Send + Sync> TransactionExecutorTrait for TransactionExecu
     {
         self.finalize()
     }
+    /// Returns the part of the closed block's state diff that is published to an alternative data
+    /// availability layer.
+    fn alternative_da_state_diff(&self, state_diff: &CommitmentStateDiff) -> CommitmentStateDiff {
+        TransactionExecutor::alternative_da_state_diff(self, state_diff)
+    }
+    /// Returns the statistics of the conflicts between the transactions executed concurrently.
+    fn conflict_stats(&self) -> ConflictStats {
+        TransactionExecutor::conflict_stats(self).clone()
+    }
 }
@@ -1,18 +1,32 @@
 use std::cmp::min;
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::vec;
 
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
-use starknet_api::executable_transaction::Transaction;
+use starknet_api::core::Nonce;
+use starknet_api::executable_transaction::{AccountTransaction, L1HandlerTransaction, Transaction};
 use starknet_api::transaction::TransactionHash;
 use starknet_l1_provider_types::errors::L1ProviderClientError;
 use starknet_l1_provider_types::{SharedL1ProviderClient, ValidationStatus as L1ValidationStatus};
-use starknet_mempool_types::communication::{MempoolClientError, SharedMempoolClient};
+use starknet_mempool_types::communication::{
+    MempoolClientError,
+    MempoolClientResult,
+    SharedMempoolClient,
+};
 use thiserror::Error;
+use tokio::sync::mpsc::error::TryRecvError;
+use tracing::{debug, error, warn};
+
+use crate::batcher::BatcherStorageReaderTrait;
 
 type TransactionProviderResult<T> = Result<T, TransactionProviderError>;
 
+// How long the mempool stream waits before fetching again from an empty mempool.
+const EMPTY_MEMPOOL_POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(100);
+
 #[derive(Clone, Debug, Error)]
 pub enum TransactionProviderError {
     #[error(transparent)]
@@ -21,6 +35,10 @@ pub enum TransactionProviderError {
     L1HandlerTransactionValidationFailed(TransactionHash),
     #[error(transparent)]
     L1ProviderError(#[from] L1ProviderClientError),
+    #[error("L1 message with nonce {nonce:?} of tx with hash {tx_hash} was already consumed.")]
+    L1MessageAlreadyConsumed { tx_hash: TransactionHash, nonce: Nonce },
+    #[error("Failed to read the consumed L1 messages: {0}.")]
+    L1MessageRegistryError(String),
 }
 
 #[derive(Debug, PartialEq)]
@@ -35,13 +53,14 @@ pub trait TransactionProvider: Send {
     async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs>;
 }
 
-#[derive(Clone)]
 pub struct ProposeTransactionProvider {
     pub mempool_client: SharedMempoolClient,
     pub l1_provider_client: SharedL1ProviderClient,
     pub max_l1_handler_txs_per_block: usize,
     phase: TxProviderPhase,
     n_l1handler_txs_so_far: usize,
+    mempool_stream: Option<MempoolTxStream>,
+    l1_message_registry: L1MessageRegistry,
 }
 
 // Keeps track of whether we need to fetch L1 handler transactions or mempool transactions.
@@ -56,6 +75,7 @@ impl ProposeTransactionProvider {
         mempool_client: SharedMempoolClient,
         l1_provider_client: SharedL1ProviderClient,
         max_l1_handler_txs_per_block: usize,
+        l1_message_registry: L1MessageRegistry,
     ) -> Self {
         Self {
             mempool_client,
@@ -63,26 +83,62 @@ impl ProposeTransactionProvider {
             max_l1_handler_txs_per_block,
             phase: TxProviderPhase::L1,
             n_l1handler_txs_so_far: 0,
+            mempool_stream: None,
+            l1_message_registry,
         }
     }
 
+    /// Switches to streaming mode: instead of fetching mempool transactions when they are
+    /// requested, they are fetched ahead into a buffer of the given size, which is refilled as
+    /// the block builder consumes it.
+    pub fn with_mempool_stream(mut self, fetch_chunk_size: usize, buffer_size: usize) -> Self {
+        self.mempool_stream = Some(MempoolTxStream::spawn(
+            self.mempool_client.clone(),
+            fetch_chunk_size,
+            buffer_size,
+        ));
+        self
+    }
+
     async fn get_l1_handler_txs(
         &mut self,
         n_txs: usize,
+    ) -> TransactionProviderResult<Vec<L1HandlerTransaction>> {
+        Ok(self.l1_provider_client.get_txs(n_txs).await?)
+    }
+
+    /// Drops the transactions of L1 messages that were already consumed, either by a committed
+    /// block or earlier in this proposal.
+    fn filter_consumed_l1_messages(
+        &mut self,
+        l1handler_txs: Vec<L1HandlerTransaction>,
     ) -> TransactionProviderResult<Vec<Transaction>> {
-        Ok(self
-            .l1_provider_client
-            .get_txs(n_txs)
-            .await?
-            .into_iter()
-            .map(Transaction::L1Handler)
-            .collect())
+        let mut unconsumed_txs = Vec::with_capacity(l1handler_txs.len());
+        for tx in l1handler_txs {
+            if self.l1_message_registry.register(&tx)? {
+                unconsumed_txs.push(Transaction::L1Handler(tx));
+            } else {
+                warn!(
+                    "Skipping L1 handler tx {} of already consumed L1 message with nonce {:?}.",
+                    tx.tx_hash, tx.tx.nonce
+                );
+            }
+        }
+        Ok(unconsumed_txs)
     }
 
     async fn get_mempool_txs(
         &mut self,
         n_txs: usize,
     ) -> TransactionProviderResult<Vec<Transaction>> {
+        if let Some(mempool_stream) = &mut self.mempool_stream {
+            return Ok(mempool_stream
+                .try_recv_many(n_txs)?
+                .into_iter()
+                .map(Transaction::Account)
+                .collect());
+        }
+
         Ok(self
             .mempool_client
             .get_txs(n_txs)
@@ -101,11 +157,13 @@ impl TransactionProvider for ProposeTransactionProvider {
         if self.phase == TxProviderPhase::L1 {
             let n_l1handler_txs_to_get =
                 min(self.max_l1_handler_txs_per_block - self.n_l1handler_txs_so_far, n_txs);
-            let mut l1handler_txs = self.get_l1_handler_txs(n_l1handler_txs_to_get).await?;
+            let l1handler_txs = self.get_l1_handler_txs(n_l1handler_txs_to_get).await?;
+            let n_fetched_l1handler_txs = l1handler_txs.len();
+            let mut l1handler_txs = self.filter_consumed_l1_messages(l1handler_txs)?;
             self.n_l1handler_txs_so_far += l1handler_txs.len();
 
             // Determine whether we need to switch to mempool phase.
-            let no_more_l1handler_in_provider = l1handler_txs.len() < n_l1handler_txs_to_get;
+            let no_more_l1handler_in_provider = n_fetched_l1handler_txs < n_l1handler_txs_to_get;
             let reached_max_l1handler_txs_in_block =
                 self.n_l1handler_txs_so_far == self.max_l1_handler_txs_per_block;
             if no_more_l1handler_in_provider || reached_max_l1handler_txs_in_block {
@@ -124,9 +182,79 @@ impl TransactionProvider for ProposeTransactionProvider {
     }
 }
 
+/// Mempool transactions fetched ahead of the block builder, in priority order.
+struct MempoolTxStream {
+    tx_receiver: tokio::sync::mpsc::Receiver<MempoolClientResult<AccountTransaction>>,
+    fetch_task: tokio::task::JoinHandle<()>,
+}
+
+impl MempoolTxStream {
+    fn spawn(
+        mempool_client: SharedMempoolClient,
+        fetch_chunk_size: usize,
+        buffer_size: usize,
+    ) -> Self {
+        assert!(
+            fetch_chunk_size <= buffer_size,
+            "The mempool stream buffer must be able to hold a fetched chunk."
+        );
+        let (tx_sender, tx_receiver) = tokio::sync::mpsc::channel(buffer_size);
+        let fetch_task = tokio::spawn(async move {
+            loop {
+                // Waits until the block builder frees enough buffer capacity for a whole chunk.
+                let Ok(mut permits) = tx_sender.reserve_many(fetch_chunk_size).await else {
+                    // The block builder is done.
+                    return;
+                };
+                match mempool_client.get_txs(fetch_chunk_size).await {
+                    Ok(txs) if txs.is_empty() => {
+                        tokio::time::sleep(EMPTY_MEMPOOL_POLL_INTERVAL).await;
+                    }
+                    Ok(txs) => {
+                        debug!("Streamed {} transactions from the mempool.", txs.len());
+                        for (permit, tx) in permits.by_ref().zip(txs) {
+                            permit.send(Ok(tx));
+                        }
+                    }
+                    Err(err) => {
+                        error!("Failed to stream transactions from the mempool: {}", err);
+                        if let Some(permit) = permits.next() {
+                            permit.send(Err(err));
+                        }
+                        return;
+                    }
+                }
+            }
+        });
+        Self { tx_receiver, fetch_task }
+    }
+
+    /// Returns up to `n_txs` of the already fetched transactions, without waiting for more.
+    fn try_recv_many(
+        &mut self,
+        n_txs: usize,
+    ) -> TransactionProviderResult<Vec<AccountTransaction>> {
+        let mut txs = Vec::with_capacity(n_txs);
+        while txs.len() < n_txs {
+            match self.tx_receiver.try_recv() {
+                Ok(tx) => txs.push(tx?),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+        Ok(txs)
+    }
+}
+
+impl Drop for MempoolTxStream {
+    fn drop(&mut self) {
+        self.fetch_task.abort();
+    }
+}
+
 pub struct ValidateTransactionProvider {
     pub tx_receiver: tokio::sync::mpsc::Receiver<Transaction>,
     pub l1_provider_client: SharedL1ProviderClient,
+    pub l1_message_registry: L1MessageRegistry,
 }
 
 #[async_trait]
@@ -149,8 +277,44 @@ impl TransactionProvider for ValidateTransactionProvider {
                         tx.tx_hash,
                     ));
                 }
+                if !self.l1_message_registry.register(tx)? {
+                    return Err(TransactionProviderError::L1MessageAlreadyConsumed {
+                        tx_hash: tx.tx_hash,
+                        nonce: tx.tx.nonce,
+                    });
+                }
             }
         }
         Ok(NextTxs::Txs(buffer))
     }
 }
+
+/// Guarantees that an L1 message is included at most once: a message is rejected if a committed
+/// block already consumed it, or if it is already part of the current proposal.
+pub struct L1MessageRegistry {
+    storage_reader: Arc<dyn BatcherStorageReaderTrait>,
+    proposal_nonces: HashSet<Nonce>,
+}
+
+impl L1MessageRegistry {
+    pub fn new(storage_reader: Arc<dyn BatcherStorageReaderTrait>) -> Self {
+        Self { storage_reader, proposal_nonces: HashSet::new() }
+    }
+
+    /// Registers the L1 message of the given transaction in the proposal. Returns false if the
+    /// message was already consumed.
+    fn register(&mut self, tx: &L1HandlerTransaction) -> TransactionProviderResult<bool> {
+        let nonce = tx.tx.nonce;
+        if self.proposal_nonces.contains(&nonce) {
+            return Ok(false);
+        }
+        let consumed = self
+            .storage_reader
+            .is_l1_message_consumed(nonce)
+            .map_err(|err| TransactionProviderError::L1MessageRegistryError(err.to_string()))?;
+        if consumed {
+            return Ok(false);
+        }
+        Ok(self.proposal_nonces.insert(nonce))
+    }
+}
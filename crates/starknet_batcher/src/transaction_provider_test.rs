@@ -3,13 +3,16 @@ use std::sync::Arc;
 use assert_matches::assert_matches;
 use mockall::predicate::eq;
 use rstest::{fixture, rstest};
+use starknet_api::core::Nonce;
 use starknet_api::executable_transaction::{L1HandlerTransaction, Transaction};
 use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
-use starknet_api::tx_hash;
+use starknet_api::{nonce, tx_hash};
 use starknet_l1_provider_types::{MockL1ProviderClient, ValidationStatus as L1ValidationStatus};
 use starknet_mempool_types::communication::MockMempoolClient;
 
+use crate::batcher::MockBatcherStorageReaderTrait;
 use crate::transaction_provider::{
+    L1MessageRegistry,
     NextTxs,
     ProposeTransactionProvider,
     TransactionProvider,
@@ -20,20 +23,34 @@ use crate::transaction_provider::{
 const MAX_L1_HANDLER_TXS_PER_BLOCK: usize = 15;
 const MAX_TXS_PER_FETCH: usize = 10;
 const VALIDATE_BUFFER_SIZE: usize = 30;
+const MEMPOOL_STREAM_BUFFER_SIZE: usize = 20;
 
 struct MockDependencies {
     mempool_client: MockMempoolClient,
     l1_provider_client: MockL1ProviderClient,
+    storage_reader: MockBatcherStorageReaderTrait,
     tx_sender: tokio::sync::mpsc::Sender<Transaction>,
     tx_receiver: tokio::sync::mpsc::Receiver<Transaction>,
+    // Used to give each L1 handler transaction returned by the L1 provider a distinct message.
+    n_l1_handler_txs: u64,
 }
 
 impl MockDependencies {
     fn expect_get_l1_handler_txs(&mut self, n_to_request: usize, n_to_return: usize) {
-        self.l1_provider_client
-            .expect_get_txs()
-            .with(eq(n_to_request))
-            .returning(move |_| Ok(vec![L1HandlerTransaction::default(); n_to_return]));
+        let first_nonce = self.n_l1_handler_txs;
+        self.n_l1_handler_txs += u64::try_from(n_to_return).unwrap();
+        self.l1_provider_client.expect_get_txs().with(eq(n_to_request)).returning(move |_| {
+            Ok((first_nonce..)
+                .take(n_to_return)
+                .map(|nonce| l1handler_tx_with_nonce(nonce!(nonce)))
+                .collect())
+        });
+    }
+
+    fn expect_consumed_l1_messages(&mut self, consumed_nonces: Vec<Nonce>) {
+        self.storage_reader
+            .expect_is_l1_message_consumed()
+            .returning(move |nonce| Ok(consumed_nonces.contains(&nonce)));
     }
 
     fn expect_get_mempool_txs(&mut self, n_to_request: usize) {
@@ -60,6 +77,7 @@ impl MockDependencies {
             Arc::new(self.mempool_client),
             Arc::new(self.l1_provider_client),
             MAX_L1_HANDLER_TXS_PER_BLOCK,
+            L1MessageRegistry::new(Arc::new(self.storage_reader)),
         )
     }
 
@@ -67,6 +85,7 @@ impl MockDependencies {
         ValidateTransactionProvider {
             tx_receiver: self.tx_receiver,
             l1_provider_client: Arc::new(self.l1_provider_client),
+            l1_message_registry: L1MessageRegistry::new(Arc::new(self.storage_reader)),
         }
     }
 }
@@ -76,12 +95,16 @@ fn mock_dependencies(
     tx_channel: (tokio::sync::mpsc::Sender<Transaction>, tokio::sync::mpsc::Receiver<Transaction>),
 ) -> MockDependencies {
     let (tx_sender, tx_receiver) = tx_channel;
-    MockDependencies {
+    let mut mock_dependencies = MockDependencies {
         mempool_client: MockMempoolClient::new(),
         l1_provider_client: MockL1ProviderClient::new(),
+        storage_reader: MockBatcherStorageReaderTrait::new(),
         tx_sender,
         tx_receiver,
-    }
+        n_l1_handler_txs: 0,
+    };
+    mock_dependencies.expect_consumed_l1_messages(vec![]);
+    mock_dependencies
 }
 
 #[fixture]
@@ -94,6 +117,12 @@ fn test_l1handler_tx() -> L1HandlerTransaction {
     L1HandlerTransaction { tx_hash: tx_hash!(1), ..Default::default() }
 }
 
+fn l1handler_tx_with_nonce(nonce: Nonce) -> L1HandlerTransaction {
+    let mut tx = L1HandlerTransaction { tx_hash: tx_hash!(nonce.0), ..Default::default() };
+    tx.tx.nonce = nonce;
+    tx
+}
+
 #[rstest]
 #[tokio::test]
 async fn fill_max_l1_handler(mut mock_dependencies: MockDependencies) {
@@ -159,6 +188,29 @@ async fn no_more_l1_handler(mut mock_dependencies: MockDependencies) {
     assert!(data.iter().all(|tx| matches!(tx, Transaction::Account(_))));
 }
 
+#[rstest]
+#[tokio::test]
+async fn stream_mempool_txs(mut mock_dependencies: MockDependencies) {
+    mock_dependencies.expect_get_l1_handler_txs(MAX_TXS_PER_FETCH, 0);
+    mock_dependencies.expect_get_mempool_txs(MAX_TXS_PER_FETCH);
+
+    let mut tx_provider = mock_dependencies
+        .propose_tx_provider()
+        .with_mempool_stream(MAX_TXS_PER_FETCH, MEMPOOL_STREAM_BUFFER_SIZE);
+
+    // The streamed transactions are returned once the stream fetched them.
+    let data = loop {
+        let txs = tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap();
+        let data = assert_matches!(txs, NextTxs::Txs(txs) => txs);
+        if !data.is_empty() {
+            break data;
+        }
+        tokio::task::yield_now().await;
+    };
+    assert_eq!(data.len(), MAX_TXS_PER_FETCH);
+    assert!(data.iter().all(|tx| matches!(tx, Transaction::Account(_))));
+}
+
 #[rstest]
 #[tokio::test]
 async fn validate_flow(mut mock_dependencies: MockDependencies) {
@@ -199,3 +251,56 @@ async fn validate_fails(mut mock_dependencies: MockDependencies) {
         Err(TransactionProviderError::L1HandlerTransactionValidationFailed(_tx_hash))
     );
 }
+
+#[rstest]
+#[tokio::test]
+async fn propose_skips_consumed_l1_messages(mut mock_dependencies: MockDependencies) {
+    // The first two L1 messages were consumed by a committed block; the block is completed with
+    // mempool transactions instead.
+    const N_CONSUMED: usize = 2;
+    mock_dependencies.storage_reader.checkpoint();
+    mock_dependencies.expect_consumed_l1_messages(vec![nonce!(0), nonce!(1)]);
+    mock_dependencies.expect_get_l1_handler_txs(MAX_TXS_PER_FETCH, MAX_TXS_PER_FETCH);
+    mock_dependencies.expect_get_mempool_txs(N_CONSUMED);
+
+    let mut tx_provider = mock_dependencies.propose_tx_provider();
+
+    let txs = tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap();
+    let data = assert_matches!(txs, NextTxs::Txs(txs) if txs.len() == MAX_TXS_PER_FETCH => txs);
+    let n_l1handler_txs = MAX_TXS_PER_FETCH - N_CONSUMED;
+    let l1handler_nonces: Vec<Nonce> = data[..n_l1handler_txs]
+        .iter()
+        .map(|tx| assert_matches!(tx, Transaction::L1Handler(tx) => tx.tx.nonce))
+        .collect();
+    assert!(!l1handler_nonces.contains(&nonce!(0)) && !l1handler_nonces.contains(&nonce!(1)));
+    assert!(data[n_l1handler_txs..].iter().all(|tx| matches!(tx, Transaction::Account(_))));
+}
+
+#[rstest]
+#[case::consumed_in_committed_block(vec![nonce!(0)], vec![l1handler_tx_with_nonce(nonce!(0))])]
+#[case::duplicate_in_proposal(
+    vec![],
+    vec![l1handler_tx_with_nonce(nonce!(0)), l1handler_tx_with_nonce(nonce!(0))]
+)]
+#[tokio::test]
+async fn validate_fails_on_consumed_l1_message(
+    mut mock_dependencies: MockDependencies,
+    #[case] consumed_nonces: Vec<Nonce>,
+    #[case] l1handler_txs: Vec<L1HandlerTransaction>,
+) {
+    mock_dependencies.storage_reader.checkpoint();
+    mock_dependencies.expect_consumed_l1_messages(consumed_nonces);
+    for tx in &l1handler_txs {
+        mock_dependencies.expect_validate_l1handler(tx.clone(), L1ValidationStatus::Validated);
+    }
+    mock_dependencies
+        .simulate_input_txs(l1handler_txs.into_iter().map(Transaction::L1Handler).collect())
+        .await;
+    let mut validate_tx_provider = mock_dependencies.validate_tx_provider();
+
+    let result = validate_tx_provider.get_txs(MAX_TXS_PER_FETCH).await;
+    assert_matches!(
+        result,
+        Err(TransactionProviderError::L1MessageAlreadyConsumed { nonce, .. }) if nonce == nonce!(0)
+    );
+}
@@ -6,14 +6,14 @@ use starknet_api::block::{BlockHashAndNumber, BlockNumber};
 use starknet_batcher_types::batcher_types::{BatcherResult, ProposalStatus};
 use starknet_batcher_types::errors::BatcherError;
 
-use crate::block_builder::BlockBuilderError;
+use crate::block_builder::{AbortSignalSender, BlockBuilderError};
 
 // BlockBuilderError is wrapped in an Arc since it doesn't implement Clone.
 pub(crate) type ProposalResult<T> = Result<T, Arc<BlockBuilderError>>;
 
 // Represents a spawned task of building new block proposal.
 pub(crate) struct ProposalTask {
-    pub abort_signal_sender: tokio::sync::oneshot::Sender<()>,
+    pub abort_signal_sender: AbortSignalSender,
     pub join_handle: tokio::task::JoinHandle<()>,
 }
 
@@ -1,15 +1,30 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber};
-use starknet_api::core::StateDiffCommitment;
+use starknet_api::block::{
+    BlockHashAndNumber,
+    BlockInfo,
+    BlockNumber,
+    GasPrice,
+    GasPriceVector,
+    StarknetVersion,
+};
+use starknet_api::core::{ascii_as_felt, ChainId, StateDiffCommitment};
+use starknet_api::crypto::utils::{verify_message_hash_signature, PublicKey, Signature};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
 
 use crate::errors::BatcherError;
 
+const EXTERNAL_BLOCK_ATTESTATION_DOMAIN: &str = "EXTERNAL_BLOCK_ATTESTATION";
+
 // TODO (Matan) decide on the id structure
 #[derive(
     Copy,
@@ -38,6 +53,12 @@ pub struct ProposeBlockInput {
     pub deadline: chrono::DateTime<Utc>,
     pub retrospective_block_hash: Option<BlockHashAndNumber>,
     pub block_info: BlockInfo,
+    // The time, in seconds since the Unix epoch, the L1 gas prices of the block were sampled at;
+    // `None` if they are not sampled from L1.
+    pub l1_gas_prices_timestamp: Option<u64>,
+    // The proposer's signature over the randomness message of the block, required by chains that
+    // expose randomness to contracts; see `blockifier::block::randomness_seed`.
+    pub randomness_signature: Option<Signature>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -69,6 +90,63 @@ pub struct ValidateBlockInput {
     pub deadline: chrono::DateTime<Utc>,
     pub retrospective_block_hash: Option<BlockHashAndNumber>,
     pub block_info: BlockInfo,
+    // See `ProposeBlockInput::randomness_signature`.
+    pub randomness_signature: Option<Signature>,
+}
+
+/// A fully-specified block built outside of the consensus, e.g., by an external builder or a shared
+/// sequencer, to validate by re-executing it on top of the latest committed state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ValidateExternalBlockInput {
+    pub block_info: BlockInfo,
+    pub retrospective_block_hash: Option<BlockHashAndNumber>,
+    pub transactions: Vec<Transaction>,
+    pub expected_commitment: ProposalCommitment,
+    pub deadline: chrono::DateTime<Utc>,
+    // See `ProposeBlockInput::randomness_signature`.
+    pub randomness_signature: Option<Signature>,
+}
+
+/// The facts an external block attestation attests to: re-executing the block on the chain
+/// resulted, or did not, in the given commitment.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExternalBlockAttestation {
+    pub chain_id: ChainId,
+    pub block_number: BlockNumber,
+    pub state_diff_commitment: StateDiffCommitment,
+    // False if the block failed execution, did not fit in a block, was not executed by its
+    // deadline, or resulted in a different commitment.
+    pub is_valid: bool,
+}
+
+impl ExternalBlockAttestation {
+    /// The message hash signed by the sequencer. The domain prefix keeps attestation signatures
+    /// from being valid for any other message signed with the same key.
+    pub fn hash(&self) -> Result<Felt, StarknetApiError> {
+        Ok(Poseidon::hash_array(&[
+            ascii_as_felt(EXTERNAL_BLOCK_ATTESTATION_DOMAIN)?,
+            ascii_as_felt(&self.chain_id.to_string())?,
+            Felt::from(self.block_number.0),
+            self.state_diff_commitment.0.0,
+            if self.is_valid { Felt::ONE } else { Felt::ZERO },
+        ]))
+    }
+}
+
+/// An external block attestation signed by the sequencer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedExternalBlockAttestation {
+    pub attestation: ExternalBlockAttestation,
+    pub signature: Signature,
+}
+
+impl SignedExternalBlockAttestation {
+    pub fn is_signed_by(&self, public_key: &PublicKey) -> bool {
+        let Ok(message_hash) = self.attestation.hash() else {
+            return false;
+        };
+        verify_message_hash_signature(&message_hash, &self.signature, public_key).unwrap_or(false)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -94,6 +172,28 @@ pub struct SendProposalContentResponse {
 pub struct DecisionReachedResponse {
     pub state_diff: ThinStateDiff,
     pub l2_gas_used: GasAmount,
+    pub fee_market_info: Option<BlockFeeMarketInfo>,
+    // The Starknet version the block was executed with.
+    pub starknet_version: StarknetVersion,
+}
+
+/// Fee market signals of a decided block, letting wallets set resource bounds without relying on
+/// heuristics.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFeeMarketInfo {
+    pub block_number: BlockNumber,
+    // The gas prices the block was executed with, in fri.
+    pub gas_prices: GasPriceVector,
+    // The used share of the block's L2 gas capacity, in percent.
+    pub fullness_percentage: u8,
+    // The predicted L2 gas price of the next block, in fri.
+    pub next_l2_gas_price: GasPrice,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetFeeMarketInfoResponse {
+    // The signals of the latest block decided by the batcher, if any.
+    pub fee_market_info: Option<BlockFeeMarketInfo>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -118,4 +218,127 @@ pub struct DecisionReachedInput {
     pub proposal_id: ProposalId,
 }
 
+/// The token in which a transaction fee was paid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RevenueFeeToken {
+    Eth,
+    Strk,
+}
+
+/// The type of a fee-paying transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RevenueTxType {
+    Declare,
+    DeployAccount,
+    Invoke,
+}
+
+/// Fees collected from a set of transactions, split into the base fee and the tip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeTotals {
+    pub n_txs: u64,
+    pub base_fee: Fee,
+    pub tip: Fee,
+}
+
+impl FeeTotals {
+    pub fn add(&mut self, other: &FeeTotals) {
+        self.n_txs += other.n_txs;
+        self.base_fee = self.base_fee.saturating_add(other.base_fee);
+        self.tip = self.tip.saturating_add(other.tip);
+    }
+}
+
+/// Fees collected in a single fee token, in its smallest unit.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenRevenue {
+    // The number of decimals of the token, to convert the totals to whole tokens.
+    #[serde(default = "default_fee_token_decimals")]
+    pub decimals: u8,
+    pub total: FeeTotals,
+    pub by_tx_type: BTreeMap<RevenueTxType, FeeTotals>,
+}
+
+impl TokenRevenue {
+    pub fn new(decimals: u8) -> Self {
+        Self { decimals, total: FeeTotals::default(), by_tx_type: BTreeMap::new() }
+    }
+}
+
+// Totals stored without their decimals were all collected in the 18-decimal ETH and STRK tokens.
+fn default_fee_token_decimals() -> u8 {
+    18
+}
+
+/// Fees collected by the sequencer, broken down by fee token and transaction type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevenueBreakdown {
+    pub by_fee_token: BTreeMap<RevenueFeeToken, TokenRevenue>,
+}
+
+impl RevenueBreakdown {
+    pub fn add_tx(
+        &mut self,
+        fee_token: RevenueFeeToken,
+        decimals: u8,
+        tx_type: RevenueTxType,
+        fees: FeeTotals,
+    ) {
+        let token_revenue =
+            self.by_fee_token.entry(fee_token).or_insert_with(|| TokenRevenue::new(decimals));
+        token_revenue.total.add(&fees);
+        token_revenue.by_tx_type.entry(tx_type).or_default().add(&fees);
+    }
+
+    pub fn merge(&mut self, other: &RevenueBreakdown) {
+        for (fee_token, other_token_revenue) in &other.by_fee_token {
+            let token_revenue = self
+                .by_fee_token
+                .entry(*fee_token)
+                .or_insert_with(|| TokenRevenue::new(other_token_revenue.decimals));
+            token_revenue.total.add(&other_token_revenue.total);
+            for (tx_type, fees) in &other_token_revenue.by_tx_type {
+                token_revenue.by_tx_type.entry(*tx_type).or_default().add(fees);
+            }
+        }
+    }
+}
+
+/// The revenue of all the blocks committed by the batcher during a single (UTC) day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRevenue {
+    pub date: NaiveDate,
+    pub n_blocks: u64,
+    pub revenue: RevenueBreakdown,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetDailyRevenueResponse {
+    // Sorted by date, oldest first.
+    pub daily_revenue: Vec<DailyRevenue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportBlockBundleInput {
+    pub height: BlockNumber,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportBlockBundleResponse {
+    // A self-contained archive with everything needed to re-execute the block offline.
+    pub archive: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockConfigInput {
+    pub height: BlockNumber,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockConfigResponse {
+    // The configuration the block was executed with (versioned constants hash, bouncer weights and
+    // gas price parameters), serialized as JSON.
+    pub block_config: String,
+}
+
 pub type BatcherResult<T> = Result<T, BatcherError>;
@@ -21,14 +21,22 @@ use crate::batcher_types::{
     BatcherResult,
     DecisionReachedInput,
     DecisionReachedResponse,
+    ExportBlockBundleInput,
+    ExportBlockBundleResponse,
+    GetBlockConfigInput,
+    GetBlockConfigResponse,
+    GetDailyRevenueResponse,
+    GetFeeMarketInfoResponse,
     GetHeightResponse,
     GetProposalContentInput,
     GetProposalContentResponse,
     ProposeBlockInput,
     SendProposalContentInput,
     SendProposalContentResponse,
+    SignedExternalBlockAttestation,
     StartHeightInput,
     ValidateBlockInput,
+    ValidateExternalBlockInput,
 };
 use crate::errors::BatcherError;
 
@@ -79,6 +87,28 @@ pub trait BatcherClient: Send + Sync {
         &self,
         input: DecisionReachedInput,
     ) -> BatcherClientResult<DecisionReachedResponse>;
+    /// Gets the fees collected in the committed blocks, aggregated per day.
+    async fn get_daily_revenue(&self) -> BatcherClientResult<GetDailyRevenueResponse>;
+    /// Exports everything needed to re-execute a recently committed block offline into a single
+    /// archive. Only blocks built by this batcher, within the retention window, can be exported.
+    async fn export_block_bundle(
+        &self,
+        input: ExportBlockBundleInput,
+    ) -> BatcherClientResult<ExportBlockBundleResponse>;
+    /// Gets the fee market signals of the latest decided block.
+    async fn get_fee_market_info(&self) -> BatcherClientResult<GetFeeMarketInfoResponse>;
+    /// Gets the configuration a committed block was executed with. Only blocks committed by this
+    /// batcher have a recorded configuration.
+    async fn get_block_config(
+        &self,
+        input: GetBlockConfigInput,
+    ) -> BatcherClientResult<GetBlockConfigResponse>;
+    /// Validates a block built outside of the consensus by re-executing it, and returns a signed
+    /// attestation of whether it results in its expected commitment. The block is not committed.
+    async fn validate_external_block(
+        &self,
+        input: ValidateExternalBlockInput,
+    ) -> BatcherClientResult<SignedExternalBlockAttestation>;
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -91,6 +121,11 @@ pub enum BatcherRequest {
     GetCurrentHeight,
     DecisionReached(DecisionReachedInput),
     AddSyncBlock(SyncBlock),
+    GetDailyRevenue,
+    ExportBlockBundle(ExportBlockBundleInput),
+    GetFeeMarketInfo,
+    GetBlockConfig(GetBlockConfigInput),
+    ValidateExternalBlock(ValidateExternalBlockInput),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -103,6 +138,11 @@ pub enum BatcherResponse {
     StartHeight(BatcherResult<()>),
     DecisionReached(BatcherResult<DecisionReachedResponse>),
     AddSyncBlock(BatcherResult<()>),
+    GetDailyRevenue(BatcherResult<GetDailyRevenueResponse>),
+    ExportBlockBundle(BatcherResult<ExportBlockBundleResponse>),
+    GetFeeMarketInfo(BatcherResult<GetFeeMarketInfoResponse>),
+    GetBlockConfig(BatcherResult<GetBlockConfigResponse>),
+    ValidateExternalBlock(BatcherResult<SignedExternalBlockAttestation>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -194,4 +234,62 @@ where
         let response = self.send(request).await;
         handle_response_variants!(BatcherResponse, AddSyncBlock, BatcherClientError, BatcherError)
     }
+
+    async fn get_daily_revenue(&self) -> BatcherClientResult<GetDailyRevenueResponse> {
+        let request = BatcherRequest::GetDailyRevenue;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            GetDailyRevenue,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn export_block_bundle(
+        &self,
+        input: ExportBlockBundleInput,
+    ) -> BatcherClientResult<ExportBlockBundleResponse> {
+        let request = BatcherRequest::ExportBlockBundle(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            ExportBlockBundle,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_fee_market_info(&self) -> BatcherClientResult<GetFeeMarketInfoResponse> {
+        let request = BatcherRequest::GetFeeMarketInfo;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            GetFeeMarketInfo,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_block_config(
+        &self,
+        input: GetBlockConfigInput,
+    ) -> BatcherClientResult<GetBlockConfigResponse> {
+        let request = BatcherRequest::GetBlockConfig(input);
+        let response = self.send(request).await;
+        handle_response_variants!(BatcherResponse, GetBlockConfig, BatcherClientError, BatcherError)
+    }
+    async fn validate_external_block(
+        &self,
+        input: ValidateExternalBlockInput,
+    ) -> BatcherClientResult<SignedExternalBlockAttestation> {
+        let request = BatcherRequest::ValidateExternalBlock(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            ValidateExternalBlock,
+            BatcherClientError,
+            BatcherError
+        )
+    }
 }
@@ -1,6 +1,7 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::BlockNumber;
+use starknet_api::block::{BlockNumber, GasPrice};
+use starknet_api::core::Nonce;
 use thiserror::Error;
 
 use crate::batcher_types::ProposalId;
@@ -16,12 +17,31 @@ pub enum BatcherError {
         "Height {storage_height} already passed, can't start working on height {requested_height}."
     )]
     HeightAlreadyPassed { storage_height: BlockNumber, requested_height: BlockNumber },
+    #[error(
+        "No bundle of block {height} is retained; it was not built by this batcher or is too old."
+    )]
+    BlockBundleNotFound { height: BlockNumber },
+    #[error("No config of block {height} is recorded; it was not committed by this batcher.")]
+    BlockConfigNotFound { height: BlockNumber },
     #[error("Height is in progress.")]
     HeightInProgress,
     #[error("Internal server error.")]
     InternalError,
+    #[error(
+        "Invalid L2 gas price {l2_gas_price}; expected a price between {min_l2_gas_price} and \
+         {max_l2_gas_price}."
+    )]
+    InvalidL2GasPrice {
+        l2_gas_price: GasPrice,
+        min_l2_gas_price: GasPrice,
+        max_l2_gas_price: GasPrice,
+    },
     #[error("Invalid block number. The active height is {active_height}, got {block_number}.")]
     InvalidBlockNumber { active_height: BlockNumber, block_number: BlockNumber },
+    #[error("L1 message with nonce {nonce} was already consumed at block {block_number}.")]
+    L1MessageAlreadyConsumed { nonce: Nonce, block_number: BlockNumber },
+    #[error("Missing the randomness signature, which the chain requires.")]
+    MissingRandomnessSignature,
     #[error("Missing retrospective block hash.")]
     MissingRetrospectiveBlockHash,
     #[error("Attempt to start proposal with no active height.")]
@@ -32,6 +52,8 @@ pub enum BatcherError {
         new_proposal_id
     )]
     AnotherProposalInProgress { active_proposal_id: ProposalId, new_proposal_id: ProposalId },
+    #[error("Proposal {active_proposal_id} is being executed, can't validate an external block.")]
+    ProposalInProgress { active_proposal_id: ProposalId },
     #[error("Proposal with ID {proposal_id} already exists.")]
     ProposalAlreadyExists { proposal_id: ProposalId },
     #[error(
@@ -50,6 +72,8 @@ pub enum BatcherError {
          {requested_height}."
     )]
     StorageNotSynced { storage_height: BlockNumber, requested_height: BlockNumber },
+    #[error("The L1 gas prices are {age_seconds} seconds old, exceeding the staleness threshold.")]
+    StaleL1GasPrices { age_seconds: u64 },
     #[error("Time to deadline is out of range. Got {deadline}.")]
     TimeToDeadlineError { deadline: chrono::DateTime<Utc> },
 }
@@ -1,23 +1,51 @@
 use std::collections::BTreeMap;
 
-use papyrus_config::dumping::{append_sub_config_name, SerializeConfig};
-use papyrus_config::{ParamPath, SerializedParam};
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_sub_config,
+    ser_param,
+    SerializeConfig,
+};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_consensus::config::ConsensusConfig;
+use papyrus_consensus_orchestrator::l1_gas_price_provider::L1GasPriceProviderConfig;
+use papyrus_consensus_orchestrator::proposal_retention::ProposalRetentionConfig;
+use papyrus_protobuf::consensus::CompressionAlgorithm;
 use serde::{Deserialize, Serialize};
+use starknet_sequencer_infra::signer::SignerConfig;
 use validator::Validate;
 
 /// The consensus manager related configuration.
-/// TODO(Matan): Remove ConsensusManagerConfig if it's only field remains ConsensusConfig.
 #[derive(Clone, Default, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct ConsensusManagerConfig {
     pub consensus_config: ConsensusConfig,
+    pub proposal_compression: CompressionAlgorithm,
+    /// If unset, the blocks this node builds are priced with temporary L1 gas prices.
+    pub l1_gas_price_provider_config: Option<L1GasPriceProviderConfig>,
+    /// Signs the randomness messages of the proposals this node builds.
+    pub signer_config: SignerConfig,
+    pub proposal_retention_config: ProposalRetentionConfig,
 }
 
 impl SerializeConfig for ConsensusManagerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        let sub_configs =
-            vec![append_sub_config_name(self.consensus_config.dump(), "consensus_config")];
-
-        sub_configs.into_iter().flatten().collect()
+        let mut dump = append_sub_config_name(self.consensus_config.dump(), "consensus_config");
+        dump.append(&mut BTreeMap::from([ser_param(
+            "proposal_compression",
+            &self.proposal_compression,
+            "The algorithm compressing the transactions of the proposals this node builds: \
+             Uncompressed, Zstd or Gzip. Compress only once all validators support it.",
+            ParamPrivacyInput::Public,
+        )]));
+        dump.append(&mut ser_optional_sub_config(
+            &self.l1_gas_price_provider_config,
+            "l1_gas_price_provider_config",
+        ));
+        dump.append(&mut append_sub_config_name(self.signer_config.dump(), "signer_config"));
+        dump.append(&mut append_sub_config_name(
+            self.proposal_retention_config.dump(),
+            "proposal_retention_config",
+        ));
+        dump
     }
 }
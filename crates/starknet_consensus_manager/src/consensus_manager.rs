@@ -5,6 +5,7 @@ use infra_utils::type_name::short_type_name;
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::ConsensusError;
 use papyrus_consensus_orchestrator::cende::CendeAmbassador;
+use papyrus_consensus_orchestrator::l1_gas_price_provider::create_l1_gas_price_provider;
 use papyrus_consensus_orchestrator::sequencer_consensus_context::SequencerConsensusContext;
 use papyrus_network::gossipsub_impl::Topic;
 use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkManager};
@@ -13,6 +14,7 @@ use starknet_api::block::BlockNumber;
 use starknet_batcher_types::communication::SharedBatcherClient;
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::errors::ComponentError;
+use starknet_sequencer_infra::signer::LocalKeySigner;
 use starknet_state_sync_types::communication::SharedStateSyncClient;
 use tracing::{error, info};
 
@@ -88,6 +90,10 @@ impl ConsensusManager {
             self.config.consensus_config.num_validators,
             self.config.consensus_config.chain_id.clone(),
             Arc::new(CendeAmbassador::new()),
+            self.config.proposal_compression,
+            self.config.l1_gas_price_provider_config.as_ref().map(create_l1_gas_price_provider),
+            Arc::new(LocalKeySigner::new(&self.config.signer_config)),
+            self.config.proposal_retention_config.clone(),
         );
 
         let mut network_handle = tokio::task::spawn(network_manager.run());
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use blockifier::bouncer::BouncerConfig;
+use blockifier::context::{BlockContext, ChainInfo, TransactionContext};
+use blockifier::execution::entry_point::{CallEntryPoint, CallType, EntryPointExecutionContext};
+use blockifier::state::cached_state::CachedState;
+use blockifier::state::state_api::StateReader;
+use blockifier::transaction::objects::{DeprecatedTransactionInfo, TransactionInfo};
+use blockifier::versioned_constants::VersionedConstants;
+use starknet_api::contract_class::EntryPointType;
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::{CallBlockId, CallInput};
+use starknet_types_core::felt::Felt;
+use tracing::{debug, error};
+
+use crate::config::GatewayConfig;
+use crate::errors::GatewayResult;
+use crate::state_reader::StateReaderFactory;
+use crate::stateful_transaction_validator::get_latest_block_info;
+
+#[cfg(test)]
+#[path = "call_executor_test.rs"]
+mod call_executor_test;
+
+/// Executes a read-only contract call against the state after the requested block, and returns
+/// its return data. The call is bounded by the configured gas limit; its state changes are
+/// discarded.
+pub(crate) fn execute_call(
+    call_input: CallInput,
+    state_reader_factory: &dyn StateReaderFactory,
+    chain_info: &ChainInfo,
+    config: &GatewayConfig,
+) -> GatewayResult<Vec<Felt>> {
+    let CallInput { contract_address, entry_point_selector, calldata, block_id } = call_input;
+
+    let latest_block_number = get_latest_block_info(state_reader_factory)?.block_number;
+    let block_number = match block_id {
+        CallBlockId::Latest | CallBlockId::Pending => latest_block_number,
+        CallBlockId::Number(block_number) if block_number <= latest_block_number => block_number,
+        CallBlockId::Number(_) => return Err(GatewaySpecError::BlockNotFound),
+    };
+    let state_reader = state_reader_factory.get_state_reader(block_number);
+    let block_info = state_reader.get_block_info().map_err(|e| {
+        error!("Failed to get the info of block {block_number}: {}", e);
+        GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+    })?;
+
+    let class_hash = state_reader.get_class_hash_at(contract_address).map_err(|e| {
+        error!("Failed to get the class hash of contract {contract_address}: {}", e);
+        GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+    })?;
+    if class_hash == ClassHash::default() {
+        return Err(GatewaySpecError::ContractNotFound);
+    }
+
+    let versioned_constants = VersionedConstants::get_scheduled_versioned_constants(
+        &config.stateful_tx_validator_config.starknet_version_schedule,
+        block_number,
+        block_info.block_timestamp,
+        config.stateful_tx_validator_config.versioned_constants_overrides.clone(),
+    )
+    .map_err(|e| {
+        error!("Failed to get versioned constants: {}", e);
+        GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+    })?;
+    let block_context = BlockContext::new(
+        block_info,
+        chain_info.clone(),
+        versioned_constants,
+        BouncerConfig::max(),
+    );
+    let tx_info = TransactionInfo::Deprecated(DeprecatedTransactionInfo::default());
+    let limit_steps_by_resources = false;
+    let mut context = EntryPointExecutionContext::new_invoke(
+        Arc::new(TransactionContext { block_context, tx_info }),
+        limit_steps_by_resources,
+    );
+
+    let mut remaining_gas = config.max_call_gas;
+    let call_entry_point = CallEntryPoint {
+        class_hash: None,
+        code_address: Some(contract_address),
+        entry_point_type: EntryPointType::External,
+        entry_point_selector,
+        calldata,
+        storage_address: contract_address,
+        caller_address: ContractAddress::default(),
+        call_type: CallType::Call,
+        initial_gas: remaining_gas,
+    };
+    // The cached state is dropped once the call returns, so nothing is written to the state.
+    let mut state = CachedState::new(state_reader);
+    let call_info =
+        call_entry_point.execute(&mut state, &mut context, &mut remaining_gas).map_err(|e| {
+            debug!("Call to contract {contract_address} failed: {}", e);
+            GatewaySpecError::ContractError { data: e.to_string() }
+        })?;
+
+    let execution = call_info.execution;
+    if execution.failed {
+        return Err(GatewaySpecError::ContractError {
+            data: format!("Execution failed. Failure reason: {:?}.", execution.retdata.0),
+        });
+    }
+    Ok(execution.retdata.0)
+}
@@ -0,0 +1,91 @@
+use assert_matches::assert_matches;
+use blockifier::context::ChainInfo;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::{CairoVersion, RunnableCairo1};
+use rstest::{fixture, rstest};
+use starknet_api::abi::abi_utils::selector_from_name;
+use starknet_api::core::ContractAddress;
+use starknet_api::{calldata, contract_address, felt};
+use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::{CallBlockId, CallInput};
+
+use crate::call_executor::execute_call;
+use crate::config::GatewayConfig;
+use crate::state_reader_test_utils::{local_test_state_reader_factory, TestStateReaderFactory};
+
+#[fixture]
+fn state_reader_factory() -> TestStateReaderFactory {
+    local_test_state_reader_factory(CairoVersion::Cairo1(RunnableCairo1::Casm), false)
+}
+
+fn return_result_call(contract_address: ContractAddress, block_id: CallBlockId) -> CallInput {
+    CallInput {
+        contract_address,
+        entry_point_selector: selector_from_name("return_result"),
+        calldata: calldata![felt!(7_u8)],
+        block_id,
+    }
+}
+
+fn test_contract_address() -> ContractAddress {
+    FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm))
+        .get_instance_address(0)
+}
+
+#[rstest]
+#[case::latest(CallBlockId::Latest)]
+#[case::pending(CallBlockId::Pending)]
+fn call_returns_retdata(
+    state_reader_factory: TestStateReaderFactory,
+    #[case] block_id: CallBlockId,
+) {
+    let call_input = return_result_call(test_contract_address(), block_id);
+
+    let retdata = execute_call(
+        call_input,
+        &state_reader_factory,
+        &ChainInfo::create_for_testing(),
+        &GatewayConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(retdata, vec![felt!(7_u8)]);
+}
+
+#[rstest]
+fn call_to_unknown_contract(state_reader_factory: TestStateReaderFactory) {
+    let call_input = return_result_call(contract_address!("0xdead"), CallBlockId::Latest);
+
+    let result = execute_call(
+        call_input,
+        &state_reader_factory,
+        &ChainInfo::create_for_testing(),
+        &GatewayConfig::default(),
+    );
+    assert_matches!(result, Err(GatewaySpecError::ContractNotFound));
+}
+
+#[rstest]
+fn call_at_future_block(state_reader_factory: TestStateReaderFactory) {
+    let future_block_number =
+        state_reader_factory.state_reader.block_info.block_number.unchecked_next();
+    let call_input =
+        return_result_call(test_contract_address(), CallBlockId::Number(future_block_number));
+
+    let result = execute_call(
+        call_input,
+        &state_reader_factory,
+        &ChainInfo::create_for_testing(),
+        &GatewayConfig::default(),
+    );
+    assert_matches!(result, Err(GatewaySpecError::BlockNotFound));
+}
+
+#[rstest]
+fn call_out_of_gas(state_reader_factory: TestStateReaderFactory) {
+    let call_input = return_result_call(test_contract_address(), CallBlockId::Latest);
+    let config = GatewayConfig { max_call_gas: 1, ..Default::default() };
+
+    let result =
+        execute_call(call_input, &state_reader_factory, &ChainInfo::create_for_testing(), &config);
+    assert_matches!(result, Err(GatewaySpecError::ContractError { .. }));
+}
@@ -18,14 +18,35 @@ impl ComponentRequestHandler<GatewayRequest, GatewayResponse> for Gateway {
             GatewayRequest::AddTransaction(gateway_input) => {
                 let p2p_message_metadata = gateway_input.message_metadata.clone();
                 GatewayResponse::AddTransaction(
-                    self.add_tx(gateway_input.rpc_tx, gateway_input.message_metadata)
-                        .await
-                        .map_err(|source| GatewayError::GatewaySpecError {
-                            source,
-                            p2p_message_metadata,
-                        }),
+                    self.add_tx(
+                        gateway_input.rpc_tx,
+                        gateway_input.message_metadata,
+                        gateway_input.reconciled,
+                    )
+                    .await
+                    .map_err(|source| GatewayError::GatewaySpecError {
+                        source,
+                        p2p_message_metadata,
+                    }),
                 )
             }
+            GatewayRequest::Call(call_input) => {
+                GatewayResponse::Call(self.call(call_input).await.map_err(|source| {
+                    GatewayError::GatewaySpecError { source, p2p_message_metadata: None }
+                }))
+            }
+            GatewayRequest::CheckFeeBalance(rpc_tx) => {
+                GatewayResponse::CheckFeeBalance(self.check_fee_balance(rpc_tx).await.map_err(
+                    |source| GatewayError::GatewaySpecError { source, p2p_message_metadata: None },
+                ))
+            }
+            GatewayRequest::NotifyDroppedTransactions(dropped_txs) => {
+                self.record_dropped_txs(dropped_txs);
+                GatewayResponse::NotifyDroppedTransactions(Ok(()))
+            }
+            GatewayRequest::GetDroppedTransaction(tx_hash) => {
+                GatewayResponse::GetDroppedTransaction(Ok(self.get_dropped_tx(tx_hash)))
+            }
         }
     }
 }
@@ -5,13 +5,14 @@ use cairo_lang_starknet_classes::contract_class::ContractClass as CairoLangContr
 use starknet_api::contract_class::{ClassInfo, ContractClass, SierraVersion};
 use starknet_api::rpc_transaction::RpcDeclareTransaction;
 use starknet_gateway_types::errors::GatewaySpecError;
-use starknet_sierra_compile::command_line_compiler::CommandLineCompiler;
+use starknet_sierra_compile::compilation_cache::CasmCompilationCache;
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 use starknet_sierra_compile::utils::into_contract_class_for_compilation;
-use starknet_sierra_compile::SierraToCasmCompiler;
-use tracing::{debug, error};
+use starknet_sierra_compile::{create_sierra_to_casm_compiler, SierraToCasmCompiler};
+use tracing::{debug, error, warn};
 
 use crate::errors::GatewayResult;
+use crate::utils::declared_class_hash;
 
 #[cfg(test)]
 #[path = "compilation_test.rs"]
@@ -21,11 +22,17 @@ mod compilation_test;
 #[derive(Clone)]
 pub struct GatewayCompiler {
     pub sierra_to_casm_compiler: Arc<dyn SierraToCasmCompiler>,
+    // If set, classes compiled before (possibly by a previous run) are loaded from this cache.
+    compilation_cache: Option<Arc<CasmCompilationCache>>,
 }
 
 impl GatewayCompiler {
-    pub fn new_command_line_compiler(config: SierraToCasmCompilationConfig) -> Self {
-        Self { sierra_to_casm_compiler: Arc::new(CommandLineCompiler::new(config)) }
+    /// Creates a compiler of the backend the config selects.
+    pub fn new(config: SierraToCasmCompilationConfig) -> Self {
+        let compilation_cache = config.compilation_cache_dir.clone().map(|dir| {
+            Arc::new(CasmCompilationCache::new(dir).expect("Failed to open the compilation cache."))
+        });
+        Self { sierra_to_casm_compiler: create_sierra_to_casm_compiler(config), compilation_cache }
     }
 
     /// Formats the contract class for compilation, compiles it, and returns the compiled contract
@@ -44,7 +51,7 @@ impl GatewayCompiler {
                 .map_err(|e| GatewaySpecError::UnexpectedError { data: (e.to_string()) })?;
 
         let casm_contract_class =
-            (self.compile(cairo_lang_contract_class)?, sierra_version.clone());
+            (self.compile_or_load(declare_tx, cairo_lang_contract_class)?, sierra_version.clone());
 
         Ok(ClassInfo {
             contract_class: ContractClass::V1(casm_contract_class),
@@ -54,6 +61,31 @@ impl GatewayCompiler {
         })
     }
 
+    /// Loads the class from the compilation cache, if it was compiled before; otherwise, compiles
+    /// it and caches the result.
+    fn compile_or_load(
+        &self,
+        declare_tx: &RpcDeclareTransaction,
+        cairo_lang_contract_class: CairoLangContractClass,
+    ) -> GatewayResult<CasmContractClass> {
+        let Some(compilation_cache) = &self.compilation_cache else {
+            return self.compile(cairo_lang_contract_class);
+        };
+
+        let RpcDeclareTransaction::V3(tx) = declare_tx;
+        let class_hash = declared_class_hash(declare_tx);
+        if let Some(casm_contract_class) = compilation_cache.get(class_hash, tx.compiled_class_hash)
+        {
+            return Ok(casm_contract_class);
+        }
+
+        let casm_contract_class = self.compile(cairo_lang_contract_class)?;
+        if let Err(error) = compilation_cache.set(class_hash, &casm_contract_class) {
+            warn!("Failed to cache the compiled class {class_hash}: {error}");
+        }
+        Ok(casm_contract_class)
+    }
+
     fn compile(
         &self,
         cairo_lang_contract_class: CairoLangContractClass,
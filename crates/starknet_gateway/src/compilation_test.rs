@@ -10,15 +10,16 @@ use starknet_api::rpc_transaction::{
     RpcTransaction,
 };
 use starknet_gateway_types::errors::GatewaySpecError;
-use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
+use starknet_sierra_compile::config::{SierraCompilerBackend, SierraToCasmCompilationConfig};
 use starknet_sierra_compile::errors::CompilationUtilError;
+use tempfile::tempdir;
 use tracing_test::traced_test;
 
 use crate::compilation::GatewayCompiler;
 
 #[fixture]
 fn gateway_compiler() -> GatewayCompiler {
-    GatewayCompiler::new_command_line_compiler(SierraToCasmCompilationConfig::default())
+    GatewayCompiler::new(SierraToCasmCompilationConfig::default())
 }
 
 #[fixture]
@@ -33,10 +34,10 @@ fn declare_tx_v3() -> RpcDeclareTransactionV3 {
 #[traced_test]
 #[rstest]
 fn test_compile_contract_class_bytecode_size_validation(declare_tx_v3: RpcDeclareTransactionV3) {
-    let gateway_compiler =
-        GatewayCompiler::new_command_line_compiler(SierraToCasmCompilationConfig {
-            max_bytecode_size: 1,
-        });
+    let gateway_compiler = GatewayCompiler::new(SierraToCasmCompilationConfig {
+        max_bytecode_size: 1,
+        ..Default::default()
+    });
 
     let result = gateway_compiler.process_declare_tx(&RpcDeclareTransaction::V3(declare_tx_v3));
     assert_matches!(result.unwrap_err(), GatewaySpecError::CompilationFailed);
@@ -81,3 +82,34 @@ fn test_process_declare_tx_success(
     assert_eq!(class_info.sierra_program_length, sierra_program_length);
     assert_eq!(class_info.abi_length, abi_length);
 }
+
+#[rstest]
+fn test_process_declare_tx_with_in_process_compiler(declare_tx_v3: RpcDeclareTransactionV3) {
+    let gateway_compiler = GatewayCompiler::new(SierraToCasmCompilationConfig {
+        backend: SierraCompilerBackend::InProcess,
+        ..Default::default()
+    });
+    let declare_tx = RpcDeclareTransaction::V3(declare_tx_v3);
+
+    let class_info = gateway_compiler.process_declare_tx(&declare_tx).unwrap();
+    assert_eq!(class_info.contract_class.compiled_class_hash(), *COMPILED_CLASS_HASH);
+}
+
+#[rstest]
+fn test_process_declare_tx_loads_cached_class(declare_tx_v3: RpcDeclareTransactionV3) {
+    let cache_dir = tempdir().unwrap();
+    let compiler_config = SierraToCasmCompilationConfig {
+        compilation_cache_dir: Some(cache_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let declare_tx = RpcDeclareTransaction::V3(declare_tx_v3);
+    let class_info =
+        GatewayCompiler::new(compiler_config.clone()).process_declare_tx(&declare_tx).unwrap();
+
+    // A compiler that fails to compile the class loads it from the cache instead.
+    let failing_compiler = GatewayCompiler::new(SierraToCasmCompilationConfig {
+        max_bytecode_size: 1,
+        ..compiler_config
+    });
+    assert_eq!(failing_compiler.process_declare_tx(&declare_tx).unwrap(), class_info);
+}
@@ -5,24 +5,49 @@ use blockifier::versioned_constants::VersionedConstantsOverrides;
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
+use starknet_api::block::StarknetVersionSchedule;
 use starknet_api::core::Nonce;
+use starknet_sequencer_infra::signer::SignerConfig;
 use starknet_types_core::felt::Felt;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use crate::compiler_version::VersionId;
 
 const JSON_RPC_VERSION: &str = "2.0";
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct GatewayConfig {
     pub stateless_tx_validator_config: StatelessTransactionValidatorConfig,
+    #[validate]
     pub stateful_tx_validator_config: StatefulTransactionValidatorConfig,
     pub chain_info: ChainInfo,
+    // The gas available to a read-only contract call.
+    pub max_call_gas: u64,
+    // The key receipts of accepted transactions are signed with.
+    pub signer_config: SignerConfig,
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            stateless_tx_validator_config: StatelessTransactionValidatorConfig::default(),
+            stateful_tx_validator_config: StatefulTransactionValidatorConfig::default(),
+            chain_info: ChainInfo::default(),
+            max_call_gas: 100_000_000,
+            signer_config: SignerConfig::default(),
+        }
+    }
 }
 
 impl SerializeConfig for GatewayConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         vec![
+            BTreeMap::from_iter([ser_param(
+                "max_call_gas",
+                &self.max_call_gas,
+                "The maximal amount of gas a read-only contract call may consume.",
+                ParamPrivacyInput::Public,
+            )]),
             append_sub_config_name(
                 self.stateless_tx_validator_config.dump(),
                 "stateless_tx_validator_config",
@@ -32,6 +57,7 @@ impl SerializeConfig for GatewayConfig {
                 "stateful_tx_validator_config",
             ),
             append_sub_config_name(self.chain_info.dump(), "chain_info"),
+            append_sub_config_name(self.signer_config.dump(), "signer_config"),
         ]
         .into_iter()
         .flatten()
@@ -45,8 +71,19 @@ pub struct StatelessTransactionValidatorConfig {
     pub validate_non_zero_l1_gas_fee: bool,
     pub validate_non_zero_l2_gas_fee: bool,
     pub validate_non_zero_l1_data_gas_fee: bool,
+    // Resource bounds with a max amount above these limits can't be used by any block; depending
+    // on `reject_excessive_resource_bounds`, such transactions are rejected or accepted with a
+    // warning.
+    pub max_l1_gas_amount: u64,
+    pub max_l2_gas_amount: u64,
+    pub max_l1_data_gas_amount: u64,
+    pub reject_excessive_resource_bounds: bool,
     pub max_calldata_length: usize,
     pub max_signature_length: usize,
+    // If true, accepts transactions with L2 nonce and fee data availability modes.
+    pub allow_alternative_da: bool,
+    // If true, accepts transactions with paymaster data, sponsored by the paymaster it names.
+    pub allow_paymaster_data: bool,
 
     // Declare txs specific config.
     pub max_contract_class_object_size: usize,
@@ -60,8 +97,14 @@ impl Default for StatelessTransactionValidatorConfig {
             validate_non_zero_l1_gas_fee: true,
             validate_non_zero_l2_gas_fee: false,
             validate_non_zero_l1_data_gas_fee: false,
+            max_l1_gas_amount: 10_000_000_000,
+            max_l2_gas_amount: 1_000_000_000_000,
+            max_l1_data_gas_amount: 10_000_000_000,
+            reject_excessive_resource_bounds: false,
             max_calldata_length: 4000,
             max_signature_length: 4000,
+            allow_alternative_da: false,
+            allow_paymaster_data: false,
             max_contract_class_object_size: 4089446,
             min_sierra_version: VersionId::new(1, 1, 0),
             max_sierra_version: VersionId::new(1, 5, usize::MAX),
@@ -91,6 +134,32 @@ impl SerializeConfig for StatelessTransactionValidatorConfig {
                  bounds.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_l1_gas_amount",
+                &self.max_l1_gas_amount,
+                "The maximal sane max amount of L1 gas in the resource bounds of a transaction.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_l2_gas_amount",
+                &self.max_l2_gas_amount,
+                "The maximal sane max amount of L2 gas in the resource bounds of a transaction.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_l1_data_gas_amount",
+                &self.max_l1_data_gas_amount,
+                "The maximal sane max amount of L1 data gas in the resource bounds of a \
+                 transaction.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "reject_excessive_resource_bounds",
+                &self.reject_excessive_resource_bounds,
+                "If true, rejects transactions whose resource bounds exceed the maximal sane \
+                 amounts; otherwise, they are accepted with a warning.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "max_signature_length",
                 &self.max_signature_length,
@@ -103,6 +172,20 @@ impl SerializeConfig for StatelessTransactionValidatorConfig {
                 "Limitation of calldata length.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "allow_alternative_da",
+                &self.allow_alternative_da,
+                "If true, accepts transactions with L2 nonce and fee data availability modes, \
+                 whose state diffs are published to an alternative data availability layer.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "allow_paymaster_data",
+                &self.allow_paymaster_data,
+                "If true, accepts transactions with paymaster data, whose fee is sponsored by the \
+                 paymaster it names. Requires paymasters to be enabled in the chain info.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "max_contract_class_object_size",
                 &self.max_contract_class_object_size,
@@ -163,7 +246,11 @@ impl SerializeConfig for RpcStateReaderConfig {
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct StatefulTransactionValidatorConfig {
     pub max_nonce_for_validation_skip: Nonce,
+    #[validate(custom = "validate_versioned_constants_overrides")]
     pub versioned_constants_overrides: VersionedConstantsOverrides,
+    pub starknet_version_schedule: StarknetVersionSchedule,
+    #[validate]
+    pub validate_spam_filter_config: ValidateSpamFilterConfig,
 }
 
 impl Default for StatefulTransactionValidatorConfig {
@@ -171,10 +258,22 @@ impl Default for StatefulTransactionValidatorConfig {
         StatefulTransactionValidatorConfig {
             max_nonce_for_validation_skip: Nonce(Felt::ONE),
             versioned_constants_overrides: VersionedConstantsOverrides::default(),
+            starknet_version_schedule: StarknetVersionSchedule::default(),
+            validate_spam_filter_config: ValidateSpamFilterConfig::default(),
         }
     }
 }
 
+fn validate_versioned_constants_overrides(
+    versioned_constants_overrides: &VersionedConstantsOverrides,
+) -> Result<(), ValidationError> {
+    versioned_constants_overrides.validate().map_err(|error| {
+        let mut validation_error = ValidationError::new("Invalid versioned constants file.");
+        validation_error.message = Some(error.to_string().into());
+        validation_error
+    })
+}
+
 impl SerializeConfig for StatefulTransactionValidatorConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         let mut dump = BTreeMap::from_iter([ser_param(
@@ -187,6 +286,99 @@ impl SerializeConfig for StatefulTransactionValidatorConfig {
             self.versioned_constants_overrides.dump(),
             "versioned_constants_overrides",
         ));
+        dump.append(&mut BTreeMap::from([ser_param(
+            "starknet_version_schedule",
+            &self.starknet_version_schedule,
+            "Space-separated <block_number>:<starknet_version> and \
+             t<unix_timestamp>:<starknet_version> pairs, including block 0; transactions are \
+             validated with the versioned constants of the version activated at the next block.",
+            ParamPrivacyInput::Public,
+        )]));
+        dump.append(&mut append_sub_config_name(
+            self.validate_spam_filter_config.dump(),
+            "validate_spam_filter_config",
+        ));
         dump
     }
 }
+
+/// Throttling of accounts whose `__validate__` runs consistently consume gas near the allowed
+/// maximum.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ValidateSpamFilterConfig {
+    pub enabled: bool,
+    // The number of most recent validations of an account its profile consists of.
+    #[validate(range(min = 1))]
+    pub window_size: usize,
+    // A validation consuming at least this percentage of the maximal validation gas is expensive.
+    #[validate(range(max = 100))]
+    pub expensive_validation_percentage: u8,
+    // An account is throttled once at least this percentage of the validations in its window are
+    // expensive.
+    #[validate(range(max = 100))]
+    pub throttling_percentage: u8,
+    // Throttled accounts have one of every this many transactions admitted.
+    #[validate(range(min = 1))]
+    pub throttled_admission_interval: u64,
+    // The number of most recently validated accounts whose profiles are kept.
+    #[validate(range(min = 1))]
+    pub max_tracked_accounts: usize,
+}
+
+impl Default for ValidateSpamFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_size: 20,
+            expensive_validation_percentage: 90,
+            throttling_percentage: 80,
+            throttled_admission_interval: 10,
+            max_tracked_accounts: 100000,
+        }
+    }
+}
+
+impl SerializeConfig for ValidateSpamFilterConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, throttles accounts whose validations are consistently expensive.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "window_size",
+                &self.window_size,
+                "The number of most recent validations of an account its throttling is decided by.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "expensive_validation_percentage",
+                &self.expensive_validation_percentage,
+                "A validation consuming at least this percentage of the maximal validation gas is \
+                 considered expensive.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "throttling_percentage",
+                &self.throttling_percentage,
+                "An account is throttled once at least this percentage of its recent validations \
+                 are expensive.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "throttled_admission_interval",
+                &self.throttled_admission_interval,
+                "A throttled account has one of every this many transactions admitted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_tracked_accounts",
+                &self.max_tracked_accounts,
+                "The number of most recently validated accounts whose validations are profiled.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::{HashMap, VecDeque};
+
+use starknet_api::transaction::TransactionHash;
+use starknet_mempool_types::mempool_types::DroppedTransaction;
+
+#[cfg(test)]
+#[path = "dropped_transactions_test.rs"]
+mod dropped_transactions_test;
+
+/// The number of dropped transactions the gateway keeps track of.
+pub const DROPPED_TRANSACTIONS_CAPACITY: usize = 100_000;
+
+/// Keeps the most recent transactions dropped by the mempool, so that clients querying them get a
+/// terminal status.
+#[derive(Debug)]
+pub struct DroppedTransactions {
+    capacity: usize,
+    dropped_txs: HashMap<TransactionHash, DroppedTransaction>,
+    // Insertion order, used to forget the oldest records once the capacity is reached.
+    insertion_order: VecDeque<TransactionHash>,
+}
+
+impl DroppedTransactions {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, dropped_txs: HashMap::new(), insertion_order: VecDeque::new() }
+    }
+
+    pub fn insert(&mut self, dropped_tx: DroppedTransaction) {
+        if self.dropped_txs.insert(dropped_tx.tx_hash, dropped_tx).is_none() {
+            self.insertion_order.push_back(dropped_tx.tx_hash);
+        }
+
+        while self.insertion_order.len() > self.capacity {
+            let oldest_tx_hash =
+                self.insertion_order.pop_front().expect("Insertion order should not be empty.");
+            self.dropped_txs.remove(&oldest_tx_hash);
+        }
+    }
+
+    pub fn get(&self, tx_hash: TransactionHash) -> Option<DroppedTransaction> {
+        self.dropped_txs.get(&tx_hash).copied()
+    }
+}
+
+impl Default for DroppedTransactions {
+    fn default() -> Self {
+        Self::new(DROPPED_TRANSACTIONS_CAPACITY)
+    }
+}
@@ -0,0 +1,25 @@
+use starknet_api::transaction::fields::Tip;
+use starknet_api::tx_hash;
+use starknet_mempool_types::mempool_types::{DroppedTransaction, TransactionDropReason};
+
+use crate::dropped_transactions::DroppedTransactions;
+
+fn dropped_tx(tx_hash: u8) -> DroppedTransaction {
+    DroppedTransaction {
+        tx_hash: tx_hash!(tx_hash),
+        tip: Tip::default(),
+        reason: TransactionDropReason::StaleNonce,
+    }
+}
+
+#[test]
+fn dropped_transactions_forgets_oldest_records() {
+    let mut dropped_txs = DroppedTransactions::new(2);
+    for tx_hash in 1..=3 {
+        dropped_txs.insert(dropped_tx(tx_hash));
+    }
+
+    assert_eq!(dropped_txs.get(tx_hash!(1)), None);
+    assert_eq!(dropped_txs.get(tx_hash!(2)), Some(dropped_tx(2)));
+    assert_eq!(dropped_txs.get(tx_hash!(3)), Some(dropped_tx(3)));
+}
@@ -2,10 +2,15 @@ use axum::http::StatusCode;
 use blockifier::state::errors::StateError;
 use serde_json::{Error as SerdeError, Value};
 use starknet_api::block::GasPrice;
+use starknet_api::core::ContractAddress;
+use starknet_api::execution_resources::GasAmount;
 use starknet_api::transaction::fields::{Resource, ResourceBounds};
 use starknet_api::StarknetApiError;
 use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_mempool_types::communication::MempoolClientError;
+use starknet_mempool_types::errors::MempoolError;
 use thiserror::Error;
+use tracing::{debug, error};
 
 use crate::compiler_version::{VersionId, VersionIdError};
 use crate::rpc_objects::{RpcErrorCode, RpcErrorResponse};
@@ -28,6 +33,15 @@ pub enum StatelessTransactionValidatorError {
         contract_class_object_size: usize,
         max_contract_class_object_size: usize,
     },
+    #[error(
+        "The max amount of {resource:?} in the resource bounds is {max_amount}, which exceeds any \
+         possible block limit (allowed amount: {max_allowed_amount})."
+    )]
+    ExcessiveResourceBounds {
+        resource: Resource,
+        max_amount: GasAmount,
+        max_allowed_amount: GasAmount,
+    },
     #[error("Entry points must be unique and sorted.")]
     EntryPointsNotUniquelySorted,
     #[error("Invalid {field_name} data availability mode.")]
@@ -63,6 +77,7 @@ impl From<StatelessTransactionValidatorError> for GatewaySpecError {
             }
             StatelessTransactionValidatorError::CalldataTooLong { .. }
             | StatelessTransactionValidatorError::EntryPointsNotUniquelySorted
+            | StatelessTransactionValidatorError::ExcessiveResourceBounds { .. }
             | StatelessTransactionValidatorError::InvalidDataAvailabilityMode { .. }
             | StatelessTransactionValidatorError::InvalidSierraVersion(..)
             | StatelessTransactionValidatorError::NonEmptyField { .. }
@@ -75,6 +90,59 @@ impl From<StatelessTransactionValidatorError> for GatewaySpecError {
     }
 }
 
+#[derive(Debug, Error)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ValidateSpamFilterError {
+    #[error(
+        "Account {sender_address} is throttled, as its validations consistently consume nearly \
+         the maximal gas."
+    )]
+    AccountThrottled { sender_address: ContractAddress },
+}
+
+impl From<ValidateSpamFilterError> for GatewaySpecError {
+    fn from(e: ValidateSpamFilterError) -> Self {
+        match e {
+            ValidateSpamFilterError::AccountThrottled { .. } => {
+                GatewaySpecError::ValidationFailure { data: e.to_string() }
+            }
+        }
+    }
+}
+
+/// Maps a mempool rejection to the spec error returned to the user. Rejections with no matching
+/// spec error are reported as unexpected errors that carry their details.
+pub fn mempool_client_err_to_gw_spec_err(value: MempoolClientError) -> GatewaySpecError {
+    let mempool_error = match value {
+        MempoolClientError::ClientError(client_error) => {
+            error!("Mempool client error: {}", client_error);
+            return GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() };
+        }
+        MempoolClientError::MempoolError(mempool_error) => mempool_error,
+    };
+    debug!("Mempool error: {}", mempool_error);
+    match mempool_error {
+        MempoolError::DuplicateNonce { .. } => {
+            GatewaySpecError::DuplicateNonce { data: mempool_error.to_string() }
+        }
+        MempoolError::NonceTooLarge(..) | MempoolError::NonceTooOld { .. } => {
+            GatewaySpecError::InvalidTransactionNonce
+        }
+        MempoolError::DuplicateTransaction { .. } => GatewaySpecError::DuplicateTx,
+        MempoolError::UnsupportedTxVersion { .. } => GatewaySpecError::UnsupportedTxVersion,
+        MempoolError::AccountSizeLimitExceeded { .. }
+        | MempoolError::DeclareNotForwarded { .. }
+        | MempoolError::MempoolFull { .. } => {
+            GatewaySpecError::UnexpectedError { data: mempool_error.to_string() }
+        }
+        MempoolError::P2pPropagatorClientError { .. }
+        | MempoolError::TransactionNotFound { .. } => {
+            error!("Failed to add tx to the mempool: {}", mempool_error);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        }
+    }
+}
+
 pub type StatelessTransactionValidatorResult<T> = Result<T, StatelessTransactionValidatorError>;
 
 pub type StatefulTransactionValidatorResult<T> = Result<T, GatewaySpecError>;
@@ -0,0 +1,53 @@
+use blockifier::context::ChainInfo;
+use blockifier::fee::fee_utils::CommittedFeeBalance;
+use blockifier::transaction::account_transaction::AccountTransaction;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::FeeBalanceCheck;
+use tracing::error;
+
+use crate::compilation::GatewayCompiler;
+use crate::errors::GatewayResult;
+use crate::state_reader::StateReaderFactory;
+use crate::stateful_transaction_validator::StatefulTransactionValidator;
+use crate::utils::compile_contract_and_build_executable_tx;
+
+#[cfg(test)]
+#[path = "fee_balance_check_test.rs"]
+mod fee_balance_check_test;
+
+/// Checks whether the current balance of the transaction's sender covers the maximal fee the
+/// transaction may be charged, using the fee computation of the executor. The transaction is
+/// neither validated nor added.
+pub(crate) fn check_fee_balance(
+    rpc_tx: RpcTransaction,
+    state_reader_factory: &dyn StateReaderFactory,
+    gateway_compiler: &GatewayCompiler,
+    stateful_tx_validator: &StatefulTransactionValidator,
+    chain_info: &ChainInfo,
+) -> GatewayResult<FeeBalanceCheck> {
+    let executable_tx =
+        compile_contract_and_build_executable_tx(rpc_tx, gateway_compiler, &chain_info.chain_id)?;
+    let mut validator =
+        stateful_tx_validator.instantiate_validator(state_reader_factory, chain_info)?;
+
+    let account_tx = AccountTransaction::new_for_sequencing(executable_tx);
+    let CommittedFeeBalance {
+        fee_token_address,
+        committed_fee,
+        balance_low,
+        balance_high,
+        covers_committed_fee,
+    } = validator.get_committed_fee_balance(&account_tx).map_err(|e| {
+        error!("Failed to get the fee balance of the transaction sender: {}", e);
+        GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+    })?;
+
+    Ok(FeeBalanceCheck {
+        fee_token_address,
+        max_fee: committed_fee,
+        balance_low,
+        balance_high,
+        covers_max_fee: covers_committed_fee,
+    })
+}
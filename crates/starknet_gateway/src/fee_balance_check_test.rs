@@ -0,0 +1,37 @@
+use blockifier::context::ChainInfo;
+use blockifier::test_utils::{CairoVersion, RunnableCairo1, BALANCE};
+use mempool_test_utils::starknet_api_test_utils::{invoke_tx, test_valid_resource_bounds};
+use rstest::rstest;
+use starknet_api::block::FeeType;
+use starknet_api::transaction::fields::Fee;
+use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
+use starknet_types_core::felt::Felt;
+
+use crate::compilation::GatewayCompiler;
+use crate::config::StatefulTransactionValidatorConfig;
+use crate::fee_balance_check::check_fee_balance;
+use crate::state_reader_test_utils::local_test_state_reader_factory;
+use crate::stateful_transaction_validator::StatefulTransactionValidator;
+
+#[rstest]
+fn fee_balance_check(#[values(true, false)] zero_balance: bool) {
+    let cairo_version = CairoVersion::Cairo1(RunnableCairo1::Casm);
+    let state_reader_factory = local_test_state_reader_factory(cairo_version, zero_balance);
+    let chain_info = ChainInfo::create_for_testing();
+
+    let fee_balance_check = check_fee_balance(
+        invoke_tx(cairo_version),
+        &state_reader_factory,
+        &GatewayCompiler::new(SierraToCasmCompilationConfig::default()),
+        &StatefulTransactionValidator::new(StatefulTransactionValidatorConfig::default()),
+        &chain_info,
+    )
+    .unwrap();
+
+    let expected_balance = if zero_balance { Fee(0) } else { BALANCE };
+    assert_eq!(fee_balance_check.fee_token_address, chain_info.fee_token_address(&FeeType::Strk));
+    assert_eq!(fee_balance_check.max_fee, test_valid_resource_bounds().max_possible_fee());
+    assert_eq!(fee_balance_check.balance_low, Felt::from(expected_balance.0));
+    assert_eq!(fee_balance_check.balance_high, Felt::ZERO);
+    assert_eq!(fee_balance_check.covers_max_fee, !zero_balance);
+}
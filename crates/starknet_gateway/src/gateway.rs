@@ -1,26 +1,42 @@
 use std::clone::Clone;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use blockifier::context::ChainInfo;
+use blockifier::state::contract_class_manager::ContractClassManager;
+use futures::executor::block_on;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use starknet_api::core::{ascii_as_felt, ClassHash};
+use starknet_api::error_context::ErrorContext;
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::rpc_transaction::RpcTransaction;
-use starknet_api::transaction::TransactionHash;
+use starknet_api::transaction::{TransactionHash, TransactionVersion};
 use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::{
+    CallInput,
+    FeeBalanceCheck,
+    GatewayReceipt,
+    SignedGatewayReceipt,
+};
 use starknet_mempool_types::communication::{AddTransactionArgsWrapper, SharedMempoolClient};
-use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
+use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs, DroppedTransaction};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::signer::{LocalKeySigner, SharedSigner, SignerBackend};
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
-use tracing::{error, info, instrument, Span};
+use starknet_types_core::felt::Felt;
+use tracing::{debug, error, info, instrument, Span};
 
+use crate::call_executor::execute_call;
 use crate::compilation::GatewayCompiler;
 use crate::config::{GatewayConfig, RpcStateReaderConfig};
-use crate::errors::GatewayResult;
+use crate::dropped_transactions::DroppedTransactions;
+use crate::errors::{mempool_client_err_to_gw_spec_err, GatewayResult};
+use crate::fee_balance_check::check_fee_balance;
 use crate::rpc_state_reader::RpcStateReaderFactory;
-use crate::state_reader::StateReaderFactory;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 use crate::stateful_transaction_validator::StatefulTransactionValidator;
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
-use crate::utils::compile_contract_and_build_executable_tx;
+use crate::utils::{compile_contract_and_build_executable_tx, declared_class_hash};
 
 #[cfg(test)]
 #[path = "gateway_test.rs"]
@@ -34,6 +50,8 @@ pub struct Gateway {
     pub gateway_compiler: Arc<GatewayCompiler>,
     pub mempool_client: SharedMempoolClient,
     pub chain_info: ChainInfo,
+    pub dropped_txs: DroppedTransactions,
+    pub signer: SharedSigner,
 }
 
 impl Gateway {
@@ -43,18 +61,26 @@ impl Gateway {
         gateway_compiler: GatewayCompiler,
         mempool_client: SharedMempoolClient,
     ) -> Self {
+        // Receipts commit to the chain ID as a felt, so a chain ID that doesn't fit in one must be
+        // rejected at startup rather than when signing a receipt.
+        ascii_as_felt(&config.chain_info.chain_id.to_string())
+            .expect("The chain ID must be encodable as a felt to sign gateway receipts.");
+        let signer = LocalKeySigner::new(&config.signer_config);
+        info!("Signing gateway receipts with public key {:#x}.", signer.public_key());
         Self {
             config: config.clone(),
             stateless_tx_validator: Arc::new(StatelessTransactionValidator {
                 config: config.stateless_tx_validator_config.clone(),
             }),
-            stateful_tx_validator: Arc::new(StatefulTransactionValidator {
-                config: config.stateful_tx_validator_config.clone(),
-            }),
+            stateful_tx_validator: Arc::new(StatefulTransactionValidator::new(
+                config.stateful_tx_validator_config.clone(),
+            )),
             state_reader_factory,
             gateway_compiler: Arc::new(gateway_compiler),
             mempool_client,
             chain_info: config.chain_info.clone(),
+            dropped_txs: DroppedTransactions::default(),
+            signer: Arc::new(signer),
         }
     }
 
@@ -63,8 +89,11 @@ impl Gateway {
         &self,
         tx: RpcTransaction,
         p2p_message_metadata: Option<BroadcastedMessageMetadata>,
-    ) -> GatewayResult<TransactionHash> {
+        reconciled: bool,
+    ) -> GatewayResult<SignedGatewayReceipt> {
         info!("Processing tx");
+        let received_timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards.").as_secs();
         let blocking_task = ProcessTxBlockingTask::new(self, tx);
         // Run the blocking task in the current span.
         let curr_span = Span::current();
@@ -76,15 +105,94 @@ impl Gateway {
                     GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
                 })??;
 
-        let tx_hash = add_tx_args.tx.tx_hash();
+        // The receipt is signed before the tx is added to the mempool, so that a signing failure
+        // never leaves an accepted tx without a receipt.
+        // TODO: Also return `ContractAddress` for deploy and `ClassHash` for Declare.
+        let signed_receipt = self.sign_receipt(GatewayReceipt {
+            tx_hash: add_tx_args.tx.tx_hash(),
+            received_timestamp,
+            chain_id: self.chain_info.chain_id.clone(),
+        })?;
+
+        let add_tx_args =
+            AddTransactionArgsWrapper { args: add_tx_args, p2p_message_metadata, reconciled };
+        self.mempool_client.add_tx(add_tx_args).await.map_err(mempool_client_err_to_gw_spec_err)?;
+        Ok(signed_receipt)
+    }
+
+    fn sign_receipt(&self, receipt: GatewayReceipt) -> GatewayResult<SignedGatewayReceipt> {
+        let signature = receipt
+            .hash()
+            .map_err(|e| e.to_string())
+            .and_then(|message_hash| self.signer.sign(message_hash).map_err(|e| e.to_string()))
+            .map_err(|e| {
+                error!("Failed to sign the receipt of tx {}: {}", receipt.tx_hash, e);
+                GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+            })?;
+        Ok(SignedGatewayReceipt { receipt, signature })
+    }
 
-        let add_tx_args = AddTransactionArgsWrapper { args: add_tx_args, p2p_message_metadata };
-        self.mempool_client.add_tx(add_tx_args).await.map_err(|e| {
-            error!("Failed to send tx to mempool: {}", e);
+    /// Executes a read-only contract call, without mutating the state.
+    #[instrument(skip(self), ret)]
+    pub async fn call(&self, call_input: CallInput) -> GatewayResult<Vec<Felt>> {
+        let state_reader_factory = self.state_reader_factory.clone();
+        let chain_info = self.chain_info.clone();
+        let config = self.config.clone();
+        // Run the call in a blocking thread, in the current span.
+        let curr_span = Span::current();
+        tokio::task::spawn_blocking(move || {
+            curr_span.in_scope(|| {
+                execute_call(call_input, state_reader_factory.as_ref(), &chain_info, &config)
+            })
+        })
+        .await
+        .map_err(|join_err| {
+            error!("Failed to execute call: {}", join_err);
             GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
-        })?;
-        // TODO: Also return `ContractAddress` for deploy and `ClassHash` for Declare.
-        Ok(tx_hash)
+        })?
+    }
+
+    /// Checks whether the sender's current balance covers the maximal fee of the transaction,
+    /// without adding it.
+    #[instrument(skip(self), ret)]
+    pub async fn check_fee_balance(&self, tx: RpcTransaction) -> GatewayResult<FeeBalanceCheck> {
+        let state_reader_factory = self.state_reader_factory.clone();
+        let gateway_compiler = self.gateway_compiler.clone();
+        let stateful_tx_validator = self.stateful_tx_validator.clone();
+        let chain_info = self.chain_info.clone();
+        // Run the check in a blocking thread, in the current span.
+        let curr_span = Span::current();
+        tokio::task::spawn_blocking(move || {
+            curr_span.in_scope(|| {
+                check_fee_balance(
+                    tx,
+                    state_reader_factory.as_ref(),
+                    &gateway_compiler,
+                    &stateful_tx_validator,
+                    &chain_info,
+                )
+            })
+        })
+        .await
+        .map_err(|join_err| {
+            error!("Failed to check the fee balance: {}", join_err);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        })?
+    }
+
+    /// Records transactions dropped by the mempool, giving them a terminal status.
+    pub fn record_dropped_txs(&mut self, dropped_txs: Vec<DroppedTransaction>) {
+        for dropped_tx in dropped_txs {
+            info!(
+                "Transaction {} was dropped by the mempool: {:?}.",
+                dropped_tx.tx_hash, dropped_tx.reason
+            );
+            self.dropped_txs.insert(dropped_tx);
+        }
+    }
+
+    pub fn get_dropped_tx(&self, tx_hash: TransactionHash) -> Option<DroppedTransaction> {
+        self.dropped_txs.get(tx_hash)
     }
 }
 
@@ -95,6 +203,7 @@ struct ProcessTxBlockingTask {
     stateful_tx_validator: Arc<StatefulTransactionValidator>,
     state_reader_factory: Arc<dyn StateReaderFactory>,
     gateway_compiler: Arc<GatewayCompiler>,
+    mempool_client: SharedMempoolClient,
     chain_info: ChainInfo,
     tx: RpcTransaction,
 }
@@ -106,6 +215,7 @@ impl ProcessTxBlockingTask {
             stateful_tx_validator: gateway.stateful_tx_validator.clone(),
             state_reader_factory: gateway.state_reader_factory.clone(),
             gateway_compiler: gateway.gateway_compiler.clone(),
+            mempool_client: gateway.mempool_client.clone(),
             chain_info: gateway.chain_info.clone(),
             tx,
         }
@@ -117,6 +227,20 @@ impl ProcessTxBlockingTask {
         // Perform stateless validations.
         self.stateless_tx_validator.validate(&self.tx)?;
 
+        // RPC transactions are all of version 3.
+        if self.chain_info.disabled_tx_versions.contains(&TransactionVersion::THREE) {
+            return Err(GatewaySpecError::UnsupportedTxVersion);
+        }
+
+        // Reject redeclarations before the costly compilation.
+        let declared_class_hash = match &self.tx {
+            RpcTransaction::Declare(declare_tx) => Some(declared_class_hash(declare_tx)),
+            RpcTransaction::DeployAccount(_) | RpcTransaction::Invoke(_) => None,
+        };
+        if let Some(class_hash) = declared_class_hash {
+            self.verify_class_not_declared(class_hash)?;
+        }
+
         let executable_tx = compile_contract_and_build_executable_tx(
             self.tx,
             self.gateway_compiler.as_ref(),
@@ -139,10 +263,61 @@ impl ProcessTxBlockingTask {
             GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
         })?;
 
-        self.stateful_tx_validator.run_validate(&executable_tx, nonce, validator)?;
+        let tx_hash = executable_tx.tx_hash();
+        self.stateful_tx_validator
+            .run_validate(&executable_tx, nonce, validator)
+            .map_err(|err| with_tx_context(err, tx_hash))?;
 
         // TODO(Arni): Add the Sierra and the Casm to the mempool input.
-        Ok(AddTransactionArgs { tx: executable_tx, account_state: AccountState { address, nonce } })
+        Ok(AddTransactionArgs {
+            tx: executable_tx,
+            account_state: AccountState { address, nonce },
+            declared_class_hash,
+        })
+    }
+
+    /// Verifies the class is neither declared in the committed state nor pending declaration in
+    /// the mempool.
+    fn verify_class_not_declared(&self, class_hash: ClassHash) -> GatewayResult<()> {
+        let pending_declare = block_on(self.mempool_client.get_pending_declare(class_hash))
+            .map_err(|e| {
+                error!("Failed to query the mempool for pending declares: {}", e);
+                GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+            })?;
+        if let Some(tx_hash) = pending_declare {
+            debug!("Class {class_hash} is pending declaration in transaction {tx_hash}.");
+            return Err(GatewaySpecError::ClassAlreadyDeclared {
+                data: format!(
+                    "Class {class_hash} is pending declaration in transaction {tx_hash}."
+                ),
+            });
+        }
+
+        let state_reader = self.state_reader_factory.get_state_reader_from_latest_block();
+        match state_reader.is_class_declared(class_hash) {
+            Ok(true) => {
+                debug!("Class {class_hash} is already declared.");
+                Err(GatewaySpecError::ClassAlreadyDeclared {
+                    data: format!("Class {class_hash} is already declared."),
+                })
+            }
+            Ok(false) => Ok(()),
+            Err(e) => {
+                error!("Failed to check whether class {class_hash} is declared: {}", e);
+                Err(GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() })
+            }
+        }
+    }
+}
+
+/// Identifies the transaction in the details of a failed validation; the client may not know the
+/// hash of a transaction it submitted.
+fn with_tx_context(error: GatewaySpecError, tx_hash: TransactionHash) -> GatewaySpecError {
+    match error {
+        GatewaySpecError::ValidationFailure { data } => GatewaySpecError::ValidationFailure {
+            data: ErrorContext::new("gateway").with_tx_hash(tx_hash).wrap(data).to_string(),
+        },
+        error => error,
     }
 }
 
@@ -151,9 +326,11 @@ pub fn create_gateway(
     rpc_state_reader_config: RpcStateReaderConfig,
     compiler_config: SierraToCasmCompilationConfig,
     mempool_client: SharedMempoolClient,
+    contract_class_manager: ContractClassManager,
 ) -> Gateway {
-    let state_reader_factory = Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config });
-    let gateway_compiler = GatewayCompiler::new_command_line_compiler(compiler_config);
+    let state_reader_factory =
+        Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config, contract_class_manager });
+    let gateway_compiler = GatewayCompiler::new(compiler_config);
 
     Gateway::new(config, state_reader_factory, gateway_compiler, mempool_client)
 }
@@ -2,19 +2,30 @@ use std::sync::Arc;
 
 use assert_matches::assert_matches;
 use blockifier::context::ChainInfo;
+use blockifier::test_utils::contracts::FeatureContract;
 use blockifier::test_utils::{CairoVersion, RunnableCairo1};
 use mempool_test_utils::starknet_api_test_utils::{declare_tx, invoke_tx};
 use mockall::predicate::eq;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_test_utils::{get_rng, GetTestInstance};
 use rstest::{fixture, rstest};
-use starknet_api::core::{ChainId, CompiledClassHash, ContractAddress};
+use starknet_api::block::GasPrice;
+use starknet_api::core::{ChainId, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::{AccountTransaction, InvokeTransaction};
 use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
+use starknet_api::transaction::fields::Tip;
+use starknet_api::transaction::{TransactionHash, TransactionVersion, TransactionVersions};
 use starknet_gateway_types::errors::GatewaySpecError;
-use starknet_mempool_types::communication::{AddTransactionArgsWrapper, MockMempoolClient};
+use starknet_mempool_types::communication::{
+    AddTransactionArgsWrapper,
+    MempoolClientError,
+    MockMempoolClient,
+};
+use starknet_mempool_types::errors::{MempoolError, ReplacementFeeThreshold};
 use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
+use starknet_sequencer_infra::signer::{SignerBackend, SignerConfig};
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
+use starknet_types_core::felt::Felt;
 
 use crate::compilation::GatewayCompiler;
 use crate::config::{
@@ -24,6 +35,7 @@ use crate::config::{
 };
 use crate::gateway::Gateway;
 use crate::state_reader_test_utils::{local_test_state_reader_factory, TestStateReaderFactory};
+use crate::utils::declared_class_hash;
 
 #[fixture]
 fn config() -> GatewayConfig {
@@ -31,12 +43,14 @@ fn config() -> GatewayConfig {
         stateless_tx_validator_config: StatelessTransactionValidatorConfig::default(),
         stateful_tx_validator_config: StatefulTransactionValidatorConfig::default(),
         chain_info: ChainInfo::create_for_testing(),
+        signer_config: SignerConfig { private_key: Felt::ONE },
+        ..Default::default()
     }
 }
 
 #[fixture]
 fn compiler() -> GatewayCompiler {
-    GatewayCompiler::new_command_line_compiler(SierraToCasmCompilationConfig::default())
+    GatewayCompiler::new(SierraToCasmCompilationConfig::default())
 }
 
 #[fixture]
@@ -74,6 +88,13 @@ impl MockDependencies {
     fn expect_add_tx(&mut self, args: AddTransactionArgsWrapper) {
         self.mock_mempool_client.expect_add_tx().once().with(eq(args)).return_once(|_| Ok(()));
     }
+
+    fn expect_get_pending_declare(&mut self, pending_declare: Option<TransactionHash>) {
+        self.mock_mempool_client
+            .expect_get_pending_declare()
+            .once()
+            .return_once(move |_| Ok(pending_declare));
+    }
 }
 
 type SenderAddress = ContractAddress;
@@ -106,17 +127,80 @@ async fn test_add_tx(mut mock_dependencies: MockDependencies) {
     let add_tx_args = AddTransactionArgs {
         tx: executable_tx,
         account_state: AccountState { address, nonce: *rpc_tx.nonce() },
+        declared_class_hash: None,
     };
     mock_dependencies.expect_add_tx(AddTransactionArgsWrapper {
         args: add_tx_args,
         p2p_message_metadata: p2p_message_metadata.clone(),
+        reconciled: false,
     });
 
     let gateway = mock_dependencies.gateway();
 
-    let response_tx_hash = gateway.add_tx(rpc_tx, p2p_message_metadata).await.unwrap();
+    let signed_receipt = gateway.add_tx(rpc_tx, p2p_message_metadata, false).await.unwrap();
 
-    assert_eq!(tx_hash, response_tx_hash);
+    assert_eq!(signed_receipt.receipt.tx_hash, tx_hash);
+    assert_eq!(signed_receipt.receipt.chain_id, ChainId::create_for_testing());
+    assert!(signed_receipt.is_signed_by(&gateway.signer.public_key()));
+}
+
+#[rstest]
+#[case::duplicate_transaction(
+    MempoolError::DuplicateTransaction { tx_hash: TransactionHash::default() },
+    GatewaySpecError::DuplicateTx
+)]
+#[case::duplicate_nonce(
+    MempoolError::DuplicateNonce {
+        address: ContractAddress::default(),
+        nonce: Nonce::default(),
+        existing_tx_hash: TransactionHash::default(),
+        replacement_fee_threshold: Some(ReplacementFeeThreshold {
+            tip: Tip(11),
+            max_l2_gas_price: GasPrice(22),
+        }),
+    },
+    GatewaySpecError::DuplicateNonce {
+        data: format!(
+            "Duplicate transaction, sender address: {}, nonce: {:?}; transaction {} has the same \
+             nonce. A replacement requires a tip of at least 11 and a max L2 gas price of at \
+             least 22.",
+            ContractAddress::default(),
+            Nonce::default(),
+            TransactionHash::default()
+        ),
+    }
+)]
+#[case::nonce_too_old(
+    MempoolError::NonceTooOld { address: ContractAddress::default(), nonce: Nonce::default() },
+    GatewaySpecError::InvalidTransactionNonce
+)]
+#[case::unsupported_tx_version(
+    MempoolError::UnsupportedTxVersion { version: TransactionVersion::THREE },
+    GatewaySpecError::UnsupportedTxVersion
+)]
+#[case::mempool_full(
+    MempoolError::MempoolFull { tx_size: 100 },
+    GatewaySpecError::UnexpectedError {
+        data: "The mempool is full; it has no room for a transaction of 100 bytes.".to_owned(),
+    }
+)]
+#[tokio::test]
+async fn test_add_tx_mempool_error(
+    mut mock_dependencies: MockDependencies,
+    #[case] mempool_error: MempoolError,
+    #[case] expected_error: GatewaySpecError,
+) {
+    let (rpc_tx, _) = create_tx();
+    mock_dependencies
+        .mock_mempool_client
+        .expect_add_tx()
+        .once()
+        .return_once(|_| Err(MempoolClientError::MempoolError(mempool_error)));
+
+    let gateway = mock_dependencies.gateway();
+
+    let err = gateway.add_tx(rpc_tx, None, false).await.unwrap_err();
+    assert_eq!(err, expected_error);
 }
 
 // Gateway spec errors tests.
@@ -127,14 +211,68 @@ async fn test_add_tx(mut mock_dependencies: MockDependencies) {
 
 #[rstest]
 #[tokio::test]
-async fn test_compiled_class_hash_mismatch(mock_dependencies: MockDependencies) {
+async fn test_disabled_tx_version(mut mock_dependencies: MockDependencies) {
+    mock_dependencies.config.chain_info.disabled_tx_versions =
+        TransactionVersions::new([TransactionVersion::THREE]);
+    let (tx, _) = create_tx();
+    let gateway = mock_dependencies.gateway();
+
+    let err = gateway.add_tx(tx, None, false).await.unwrap_err();
+    assert_matches!(err, GatewaySpecError::UnsupportedTxVersion);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_compiled_class_hash_mismatch(mut mock_dependencies: MockDependencies) {
     let mut declare_tx =
         assert_matches!(declare_tx(), RpcTransaction::Declare(RpcDeclareTransaction::V3(tx)) => tx);
     declare_tx.compiled_class_hash = CompiledClassHash::default();
     let tx = RpcTransaction::Declare(RpcDeclareTransaction::V3(declare_tx));
 
+    mock_dependencies.expect_get_pending_declare(None);
     let gateway = mock_dependencies.gateway();
 
-    let err = gateway.add_tx(tx, None).await.unwrap_err();
+    let err = gateway.add_tx(tx, None, false).await.unwrap_err();
     assert_matches!(err, GatewaySpecError::CompiledClassHashMismatch);
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_class_pending_declaration(mut mock_dependencies: MockDependencies) {
+    let tx = declare_tx();
+    mock_dependencies.expect_get_pending_declare(Some(TransactionHash(Felt::ONE)));
+    let gateway = mock_dependencies.gateway();
+
+    let err = gateway.add_tx(tx, None, false).await.unwrap_err();
+    assert_matches!(
+        err,
+        GatewaySpecError::ClassAlreadyDeclared { data } if data.contains("pending declaration")
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_class_already_declared(mut mock_dependencies: MockDependencies) {
+    let tx = declare_tx();
+    let class_hash = declared_class_hash(
+        assert_matches!(&tx, RpcTransaction::Declare(declare_tx) => declare_tx),
+    );
+    mock_dependencies
+        .state_reader_factory
+        .state_reader
+        .blockifier_state_reader
+        .class_hash_to_class
+        .insert(
+            class_hash,
+            FeatureContract::TestContract(CairoVersion::Cairo1(RunnableCairo1::Casm))
+                .get_runnable_class(),
+        );
+    mock_dependencies.expect_get_pending_declare(None);
+    let gateway = mock_dependencies.gateway();
+
+    let err = gateway.add_tx(tx, None, false).await.unwrap_err();
+    assert_matches!(
+        err,
+        GatewaySpecError::ClassAlreadyDeclared { data } if data.contains("already declared")
+    );
+}
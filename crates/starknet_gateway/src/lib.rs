@@ -1,8 +1,11 @@
+mod call_executor;
 pub mod communication;
 pub mod compilation;
 mod compiler_version;
 pub mod config;
+pub mod dropped_transactions;
 pub mod errors;
+mod fee_balance_check;
 pub mod gateway;
 pub mod rpc_objects;
 pub mod rpc_state_reader;
@@ -17,3 +20,4 @@ mod sync_state_reader;
 #[cfg(test)]
 mod test_utils;
 mod utils;
+mod validate_gas_profiles;
@@ -54,6 +54,12 @@ pub struct GetClassHashAtParams {
     pub block_id: BlockId,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct GetClassParams {
+    pub class_hash: ClassHash,
+    pub block_id: BlockId,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct GetCompiledClassParams {
     pub class_hash: ClassHash,
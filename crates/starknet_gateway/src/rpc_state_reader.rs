@@ -3,6 +3,7 @@ use blockifier::execution::contract_class::{
     CompiledClassV1,
     RunnableCompiledClass,
 };
+use blockifier::state::contract_class_manager::ContractClassManager;
 use blockifier::state::errors::StateError;
 use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
 use papyrus_rpc::CompiledContractClass;
@@ -22,6 +23,7 @@ use crate::rpc_objects::{
     BlockId,
     GetBlockWithTxHashesParams,
     GetClassHashAtParams,
+    GetClassParams,
     GetCompiledClassParams,
     GetNonceParams,
     GetStorageAtParams,
@@ -37,14 +39,28 @@ use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 pub struct RpcStateReader {
     pub config: RpcStateReaderConfig,
     pub block_id: BlockId,
+    // If set, compiled classes are cached in (and served from) this manager's cache.
+    pub contract_class_manager: Option<ContractClassManager>,
 }
 
 impl RpcStateReader {
     pub fn from_number(config: &RpcStateReaderConfig, block_number: BlockNumber) -> Self {
-        Self { config: config.clone(), block_id: BlockId::Number(block_number) }
+        Self {
+            config: config.clone(),
+            block_id: BlockId::Number(block_number),
+            contract_class_manager: None,
+        }
     }
     pub fn from_latest(config: &RpcStateReaderConfig) -> Self {
-        Self { config: config.clone(), block_id: BlockId::Latest }
+        Self { config: config.clone(), block_id: BlockId::Latest, contract_class_manager: None }
+    }
+    /// Shares the compiled classes with the other users of the given manager, e.g., the batcher.
+    pub fn with_contract_class_manager(
+        mut self,
+        contract_class_manager: ContractClassManager,
+    ) -> Self {
+        self.contract_class_manager = Some(contract_class_manager);
+        self
     }
     // Note: This function is blocking though it is sending a request to the rpc server and waiting
     // for the response.
@@ -90,6 +106,28 @@ impl RpcStateReader {
             },
         }
     }
+
+    fn get_compiled_class_from_rpc(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<RunnableCompiledClass> {
+        let get_compiled_class_params =
+            GetCompiledClassParams { class_hash, block_id: self.block_id };
+
+        let result =
+            self.send_rpc_request("starknet_getCompiledContractClass", get_compiled_class_params)?;
+        let (contract_class, sierra_version): (CompiledContractClass, SierraVersion) =
+            serde_json::from_value(result).map_err(serde_err_to_state_err)?;
+        match contract_class {
+            CompiledContractClass::V1(contract_class_v1) => Ok(RunnableCompiledClass::V1(
+                CompiledClassV1::try_from((contract_class_v1, sierra_version))
+                    .map_err(StateError::ProgramError)?,
+            )),
+            CompiledContractClass::V0(contract_class_v0) => Ok(RunnableCompiledClass::V0(
+                CompiledClassV0::try_from(contract_class_v0).map_err(StateError::ProgramError)?,
+            )),
+        }
+    }
 }
 
 impl MempoolStateReader for RpcStateReader {
@@ -104,6 +142,22 @@ impl MempoolStateReader for RpcStateReader {
         let block_info = block_header.try_into()?;
         Ok(block_info)
     }
+
+    fn is_class_declared(&self, class_hash: ClassHash) -> StateResult<bool> {
+        if let Some(contract_class_manager) = &self.contract_class_manager {
+            if contract_class_manager.get_runnable(&class_hash).is_some() {
+                return Ok(true);
+            }
+        }
+
+        // The class is fetched, but neither parsed nor compiled.
+        let get_class_params = GetClassParams { class_hash, block_id: self.block_id };
+        match self.send_rpc_request("starknet_getClass", get_class_params) {
+            Ok(_) => Ok(true),
+            Err(RPCStateReaderError::ClassHashNotFound(_)) => Ok(false),
+            Err(e) => Err(e)?,
+        }
+    }
 }
 
 impl BlockifierStateReader for RpcStateReader {
@@ -141,22 +195,16 @@ impl BlockifierStateReader for RpcStateReader {
     }
 
     fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
-        let get_compiled_class_params =
-            GetCompiledClassParams { class_hash, block_id: self.block_id };
-
-        let result =
-            self.send_rpc_request("starknet_getCompiledContractClass", get_compiled_class_params)?;
-        let (contract_class, sierra_version): (CompiledContractClass, SierraVersion) =
-            serde_json::from_value(result).map_err(serde_err_to_state_err)?;
-        match contract_class {
-            CompiledContractClass::V1(contract_class_v1) => Ok(RunnableCompiledClass::V1(
-                CompiledClassV1::try_from((contract_class_v1, sierra_version))
-                    .map_err(StateError::ProgramError)?,
-            )),
-            CompiledContractClass::V0(contract_class_v0) => Ok(RunnableCompiledClass::V0(
-                CompiledClassV0::try_from(contract_class_v0).map_err(StateError::ProgramError)?,
-            )),
+        if let Some(contract_class_manager) = &self.contract_class_manager {
+            if let Some(runnable_class) = contract_class_manager.get_runnable(&class_hash) {
+                return Ok(runnable_class);
+            }
+        }
+        let runnable_class = self.get_compiled_class_from_rpc(class_hash)?;
+        if let Some(contract_class_manager) = &self.contract_class_manager {
+            contract_class_manager.set_runnable(class_hash, runnable_class.clone());
         }
+        Ok(runnable_class)
     }
 
     fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
@@ -182,14 +230,21 @@ impl BlockifierStateReader for RpcStateReader {
 
 pub struct RpcStateReaderFactory {
     pub config: RpcStateReaderConfig,
+    pub contract_class_manager: ContractClassManager,
 }
 
 impl StateReaderFactory for RpcStateReaderFactory {
     fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
-        Box::new(RpcStateReader::from_latest(&self.config))
+        Box::new(
+            RpcStateReader::from_latest(&self.config)
+                .with_contract_class_manager(self.contract_class_manager.clone()),
+        )
     }
 
     fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
-        Box::new(RpcStateReader::from_number(&self.config, block_number))
+        Box::new(
+            RpcStateReader::from_number(&self.config, block_number)
+                .with_contract_class_manager(self.contract_class_manager.clone()),
+        )
     }
 }
@@ -15,12 +15,16 @@ use crate::rpc_objects::{
     BlockId,
     GetBlockWithTxHashesParams,
     GetClassHashAtParams,
+    GetClassParams,
     GetCompiledClassParams,
     GetNonceParams,
     GetStorageAtParams,
     ResourcePrice,
+    RpcErrorResponse,
     RpcResponse,
+    RpcSpecError,
     RpcSuccessResponse,
+    RPC_CLASS_HASH_NOT_FOUND,
 };
 use crate::rpc_state_reader::RpcStateReader;
 use crate::state_reader::MempoolStateReader;
@@ -202,6 +206,31 @@ async fn test_get_compiled_class() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_is_class_declared_undeclared_class() {
+    let mut server = run_rpc_server().await;
+    let config = RpcStateReaderConfig { url: server.url(), ..Default::default() };
+
+    let mock = mock_rpc_interaction(
+        &mut server,
+        &config.json_rpc_version,
+        "starknet_getClass",
+        GetClassParams { block_id: BlockId::Latest, class_hash: class_hash!("0x1") },
+        &RpcResponse::Error(RpcErrorResponse {
+            error: RpcSpecError { code: RPC_CLASS_HASH_NOT_FOUND, ..Default::default() },
+            ..Default::default()
+        }),
+    );
+
+    let client = RpcStateReader::from_latest(&config);
+    let result = tokio::task::spawn_blocking(move || client.is_class_declared(class_hash!("0x1")))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!result);
+    mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_get_class_hash_at() {
     let mut server = run_rpc_server().await;
@@ -10,6 +10,9 @@ use starknet_types_core::felt::Felt;
 
 pub trait MempoolStateReader: BlockifierStateReader + Send + Sync {
     fn get_block_info(&self) -> Result<BlockInfo, StateError>;
+
+    /// Returns whether the class is declared, without compiling it.
+    fn is_class_declared(&self, class_hash: ClassHash) -> StateResult<bool>;
 }
 
 #[cfg_attr(test, automock)]
@@ -25,6 +28,10 @@ impl MempoolStateReader for Box<dyn MempoolStateReader> {
     fn get_block_info(&self) -> Result<BlockInfo, StateError> {
         self.as_ref().get_block_info()
     }
+
+    fn is_class_declared(&self, class_hash: ClassHash) -> StateResult<bool> {
+        self.as_ref().is_class_declared(class_hash)
+    }
 }
 
 impl BlockifierStateReader for Box<dyn MempoolStateReader> {
@@ -24,6 +24,10 @@ impl MempoolStateReader for TestStateReader {
     fn get_block_info(&self) -> Result<BlockInfo, StateError> {
         Ok(self.block_info.clone())
     }
+
+    fn is_class_declared(&self, class_hash: ClassHash) -> StateResult<bool> {
+        Ok(self.blockifier_state_reader.class_hash_to_class.contains_key(&class_hash))
+    }
 }
 
 impl BlockifierStateReader for TestStateReader {
@@ -4,6 +4,7 @@ use blockifier::blockifier::stateful_validator::{
 };
 use blockifier::bouncer::BouncerConfig;
 use blockifier::context::{BlockContext, ChainInfo};
+use blockifier::execution::call_info::CallInfo;
 use blockifier::state::cached_state::CachedState;
 use blockifier::transaction::account_transaction::{AccountTransaction, ExecutionFlags};
 use blockifier::transaction::transactions::enforce_fee;
@@ -16,6 +17,7 @@ use starknet_api::executable_transaction::{
     AccountTransaction as ExecutableTransaction,
     InvokeTransaction as ExecutableInvokeTransaction,
 };
+use starknet_api::execution_resources::GasAmount;
 use starknet_gateway_types::errors::GatewaySpecError;
 use starknet_types_core::felt::Felt;
 use tracing::error;
@@ -23,6 +25,7 @@ use tracing::error;
 use crate::config::StatefulTransactionValidatorConfig;
 use crate::errors::StatefulTransactionValidatorResult;
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+use crate::validate_gas_profiles::ValidateGasProfiles;
 
 #[cfg(test)]
 #[path = "stateful_transaction_validator_test.rs"]
@@ -30,6 +33,7 @@ mod stateful_transaction_validator_test;
 
 pub struct StatefulTransactionValidator {
     pub config: StatefulTransactionValidatorConfig,
+    pub validate_gas_profiles: ValidateGasProfiles,
 }
 
 type BlockifierStatefulValidator = StatefulValidator<Box<dyn MempoolStateReader>>;
@@ -37,11 +41,15 @@ type BlockifierStatefulValidator = StatefulValidator<Box<dyn MempoolStateReader>
 // TODO(yair): move the trait to Blockifier.
 #[cfg_attr(test, automock)]
 pub trait StatefulTransactionValidatorTrait {
+    /// Returns the call info of the validation entry point, if it was run.
     fn validate(
         &mut self,
         account_tx: AccountTransaction,
         skip_validate: bool,
-    ) -> BlockifierStatefulValidatorResult<()>;
+    ) -> BlockifierStatefulValidatorResult<Option<CallInfo>>;
+
+    /// The maximal gas a validation entry point may consume.
+    fn validate_max_sierra_gas(&self) -> GasAmount;
 
     fn get_nonce(
         &mut self,
@@ -54,10 +62,14 @@ impl StatefulTransactionValidatorTrait for BlockifierStatefulValidator {
         &mut self,
         account_tx: AccountTransaction,
         skip_validate: bool,
-    ) -> BlockifierStatefulValidatorResult<()> {
+    ) -> BlockifierStatefulValidatorResult<Option<CallInfo>> {
         self.perform_validations(account_tx, skip_validate)
     }
 
+    fn validate_max_sierra_gas(&self) -> GasAmount {
+        self.block_context().versioned_constants().validate_max_sierra_gas
+    }
+
     fn get_nonce(
         &mut self,
         account_address: ContractAddress,
@@ -67,6 +79,12 @@ impl StatefulTransactionValidatorTrait for BlockifierStatefulValidator {
 }
 
 impl StatefulTransactionValidator {
+    pub fn new(config: StatefulTransactionValidatorConfig) -> Self {
+        let validate_gas_profiles =
+            ValidateGasProfiles::new(config.validate_spam_filter_config.clone());
+        Self { config, validate_gas_profiles }
+    }
+
     // TODO(Arni): consider separating validation from transaction conversion, as transaction
     // conversion is also relevant for the Mempool.
     pub fn run_validate<V: StatefulTransactionValidatorTrait>(
@@ -81,9 +99,19 @@ impl StatefulTransactionValidator {
         let execution_flags = ExecutionFlags { only_query, charge_fee, validate: !skip_validate };
 
         let account_tx = AccountTransaction { tx: executable_tx.clone(), execution_flags };
-        validator
+        let validate_call_info = validator
             .validate(account_tx, skip_validate)
             .map_err(|err| GatewaySpecError::ValidationFailure { data: err.to_string() })?;
+
+        // Only Sierra gas is profiled; validations of Cairo 0 accounts consume none.
+        let Some(CallInfo { execution, .. }) = validate_call_info else {
+            return Ok(());
+        };
+        self.validate_gas_profiles.record_validation(
+            executable_tx.sender_address(),
+            GasAmount(execution.gas_consumed),
+            validator.validate_max_sierra_gas(),
+        )?;
         Ok(())
     }
 
@@ -97,11 +125,18 @@ impl StatefulTransactionValidator {
         let latest_block_info = get_latest_block_info(state_reader_factory)?;
         let state_reader = state_reader_factory.get_state_reader(latest_block_info.block_number);
         let state = CachedState::new(state_reader);
-        let versioned_constants = VersionedConstants::get_versioned_constants(
-            self.config.versioned_constants_overrides.clone(),
-        );
         let mut block_info = latest_block_info;
         block_info.block_number = block_info.block_number.unchecked_next();
+        let versioned_constants = VersionedConstants::get_scheduled_versioned_constants(
+            &self.config.starknet_version_schedule,
+            block_info.block_number,
+            block_info.block_timestamp,
+            self.config.versioned_constants_overrides.clone(),
+        )
+        .map_err(|e| {
+            error!("Failed to get versioned constants: {}", e);
+            GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+        })?;
         // TODO(yael 21/4/24): create the block context using pre_process_block once we will be
         // able to read the block_hash of 10 blocks ago from papyrus.
         let block_context = BlockContext::new(
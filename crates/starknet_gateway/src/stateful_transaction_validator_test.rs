@@ -3,6 +3,7 @@ use blockifier::blockifier::stateful_validator::{
     StatefulValidatorResult as BlockifierStatefulValidatorResult,
 };
 use blockifier::context::ChainInfo;
+use blockifier::execution::call_info::{CallExecution, CallInfo};
 use blockifier::test_utils::{CairoVersion, RunnableCairo1};
 use blockifier::transaction::errors::{TransactionFeeError, TransactionPreValidationError};
 use mempool_test_utils::starknet_api_test_utils::{
@@ -26,7 +27,8 @@ use starknet_api::transaction::fields::Resource;
 use starknet_api::{deploy_account_tx_args, invoke_tx_args, nonce};
 use starknet_gateway_types::errors::GatewaySpecError;
 
-use crate::config::StatefulTransactionValidatorConfig;
+use crate::config::{StatefulTransactionValidatorConfig, ValidateSpamFilterConfig};
+use crate::errors::ValidateSpamFilterError;
 use crate::state_reader::{MockStateReaderFactory, StateReaderFactory};
 use crate::state_reader_test_utils::local_test_state_reader_factory;
 use crate::stateful_transaction_validator::{
@@ -48,7 +50,7 @@ pub const STATEFUL_VALIDATOR_FEE_ERROR: BlockifierStatefulValidatorError =
 
 #[fixture]
 fn stateful_validator() -> StatefulTransactionValidator {
-    StatefulTransactionValidator { config: StatefulTransactionValidatorConfig::default() }
+    StatefulTransactionValidator::new(StatefulTransactionValidatorConfig::default())
 }
 
 // TODO(Arni): consider testing declare and deploy account.
@@ -74,7 +76,7 @@ fn test_stateful_tx_validator(
         });
 
     let mut mock_validator = MockStatefulTransactionValidatorTrait::new();
-    mock_validator.expect_validate().return_once(|_, _| expected_result.map(|_| ()));
+    mock_validator.expect_validate().return_once(|_, _| expected_result.map(|_| None));
 
     let account_nonce = nonce!(0);
     let result = stateful_validator.run_validate(&executable_tx, account_nonce, mock_validator);
@@ -145,6 +147,41 @@ fn test_skip_stateful_validation(
     mock_validator
         .expect_validate()
         .withf(move |_, skip_validate| *skip_validate == should_skip_validate)
-        .returning(|_, _| Ok(()));
+        .returning(|_, _| Ok(None));
     let _ = stateful_validator.run_validate(&executable_tx, sender_nonce, mock_validator);
 }
+
+#[test]
+fn test_validate_spam_filter() {
+    const MAX_VALIDATE_GAS: GasAmount = GasAmount(1000);
+    let stateful_validator =
+        StatefulTransactionValidator::new(StatefulTransactionValidatorConfig {
+            validate_spam_filter_config: ValidateSpamFilterConfig {
+                enabled: true,
+                window_size: 1,
+                throttled_admission_interval: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    let executable_tx = create_executable_invoke_tx(CairoVersion::Cairo1(RunnableCairo1::Casm));
+    let run_expensive_validation = || {
+        let mut mock_validator = MockStatefulTransactionValidatorTrait::new();
+        mock_validator.expect_validate().return_once(|_, _| {
+            Ok(Some(CallInfo {
+                execution: CallExecution { gas_consumed: MAX_VALIDATE_GAS.0, ..Default::default() },
+                ..Default::default()
+            }))
+        });
+        mock_validator.expect_validate_max_sierra_gas().return_const(MAX_VALIDATE_GAS);
+        stateful_validator.run_validate(&executable_tx, nonce!(0), mock_validator)
+    };
+
+    // With a window of a single validation, the first expensive one throttles the account, after
+    // which one of every two transactions is admitted.
+    let throttled_error = ValidateSpamFilterError::AccountThrottled {
+        sender_address: executable_tx.sender_address(),
+    };
+    assert_eq!(run_expensive_validation(), Err(throttled_error.into()));
+    assert_eq!(run_expensive_validation(), Ok(()));
+}
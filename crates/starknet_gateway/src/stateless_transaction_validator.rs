@@ -10,7 +10,7 @@ use starknet_api::rpc_transaction::{
 use starknet_api::state::EntryPoint;
 use starknet_api::transaction::fields::{AllResourceBounds, Resource};
 use starknet_types_core::felt::Felt;
-use tracing::{instrument, Level};
+use tracing::{instrument, warn, Level};
 
 use crate::compiler_version::VersionId;
 use crate::config::StatelessTransactionValidatorConfig;
@@ -33,7 +33,7 @@ impl StatelessTransactionValidator {
 
         Self::validate_contract_address(tx)?;
         Self::validate_empty_account_deployment_data(tx)?;
-        Self::validate_empty_paymaster_data(tx)?;
+        self.validate_empty_paymaster_data(tx)?;
         self.validate_resource_bounds(tx)?;
         self.validate_tx_size(tx)?;
         self.validate_nonce_data_availability_mode(tx)?;
@@ -60,6 +60,41 @@ impl StatelessTransactionValidator {
         if self.config.validate_non_zero_l1_data_gas_fee {
             validate_resource_is_non_zero(resource_bounds_mapping, Resource::L1DataGas)?;
         }
+        self.validate_resource_bounds_are_sane(resource_bounds_mapping)?;
+
+        Ok(())
+    }
+
+    /// Max amounts far above any block limit can't be consumed, and only lock the balance of the
+    /// sender; they usually indicate a bug in the client's fee estimation.
+    fn validate_resource_bounds_are_sane(
+        &self,
+        all_resource_bounds: &AllResourceBounds,
+    ) -> StatelessTransactionValidatorResult<()> {
+        for (resource, max_allowed_amount) in [
+            (Resource::L1Gas, self.config.max_l1_gas_amount),
+            (Resource::L2Gas, self.config.max_l2_gas_amount),
+            (Resource::L1DataGas, self.config.max_l1_data_gas_amount),
+        ] {
+            let max_amount = all_resource_bounds.get_bound(resource).max_amount;
+            let max_allowed_amount = GasAmount(max_allowed_amount);
+            if max_amount <= max_allowed_amount {
+                continue;
+            }
+            if self.config.reject_excessive_resource_bounds {
+                return Err(StatelessTransactionValidatorError::ExcessiveResourceBounds {
+                    resource,
+                    max_amount,
+                    max_allowed_amount,
+                });
+            }
+            warn!(
+                ?resource,
+                %max_amount,
+                %max_allowed_amount,
+                "Accepting a transaction with excessive resource bounds."
+            );
+        }
 
         Ok(())
     }
@@ -94,11 +129,15 @@ impl StatelessTransactionValidator {
         }
     }
 
-    /// The Starknet OS enforces that the paymaster data is empty. We add this validation here in
-    /// the gateway to prevent transactions from failing the OS.
+    /// The Starknet OS enforces that the paymaster data is empty, unless paymasters are enabled.
+    /// We add this validation here in the gateway to prevent transactions from failing the OS.
     fn validate_empty_paymaster_data(
+        &self,
         tx: &RpcTransaction,
     ) -> StatelessTransactionValidatorResult<()> {
+        if self.config.allow_paymaster_data {
+            return Ok(());
+        }
         let paymaster_data = match tx {
             RpcTransaction::DeployAccount(RpcDeployAccountTransaction::V3(tx)) => {
                 &tx.paymaster_data
@@ -167,11 +206,15 @@ impl StatelessTransactionValidator {
     }
 
     /// The Starknet OS enforces that the nonce data availability mode is L1. We add this validation
-    /// here in the gateway to prevent transactions from failing the OS.
+    /// here in the gateway to prevent transactions from failing the OS, unless the chain publishes
+    /// state diffs to an alternative data availability layer.
     fn validate_nonce_data_availability_mode(
         &self,
         tx: &RpcTransaction,
     ) -> StatelessTransactionValidatorResult<()> {
+        if self.config.allow_alternative_da {
+            return Ok(());
+        }
         let expected_da_mode = DataAvailabilityMode::L1;
         let da_mode = *tx.nonce_data_availability_mode();
         if da_mode != expected_da_mode {
@@ -184,11 +227,15 @@ impl StatelessTransactionValidator {
     }
 
     /// The Starknet OS enforces that the fee data availability mode is L1. We add this validation
-    /// here in the gateway to prevent transactions from failing the OS.
+    /// here in the gateway to prevent transactions from failing the OS, unless the chain publishes
+    /// state diffs to an alternative data availability layer.
     fn validate_fee_data_availability_mode(
         &self,
         tx: &RpcTransaction,
     ) -> StatelessTransactionValidatorResult<()> {
+        if self.config.allow_alternative_da {
+            return Ok(());
+        }
         let expected_fee_mode = DataAvailabilityMode::L1;
         let fee_mode = *tx.fee_data_availability_mode();
         if fee_mode != expected_fee_mode {
@@ -3,8 +3,10 @@ use std::vec;
 
 use assert_matches::assert_matches;
 use rstest::rstest;
+use starknet_api::block::GasPrice;
 use starknet_api::core::{EntryPointSelector, L2_ADDRESS_UPPER_BOUND};
 use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::execution_resources::GasAmount;
 use starknet_api::rpc_transaction::EntryPointByType;
 use starknet_api::state::{EntryPoint, SierraContractClass};
 use starknet_api::test_utils::declare::rpc_declare_tx;
@@ -42,8 +44,14 @@ static DEFAULT_VALIDATOR_CONFIG_FOR_TESTING: LazyLock<StatelessTransactionValida
         validate_non_zero_l1_gas_fee: false,
         validate_non_zero_l2_gas_fee: false,
         validate_non_zero_l1_data_gas_fee: false,
+        max_l1_gas_amount: 1000,
+        max_l2_gas_amount: 1000,
+        max_l1_data_gas_amount: 1000,
+        reject_excessive_resource_bounds: true,
         max_calldata_length: 1,
         max_signature_length: 1,
+        allow_alternative_da: false,
+        allow_paymaster_data: false,
         max_contract_class_object_size: 100000,
         min_sierra_version: *MIN_SIERRA_VERSION,
         max_sierra_version: *MAX_SIERRA_VERSION,
@@ -122,6 +130,37 @@ static DEFAULT_VALIDATOR_CONFIG_FOR_TESTING: LazyLock<StatelessTransactionValida
     DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
     RpcTransactionArgs { signature: TransactionSignature(vec![Felt::ONE]), ..Default::default()}
 )]
+#[case::alternative_da(
+    StatelessTransactionValidatorConfig {
+        allow_alternative_da: true,
+        ..*DEFAULT_VALIDATOR_CONFIG_FOR_TESTING
+    },
+    RpcTransactionArgs {
+        nonce_data_availability_mode: DataAvailabilityMode::L2,
+        fee_data_availability_mode: DataAvailabilityMode::L2,
+        ..Default::default()
+    }
+)]
+#[case::paymaster_data(
+    StatelessTransactionValidatorConfig {
+        allow_paymaster_data: true,
+        ..*DEFAULT_VALIDATOR_CONFIG_FOR_TESTING
+    },
+    RpcTransactionArgs { paymaster_data: PaymasterData(vec![felt!(1_u128)]), ..Default::default() }
+)]
+#[case::excessive_resource_bounds_with_warning(
+    StatelessTransactionValidatorConfig {
+        reject_excessive_resource_bounds: false,
+        ..*DEFAULT_VALIDATOR_CONFIG_FOR_TESTING
+    },
+    RpcTransactionArgs {
+        resource_bounds: AllResourceBounds {
+            l1_gas: ResourceBounds { max_amount: GasAmount(1001), max_price_per_unit: GasPrice(1) },
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+)]
 #[case::valid_tx(DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(), RpcTransactionArgs::default())]
 fn test_positive_flow(
     #[case] config: StatelessTransactionValidatorConfig,
@@ -173,6 +212,18 @@ fn test_positive_flow(
         resource: Resource::L1DataGas, resource_bounds: ResourceBounds::default()
     }
 )]
+#[case::excessive_l2_gas_resource_bounds(
+    DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+    AllResourceBounds {
+        l2_gas: ResourceBounds { max_amount: GasAmount(1001), max_price_per_unit: GasPrice(1) },
+        ..Default::default()
+    },
+    StatelessTransactionValidatorError::ExcessiveResourceBounds {
+        resource: Resource::L2Gas,
+        max_amount: GasAmount(1001),
+        max_allowed_amount: GasAmount(1000),
+    }
+)]
 fn test_invalid_resource_bounds(
     #[case] config: StatelessTransactionValidatorConfig,
     #[case] resource_bounds: AllResourceBounds,
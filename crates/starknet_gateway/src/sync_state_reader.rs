@@ -6,7 +6,8 @@ use starknet_api::block::{BlockInfo, BlockNumber};
 use starknet_api::contract_class::ContractClass;
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::state::StorageKey;
-use starknet_state_sync_types::communication::SharedStateSyncClient;
+use starknet_state_sync_types::communication::{SharedStateSyncClient, StateSyncClientError};
+use starknet_state_sync_types::errors::StateSyncError;
 use starknet_types_core::felt::Felt;
 
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
@@ -30,6 +31,20 @@ impl MempoolStateReader for SyncStateReader {
     fn get_block_info(&self) -> StateResult<BlockInfo> {
         todo!()
     }
+
+    // TODO: Query the existence of the class once state sync supports it.
+    fn is_class_declared(&self, class_hash: ClassHash) -> StateResult<bool> {
+        let result = block_on(
+            self.state_sync_client.get_compiled_class_deprecated(self.block_number, class_hash),
+        );
+        match result {
+            Ok(_) => Ok(true),
+            Err(StateSyncClientError::StateSyncError(StateSyncError::ClassNotFound(_))) => {
+                Ok(false)
+            }
+            Err(e) => Err(StateError::StateReadError(e.to_string())),
+        }
+    }
 }
 
 impl BlockifierStateReader for SyncStateReader {
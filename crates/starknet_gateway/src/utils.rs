@@ -1,4 +1,5 @@
-use starknet_api::core::ChainId;
+use papyrus_common::class_hash::calculate_class_hash;
+use starknet_api::core::{ChainId, ClassHash};
 use starknet_api::executable_transaction::{
     AccountTransaction as ExecutableTransaction,
     DeclareTransaction as ExecutableDeclareTransaction,
@@ -61,6 +62,13 @@ pub fn compile_contract_and_build_executable_tx(
     })
 }
 
+/// Returns the hash of the class declared by the transaction.
+pub fn declared_class_hash(rpc_tx: &RpcDeclareTransaction) -> ClassHash {
+    match rpc_tx {
+        RpcDeclareTransaction::V3(tx) => calculate_class_hash(&tx.contract_class),
+    }
+}
+
 fn compile_contract_and_build_executable_declare_tx(
     rpc_tx: RpcDeclareTransaction,
     gateway_compiler: &GatewayCompiler,
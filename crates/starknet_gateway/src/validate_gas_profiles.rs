@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+use starknet_api::core::ContractAddress;
+use starknet_api::execution_resources::GasAmount;
+use tracing::warn;
+
+use crate::config::ValidateSpamFilterConfig;
+use crate::errors::ValidateSpamFilterError;
+
+#[cfg(test)]
+#[path = "validate_gas_profiles_test.rs"]
+mod validate_gas_profiles_test;
+
+/// Whether the most recent `__validate__` runs of an account were expensive, oldest first.
+#[derive(Debug, Default)]
+struct ValidateGasProfile {
+    recent_validations: VecDeque<bool>,
+    n_throttled_txs: u64,
+}
+
+/// Tracks the `__validate__` gas consumption of accounts, and throttles the accounts whose
+/// validations consistently consume gas near the allowed maximum; these cost the proposer a lot
+/// of execution for little throughput.
+///
+/// Accounts are profiled by address rather than by class, so that a spammer does not throttle
+/// every other user of its account class. Only the most recently validated accounts are tracked;
+/// an evicted account starts over with an empty profile.
+#[derive(Debug)]
+pub struct ValidateGasProfiles {
+    config: ValidateSpamFilterConfig,
+    profiles: Mutex<LruCache<ContractAddress, ValidateGasProfile>>,
+}
+
+impl ValidateGasProfiles {
+    pub fn new(config: ValidateSpamFilterConfig) -> Self {
+        let max_tracked_accounts = NonZeroUsize::new(config.max_tracked_accounts)
+            .expect("The number of tracked accounts should be positive.");
+        Self { config, profiles: Mutex::new(LruCache::new(max_tracked_accounts)) }
+    }
+
+    /// Records a validation of the account, and returns an error if the validated transaction
+    /// should not be admitted. Throttled accounts still have some of their transactions admitted,
+    /// keeping their profile up to date so that they can recover.
+    pub fn record_validation(
+        &self,
+        sender_address: ContractAddress,
+        gas_consumed: GasAmount,
+        max_gas: GasAmount,
+    ) -> Result<(), ValidateSpamFilterError> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let is_expensive = u128::from(gas_consumed.0) * 100
+            >= u128::from(max_gas.0) * u128::from(self.config.expensive_validation_percentage);
+        let mut profiles = self.profiles.lock().expect("Lock was poisoned.");
+        let profile = profiles.get_or_insert_mut(sender_address, ValidateGasProfile::default);
+        profile.recent_validations.push_back(is_expensive);
+        if profile.recent_validations.len() > self.config.window_size {
+            profile.recent_validations.pop_front();
+        }
+
+        if !self.is_throttled(profile) {
+            profile.n_throttled_txs = 0;
+            return Ok(());
+        }
+        if profile.n_throttled_txs == 0 {
+            warn!(
+                "Throttling account {sender_address}: its validations are consistently expensive."
+            );
+        }
+        profile.n_throttled_txs += 1;
+        if profile.n_throttled_txs % self.config.throttled_admission_interval == 0 {
+            return Ok(());
+        }
+        Err(ValidateSpamFilterError::AccountThrottled { sender_address })
+    }
+
+    fn is_throttled(&self, profile: &ValidateGasProfile) -> bool {
+        // Accounts are judged on a full window only.
+        if profile.recent_validations.len() < self.config.window_size {
+            return false;
+        }
+        let n_expensive =
+            profile.recent_validations.iter().filter(|is_expensive| **is_expensive).count();
+        n_expensive * 100
+            >= self.config.window_size * usize::from(self.config.throttling_percentage)
+    }
+}
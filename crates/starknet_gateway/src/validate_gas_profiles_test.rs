@@ -0,0 +1,89 @@
+use assert_matches::assert_matches;
+use starknet_api::contract_address;
+use starknet_api::execution_resources::GasAmount;
+
+use crate::config::ValidateSpamFilterConfig;
+use crate::errors::ValidateSpamFilterError;
+use crate::validate_gas_profiles::ValidateGasProfiles;
+
+const MAX_GAS: GasAmount = GasAmount(1000);
+const EXPENSIVE_GAS: GasAmount = GasAmount(950);
+const CHEAP_GAS: GasAmount = GasAmount(100);
+
+fn profiles() -> ValidateGasProfiles {
+    ValidateGasProfiles::new(ValidateSpamFilterConfig {
+        enabled: true,
+        window_size: 4,
+        expensive_validation_percentage: 90,
+        throttling_percentage: 75,
+        throttled_admission_interval: 3,
+        max_tracked_accounts: 2,
+    })
+}
+
+#[test]
+fn expensive_account_is_throttled() {
+    let profiles = profiles();
+    let sender_address = contract_address!("0x1");
+
+    // Accounts are not judged before their window is full.
+    for _ in 0..3 {
+        assert_eq!(profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS), Ok(()));
+    }
+
+    // Once throttled, one of every 3 transactions is admitted.
+    let admissions: Vec<bool> = (0..6)
+        .map(|_| profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS).is_ok())
+        .collect();
+    assert_eq!(admissions, [false, false, true, false, false, true]);
+    assert_matches!(
+        profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS),
+        Err(ValidateSpamFilterError::AccountThrottled { sender_address: address })
+        if address == sender_address
+    );
+
+    // Other accounts are unaffected, even if they share the class.
+    assert_eq!(
+        profiles.record_validation(contract_address!("0x2"), EXPENSIVE_GAS, MAX_GAS),
+        Ok(())
+    );
+}
+
+#[test]
+fn throttled_account_recovers() {
+    let profiles = profiles();
+    let sender_address = contract_address!("0x1");
+    for _ in 0..4 {
+        let _ = profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS);
+    }
+    assert!(profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS).is_err());
+
+    // Two cheap validations leave half of the window expensive.
+    let _ = profiles.record_validation(sender_address, CHEAP_GAS, MAX_GAS);
+    assert_eq!(profiles.record_validation(sender_address, CHEAP_GAS, MAX_GAS), Ok(()));
+}
+
+#[test]
+fn least_recently_validated_account_is_evicted() {
+    let profiles = profiles();
+    let sender_address = contract_address!("0x1");
+    for _ in 0..4 {
+        let _ = profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS);
+    }
+
+    // Tracking two more accounts evicts the throttled one, which starts over.
+    for other_address in [contract_address!("0x2"), contract_address!("0x3")] {
+        let _ = profiles.record_validation(other_address, CHEAP_GAS, MAX_GAS);
+    }
+    assert_eq!(profiles.record_validation(sender_address, EXPENSIVE_GAS, MAX_GAS), Ok(()));
+}
+
+#[test]
+fn disabled_filter_admits_all() {
+    let profiles =
+        ValidateGasProfiles::new(ValidateSpamFilterConfig { enabled: false, ..Default::default() });
+    let sender_address = contract_address!("0x1");
+    for _ in 0..100 {
+        assert_eq!(profiles.record_validation(sender_address, MAX_GAS, MAX_GAS), Ok(()));
+    }
+}
@@ -5,7 +5,9 @@ use async_trait::async_trait;
 use mockall::automock;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
+use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
+use starknet_mempool_types::mempool_types::DroppedTransaction;
 use starknet_sequencer_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -15,10 +17,17 @@ use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
     ComponentRequestAndResponseSender,
 };
+use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
 use crate::errors::GatewayError;
-use crate::gateway_types::{GatewayInput, GatewayResult};
+use crate::gateway_types::{
+    CallInput,
+    FeeBalanceCheck,
+    GatewayInput,
+    GatewayResult,
+    SignedGatewayReceipt,
+};
 
 pub type LocalGatewayClient = LocalComponentClient<GatewayRequest, GatewayResponse>;
 pub type RemoteGatewayClient = RemoteComponentClient<GatewayRequest, GatewayResponse>;
@@ -33,17 +42,51 @@ use tracing::{error, instrument};
 #[cfg_attr(any(feature = "testing", test), automock)]
 #[async_trait]
 pub trait GatewayClient: Send + Sync {
-    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<TransactionHash>;
+    /// Adds the transaction, returning the sequencer-signed receipt of its acceptance.
+    async fn add_tx(
+        &self,
+        gateway_input: GatewayInput,
+    ) -> GatewayClientResult<SignedGatewayReceipt>;
+
+    /// Executes a read-only contract call, returning its return data.
+    async fn call(&self, call_input: CallInput) -> GatewayClientResult<Vec<Felt>>;
+
+    /// Checks whether the sender's current balance covers the maximal fee of the transaction,
+    /// without adding it.
+    async fn check_fee_balance(
+        &self,
+        rpc_tx: RpcTransaction,
+    ) -> GatewayClientResult<FeeBalanceCheck>;
+
+    /// Notifies the gateway of transactions that were dropped by the mempool.
+    async fn notify_dropped_txs(
+        &self,
+        dropped_txs: Vec<DroppedTransaction>,
+    ) -> GatewayClientResult<()>;
+
+    /// Returns the drop record of the transaction, if the mempool dropped it.
+    async fn get_dropped_tx(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> GatewayClientResult<Option<DroppedTransaction>>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GatewayRequest {
     AddTransaction(GatewayInput),
+    Call(CallInput),
+    CheckFeeBalance(RpcTransaction),
+    NotifyDroppedTransactions(Vec<DroppedTransaction>),
+    GetDroppedTransaction(TransactionHash),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GatewayResponse {
-    AddTransaction(GatewayResult<TransactionHash>),
+    AddTransaction(GatewayResult<SignedGatewayReceipt>),
+    Call(GatewayResult<Vec<Felt>>),
+    CheckFeeBalance(GatewayResult<FeeBalanceCheck>),
+    NotifyDroppedTransactions(GatewayResult<()>),
+    GetDroppedTransaction(GatewayResult<Option<DroppedTransaction>>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -60,9 +103,64 @@ where
     ComponentClientType: Send + Sync + ComponentClient<GatewayRequest, GatewayResponse>,
 {
     #[instrument(skip(self))]
-    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<TransactionHash> {
+    async fn add_tx(
+        &self,
+        gateway_input: GatewayInput,
+    ) -> GatewayClientResult<SignedGatewayReceipt> {
         let request = GatewayRequest::AddTransaction(gateway_input);
         let response = self.send(request).await;
         handle_response_variants!(GatewayResponse, AddTransaction, GatewayClientError, GatewayError)
     }
+
+    #[instrument(skip(self))]
+    async fn call(&self, call_input: CallInput) -> GatewayClientResult<Vec<Felt>> {
+        let request = GatewayRequest::Call(call_input);
+        let response = self.send(request).await;
+        handle_response_variants!(GatewayResponse, Call, GatewayClientError, GatewayError)
+    }
+
+    #[instrument(skip(self))]
+    async fn check_fee_balance(
+        &self,
+        rpc_tx: RpcTransaction,
+    ) -> GatewayClientResult<FeeBalanceCheck> {
+        let request = GatewayRequest::CheckFeeBalance(rpc_tx);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            GatewayResponse,
+            CheckFeeBalance,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    #[instrument(skip(self))]
+    async fn notify_dropped_txs(
+        &self,
+        dropped_txs: Vec<DroppedTransaction>,
+    ) -> GatewayClientResult<()> {
+        let request = GatewayRequest::NotifyDroppedTransactions(dropped_txs);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            GatewayResponse,
+            NotifyDroppedTransactions,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    #[instrument(skip(self))]
+    async fn get_dropped_tx(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> GatewayClientResult<Option<DroppedTransaction>> {
+        let request = GatewayRequest::GetDroppedTransaction(tx_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            GatewayResponse,
+            GetDroppedTransaction,
+            GatewayClientError,
+            GatewayError
+        )
+    }
 }
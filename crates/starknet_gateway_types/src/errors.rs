@@ -1,14 +1,18 @@
 use enum_assoc::Assoc;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_rpc::error::{
+    class_already_declared,
+    contract_error,
+    invalid_transaction_nonce,
     unexpected_error,
     validation_failure,
     JsonRpcError,
-    CLASS_ALREADY_DECLARED,
+    BLOCK_NOT_FOUND,
     CLASS_HASH_NOT_FOUND,
     COMPILATION_FAILED,
     COMPILED_CLASS_HASH_MISMATCH,
     CONTRACT_CLASS_SIZE_IS_TOO_LARGE,
+    CONTRACT_NOT_FOUND,
     DUPLICATE_TX,
     INSUFFICIENT_ACCOUNT_BALANCE,
     INSUFFICIENT_MAX_FEE,
@@ -21,14 +25,18 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Error returned by the gateway, adhering to the Starknet RPC error format.
+/// This is the complete catalogue of gateway rejections; each error has the stable code of its
+/// Starknet RPC error.
 // To get JsonRpcError from GatewaySpecError, use `into_rpc` method.
 // TODO(yair): papyrus_rpc has a test that the add_tx functions return the correct error. Make sure
 // it is tested when we have a single gateway.
 #[derive(Debug, Clone, Eq, PartialEq, Assoc, Error, Serialize, Deserialize)]
 #[func(pub fn into_rpc(self) -> JsonRpcError<String>)]
 pub enum GatewaySpecError {
-    #[assoc(into_rpc = CLASS_ALREADY_DECLARED)]
-    ClassAlreadyDeclared,
+    #[assoc(into_rpc = BLOCK_NOT_FOUND)]
+    BlockNotFound,
+    #[assoc(into_rpc = class_already_declared(_data))]
+    ClassAlreadyDeclared { data: String },
     #[assoc(into_rpc = CLASS_HASH_NOT_FOUND)]
     ClassHashNotFound,
     #[assoc(into_rpc = COMPILED_CLASS_HASH_MISMATCH)]
@@ -37,6 +45,15 @@ pub enum GatewaySpecError {
     CompilationFailed,
     #[assoc(into_rpc = CONTRACT_CLASS_SIZE_IS_TOO_LARGE)]
     ContractClassSizeIsTooLarge,
+    #[assoc(into_rpc = contract_error(_data))]
+    ContractError { data: String },
+    #[assoc(into_rpc = CONTRACT_NOT_FOUND)]
+    ContractNotFound,
+    // An invalid nonce, already taken by a pending transaction; the data tells whether, and at
+    // what fee, that transaction can be replaced. Added as a separate variant so that the
+    // other variants keep their wire shape.
+    #[assoc(into_rpc = invalid_transaction_nonce(_data))]
+    DuplicateNonce { data: String },
     #[assoc(into_rpc = DUPLICATE_TX)]
     DuplicateTx,
     #[assoc(into_rpc = INSUFFICIENT_ACCOUNT_BALANCE)]
@@ -70,6 +87,23 @@ impl std::fmt::Display for GatewaySpecError {
     }
 }
 
+/// The JSON-RPC error object of a gateway error, as returned by both the HTTP server and
+/// papyrus_rpc.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GatewayErrorObject {
+    pub code: i32,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub data: Option<String>,
+}
+
+impl From<GatewaySpecError> for GatewayErrorObject {
+    fn from(error: GatewaySpecError) -> Self {
+        let JsonRpcError { code, message, data } = error.into_rpc();
+        Self { code, message: message.to_string(), data }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GatewayError {
     #[error("{source:?}")]
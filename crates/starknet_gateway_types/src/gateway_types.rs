@@ -1,13 +1,101 @@
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ascii_as_felt, ChainId, ContractAddress, EntryPointSelector};
+use starknet_api::crypto::utils::{verify_message_hash_signature, PublicKey, Signature};
 use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::fields::{Calldata, Fee};
+use starknet_api::transaction::TransactionHash;
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
 
 use crate::errors::GatewayError;
 
+const GATEWAY_RECEIPT_DOMAIN: &str = "GATEWAY_RECEIPT";
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GatewayInput {
     pub rpc_tx: RpcTransaction,
     pub message_metadata: Option<BroadcastedMessageMetadata>,
+    /// Whether a peer sent the transaction through mempool reconciliation; such transactions are
+    /// not propagated, as reconciliation already spreads them.
+    #[serde(default)]
+    pub reconciled: bool,
+}
+
+/// The state a call is executed against.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CallBlockId {
+    #[serde(rename = "latest")]
+    Latest,
+    // The sequencer does not expose the block under construction, so pending calls are executed
+    // against the latest block.
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "block_number")]
+    Number(BlockNumber),
+}
+
+/// A read-only call to a contract entry point (`starknet_call`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CallInput {
+    pub contract_address: ContractAddress,
+    pub entry_point_selector: EntryPointSelector,
+    pub calldata: Calldata,
+    pub block_id: CallBlockId,
+}
+
+/// The result of a fee balance preflight check of a transaction: whether its sender's current
+/// balance in the fee token of the transaction covers the maximal fee the transaction may be
+/// charged, given its resource bounds.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeeBalanceCheck {
+    pub fee_token_address: ContractAddress,
+    pub max_fee: Fee,
+    /// The low and high 128 bits of the sender's balance.
+    pub balance_low: Felt,
+    pub balance_high: Felt,
+    pub covers_max_fee: bool,
+}
+
+/// The facts a gateway receipt attests to: the gateway accepted the transaction on the chain at the
+/// given time.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GatewayReceipt {
+    pub tx_hash: TransactionHash,
+    /// Seconds since the Unix epoch.
+    pub received_timestamp: u64,
+    pub chain_id: ChainId,
+}
+
+impl GatewayReceipt {
+    /// The message hash signed by the sequencer. The domain prefix keeps receipt signatures from
+    /// being valid for any other message signed with the same key.
+    pub fn hash(&self) -> Result<Felt, StarknetApiError> {
+        Ok(Poseidon::hash_array(&[
+            ascii_as_felt(GATEWAY_RECEIPT_DOMAIN)?,
+            ascii_as_felt(&self.chain_id.to_string())?,
+            self.tx_hash.0,
+            Felt::from(self.received_timestamp),
+        ]))
+    }
+}
+
+/// A gateway receipt signed by the sequencer, giving the submitter proof of submission.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedGatewayReceipt {
+    pub receipt: GatewayReceipt,
+    pub signature: Signature,
+}
+
+impl SignedGatewayReceipt {
+    pub fn is_signed_by(&self, public_key: &PublicKey) -> bool {
+        let Ok(message_hash) = self.receipt.hash() else {
+            return false;
+        };
+        verify_message_hash_signature(&message_hash, &self.signature, public_key).unwrap_or(false)
+    }
 }
 
 pub type GatewayResult<T> = Result<T, GatewayError>;
@@ -1,5 +1,7 @@
+use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use jsonrpsee::types::error::ErrorCode;
+use starknet_batcher_types::communication::BatcherClientError;
 use starknet_gateway_types::communication::GatewayClientError;
 use starknet_gateway_types::errors::GatewayError;
 use thiserror::Error;
@@ -17,12 +19,18 @@ pub enum HttpServerRunError {
 pub enum HttpServerError {
     #[error(transparent)]
     GatewayClientError(#[from] GatewayClientError),
+    #[error(transparent)]
+    BatcherClientError(#[from] BatcherClientError),
 }
 
 impl IntoResponse for HttpServerError {
     fn into_response(self) -> Response {
         match self {
             HttpServerError::GatewayClientError(e) => gw_client_err_into_response(e),
+            HttpServerError::BatcherClientError(e) => {
+                error!("Encountered a BatcherClientError: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
         }
     }
 }
@@ -2,15 +2,22 @@ use std::clone::Clone;
 use std::net::SocketAddr;
 
 use axum::extract::State;
-use axum::routing::post;
+use axum::routing::{get, post};
 use axum::{async_trait, Json, Router};
 use infra_utils::type_name::short_type_name;
 use starknet_api::rpc_transaction::RpcTransaction;
-use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::GetFeeMarketInfoResponse;
+use starknet_batcher_types::communication::SharedBatcherClient;
 use starknet_gateway_types::communication::SharedGatewayClient;
-use starknet_gateway_types::gateway_types::GatewayInput;
+use starknet_gateway_types::gateway_types::{
+    CallInput,
+    FeeBalanceCheck,
+    GatewayInput,
+    SignedGatewayReceipt,
+};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::errors::ComponentError;
+use starknet_types_core::felt::Felt;
 use tracing::{debug, info, instrument};
 
 use crate::config::HttpServerConfig;
@@ -31,11 +38,16 @@ pub struct HttpServer {
 #[derive(Clone)]
 pub struct AppState {
     pub gateway_client: SharedGatewayClient,
+    pub batcher_client: SharedBatcherClient,
 }
 
 impl HttpServer {
-    pub fn new(config: HttpServerConfig, gateway_client: SharedGatewayClient) -> Self {
-        let app_state = AppState { gateway_client };
+    pub fn new(
+        config: HttpServerConfig,
+        gateway_client: SharedGatewayClient,
+        batcher_client: SharedBatcherClient,
+    ) -> Self {
+        let app_state = AppState { gateway_client, batcher_client };
         init_metrics();
         HttpServer { config, app_state }
     }
@@ -52,7 +64,12 @@ impl HttpServer {
     }
 
     pub fn app(&self) -> Router {
-        Router::new().route("/add_tx", post(add_tx)).with_state(self.app_state.clone())
+        Router::new()
+            .route("/add_tx", post(add_tx))
+            .route("/call", post(call))
+            .route("/check_fee_balance", post(check_fee_balance))
+            .route("/fee_market_info", get(fee_market_info))
+            .with_state(self.app_state.clone())
     }
 }
 
@@ -64,9 +81,10 @@ impl HttpServer {
 async fn add_tx(
     State(app_state): State<AppState>,
     Json(tx): Json<RpcTransaction>,
-) -> HttpServerResult<Json<TransactionHash>> {
+) -> HttpServerResult<Json<SignedGatewayReceipt>> {
     record_added_transaction();
-    let gateway_input: GatewayInput = GatewayInput { rpc_tx: tx, message_metadata: None };
+    let gateway_input: GatewayInput =
+        GatewayInput { rpc_tx: tx, message_metadata: None, reconciled: false };
     let add_tx_result = app_state.gateway_client.add_tx(gateway_input).await.map_err(|e| {
         debug!("Error while adding transaction: {}", e);
         HttpServerError::from(e)
@@ -76,18 +94,54 @@ async fn add_tx(
     add_tx_result_as_json(add_tx_result)
 }
 
+#[instrument(skip(app_state))]
+async fn call(
+    State(app_state): State<AppState>,
+    Json(call_input): Json<CallInput>,
+) -> HttpServerResult<Json<Vec<Felt>>> {
+    let retdata = app_state.gateway_client.call(call_input).await.map_err(|e| {
+        debug!("Error while executing call: {}", e);
+        HttpServerError::from(e)
+    })?;
+    Ok(Json(retdata))
+}
+
+#[instrument(skip(app_state))]
+async fn check_fee_balance(
+    State(app_state): State<AppState>,
+    Json(tx): Json<RpcTransaction>,
+) -> HttpServerResult<Json<FeeBalanceCheck>> {
+    let fee_balance_check = app_state.gateway_client.check_fee_balance(tx).await.map_err(|e| {
+        debug!("Error while checking the fee balance: {}", e);
+        HttpServerError::from(e)
+    })?;
+    Ok(Json(fee_balance_check))
+}
+
+#[instrument(skip(app_state))]
+async fn fee_market_info(
+    State(app_state): State<AppState>,
+) -> HttpServerResult<Json<GetFeeMarketInfoResponse>> {
+    let response = app_state.batcher_client.get_fee_market_info().await.map_err(|e| {
+        debug!("Error while getting the fee market info: {}", e);
+        HttpServerError::from(e)
+    })?;
+    Ok(Json(response))
+}
+
 pub(crate) fn add_tx_result_as_json(
-    result: HttpServerResult<TransactionHash>,
-) -> HttpServerResult<Json<TransactionHash>> {
-    let tx_hash = result?;
-    Ok(Json(tx_hash))
+    result: HttpServerResult<SignedGatewayReceipt>,
+) -> HttpServerResult<Json<SignedGatewayReceipt>> {
+    let signed_receipt = result?;
+    Ok(Json(signed_receipt))
 }
 
 pub fn create_http_server(
     config: HttpServerConfig,
     gateway_client: SharedGatewayClient,
+    batcher_client: SharedBatcherClient,
 ) -> HttpServer {
-    HttpServer::new(config, gateway_client)
+    HttpServer::new(config, gateway_client, batcher_client)
 }
 
 #[async_trait]
@@ -1,20 +1,51 @@
 use axum::body::{Bytes, HttpBody};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::communication::GatewayClientError;
+use starknet_gateway_types::errors::{GatewayError, GatewayErrorObject, GatewaySpecError};
+use starknet_gateway_types::gateway_types::{GatewayReceipt, SignedGatewayReceipt};
+use starknet_types_core::felt::Felt;
 
+use crate::errors::HttpServerError;
 use crate::http_server::add_tx_result_as_json;
 
 #[tokio::test]
-async fn test_tx_hash_json_conversion() {
-    let tx_hash = TransactionHash::default();
-    let response = add_tx_result_as_json(Ok(tx_hash)).into_response();
+async fn test_signed_receipt_json_conversion() {
+    let signed_receipt = SignedGatewayReceipt {
+        receipt: GatewayReceipt {
+            tx_hash: TransactionHash::default(),
+            received_timestamp: 1700000000,
+            chain_id: ChainId::Mainnet,
+        },
+        signature: Signature { r: Felt::ONE, s: Felt::TWO },
+    };
+    let response = add_tx_result_as_json(Ok(signed_receipt.clone())).into_response();
 
     let status_code = response.status();
     let response_bytes = &to_bytes(response).await;
 
     assert_eq!(status_code, StatusCode::OK, "{response_bytes:?}");
-    assert_eq!(tx_hash, serde_json::from_slice(response_bytes).unwrap());
+    assert_eq!(signed_receipt, serde_json::from_slice(response_bytes).unwrap());
+}
+
+#[tokio::test]
+async fn test_gateway_spec_error_json_conversion() {
+    let spec_error = GatewaySpecError::ValidationFailure { data: "Invalid signature.".to_owned() };
+    let error = HttpServerError::GatewayClientError(GatewayClientError::GatewayError(
+        GatewayError::GatewaySpecError { source: spec_error.clone(), p2p_message_metadata: None },
+    ));
+    let response = error.into_response();
+
+    let status_code = response.status();
+    let response_bytes = &to_bytes(response).await;
+
+    assert_eq!(status_code, StatusCode::OK, "{response_bytes:?}");
+    // The error is the JSON-RPC error object papyrus_rpc returns for the same error.
+    let error_object: GatewayErrorObject = serde_json::from_slice(response_bytes).unwrap();
+    assert_eq!(error_object, GatewayErrorObject::from(spec_error));
 }
 
 async fn to_bytes(res: Response) -> Bytes {
@@ -6,6 +6,7 @@ use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::test_utils::rpc_tx_to_json;
 use starknet_api::transaction::TransactionHash;
 use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::SignedGatewayReceipt;
 
 use crate::config::HttpServerConfig;
 
@@ -25,7 +26,8 @@ impl HttpTestClient {
         let response = self.add_tx(rpc_tx).await;
         assert!(response.status().is_success());
 
-        response.json().await.unwrap()
+        let signed_receipt: SignedGatewayReceipt = response.json().await.unwrap();
+        signed_receipt.receipt.tx_hash
     }
 
     // TODO: implement when usage eventually arises.
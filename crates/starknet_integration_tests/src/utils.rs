@@ -32,6 +32,7 @@ use starknet_http_server::config::HttpServerConfig;
 use starknet_http_server::test_utils::create_http_server_config;
 use starknet_mempool_p2p::config::MempoolP2pConfig;
 use starknet_monitoring_endpoint::config::MonitoringEndpointConfig;
+use starknet_sequencer_infra::signer::SignerConfig;
 use starknet_sequencer_infra::test_utils::{get_available_socket, AvailablePorts};
 use starknet_sequencer_node::config::component_config::ComponentConfig;
 use starknet_sequencer_node::config::node_config::SequencerNodeConfig;
@@ -39,6 +40,9 @@ use starknet_sequencer_node::config::test_utils::RequiredParams;
 use starknet_state_sync::config::StateSyncConfig;
 use starknet_types_core::felt::Felt;
 
+// The key the test sequencers sign with.
+pub const TEST_SIGNER_PRIVATE_KEY: Felt = Felt::ONE;
+
 pub fn create_chain_info() -> ChainInfo {
     let mut chain_info = ChainInfo::create_for_testing();
     // Note that the chain_id affects hashes of transactions and blocks, therefore affecting the
@@ -92,6 +96,7 @@ pub async fn create_node_config(
             eth_fee_token_address: fee_token_addresses.eth_fee_token_address,
             strk_fee_token_address: fee_token_addresses.strk_fee_token_address,
             validator_id,
+            signer_private_key: TEST_SIGNER_PRIVATE_KEY,
         },
     )
 }
@@ -129,6 +134,8 @@ pub fn create_consensus_manager_configs_and_channels(
                 timeouts: timeouts.clone(),
                 ..Default::default()
             },
+            signer_config: SignerConfig { private_key: TEST_SIGNER_PRIVATE_KEY },
+            ..Default::default()
         })
         .collect();
 
@@ -263,7 +270,13 @@ pub async fn create_gateway_config(chain_info: ChainInfo) -> GatewayConfig {
     };
     let stateful_tx_validator_config = StatefulTransactionValidatorConfig::default();
 
-    GatewayConfig { stateless_tx_validator_config, stateful_tx_validator_config, chain_info }
+    GatewayConfig {
+        stateless_tx_validator_config,
+        stateful_tx_validator_config,
+        chain_info,
+        signer_config: SignerConfig { private_key: TEST_SIGNER_PRIVATE_KEY },
+        ..Default::default()
+    }
 }
 
 // TODO(Tsabary): deprecate this function.
@@ -281,6 +294,7 @@ pub fn create_batcher_config(
     BatcherConfig {
         storage: batcher_storage_config,
         block_builder_config: BlockBuilderConfig { chain_info, ..Default::default() },
+        signer_config: SignerConfig { private_key: TEST_SIGNER_PRIVATE_KEY },
         ..Default::default()
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use blockifier::blockifier::block::randomness_message_hash;
 use futures::StreamExt;
 use mempool_test_utils::starknet_api_test_utils::MultiAccountTransactionGenerator;
 use papyrus_consensus::types::ValidatorId;
@@ -23,7 +24,9 @@ use starknet_integration_tests::utils::{
     create_txs_for_integration_test,
     run_integration_test_scenario,
     test_tx_hashes_for_integration_test,
+    TEST_SIGNER_PRIVATE_KEY,
 };
+use starknet_sequencer_infra::signer::{LocalKeySigner, SignerBackend, SignerConfig};
 use starknet_sequencer_infra::trace_util::configure_tracing;
 use starknet_types_core::felt::Felt;
 use tracing::debug;
@@ -150,9 +153,11 @@ async fn listen_to_broadcasted_messages(
     let broadcasted_messages_receiver =
         &mut consensus_proposals_channels.broadcasted_messages_receiver;
     // TODO (Dan, Guy): retrieve / calculate the expected proposal init and fin.
+    let signer = LocalKeySigner::new(&SignerConfig { private_key: TEST_SIGNER_PRIVATE_KEY });
     let expected_proposal_init = ProposalInit {
         height: expected_height,
         proposer: expected_proposer_id,
+        randomness_signature: Some(signer.sign(randomness_message_hash(expected_height)).unwrap()),
         ..Default::default()
     };
     let expected_proposal_fin = ProposalFin { proposal_content_id: BlockHash(expected_content_id) };
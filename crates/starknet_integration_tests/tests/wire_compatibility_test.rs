@@ -0,0 +1,282 @@
+//! Wire compatibility of the remote component protocol. During a rolling upgrade, components of
+//! the previous release communicate with components of the current one, so the serialization of
+//! every request and response must be readable by both. The fixtures hold the serializations of
+//! the messages of the previous release, as produced by that release; they are not generated from
+//! this tree.
+//!
+//! The tests check the messages, not the components: running the binaries of the previous release
+//! against the current ones is out of their scope.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+
+use pretty_assertions::assert_eq;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::executable_transaction::{AccountTransaction, InvokeTransaction};
+use starknet_api::rpc_transaction::{RpcInvokeTransaction, RpcInvokeTransactionV3, RpcTransaction};
+use starknet_api::transaction::fields::{Fee, TransactionSignature};
+use starknet_api::transaction::{InvokeTransactionV1, TransactionHash};
+use starknet_api::{calldata, contract_address, felt};
+use starknet_batcher_types::batcher_types::{GetHeightResponse, StartHeightInput};
+use starknet_batcher_types::communication::{BatcherRequest, BatcherResponse};
+use starknet_gateway_types::communication::{GatewayRequest, GatewayResponse};
+use starknet_gateway_types::errors::{GatewayError, GatewaySpecError};
+use starknet_gateway_types::gateway_types::GatewayInput;
+use starknet_mempool_types::communication::{
+    AddTransactionArgsWrapper,
+    MempoolRequest,
+    MempoolResponse,
+};
+use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs, CommitBlockArgs};
+use starknet_sequencer_infra::serde_utils::SerdeWrapper;
+
+const FIX_SUGGESTION: &str = "Changing the serialization of a component message breaks rolling \
+                              upgrades. Fields may only be added with a default, and must be \
+                              listed as added fields. An intended break, e.g., in a release which \
+                              drops the compatibility, must be listed as a breaking change.";
+
+/// The deviations of the current release from the wire format of the previous one.
+struct WireChanges {
+    /// Fields added with a default since the previous release. The previous release ignores them,
+    /// hence they must not be added to types which deny unknown fields.
+    added_fields: &'static [&'static str],
+    /// Messages of the previous release which the current one cannot read, so the components
+    /// exchanging them must be upgraded together. Names are matched against both the requests and
+    /// the responses.
+    breaking_changes: &'static [&'static str],
+}
+
+fn fixture_path(component: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("resources/wire_compatibility")
+        .join(format!("{component}.json"))
+}
+
+fn to_wire<T: Serialize + DeserializeOwned + Debug>(message: T) -> Value {
+    serde_json::from_slice(&SerdeWrapper::new(message).wrapper_serialize().unwrap()).unwrap()
+}
+
+/// Removes the added fields, which the previous release does not serialize.
+fn without_added_fields(mut value: Value, added_fields: &[&str]) -> Value {
+    match &mut value {
+        Value::Object(map) => {
+            map.retain(|key, _| !added_fields.contains(&key.as_str()));
+            for field in map.values_mut() {
+                *field = without_added_fields(field.take(), added_fields);
+            }
+        }
+        Value::Array(elements) => {
+            for element in elements {
+                *element = without_added_fields(element.take(), added_fields);
+            }
+        }
+        _ => {}
+    }
+    value
+}
+
+/// Asserts every message of the previous release is readable by the current one, and every
+/// current message is serialized as in the previous release, up to the listed changes.
+fn assert_wire_compatible<T: Serialize + DeserializeOwned + Debug>(
+    fixture: &Value,
+    current_messages: Vec<(&str, T)>,
+    wire_changes: &WireChanges,
+) {
+    let fixture = fixture.as_object().expect("Fixture messages should be a JSON object.");
+    for (name, wire_message) in fixture {
+        let bytes = serde_json::to_vec(wire_message).unwrap();
+        let message = SerdeWrapper::<T>::wrapper_deserialize(&bytes);
+        if wire_changes.breaking_changes.contains(&name.as_str()) {
+            assert!(message.is_err(), "Message {name} is readable; it is not a breaking change.");
+            continue;
+        }
+        let message = message.unwrap_or_else(|error| {
+            panic!("Failed to deserialize message {name}: {error}.\n{FIX_SUGGESTION}")
+        });
+        assert_eq!(
+            &without_added_fields(to_wire(message), wire_changes.added_fields),
+            wire_message,
+            "Message {name} does not survive a round trip.\n{FIX_SUGGESTION}"
+        );
+    }
+
+    let current_messages: Map<String, Value> = current_messages
+        .into_iter()
+        .map(|(name, message)| {
+            (name.to_string(), without_added_fields(to_wire(message), wire_changes.added_fields))
+        })
+        .collect();
+    let compatible_fixture: Map<String, Value> = fixture
+        .iter()
+        .filter(|(name, _)| !wire_changes.breaking_changes.contains(&name.as_str()))
+        .map(|(name, message)| (name.clone(), message.clone()))
+        .collect();
+    assert_eq!(
+        current_messages, compatible_fixture,
+        "Messages are serialized differently than in the previous release.\n{FIX_SUGGESTION}"
+    );
+}
+
+fn run_wire_compatibility_test<Request, Response>(
+    component: &str,
+    requests: Vec<(&str, Request)>,
+    responses: Vec<(&str, Response)>,
+    wire_changes: WireChanges,
+) where
+    Request: Serialize + DeserializeOwned + Debug,
+    Response: Serialize + DeserializeOwned + Debug,
+{
+    let path = fixture_path(component);
+    let fixture: Value = serde_json::from_str(
+        &fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Failed to read fixture {}.", path.display())),
+    )
+    .unwrap();
+    assert_wire_compatible(&fixture["requests"], requests, &wire_changes);
+    assert_wire_compatible(&fixture["responses"], responses, &wire_changes);
+}
+
+fn account_tx() -> AccountTransaction {
+    AccountTransaction::Invoke(InvokeTransaction {
+        tx: InvokeTransactionV1 {
+            max_fee: Fee(100),
+            signature: TransactionSignature(vec![felt!("0x5")]),
+            nonce: Nonce(felt!("0x1")),
+            sender_address: contract_address!("0x2"),
+            calldata: calldata![felt!("0x3")],
+        }
+        .into(),
+        tx_hash: TransactionHash(felt!("0x4")),
+    })
+}
+
+fn rpc_tx() -> RpcTransaction {
+    RpcTransaction::Invoke(RpcInvokeTransaction::V3(RpcInvokeTransactionV3 {
+        sender_address: contract_address!("0x2"),
+        calldata: calldata![felt!("0x3")],
+        signature: TransactionSignature(vec![felt!("0x5")]),
+        nonce: Nonce(felt!("0x1")),
+        resource_bounds: Default::default(),
+        tip: Default::default(),
+        paymaster_data: Default::default(),
+        account_deployment_data: Default::default(),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+    }))
+}
+
+#[test]
+fn mempool_wire_compatibility() {
+    run_wire_compatibility_test(
+        "mempool",
+        vec![
+            (
+                "AddTransaction",
+                MempoolRequest::AddTransaction(AddTransactionArgsWrapper {
+                    args: AddTransactionArgs {
+                        tx: account_tx(),
+                        account_state: AccountState {
+                            address: contract_address!("0x2"),
+                            nonce: Nonce(felt!("0x1")),
+                        },
+                        declared_class_hash: None,
+                    },
+                    p2p_message_metadata: None,
+                    reconciled: false,
+                }),
+            ),
+            (
+                "CommitBlock",
+                MempoolRequest::CommitBlock(CommitBlockArgs {
+                    address_to_nonce: HashMap::from([(
+                        contract_address!("0x2"),
+                        Nonce(felt!("0x2")),
+                    )]),
+                    tx_hashes: HashSet::from([TransactionHash(felt!("0x4"))]),
+                }),
+            ),
+            ("GetTransactions", MempoolRequest::GetTransactions(3)),
+        ],
+        vec![
+            ("AddTransaction", MempoolResponse::AddTransaction(Ok(()))),
+            (
+                "AddTransactionRejected",
+                MempoolResponse::AddTransaction(Err(MempoolError::DuplicateNonce {
+                    address: contract_address!("0x2"),
+                    nonce: Nonce(felt!("0x1")),
+                    existing_tx_hash: TransactionHash(felt!("0x4")),
+                    replacement_fee_threshold: None,
+                })),
+            ),
+            ("CommitBlock", MempoolResponse::CommitBlock(Ok(()))),
+            ("GetTransactions", MempoolResponse::GetTransactions(Ok(vec![account_tx()]))),
+        ],
+        WireChanges {
+            added_fields: &[
+                "reconciled",
+                "declared_class_hash",
+                "existing_tx_hash",
+                "replacement_fee_threshold",
+            ],
+            breaking_changes: &[],
+        },
+    );
+}
+
+#[test]
+fn batcher_wire_compatibility() {
+    run_wire_compatibility_test(
+        "batcher",
+        vec![
+            ("GetCurrentHeight", BatcherRequest::GetCurrentHeight),
+            (
+                "StartHeight",
+                BatcherRequest::StartHeight(StartHeightInput { height: BlockNumber(5) }),
+            ),
+        ],
+        vec![
+            (
+                "GetCurrentHeight",
+                BatcherResponse::GetCurrentHeight(Ok(GetHeightResponse { height: BlockNumber(5) })),
+            ),
+            ("StartHeight", BatcherResponse::StartHeight(Ok(()))),
+        ],
+        WireChanges { added_fields: &[], breaking_changes: &[] },
+    );
+}
+
+#[test]
+fn gateway_wire_compatibility() {
+    run_wire_compatibility_test(
+        "gateway",
+        vec![(
+            "AddTransaction",
+            GatewayRequest::AddTransaction(GatewayInput {
+                rpc_tx: rpc_tx(),
+                message_metadata: None,
+                reconciled: false,
+            }),
+        )],
+        vec![(
+            "AddTransactionRejected",
+            GatewayResponse::AddTransaction(Err(GatewayError::GatewaySpecError {
+                source: GatewaySpecError::InvalidTransactionNonce,
+                p2p_message_metadata: None,
+            })),
+        )],
+        WireChanges {
+            added_fields: &["reconciled"],
+            // An accepted transaction is answered with a signed receipt instead of its hash, so the
+            // HTTP server and the gateway must be upgraded together.
+            breaking_changes: &["AddTransactionAccepted"],
+        },
+    );
+}
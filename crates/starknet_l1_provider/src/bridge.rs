@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use papyrus_base_layer::l1_handler::executable_l1_handler_txs;
+use papyrus_base_layer::{BaseLayerContract, Finality, L1Event};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ChainId, ContractAddress, EthAddress};
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::L1HandlerTransaction as L1HandlerTransactionInner;
+use starknet_types_core::felt::Felt;
+use tracing::{debug, info};
+use validator::Validate;
+
+use crate::errors::L1ScraperError;
+
+#[cfg(test)]
+#[path = "bridge_test.rs"]
+pub mod bridge_test;
+
+/// Configuration of the native token bridge, used by appchains without a genesis state to fund
+/// accounts from L1.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct BridgeConfig {
+    pub chain_id: ChainId,
+    /// The L1 bridge contract; only its messages are treated as deposits.
+    pub l1_bridge_address: EthAddress,
+    /// The L2 bridge contract, whose L1 handler credits the deposited amount.
+    pub l2_bridge_address: ContractAddress,
+    /// The first L1 block to scan for deposits.
+    pub start_l1_block: u64,
+    /// How final an L1 block must be before its deposits are handled.
+    pub finality: Finality,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: ChainId::Other("0x0".to_owned()),
+            l1_bridge_address: EthAddress::default(),
+            l2_bridge_address: ContractAddress::default(),
+            start_l1_block: 0,
+            finality: Finality::Confirmations(10),
+        }
+    }
+}
+
+impl SerializeConfig for BridgeConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from([
+            ser_param(
+                "chain_id",
+                &self.chain_id,
+                "The chain ID of the L2 network.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "l1_bridge_address",
+                &self.l1_bridge_address,
+                "Address of the L1 bridge contract whose messages are treated as deposits.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "l2_bridge_address",
+                &self.l2_bridge_address,
+                "Address of the L2 bridge contract that credits the deposits.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "start_l1_block",
+                &self.start_l1_block,
+                "The first L1 block to scan for deposits.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "finality",
+                &self.finality,
+                "How final an L1 block must be before its deposits are handled: latest, safe, \
+                 finalized or a number of confirmations.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Scans the base layer for deposits made through the L1 bridge, and turns them into the L1
+/// handler transactions that credit them on L2.
+///
+/// Each L1 block is scanned once, unless it is reorged out, in which case it is scanned again; the
+/// L1 provider drops transactions it already knows by their hash, so every deposit is credited
+/// exactly once.
+pub struct BridgeDepositScraper<B: BaseLayerContract> {
+    base_layer: B,
+    config: BridgeConfig,
+    next_l1_block: u64,
+}
+
+impl<B> BridgeDepositScraper<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    L1ScraperError: From<B::Error>,
+{
+    pub fn new(config: BridgeConfig, base_layer: B) -> Self {
+        let next_l1_block = config.start_l1_block;
+        Self { base_layer, config, next_l1_block }
+    }
+
+    /// The next L1 block to scan for deposits.
+    pub fn next_l1_block(&self) -> u64 {
+        self.next_l1_block
+    }
+
+    /// Returns the deposits made in the L1 blocks that became final since the previous call.
+    pub async fn fetch_deposits(&mut self) -> Result<Vec<L1HandlerTransaction>, L1ScraperError> {
+        if let Some(reverted_range) = self.base_layer.detect_reorg().await? {
+            if reverted_range.from_block < self.next_l1_block {
+                info!(
+                    "L1 blocks {}-{} were reorged out, rescanning them for deposits.",
+                    reverted_range.from_block, reverted_range.until_block
+                );
+                self.next_l1_block = reverted_range.from_block.max(self.config.start_l1_block);
+            }
+        }
+
+        let Some(latest_l1_block) =
+            self.base_layer.latest_l1_block_number(self.config.finality).await?
+        else {
+            return Ok(vec![]);
+        };
+        if latest_l1_block < self.next_l1_block {
+            return Ok(vec![]);
+        }
+
+        let events =
+            self.base_layer.l1_to_l2_message_events(self.next_l1_block, latest_l1_block).await?;
+        let deposit_events = events.into_iter().filter(
+            |event| matches!(event, L1Event::LogMessageToL2 { tx, .. } if self.is_deposit(tx)),
+        );
+        let deposits = executable_l1_handler_txs(deposit_events, &self.config.chain_id)?;
+
+        debug!(
+            "Found {} deposits in L1 blocks {}-{}.",
+            deposits.len(),
+            self.next_l1_block,
+            latest_l1_block
+        );
+        self.next_l1_block = latest_l1_block + 1;
+        Ok(deposits)
+    }
+
+    // The L1 sender of a message is the first element of the L1 handler calldata.
+    fn is_deposit(&self, tx: &L1HandlerTransactionInner) -> bool {
+        tx.contract_address == self.config.l2_bridge_address
+            && tx.calldata.0.first() == Some(&Felt::from(self.config.l1_bridge_address))
+    }
+}
@@ -0,0 +1,107 @@
+use papyrus_base_layer::reorg_monitor::RevertedRange;
+use papyrus_base_layer::test_utils::FakeBaseLayer;
+use papyrus_base_layer::{Finality, L1Event};
+use pretty_assertions::assert_eq;
+use starknet_api::core::{ChainId, ContractAddress, EthAddress};
+use starknet_api::executable_transaction::L1HandlerTransaction as ExecutableL1HandlerTransaction;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::L1HandlerTransaction;
+use starknet_api::{calldata, contract_address, felt};
+use starknet_types_core::felt::Felt;
+
+use crate::bridge::{BridgeConfig, BridgeDepositScraper};
+use crate::test_utils::L1ProviderContentBuilder;
+
+const L1_BRIDGE_ADDRESS: u64 = 0xb1;
+const L2_BRIDGE_ADDRESS: &str = "0xb2";
+
+fn eth_address(address: u64) -> EthAddress {
+    EthAddress::try_from(felt!(address)).unwrap()
+}
+
+fn message(l1_sender: EthAddress, l2_recipient: ContractAddress, amount: u64) -> L1Event {
+    L1Event::LogMessageToL2 {
+        tx: L1HandlerTransaction {
+            contract_address: l2_recipient,
+            calldata: calldata![Felt::from(l1_sender), felt!(amount)],
+            ..Default::default()
+        },
+        fee: Fee(1),
+    }
+}
+
+pub(crate) fn deposit(amount: u64) -> L1Event {
+    message(eth_address(L1_BRIDGE_ADDRESS), contract_address!(L2_BRIDGE_ADDRESS), amount)
+}
+
+fn scraper(messages: Vec<(u64, L1Event)>) -> BridgeDepositScraper<FakeBaseLayer> {
+    scraper_with_base_layer(FakeBaseLayer { events: messages, ..Default::default() })
+}
+
+pub(crate) fn scraper_with_base_layer(
+    base_layer: FakeBaseLayer,
+) -> BridgeDepositScraper<FakeBaseLayer> {
+    let config = BridgeConfig {
+        chain_id: ChainId::create_for_testing(),
+        l1_bridge_address: eth_address(L1_BRIDGE_ADDRESS),
+        l2_bridge_address: contract_address!(L2_BRIDGE_ADDRESS),
+        start_l1_block: 1,
+        finality: Finality::Latest,
+        ..Default::default()
+    };
+    BridgeDepositScraper::new(config, base_layer)
+}
+
+fn amounts(deposits: &[ExecutableL1HandlerTransaction]) -> Vec<Felt> {
+    deposits.iter().map(|deposit| deposit.tx.calldata.0[1]).collect()
+}
+
+#[tokio::test]
+async fn only_bridge_messages_are_deposits() {
+    let mut scraper = scraper(vec![
+        (1, deposit(10)),
+        // Sent by another L1 contract.
+        (2, message(eth_address(0xe1), contract_address!(L2_BRIDGE_ADDRESS), 20)),
+        // Sent by the bridge to another L2 contract.
+        (3, message(eth_address(L1_BRIDGE_ADDRESS), contract_address!("0xe2"), 30)),
+        (4, deposit(40)),
+    ]);
+    *scraper.base_layer.latest_l1_block.lock().unwrap() = 4;
+
+    let deposits = scraper.fetch_deposits().await.unwrap();
+    assert_eq!(amounts(&deposits), [felt!(10_u64), felt!(40_u64)]);
+    assert!(deposits.iter().all(|deposit| deposit.paid_fee_on_l1 == Fee(1)));
+    assert_eq!(scraper.next_l1_block(), 5);
+}
+
+#[tokio::test]
+async fn each_block_is_scanned_once() {
+    let mut scraper = scraper(vec![(1, deposit(10)), (2, deposit(20))]);
+
+    *scraper.base_layer.latest_l1_block.lock().unwrap() = 1;
+    assert_eq!(amounts(&scraper.fetch_deposits().await.unwrap()), [felt!(10_u64)]);
+    // No new final blocks.
+    assert_eq!(scraper.fetch_deposits().await.unwrap(), []);
+
+    *scraper.base_layer.latest_l1_block.lock().unwrap() = 2;
+    assert_eq!(amounts(&scraper.fetch_deposits().await.unwrap()), [felt!(20_u64)]);
+}
+
+#[tokio::test]
+async fn reorged_deposits_are_credited_once() {
+    let mut scraper = scraper(vec![(1, deposit(10)), (2, deposit(20))]);
+    let mut l1_provider = L1ProviderContentBuilder::new().build_into_l1_provider();
+
+    *scraper.base_layer.latest_l1_block.lock().unwrap() = 2;
+    let deposits = scraper.fetch_deposits().await.unwrap();
+    l1_provider.add_txs(deposits.clone());
+
+    // Block 2 is reorged out, and its deposit is rescanned.
+    *scraper.base_layer.reverted_range.lock().unwrap() =
+        Some(RevertedRange { from_block: 2, until_block: 2 });
+    let rescanned_deposits = scraper.fetch_deposits().await.unwrap();
+    assert_eq!(rescanned_deposits, deposits[1..]);
+    l1_provider.add_txs(rescanned_deposits);
+
+    assert_eq!(l1_provider.tx_manager.txs.values().cloned().collect::<Vec<_>>(), deposits);
+}
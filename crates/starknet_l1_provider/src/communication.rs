@@ -26,6 +26,15 @@ impl ComponentRequestHandler<L1ProviderRequest, L1ProviderResponse> for L1Provid
             L1ProviderRequest::GetTransactions(n_txs) => {
                 L1ProviderResponse::GetTransactions(self.get_txs(n_txs))
             }
+            L1ProviderRequest::AddTransactions(txs) => {
+                self.add_txs(txs);
+                L1ProviderResponse::AddTransactions(Ok(()))
+            }
+            L1ProviderRequest::GetL1Status => L1ProviderResponse::GetL1Status(Ok(self.l1_status())),
+            L1ProviderRequest::ReportL1Sync(succeeded) => {
+                self.report_l1_sync(succeeded);
+                L1ProviderResponse::ReportL1Sync(Ok(()))
+            }
         }
     }
 }
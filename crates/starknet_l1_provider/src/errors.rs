@@ -1,4 +1,5 @@
 use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerError;
+use starknet_api::StarknetApiError;
 use thiserror::Error;
 
 // TODO(Gilad): move to scraper module once it's created.
@@ -6,4 +7,6 @@ use thiserror::Error;
 pub enum L1ScraperError {
     #[error(transparent)]
     BaseLayer(#[from] EthereumBaseLayerError),
+    #[error(transparent)]
+    StarknetApi(#[from] StarknetApiError),
 }
@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use starknet_l1_provider_types::L1Status;
+use tracing::{info, warn};
+
+#[cfg(test)]
+#[path = "l1_health_test.rs"]
+pub mod l1_health_test;
+
+pub(crate) const L1_DEGRADED: (&str, &str) =
+    ("l1_provider_l1_degraded", "Whether the base layer is unreachable (1) or synced (0)");
+
+pub(crate) const DEFAULT_L1_OUTAGE_THRESHOLD: Duration = Duration::from_secs(300);
+
+/// Tracks the outcome of base layer syncs, and decides when an outage is long enough for the node
+/// to degrade: blocks keep being produced, but the data read from L1 is considered stale and L1
+/// handler transactions are not included until the base layer is synced again.
+#[derive(Debug)]
+pub struct L1HealthMonitor {
+    outage_threshold: Duration,
+    // The time since which the base layer is unreachable, if it is.
+    failing_since: Option<Instant>,
+    status: L1Status,
+}
+
+impl L1HealthMonitor {
+    pub fn new(outage_threshold: Duration) -> Self {
+        Self { outage_threshold, failing_since: None, status: L1Status::Synced }
+    }
+
+    pub fn status(&self) -> L1Status {
+        self.status
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.status == L1Status::Degraded
+    }
+
+    /// Records the outcome of a base layer sync attempt made at `now`. A successful sync ends the
+    /// outage immediately; failures degrade the node only once they persist for the threshold.
+    pub fn record_sync(&mut self, succeeded: bool, now: Instant) {
+        let status = if succeeded {
+            self.failing_since = None;
+            L1Status::Synced
+        } else {
+            let failing_since = *self.failing_since.get_or_insert(now);
+            if now.saturating_duration_since(failing_since) >= self.outage_threshold {
+                L1Status::Degraded
+            } else {
+                self.status
+            }
+        };
+        self.set_status(status);
+    }
+
+    fn set_status(&mut self, status: L1Status) {
+        if status == self.status {
+            return;
+        }
+        match status {
+            L1Status::Degraded => warn!(
+                "The base layer is unreachable for over {:?}; L1 data is stale and L1 handler \
+                 transactions are paused.",
+                self.outage_threshold
+            ),
+            L1Status::Synced => info!("The base layer is reachable again; resuming L1 handling."),
+        }
+        metrics::gauge!(L1_DEGRADED.0, f64::from(u8::from(status == L1Status::Degraded)));
+        self.status = status;
+    }
+}
+
+impl Default for L1HealthMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_L1_OUTAGE_THRESHOLD)
+    }
+}
+
+pub(crate) fn init_metrics() {
+    metrics::register_gauge!(L1_DEGRADED.0);
+    metrics::describe_gauge!(L1_DEGRADED.0, L1_DEGRADED.1);
+    metrics::gauge!(L1_DEGRADED.0, 0.0);
+}
@@ -0,0 +1,36 @@
+use std::time::{Duration, Instant};
+
+use pretty_assertions::assert_eq;
+use starknet_l1_provider_types::L1Status;
+
+use crate::l1_health::L1HealthMonitor;
+
+const OUTAGE_THRESHOLD: Duration = Duration::from_secs(60);
+
+#[test]
+fn degrades_only_after_outage_threshold() {
+    let mut l1_health = L1HealthMonitor::new(OUTAGE_THRESHOLD);
+    let start = Instant::now();
+
+    l1_health.record_sync(false, start);
+    l1_health.record_sync(false, start + OUTAGE_THRESHOLD / 2);
+    assert_eq!(l1_health.status(), L1Status::Synced);
+
+    l1_health.record_sync(false, start + OUTAGE_THRESHOLD);
+    assert_eq!(l1_health.status(), L1Status::Degraded);
+}
+
+#[test]
+fn successful_sync_ends_outage() {
+    let mut l1_health = L1HealthMonitor::new(OUTAGE_THRESHOLD);
+    let start = Instant::now();
+
+    l1_health.record_sync(false, start);
+    l1_health.record_sync(false, start + OUTAGE_THRESHOLD);
+    l1_health.record_sync(true, start + OUTAGE_THRESHOLD * 2);
+    assert_eq!(l1_health.status(), L1Status::Synced);
+
+    // A new outage is measured from its own first failure.
+    l1_health.record_sync(false, start + OUTAGE_THRESHOLD * 3);
+    assert_eq!(l1_health.status(), L1Status::Synced);
+}
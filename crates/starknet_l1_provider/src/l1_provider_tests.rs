@@ -1,11 +1,14 @@
+use std::time::Duration;
+
 use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use starknet_api::test_utils::l1_handler::executable_l1_handler_tx;
 use starknet_api::transaction::TransactionHash;
 use starknet_api::{l1_handler_tx_args, tx_hash};
 use starknet_l1_provider_types::errors::L1ProviderError;
-use starknet_l1_provider_types::ValidationStatus;
+use starknet_l1_provider_types::{L1Status, ValidationStatus};
 
+use crate::l1_health::L1HealthMonitor;
 use crate::test_utils::L1ProviderContentBuilder;
 use crate::L1Provider;
 use crate::ProviderState::{Pending, Propose, Uninitialized, Validate};
@@ -36,6 +39,27 @@ fn get_txs_happy_flow() {
     assert_eq!(l1_provider.get_txs(1).unwrap(), []);
 }
 
+#[test]
+fn get_txs_is_paused_while_l1_is_degraded() {
+    // Setup.
+    let txs = [tx!(tx_hash: 0), tx!(tx_hash: 1)];
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs(txs.clone())
+        .with_state(Propose)
+        .build_into_l1_provider();
+    l1_provider.l1_health = L1HealthMonitor::new(Duration::ZERO);
+
+    // Test.
+    l1_provider.report_l1_sync(false);
+    assert_eq!(l1_provider.l1_status(), L1Status::Degraded);
+    assert_eq!(l1_provider.get_txs(2).unwrap(), []);
+
+    // Transactions are proposed again, in order, once L1 is reachable.
+    l1_provider.report_l1_sync(true);
+    assert_eq!(l1_provider.l1_status(), L1Status::Synced);
+    assert_eq!(l1_provider.get_txs(2).unwrap(), txs);
+}
+
 #[test]
 fn validate_happy_flow() {
     // Setup.
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use papyrus_base_layer::ethereum_base_layer_contract::{
+    EthereumBaseLayerConfig,
+    EthereumBaseLayerContract,
+};
+use papyrus_base_layer::BaseLayerContract;
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_l1_provider_types::{L1ProviderClientResult, SharedL1ProviderClient};
+use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::component_server::WrapperServer;
+use starknet_sequencer_infra::errors::ComponentError;
+use tokio::time::interval;
+use tracing::{error, warn};
+use url::Url;
+use validator::Validate;
+
+use crate::bridge::{BridgeConfig, BridgeDepositScraper};
+use crate::errors::L1ScraperError;
+
+#[cfg(test)]
+#[path = "l1_scraper_test.rs"]
+pub mod l1_scraper_test;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct L1ScraperConfig {
+    /// The Ethereum node the base layer is scraped from.
+    pub node_url: Url,
+    pub bridge_config: BridgeConfig,
+}
+
+impl Default for L1ScraperConfig {
+    fn default() -> Self {
+        Self {
+            node_url: EthereumBaseLayerConfig::default().node_url,
+            bridge_config: BridgeConfig::default(),
+        }
+    }
+}
+
+impl SerializeConfig for L1ScraperConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut dump = BTreeMap::from([ser_param(
+            "node_url",
+            &self.node_url,
+            "URL of the Ethereum node the base layer is scraped from.",
+            ParamPrivacyInput::Private,
+        )]);
+        dump.append(&mut append_sub_config_name(self.bridge_config.dump(), "bridge_config"));
+        dump
+    }
+}
+
+pub type EthereumL1Scraper = L1Scraper<EthereumBaseLayerContract>;
+pub type L1ScraperServer = WrapperServer<EthereumL1Scraper>;
+
+/// The L1 sync loop: periodically scrapes the base layer for deposits, hands them to the L1
+/// provider, and reports the outcome of every attempt, so that the provider can tell when the base
+/// layer is unreachable for long enough to degrade.
+pub struct L1Scraper<B: BaseLayerContract> {
+    deposit_scraper: BridgeDepositScraper<B>,
+    l1_provider_client: SharedL1ProviderClient,
+    poll_interval: Duration,
+}
+
+impl<B> L1Scraper<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    L1ScraperError: From<B::Error>,
+{
+    pub fn new(
+        deposit_scraper: BridgeDepositScraper<B>,
+        l1_provider_client: SharedL1ProviderClient,
+        poll_interval: Duration,
+    ) -> Self {
+        Self { deposit_scraper, l1_provider_client, poll_interval }
+    }
+
+    /// Makes a single attempt to sync with the base layer. A failure to reach the base layer is
+    /// reported to the provider rather than returned; only failures to reach the provider are.
+    pub async fn sync(&mut self) -> L1ProviderClientResult<()> {
+        let succeeded = match self.deposit_scraper.fetch_deposits().await {
+            Ok(deposits) => {
+                if !deposits.is_empty() {
+                    self.l1_provider_client.add_txs(deposits).await?;
+                }
+                true
+            }
+            Err(e) => {
+                warn!("Failed to sync with the base layer: {e}");
+                false
+            }
+        };
+        self.l1_provider_client.report_l1_sync(succeeded).await
+    }
+}
+
+#[async_trait]
+impl<B> ComponentStarter for L1Scraper<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    L1ScraperError: From<B::Error>,
+{
+    async fn start(&mut self) -> Result<(), ComponentError> {
+        let mut ticker = interval(self.poll_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.sync().await {
+                error!("Failed to reach the L1 provider: {e}");
+                return Err(ComponentError::InternalComponentError);
+            }
+        }
+    }
+}
+
+pub fn create_l1_scraper(
+    config: L1ScraperConfig,
+    poll_interval: Duration,
+    l1_provider_client: SharedL1ProviderClient,
+) -> EthereumL1Scraper {
+    let base_layer = EthereumBaseLayerContract::new(EthereumBaseLayerConfig {
+        node_url: config.node_url,
+        ..Default::default()
+    });
+    let deposit_scraper = BridgeDepositScraper::new(config.bridge_config, base_layer);
+    L1Scraper::new(deposit_scraper, l1_provider_client, poll_interval)
+}
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use papyrus_base_layer::test_utils::{BaseLayerQuery, FakeBaseLayer};
+use starknet_l1_provider_types::MockL1ProviderClient;
+
+use crate::bridge::bridge_test::{deposit, scraper_with_base_layer};
+use crate::l1_scraper::L1Scraper;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[tokio::test]
+async fn sync_adds_deposits_and_reports_success() {
+    let base_layer = FakeBaseLayer {
+        events: vec![(1, deposit(10))],
+        latest_l1_block: Mutex::new(1),
+        ..Default::default()
+    };
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_add_txs().withf(|txs| txs.len() == 1).times(1).returning(|_| Ok(()));
+    l1_provider_client
+        .expect_report_l1_sync()
+        .withf(|succeeded| *succeeded)
+        .times(1)
+        .returning(|_| Ok(()));
+    let mut l1_scraper = L1Scraper::new(
+        scraper_with_base_layer(base_layer),
+        Arc::new(l1_provider_client),
+        POLL_INTERVAL,
+    );
+
+    l1_scraper.sync().await.unwrap();
+}
+
+#[tokio::test]
+async fn sync_reports_unreachable_base_layer() {
+    let base_layer = FakeBaseLayer {
+        events: vec![(1, deposit(10))],
+        latest_l1_block: Mutex::new(1),
+        failing_queries: Mutex::new(HashSet::from(BaseLayerQuery::ALL)),
+        ..Default::default()
+    };
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_add_txs().never();
+    l1_provider_client
+        .expect_report_l1_sync()
+        .withf(|succeeded| !*succeeded)
+        .times(1)
+        .returning(|_| Ok(()));
+    let mut l1_scraper = L1Scraper::new(
+        scraper_with_base_layer(base_layer),
+        Arc::new(l1_provider_client),
+        POLL_INTERVAL,
+    );
+
+    // Failing to reach the base layer is reported, not returned.
+    l1_scraper.sync().await.unwrap();
+}
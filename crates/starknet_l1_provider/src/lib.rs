@@ -1,24 +1,34 @@
+pub mod bridge;
 pub mod communication;
 pub mod errors;
+pub mod l1_health;
+pub mod l1_scraper;
 
 #[cfg(test)]
 pub mod test_utils;
 
 use std::collections::BTreeMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use indexmap::{IndexMap, IndexSet};
-use papyrus_config::converters::deserialize_milliseconds_to_duration;
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::converters::{
+    deserialize_milliseconds_to_duration,
+    deserialize_seconds_to_duration,
+};
+use papyrus_config::dumping::{ser_optional_sub_config, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::executable_transaction::L1HandlerTransaction;
 use starknet_api::transaction::TransactionHash;
 use starknet_l1_provider_types::errors::L1ProviderError;
-use starknet_l1_provider_types::{L1ProviderResult, ValidationStatus};
+use starknet_l1_provider_types::{L1ProviderResult, L1Status, ValidationStatus};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use tracing::debug;
 use validator::Validate;
 
+use crate::l1_health::{L1HealthMonitor, DEFAULT_L1_OUTAGE_THRESHOLD};
+use crate::l1_scraper::L1ScraperConfig;
+
 #[cfg(test)]
 #[path = "l1_provider_tests.rs"]
 pub mod l1_provider_tests;
@@ -31,6 +41,7 @@ pub struct L1Provider {
     // TODO(Gilad): consider transitioning to a generic phantom state once the infra is stabilized
     // and we see how well it handles consuming the L1Provider when moving between states.
     state: ProviderState,
+    l1_health: L1HealthMonitor,
 }
 
 impl L1Provider {
@@ -39,8 +50,14 @@ impl L1Provider {
     }
 
     /// Retrieves up to `n_txs` transactions that have yet to be proposed or accepted on L2.
+    /// While the base layer is degraded, no transactions are returned, so that blocks are built
+    /// from L2 transactions only.
     pub fn get_txs(&mut self, n_txs: usize) -> L1ProviderResult<Vec<L1HandlerTransaction>> {
         match self.state {
+            ProviderState::Propose if self.l1_health.is_degraded() => {
+                debug!("The base layer is degraded, not proposing L1 handler transactions.");
+                Ok(vec![])
+            }
             ProviderState::Propose => Ok(self.tx_manager.get_txs(n_txs)),
             ProviderState::Pending => Err(L1ProviderError::GetTransactionsInPendingState),
             ProviderState::Validate => Err(L1ProviderError::GetTransactionConsensusBug),
@@ -48,6 +65,25 @@ impl L1Provider {
         }
     }
 
+    /// Adds L1 handler transactions scraped from L1, e.g. bridge deposits. Transactions that are
+    /// already known, either pending or included on L2, are ignored, so that re-scraping L1 blocks
+    /// never introduces duplicates.
+    pub fn add_txs(&mut self, txs: Vec<L1HandlerTransaction>) {
+        for tx in txs {
+            self.tx_manager.add_unconsumed_l1_not_in_l2_block_tx(tx);
+        }
+    }
+
+    /// Records the outcome of an attempt to sync with the base layer, as reported by the
+    /// [`L1Scraper`](crate::l1_scraper::L1Scraper).
+    pub fn report_l1_sync(&mut self, succeeded: bool) {
+        self.l1_health.record_sync(succeeded, Instant::now());
+    }
+
+    pub fn l1_status(&self) -> L1Status {
+        self.l1_health.status()
+    }
+
     /// Returns true if and only if the given transaction is both not included in an L2 block, and
     /// unconsumed on L1.
     pub fn validate(&self, tx_hash: TransactionHash) -> L1ProviderResult<ValidationStatus> {
@@ -86,15 +122,6 @@ impl L1Provider {
         self.reset().await
     }
 
-    // TODO: this will likely change during integration with infra team.
-    pub async fn start(&self) {
-        todo!(
-            "Create a process that wakes up every config.poll_interval seconds and updates
-        internal L1 and L2 buffers according to collected L1 events and recent blocks created on
-        L2."
-        )
-    }
-
     pub async fn reset(&mut self) -> L1ProviderResult<()> {
         todo!(
             "resets internal buffers and rewinds the internal crawler _pointer_ back for ~1 \
@@ -138,11 +165,17 @@ impl TransactionManager {
         }
     }
 
-    pub fn _add_unconsumed_l1_not_in_l2_block_tx(&mut self, _tx: L1HandlerTransaction) {
-        todo!(
-            "Check if tx is in L2, if it isn't on L2 add it to the txs buffer, otherwise print
-             debug and do nothing."
-        )
+    pub fn add_unconsumed_l1_not_in_l2_block_tx(&mut self, tx: L1HandlerTransaction) {
+        let tx_hash = tx.tx_hash;
+        if self.on_l2_awaiting_l1_consumption.contains(&tx_hash) {
+            debug!("Transaction {tx_hash} is already included on L2, ignoring it.");
+            return;
+        }
+        if self.txs.contains_key(&tx_hash) {
+            debug!("Transaction {tx_hash} is already known, ignoring it.");
+            return;
+        }
+        self.txs.insert(tx_hash, tx);
     }
 
     pub fn _mark_tx_included_on_l2(&mut self, _tx_hash: &TransactionHash) {
@@ -195,23 +228,56 @@ impl std::fmt::Display for ProviderState {
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct L1ProviderConfig {
     #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
-    pub _poll_interval: Duration,
+    pub poll_interval: Duration,
+    /// How long the base layer may be unreachable before the node degrades.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub l1_outage_threshold: Duration,
+    /// If set, the node syncs with the base layer; otherwise it never receives L1 handler
+    /// transactions.
+    pub l1_scraper_config: Option<L1ScraperConfig>,
+}
+
+impl Default for L1ProviderConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(100),
+            l1_outage_threshold: DEFAULT_L1_OUTAGE_THRESHOLD,
+            l1_scraper_config: None,
+        }
+    }
 }
 
 impl SerializeConfig for L1ProviderConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from([ser_param(
-            "_poll_interval",
-            &Duration::from_millis(100).as_millis(),
-            "Interval in milliseconds between each scraping attempt of L1.",
-            ParamPrivacyInput::Public,
-        )])
+        let mut dump = BTreeMap::from([
+            ser_param(
+                "poll_interval",
+                &self.poll_interval.as_millis(),
+                "Interval in milliseconds between each scraping attempt of L1.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "l1_outage_threshold",
+                &self.l1_outage_threshold.as_secs(),
+                "Time in seconds the base layer may be unreachable before the node degrades: \
+                 blocks are still produced, but L1 data is marked stale and L1 handler \
+                 transactions are paused until the base layer is reachable again.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.extend(ser_optional_sub_config(&self.l1_scraper_config, "l1_scraper_config"));
+        dump
     }
 }
 
-pub fn create_l1_provider(_config: L1ProviderConfig) -> L1Provider {
-    L1Provider { state: ProviderState::Propose, ..Default::default() }
+pub fn create_l1_provider(config: L1ProviderConfig) -> L1Provider {
+    l1_health::init_metrics();
+    L1Provider {
+        state: ProviderState::Propose,
+        l1_health: L1HealthMonitor::new(config.l1_outage_threshold),
+        ..Default::default()
+    }
 }
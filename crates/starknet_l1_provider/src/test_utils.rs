@@ -20,6 +20,7 @@ impl From<L1ProviderContent> for L1Provider {
                 .map(|tm_content| tm_content.complete_to_tx_manager())
                 .unwrap_or_default(),
             state: content.state.unwrap_or_default(),
+            l1_health: Default::default(),
         }
     }
 }
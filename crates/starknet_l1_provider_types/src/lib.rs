@@ -26,14 +26,31 @@ pub enum ValidationStatus {
     ConsumedOnL1OrUnknown,
 }
 
+/// Whether the data the node reads from the base layer is up to date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1Status {
+    Synced,
+    /// The base layer is unreachable for longer than the configured outage threshold: blocks are
+    /// still produced, but L1 data (e.g. messages and gas prices) is stale, and L1 handler
+    /// transactions are not proposed. L1 sync keeps being attempted, and the node returns to
+    /// `Synced` on the first successful one.
+    Degraded,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum L1ProviderRequest {
     GetTransactions(usize),
+    AddTransactions(Vec<L1HandlerTransaction>),
+    GetL1Status,
+    ReportL1Sync(bool),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum L1ProviderResponse {
     GetTransactions(L1ProviderResult<Vec<L1HandlerTransaction>>),
+    AddTransactions(L1ProviderResult<()>),
+    GetL1Status(L1ProviderResult<L1Status>),
+    ReportL1Sync(L1ProviderResult<()>),
 }
 
 /// Serves as the provider's shared interface. Requires `Send + Sync` to allow transferring and
@@ -44,6 +61,10 @@ pub trait L1ProviderClient: Send + Sync {
     async fn get_txs(&self, n_txs: usize) -> L1ProviderClientResult<Vec<L1HandlerTransaction>>;
     async fn validate(&self, _tx_hash: TransactionHash)
     -> L1ProviderClientResult<ValidationStatus>;
+    async fn add_txs(&self, txs: Vec<L1HandlerTransaction>) -> L1ProviderClientResult<()>;
+    async fn l1_status(&self) -> L1ProviderClientResult<L1Status>;
+    /// Reports whether the latest attempt to sync with the base layer succeeded.
+    async fn report_l1_sync(&self, succeeded: bool) -> L1ProviderClientResult<()>;
 }
 
 #[async_trait]
@@ -62,6 +83,39 @@ where
             L1ProviderError
         )
     }
+    #[instrument(skip(self, txs))]
+    async fn add_txs(&self, txs: Vec<L1HandlerTransaction>) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::AddTransactions(txs);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            AddTransactions,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+    #[instrument(skip(self))]
+    async fn l1_status(&self) -> L1ProviderClientResult<L1Status> {
+        let request = L1ProviderRequest::GetL1Status;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            GetL1Status,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+    #[instrument(skip(self))]
+    async fn report_l1_sync(&self, succeeded: bool) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::ReportL1Sync(succeeded);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            ReportL1Sync,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
     async fn validate(
         &self,
         _tx_hash: TransactionHash,
@@ -1,11 +1,15 @@
 use async_trait::async_trait;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use starknet_api::core::ClassHash;
+use starknet_api::error_context::ErrorContext;
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::rpc_transaction::{
     RpcDeployAccountTransaction,
     RpcInvokeTransaction,
     RpcTransaction,
 };
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::communication::SharedGatewayClient;
 use starknet_mempool_p2p_types::communication::SharedMempoolP2pPropagatorClient;
 use starknet_mempool_types::communication::{
     AddTransactionArgsWrapper,
@@ -16,7 +20,9 @@ use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{CommitBlockArgs, MempoolResult};
 use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
 use starknet_sequencer_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use tracing::{debug, warn};
 
+use crate::config::MempoolConfig;
 use crate::mempool::Mempool;
 
 pub type LocalMempoolServer =
@@ -24,23 +30,48 @@ pub type LocalMempoolServer =
 pub type RemoteMempoolServer = RemoteComponentServer<MempoolRequest, MempoolResponse>;
 
 pub fn create_mempool(
+    config: MempoolConfig,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    gateway_client: SharedGatewayClient,
 ) -> MempoolCommunicationWrapper {
-    MempoolCommunicationWrapper::new(Mempool::default(), mempool_p2p_propagator_client)
+    MempoolCommunicationWrapper::new(
+        Mempool::new(config),
+        mempool_p2p_propagator_client,
+        gateway_client,
+    )
 }
 
 /// Wraps the mempool to enable inbound async communication from other components.
 pub struct MempoolCommunicationWrapper {
     mempool: Mempool,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    gateway_client: SharedGatewayClient,
 }
 
 impl MempoolCommunicationWrapper {
     pub fn new(
         mempool: Mempool,
         mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+        gateway_client: SharedGatewayClient,
     ) -> Self {
-        MempoolCommunicationWrapper { mempool, mempool_p2p_propagator_client }
+        MempoolCommunicationWrapper { mempool, mempool_p2p_propagator_client, gateway_client }
+    }
+
+    /// Notifies the gateway of the transactions dropped by the mempool, so that their clients get
+    /// a terminal status.
+    fn notify_dropped_txs(&mut self) {
+        let dropped_txs = self.mempool.drain_dropped_txs();
+        if dropped_txs.is_empty() {
+            return;
+        }
+
+        // Sent in the background: the gateway may itself be waiting on the mempool.
+        let gateway_client = self.gateway_client.clone();
+        tokio::spawn(async move {
+            if let Err(err) = gateway_client.notify_dropped_txs(dropped_txs).await {
+                warn!("Failed to notify the gateway of dropped transactions: {err}");
+            }
+        });
     }
 
     async fn send_tx_to_p2p(
@@ -56,22 +87,11 @@ impl MempoolCommunicationWrapper {
                 .map_err(|_| MempoolError::P2pPropagatorClientError { tx_hash: tx.tx_hash() }),
             None => {
                 let tx_hash = tx.tx_hash();
-                match tx {
-                    AccountTransaction::Invoke(invoke_tx) => self
-                        .mempool_p2p_propagator_client
-                        .add_transaction(RpcTransaction::Invoke(RpcInvokeTransaction::V3(
-                            invoke_tx.into(),
-                        )))
-                        .await
-                        .map_err(|_| MempoolError::P2pPropagatorClientError { tx_hash })?,
-                    AccountTransaction::DeployAccount(deploy_account_tx) => self
-                        .mempool_p2p_propagator_client
-                        .add_transaction(RpcTransaction::DeployAccount(
-                            RpcDeployAccountTransaction::V3(deploy_account_tx.into()),
-                        ))
+                if let Some(rpc_tx) = to_propagated_rpc_tx(tx) {
+                    self.mempool_p2p_propagator_client
+                        .add_transaction(rpc_tx)
                         .await
-                        .map_err(|_| MempoolError::P2pPropagatorClientError { tx_hash })?,
-                    AccountTransaction::Declare(_) => {}
+                        .map_err(|_| MempoolError::P2pPropagatorClientError { tx_hash })?;
                 }
                 Ok(())
             }
@@ -82,7 +102,18 @@ impl MempoolCommunicationWrapper {
         &mut self,
         args_wrapper: AddTransactionArgsWrapper,
     ) -> MempoolResult<()> {
-        self.mempool.add_tx(args_wrapper.args.clone())?;
+        let add_tx_result = self.mempool.add_tx(args_wrapper.args.clone());
+        self.notify_dropped_txs();
+        if let Err(err) = &add_tx_result {
+            let context = ErrorContext::new("mempool").with_tx_hash(args_wrapper.args.tx.tx_hash());
+            debug!("Rejected transaction: {}", context.wrap(err));
+        }
+        add_tx_result?;
+        // Peers reconcile their mempools with each other, so reconciled transactions are not
+        // gossiped again.
+        if args_wrapper.reconciled {
+            return Ok(());
+        }
         // TODO: Verify that only transactions that were added to the mempool are sent.
         // TODO: handle declare correctly and remove this match.
         match args_wrapper.args.tx {
@@ -92,12 +123,52 @@ impl MempoolCommunicationWrapper {
     }
 
     fn commit_block(&mut self, args: CommitBlockArgs) -> MempoolResult<()> {
-        self.mempool.commit_block(args)
+        let commit_block_result = self.mempool.commit_block(args);
+        self.notify_dropped_txs();
+        commit_block_result
     }
 
     fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<AccountTransaction>> {
         self.mempool.get_txs(n_txs)
     }
+
+    fn peek_txs(&self, n_txs: usize) -> MempoolResult<Vec<AccountTransaction>> {
+        Ok(self.mempool.peek_txs(n_txs))
+    }
+
+    fn get_pending_declare(&self, class_hash: ClassHash) -> MempoolResult<Option<TransactionHash>> {
+        Ok(self.mempool.get_pending_declare(class_hash))
+    }
+
+    fn get_tx_hashes(&self) -> MempoolResult<Vec<TransactionHash>> {
+        Ok(self.mempool.tx_hashes())
+    }
+
+    fn get_rpc_txs(&self, tx_hashes: Vec<TransactionHash>) -> MempoolResult<Vec<RpcTransaction>> {
+        Ok(self
+            .mempool
+            .get_txs_by_hash(&tx_hashes)
+            .into_iter()
+            .filter_map(to_propagated_rpc_tx)
+            .collect())
+    }
+}
+
+// Returns the form in which the given transaction is propagated to the network, unless it is not
+// propagated.
+// TODO: handle declare correctly.
+fn to_propagated_rpc_tx(tx: AccountTransaction) -> Option<RpcTransaction> {
+    match tx {
+        AccountTransaction::Invoke(invoke_tx) => {
+            Some(RpcTransaction::Invoke(RpcInvokeTransaction::V3(invoke_tx.into())))
+        }
+        AccountTransaction::DeployAccount(deploy_account_tx) => {
+            Some(RpcTransaction::DeployAccount(RpcDeployAccountTransaction::V3(
+                deploy_account_tx.into(),
+            )))
+        }
+        AccountTransaction::Declare(_) => None,
+    }
 }
 
 #[async_trait]
@@ -113,6 +184,18 @@ impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommuni
             MempoolRequest::GetTransactions(n_txs) => {
                 MempoolResponse::GetTransactions(self.get_txs(n_txs))
             }
+            MempoolRequest::PeekTransactions(n_txs) => {
+                MempoolResponse::PeekTransactions(self.peek_txs(n_txs))
+            }
+            MempoolRequest::GetPendingDeclare(class_hash) => {
+                MempoolResponse::GetPendingDeclare(self.get_pending_declare(class_hash))
+            }
+            MempoolRequest::GetTransactionHashes => {
+                MempoolResponse::GetTransactionHashes(self.get_tx_hashes())
+            }
+            MempoolRequest::GetRpcTransactions(tx_hashes) => {
+                MempoolResponse::GetRpcTransactions(self.get_rpc_txs(tx_hashes))
+            }
         }
     }
 }
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::TransactionVersions;
+use validator::{Validate, ValidationError};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Validate)]
+#[validate(schema(function = "validate_mempool_config"))]
+pub struct MempoolConfig {
+    pub enable_fee_escalation: bool,
+    // Percentage increase for tip and max gas price to enable transaction replacement; a zero
+    // increase would let a transaction be replaced for free.
+    #[validate(range(min = 1, max = 100))]
+    pub fee_escalation_percentage: u8, // E.g., 10 for a 10% increase.
+    // Transaction versions rejected on admission.
+    pub disabled_tx_versions: TransactionVersions,
+    // Admitted transactions are only forwarded to the network, not kept for block building; for
+    // nodes that run no batcher, e.g. ingress nodes.
+    pub forward_only: bool,
+    // Limits of the total serialized size of the held transactions, in bytes; large declares and
+    // calldata-heavy invokes take far more memory than the average transaction.
+    #[validate(range(min = 1))]
+    pub max_pool_size_in_bytes: usize,
+    #[validate(range(min = 1))]
+    pub max_account_size_in_bytes: usize,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        MempoolConfig {
+            enable_fee_escalation: true,
+            fee_escalation_percentage: 10,
+            disabled_tx_versions: TransactionVersions::default(),
+            forward_only: false,
+            max_pool_size_in_bytes: 1 << 30,
+            max_account_size_in_bytes: 16 << 20,
+        }
+    }
+}
+
+impl SerializeConfig for MempoolConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable_fee_escalation",
+                &self.enable_fee_escalation,
+                "If true, transactions can be replaced with higher fee transactions.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "fee_escalation_percentage",
+                &self.fee_escalation_percentage,
+                "Percentage increase for tip and max gas price to enable transaction replacement.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "disabled_tx_versions",
+                &self.disabled_tx_versions,
+                "Space-separated account transaction versions that are neither accepted nor \
+                 executed on the chain, e.g. \"0 1\".",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "forward_only",
+                &self.forward_only,
+                "If true, admitted transactions are only propagated to the network and are not \
+                 kept for block building. Meant for nodes that do not run a batcher.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_pool_size_in_bytes",
+                &self.max_pool_size_in_bytes,
+                "The maximal total serialized size of the transactions held in the mempool, in \
+                 bytes.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_account_size_in_bytes",
+                &self.max_account_size_in_bytes,
+                "The maximal total serialized size of the transactions of a single account held \
+                 in the mempool, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+fn validate_mempool_config(mempool_config: &MempoolConfig) -> Result<(), ValidationError> {
+    if mempool_config.max_account_size_in_bytes > mempool_config.max_pool_size_in_bytes {
+        return Err(ValidationError::new(
+            "max_account_size_in_bytes must be at most max_pool_size_in_bytes",
+        ));
+    }
+    Ok(())
+}
@@ -1,20 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::SystemTime;
 
 use starknet_api::block::GasPrice;
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
-use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::errors::{MempoolError, ReplacementFeeThreshold};
 use starknet_mempool_types::mempool_types::{
     AccountState,
     AddTransactionArgs,
     CommitBlockArgs,
+    DroppedTransaction,
     MempoolResult,
+    TransactionDropReason,
 };
 use tracing::{debug, info, instrument};
 
-use crate::transaction_pool::TransactionPool;
+use crate::config::MempoolConfig;
+use crate::transaction_pool::{tx_size_in_bytes, TransactionPool};
 use crate::transaction_queue::TransactionQueue;
 use crate::utils::try_increment_nonce;
 
@@ -22,22 +26,11 @@ use crate::utils::try_increment_nonce;
 #[path = "mempool_test.rs"]
 pub mod mempool_test;
 
-#[derive(Debug)]
-pub struct MempoolConfig {
-    enable_fee_escalation: bool,
-    // TODO: consider adding validations; should be bounded?
-    // Percentage increase for tip and max gas price to enable transaction replacement.
-    fee_escalation_percentage: u8, // E.g., 10 for a 10% increase.
-}
-
-impl Default for MempoolConfig {
-    fn default() -> Self {
-        MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage: 10 }
-    }
-}
-
 type AddressToNonce = HashMap<ContractAddress, Nonce>;
 
+/// The number of forwarded transactions a forward-only mempool remembers to reject duplicates.
+const FORWARDED_TRANSACTIONS_CAPACITY: usize = 100_000;
+
 /// Represents the state tracked by the mempool.
 /// It is partitioned into categories, each serving a distinct role in the lifecycle of transaction
 /// management.
@@ -133,6 +126,31 @@ impl MempoolState {
     }
 }
 
+/// The most recent transactions forwarded by a forward-only mempool, which holds no transactions.
+#[derive(Debug, Default)]
+struct ForwardedTransactions {
+    tx_hashes: HashSet<TransactionHash>,
+    // Insertion order, used to forget the oldest transactions once the capacity is reached.
+    insertion_order: VecDeque<TransactionHash>,
+}
+
+impl ForwardedTransactions {
+    /// Returns false if the transaction was already forwarded.
+    fn insert(&mut self, tx_hash: TransactionHash) -> bool {
+        if !self.tx_hashes.insert(tx_hash) {
+            return false;
+        }
+        self.insertion_order.push_back(tx_hash);
+
+        if self.insertion_order.len() > FORWARDED_TRANSACTIONS_CAPACITY {
+            let oldest_tx_hash =
+                self.insertion_order.pop_front().expect("Insertion order should not be empty.");
+            self.tx_hashes.remove(&oldest_tx_hash);
+        }
+        true
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Mempool {
     config: MempoolConfig,
@@ -142,9 +160,17 @@ pub struct Mempool {
     // Transactions eligible for sequencing.
     tx_queue: TransactionQueue,
     state: MempoolState,
+    // Transactions dropped since the last drain, pending notification.
+    dropped_txs: Vec<DroppedTransaction>,
+    // Transactions forwarded in forward-only mode.
+    forwarded_txs: ForwardedTransactions,
 }
 
 impl Mempool {
+    pub fn new(config: MempoolConfig) -> Self {
+        Mempool { config, ..Default::default() }
+    }
+
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter(&self) -> impl Iterator<Item = &TransactionReference> {
@@ -188,6 +214,55 @@ impl Mempool {
             .collect())
     }
 
+    /// Returns up to `n_txs` transactions with the highest priority, i.e., the transactions that
+    /// are likely to be sequenced next, without removing them from the queue.
+    pub fn peek_txs(&self, n_txs: usize) -> Vec<AccountTransaction> {
+        self.iter()
+            .take(n_txs)
+            .map(|tx_reference| {
+                self.tx_pool
+                    .get_by_tx_hash(tx_reference.tx_hash)
+                    .expect("Transaction hash from queue must appear in pool.")
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the pending transactions of the given account in ascending nonce order, with their
+    /// fees and submission times.
+    pub fn account_txs(
+        &self,
+        address: ContractAddress,
+    ) -> impl Iterator<Item = PendingTransaction> + '_ {
+        self.tx_pool.account_pending_txs(address)
+    }
+
+    /// Returns the hashes of all transactions held in the mempool.
+    pub fn tx_hashes(&self) -> Vec<TransactionHash> {
+        self.tx_pool.tx_hashes().collect()
+    }
+
+    /// Returns the held transactions of the given hashes; hashes of transactions that are not held
+    /// are skipped.
+    pub fn get_txs_by_hash(&self, tx_hashes: &[TransactionHash]) -> Vec<AccountTransaction> {
+        tx_hashes
+            .iter()
+            .filter_map(|tx_hash| self.tx_pool.get_by_tx_hash(*tx_hash).ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the hash of a pending declare transaction of the given class, if there is one.
+    pub fn get_pending_declare(&self, class_hash: ClassHash) -> Option<TransactionHash> {
+        self.tx_pool.get_declare_by_class_hash(class_hash)
+    }
+
+    /// Returns the transactions dropped from the mempool since the last call, which will not be
+    /// included in a block.
+    pub fn drain_dropped_txs(&mut self) -> Vec<DroppedTransaction> {
+        std::mem::take(&mut self.dropped_txs)
+    }
+
     /// Adds a new transaction to the mempool.
     #[instrument(
         skip(self, args),
@@ -201,13 +276,21 @@ impl Mempool {
         err
     )]
     pub fn add_tx(&mut self, args: AddTransactionArgs) -> MempoolResult<()> {
-        let AddTransactionArgs { tx, account_state } = args;
+        let AddTransactionArgs { tx, account_state, declared_class_hash } = args;
         debug!("Adding transaction to mempool: {tx:#?}.");
         let tx_reference = TransactionReference::new(&tx);
+        if self.config.disabled_tx_versions.contains(&tx.version()) {
+            return Err(MempoolError::UnsupportedTxVersion { version: tx.version() });
+        }
         self.validate_incoming_tx(tx_reference)?;
+        if self.config.forward_only {
+            return self.forward_tx(&tx, account_state);
+        }
+        let tx_size = tx_size_in_bytes(&tx);
+        self.validate_capacity(tx_reference, tx_size)?;
 
         self.handle_fee_escalation(&tx)?;
-        self.tx_pool.insert(tx)?;
+        self.tx_pool.insert(tx, tx_size, declared_class_hash)?;
 
         // Align to account nonce, only if it is at least the one stored.
         let AccountState { address, nonce: incoming_account_nonce } = account_state;
@@ -220,6 +303,28 @@ impl Mempool {
         Ok(())
     }
 
+    // The transaction is only propagated to the network, by the caller. Blocks are not committed
+    // to a forward-only mempool, so the account nonces it validates against are the ones the
+    // transactions were admitted with.
+    fn forward_tx(
+        &mut self,
+        tx: &AccountTransaction,
+        account_state: AccountState,
+    ) -> MempoolResult<()> {
+        let tx_hash = tx.tx_hash();
+        // TODO: remove once declare transactions are propagated.
+        if let AccountTransaction::Declare(_) = tx {
+            return Err(MempoolError::DeclareNotForwarded { tx_hash });
+        }
+        if !self.forwarded_txs.insert(tx_hash) {
+            return Err(MempoolError::DuplicateTransaction { tx_hash });
+        }
+
+        let AccountState { address, nonce } = account_state;
+        self.state.get_or_insert(address, nonce);
+        Ok(())
+    }
+
     /// Update the mempool's internal state according to the committed block (resolves nonce gaps,
     /// updates account balances).
     #[instrument(skip(self, args), err)]
@@ -240,8 +345,12 @@ impl Mempool {
                 assert!(self.tx_queue.remove(address), "Expected to remove address from queue.");
             }
 
-            // Remove from pool.
-            self.tx_pool.remove_up_to_nonce(address, next_nonce);
+            // Remove from pool; transactions that were not committed are dropped.
+            let removed_txs = self.tx_pool.remove_up_to_nonce(address, next_nonce);
+            let stale_txs = removed_txs.into_iter().filter(|tx| !tx_hashes.contains(&tx.tx_hash));
+            for tx_reference in stale_txs {
+                self.drop_tx(tx_reference, TransactionDropReason::StaleNonce);
+            }
 
             // Maybe close nonce gap.
             if self.tx_queue.get_nonce(address).is_none() {
@@ -309,32 +418,62 @@ impl Mempool {
         Ok(())
     }
 
+    /// Rejects a transaction that would exceed the size limits of the pool or of its account. The
+    /// size of a transaction it may replace is not counted, as the replaced one is dropped.
+    fn validate_capacity(
+        &self,
+        tx_reference: TransactionReference,
+        tx_size: usize,
+    ) -> MempoolResult<()> {
+        let TransactionReference { address, nonce, .. } = tx_reference;
+        let replaced_tx_size = self
+            .tx_pool
+            .get_by_address_and_nonce(address, nonce)
+            .and_then(|existing_tx| self.tx_pool.tx_size_in_bytes(existing_tx.tx_hash))
+            .unwrap_or_default();
+
+        let pool_size = self.tx_pool.size_in_bytes() - replaced_tx_size + tx_size;
+        if pool_size > self.config.max_pool_size_in_bytes {
+            return Err(MempoolError::MempoolFull { tx_size });
+        }
+        let account_size = self.tx_pool.account_size_in_bytes(address) - replaced_tx_size + tx_size;
+        if account_size > self.config.max_account_size_in_bytes {
+            return Err(MempoolError::AccountSizeLimitExceeded {
+                address,
+                max_account_size: self.config.max_account_size_in_bytes,
+            });
+        }
+
+        Ok(())
+    }
+
     #[instrument(level = "debug", skip(self, incoming_tx), err)]
     fn handle_fee_escalation(&mut self, incoming_tx: &AccountTransaction) -> MempoolResult<()> {
         let incoming_tx_reference = TransactionReference::new(incoming_tx);
         let TransactionReference { address, nonce, .. } = incoming_tx_reference;
 
-        if !self.config.enable_fee_escalation {
-            if self.tx_pool.get_by_address_and_nonce(address, nonce).is_some() {
-                return Err(MempoolError::DuplicateNonce { address, nonce });
-            };
-
-            return Ok(());
-        }
-
         let Some(existing_tx_reference) = self.tx_pool.get_by_address_and_nonce(address, nonce)
         else {
             // Replacement irrelevant: no existing transaction with the same nonce for address.
             return Ok(());
         };
 
-        if !self.should_replace_tx(&existing_tx_reference, &incoming_tx_reference) {
+        let replacement_fee_threshold = self.replacement_fee_threshold(&existing_tx_reference);
+        let should_replace = replacement_fee_threshold.as_ref().is_some_and(|threshold| {
+            incoming_tx_reference.tip >= threshold.tip
+                && incoming_tx_reference.max_l2_gas_price >= threshold.max_l2_gas_price
+        });
+        if !should_replace {
             debug!(
-                "{existing_tx_reference} was not replaced by {incoming_tx_reference} due to
-                insufficient fee escalation."
+                "{existing_tx_reference} was not replaced by {incoming_tx_reference} due to \
+                 insufficient fee escalation."
             );
-            // TODO(Elin): consider adding a more specific error type / message.
-            return Err(MempoolError::DuplicateNonce { address, nonce });
+            return Err(MempoolError::DuplicateNonce {
+                address,
+                nonce,
+                existing_tx_hash: existing_tx_reference.tx_hash,
+                replacement_fee_threshold,
+            });
         }
 
         debug!("{existing_tx_reference} will be replaced by {incoming_tx_reference}.");
@@ -343,37 +482,47 @@ impl Mempool {
         self.tx_pool
             .remove(existing_tx_reference.tx_hash)
             .expect("Transaction hash from pool must exist.");
+        self.drop_tx(
+            existing_tx_reference,
+            TransactionDropReason::Replaced { replaced_by: incoming_tx_reference.tx_hash },
+        );
 
         Ok(())
     }
 
-    fn should_replace_tx(
+    fn drop_tx(&mut self, tx_reference: TransactionReference, reason: TransactionDropReason) {
+        debug!("{tx_reference} was dropped: {reason:?}.");
+        self.dropped_txs.push(DroppedTransaction {
+            tx_hash: tx_reference.tx_hash,
+            tip: tx_reference.tip,
+            reason,
+        });
+    }
+
+    /// Returns the minimal fee values of a transaction replacing the given one, or `None` if it
+    /// cannot be replaced: fee escalation is disabled, or the escalated values overflow.
+    fn replacement_fee_threshold(
         &self,
         existing_tx: &TransactionReference,
-        incoming_tx: &TransactionReference,
-    ) -> bool {
-        let [existing_tip, incoming_tip] =
-            [existing_tx, incoming_tx].map(|tx| u128::from(tx.tip.0));
-        let [existing_max_l2_gas_price, incoming_max_l2_gas_price] =
-            [existing_tx, incoming_tx].map(|tx| tx.max_l2_gas_price.0);
-
-        self.increased_enough(existing_tip, incoming_tip)
-            && self.increased_enough(existing_max_l2_gas_price, incoming_max_l2_gas_price)
+    ) -> Option<ReplacementFeeThreshold> {
+        if !self.config.enable_fee_escalation {
+            return None;
+        }
+
+        let tip = self.escalated_value(u128::from(existing_tx.tip.0))?;
+        let max_l2_gas_price = self.escalated_value(existing_tx.max_l2_gas_price.0)?;
+        Some(ReplacementFeeThreshold {
+            tip: Tip(u64::try_from(tip).ok()?),
+            max_l2_gas_price: GasPrice(max_l2_gas_price),
+        })
     }
 
-    fn increased_enough(&self, existing_value: u128, incoming_value: u128) -> bool {
+    fn escalated_value(&self, existing_value: u128) -> Option<u128> {
         let percentage = u128::from(self.config.fee_escalation_percentage);
-
-        let Some(escalation_qualified_value) = existing_value
+        existing_value
             .checked_mul(percentage)
             .map(|v| v / 100)
             .and_then(|increase| existing_value.checked_add(increase))
-        else {
-            // Overflow occurred during calculation; reject the transaction.
-            return false;
-        };
-
-        incoming_value >= escalation_qualified_value
     }
 }
 
@@ -409,6 +558,12 @@ impl TransactionReference {
             max_l2_gas_price: max_l2_gas_price(tx),
         }
     }
+
+    /// The tip the transaction pays per unit of L2 gas at the given L2 gas price; see
+    /// [Tip::effective].
+    pub fn effective_tip(&self, l2_gas_price: GasPrice) -> Tip {
+        self.tip.effective(self.max_l2_gas_price, l2_gas_price)
+    }
 }
 
 impl std::fmt::Display for TransactionReference {
@@ -421,3 +576,10 @@ impl std::fmt::Display for TransactionReference {
         )
     }
 }
+
+/// A transaction held in the mempool, along with the time it was added.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PendingTransaction {
+    pub tx_reference: TransactionReference,
+    pub submission_time: SystemTime,
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use mockall::predicate;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
@@ -12,16 +13,29 @@ use starknet_api::rpc_transaction::{
     RpcInvokeTransaction,
     RpcTransaction,
 };
-use starknet_api::{contract_address, nonce};
+use starknet_api::transaction::fields::Tip;
+use starknet_api::transaction::TransactionVersions;
+use starknet_api::{class_hash, contract_address, nonce, tx_hash};
+use starknet_gateway_types::communication::MockGatewayClient;
 use starknet_mempool_p2p_types::communication::MockMempoolP2pPropagatorClient;
-use starknet_mempool_types::communication::AddTransactionArgsWrapper;
-use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::AddTransactionArgs;
+use starknet_mempool_types::communication::{
+    AddTransactionArgsWrapper,
+    MempoolRequest,
+    MempoolResponse,
+};
+use starknet_mempool_types::errors::{MempoolError, ReplacementFeeThreshold};
+use starknet_mempool_types::mempool_types::{
+    AddTransactionArgs,
+    DroppedTransaction,
+    TransactionDropReason,
+};
+use starknet_sequencer_infra::component_definitions::ComponentRequestHandler;
 
 use crate::communication::MempoolCommunicationWrapper;
-use crate::mempool::{Mempool, MempoolConfig, TransactionReference};
+use crate::config::MempoolConfig;
+use crate::mempool::{Mempool, TransactionReference};
 use crate::test_utils::{add_tx, add_tx_expect_error, commit_block, get_txs_and_assert_expected};
-use crate::transaction_pool::TransactionPool;
+use crate::transaction_pool::{tx_size_in_bytes, TransactionPool};
 use crate::transaction_queue::transaction_queue_test_utils::{
     TransactionQueueContent,
     TransactionQueueContentBuilder,
@@ -63,6 +77,7 @@ impl From<MempoolContent> for Mempool {
                 .unwrap_or_default(),
             // TODO: Add implementation when needed.
             state: Default::default(),
+            dropped_txs: Default::default(),
         }
     }
 }
@@ -114,7 +129,11 @@ impl MempoolContentBuilder {
     }
 
     fn with_fee_escalation_percentage(mut self, fee_escalation_percentage: u8) -> Self {
-        self.config = MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage };
+        self.config = MempoolConfig {
+            enable_fee_escalation: true,
+            fee_escalation_percentage,
+            ..Default::default()
+        };
         self
     }
 
@@ -135,7 +154,8 @@ impl FromIterator<AccountTransaction> for TransactionPool {
     fn from_iter<T: IntoIterator<Item = AccountTransaction>>(txs: T) -> Self {
         let mut pool = Self::default();
         for tx in txs {
-            pool.insert(tx).unwrap();
+            let tx_size = tx_size_in_bytes(&tx);
+            pool.insert(tx, tx_size, None).unwrap();
         }
         pool
     }
@@ -202,6 +222,7 @@ fn add_txs_and_verify_no_replacement(
     mut mempool: Mempool,
     existing_tx: AccountTransaction,
     invalid_replacement_inputs: impl IntoIterator<Item = AddTransactionArgs>,
+    replacement_fee_threshold: Option<ReplacementFeeThreshold>,
     in_priority_queue: bool,
     in_pending_queue: bool,
 ) {
@@ -212,6 +233,8 @@ fn add_txs_and_verify_no_replacement(
             MempoolError::DuplicateNonce {
                 address: input.tx.contract_address(),
                 nonce: input.tx.nonce(),
+                existing_tx_hash: existing_tx.tx_hash(),
+                replacement_fee_threshold: replacement_fee_threshold.clone(),
             },
         );
     }
@@ -228,6 +251,7 @@ fn add_txs_and_verify_no_replacement_in_pool(
     mempool: Mempool,
     existing_tx: AccountTransaction,
     invalid_replacement_inputs: impl IntoIterator<Item = AddTransactionArgs>,
+    replacement_fee_threshold: Option<ReplacementFeeThreshold>,
 ) {
     let in_priority_queue = false;
     let in_pending_queue = false;
@@ -235,6 +259,7 @@ fn add_txs_and_verify_no_replacement_in_pool(
         mempool,
         existing_tx,
         invalid_replacement_inputs,
+        replacement_fee_threshold,
         in_priority_queue,
         in_pending_queue,
     );
@@ -284,6 +309,24 @@ fn test_get_txs_returns_by_priority(#[case] n_requested_txs: usize) {
     expected_mempool_content.assert_eq(&mempool);
 }
 
+#[rstest]
+fn test_peek_txs_does_not_remove_txs_from_queue() {
+    // Setup.
+    let tx_tip_20 = tx!(tx_hash: 1, address: "0x0", tip: 20);
+    let tx_tip_30 = tx!(tx_hash: 2, address: "0x1", tip: 30);
+
+    let queue_txs = [&tx_tip_20, &tx_tip_30].map(TransactionReference::new);
+    let pool_txs = [&tx_tip_20, &tx_tip_30].map(|tx| tx.clone());
+    let mut mempool = MempoolContentBuilder::new()
+        .with_pool(pool_txs)
+        .with_priority_queue(queue_txs)
+        .build_into_mempool();
+
+    // Test and assert.
+    assert_eq!(mempool.peek_txs(1), [tx_tip_30.clone()]);
+    get_txs_and_assert_expected(&mut mempool, 2, &[tx_tip_30, tx_tip_20]);
+}
+
 #[rstest]
 fn test_get_txs_returns_by_secondary_priority_on_tie() {
     // Setup.
@@ -432,6 +475,115 @@ fn test_add_tx_correctly_places_txs_in_queue_and_pool(mut mempool: Mempool) {
     expected_mempool_content.assert_eq(&mempool);
 }
 
+#[rstest]
+fn test_account_txs_sorted_by_nonce(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_2 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, account_nonce: 0, tip: 30);
+    let input_nonce_0 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10);
+    let input_other_address =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 40);
+    let input_nonce_1 =
+        add_tx_input!(tx_hash: 4, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 20);
+    let before_submission = SystemTime::now();
+
+    // Test.
+    for input in [&input_nonce_2, &input_nonce_0, &input_other_address, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Assert: only the account's transactions are returned, ordered by nonce.
+    let account_txs: Vec<_> = mempool.account_txs(contract_address!("0x0")).collect();
+    let expected_tx_references =
+        [&input_nonce_0.tx, &input_nonce_1.tx, &input_nonce_2.tx].map(TransactionReference::new);
+    assert_eq!(
+        account_txs.iter().map(|tx| tx.tx_reference).collect::<Vec<_>>(),
+        expected_tx_references
+    );
+    assert!(account_txs.iter().all(|tx| tx.submission_time >= before_submission));
+    assert_eq!(mempool.account_txs(contract_address!("0x2")).count(), 0);
+}
+
+#[rstest]
+fn test_add_tx_rejects_disabled_tx_version() {
+    // Setup.
+    let input = add_tx_input!(tx_hash: 1, tx_nonce: 0, account_nonce: 0);
+    let version = input.tx.version();
+    let mut mempool = Mempool::new(MempoolConfig {
+        disabled_tx_versions: TransactionVersions::new([version]),
+        ..Default::default()
+    });
+
+    // Test and assert: the transaction is not added.
+    add_tx_expect_error(&mut mempool, &input, MempoolError::UnsupportedTxVersion { version });
+    let expected_mempool_content = MempoolContentBuilder::new().with_pool([]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[test]
+fn test_add_tx_forward_only() {
+    // Setup.
+    let input = add_tx_input!(tx_hash: 1, tx_nonce: 0, account_nonce: 0);
+    let mut mempool = Mempool::new(MempoolConfig { forward_only: true, ..Default::default() });
+
+    // Test and assert: the transaction is admitted, but not kept.
+    add_tx(&mut mempool, &input);
+    let expected_mempool_content = MempoolContentBuilder::new().with_pool([]).build();
+    expected_mempool_content.assert_eq(&mempool);
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![]);
+
+    // Test and assert: forwarded transactions are still validated.
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::DuplicateTransaction { tx_hash: input.tx.tx_hash() },
+    );
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 2, tx_nonce: 1, account_nonce: 1));
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 3, tx_nonce: 0, account_nonce: 1),
+        MempoolError::NonceTooOld { address: contract_address!("0x0"), nonce: nonce!(0) },
+    );
+}
+
+#[test]
+fn test_add_tx_rejects_txs_exceeding_size_limits() {
+    // Setup: the transactions are of the same size; the pool has room for two of them, and an
+    // account for one.
+    let input = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let same_account_input =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let other_account_input =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    let third_account_input =
+        add_tx_input!(tx_hash: 4, address: "0x2", tx_nonce: 0, account_nonce: 0);
+    let tx_size = tx_size_in_bytes(&input.tx);
+    let mut mempool = Mempool::new(MempoolConfig {
+        max_pool_size_in_bytes: 2 * tx_size,
+        max_account_size_in_bytes: tx_size,
+        ..Default::default()
+    });
+
+    // Test.
+    add_tx(&mut mempool, &input);
+    add_tx_expect_error(
+        &mut mempool,
+        &same_account_input,
+        MempoolError::AccountSizeLimitExceeded {
+            address: contract_address!("0x0"),
+            max_account_size: tx_size,
+        },
+    );
+    add_tx(&mut mempool, &other_account_input);
+    add_tx_expect_error(&mut mempool, &third_account_input, MempoolError::MempoolFull { tx_size });
+
+    // Assert: only the transactions within the limits were added.
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool([input.tx, other_account_input.tx]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
 // TODO(Elin): reconsider this test in a more realistic scenario.
 #[rstest]
 fn test_add_tx_rejects_duplicate_tx_hash(mut mempool: Mempool) {
@@ -457,7 +609,12 @@ fn test_add_tx_rejects_duplicate_tx_hash(mut mempool: Mempool) {
 
 #[rstest]
 #[case::lower_nonce(0, MempoolError::NonceTooOld { address: contract_address!("0x0"), nonce: nonce!(0) })]
-#[case::equal_nonce(1, MempoolError::DuplicateNonce { address: contract_address!("0x0"), nonce: nonce!(1) })]
+#[case::equal_nonce(1, MempoolError::DuplicateNonce {
+    address: contract_address!("0x0"),
+    nonce: nonce!(1),
+    existing_tx_hash: tx_hash!(1),
+    replacement_fee_threshold: None,
+})]
 fn test_add_tx_rejects_tx_of_queued_nonce(
     #[case] tx_nonce: u64,
     #[case] expected_error: MempoolError,
@@ -586,6 +743,51 @@ fn test_commit_block_includes_all_proposed_txs() {
     expected_mempool_content.assert_eq(&mempool);
 }
 
+#[rstest]
+fn test_commit_block_drops_txs_of_committed_nonces() {
+    // Setup.
+    let tx_nonce_3 = tx!(tx_hash: 1, address: "0x0", tx_nonce: 3);
+    let tx_nonce_4 = tx!(tx_hash: 2, address: "0x0", tx_nonce: 4, tip: 50);
+    let mut mempool =
+        MempoolContentBuilder::new().with_pool([tx_nonce_3, tx_nonce_4]).build_into_mempool();
+
+    // Test: nonce 4 was used by a transaction unknown to the mempool.
+    commit_block(&mut mempool, [("0x0", 5)], [1]);
+
+    // Assert.
+    let expected_dropped_tx = DroppedTransaction {
+        tx_hash: tx_hash!(2),
+        tip: Tip(50),
+        reason: TransactionDropReason::StaleNonce,
+    };
+    assert_eq!(mempool.drain_dropped_txs(), [expected_dropped_tx]);
+    assert!(mempool.drain_dropped_txs().is_empty());
+}
+
+#[rstest]
+fn test_pending_declares_indexed_by_given_class_hash(mut mempool: Mempool) {
+    // Setup.
+    let declared_class_hash = Some(class_hash!(7));
+    let first_declare = AddTransactionArgs {
+        declared_class_hash,
+        ..add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0)
+    };
+    let second_declare = AddTransactionArgs {
+        declared_class_hash,
+        ..add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0)
+    };
+    for input in [&first_declare, &second_declare] {
+        add_tx(&mut mempool, input);
+    }
+    assert_eq!(mempool.get_pending_declare(class_hash!(7)), Some(tx_hash!(1)));
+
+    // Test and assert: the remaining declare of the class is indexed.
+    commit_block(&mut mempool, [("0x0", 1)], [1]);
+    assert_eq!(mempool.get_pending_declare(class_hash!(7)), Some(tx_hash!(2)));
+    commit_block(&mut mempool, [("0x1", 1)], [2]);
+    assert_eq!(mempool.get_pending_declare(class_hash!(7)), None);
+}
+
 // Fee escalation tests.
 
 #[rstest]
@@ -627,6 +829,28 @@ fn test_fee_escalation_valid_replacement(
     }
 }
 
+#[rstest]
+fn test_fee_escalation_replacement_drops_existing_tx() {
+    // Setup.
+    let existing_tx = tx!(tx_hash: 1, tip: 90, max_l2_gas_price: 90);
+    let mut mempool = MempoolContentBuilder::new()
+        .with_pool([existing_tx])
+        .with_fee_escalation_percentage(10)
+        .build_into_mempool();
+
+    // Test.
+    let valid_replacement_input = add_tx_input!(tx_hash: 2, tip: 100, max_l2_gas_price: 100);
+    add_tx(&mut mempool, &valid_replacement_input);
+
+    // Assert.
+    let expected_dropped_tx = DroppedTransaction {
+        tx_hash: tx_hash!(1),
+        tip: Tip(90),
+        reason: TransactionDropReason::Replaced { replaced_by: tx_hash!(2) },
+    };
+    assert_eq!(mempool.drain_dropped_txs(), [expected_dropped_tx]);
+}
+
 #[rstest]
 #[case::pool(false, false)]
 #[case::pool_and_priority_queue(true, false)]
@@ -654,10 +878,13 @@ fn test_fee_escalation_invalid_replacement(
     // Test and assert.
     let invalid_replacement_inputs =
         [input_not_enough_tip, input_not_enough_gas_price, input_not_enough_both];
+    let replacement_fee_threshold =
+        ReplacementFeeThreshold { tip: Tip(110), max_l2_gas_price: GasPrice(110) };
     add_txs_and_verify_no_replacement(
         mempool,
         existing_tx,
         invalid_replacement_inputs,
+        Some(replacement_fee_threshold),
         in_priority_queue,
         in_pending_queue,
     );
@@ -720,6 +947,7 @@ fn test_fee_escalation_invalid_replacement_overflow_gracefully_handled() {
             mempool,
             existing_tx,
             [invalid_replacement_input],
+            None,
         );
     }
 
@@ -734,7 +962,12 @@ fn test_fee_escalation_invalid_replacement_overflow_gracefully_handled() {
 
     // Test and assert: overflow gracefully handled.
     let invalid_replacement_input = add_tx_input!(tip: u64::MAX, max_l2_gas_price: u128::MAX);
-    add_txs_and_verify_no_replacement_in_pool(mempool, existing_tx, [invalid_replacement_input]);
+    add_txs_and_verify_no_replacement_in_pool(
+        mempool,
+        existing_tx,
+        [invalid_replacement_input],
+        None,
+    );
 }
 
 // `update_gas_price_threshold` tests.
@@ -791,13 +1024,40 @@ fn test_update_gas_price_threshold_decreases_threshold() {
     expected_mempool_content.assert_eq(&mempool);
 }
 
+#[rstest]
+fn test_update_gas_price_threshold_reorders_by_effective_tip() {
+    // Setup.
+    let [tx_high_tip, tx_high_gas] = [
+        &tx!(tx_hash: 0, address: "0x0", tx_nonce: 0, tip: 50, max_l2_gas_price: 150),
+        &tx!(tx_hash: 1, address: "0x1", tx_nonce: 0, tip: 20, max_l2_gas_price: 200),
+    ]
+    .map(TransactionReference::new);
+
+    let mut mempool: Mempool = MempoolContentBuilder::new()
+        .with_priority_queue([tx_high_tip, tx_high_gas])
+        .with_gas_price_threshold(100)
+        .build()
+        .into();
+
+    // Test.
+    mempool.update_gas_price_threshold(GasPrice(140));
+
+    // Assert: the tip of the first transaction is capped by its max L2 gas price.
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_priority_queue([tx_high_gas, tx_high_tip]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_new_tx_sent_to_p2p(mempool: Mempool) {
     // add_tx_input! creates an Invoke Transaction
     let tx_args = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, account_nonce: 2);
-    let propagateor_args =
-        AddTransactionArgsWrapper { args: tx_args.clone(), p2p_message_metadata: None };
+    let propagateor_args = AddTransactionArgsWrapper {
+        args: tx_args.clone(),
+        p2p_message_metadata: None,
+        reconciled: false,
+    };
     // TODO: use regular conversion once we have a compiler component
     let rpc_tx = match tx_args.tx {
         AccountTransaction::Declare(_declare_tx) => {
@@ -819,8 +1079,11 @@ async fn test_new_tx_sent_to_p2p(mempool: Mempool) {
         .times(1)
         .with(predicate::eq(rpc_tx))
         .returning(|_| Ok(()));
-    let mut mempool_wrapper =
-        MempoolCommunicationWrapper::new(mempool, Arc::new(mock_mempool_p2p_propagator_client));
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(mock_mempool_p2p_propagator_client),
+        Arc::new(MockGatewayClient::new()),
+    );
 
     mempool_wrapper.add_tx(propagateor_args).await.unwrap();
 }
@@ -834,6 +1097,7 @@ async fn test_propagated_tx_sent_to_p2p(mempool: Mempool) {
     let propagated_args = AddTransactionArgsWrapper {
         args: tx_args.clone(),
         p2p_message_metadata: Some(expected_message_metadata.clone()),
+        reconciled: false,
     };
 
     let mut mock_mempool_p2p_propagator_client = MockMempoolP2pPropagatorClient::new();
@@ -843,8 +1107,61 @@ async fn test_propagated_tx_sent_to_p2p(mempool: Mempool) {
         .with(predicate::eq(expected_message_metadata.clone()))
         .returning(|_| Ok(()));
 
-    let mut mempool_wrapper =
-        MempoolCommunicationWrapper::new(mempool, Arc::new(mock_mempool_p2p_propagator_client));
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(mock_mempool_p2p_propagator_client),
+        Arc::new(MockGatewayClient::new()),
+    );
 
     mempool_wrapper.add_tx(propagated_args).await.unwrap();
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_reconciled_tx_not_sent_to_p2p(mempool: Mempool) {
+    let tx_args = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, account_nonce: 2);
+    let reconciled_args =
+        AddTransactionArgsWrapper { args: tx_args, p2p_message_metadata: None, reconciled: true };
+
+    // The propagator client expects no calls.
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(MockMempoolP2pPropagatorClient::new()),
+        Arc::new(MockGatewayClient::new()),
+    );
+
+    mempool_wrapper.add_tx(reconciled_args).await.unwrap();
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_txs_for_reconciliation(mut mempool: Mempool) {
+    let tx_args = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, account_nonce: 2);
+    mempool.add_tx(tx_args.clone()).unwrap();
+    let AccountTransaction::Invoke(invoke_tx) = tx_args.tx else {
+        panic!("add_tx_input! creates an Invoke Transaction.");
+    };
+    let rpc_tx = RpcTransaction::Invoke(RpcInvokeTransaction::V3(invoke_tx.into()));
+
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(MockMempoolP2pPropagatorClient::new()),
+        Arc::new(MockGatewayClient::new()),
+    );
+
+    let MempoolResponse::GetTransactionHashes(tx_hashes) =
+        mempool_wrapper.handle_request(MempoolRequest::GetTransactionHashes).await
+    else {
+        panic!("Unexpected response.");
+    };
+    assert_eq!(tx_hashes.unwrap(), vec![tx_hash!(1)]);
+
+    // Transactions that are not held are skipped.
+    let request = MempoolRequest::GetRpcTransactions(vec![tx_hash!(1), tx_hash!(2)]);
+    let MempoolResponse::GetRpcTransactions(rpc_txs) =
+        mempool_wrapper.handle_request(request).await
+    else {
+        panic!("Unexpected response.");
+    };
+    assert_eq!(rpc_txs.unwrap(), vec![rpc_tx]);
+}
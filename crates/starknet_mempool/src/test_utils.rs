@@ -112,7 +112,7 @@ macro_rules! add_tx_input {
         let account_nonce = nonce!($account_nonce);
         let account_state = AccountState { address, nonce: account_nonce };
 
-        AddTransactionArgs { tx, account_state }
+        AddTransactionArgs { tx, account_state, declared_class_hash: None }
     }};
     (
         tx_hash: $tx_hash:expr,
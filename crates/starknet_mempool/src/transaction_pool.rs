@@ -1,12 +1,13 @@
 use std::collections::{hash_map, BTreeMap, HashMap};
+use std::time::SystemTime;
 
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::transaction::TransactionHash;
 use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{AccountState, MempoolResult};
 
-use crate::mempool::TransactionReference;
+use crate::mempool::{PendingTransaction, TransactionReference};
 use crate::utils::try_increment_nonce;
 
 type HashToTransaction = HashMap<TransactionHash, AccountTransaction>;
@@ -15,18 +16,31 @@ type HashToTransaction = HashMap<TransactionHash, AccountTransaction>;
 /// Invariant: both data structures are consistent regarding the existence of transactions:
 /// A transaction appears in one if and only if it appears in the other.
 /// No duplicate transactions appear in the pool.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Default)]
 pub struct TransactionPool {
     // Holds the complete transaction objects; it should be the sole entity that does so.
     tx_pool: HashToTransaction,
+    // The time each transaction was added to the pool.
+    submission_times: HashMap<TransactionHash, SystemTime>,
     // Transactions organized by account address, sorted by ascending nonce values.
     txs_by_account: AccountTransactionIndex,
+    // The classes declared by the declare transactions.
+    declared_classes: HashMap<TransactionHash, ClassHash>,
+    // Declare transactions, by the class they declare.
+    declares_by_class_hash: HashMap<ClassHash, TransactionHash>,
     // Tracks the capacity of the pool.
     capacity: PoolCapacity,
 }
 
 impl TransactionPool {
-    pub fn insert(&mut self, tx: AccountTransaction) -> MempoolResult<()> {
+    /// Inserts a transaction of the given serialized size; see [`tx_size_in_bytes`]. Declare
+    /// transactions are indexed by the given class they declare.
+    pub fn insert(
+        &mut self,
+        tx: AccountTransaction,
+        tx_size: usize,
+        declared_class_hash: Option<ClassHash>,
+    ) -> MempoolResult<()> {
         let tx_reference = TransactionReference::new(&tx);
         let tx_hash = tx_reference.tx_hash;
 
@@ -47,7 +61,13 @@ impl TransactionPool {
             )
         };
 
-        self.capacity.add();
+        if let Some(class_hash) = declared_class_hash {
+            self.declared_classes.insert(tx_hash, class_hash);
+            self.declares_by_class_hash.entry(class_hash).or_insert(tx_hash);
+        }
+        self.submission_times.insert(tx_hash, SystemTime::now());
+
+        self.capacity.add(tx_reference, tx_size);
 
         Ok(())
     }
@@ -65,24 +85,37 @@ impl TransactionPool {
             )
         });
 
-        self.capacity.remove();
+        self.remove_declare(tx_hash);
+        self.submission_times.remove(&tx_hash);
+        self.capacity.remove(TransactionReference::new(&tx));
 
         Ok(tx)
     }
 
-    pub fn remove_up_to_nonce(&mut self, address: ContractAddress, nonce: Nonce) {
+    /// Removes all transactions of the given account with a lower nonce; returns the removed
+    /// transactions.
+    pub fn remove_up_to_nonce(
+        &mut self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Vec<TransactionReference> {
         let removed_txs = self.txs_by_account.remove_up_to_nonce(address, nonce);
 
-        for TransactionReference { tx_hash, .. } in removed_txs {
-            self.tx_pool.remove(&tx_hash).unwrap_or_else(|| {
+        for tx_reference in &removed_txs {
+            let tx_hash = &tx_reference.tx_hash;
+            self.tx_pool.remove(tx_hash).unwrap_or_else(|| {
                 panic!(
                     "Transaction pool consistency error: transaction with hash {tx_hash} appears
                     in account mapping, but does not appear in the main mapping"
                 );
             });
 
-            self.capacity.remove();
+            self.remove_declare(*tx_hash);
+            self.submission_times.remove(tx_hash);
+            self.capacity.remove(*tx_reference);
         }
+
+        removed_txs
     }
 
     pub fn account_txs_sorted_by_nonce(
@@ -92,6 +125,21 @@ impl TransactionPool {
         self.txs_by_account.account_txs_sorted_by_nonce(address)
     }
 
+    /// Returns the pending transactions of the given account in ascending nonce order, along with
+    /// their submission times.
+    pub fn account_pending_txs(
+        &self,
+        address: ContractAddress,
+    ) -> impl Iterator<Item = PendingTransaction> + '_ {
+        self.account_txs_sorted_by_nonce(address).map(|&tx_reference| PendingTransaction {
+            tx_reference,
+            submission_time: *self
+                .submission_times
+                .get(&tx_reference.tx_hash)
+                .expect("Transaction from account mapping must have a submission time."),
+        })
+    }
+
     pub fn get_by_tx_hash(&self, tx_hash: TransactionHash) -> MempoolResult<&AccountTransaction> {
         self.tx_pool.get(&tx_hash).ok_or(MempoolError::TransactionNotFound { tx_hash })
     }
@@ -113,9 +161,70 @@ impl TransactionPool {
         Ok(self.get_by_address_and_nonce(address, next_nonce))
     }
 
+    pub fn tx_hashes(&self) -> impl Iterator<Item = TransactionHash> + '_ {
+        self.tx_pool.keys().copied()
+    }
+
+    pub fn get_declare_by_class_hash(&self, class_hash: ClassHash) -> Option<TransactionHash> {
+        self.declares_by_class_hash.get(&class_hash).copied()
+    }
+
+    /// The total serialized size of the transactions in the pool, in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.capacity.n_bytes
+    }
+
+    /// The total serialized size of the transactions of the given account, in bytes.
+    pub fn account_size_in_bytes(&self, address: ContractAddress) -> usize {
+        self.capacity.account_n_bytes.get(&address).copied().unwrap_or_default()
+    }
+
+    /// The serialized size of the given transaction, if it is in the pool.
+    pub fn tx_size_in_bytes(&self, tx_hash: TransactionHash) -> Option<usize> {
+        self.capacity.tx_sizes.get(&tx_hash).copied()
+    }
+
     pub fn _contains_account(&self, address: ContractAddress) -> bool {
         self.txs_by_account._contains(address)
     }
+
+    fn remove_declare(&mut self, tx_hash: TransactionHash) {
+        let Some(class_hash) = self.declared_classes.remove(&tx_hash) else {
+            return;
+        };
+        if self.declares_by_class_hash.get(&class_hash) != Some(&tx_hash) {
+            return;
+        }
+
+        // Index another pending declare of the same class, if there is one.
+        let other_declare_tx_hash = self
+            .declared_classes
+            .iter()
+            .find(|(_, other_class_hash)| **other_class_hash == class_hash)
+            .map(|(other_tx_hash, _)| *other_tx_hash);
+        match other_declare_tx_hash {
+            Some(other_tx_hash) => self.declares_by_class_hash.insert(class_hash, other_tx_hash),
+            None => self.declares_by_class_hash.remove(&class_hash),
+        };
+    }
+}
+
+// Submission times are not compared, as they depend on when the transactions were inserted.
+impl PartialEq for TransactionPool {
+    fn eq(&self, other: &Self) -> bool {
+        self.tx_pool == other.tx_pool
+            && self.txs_by_account == other.txs_by_account
+            && self.declared_classes == other.declared_classes
+            && self.declares_by_class_hash == other.declares_by_class_hash
+            && self.capacity == other.capacity
+    }
+}
+
+impl Eq for TransactionPool {}
+
+/// The serialized size of a transaction, which dominates the memory it takes in the pool.
+pub fn tx_size_in_bytes(tx: &AccountTransaction) -> usize {
+    serde_json::to_vec(tx).expect("Transactions should be serializable.").len()
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
@@ -180,16 +289,35 @@ impl AccountTransactionIndex {
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct PoolCapacity {
     n_txs: usize,
-    // TODO(Ayelet): Add size tracking.
+    n_bytes: usize,
+    account_n_bytes: HashMap<ContractAddress, usize>,
+    tx_sizes: HashMap<TransactionHash, usize>,
 }
 
 impl PoolCapacity {
-    fn add(&mut self) {
+    fn add(&mut self, tx_reference: TransactionReference, tx_size: usize) {
         self.n_txs += 1;
+        self.n_bytes += tx_size;
+        *self.account_n_bytes.entry(tx_reference.address).or_default() += tx_size;
+        self.tx_sizes.insert(tx_reference.tx_hash, tx_size);
     }
 
-    fn remove(&mut self) {
+    fn remove(&mut self, tx_reference: TransactionReference) {
         self.n_txs =
             self.n_txs.checked_sub(1).expect("Underflow: Cannot subtract from an empty pool.");
+        let tx_size = self
+            .tx_sizes
+            .remove(&tx_reference.tx_hash)
+            .expect("Removed transaction must have a tracked size.");
+        self.n_bytes -= tx_size;
+        let hash_map::Entry::Occupied(mut account_n_bytes) =
+            self.account_n_bytes.entry(tx_reference.address)
+        else {
+            panic!("Removed transaction must have a tracked account size.");
+        };
+        *account_n_bytes.get_mut() -= tx_size;
+        if *account_n_bytes.get() == 0 {
+            account_n_bytes.remove();
+        }
     }
 }
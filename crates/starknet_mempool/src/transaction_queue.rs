@@ -18,12 +18,15 @@ pub mod transaction_queue_test_utils;
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct TransactionQueue {
     gas_price_threshold: GasPrice,
-    // Transactions with gas price above gas price threshold (sorted by tip).
+    // Transactions with gas price above gas price threshold (sorted by effective tip).
     priority_queue: BTreeSet<PriorityTransaction>,
     // Transactions with gas price below gas price threshold (sorted by price).
     pending_queue: BTreeSet<PendingTransaction>,
     // Set of account addresses for efficient existence checks.
     address_to_tx: HashMap<ContractAddress, TransactionReference>,
+    // The transactions by the threshold above which their tip is capped by their max L2 gas price;
+    // only their effective tips change with the threshold (sorted by this threshold).
+    tip_cap_thresholds: BTreeSet<(GasPrice, ContractAddress)>,
 }
 
 impl TransactionQueue {
@@ -36,12 +39,14 @@ impl TransactionQueue {
             "Only a single transaction from the same contract class can be in the mempool at a \
              time."
         );
+        self.tip_cap_thresholds.insert((tip_cap_threshold(&tx_reference), tx_reference.address));
 
         let new_tx_successfully_inserted =
             if tx_reference.max_l2_gas_price < self.gas_price_threshold {
                 self.pending_queue.insert(tx_reference.into())
             } else {
-                self.priority_queue.insert(tx_reference.into())
+                self.priority_queue
+                    .insert(PriorityTransaction::new(tx_reference, self.gas_price_threshold))
             };
         assert!(
             new_tx_successfully_inserted,
@@ -52,9 +57,10 @@ impl TransactionQueue {
     // TODO(gilad): remove collect, if returning an iterator is possible.
     pub fn pop_ready_chunk(&mut self, n_txs: usize) -> Vec<TransactionReference> {
         let txs: Vec<TransactionReference> =
-            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.0)).collect();
+            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.tx)).collect();
         for tx in &txs {
             self.address_to_tx.remove(&tx.address);
+            self.tip_cap_thresholds.remove(&(tip_cap_threshold(tx), tx.address));
         }
 
         txs
@@ -63,7 +69,7 @@ impl TransactionQueue {
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter_over_ready_txs(&self) -> impl Iterator<Item = &TransactionReference> {
-        self.priority_queue.iter().rev().map(|tx| &tx.0)
+        self.priority_queue.iter().rev().map(|tx| &tx.tx)
     }
 
     pub fn get_nonce(&self, address: ContractAddress) -> Option<Nonce> {
@@ -76,9 +82,10 @@ impl TransactionQueue {
         let Some(tx_reference) = self.address_to_tx.remove(&address) else {
             return false;
         };
+        self.tip_cap_thresholds.remove(&(tip_cap_threshold(&tx_reference), address));
 
-        self.priority_queue.remove(&tx_reference.into())
-            || self.pending_queue.remove(&tx_reference.into())
+        let priority_tx = PriorityTransaction::new(tx_reference, self.gas_price_threshold);
+        self.priority_queue.remove(&priority_tx) || self.pending_queue.remove(&tx_reference.into())
     }
 
     pub fn has_ready_txs(&self) -> bool {
@@ -86,13 +93,32 @@ impl TransactionQueue {
     }
 
     pub fn update_gas_price_threshold(&mut self, threshold: GasPrice) {
-        match threshold.cmp(&self.gas_price_threshold) {
+        let previous_threshold = self.gas_price_threshold;
+        match threshold.cmp(&previous_threshold) {
             Ordering::Less => self.promote_txs_to_priority(threshold),
             Ordering::Greater => self.demote_txs_to_pending(threshold),
-            Ordering::Equal => {}
+            Ordering::Equal => return,
         }
 
         self.gas_price_threshold = threshold;
+        self.reorder_capped_tips(previous_threshold, threshold);
+    }
+
+    // Reorders the prioritized transactions whose effective tip changed with the threshold: those
+    // whose tip is capped under either threshold. Transactions promoted by the update are already
+    // ordered by the new threshold.
+    fn reorder_capped_tips(&mut self, previous_threshold: GasPrice, threshold: GasPrice) {
+        let split_tx = (previous_threshold.max(threshold), ContractAddress::default());
+        for (_, address) in self.tip_cap_thresholds.range(..split_tx) {
+            let tx_reference = self.address_to_tx[address];
+            let previous_priority_tx = PriorityTransaction::new(tx_reference, previous_threshold);
+            let priority_tx = PriorityTransaction::new(tx_reference, threshold);
+            if previous_priority_tx != priority_tx
+                && self.priority_queue.remove(&previous_priority_tx)
+            {
+                self.priority_queue.insert(priority_tx);
+            }
+        }
     }
 
     fn promote_txs_to_priority(&mut self, threshold: GasPrice) {
@@ -111,7 +137,8 @@ impl TransactionQueue {
         // `tmp_split_tx`.
         // Note: extend will reorder transactions by `Tip` during insertion, despite them being
         // initially ordered by fee.
-        self.priority_queue.extend(txs_over_threshold.map(|tx| PriorityTransaction::from(tx.0)));
+        self.priority_queue
+            .extend(txs_over_threshold.map(|tx| PriorityTransaction::new(tx.0, threshold)));
     }
 
     fn demote_txs_to_pending(&mut self, threshold: GasPrice) {
@@ -119,7 +146,7 @@ impl TransactionQueue {
 
         // Remove all transactions from the priority queue that are below the threshold.
         for priority_tx in &self.priority_queue {
-            if priority_tx.max_l2_gas_price < threshold {
+            if priority_tx.tx.max_l2_gas_price < threshold {
                 txs_to_remove.push(*priority_tx);
             }
         }
@@ -127,10 +154,15 @@ impl TransactionQueue {
         for tx in &txs_to_remove {
             self.priority_queue.remove(tx);
         }
-        self.pending_queue.extend(txs_to_remove.iter().map(|tx| PendingTransaction::from(tx.0)));
+        self.pending_queue.extend(txs_to_remove.iter().map(|tx| PendingTransaction::from(tx.tx)));
     }
 }
 
+// The gas price threshold above which the tip of the transaction is capped by its max L2 gas price.
+fn tip_cap_threshold(tx_reference: &TransactionReference) -> GasPrice {
+    GasPrice(tx_reference.max_l2_gas_price.0.saturating_sub(tx_reference.tip.0.into()))
+}
+
 /// Encapsulates a transaction reference to assess its order (i.e., gas price).
 #[derive(Clone, Copy, Debug, derive_more::Deref, derive_more::From)]
 struct PendingTransaction(pub TransactionReference);
@@ -163,13 +195,23 @@ impl PartialOrd for PendingTransaction {
 }
 
 /// This struct behaves similarly to `PendingTransaction`, encapsulating a transaction reference
-/// to assess its order (i.e., tip); see its documentation for more details.
-#[derive(Clone, Copy, Debug, derive_more::Deref, derive_more::From)]
-struct PriorityTransaction(pub TransactionReference);
+/// to assess its order (i.e., effective tip); see its documentation for more details.
+#[derive(Clone, Copy, Debug)]
+struct PriorityTransaction {
+    tx: TransactionReference,
+    // The tip the transaction pays when the L2 gas price is at the gas price threshold.
+    effective_tip: Tip,
+}
+
+impl PriorityTransaction {
+    fn new(tx: TransactionReference, gas_price_threshold: GasPrice) -> Self {
+        Self { tx, effective_tip: tx.effective_tip(gas_price_threshold) }
+    }
+}
 
 impl PartialEq for PriorityTransaction {
     fn eq(&self, other: &PriorityTransaction) -> bool {
-        self.tip == other.tip && self.tx_hash == other.tx_hash
+        self.effective_tip == other.effective_tip && self.tx.tx_hash == other.tx.tx_hash
     }
 }
 
@@ -177,7 +219,9 @@ impl Eq for PriorityTransaction {}
 
 impl Ord for PriorityTransaction {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.tip.cmp(&other.tip).then_with(|| self.tx_hash.cmp(&other.tx_hash))
+        self.effective_tip
+            .cmp(&other.effective_tip)
+            .then_with(|| self.tx.tx_hash.cmp(&other.tx.tx_hash))
     }
 }
 
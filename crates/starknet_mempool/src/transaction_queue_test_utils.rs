@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use starknet_api::block::GasPrice;
 
 use crate::mempool::TransactionReference;
-use crate::transaction_queue::{PendingTransaction, PriorityTransaction, TransactionQueue};
-
-type OptionalPriorityTransactions = Option<Vec<PriorityTransaction>>;
+use crate::transaction_queue::{
+    tip_cap_threshold,
+    PendingTransaction,
+    PriorityTransaction,
+    TransactionQueue,
+};
+
+type OptionalPriorityTransactions = Option<Vec<TransactionReference>>;
 type OptionalPendingTransactions = Option<Vec<PendingTransaction>>;
 
 /// Represents the internal content of the transaction queue.
@@ -22,7 +27,7 @@ impl TransactionQueueContent {
     #[track_caller]
     pub fn assert_eq(&self, tx_queue: &TransactionQueue) {
         if let Some(priority_queue) = &self.priority_queue {
-            let expected_priority_txs: Vec<_> = priority_queue.iter().map(|tx| &tx.0).collect();
+            let expected_priority_txs: Vec<_> = priority_queue.iter().collect();
             let actual_priority_txs: Vec<_> = tx_queue.iter_over_ready_txs().collect();
             assert_eq!(actual_priority_txs, expected_priority_txs);
         }
@@ -44,19 +49,25 @@ impl TransactionQueueContent {
         let tx_references = pending_queue
             .iter()
             .map(|pending_tx| pending_tx.0)
-            .chain(priority_queue.iter().map(|priority_tx| priority_tx.0));
+            .chain(priority_queue.iter().copied());
         let mut address_to_tx = HashMap::new();
+        let mut tip_cap_thresholds = BTreeSet::new();
         for tx_ref in tx_references {
             let address = tx_ref.address;
             if address_to_tx.insert(address, tx_ref).is_some() {
                 panic!("Duplicate address: {address}; queues must be mutually exclusive.");
             }
+            tip_cap_thresholds.insert((tip_cap_threshold(&tx_ref), address));
         }
 
         TransactionQueue {
-            priority_queue: priority_queue.into_iter().collect(),
+            priority_queue: priority_queue
+                .into_iter()
+                .map(|tx| PriorityTransaction::new(tx, gas_price_threshold))
+                .collect(),
             pending_queue: pending_queue.into_iter().collect(),
             address_to_tx,
+            tip_cap_thresholds,
             gas_price_threshold,
         }
     }
@@ -74,8 +85,7 @@ impl TransactionQueueContentBuilder {
     where
         P: IntoIterator<Item = TransactionReference>,
     {
-        self.priority_queue =
-            Some(priority_txs.into_iter().map(PriorityTransaction::from).collect());
+        self.priority_queue = Some(priority_txs.into_iter().collect());
         self
     }
 
@@ -1,6 +1,8 @@
+use mempool_test_utils::starknet_api_test_utils::VALID_L2_GAS_MAX_PRICE_PER_UNIT;
 use rstest::{fixture, rstest};
 use starknet_api::block::GasPrice;
-use starknet_api::{contract_address, nonce};
+use starknet_api::transaction::fields::Tip;
+use starknet_api::{contract_address, nonce, tx_hash};
 use starknet_mempool::add_tx_input;
 use starknet_mempool::mempool::Mempool;
 use starknet_mempool::test_utils::{
@@ -9,7 +11,7 @@ use starknet_mempool::test_utils::{
     commit_block,
     get_txs_and_assert_expected,
 };
-use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::errors::{MempoolError, ReplacementFeeThreshold};
 
 // Fixtures.
 
@@ -96,7 +98,15 @@ fn test_add_same_nonce_tx_after_previous_not_included_in_block(mut mempool: Memp
     add_tx_expect_error(
         &mut mempool,
         &tx_nonce_4_account_nonce_4,
-        MempoolError::DuplicateNonce { address: contract_address!("0x0"), nonce: nonce!(4) },
+        MempoolError::DuplicateNonce {
+            address: contract_address!("0x0"),
+            nonce: nonce!(4),
+            existing_tx_hash: tx_hash!(2),
+            replacement_fee_threshold: Some(ReplacementFeeThreshold {
+                tip: Tip(0),
+                max_l2_gas_price: GasPrice(VALID_L2_GAS_MAX_PRICE_PER_UNIT * 11 / 10),
+            }),
+        },
     );
 
     get_txs_and_assert_expected(
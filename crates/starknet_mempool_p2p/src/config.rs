@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 
+use papyrus_config::converters::deserialize_seconds_to_duration;
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_network::NetworkConfig;
@@ -11,11 +13,16 @@ pub struct MempoolP2pConfig {
     #[validate]
     pub network_config: NetworkConfig,
     pub network_buffer_size: usize,
+    pub reconciliation_config: ReconciliationConfig,
 }
 
 impl Default for MempoolP2pConfig {
     fn default() -> Self {
-        Self { network_config: NetworkConfig::default(), network_buffer_size: 10000 }
+        Self {
+            network_config: NetworkConfig::default(),
+            network_buffer_size: 10000,
+            reconciliation_config: ReconciliationConfig::default(),
+        }
     }
 }
 
@@ -29,9 +36,44 @@ impl SerializeConfig for MempoolP2pConfig {
                 ParamPrivacyInput::Public,
             )]),
             append_sub_config_name(self.network_config.dump(), "network_config"),
+            append_sub_config_name(self.reconciliation_config.dump(), "reconciliation_config"),
         ]
         .into_iter()
         .flatten()
         .collect()
     }
 }
+
+/// Configuration of the periodic mempool reconciliation between peers, which recovers
+/// transactions missed by the gossip propagation.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
+pub struct ReconciliationConfig {
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub interval: Duration,
+    pub max_reconciled_txs: usize,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(30), max_reconciled_txs: 100 }
+    }
+}
+
+impl SerializeConfig for ReconciliationConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "interval",
+                &self.interval.as_secs(),
+                "Time in seconds between sending the mempool summary to a peer.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_reconciled_txs",
+                &self.max_reconciled_txs,
+                "Maximal number of transactions sent in response to a single peer summary.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
@@ -1,21 +1,25 @@
 pub mod config;
 pub mod propagator;
+pub mod reconciliation;
 pub mod runner;
 
 use futures::FutureExt;
 use papyrus_network::gossipsub_impl::Topic;
 use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkManager};
 use starknet_gateway_types::communication::SharedGatewayClient;
+use starknet_mempool_types::communication::SharedMempoolClient;
 
 use crate::config::MempoolP2pConfig;
 use crate::propagator::MempoolP2pPropagator;
 use crate::runner::MempoolP2pRunner;
 
 pub const MEMPOOL_TOPIC: &str = "starknet_mempool_transaction_propagation/0.1.0";
+pub const MEMPOOL_RECONCILIATION_PROTOCOL: &str = "/starknet/mempool_reconciliation/0.1.0";
 
 pub fn create_p2p_propagator_and_runner(
     mempool_p2p_config: MempoolP2pConfig,
     gateway_client: SharedGatewayClient,
+    mempool_client: SharedMempoolClient,
 ) -> (MempoolP2pPropagator, MempoolP2pRunner) {
     let mut network_manager = NetworkManager::new(
         mempool_p2p_config.network_config,
@@ -29,13 +33,25 @@ pub fn create_p2p_propagator_and_runner(
                 mempool_p2p_config.network_buffer_size,
             )
             .expect("Failed to register broadcast topic");
+    let reconciliation_server = network_manager.register_sqmr_protocol_server(
+        MEMPOOL_RECONCILIATION_PROTOCOL.to_string(),
+        mempool_p2p_config.network_buffer_size,
+    );
+    let reconciliation_client = network_manager.register_sqmr_protocol_client(
+        MEMPOOL_RECONCILIATION_PROTOCOL.to_string(),
+        mempool_p2p_config.network_buffer_size,
+    );
     let network_future = network_manager.run();
     let mempool_p2p_propagator = MempoolP2pPropagator::new(broadcast_topic_client.clone());
     let mempool_p2p_runner = MempoolP2pRunner::new(
         network_future.boxed(),
         broadcasted_messages_receiver,
         broadcast_topic_client,
+        reconciliation_server,
+        reconciliation_client,
         gateway_client,
+        mempool_client,
+        mempool_p2p_config.reconciliation_config,
     );
     (mempool_p2p_propagator, mempool_p2p_runner)
 }
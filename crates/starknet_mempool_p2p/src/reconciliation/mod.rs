@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod test;
+
+use std::collections::HashSet;
+
+use starknet_api::transaction::TransactionHash;
+
+/// The maximal number of short transaction ids in a mempool summary.
+pub const MAX_SUMMARY_SIZE: usize = 4096;
+
+/// A compact identifier of a transaction, used to summarize the content of a mempool; it is the
+/// low 8 bytes of the transaction hash.
+pub fn short_tx_id(tx_hash: &TransactionHash) -> u64 {
+    let bytes = tx_hash.0.to_bytes_be();
+    u64::from_be_bytes(bytes[24..].try_into().expect("A felt is 32 bytes long."))
+}
+
+/// Summarizes the given transactions for peers, capped at [MAX_SUMMARY_SIZE] ids.
+pub fn summarize(tx_hashes: &[TransactionHash]) -> Vec<u64> {
+    tx_hashes.iter().take(MAX_SUMMARY_SIZE).map(short_tx_id).collect()
+}
+
+/// Returns up to `max_txs` of the given transactions which are absent from a peer's summary.
+/// Short id collisions may hide a missing transaction; it is sent in a later round, once the
+/// colliding transaction leaves the peer's mempool.
+pub fn missing_tx_hashes(
+    tx_hashes: Vec<TransactionHash>,
+    peer_summary: &[u64],
+    max_txs: usize,
+) -> Vec<TransactionHash> {
+    let peer_short_tx_ids: HashSet<u64> = peer_summary.iter().copied().collect();
+    tx_hashes
+        .into_iter()
+        .filter(|tx_hash| !peer_short_tx_ids.contains(&short_tx_id(tx_hash)))
+        .take(max_txs)
+        .collect()
+}
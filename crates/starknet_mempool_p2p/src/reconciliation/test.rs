@@ -0,0 +1,30 @@
+use starknet_api::transaction::TransactionHash;
+use starknet_api::tx_hash;
+
+use super::{missing_tx_hashes, short_tx_id, summarize, MAX_SUMMARY_SIZE};
+
+#[test]
+fn short_tx_id_is_the_low_bytes_of_the_hash() {
+    assert_eq!(short_tx_id(&tx_hash!(0x1234)), 0x1234);
+    assert_eq!(short_tx_id(&tx_hash!(u64::MAX)), u64::MAX);
+}
+
+#[test]
+fn summary_is_capped() {
+    let tx_hashes: Vec<TransactionHash> = (0..MAX_SUMMARY_SIZE + 1).map(|i| tx_hash!(i)).collect();
+    let summary = summarize(&tx_hashes);
+    assert_eq!(summary.len(), MAX_SUMMARY_SIZE);
+    assert_eq!(summary[1], 1);
+}
+
+#[test]
+fn missing_tx_hashes_skips_summarized_txs() {
+    let tx_hashes = vec![tx_hash!(1), tx_hash!(2), tx_hash!(3), tx_hash!(4)];
+    let peer_summary = summarize(&[tx_hash!(2), tx_hash!(5)]);
+
+    assert_eq!(
+        missing_tx_hashes(tx_hashes.clone(), &peer_summary, 10),
+        vec![tx_hash!(1), tx_hash!(3), tx_hash!(4)]
+    );
+    assert_eq!(missing_tx_hashes(tx_hashes, &peer_summary, 2), vec![tx_hash!(1), tx_hash!(3)]);
+}
@@ -3,28 +3,45 @@ mod test;
 
 use async_trait::async_trait;
 use futures::future::BoxFuture;
-use futures::stream::FuturesUnordered;
+use futures::stream::{FuturesUnordered, SelectAll};
 use futures::StreamExt;
 use papyrus_network::network_manager::{
     BroadcastTopicClient,
     BroadcastTopicClientTrait,
     BroadcastTopicServer,
+    ClientResponsesManager,
     NetworkError,
+    ServerQueryManager,
+    SqmrClientSender,
+    SqmrServerReceiver,
 };
-use papyrus_protobuf::mempool::RpcTransactionWrapper;
+use papyrus_protobuf::mempool::{MempoolSummary, RpcTransactionWrapper};
 use starknet_gateway_types::communication::{GatewayClientError, SharedGatewayClient};
 use starknet_gateway_types::errors::GatewayError;
 use starknet_gateway_types::gateway_types::GatewayInput;
+use starknet_mempool_types::communication::SharedMempoolClient;
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::component_server::WrapperServer;
 use starknet_sequencer_infra::errors::ComponentError;
+use tokio::time::{interval_at, Instant};
 use tracing::warn;
 
+use crate::config::ReconciliationConfig;
+use crate::reconciliation::{missing_tx_hashes, summarize};
+
 pub struct MempoolP2pRunner {
     network_future: BoxFuture<'static, Result<(), NetworkError>>,
     broadcasted_topic_server: BroadcastTopicServer<RpcTransactionWrapper>,
     broadcast_topic_client: BroadcastTopicClient<RpcTransactionWrapper>,
+    // Answers the summaries of peers with the transactions missing from them.
+    reconciliation_server: SqmrServerReceiver<MempoolSummary, RpcTransactionWrapper>,
+    // Sends the summaries of this mempool to peers.
+    reconciliation_client: SqmrClientSender<MempoolSummary, RpcTransactionWrapper>,
+    // The transactions peers respond with to the summaries of this mempool.
+    reconciled_txs: SelectAll<ClientResponsesManager<RpcTransactionWrapper>>,
     gateway_client: SharedGatewayClient,
+    mempool_client: SharedMempoolClient,
+    reconciliation_config: ReconciliationConfig,
 }
 
 impl MempoolP2pRunner {
@@ -32,9 +49,83 @@ impl MempoolP2pRunner {
         network_future: BoxFuture<'static, Result<(), NetworkError>>,
         broadcasted_topic_server: BroadcastTopicServer<RpcTransactionWrapper>,
         broadcast_topic_client: BroadcastTopicClient<RpcTransactionWrapper>,
+        reconciliation_server: SqmrServerReceiver<MempoolSummary, RpcTransactionWrapper>,
+        reconciliation_client: SqmrClientSender<MempoolSummary, RpcTransactionWrapper>,
         gateway_client: SharedGatewayClient,
+        mempool_client: SharedMempoolClient,
+        reconciliation_config: ReconciliationConfig,
     ) -> Self {
-        Self { network_future, broadcasted_topic_server, broadcast_topic_client, gateway_client }
+        Self {
+            network_future,
+            broadcasted_topic_server,
+            broadcast_topic_client,
+            reconciliation_server,
+            reconciliation_client,
+            reconciled_txs: SelectAll::new(),
+            gateway_client,
+            mempool_client,
+            reconciliation_config,
+        }
+    }
+
+    /// Sends a summary of the mempool content to a peer, which responds with the transactions
+    /// missing from it.
+    async fn send_summary(&mut self) {
+        let tx_hashes = match self.mempool_client.get_tx_hashes().await {
+            Ok(tx_hashes) => tx_hashes,
+            Err(e) => {
+                warn!("Failed to get the mempool transactions for reconciliation: {:?}", e);
+                return;
+            }
+        };
+        let summary = MempoolSummary(summarize(&tx_hashes));
+        match self.reconciliation_client.send_new_query(summary).await {
+            Ok(responses) => self.reconciled_txs.push(responses),
+            Err(e) => warn!("Failed to send the mempool summary: {:?}", e),
+        }
+    }
+
+    /// Responds to a peer's summary with the mempool transactions missing from it.
+    async fn send_missing_txs(
+        &mut self,
+        mut query_manager: ServerQueryManager<MempoolSummary, RpcTransactionWrapper>,
+    ) {
+        let peer_summary = match query_manager.query() {
+            Ok(MempoolSummary(peer_summary)) => peer_summary.clone(),
+            Err(e) => {
+                warn!("Received a faulty mempool summary: {:?}. Reporting the sending peer", e);
+                query_manager.report_peer();
+                return;
+            }
+        };
+        let tx_hashes = match self.mempool_client.get_tx_hashes().await {
+            Ok(tx_hashes) => tx_hashes,
+            Err(e) => {
+                warn!("Failed to get the mempool transactions for reconciliation: {:?}", e);
+                return;
+            }
+        };
+        let missing_tx_hashes = missing_tx_hashes(
+            tx_hashes,
+            &peer_summary,
+            self.reconciliation_config.max_reconciled_txs,
+        );
+        if missing_tx_hashes.is_empty() {
+            return;
+        }
+        let rpc_txs = match self.mempool_client.get_rpc_txs(missing_tx_hashes).await {
+            Ok(rpc_txs) => rpc_txs,
+            Err(e) => {
+                warn!("Failed to get the transactions missing from a peer: {:?}", e);
+                return;
+            }
+        };
+        for rpc_tx in rpc_txs {
+            if let Err(e) = query_manager.send_response(RpcTransactionWrapper(rpc_tx)).await {
+                warn!("Failed to send a transaction missing from a peer: {:?}", e);
+                return;
+            }
+        }
     }
 }
 
@@ -42,6 +133,10 @@ impl MempoolP2pRunner {
 impl ComponentStarter for MempoolP2pRunner {
     async fn start(&mut self) -> Result<(), ComponentError> {
         let mut gateway_futures = FuturesUnordered::new();
+        // The first summary is sent only after an interval, giving the node time to connect.
+        let reconciliation_interval = self.reconciliation_config.interval;
+        let mut reconciliation_ticker =
+            interval_at(Instant::now() + reconciliation_interval, reconciliation_interval);
         loop {
             tokio::select! {
                 result = &mut self.network_future => {
@@ -65,7 +160,11 @@ impl ComponentStarter for MempoolP2pRunner {
                     match message_result {
                         Ok(message) => {
                             gateway_futures.push(self.gateway_client.add_tx(
-                                GatewayInput { rpc_tx: message.0, message_metadata: Some(broadcasted_message_metadata.clone()) }
+                                GatewayInput {
+                                    rpc_tx: message.0,
+                                    message_metadata: Some(broadcasted_message_metadata.clone()),
+                                    reconciled: false,
+                                }
                             ));
                         }
                         Err(e) => {
@@ -76,6 +175,26 @@ impl ComponentStarter for MempoolP2pRunner {
                         }
                     }
                 }
+                _ = reconciliation_ticker.tick() => {
+                    self.send_summary().await;
+                }
+                Some(query_manager) = self.reconciliation_server.next() => {
+                    self.send_missing_txs(query_manager).await;
+                }
+                // Reconciled transactions may be stale by the time they arrive, so their sender is
+                // not reported if they are rejected.
+                Some(response) = self.reconciled_txs.next() => {
+                    match response {
+                        Ok(RpcTransactionWrapper(rpc_tx)) => {
+                            gateway_futures.push(self.gateway_client.add_tx(
+                                GatewayInput { rpc_tx, message_metadata: None, reconciled: true }
+                            ));
+                        }
+                        Err(e) => {
+                            warn!("Received a faulty reconciled transaction from network: {:?}", e);
+                        }
+                    }
+                }
             }
         }
     }
@@ -7,70 +7,161 @@ use futures::future::{pending, ready, BoxFuture};
 use futures::stream::StreamExt;
 use futures::{FutureExt, SinkExt};
 use papyrus_network::network_manager::test_utils::{
+    create_test_server_query_manager,
     mock_register_broadcast_topic,
+    mock_register_sqmr_protocol_client,
+    mock_register_sqmr_protocol_server,
     BroadcastNetworkMock,
+    MockClientResponsesManager,
     TestSubscriberChannels,
 };
-use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkError};
+use papyrus_network::network_manager::{
+    BroadcastTopicChannels,
+    GenericReceiver,
+    NetworkError,
+    ServerQueryManager,
+};
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
-use papyrus_protobuf::mempool::RpcTransactionWrapper;
+use papyrus_protobuf::mempool::{MempoolSummary, RpcTransactionWrapper};
 use papyrus_test_utils::{get_rng, GetTestInstance};
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
+use starknet_api::tx_hash;
 use starknet_gateway_types::communication::{GatewayClient, GatewayClientResult};
-use starknet_gateway_types::gateway_types::GatewayInput;
+use starknet_gateway_types::gateway_types::{
+    CallInput,
+    FeeBalanceCheck,
+    GatewayInput,
+    GatewayReceipt,
+    SignedGatewayReceipt,
+};
+use starknet_mempool_types::communication::{MockMempoolClient, SharedMempoolClient};
+use starknet_mempool_types::mempool_types::DroppedTransaction;
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_types_core::felt::Felt;
 use tokio::time::sleep;
 
 use super::MempoolP2pRunner;
+use crate::config::ReconciliationConfig;
+use crate::reconciliation::summarize;
+
+const BUFFER_SIZE: usize = 10;
+
+struct MockReconciliationNetwork {
+    // Summaries sent to this node by peers.
+    summaries_sender: Sender<ServerQueryManager<MempoolSummary, RpcTransactionWrapper>>,
+    // Summaries sent by this node to peers.
+    summaries_receiver:
+        GenericReceiver<MockClientResponsesManager<MempoolSummary, RpcTransactionWrapper>>,
+}
 
 fn setup(
     network_future: BoxFuture<'static, Result<(), NetworkError>>,
     gateway_client: Arc<dyn GatewayClient>,
-) -> (MempoolP2pRunner, BroadcastNetworkMock<RpcTransactionWrapper>) {
+    mempool_client: SharedMempoolClient,
+    reconciliation_config: ReconciliationConfig,
+) -> (MempoolP2pRunner, BroadcastNetworkMock<RpcTransactionWrapper>, MockReconciliationNetwork) {
     let TestSubscriberChannels { mock_network, subscriber_channels } =
         mock_register_broadcast_topic().expect("Failed to create mock network");
     let BroadcastTopicChannels { broadcasted_messages_receiver, broadcast_topic_client } =
         subscriber_channels;
+    let (reconciliation_server, summaries_sender) = mock_register_sqmr_protocol_server(BUFFER_SIZE);
+    let (reconciliation_client, summaries_receiver) =
+        mock_register_sqmr_protocol_client(BUFFER_SIZE);
     let mempool_p2p_runner = MempoolP2pRunner::new(
         network_future,
         broadcasted_messages_receiver,
         broadcast_topic_client,
+        reconciliation_server,
+        reconciliation_client,
         gateway_client,
+        mempool_client,
+        reconciliation_config,
     );
-    (mempool_p2p_runner, mock_network)
+    (
+        mempool_p2p_runner,
+        mock_network,
+        MockReconciliationNetwork { summaries_sender, summaries_receiver },
+    )
 }
 
-#[test]
-fn run_returns_when_network_future_returns() {
+#[tokio::test]
+async fn run_returns_when_network_future_returns() {
     let network_future = ready(Ok(())).boxed();
     let gateway_client =
         Arc::new(MockGatewayClient { add_tx_sender: futures::channel::mpsc::channel(1).0 });
-    let (mut mempool_p2p_runner, _) = setup(network_future, gateway_client);
+    let (mut mempool_p2p_runner, _, _) = setup(
+        network_future,
+        gateway_client,
+        Arc::new(MockMempoolClient::new()),
+        ReconciliationConfig::default(),
+    );
     mempool_p2p_runner.start().now_or_never().unwrap().unwrap();
 }
 
-#[test]
-fn run_returns_error_when_network_future_returns_error() {
+#[tokio::test]
+async fn run_returns_error_when_network_future_returns_error() {
     let network_future =
         ready(Err(NetworkError::DialError(libp2p::swarm::DialError::Aborted))).boxed();
     let gateway_client =
         Arc::new(MockGatewayClient { add_tx_sender: futures::channel::mpsc::channel(1).0 });
-    let (mut mempool_p2p_runner, _) = setup(network_future, gateway_client);
+    let (mut mempool_p2p_runner, _, _) = setup(
+        network_future,
+        gateway_client,
+        Arc::new(MockMempoolClient::new()),
+        ReconciliationConfig::default(),
+    );
     mempool_p2p_runner.start().now_or_never().unwrap().unwrap_err();
 }
 
 // TODO(eitan): Make it an automock
 #[derive(Clone)]
 struct MockGatewayClient {
-    add_tx_sender: Sender<RpcTransaction>,
+    add_tx_sender: Sender<GatewayInput>,
 }
 
 #[async_trait]
 impl GatewayClient for MockGatewayClient {
-    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<TransactionHash> {
-        let _ = self.clone().add_tx_sender.send(gateway_input.rpc_tx).await;
-        Ok(TransactionHash::default())
+    async fn add_tx(
+        &self,
+        gateway_input: GatewayInput,
+    ) -> GatewayClientResult<SignedGatewayReceipt> {
+        let _ = self.clone().add_tx_sender.send(gateway_input).await;
+        Ok(SignedGatewayReceipt {
+            receipt: GatewayReceipt {
+                tx_hash: TransactionHash::default(),
+                received_timestamp: 0,
+                chain_id: ChainId::Mainnet,
+            },
+            signature: Signature::default(),
+        })
+    }
+
+    async fn call(&self, _call_input: CallInput) -> GatewayClientResult<Vec<Felt>> {
+        Ok(Vec::new())
+    }
+
+    async fn check_fee_balance(
+        &self,
+        _rpc_tx: RpcTransaction,
+    ) -> GatewayClientResult<FeeBalanceCheck> {
+        unimplemented!()
+    }
+
+    async fn notify_dropped_txs(
+        &self,
+        _dropped_txs: Vec<DroppedTransaction>,
+    ) -> GatewayClientResult<()> {
+        Ok(())
+    }
+
+    async fn get_dropped_tx(
+        &self,
+        _tx_hash: TransactionHash,
+    ) -> GatewayClientResult<Option<DroppedTransaction>> {
+        Ok(None)
     }
 }
 
@@ -79,7 +170,12 @@ async fn start_component_receive_tx_happy_flow() {
     let network_future = pending().boxed();
     let (add_tx_sender, mut add_tx_receiver) = futures::channel::mpsc::channel(1);
     let mock_gateway_client = Arc::new(MockGatewayClient { add_tx_sender });
-    let (mut mempool_p2p_runner, mock_network) = setup(network_future, mock_gateway_client);
+    let (mut mempool_p2p_runner, mock_network, _) = setup(
+        network_future,
+        mock_gateway_client,
+        Arc::new(MockMempoolClient::new()),
+        ReconciliationConfig::default(),
+    );
     let BroadcastNetworkMock {
         broadcasted_messages_sender: mut mock_broadcasted_messages_sender,
         ..
@@ -95,12 +191,91 @@ async fn start_component_receive_tx_happy_flow() {
     res.await.expect("Failed to send message");
     tokio::select! {
         _ = mempool_p2p_runner.start() => {panic!("Mempool receiver failed to start");}
-        actual_rpc_transaction = add_tx_receiver.next() => {
-            assert_eq!(actual_rpc_transaction, Some(expected_rpc_transaction.0));
+        gateway_input = add_tx_receiver.next() => {
+            let gateway_input = gateway_input.unwrap();
+            assert_eq!(gateway_input.rpc_tx, expected_rpc_transaction.0);
+            assert!(!gateway_input.reconciled);
         }
         _ = sleep(Duration::from_secs(5)) => {
             panic!("Test timed out");
         }
     }
 }
+
+#[tokio::test]
+async fn start_component_send_txs_missing_from_peer_summary() {
+    let network_future = pending().boxed();
+    let gateway_client =
+        Arc::new(MockGatewayClient { add_tx_sender: futures::channel::mpsc::channel(1).0 });
+    let expected_rpc_transaction = RpcTransaction::get_test_instance(&mut get_rng());
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_tx_hashes().returning(|| Ok(vec![tx_hash!(1), tx_hash!(2)]));
+    let rpc_transaction = expected_rpc_transaction.clone();
+    mempool_client
+        .expect_get_rpc_txs()
+        .withf(|tx_hashes| tx_hashes == &vec![tx_hash!(2)])
+        .times(1)
+        .return_once(move |_| Ok(vec![rpc_transaction]));
+    let (mut mempool_p2p_runner, _, mock_reconciliation_network) = setup(
+        network_future,
+        gateway_client,
+        Arc::new(mempool_client),
+        ReconciliationConfig::default(),
+    );
+    let MockReconciliationNetwork { mut summaries_sender, .. } = mock_reconciliation_network;
+
+    // The peer holds only the first transaction.
+    let (query_manager, _report_receiver, mut responses_receiver) =
+        create_test_server_query_manager(MempoolSummary(summarize(&[tx_hash!(1)])));
+    summaries_sender.send(query_manager).await.expect("Failed to send the peer summary");
+    tokio::select! {
+        _ = mempool_p2p_runner.start() => {panic!("Mempool receiver failed to start");}
+        response = responses_receiver.next() => {
+            assert_eq!(response, Some(RpcTransactionWrapper(expected_rpc_transaction)));
+        }
+        _ = sleep(Duration::from_secs(5)) => {
+            panic!("Test timed out");
+        }
+    }
+}
+
+#[tokio::test]
+async fn start_component_receive_reconciled_tx() {
+    let network_future = pending().boxed();
+    let (add_tx_sender, mut add_tx_receiver) = futures::channel::mpsc::channel(1);
+    let mock_gateway_client = Arc::new(MockGatewayClient { add_tx_sender });
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_tx_hashes().returning(|| Ok(vec![tx_hash!(1)]));
+    let reconciliation_config =
+        ReconciliationConfig { interval: Duration::from_millis(10), ..Default::default() };
+    let (mut mempool_p2p_runner, _, mock_reconciliation_network) =
+        setup(network_future, mock_gateway_client, Arc::new(mempool_client), reconciliation_config);
+    let MockReconciliationNetwork { mut summaries_receiver, .. } = mock_reconciliation_network;
+    let expected_rpc_transaction = RpcTransaction::get_test_instance(&mut get_rng());
+
+    let peer_response = async {
+        let mut responses_manager = summaries_receiver.next().await.unwrap();
+        assert_eq!(
+            responses_manager.query().as_ref().unwrap(),
+            &MempoolSummary(summarize(&[tx_hash!(1)]))
+        );
+        responses_manager
+            .send_response(RpcTransactionWrapper(expected_rpc_transaction.clone()))
+            .await
+            .expect("Failed to send the reconciled transaction");
+        add_tx_receiver.next().await
+    };
+    tokio::select! {
+        _ = mempool_p2p_runner.start() => {panic!("Mempool receiver failed to start");}
+        gateway_input = peer_response => {
+            let gateway_input = gateway_input.unwrap();
+            assert_eq!(gateway_input.rpc_tx, expected_rpc_transaction);
+            assert!(gateway_input.reconciled);
+        }
+        _ = sleep(Duration::from_secs(5)) => {
+            panic!("Test timed out");
+        }
+    }
+}
+
 // TODO(eitan): Add test for when the gateway client fails to add the transaction
@@ -6,7 +6,10 @@ use mockall::automock;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
+use starknet_api::core::ClassHash;
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::TransactionHash;
 use starknet_sequencer_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -33,6 +36,10 @@ pub type SharedMempoolClient = Arc<dyn MempoolClient>;
 pub struct AddTransactionArgsWrapper {
     pub args: AddTransactionArgs,
     pub p2p_message_metadata: Option<BroadcastedMessageMetadata>,
+    /// Whether the transaction was received through mempool reconciliation, in which case it is
+    /// not propagated.
+    #[serde(default)]
+    pub reconciled: bool,
 }
 
 /// Serves as the mempool's shared interface. Requires `Send + Sync` to allow transferring and
@@ -45,6 +52,22 @@ pub trait MempoolClient: Send + Sync {
     async fn add_tx(&self, args: AddTransactionArgsWrapper) -> MempoolClientResult<()>;
     async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()>;
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<AccountTransaction>>;
+    /// Returns up to `n_txs` of the transactions likely to be sequenced next, without removing
+    /// them from the mempool.
+    async fn peek_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<AccountTransaction>>;
+    /// Returns the hash of a pending declare transaction of the given class, if there is one.
+    async fn get_pending_declare(
+        &self,
+        class_hash: ClassHash,
+    ) -> MempoolClientResult<Option<TransactionHash>>;
+    /// Returns the hashes of all transactions held in the mempool.
+    async fn get_tx_hashes(&self) -> MempoolClientResult<Vec<TransactionHash>>;
+    /// Returns the held transactions of the given hashes, in the form they are propagated to the
+    /// network; transactions that are not held or not propagated are skipped.
+    async fn get_rpc_txs(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolClientResult<Vec<RpcTransaction>>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -52,6 +75,10 @@ pub enum MempoolRequest {
     AddTransaction(AddTransactionArgsWrapper),
     CommitBlock(CommitBlockArgs),
     GetTransactions(usize),
+    PeekTransactions(usize),
+    GetPendingDeclare(ClassHash),
+    GetTransactionHashes,
+    GetRpcTransactions(Vec<TransactionHash>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -59,6 +86,10 @@ pub enum MempoolResponse {
     AddTransaction(MempoolResult<()>),
     CommitBlock(MempoolResult<()>),
     GetTransactions(MempoolResult<Vec<AccountTransaction>>),
+    PeekTransactions(MempoolResult<Vec<AccountTransaction>>),
+    GetPendingDeclare(MempoolResult<Option<TransactionHash>>),
+    GetTransactionHashes(MempoolResult<Vec<TransactionHash>>),
+    GetRpcTransactions(MempoolResult<Vec<RpcTransaction>>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -96,4 +127,54 @@ where
             MempoolError
         )
     }
+
+    async fn peek_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<AccountTransaction>> {
+        let request = MempoolRequest::PeekTransactions(n_txs);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            PeekTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_pending_declare(
+        &self,
+        class_hash: ClassHash,
+    ) -> MempoolClientResult<Option<TransactionHash>> {
+        let request = MempoolRequest::GetPendingDeclare(class_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetPendingDeclare,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_tx_hashes(&self) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::GetTransactionHashes;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetTransactionHashes,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_rpc_txs(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolClientResult<Vec<RpcTransaction>> {
+        let request = MempoolRequest::GetRpcTransactions(tx_hashes);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetRpcTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
 }
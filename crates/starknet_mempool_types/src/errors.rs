@@ -1,14 +1,41 @@
 use serde::{Deserialize, Serialize};
+use starknet_api::block::GasPrice;
 use starknet_api::core::{ContractAddress, Nonce};
-use starknet_api::transaction::TransactionHash;
+use starknet_api::transaction::fields::Tip;
+use starknet_api::transaction::{TransactionHash, TransactionVersion};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MempoolError {
-    #[error("Duplicate transaction, sender address: {address}, nonce: {:?}", nonce)]
-    DuplicateNonce { address: ContractAddress, nonce: Nonce },
+    #[error(
+        "The pending transactions of account {address} would exceed the limit of \
+         {max_account_size} bytes."
+    )]
+    AccountSizeLimitExceeded { address: ContractAddress, max_account_size: usize },
+    #[error("Declare transaction with hash: {tx_hash} cannot be forwarded to the network.")]
+    DeclareNotForwarded { tx_hash: TransactionHash },
+    #[error(
+        "Duplicate transaction, sender address: {address}, nonce: {:?}; transaction \
+         {existing_tx_hash} has the same nonce. {}",
+        nonce,
+        replacement_requirement(replacement_fee_threshold)
+    )]
+    // The fields following `nonce` were added after the previous release. They default when
+    // absent, so that the error stays readable across the remote component protocol in both
+    // directions.
+    DuplicateNonce {
+        address: ContractAddress,
+        nonce: Nonce,
+        #[serde(default)]
+        existing_tx_hash: TransactionHash,
+        // `None` if the existing transaction cannot be replaced.
+        #[serde(default)]
+        replacement_fee_threshold: Option<ReplacementFeeThreshold>,
+    },
     #[error("Duplicate transaction, with hash: {tx_hash}")]
     DuplicateTransaction { tx_hash: TransactionHash },
+    #[error("The mempool is full; it has no room for a transaction of {tx_size} bytes.")]
+    MempoolFull { tx_size: usize },
     #[error("{0}")]
     NonceTooLarge(Nonce),
     #[error("Nonce: {nonce} for account address {address} has already been processed.")]
@@ -17,4 +44,23 @@ pub enum MempoolError {
     P2pPropagatorClientError { tx_hash: TransactionHash },
     #[error("Transaction with hash: {tx_hash} not found")]
     TransactionNotFound { tx_hash: TransactionHash },
+    #[error("Transaction version {version:?} is not supported.")]
+    UnsupportedTxVersion { version: TransactionVersion },
+}
+
+/// The minimal fee values of a transaction replacing a transaction with the same sender and nonce.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplacementFeeThreshold {
+    pub tip: Tip,
+    pub max_l2_gas_price: GasPrice,
+}
+
+fn replacement_requirement(replacement_fee_threshold: &Option<ReplacementFeeThreshold>) -> String {
+    match replacement_fee_threshold {
+        Some(ReplacementFeeThreshold { tip, max_l2_gas_price }) => format!(
+            "A replacement requires a tip of at least {tip} and a max L2 gas price of at least \
+             {max_l2_gas_price}."
+        ),
+        None => "It cannot be replaced.".to_string(),
+    }
 }
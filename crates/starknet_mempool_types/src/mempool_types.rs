@@ -1,8 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
 
 use crate::errors::MempoolError;
@@ -25,6 +26,8 @@ impl std::fmt::Display for AccountState {
 pub struct AddTransactionArgs {
     pub tx: AccountTransaction,
     pub account_state: AccountState,
+    // The class declared by a declare transaction, which the transaction does not hold yet.
+    pub declared_class_hash: Option<ClassHash>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -33,4 +36,22 @@ pub struct CommitBlockArgs {
     pub tx_hashes: HashSet<TransactionHash>,
 }
 
+/// The reason a transaction left the mempool without being included in a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TransactionDropReason {
+    /// Replaced by a transaction of the same account and nonce, paying a higher fee.
+    Replaced { replaced_by: TransactionHash },
+    /// A different transaction of the same account and nonce was committed.
+    StaleNonce,
+}
+
+/// A transaction that was dropped by the mempool; it will not be included in a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DroppedTransaction {
+    pub tx_hash: TransactionHash,
+    // The priority the transaction had in the mempool.
+    pub tip: Tip,
+    pub reason: TransactionDropReason,
+}
+
 pub type MempoolResult<T> = Result<T, MempoolError>;
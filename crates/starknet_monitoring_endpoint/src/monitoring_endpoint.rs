@@ -3,11 +3,12 @@ use std::net::SocketAddr;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use axum::{async_trait, Router, Server};
+use axum::{async_trait, Json, Router, Server};
 use hyper::Error;
 use infra_utils::type_name::short_type_name;
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::crash_report::recent_crash_reports;
 use starknet_sequencer_infra::errors::ComponentError;
 use tracing::{info, instrument};
 
@@ -22,6 +23,7 @@ pub(crate) const ALIVE: &str = "alive";
 pub(crate) const READY: &str = "ready";
 pub(crate) const VERSION: &str = "nodeVersion";
 pub(crate) const METRICS: &str = "metrics";
+pub(crate) const CRASH_REPORTS: &str = "crashReports";
 
 pub struct MonitoringEndpoint {
     config: MonitoringEndpointConfig,
@@ -82,6 +84,10 @@ impl MonitoringEndpoint {
                 format!("/{MONITORING_PREFIX}/{METRICS}").as_str(),
                 get(move || metrics(prometheus_handle)),
             )
+            .route(
+                format!("/{MONITORING_PREFIX}/{CRASH_REPORTS}").as_str(),
+                get(move || async { Json(recent_crash_reports()) }),
+            )
     }
 }
 
@@ -7,6 +7,7 @@ use hyper::body::to_bytes;
 use hyper::Client;
 use metrics::{absolute_counter, describe_counter, register_counter};
 use pretty_assertions::assert_eq;
+use starknet_sequencer_infra::crash_report::{recent_crash_reports, CrashReport};
 use tokio::spawn;
 use tokio::task::yield_now;
 use tower::ServiceExt;
@@ -16,6 +17,7 @@ use crate::monitoring_endpoint::{
     create_monitoring_endpoint,
     MonitoringEndpoint,
     ALIVE,
+    CRASH_REPORTS,
     METRICS,
     READY,
     VERSION,
@@ -54,6 +56,16 @@ async fn ready_endpoint() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn crash_reports_endpoint() {
+    let response = request_app(setup_monitoring_endpoint(None).app(), CRASH_REPORTS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let crash_reports: Vec<CrashReport> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(crash_reports, recent_crash_reports());
+}
+
 #[tokio::test]
 async fn with_metrics() {
     let config = MonitoringEndpointConfig { collect_metrics: true, ..Default::default() };
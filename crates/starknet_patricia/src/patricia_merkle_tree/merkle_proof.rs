@@ -0,0 +1,99 @@
+use thiserror::Error;
+
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::filled_tree::node::FilledNode;
+use crate::patricia_merkle_tree::node_data::inner_node::{BinaryData, EdgeData, NodeData};
+use crate::patricia_merkle_tree::node_data::leaf::Leaf;
+use crate::patricia_merkle_tree::types::{NodeIndex, SubTreeHeight};
+use crate::storage::errors::DeserializationError;
+use crate::storage::storage_trait::{create_db_key, StarknetPrefix, Storage};
+
+#[cfg(test)]
+#[path = "merkle_proof_test.rs"]
+pub mod merkle_proof_test;
+
+/// An inner node on the path from the root of a tree to one of its leaves.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProofNode {
+    Binary(BinaryData),
+    Edge(EdgeData),
+}
+
+/// The preimages of the inner nodes on the path from the root of a tree to a leaf, root first,
+/// proving the hash of the leaf against the root. If the leaf is not in the tree, the path ends at
+/// the edge that diverges from it, proving its absence.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MerkleProof {
+    pub nodes: Vec<ProofNode>,
+    /// The hash of the leaf, if it is in the tree.
+    pub leaf_hash: Option<HashOutput>,
+}
+
+#[derive(Debug, Error)]
+pub enum MerkleProofError {
+    #[error("The node of hash {0:?} is missing from the storage.")]
+    MissingNode(HashOutput),
+    #[error(transparent)]
+    Deserialization(#[from] DeserializationError),
+}
+
+pub type MerkleProofResult<T> = Result<T, MerkleProofError>;
+
+/// Fetches the proof of the leaf at the given index, in the tree of the given root, from the
+/// storage.
+pub fn fetch_merkle_proof<L: Leaf>(
+    storage: &impl Storage,
+    root_hash: HashOutput,
+    leaf_index: NodeIndex,
+) -> MerkleProofResult<MerkleProof> {
+    let mut proof = MerkleProof::default();
+    if root_hash == HashOutput::ROOT_OF_EMPTY_TREE {
+        return Ok(proof);
+    }
+
+    let mut node_index = NodeIndex::ROOT;
+    let mut node_hash = root_hash;
+    // The height of the current node above the leaves.
+    let mut height = SubTreeHeight::ACTUAL_HEIGHT.0;
+    while height > 0 {
+        let node_key = create_db_key(
+            StarknetPrefix::InnerNode.to_storage_prefix(),
+            &node_hash.0.to_bytes_be(),
+        );
+        let node_value = storage.get(&node_key).ok_or(MerkleProofError::MissingNode(node_hash))?;
+        match FilledNode::<L>::deserialize(node_hash, node_value, false)?.data {
+            NodeData::Binary(binary_data) => {
+                height -= 1;
+                let child_index = leaf_index >> height;
+                node_hash = if child_index == node_index << 1 {
+                    binary_data.left_hash
+                } else {
+                    binary_data.right_hash
+                };
+                node_index = child_index;
+                proof.nodes.push(ProofNode::Binary(binary_data));
+            }
+            NodeData::Edge(edge_data) => {
+                height -= u8::from(edge_data.path_to_bottom.length);
+                let bottom_index = edge_data.path_to_bottom.bottom_index(node_index);
+                let diverges_from_leaf = leaf_index >> height != bottom_index;
+                node_hash = edge_data.bottom_hash;
+                node_index = bottom_index;
+                proof.nodes.push(ProofNode::Edge(edge_data));
+                if diverges_from_leaf {
+                    return Ok(proof);
+                }
+            }
+            NodeData::Leaf(_) => unreachable!("Inner nodes are not deserialized as leaves."),
+        }
+    }
+    proof.leaf_hash = Some(node_hash);
+    Ok(proof)
+}
+
+/// Fetches the leaf of the given hash from the storage.
+pub fn fetch_leaf<L: Leaf>(storage: &impl Storage, leaf_hash: HashOutput) -> MerkleProofResult<L> {
+    let leaf_key = create_db_key(L::prefix(), &leaf_hash.0.to_bytes_be());
+    let leaf_value = storage.get(&leaf_key).ok_or(MerkleProofError::MissingNode(leaf_hash))?;
+    Ok(L::deserialize(leaf_value)?)
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use ethnum::U256;
+use pretty_assertions::assert_eq;
+use rstest::{fixture, rstest};
+
+use crate::felt::Felt;
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::external_test_utils::{create_binary_entry, create_edge_entry};
+use crate::patricia_merkle_tree::internal_test_utils::MockLeaf;
+use crate::patricia_merkle_tree::merkle_proof::{
+    fetch_merkle_proof,
+    MerkleProof,
+    MerkleProofError,
+    ProofNode,
+};
+use crate::patricia_merkle_tree::node_data::inner_node::{
+    BinaryData,
+    EdgeData,
+    EdgePathLength,
+    PathToBottom,
+};
+use crate::patricia_merkle_tree::types::NodeIndex;
+use crate::storage::map_storage::MapStorage;
+
+fn hash(value: u128) -> HashOutput {
+    HashOutput(Felt::from(value))
+}
+
+fn edge(bottom_hash: u128, path: u128, length: u8) -> ProofNode {
+    ProofNode::Edge(EdgeData {
+        bottom_hash: hash(bottom_hash),
+        path_to_bottom: PathToBottom::new(path.into(), EdgePathLength::new(length).unwrap())
+            .unwrap(),
+    })
+}
+
+const ROOT_HASH: u128 = 535;
+
+/// A tree of two leaves, one in each half of the tree:
+///                 535
+///              /       \
+///           260         275
+///   (path 0, 250 edges)   (path 5, 250 edges)
+///           10           20
+#[fixture]
+fn storage() -> MapStorage {
+    MapStorage {
+        storage: HashMap::from([
+            create_binary_entry(260, 275),
+            create_edge_entry(10, 0, 250),
+            create_edge_entry(20, 5, 250),
+        ]),
+    }
+}
+
+fn right_half_leaf_index(offset: u128) -> NodeIndex {
+    NodeIndex::FIRST_LEAF + NodeIndex(U256::ONE << 250) + offset
+}
+
+#[rstest]
+#[case::left_leaf(NodeIndex::FIRST_LEAF, edge(10, 0, 250), Some(10))]
+#[case::right_leaf(right_half_leaf_index(5), edge(20, 5, 250), Some(20))]
+#[case::absent_leaf(right_half_leaf_index(6), edge(20, 5, 250), None)]
+fn test_fetch_merkle_proof(
+    storage: MapStorage,
+    #[case] leaf_index: NodeIndex,
+    #[case] expected_edge: ProofNode,
+    #[case] expected_leaf_hash: Option<u128>,
+) {
+    let root_node = ProofNode::Binary(BinaryData { left_hash: hash(260), right_hash: hash(275) });
+    assert_eq!(
+        fetch_merkle_proof::<MockLeaf>(&storage, hash(ROOT_HASH), leaf_index).unwrap(),
+        MerkleProof {
+            nodes: vec![root_node, expected_edge],
+            leaf_hash: expected_leaf_hash.map(hash),
+        }
+    );
+}
+
+#[rstest]
+fn test_fetch_merkle_proof_of_empty_tree(storage: MapStorage) {
+    let empty_tree_root = HashOutput::ROOT_OF_EMPTY_TREE;
+    assert_eq!(
+        fetch_merkle_proof::<MockLeaf>(&storage, empty_tree_root, NodeIndex::FIRST_LEAF).unwrap(),
+        MerkleProof::default()
+    );
+}
+
+#[rstest]
+fn test_fetch_merkle_proof_missing_node(storage: MapStorage) {
+    let result = fetch_merkle_proof::<MockLeaf>(&storage, hash(7), NodeIndex::FIRST_LEAF);
+    let Err(MerkleProofError::MissingNode(missing_node_hash)) = result else {
+        panic!("Expected a missing node error, got {result:?}.");
+    };
+    assert_eq!(missing_node_hash, hash(7));
+}
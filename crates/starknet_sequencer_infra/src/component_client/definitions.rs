@@ -20,6 +20,8 @@ pub enum ClientError {
     ResponseError(StatusCode, ServerError),
     #[error("Got an unexpected response type: {0}")]
     UnexpectedResponse(String),
+    #[error("The component crashed while handling the request.")]
+    ComponentCrashed,
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -5,7 +5,7 @@ use serde::Serialize;
 use tokio::sync::mpsc::{channel, Sender};
 use tracing::warn;
 
-use crate::component_client::ClientResult;
+use crate::component_client::{ClientError, ClientResult};
 use crate::component_definitions::{ComponentClient, ComponentRequestAndResponseSender};
 
 /// The `LocalComponentClient` struct is a generic client for sending component requests and
@@ -92,7 +92,9 @@ where
         let (res_tx, mut res_rx) = channel::<Response>(1);
         let request_and_res_tx = ComponentRequestAndResponseSender { request, tx: res_tx };
         self.tx.send(request_and_res_tx).await.expect("Outbound connection should be open.");
-        Ok(res_rx.recv().await.expect("Inbound connection should be open."))
+        // The response channel is closed without a response if the component crashed while
+        // handling the request.
+        res_rx.recv().await.ok_or(ClientError::ComponentCrashed)
     }
 }
 
@@ -14,9 +14,11 @@ use serde::Serialize;
 use super::definitions::{ClientError, ClientResult};
 use crate::component_definitions::{
     ComponentClient,
+    NodeIdentity,
     RemoteClientConfig,
     ServerError,
     APPLICATION_OCTET_STREAM,
+    HANDSHAKE_PATH,
 };
 use crate::serde_utils::SerdeWrapper;
 
@@ -122,6 +124,31 @@ where
             .expect("Request building should succeed")
     }
 
+    /// Requests the identity of the node running the remote server, without retries.
+    pub async fn handshake(&self) -> ClientResult<NodeIdentity> {
+        let mut uri_parts = self.uri.clone().into_parts();
+        uri_parts.path_and_query =
+            Some(HANDSHAKE_PATH.parse().expect("Handshake path should be valid"));
+        let uri = Uri::from_parts(uri_parts).expect("Handshake URI building should succeed");
+        let http_request =
+            HyperRequest::get(uri).body(Body::empty()).expect("Request building should succeed");
+
+        let http_response = self
+            .client
+            .request(http_request)
+            .await
+            .map_err(|e| ClientError::CommunicationFailure(Arc::new(e)))?;
+        match http_response.status() {
+            StatusCode::OK => get_response_body(http_response).await,
+            status_code => Err(ClientError::ResponseError(
+                status_code,
+                ServerError::RequestDeserializationFailure(
+                    "Could not deserialize handshake response".to_string(),
+                ),
+            )),
+        }
+    }
+
     async fn try_send(&self, http_request: HyperRequest<Body>) -> ClientResult<Response> {
         let http_response = self
             .client
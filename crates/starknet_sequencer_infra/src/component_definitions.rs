@@ -17,11 +17,17 @@ use crate::component_client::ClientResult;
 use crate::errors::ComponentError;
 
 pub const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
+/// The path on which remote component servers answer node identity handshakes.
+pub const HANDSHAKE_PATH: &str = "/handshake";
 const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 32;
 const DEFAULT_RETRIES: usize = 3;
 const DEFAULT_IDLE_CONNECTIONS: usize = usize::MAX;
 const DEFAULT_IDLE_TIMEOUT: u64 = 90;
 
+/// Properties a node must agree on with the remote components it communicates with, e.g., the
+/// chain id; keyed by property name.
+pub type NodeIdentity = BTreeMap<String, String>;
+
 #[async_trait]
 pub trait ComponentRequestHandler<Request, Response> {
     async fn handle_request(&mut self, request: Request) -> Response;
@@ -76,6 +82,8 @@ where
 pub enum ServerError {
     #[error("Could not deserialize client request: {0}")]
     RequestDeserializationFailure(String),
+    #[error("The component crashed while handling the request.")]
+    ComponentCrashed,
 }
 
 // The communication configuration of the local component.
@@ -4,6 +4,7 @@ use tracing::info;
 
 use crate::component_definitions::ComponentStarter;
 use crate::component_server::{ComponentReplacer, ComponentServerStarter};
+use crate::crash_report::catch_component_panic;
 use crate::errors::{ComponentServerError, ReplaceComponentError};
 
 pub struct WrapperServer<Component> {
@@ -20,7 +21,10 @@ impl<Component: Send> WrapperServer<Component> {
 impl<Component: ComponentStarter + Send> ComponentServerStarter for WrapperServer<Component> {
     async fn start(&mut self) -> Result<(), ComponentServerError> {
         info!("Starting WrapperServer for {}.", short_type_name::<Component>());
-        let res = self.component.start().await.map_err(ComponentServerError::ComponentError);
+        let res =
+            catch_component_panic(&short_type_name::<Component>(), None, self.component.start())
+                .await?
+                .map_err(ComponentServerError::ComponentError);
         info!("Finished running WrapperServer for {}.", short_type_name::<Component>());
         res
     }
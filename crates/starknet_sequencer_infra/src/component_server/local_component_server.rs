@@ -12,6 +12,7 @@ use crate::component_definitions::{
     ComponentStarter,
 };
 use crate::component_server::{ComponentReplacer, ComponentServerStarter};
+use crate::crash_report::catch_component_panic;
 use crate::errors::{ComponentServerError, ReplaceComponentError};
 
 /// The `LocalComponentServer` struct is a generic server that handles requests and responses for a
@@ -127,7 +128,8 @@ where
 {
     async fn start(&mut self) -> Result<(), ComponentServerError> {
         info!("Starting LocalComponentServer for {}.", short_type_name::<Component>());
-        self.component.start().await?;
+        catch_component_panic(&short_type_name::<Component>(), None, self.component.start())
+            .await??;
         request_response_loop(&mut self.rx, &mut self.component).await;
         info!("Finished LocalComponentServer for {}.", short_type_name::<Component>());
         Ok(())
@@ -148,12 +150,17 @@ where
 {
     async fn start(&mut self) -> Result<(), ComponentServerError> {
         let mut component = self.component.clone();
-        let component_future = async move { component.start().await };
+        let component_future = async move {
+            catch_component_panic(&short_type_name::<Component>(), None, component.start()).await
+        };
         let request_response_future = request_response_loop(&mut self.rx, &mut self.component);
 
         tokio::select! {
-            _res = component_future => {
+            res = component_future => {
                 error!("Component stopped.");
+                if let Err(crash_report) = res {
+                    return Err(ComponentServerError::ComponentPanicked(crash_report));
+                }
             }
             _res = request_response_future => {
                 error!("Server stopped.");
@@ -230,7 +237,18 @@ async fn request_response_loop<Request, Response, Component>(
         let tx = request_and_res_tx.tx;
         debug!("Component {} received request {:?}", short_type_name::<Component>(), request);
 
-        let response = component.handle_request(request).await;
+        // A panic while handling the request is reported and the request is dropped, closing its
+        // response channel; the component keeps serving the following requests.
+        let request_description = format!("{request:?}");
+        let Ok(response) = catch_component_panic(
+            &short_type_name::<Component>(),
+            Some(request_description),
+            component.handle_request(request),
+        )
+        .await
+        else {
+            continue;
+        };
         debug!("Component {} is sending response {:?}", short_type_name::<Component>(), response);
 
         // Send the response to the client. This might result in a panic if the client has closed
@@ -15,9 +15,11 @@ use tracing::warn;
 use crate::component_client::{ClientError, LocalComponentClient};
 use crate::component_definitions::{
     ComponentClient,
+    NodeIdentity,
     RemoteServerConfig,
     ServerError,
     APPLICATION_OCTET_STREAM,
+    HANDSHAKE_PATH,
 };
 use crate::component_server::ComponentServerStarter;
 use crate::errors::ComponentServerError;
@@ -42,6 +44,7 @@ use crate::serde_utils::SerdeWrapper;
 ///
 /// - `component`: The component responsible for handling the requests and generating responses.
 /// - `socket`: A socket address for the server to listen on.
+/// - `node_identity`: The identity of the node running the server, answered on handshakes.
 ///
 /// # Example
 /// ```rust
@@ -113,6 +116,7 @@ where
 {
     socket: SocketAddr,
     local_client: LocalComponentClient<Request, Response>,
+    node_identity: Arc<NodeIdentity>,
 }
 
 impl<Request, Response> RemoteComponentServer<Request, Response>
@@ -124,13 +128,33 @@ where
         local_client: LocalComponentClient<Request, Response>,
         config: RemoteServerConfig,
     ) -> Self {
-        Self { local_client, socket: config.socket }
+        Self { local_client, socket: config.socket, node_identity: Arc::new(NodeIdentity::new()) }
+    }
+
+    /// Sets the node identity answered to remote clients on handshakes.
+    pub fn with_node_identity(mut self, node_identity: NodeIdentity) -> Self {
+        self.node_identity = Arc::new(node_identity);
+        self
     }
 
     async fn remote_component_server_handler(
         http_request: HyperRequest<Body>,
         local_client: LocalComponentClient<Request, Response>,
+        node_identity: Arc<NodeIdentity>,
     ) -> Result<HyperResponse<Body>, hyper::Error> {
+        if http_request.uri().path() == HANDSHAKE_PATH {
+            let http_response = HyperResponse::builder()
+                .status(StatusCode::OK)
+                .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
+                .body(Body::from(
+                    SerdeWrapper::new(node_identity.as_ref().clone())
+                        .wrapper_serialize()
+                        .expect("Node identity serialization should succeed"),
+                ))
+                .expect("Response building should succeed");
+            return Ok(http_response);
+        }
+
         let body_bytes = to_bytes(http_request.into_body()).await?;
 
         let http_response = match SerdeWrapper::<Request>::wrapper_deserialize(&body_bytes)
@@ -147,6 +171,13 @@ where
                                 .wrapper_serialize()
                                 .expect("Response serialization should succeed"),
                         )),
+                    Err(ClientError::ComponentCrashed) => HyperResponse::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from(
+                            SerdeWrapper::new(ServerError::ComponentCrashed)
+                                .wrapper_serialize()
+                                .expect("Server error serialization should succeed"),
+                        )),
                     Err(error) => {
                         panic!(
                             "Remote server failed sending with its local client. Error: {:?}",
@@ -179,9 +210,14 @@ where
     async fn start(&mut self) -> Result<(), ComponentServerError> {
         let make_svc = make_service_fn(|_conn| {
             let local_client = self.local_client.clone();
+            let node_identity = self.node_identity.clone();
             async {
                 Ok::<_, hyper::Error>(service_fn(move |req| {
-                    Self::remote_component_server_handler(req, local_client.clone())
+                    Self::remote_component_server_handler(
+                        req,
+                        local_client.clone(),
+                        node_identity.clone(),
+                    )
                 }))
             }
         });
@@ -0,0 +1,133 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{LazyLock, Mutex, Once};
+
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+#[cfg(test)]
+#[path = "crash_report_test.rs"]
+mod crash_report_test;
+
+// The number of most recent crash reports kept for monitoring.
+const MAX_RETAINED_CRASH_REPORTS: usize = 100;
+const CRASH_REPORTS_CHANNEL_CAPACITY: usize = 16;
+
+/// A panic caught at a component's boundary.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CrashReport {
+    pub component: String,
+    /// The request the component was handling; [None] if it panicked outside of a request, e.g.,
+    /// while starting.
+    pub request: Option<String>,
+    pub message: String,
+    pub backtrace: String,
+}
+
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Component {} panicked", self.component)?;
+        if let Some(request) = &self.request {
+            write!(f, " while handling request {request}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for CrashReport {}
+
+struct CrashReports {
+    recent: Mutex<VecDeque<CrashReport>>,
+    sender: broadcast::Sender<CrashReport>,
+}
+
+static CRASH_REPORTS: LazyLock<CrashReports> = LazyLock::new(|| CrashReports {
+    recent: Mutex::new(VecDeque::with_capacity(MAX_RETAINED_CRASH_REPORTS)),
+    sender: broadcast::channel(CRASH_REPORTS_CHANNEL_CAPACITY).0,
+});
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    // The backtrace of the last panic on this thread, captured by the panic hook.
+    static PANIC_BACKTRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Subscribes to the crash reports of all components, delivered as they occur.
+pub fn subscribe_to_crash_reports() -> broadcast::Receiver<CrashReport> {
+    CRASH_REPORTS.sender.subscribe()
+}
+
+/// Returns the most recent crash reports, oldest first.
+pub fn recent_crash_reports() -> Vec<CrashReport> {
+    CRASH_REPORTS.recent.lock().expect("Crash reports lock is poisoned.").iter().cloned().collect()
+}
+
+/// Runs the given future of `component`, converting a panic into a crash report which is
+/// published to the crash report subscribers and returned.
+///
+/// Note: the component may be left in an inconsistent state by the panic; it is up to the caller
+/// to decide whether to keep using it.
+pub async fn catch_component_panic<T>(
+    component: &str,
+    request: Option<String>,
+    future: impl Future<Output = T>,
+) -> Result<T, CrashReport> {
+    install_panic_hook();
+    let panic_payload = match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(output) => return Ok(output),
+        Err(panic_payload) => panic_payload,
+    };
+
+    let backtrace = PANIC_BACKTRACE.with(|backtrace| backtrace.borrow_mut().take());
+    let crash_report = CrashReport {
+        component: component.to_string(),
+        request,
+        message: panic_message(panic_payload.as_ref()),
+        backtrace: backtrace.unwrap_or_default(),
+    };
+    publish_crash_report(crash_report.clone());
+
+    Err(crash_report)
+}
+
+fn publish_crash_report(crash_report: CrashReport) {
+    {
+        let mut recent = CRASH_REPORTS.recent.lock().expect("Crash reports lock is poisoned.");
+        if recent.len() == MAX_RETAINED_CRASH_REPORTS {
+            recent.pop_front();
+        }
+        recent.push_back(crash_report.clone());
+    }
+    // Sending fails only if there are no subscribers, in which case the report is only retained.
+    let _ = CRASH_REPORTS.sender.send(crash_report);
+}
+
+// Chains a hook capturing the backtrace of every panic to the existing panic hook; the backtrace
+// is no longer available once the panic unwinds to the component's boundary.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let backtrace = Backtrace::force_capture().to_string();
+            PANIC_BACKTRACE.with(|panic_backtrace| *panic_backtrace.borrow_mut() = Some(backtrace));
+            previous_hook(panic_info);
+        }));
+    });
+}
+
+fn panic_message(panic_payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic_payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic_payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic payload.".to_string()
+    }
+}
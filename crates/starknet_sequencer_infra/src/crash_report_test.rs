@@ -0,0 +1,101 @@
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::channel;
+use tokio::task;
+
+use crate::component_client::{ClientError, LocalComponentClient};
+use crate::component_definitions::{
+    ComponentClient,
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+    ComponentStarter,
+};
+use crate::component_server::{ComponentServerStarter, LocalComponentServer};
+use crate::crash_report::{
+    catch_component_panic,
+    recent_crash_reports,
+    subscribe_to_crash_reports,
+    CrashReport,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+enum PanickingComponentRequest {
+    Echo(u64),
+    Panic,
+}
+
+struct PanickingComponent;
+
+impl ComponentStarter for PanickingComponent {}
+
+#[async_trait]
+impl ComponentRequestHandler<PanickingComponentRequest, u64> for PanickingComponent {
+    async fn handle_request(&mut self, request: PanickingComponentRequest) -> u64 {
+        match request {
+            PanickingComponentRequest::Echo(value) => value,
+            PanickingComponentRequest::Panic => panic!("Malformed request."),
+        }
+    }
+}
+
+#[tokio::test]
+async fn no_panic() {
+    assert_eq!(catch_component_panic("Component", None, async { 7 }).await, Ok(7));
+}
+
+#[tokio::test]
+async fn panic_is_reported() {
+    let mut crash_reports = subscribe_to_crash_reports();
+    let component = "ReportedComponent";
+
+    let crash_report = catch_component_panic(component, Some("Request".to_string()), async {
+        panic!("Boom.");
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(crash_report.component, component);
+    assert_eq!(crash_report.request, Some("Request".to_string()));
+    assert_eq!(crash_report.message, "Boom.");
+    assert!(!crash_report.backtrace.is_empty());
+
+    // Reports of other tests may be interleaved.
+    let delivered_report = loop {
+        let report = crash_reports.recv().await.unwrap();
+        if report.component == component {
+            break report;
+        }
+    };
+    assert_eq!(delivered_report, crash_report);
+    assert!(recent_crash_reports().contains(&crash_report));
+}
+
+#[tokio::test]
+async fn server_survives_request_panic() {
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<PanickingComponentRequest, u64>>(32);
+    let client = LocalComponentClient::new(tx);
+    let mut server = LocalComponentServer::new(PanickingComponent, rx);
+    task::spawn(async move {
+        let _ = server.start().await;
+    });
+
+    assert_matches!(
+        client.send(PanickingComponentRequest::Panic).await,
+        Err(ClientError::ComponentCrashed)
+    );
+    assert_eq!(client.send(PanickingComponentRequest::Echo(3)).await.unwrap(), 3);
+
+    let expected_report = CrashReport {
+        component: "PanickingComponent".to_string(),
+        request: Some("Panic".to_string()),
+        message: "Malformed request.".to_string(),
+        backtrace: String::new(),
+    };
+    assert!(
+        recent_crash_reports()
+            .iter()
+            .any(|report| CrashReport { backtrace: String::new(), ..report.clone() }
+                == expected_report)
+    );
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::crash_report::CrashReport;
+
 #[derive(Error, Debug, PartialEq, Clone)]
 pub enum ComponentError {
     #[error("Error in the component configuration.")]
@@ -16,6 +18,8 @@ pub enum ComponentServerError {
     HttpServerStartError(String),
     #[error("Server unexpectedly stopped.")]
     ServerUnexpectedlyStopped,
+    #[error(transparent)]
+    ComponentPanicked(#[from] CrashReport),
 }
 
 #[derive(Clone, Debug, Error)]
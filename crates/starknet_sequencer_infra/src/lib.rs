@@ -1,8 +1,10 @@
 pub mod component_client;
 pub mod component_definitions;
 pub mod component_server;
+pub mod crash_report;
 pub mod errors;
 pub mod serde_utils;
+pub mod signer;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
 #[cfg(test)]
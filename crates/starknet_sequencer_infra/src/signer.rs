@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use papyrus_config::dumping::{ser_required_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::crypto::utils::{PublicKey, Signature};
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "signer_test.rs"]
+mod signer_test;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum SignerError {
+    #[error("Failed signing message hash {message_hash}: {reason}")]
+    SigningFailed { message_hash: Felt, reason: String },
+}
+
+pub type SignerResult<T> = Result<T, SignerError>;
+
+/// Signs message hashes with the sequencer's key, so that components never handle the key itself.
+pub trait SignerBackend: Send + Sync {
+    fn public_key(&self) -> PublicKey;
+
+    fn sign(&self, message_hash: Felt) -> SignerResult<Signature>;
+}
+
+pub type SharedSigner = Arc<dyn SignerBackend>;
+
+/// The private key has no default; a zero key stands for an unset one, and signers refuse it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct SignerConfig {
+    pub private_key: Felt,
+}
+
+impl SerializeConfig for SignerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_required_param(
+            "private_key",
+            SerializationType::String,
+            "The Stark private key the sequencer signs with.",
+            ParamPrivacyInput::Private,
+        )])
+    }
+}
+
+/// A signer backend holding the private key in memory.
+pub struct LocalKeySigner {
+    private_key: Felt,
+    public_key: PublicKey,
+}
+
+impl LocalKeySigner {
+    pub fn new(config: &SignerConfig) -> Self {
+        assert_ne!(config.private_key, Felt::ZERO, "The signer private key must be set.");
+        let public_key = PublicKey(starknet_crypto::get_public_key(&config.private_key));
+        Self { private_key: config.private_key, public_key }
+    }
+}
+
+impl SignerBackend for LocalKeySigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign(&self, message_hash: Felt) -> SignerResult<Signature> {
+        // Deterministic nonce generation, so that no randomness source is needed.
+        let k = starknet_crypto::rfc6979_generate_k(&message_hash, &self.private_key, None);
+        let signature = starknet_crypto::sign(&self.private_key, &message_hash, &k)
+            .map_err(|err| SignerError::SigningFailed { message_hash, reason: err.to_string() })?;
+        Ok(Signature { r: signature.r, s: signature.s })
+    }
+}
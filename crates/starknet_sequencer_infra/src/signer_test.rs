@@ -0,0 +1,24 @@
+use starknet_api::crypto::utils::verify_message_hash_signature;
+use starknet_types_core::felt::Felt;
+
+use crate::signer::{LocalKeySigner, SignerBackend, SignerConfig};
+
+#[test]
+fn local_key_signer_signature_is_verifiable() {
+    let signer = LocalKeySigner::new(&SignerConfig { private_key: Felt::from(1234_u32) });
+    let message_hash = Felt::from(5678_u32);
+
+    let signature = signer.sign(message_hash).unwrap();
+
+    assert!(
+        verify_message_hash_signature(&message_hash, &signature, &signer.public_key()).unwrap()
+    );
+    assert!(
+        !verify_message_hash_signature(
+            &(message_hash + Felt::ONE),
+            &signature,
+            &signer.public_key()
+        )
+        .unwrap()
+    );
+}
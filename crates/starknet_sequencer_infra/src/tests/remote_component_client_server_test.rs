@@ -24,6 +24,7 @@ use crate::component_client::{
 use crate::component_definitions::{
     ComponentClient,
     ComponentRequestAndResponseSender,
+    NodeIdentity,
     RemoteClientConfig,
     RemoteServerConfig,
     ServerError,
@@ -335,3 +336,24 @@ async fn test_retry_request() {
     let expected_error_contained_keywords = [StatusCode::IM_A_TEAPOT.as_str()];
     verify_error(a_client_no_retry.clone(), &expected_error_contained_keywords).await;
 }
+
+#[tokio::test]
+async fn test_handshake() {
+    let socket = get_available_socket().await;
+    let node_identity = NodeIdentity::from([("chain_id".to_string(), "SN_MAIN".to_string())]);
+
+    let (tx_a, _rx_a) =
+        channel::<ComponentRequestAndResponseSender<ComponentARequest, ComponentAResponse>>(32);
+    let a_local_client = LocalComponentClient::<ComponentARequest, ComponentAResponse>::new(tx_a);
+    let mut remote_server =
+        RemoteComponentServer::new(a_local_client, RemoteServerConfig { socket })
+            .with_node_identity(node_identity.clone());
+    task::spawn(async move {
+        let _ = remote_server.start().await;
+    });
+    // Todo(uriel): Get rid of this
+    task::yield_now().await;
+
+    let client = ComponentAClient::new(RemoteClientConfig { socket, ..Default::default() });
+    assert_eq!(client.handshake().await.unwrap(), node_identity);
+}
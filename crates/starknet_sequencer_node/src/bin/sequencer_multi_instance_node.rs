@@ -0,0 +1,21 @@
+use std::env::args;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use starknet_sequencer_infra::trace_util::configure_tracing;
+use starknet_sequencer_node::multi_instance::{run_instances, MultiInstanceConfig};
+use tracing::info;
+
+/// Runs several independent sequencer instances in one process:
+/// cargo run --bin sequencer_multi_instance_node -- <multi_instance_config_path>
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    configure_tracing().await;
+
+    let config_path: PathBuf =
+        args().nth(1).context("Expected the path of the multi-instance config.")?.into();
+    let instance_configs = MultiInstanceConfig::load(&config_path)?.instance_configs()?;
+    info!("Finished loading the configuration of {} instances.", instance_configs.len());
+
+    run_instances(instance_configs).await
+}
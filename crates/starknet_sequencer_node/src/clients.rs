@@ -31,6 +31,7 @@ use starknet_mempool_types::communication::{
     SharedMempoolClient,
 };
 use starknet_sequencer_infra::component_client::{Client, LocalComponentClient};
+use starknet_sequencer_infra::component_definitions::NodeIdentity;
 use starknet_state_sync_types::communication::{
     LocalStateSyncClient,
     RemoteStateSyncClient,
@@ -42,6 +43,7 @@ use starknet_state_sync_types::communication::{
 use crate::communication::SequencerNodeCommunication;
 use crate::config::component_execution_config::ReactiveComponentExecutionMode;
 use crate::config::node_config::SequencerNodeConfig;
+use crate::node_identity::verify_remote_node_identity;
 
 pub struct SequencerNodeClients {
     batcher_client: Client<BatcherRequest, BatcherResponse>,
@@ -158,6 +160,47 @@ impl SequencerNodeClients {
     ) -> Option<LocalComponentClient<StateSyncRequest, StateSyncResponse>> {
         self.state_sync_client.get_local_client()
     }
+
+    /// Verifies all remote components agree with this node on its identity, waiting for each of
+    /// them to answer a handshake.
+    pub async fn verify_remote_node_identities(
+        &self,
+        node_identity: &NodeIdentity,
+    ) -> anyhow::Result<()> {
+        tokio::try_join!(
+            verify_remote_node_identity(
+                "Batcher",
+                self.batcher_client.get_remote_client(),
+                node_identity
+            ),
+            verify_remote_node_identity(
+                "Mempool",
+                self.mempool_client.get_remote_client(),
+                node_identity
+            ),
+            verify_remote_node_identity(
+                "Gateway",
+                self.gateway_client.get_remote_client(),
+                node_identity
+            ),
+            verify_remote_node_identity(
+                "Mempool P2P Propagator",
+                self.mempool_p2p_propagator_client.get_remote_client(),
+                node_identity
+            ),
+            verify_remote_node_identity(
+                "State Sync",
+                self.state_sync_client.get_remote_client(),
+                node_identity
+            ),
+            verify_remote_node_identity(
+                "L1 Provider",
+                self.l1_provider_client.get_remote_client(),
+                node_identity
+            ),
+        )?;
+        Ok(())
+    }
 }
 
 /// A macro for creating a component client fitting the component's execution mode. Returns a
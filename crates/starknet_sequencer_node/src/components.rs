@@ -1,7 +1,9 @@
+use blockifier::state::contract_class_manager::ContractClassManager;
 use starknet_batcher::batcher::{create_batcher, Batcher};
 use starknet_consensus_manager::consensus_manager::ConsensusManager;
 use starknet_gateway::gateway::{create_gateway, Gateway};
 use starknet_http_server::http_server::{create_http_server, HttpServer};
+use starknet_l1_provider::l1_scraper::{create_l1_scraper, EthereumL1Scraper};
 use starknet_l1_provider::{create_l1_provider, L1Provider};
 use starknet_mempool::communication::{create_mempool, MempoolCommunicationWrapper};
 use starknet_mempool_p2p::create_p2p_propagator_and_runner;
@@ -28,6 +30,7 @@ pub struct SequencerNodeComponents {
     pub gateway: Option<Gateway>,
     pub http_server: Option<HttpServer>,
     pub l1_provider: Option<L1Provider>,
+    pub l1_scraper: Option<EthereumL1Scraper>,
     pub mempool: Option<MempoolCommunicationWrapper>,
     pub monitoring_endpoint: Option<MonitoringEndpoint>,
     pub mempool_p2p_propagator: Option<MempoolP2pPropagator>,
@@ -40,6 +43,9 @@ pub fn create_node_components(
     config: &SequencerNodeConfig,
     clients: &SequencerNodeClients,
 ) -> SequencerNodeComponents {
+    // The compiled classes are cached once per node, and shared by the batcher and the gateway.
+    let contract_class_manager =
+        ContractClassManager::start(config.batcher_config.contract_class_manager_config.clone());
     let batcher = match config.components.batcher.execution_mode {
         ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
@@ -48,7 +54,12 @@ pub fn create_node_components(
             let l1_provider_client = clients
                 .get_l1_provider_shared_client()
                 .expect("L1 Provider Client should be available");
-            Some(create_batcher(config.batcher_config.clone(), mempool_client, l1_provider_client))
+            Some(create_batcher(
+                config.batcher_config.clone(),
+                mempool_client,
+                l1_provider_client,
+                contract_class_manager.clone(),
+            ))
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
     };
@@ -78,6 +89,7 @@ pub fn create_node_components(
                 config.rpc_state_reader_config.clone(),
                 config.compiler_config.clone(),
                 mempool_client,
+                contract_class_manager.clone(),
             ))
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
@@ -87,28 +99,39 @@ pub fn create_node_components(
             let gateway_client =
                 clients.get_gateway_shared_client().expect("Gateway Client should be available");
 
-            Some(create_http_server(config.http_server_config.clone(), gateway_client))
+            let batcher_client =
+                clients.get_batcher_shared_client().expect("Batcher Client should be available");
+
+            Some(create_http_server(
+                config.http_server_config.clone(),
+                gateway_client,
+                batcher_client,
+            ))
         }
         ActiveComponentExecutionMode::Disabled => None,
     };
 
-    let (mempool_p2p_propagator, mempool_p2p_runner) = match config
-        .components
-        .mempool_p2p
-        .execution_mode
-    {
-        ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
-        | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
-            let gateway_client =
-                clients.get_gateway_shared_client().expect("Gateway Client should be available");
-            let (mempool_p2p_propagator, mempool_p2p_runner) =
-                create_p2p_propagator_and_runner(config.mempool_p2p_config.clone(), gateway_client);
-            (Some(mempool_p2p_propagator), Some(mempool_p2p_runner))
-        }
-        ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
-            (None, None)
-        }
-    };
+    let (mempool_p2p_propagator, mempool_p2p_runner) =
+        match config.components.mempool_p2p.execution_mode {
+            ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+            | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
+                let gateway_client = clients
+                    .get_gateway_shared_client()
+                    .expect("Gateway Client should be available");
+                let mempool_client = clients
+                    .get_mempool_shared_client()
+                    .expect("Mempool Client should be available");
+                let (mempool_p2p_propagator, mempool_p2p_runner) = create_p2p_propagator_and_runner(
+                    config.mempool_p2p_config.clone(),
+                    gateway_client,
+                    mempool_client,
+                );
+                (Some(mempool_p2p_propagator), Some(mempool_p2p_runner))
+            }
+            ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
+                (None, None)
+            }
+        };
 
     let mempool = match config.components.mempool.execution_mode {
         ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
@@ -116,7 +139,13 @@ pub fn create_node_components(
             let mempool_p2p_propagator_client = clients
                 .get_mempool_p2p_propagator_shared_client()
                 .expect("Propagator Client should be available");
-            let mempool = create_mempool(mempool_p2p_propagator_client);
+            let gateway_client =
+                clients.get_gateway_shared_client().expect("Gateway Client should be available");
+            let mempool = create_mempool(
+                config.mempool_config.clone(),
+                mempool_p2p_propagator_client,
+                gateway_client,
+            );
             Some(mempool)
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
@@ -142,12 +171,25 @@ pub fn create_node_components(
         }
     };
 
-    let l1_provider = match config.components.l1_provider.execution_mode {
+    let (l1_provider, l1_scraper) = match config.components.l1_provider.execution_mode {
         ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
-            Some(create_l1_provider(config.l1_provider_config.clone()))
+            let l1_scraper =
+                config.l1_provider_config.l1_scraper_config.clone().map(|l1_scraper_config| {
+                    let l1_provider_client = clients
+                        .get_l1_provider_shared_client()
+                        .expect("L1 Provider Client should be available");
+                    create_l1_scraper(
+                        l1_scraper_config,
+                        config.l1_provider_config.poll_interval,
+                        l1_provider_client,
+                    )
+                });
+            (Some(create_l1_provider(config.l1_provider_config.clone())), l1_scraper)
+        }
+        ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
+            (None, None)
         }
-        ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
     };
 
     SequencerNodeComponents {
@@ -156,6 +198,7 @@ pub fn create_node_components(
         gateway,
         http_server,
         l1_provider,
+        l1_scraper,
         mempool,
         monitoring_endpoint,
         mempool_p2p_propagator,
@@ -4,6 +4,7 @@ use std::fs::File;
 
 use assert_json_diff::assert_json_eq;
 use assert_matches::assert_matches;
+use blockifier::bouncer::BouncerConfig;
 use colored::Colorize;
 use infra_utils::path::resolve_project_relative_path;
 use papyrus_config::dumping::SerializeConfig;
@@ -12,6 +13,7 @@ use papyrus_config::SerializedParam;
 use rstest::rstest;
 use starknet_batcher::block_builder::BlockBuilderConfig;
 use starknet_batcher::config::BatcherConfig;
+use starknet_mempool::config::MempoolConfig;
 use starknet_sequencer_infra::component_definitions::{
     LocalServerConfig,
     RemoteClientConfig,
@@ -19,6 +21,7 @@ use starknet_sequencer_infra::component_definitions::{
 };
 use validator::Validate;
 
+use crate::config::component_config::ComponentConfig;
 use crate::config::component_execution_config::{
     ReactiveComponentExecutionConfig,
     ReactiveComponentExecutionMode,
@@ -165,3 +168,64 @@ fn test_validate_batcher_config_failure() {
             .contains("input_stream_content_buffer_size must be at least tx_chunk_size")
     );
 }
+
+#[test]
+fn test_validate_reserved_capacity_percent() {
+    let config = SequencerNodeConfig {
+        batcher_config: BatcherConfig {
+            block_builder_config: BlockBuilderConfig {
+                bouncer_config: BouncerConfig {
+                    reserved_capacity_percent: 101,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let error = config.validate().unwrap_err();
+    assert!(
+        error.to_string().contains("The reserved capacity is 101%, more than the whole block.")
+    );
+}
+
+#[test]
+fn test_validate_forward_only_mempool() {
+    let mut config = SequencerNodeConfig {
+        mempool_config: MempoolConfig { forward_only: true, ..Default::default() },
+        ..Default::default()
+    };
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("A forward-only mempool cannot serve a batcher."));
+
+    // An ingress node runs no batcher.
+    config.components = ComponentConfig {
+        batcher: ReactiveComponentExecutionConfig::disabled(),
+        ..Default::default()
+    };
+    assert_matches!(config.validate(), Ok(()));
+}
+
+#[rstest]
+#[case::free_replacement(
+    MempoolConfig { fee_escalation_percentage: 0, ..Default::default() },
+    "fee_escalation_percentage"
+)]
+#[case::empty_pool(
+    MempoolConfig { max_pool_size_in_bytes: 0, max_account_size_in_bytes: 0, ..Default::default() },
+    "max_pool_size_in_bytes"
+)]
+#[case::account_larger_than_pool(
+    MempoolConfig { max_pool_size_in_bytes: 1, max_account_size_in_bytes: 2, ..Default::default() },
+    "max_account_size_in_bytes must be at most max_pool_size_in_bytes"
+)]
+fn test_validate_mempool_config_failure(
+    #[case] mempool_config: MempoolConfig,
+    #[case] expected_error: &str,
+) {
+    let config = SequencerNodeConfig { mempool_config, ..Default::default() };
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains(expected_error), "Unexpected error: {error}");
+}
@@ -4,11 +4,13 @@ use std::path::Path;
 use std::sync::LazyLock;
 use std::vec::Vec;
 
+use blockifier::context::{CustomFeeTokens, FeeDistribution, PaymasterConfig};
 use clap::Command;
 use infra_utils::path::resolve_project_relative_path;
 use papyrus_config::dumping::{
     append_sub_config_name,
     generate_struct_pointer,
+    ser_pointer_target_param,
     ser_pointer_target_required_param,
     set_pointing_param_paths,
     ConfigPointers,
@@ -18,19 +20,23 @@ use papyrus_config::dumping::{
 use papyrus_config::loading::load_and_process_config;
 use papyrus_config::{ConfigError, ParamPath, SerializationType, SerializedParam};
 use serde::{Deserialize, Serialize};
+use starknet_api::block::StarknetVersionSchedule;
+use starknet_api::transaction::TransactionVersions;
 use starknet_batcher::config::BatcherConfig;
 use starknet_batcher::VersionedConstantsOverrides;
 use starknet_consensus_manager::config::ConsensusManagerConfig;
 use starknet_gateway::config::{GatewayConfig, RpcStateReaderConfig};
 use starknet_http_server::config::HttpServerConfig;
 use starknet_l1_provider::L1ProviderConfig;
+use starknet_mempool::config::MempoolConfig;
 use starknet_mempool_p2p::config::MempoolP2pConfig;
 use starknet_monitoring_endpoint::config::MonitoringEndpointConfig;
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 use starknet_state_sync::config::StateSyncConfig;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use crate::config::component_config::ComponentConfig;
+use crate::config::component_execution_config::ReactiveComponentExecutionMode;
 use crate::version::VERSION_FULL;
 
 // The path of the default configuration file, provided as part of the crate.
@@ -51,6 +57,7 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
                 "consensus_manager_config.consensus_config.chain_id",
                 "consensus_manager_config.consensus_config.network_config.chain_id",
                 "gateway_config.chain_info.chain_id",
+                "l1_provider_config.l1_scraper_config.bridge_config.chain_id",
                 "mempool_p2p_config.network_config.chain_id",
                 "state_sync_config.storage_config.db_config.chain_id",
                 "state_sync_config.network_config.chain_id",
@@ -89,6 +96,71 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
             ),
             set_pointing_param_paths(&["consensus_manager_config.consensus_config.validator_id"]),
         ),
+        (
+            ser_pointer_target_required_param(
+                "signer_private_key",
+                SerializationType::String,
+                "The Stark private key the sequencer signs gateway receipts, external block \
+                 attestations and the randomness messages of its proposals with.",
+            ),
+            set_pointing_param_paths(&[
+                "batcher_config.signer_config.private_key",
+                "consensus_manager_config.signer_config.private_key",
+                "gateway_config.signer_config.private_key",
+            ]),
+        ),
+        (
+            ser_pointer_target_param(
+                "starknet_version_schedule",
+                &StarknetVersionSchedule::default(),
+                "Space-separated <block_number>:<starknet_version> and \
+                 t<unix_timestamp>:<starknet_version> pairs scheduling protocol upgrades; a \
+                 version must be scheduled for block 0. Each block is built and validated with \
+                 the highest version activated by its height or timestamp.",
+            ),
+            set_pointing_param_paths(&[
+                "batcher_config.block_builder_config.starknet_version_schedule",
+                "gateway_config.stateful_tx_validator_config.starknet_version_schedule",
+            ]),
+        ),
+        (
+            ser_pointer_target_param(
+                "custom_fee_tokens",
+                &CustomFeeTokens::default(),
+                "Space-separated <fee_type>:<address>:<decimals> fee tokens charged instead of the \
+                 default fee token of their fee type, e.g. \"strk:0x1234:6\".",
+            ),
+            set_pointing_param_paths(&[
+                "batcher_config.block_builder_config.chain_info.custom_fee_tokens",
+                "gateway_config.chain_info.custom_fee_tokens",
+            ]),
+        ),
+        (
+            ser_pointer_target_param(
+                "disabled_tx_versions",
+                &TransactionVersions::default(),
+                "Space-separated account transaction versions that are neither accepted nor \
+                 executed on the chain, e.g. \"0 1\".",
+            ),
+            set_pointing_param_paths(&[
+                "batcher_config.block_builder_config.chain_info.disabled_tx_versions",
+                "gateway_config.chain_info.disabled_tx_versions",
+                "mempool_config.disabled_tx_versions",
+            ]),
+        ),
+        (
+            ser_pointer_target_param(
+                "sierra_gas_only",
+                &false,
+                "If true, transactions run entirely under Sierra gas accounting: Cairo 0 classes \
+                 may not be executed, and transactions must bound all resources, including L2 \
+                 gas.",
+            ),
+            set_pointing_param_paths(&[
+                "batcher_config.block_builder_config.chain_info.sierra_gas_only",
+                "gateway_config.chain_info.sierra_gas_only",
+            ]),
+        ),
     ];
     let mut common_execution_config = generate_struct_pointer(
         "versioned_constants_overrides".to_owned(),
@@ -99,6 +171,24 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
         ]),
     );
     pointers.append(&mut common_execution_config);
+    let mut fee_distribution = generate_struct_pointer(
+        "fee_distribution".to_owned(),
+        &FeeDistribution::default(),
+        set_pointing_param_paths(&[
+            "batcher_config.block_builder_config.chain_info.fee_distribution",
+            "gateway_config.chain_info.fee_distribution",
+        ]),
+    );
+    pointers.append(&mut fee_distribution);
+    let mut paymaster = generate_struct_pointer(
+        "paymaster".to_owned(),
+        &PaymasterConfig::default(),
+        set_pointing_param_paths(&[
+            "batcher_config.block_builder_config.chain_info.paymaster",
+            "gateway_config.chain_info.paymaster",
+        ]),
+    );
+    pointers.append(&mut paymaster);
     pointers
 });
 
@@ -108,6 +198,7 @@ pub static CONFIG_NON_POINTERS_WHITELIST: LazyLock<Pointers> =
 
 /// The configurations of the various components of the node.
 #[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq, Validate)]
+#[validate(schema(function = "validate_node_config"))]
 pub struct SequencerNodeConfig {
     #[validate]
     pub components: ComponentConfig,
@@ -126,6 +217,8 @@ pub struct SequencerNodeConfig {
     #[validate]
     pub l1_provider_config: L1ProviderConfig,
     #[validate]
+    pub mempool_config: MempoolConfig,
+    #[validate]
     pub mempool_p2p_config: MempoolP2pConfig,
     #[validate]
     pub monitoring_endpoint_config: MonitoringEndpointConfig,
@@ -146,6 +239,7 @@ impl SerializeConfig for SequencerNodeConfig {
             append_sub_config_name(self.http_server_config.dump(), "http_server_config"),
             append_sub_config_name(self.rpc_state_reader_config.dump(), "rpc_state_reader_config"),
             append_sub_config_name(self.compiler_config.dump(), "compiler_config"),
+            append_sub_config_name(self.mempool_config.dump(), "mempool_config"),
             append_sub_config_name(self.mempool_p2p_config.dump(), "mempool_p2p_config"),
             append_sub_config_name(
                 self.monitoring_endpoint_config.dump(),
@@ -159,6 +253,24 @@ impl SerializeConfig for SequencerNodeConfig {
     }
 }
 
+// A forward-only mempool, e.g. of an ingress node, holds no transactions to build blocks from, and
+// relies on the mempool p2p component to propagate the transactions it admits.
+fn validate_node_config(config: &SequencerNodeConfig) -> Result<(), ValidationError> {
+    if !config.mempool_config.forward_only {
+        return Ok(());
+    }
+    if config.components.batcher.execution_mode != ReactiveComponentExecutionMode::Disabled {
+        return Err(ValidationError::new("A forward-only mempool cannot serve a batcher."));
+    }
+    match config.components.mempool_p2p.execution_mode {
+        ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+        | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => Ok(()),
+        ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => Err(
+            ValidationError::new("A forward-only mempool requires a local mempool p2p component."),
+        ),
+    }
+}
+
 impl SequencerNodeConfig {
     /// Creates a config object. Selects the values from the default file and from resources with
     /// higher priority.
@@ -2,6 +2,8 @@ pub mod clients;
 pub mod communication;
 pub mod components;
 pub mod config;
+pub mod multi_instance;
+pub mod node_identity;
 pub mod servers;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
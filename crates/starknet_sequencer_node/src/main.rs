@@ -5,8 +5,7 @@ use papyrus_config::validators::config_validate;
 use papyrus_config::ConfigError;
 use starknet_sequencer_infra::trace_util::configure_tracing;
 use starknet_sequencer_node::config::node_config::SequencerNodeConfig;
-use starknet_sequencer_node::servers::run_component_servers;
-use starknet_sequencer_node::utils::create_node_modules;
+use starknet_sequencer_node::utils::run_node;
 use tracing::{error, info};
 
 #[tokio::main]
@@ -27,11 +26,7 @@ async fn main() -> anyhow::Result<()> {
     }
     info!("Finished validating configuration.");
 
-    // Clients are currently unused, but should not be dropped.
-    let (_clients, servers) = create_node_modules(&config);
-
-    info!("Starting components!");
-    run_component_servers(servers).await?;
+    run_node(&config).await?;
 
     // TODO(Tsabary): Add graceful shutdown.
     Ok(())
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use futures::future::try_join_all;
+use papyrus_config::validators::config_validate;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tracing::{info, info_span, Instrument};
+
+use crate::config::component_execution_config::{
+    ActiveComponentExecutionMode,
+    ReactiveComponentExecutionConfig,
+    ReactiveComponentExecutionMode,
+};
+use crate::config::node_config::{node_command, SequencerNodeConfig};
+use crate::utils::run_node;
+
+#[cfg(test)]
+#[path = "multi_instance_test.rs"]
+mod multi_instance_test;
+
+/// The configuration of a node process hosting several independent sequencer instances.
+///
+/// Each instance is configured by the param path to value overrides it applies on top of the
+/// default node config, e.g. `{"chain_id": "APPCHAIN_A", "http_server_config.port": 8080}`. Values
+/// set through environment variables apply to all the instances.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct MultiInstanceConfig {
+    pub instances: BTreeMap<String, Map<String, Value>>,
+}
+
+impl MultiInstanceConfig {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed opening multi-instance config {}.", path.display()))?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Loads and validates the node config of every instance. The instances share the process'
+    /// monitoring endpoint, which is run by the first instance only; the metrics recorder is
+    /// global, so it reports the metrics of all the instances.
+    pub fn instance_configs(&self) -> anyhow::Result<Vec<(String, SequencerNodeConfig)>> {
+        let mut configs = Vec::with_capacity(self.instances.len());
+        for (index, (name, overrides)) in self.instances.iter().enumerate() {
+            let mut config = SequencerNodeConfig::load_and_process(instance_args(overrides))
+                .with_context(|| format!("Failed loading the config of instance {name}."))?;
+            if index > 0 {
+                config.components.monitoring_endpoint.execution_mode =
+                    ActiveComponentExecutionMode::Disabled;
+            }
+            config_validate(&config)
+                .with_context(|| format!("Invalid config of instance {name}."))?;
+            configs.push((name.clone(), config));
+        }
+        validate_instances_are_independent(&configs)?;
+        Ok(configs)
+    }
+}
+
+/// Converts the overrides of an instance into command line arguments of the node.
+fn instance_args(overrides: &Map<String, Value>) -> Vec<String> {
+    let mut args = vec![node_command().to_string()];
+    for (param_path, value) in overrides {
+        args.push(format!("--{param_path}"));
+        args.push(match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        });
+    }
+    args
+}
+
+/// Verifies no two instances follow the same chain, which also keeps their storages apart, or
+/// listen on the same port.
+fn validate_instances_are_independent(
+    configs: &[(String, SequencerNodeConfig)],
+) -> anyhow::Result<()> {
+    let mut chain_ids = HashMap::new();
+    let mut ports = HashMap::new();
+    for (name, config) in configs {
+        let chain_id = &config.batcher_config.block_builder_config.chain_info.chain_id;
+        if let Some(other) = chain_ids.insert(chain_id.clone(), name) {
+            bail!("Instances {other} and {name} both follow chain {chain_id}.");
+        }
+        for (server, port) in listening_ports(config) {
+            if let Some((other, other_server)) = ports.insert(port, (name, server)) {
+                bail!(
+                    "The {other_server} of instance {other} and the {server} of instance {name} \
+                     both listen on port {port}."
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The ports the servers run by the node listen on.
+fn listening_ports(config: &SequencerNodeConfig) -> Vec<(&'static str, u16)> {
+    let components = &config.components;
+    let mut ports = vec![];
+    if components.http_server.execution_mode == ActiveComponentExecutionMode::Enabled {
+        ports.push(("http server", config.http_server_config.port));
+    }
+    if components.monitoring_endpoint.execution_mode == ActiveComponentExecutionMode::Enabled {
+        ports.push(("monitoring endpoint", config.monitoring_endpoint_config.port));
+    }
+    if components.consensus_manager.execution_mode == ActiveComponentExecutionMode::Enabled {
+        ports.push((
+            "consensus network",
+            config.consensus_manager_config.consensus_config.network_config.tcp_port,
+        ));
+    }
+    if runs_locally(&components.mempool_p2p) {
+        ports.push(("mempool p2p network", config.mempool_p2p_config.network_config.tcp_port));
+    }
+    if runs_locally(&components.state_sync) {
+        ports.push(("state sync network", config.state_sync_config.network_config.tcp_port));
+    }
+    let reactive_components = [
+        ("batcher server", &components.batcher),
+        ("gateway server", &components.gateway),
+        ("mempool server", &components.mempool),
+        ("mempool p2p server", &components.mempool_p2p),
+        ("state sync server", &components.state_sync),
+        ("l1 provider server", &components.l1_provider),
+    ];
+    for (label, component) in reactive_components {
+        if let Some(remote_server_config) = &component.remote_server_config {
+            ports.push((label, remote_server_config.socket.port()));
+        }
+    }
+    ports
+}
+
+fn runs_locally(component: &ReactiveComponentExecutionConfig) -> bool {
+    matches!(
+        component.execution_mode,
+        ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled
+            | ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+    )
+}
+
+/// Runs all the instances on the current runtime, until one of them fails.
+pub async fn run_instances(configs: Vec<(String, SequencerNodeConfig)>) -> anyhow::Result<()> {
+    let instances = configs.into_iter().map(|(name, config)| {
+        let span = info_span!("instance", name = %name);
+        async move {
+            info!("Starting instance.");
+            run_node(&config).await
+        }
+        .instrument(span)
+    });
+    try_join_all(instances).await?;
+    Ok(())
+}
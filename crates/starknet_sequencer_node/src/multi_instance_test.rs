@@ -0,0 +1,91 @@
+use serde_json::{json, Map, Value};
+use starknet_api::core::ChainId;
+
+use crate::config::component_execution_config::ActiveComponentExecutionMode;
+use crate::config::test_utils::RequiredParams;
+use crate::multi_instance::MultiInstanceConfig;
+
+/// The overrides of an instance following the given chain, with the ports of its servers shifted
+/// by the given offset.
+fn instance_overrides(chain_id: &str, port_offset: u16) -> Map<String, Value> {
+    let required_params = RequiredParams {
+        chain_id: ChainId::Other(chain_id.to_owned()),
+        ..RequiredParams::create_for_testing()
+    };
+    let mut overrides: Map<String, Value> = required_params
+        .cli_args()
+        .chunks(2)
+        .map(|arg| (arg[0].trim_start_matches("--").to_owned(), json!(arg[1])))
+        .collect();
+    for (param_path, port) in [
+        ("http_server_config.port", 8080),
+        ("consensus_manager_config.consensus_config.network_config.tcp_port", 10100),
+        ("mempool_p2p_config.network_config.tcp_port", 10000),
+        ("state_sync_config.network_config.tcp_port", 12345),
+    ] {
+        overrides.insert(param_path.to_owned(), json!(port + port_offset));
+    }
+    overrides
+}
+
+fn multi_instance_config(instances: Vec<(&str, Map<String, Value>)>) -> MultiInstanceConfig {
+    MultiInstanceConfig {
+        instances: instances
+            .into_iter()
+            .map(|(name, overrides)| (name.to_owned(), overrides))
+            .collect(),
+    }
+}
+
+#[test]
+fn load_independent_instances() {
+    let config = multi_instance_config(vec![
+        ("appchain_a", instance_overrides("APPCHAIN_A", 0)),
+        ("appchain_b", instance_overrides("APPCHAIN_B", 1)),
+    ]);
+
+    let instance_configs = config.instance_configs().unwrap();
+
+    let [(name_a, config_a), (name_b, config_b)] = instance_configs.as_slice() else {
+        panic!("Expected two instances, got {}.", instance_configs.len());
+    };
+    assert_eq!((name_a.as_str(), name_b.as_str()), ("appchain_a", "appchain_b"));
+    assert_eq!(
+        config_a.batcher_config.block_builder_config.chain_info.chain_id,
+        ChainId::Other("APPCHAIN_A".to_owned())
+    );
+    assert_eq!(config_b.http_server_config.port, 8081);
+    // Only the first instance runs the shared monitoring endpoint.
+    assert_eq!(
+        config_a.components.monitoring_endpoint.execution_mode,
+        ActiveComponentExecutionMode::Enabled
+    );
+    assert_eq!(
+        config_b.components.monitoring_endpoint.execution_mode,
+        ActiveComponentExecutionMode::Disabled
+    );
+}
+
+#[test]
+fn instances_following_the_same_chain() {
+    let config = multi_instance_config(vec![
+        ("appchain_a", instance_overrides("APPCHAIN", 0)),
+        ("appchain_b", instance_overrides("APPCHAIN", 1)),
+    ]);
+
+    let error = config.instance_configs().unwrap_err();
+    assert!(error.to_string().contains("both follow chain"), "{error}");
+}
+
+#[test]
+fn instances_listening_on_the_same_port() {
+    let mut overrides_b = instance_overrides("APPCHAIN_B", 1);
+    overrides_b.insert("http_server_config.port".to_owned(), json!(8080));
+    let config = multi_instance_config(vec![
+        ("appchain_a", instance_overrides("APPCHAIN_A", 0)),
+        ("appchain_b", overrides_b),
+    ]);
+
+    let error = config.instance_configs().unwrap_err();
+    assert!(error.to_string().contains("both listen on port 8080"), "{error}");
+}
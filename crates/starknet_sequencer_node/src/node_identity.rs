@@ -0,0 +1,117 @@
+use std::fmt::Debug;
+use std::fs;
+use std::time::Duration;
+
+use blockifier::versioned_constants::{
+    VersionedConstantsOverrides,
+    VERSIONED_CONSTANTS_LATEST_JSON,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use starknet_api::block::StarknetVersion;
+use starknet_api::hash::starknet_keccak_hash;
+use starknet_sequencer_infra::component_client::RemoteComponentClient;
+use starknet_sequencer_infra::component_definitions::NodeIdentity;
+use tracing::{info, warn};
+
+use crate::config::node_config::SequencerNodeConfig;
+
+#[cfg(test)]
+#[path = "node_identity_test.rs"]
+mod node_identity_test;
+
+const HANDSHAKE_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the properties this node must agree on with all the remote components it communicates
+/// with.
+pub fn node_identity(config: &SequencerNodeConfig) -> NodeIdentity {
+    let block_builder_config = &config.batcher_config.block_builder_config;
+    NodeIdentity::from([
+        ("chain_id".to_string(), block_builder_config.chain_info.chain_id.to_string()),
+        ("starknet_version".to_string(), StarknetVersion::LATEST.to_string()),
+        (
+            "starknet_version_schedule".to_string(),
+            block_builder_config.starknet_version_schedule.clone().into(),
+        ),
+        (
+            "start_height".to_string(),
+            config.consensus_manager_config.consensus_config.start_height.to_string(),
+        ),
+        (
+            "versioned_constants_hash".to_string(),
+            versioned_constants_hash(&block_builder_config.versioned_constants_overrides),
+        ),
+    ])
+}
+
+/// Hashes the latest versioned constants, or the configured versioned constants file, together
+/// with the configured overrides.
+fn versioned_constants_hash(overrides: &VersionedConstantsOverrides) -> String {
+    let VersionedConstantsOverrides {
+        validate_max_n_steps,
+        max_recursion_depth,
+        invoke_tx_max_n_steps,
+        execution_quotas,
+        versioned_constants_path,
+    } = overrides;
+    let versioned_constants_json = match versioned_constants_path {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|error| {
+            panic!("Failed to read versioned constants file {}: {error}", path.display())
+        }),
+        None => VERSIONED_CONSTANTS_LATEST_JSON.clone(),
+    };
+    let mut hash_input = format!(
+        "{versioned_constants_json}validate_max_n_steps:{validate_max_n_steps},\
+         max_recursion_depth:{max_recursion_depth},invoke_tx_max_n_steps:{invoke_tx_max_n_steps}"
+    );
+    // Appended only if set, keeping the identities of nodes without quotas unchanged.
+    if !execution_quotas.is_empty() {
+        hash_input += &format!(",execution_quotas:{}", String::from(execution_quotas.clone()));
+    }
+    starknet_keccak_hash(hash_input.as_bytes()).to_hex_string()
+}
+
+/// Describes every property on which the remote identity differs from the local one.
+pub(crate) fn identity_mismatches(local: &NodeIdentity, remote: &NodeIdentity) -> Vec<String> {
+    let mut keys: Vec<&String> = local.keys().chain(remote.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter(|key| local.get(*key) != remote.get(*key))
+        .map(|key| format!("{key}: local {:?}, remote {:?}", local.get(key), remote.get(key)))
+        .collect()
+}
+
+/// Waits for the remote component to answer a handshake, and verifies it agrees on the node
+/// identity. Components that are not remote are trivially consistent.
+pub(crate) async fn verify_remote_node_identity<Request, Response>(
+    label: &str,
+    remote_client: Option<RemoteComponentClient<Request, Response>>,
+    node_identity: &NodeIdentity,
+) -> anyhow::Result<()>
+where
+    Request: Serialize + DeserializeOwned + Debug,
+    Response: Serialize + DeserializeOwned + Debug,
+{
+    let Some(remote_client) = remote_client else {
+        return Ok(());
+    };
+
+    let remote_identity = loop {
+        match remote_client.handshake().await {
+            Ok(remote_identity) => break remote_identity,
+            Err(error) => {
+                warn!("Handshake with remote {label} failed, retrying: {error}");
+                tokio::time::sleep(HANDSHAKE_RETRY_INTERVAL).await;
+            }
+        }
+    };
+
+    let mismatches = identity_mismatches(node_identity, &remote_identity);
+    if !mismatches.is_empty() {
+        anyhow::bail!("Remote {label} is inconsistent with this node: {}.", mismatches.join("; "));
+    }
+    info!("Remote {label} is consistent with this node.");
+    Ok(())
+}
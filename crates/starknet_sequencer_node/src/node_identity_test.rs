@@ -0,0 +1,46 @@
+use starknet_sequencer_infra::component_definitions::NodeIdentity;
+
+use crate::config::node_config::SequencerNodeConfig;
+use crate::node_identity::{identity_mismatches, node_identity};
+
+fn identity(entries: &[(&str, &str)]) -> NodeIdentity {
+    entries.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn node_identity_is_deterministic() {
+    let config = SequencerNodeConfig::default();
+    assert_eq!(node_identity(&config), node_identity(&config));
+    assert!(identity_mismatches(&node_identity(&config), &node_identity(&config)).is_empty());
+}
+
+#[test]
+fn node_identity_depends_on_versioned_constants_overrides() {
+    let config = SequencerNodeConfig::default();
+    let mut overridden_config = config.clone();
+    overridden_config
+        .batcher_config
+        .block_builder_config
+        .versioned_constants_overrides
+        .max_recursion_depth += 1;
+
+    let mismatches =
+        identity_mismatches(&node_identity(&config), &node_identity(&overridden_config));
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0].starts_with("versioned_constants_hash"));
+}
+
+#[test]
+fn identity_mismatches_reports_differing_and_missing_properties() {
+    let local = identity(&[("chain_id", "SN_MAIN"), ("start_height", "0")]);
+    let remote = identity(&[("chain_id", "SN_SEPOLIA"), ("starknet_version", "0.13.4")]);
+
+    assert_eq!(
+        identity_mismatches(&local, &remote),
+        vec![
+            r#"chain_id: local Some("SN_MAIN"), remote Some("SN_SEPOLIA")"#.to_string(),
+            r#"start_height: local Some("0"), remote None"#.to_string(),
+            r#"starknet_version: local None, remote Some("0.13.4")"#.to_string(),
+        ]
+    );
+}
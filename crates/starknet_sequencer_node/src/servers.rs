@@ -7,6 +7,7 @@ use starknet_consensus_manager::communication::ConsensusManagerServer;
 use starknet_gateway::communication::{LocalGatewayServer, RemoteGatewayServer};
 use starknet_http_server::communication::HttpServer;
 use starknet_l1_provider::communication::{LocalL1ProviderServer, RemoteL1ProviderServer};
+use starknet_l1_provider::l1_scraper::L1ScraperServer;
 use starknet_mempool::communication::{LocalMempoolServer, RemoteMempoolServer};
 use starknet_mempool_p2p::propagator::{
     LocalMempoolP2pPropagatorServer,
@@ -20,11 +21,14 @@ use starknet_sequencer_infra::component_server::{
     RemoteComponentServer,
     WrapperServer,
 };
+use starknet_sequencer_infra::crash_report::{subscribe_to_crash_reports, CrashReport};
 use starknet_sequencer_infra::errors::ComponentServerError;
 use starknet_state_sync::runner::StateSyncRunnerServer;
 use starknet_state_sync::{LocalStateSyncServer, RemoteStateSyncServer};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::task::{JoinError, JoinSet};
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::clients::SequencerNodeClients;
 use crate::communication::SequencerNodeCommunication;
@@ -34,6 +38,7 @@ use crate::config::component_execution_config::{
     ReactiveComponentExecutionMode,
 };
 use crate::config::node_config::SequencerNodeConfig;
+use crate::node_identity::node_identity;
 
 // Component servers that can run locally.
 struct LocalServers {
@@ -49,6 +54,7 @@ struct LocalServers {
 struct WrapperServers {
     pub(crate) consensus_manager: Option<Box<ConsensusManagerServer>>,
     pub(crate) http_server: Option<Box<HttpServer>>,
+    pub(crate) l1_scraper: Option<Box<L1ScraperServer>>,
     pub(crate) monitoring_endpoint: Option<Box<MonitoringEndpointServer>>,
     pub(crate) mempool_p2p_runner: Option<Box<MempoolP2pRunnerServer>>,
     pub(crate) state_sync_runner: Option<Box<StateSyncRunnerServer>>,
@@ -81,6 +87,7 @@ pub struct SequencerNodeServers {
 /// * `$local_client_getter` - Local client getter function, used for the remote server
 ///   initialization if needed.
 /// * `$config` - Remote server configuration.
+/// * `$node_identity` - The node identity answered on handshakes.
 ///
 /// # Returns
 ///
@@ -94,7 +101,8 @@ pub struct SequencerNodeServers {
 /// let batcher_remote_server = create_remote_server!(
 ///     &config.components.batcher.execution_mode,
 ///     || {clients.get_gateway_local_client()},
-///     config.remote_server_config
+///     config.remote_server_config,
+///     node_identity
 /// );
 /// match batcher_remote_server {
 ///     Some(server) => println!("Remote server created: {:?}", server),
@@ -103,7 +111,12 @@ pub struct SequencerNodeServers {
 /// ```
 #[macro_export]
 macro_rules! create_remote_server {
-    ($execution_mode:expr, $local_client_getter:expr, $remote_server_config:expr) => {
+    (
+        $execution_mode:expr,
+        $local_client_getter:expr,
+        $remote_server_config:expr,
+        $node_identity:expr
+    ) => {
         match *$execution_mode {
             ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
                 let local_client = $local_client_getter()
@@ -112,10 +125,10 @@ macro_rules! create_remote_server {
                     .as_ref()
                     .expect("Remote server config should be set for inbound remote connections.");
 
-                Some(Box::new(RemoteComponentServer::new(
-                    local_client,
-                    remote_server_config.clone(),
-                )))
+                Some(Box::new(
+                    RemoteComponentServer::new(local_client, remote_server_config.clone())
+                        .with_node_identity($node_identity.clone()),
+                ))
             }
             ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
             | ReactiveComponentExecutionMode::Remote
@@ -294,40 +307,48 @@ pub fn create_remote_servers(
     config: &SequencerNodeConfig,
     clients: &SequencerNodeClients,
 ) -> RemoteServers {
+    let node_identity = node_identity(config);
+
     let batcher_server = create_remote_server!(
         &config.components.batcher.execution_mode,
         || { clients.get_batcher_local_client() },
-        config.components.batcher.remote_server_config
+        config.components.batcher.remote_server_config,
+        node_identity
     );
 
     let gateway_server = create_remote_server!(
         &config.components.gateway.execution_mode,
         || { clients.get_gateway_local_client() },
-        config.components.gateway.remote_server_config
+        config.components.gateway.remote_server_config,
+        node_identity
     );
 
     let l1_provider_server = create_remote_server!(
         &config.components.l1_provider.execution_mode,
         || { clients.get_l1_provider_local_client() },
-        config.components.l1_provider.remote_server_config
+        config.components.l1_provider.remote_server_config,
+        node_identity
     );
 
     let mempool_server = create_remote_server!(
         &config.components.mempool.execution_mode,
         || { clients.get_mempool_local_client() },
-        config.components.mempool.remote_server_config
+        config.components.mempool.remote_server_config,
+        node_identity
     );
 
     let mempool_p2p_propagator_server = create_remote_server!(
         &config.components.mempool_p2p.execution_mode,
         || { clients.get_mempool_p2p_propagator_local_client() },
-        config.components.mempool_p2p.remote_server_config
+        config.components.mempool_p2p.remote_server_config,
+        node_identity
     );
 
     let state_sync_server = create_remote_server!(
         &config.components.state_sync.execution_mode,
         || { clients.get_state_sync_local_client() },
-        config.components.state_sync.remote_server_config
+        config.components.state_sync.remote_server_config,
+        node_identity
     );
 
     RemoteServers {
@@ -367,6 +388,10 @@ fn create_wrapper_servers(
         components.http_server
     );
 
+    // The scraper is created only if the L1 provider runs locally and L1 scraping is configured.
+    let l1_scraper_server =
+        components.l1_scraper.take().map(|l1_scraper| Box::new(WrapperServer::new(l1_scraper)));
+
     let monitoring_endpoint_server = create_wrapper_server!(
         &config.components.monitoring_endpoint.execution_mode,
         components.monitoring_endpoint
@@ -384,6 +409,7 @@ fn create_wrapper_servers(
     WrapperServers {
         consensus_manager: consensus_manager_server,
         http_server,
+        l1_scraper: l1_scraper_server,
         monitoring_endpoint: monitoring_endpoint_server,
         mempool_p2p_runner: mempool_p2p_runner_server,
         state_sync_runner: state_sync_runner_server,
@@ -395,6 +421,7 @@ impl WrapperServers {
         create_servers(vec![
             server_future_and_label(self.consensus_manager, "Consensus Manager"),
             server_future_and_label(self.http_server, "Http"),
+            server_future_and_label(self.l1_scraper, "L1 Scraper"),
             server_future_and_label(self.monitoring_endpoint, "Monitoring Endpoint"),
             server_future_and_label(self.mempool_p2p_runner, "Mempool P2P Runner"),
             server_future_and_label(self.state_sync_runner, "State Sync Runner"),
@@ -438,7 +465,24 @@ fn get_server_error(
     }
 }
 
+// Logs the crash reports of the components. A component that panics while handling a request
+// keeps serving the following ones, so crashes do not stop the node.
+async fn log_crash_reports(mut crash_reports: broadcast::Receiver<CrashReport>) {
+    loop {
+        match crash_reports.recv().await {
+            Ok(crash_report) => {
+                error!("{crash_report}.\nBacktrace:\n{}", crash_report.backtrace);
+            }
+            Err(RecvError::Lagged(n_skipped_reports)) => {
+                warn!("Skipped logging {n_skipped_reports} crash reports.");
+            }
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
 pub async fn run_component_servers(servers: SequencerNodeServers) -> anyhow::Result<()> {
+    let crash_reports_logger = tokio::spawn(log_crash_reports(subscribe_to_crash_reports()));
     let mut local_servers = servers.local_servers.run().await;
     let mut remote_servers = servers.remote_servers.run().await;
     let mut wrapper_servers = servers.wrapper_servers.run().await;
@@ -462,6 +506,7 @@ pub async fn run_component_servers(servers: SequencerNodeServers) -> anyhow::Res
     local_servers.abort_all();
     remote_servers.abort_all();
     wrapper_servers.abort_all();
+    crash_reports_logger.abort();
 
     result
 }
@@ -1,8 +1,11 @@
+use tracing::info;
+
 use crate::clients::{create_node_clients, SequencerNodeClients};
 use crate::communication::create_node_channels;
 use crate::components::create_node_components;
 use crate::config::node_config::SequencerNodeConfig;
-use crate::servers::{create_node_servers, SequencerNodeServers};
+use crate::node_identity::node_identity;
+use crate::servers::{create_node_servers, run_component_servers, SequencerNodeServers};
 
 pub fn create_node_modules(
     config: &SequencerNodeConfig,
@@ -14,3 +17,19 @@ pub fn create_node_modules(
 
     (clients, servers)
 }
+
+/// Creates the node modules and runs its servers.
+pub async fn run_node(config: &SequencerNodeConfig) -> anyhow::Result<()> {
+    // Clients should not be dropped, as the components use them.
+    let (clients, servers) = create_node_modules(config);
+    let node_identity = node_identity(config);
+
+    // The servers must be running while verifying the node identity, so that remote components
+    // verifying this node concurrently can complete their handshakes.
+    info!("Starting components!");
+    tokio::try_join!(
+        run_component_servers(servers),
+        clients.verify_remote_node_identities(&node_identity)
+    )?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::os::unix::process::ExitStatusExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -14,6 +15,7 @@ use crate::constants::CAIRO_LANG_BINARY_NAME;
 use crate::constants::CAIRO_NATIVE_BINARY_NAME;
 use crate::errors::CompilationUtilError;
 use crate::paths::binary_path;
+use crate::resource_limits::ResourceLimits;
 use crate::SierraToCasmCompiler;
 #[cfg(feature = "cairo_native")]
 use crate::SierraToNativeCompiler;
@@ -52,7 +54,12 @@ impl SierraToCasmCompiler for CommandLineCompiler {
             &self.config.max_bytecode_size.to_string(),
         ];
 
-        let stdout = compile_with_args(compiler_binary_path, contract_class, &additional_args)?;
+        let stdout = compile_with_args(
+            compiler_binary_path,
+            contract_class,
+            &additional_args,
+            ResourceLimits::from(&self.config),
+        )?;
         Ok(serde_json::from_slice::<CasmContractClass>(&stdout)?)
     }
 }
@@ -71,7 +78,12 @@ impl SierraToNativeCompiler for CommandLineCompiler {
         )?;
         let additional_args = [output_file_path];
 
-        let _stdout = compile_with_args(compiler_binary_path, contract_class, &additional_args)?;
+        let _stdout = compile_with_args(
+            compiler_binary_path,
+            contract_class,
+            &additional_args,
+            ResourceLimits::from(&self.config),
+        )?;
 
         Ok(AotContractExecutor::load(Path::new(&output_file_path))?)
     }
@@ -81,6 +93,7 @@ fn compile_with_args(
     compiler_binary_path: &Path,
     contract_class: ContractClass,
     additional_args: &[&str],
+    resource_limits: ResourceLimits,
 ) -> Result<Vec<u8>, CompilationUtilError> {
     // Create a temporary file to store the Sierra contract class.
     let serialized_contract_class = serde_json::to_string(&contract_class)?;
@@ -92,14 +105,20 @@ fn compile_with_args(
     ))?;
 
     // Set the parameters for the compile process.
-    // TODO(Arni, Avi): Setup the ulimit for the process.
     let mut command = Command::new(compiler_binary_path.as_os_str());
     command.arg(temp_file_path).args(additional_args);
+    resource_limits.apply(&mut command);
 
     // Run the compile process.
     let compile_output = command.output()?;
 
     if !compile_output.status.success() {
+        if let Some(signal) = compile_output.status.signal() {
+            return Err(CompilationUtilError::CompilationError(format!(
+                "Compilation process was terminated by signal {signal}; it may have exceeded its \
+                 resource limits ({resource_limits:?})."
+            )));
+        }
         let stderr_output = String::from_utf8(compile_output.stderr)
             .unwrap_or("Failed to get stderr output".into());
         return Err(CompilationUtilError::CompilationError(stderr_output));
@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ClassHash, CompiledClassHash};
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+use crate::errors::CompilationUtilError;
+
+#[cfg(test)]
+#[path = "compilation_cache_test.rs"]
+mod test;
+
+/// A disk-backed cache of Casm classes compiled from Sierra, that outlives the process; a file per
+/// class, named by its compiled class hash. Entries are verified when loaded: the Casm must hash to
+/// the requested compiled class hash and must have been compiled from the requested Sierra class.
+/// Entries that fail verification are removed, and the class is compiled again.
+#[derive(Debug)]
+pub struct CasmCompilationCache {
+    dir: PathBuf,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CacheEntry {
+    // The hash of the Sierra class the Casm was compiled from.
+    class_hash: ClassHash,
+    casm: CasmContractClass,
+}
+
+impl CasmCompilationCache {
+    /// Opens the cache in the given directory, creating it if needed.
+    pub fn new(dir: PathBuf) -> Result<Self, CompilationUtilError> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the Casm compiled from the Sierra class of the given hash, if it is cached under the
+    /// given compiled class hash and passes verification.
+    pub fn get(
+        &self,
+        class_hash: ClassHash,
+        compiled_class_hash: CompiledClassHash,
+    ) -> Option<CasmContractClass> {
+        let path = self.entry_path(compiled_class_hash);
+        let serialized_entry = match fs::read(&path) {
+            Ok(serialized_entry) => serialized_entry,
+            Err(error) if error.kind() == ErrorKind::NotFound => return None,
+            Err(error) => {
+                warn!("Failed to read compilation cache entry {}: {error}", path.display());
+                return None;
+            }
+        };
+
+        match serde_json::from_slice::<CacheEntry>(&serialized_entry) {
+            Ok(entry)
+                if entry.class_hash == class_hash
+                    && CompiledClassHash(entry.casm.compiled_class_hash())
+                        == compiled_class_hash =>
+            {
+                Some(entry.casm)
+            }
+            _ => {
+                warn!("Removing corrupted compilation cache entry {}.", path.display());
+                remove_entry(&path);
+                None
+            }
+        }
+    }
+
+    /// Caches the Casm compiled from the Sierra class of the given hash. The entry is written to a
+    /// temporary file first, so that a crash never leaves a partially written entry behind.
+    pub fn set(
+        &self,
+        class_hash: ClassHash,
+        casm: &CasmContractClass,
+    ) -> Result<(), CompilationUtilError> {
+        let compiled_class_hash = CompiledClassHash(casm.compiled_class_hash());
+        let entry = CacheEntry { class_hash, casm: casm.clone() };
+
+        let mut temp_file = NamedTempFile::new_in(&self.dir)?;
+        temp_file.write_all(&serde_json::to_vec(&entry)?)?;
+        temp_file
+            .persist(self.entry_path(compiled_class_hash))
+            .map_err(|error| CompilationUtilError::from(error.error))?;
+        Ok(())
+    }
+
+    fn entry_path(&self, compiled_class_hash: CompiledClassHash) -> PathBuf {
+        self.dir.join(format!("{}.json", compiled_class_hash.0.to_hex_string()))
+    }
+}
+
+fn remove_entry(path: &Path) {
+    if let Err(error) = fs::remove_file(path) {
+        warn!("Failed to remove compilation cache entry {}: {error}", path.display());
+    }
+}
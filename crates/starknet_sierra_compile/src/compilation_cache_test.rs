@@ -0,0 +1,48 @@
+use std::path::Path;
+use std::{env, fs};
+
+use infra_utils::path::resolve_project_relative_path;
+use mempool_test_utils::{FAULTY_ACCOUNT_CLASS_FILE, TEST_FILES_FOLDER};
+use starknet_api::class_hash;
+use starknet_api::core::CompiledClassHash;
+use tempfile::tempdir;
+
+use crate::command_line_compiler::CommandLineCompiler;
+use crate::compilation_cache::CasmCompilationCache;
+use crate::config::SierraToCasmCompilationConfig;
+use crate::test_utils::contract_class_from_file;
+use crate::SierraToCasmCompiler;
+
+#[test]
+fn test_compilation_cache() {
+    env::set_current_dir(resolve_project_relative_path(TEST_FILES_FOLDER).unwrap())
+        .expect("Failed to set current dir.");
+    let contract_class = contract_class_from_file(Path::new(FAULTY_ACCOUNT_CLASS_FILE));
+    let casm = CommandLineCompiler::new(SierraToCasmCompilationConfig::default())
+        .compile(contract_class)
+        .unwrap();
+    let compiled_class_hash = CompiledClassHash(casm.compiled_class_hash());
+    let (class_hash, other_class_hash) = (class_hash!("0x1"), class_hash!("0x2"));
+
+    let cache_dir = tempdir().unwrap();
+    let cache = CasmCompilationCache::new(cache_dir.path().to_path_buf()).unwrap();
+    assert_eq!(cache.get(class_hash, compiled_class_hash), None);
+    cache.set(class_hash, &casm).unwrap();
+
+    // The cache outlives the instance that filled it.
+    let cache = CasmCompilationCache::new(cache_dir.path().to_path_buf()).unwrap();
+    assert_eq!(cache.get(class_hash, compiled_class_hash), Some(casm.clone()));
+    assert_eq!(cache.get(class_hash, CompiledClassHash::default()), None);
+
+    // An entry compiled from a different Sierra class fails verification, and is removed.
+    assert_eq!(cache.get(other_class_hash, compiled_class_hash), None);
+    assert_eq!(cache.get(class_hash, compiled_class_hash), None);
+
+    // So is a corrupted entry.
+    cache.set(class_hash, &casm).unwrap();
+    let entry_path =
+        cache_dir.path().join(format!("{}.json", compiled_class_hash.0.to_hex_string()));
+    fs::write(&entry_path, b"corrupted").unwrap();
+    assert_eq!(cache.get(class_hash, compiled_class_hash), None);
+    assert!(!entry_path.exists());
+}
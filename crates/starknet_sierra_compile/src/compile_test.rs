@@ -8,19 +8,30 @@ use mempool_test_utils::{FAULTY_ACCOUNT_CLASS_FILE, TEST_FILES_FOLDER};
 use rstest::rstest;
 
 use crate::command_line_compiler::CommandLineCompiler;
-use crate::config::SierraToCasmCompilationConfig;
+use crate::config::{SierraCompilerBackend, SierraToCasmCompilationConfig};
 use crate::errors::CompilationUtilError;
+use crate::in_process_compiler::InProcessCompiler;
 use crate::test_utils::contract_class_from_file;
 use crate::SierraToCasmCompiler;
 #[cfg(feature = "cairo_native")]
 use crate::SierraToNativeCompiler;
 
 const SIERRA_TO_CASM_COMPILATION_CONFIG: SierraToCasmCompilationConfig =
-    SierraToCasmCompilationConfig { max_bytecode_size: 81920 };
+    SierraToCasmCompilationConfig {
+        max_bytecode_size: 81920,
+        compilation_cache_dir: None,
+        backend: SierraCompilerBackend::CommandLine,
+        max_cpu_time: 20,
+        max_memory_usage: 5 * 1024 * 1024 * 1024,
+    };
 
 fn command_line_compiler() -> CommandLineCompiler {
     CommandLineCompiler::new(SIERRA_TO_CASM_COMPILATION_CONFIG)
 }
+
+fn in_process_compiler() -> InProcessCompiler {
+    InProcessCompiler::new(SIERRA_TO_CASM_COMPILATION_CONFIG)
+}
 fn get_test_contract() -> ContractClass {
     env::set_current_dir(resolve_project_relative_path(TEST_FILES_FOLDER).unwrap())
         .expect("Failed to set current dir.");
@@ -37,6 +48,7 @@ fn get_faulty_test_contract() -> ContractClass {
 
 #[rstest]
 #[case::command_line_compiler(command_line_compiler())]
+#[case::in_process_compiler(in_process_compiler())]
 fn test_compile_sierra_to_casm(#[case] compiler: impl SierraToCasmCompiler) {
     let expected_casm_contract_length = 72304;
 
@@ -50,6 +62,7 @@ fn test_compile_sierra_to_casm(#[case] compiler: impl SierraToCasmCompiler) {
 // TODO(Arni, 1/5/2024): Add a test for panic result test.
 #[rstest]
 #[case::command_line_compiler(command_line_compiler())]
+#[case::in_process_compiler(in_process_compiler())]
 fn test_negative_flow_compile_sierra_to_casm(#[case] compiler: impl SierraToCasmCompiler) {
     let contract_class = get_faulty_test_contract();
 
@@ -57,6 +70,19 @@ fn test_negative_flow_compile_sierra_to_casm(#[case] compiler: impl SierraToCasm
     assert_matches!(result, Err(CompilationUtilError::CompilationError(..)));
 }
 
+#[test]
+fn test_compile_sierra_to_casm_exceeding_memory_limit() {
+    let compiler = CommandLineCompiler::new(SierraToCasmCompilationConfig {
+        max_memory_usage: 1024 * 1024,
+        ..SIERRA_TO_CASM_COMPILATION_CONFIG
+    });
+    let contract_class = get_test_contract();
+
+    // Depending on how early the limit is hit, either the compiler process fails to start, or it
+    // fails to allocate.
+    assert!(compiler.compile(contract_class).is_err());
+}
+
 #[cfg(feature = "cairo_native")]
 #[test]
 fn test_compile_sierra_to_native() {
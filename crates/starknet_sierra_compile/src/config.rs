@@ -1,29 +1,83 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+/// The compiler used to compile Sierra into Casm.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum SierraCompilerBackend {
+    /// Compiles in a separate process, whose CPU time and memory are limited.
+    #[default]
+    CommandLine,
+    /// Compiles in the calling process, without resource limits. Use only with trusted classes.
+    InProcess,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct SierraToCasmCompilationConfig {
     /// CASM bytecode size limit.
     pub max_bytecode_size: usize,
+    /// If set, the compiled classes are cached on disk in this directory, across restarts.
+    pub compilation_cache_dir: Option<PathBuf>,
+    /// Whether compilation runs in a separate, resource-limited process.
+    pub backend: SierraCompilerBackend,
+    /// CPU time limit of a compilation process, in seconds.
+    pub max_cpu_time: u64,
+    /// Memory limit of a compilation process, in bytes.
+    pub max_memory_usage: u64,
 }
 
 impl Default for SierraToCasmCompilationConfig {
     fn default() -> Self {
-        Self { max_bytecode_size: 81920 }
+        Self {
+            max_bytecode_size: 81920,
+            compilation_cache_dir: None,
+            backend: SierraCompilerBackend::default(),
+            max_cpu_time: 20,
+            max_memory_usage: 5 * 1024 * 1024 * 1024,
+        }
     }
 }
 
 impl SerializeConfig for SierraToCasmCompilationConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
-            "max_bytecode_size",
-            &self.max_bytecode_size,
-            "Limitation of contract bytecode size.",
+        let mut dump = BTreeMap::from_iter([
+            ser_param(
+                "max_bytecode_size",
+                &self.max_bytecode_size,
+                "Limitation of contract bytecode size.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "backend",
+                &self.backend,
+                "The compiler used to compile Sierra into Casm: CommandLine, in a separate \
+                 resource-limited process, or InProcess.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_cpu_time",
+                &self.max_cpu_time,
+                "Limitation of the CPU time of a compilation process, in seconds.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_memory_usage",
+                &self.max_memory_usage,
+                "Limitation of the memory of a compilation process, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.append(&mut ser_optional_param(
+            &self.compilation_cache_dir,
+            PathBuf::from("./sequencer_data/compilation_cache"),
+            "compilation_cache_dir",
+            "If set, the directory in which the compiled classes are cached across restarts.",
             ParamPrivacyInput::Public,
-        )])
+        ));
+        dump
     }
 }
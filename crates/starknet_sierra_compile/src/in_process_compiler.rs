@@ -0,0 +1,36 @@
+use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+
+use crate::config::SierraToCasmCompilationConfig;
+use crate::errors::CompilationUtilError;
+use crate::SierraToCasmCompiler;
+
+/// Compiles Sierra into Casm in the calling process.
+/// NOTE: the resource limits of the config are not enforced, so a malicious class may exhaust the
+/// CPU or memory of the calling process. Use only with trusted classes.
+#[derive(Clone)]
+pub struct InProcessCompiler {
+    pub config: SierraToCasmCompilationConfig,
+}
+
+impl InProcessCompiler {
+    pub fn new(config: SierraToCasmCompilationConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl SierraToCasmCompiler for InProcessCompiler {
+    fn compile(
+        &self,
+        contract_class: ContractClass,
+    ) -> Result<CasmContractClass, CompilationUtilError> {
+        contract_class.validate_version_compatible(ListSelector::default())?;
+        let add_pythonic_hints = true;
+        Ok(CasmContractClass::from_contract_class(
+            contract_class,
+            add_pythonic_hints,
+            self.config.max_bytecode_size,
+        )?)
+    }
+}
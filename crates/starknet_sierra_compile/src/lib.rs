@@ -1,16 +1,24 @@
 //! A lib for compiling Sierra into Casm.
+use std::sync::Arc;
+
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_starknet_classes::contract_class::ContractClass;
 #[cfg(feature = "cairo_native")]
 use cairo_native::executor::AotContractExecutor;
 
+use crate::command_line_compiler::CommandLineCompiler;
+use crate::config::{SierraCompilerBackend, SierraToCasmCompilationConfig};
 use crate::errors::CompilationUtilError;
+use crate::in_process_compiler::InProcessCompiler;
 
 pub mod command_line_compiler;
+pub mod compilation_cache;
 pub mod config;
 pub mod constants;
 pub mod errors;
+pub mod in_process_compiler;
 pub mod paths;
+pub mod resource_limits;
 pub mod utils;
 
 #[cfg(test)]
@@ -27,6 +35,16 @@ pub trait SierraToCasmCompiler: Send + Sync {
     ) -> Result<CasmContractClass, CompilationUtilError>;
 }
 
+/// Creates the Sierra to Casm compiler of the backend the given config selects.
+pub fn create_sierra_to_casm_compiler(
+    config: SierraToCasmCompilationConfig,
+) -> Arc<dyn SierraToCasmCompiler> {
+    match config.backend {
+        SierraCompilerBackend::CommandLine => Arc::new(CommandLineCompiler::new(config)),
+        SierraCompilerBackend::InProcess => Arc::new(InProcessCompiler::new(config)),
+    }
+}
+
 #[cfg(feature = "cairo_native")]
 pub trait SierraToNativeCompiler: Send + Sync {
     fn compile_to_native(
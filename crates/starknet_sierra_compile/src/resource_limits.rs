@@ -0,0 +1,36 @@
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+use rlimit::{setrlimit, Resource};
+
+use crate::config::SierraToCasmCompilationConfig;
+
+/// Limits on the resources of a compilation process. A process exceeding its CPU time is killed;
+/// one exceeding its memory fails to allocate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResourceLimits {
+    /// In seconds.
+    pub cpu_time: u64,
+    /// In bytes.
+    pub memory_usage: u64,
+}
+
+impl ResourceLimits {
+    /// Sets the limits of the process the given command spawns, before it starts running.
+    pub fn apply(self, command: &mut Command) {
+        // SAFETY: the closure runs in the forked child, and only makes `setrlimit` system calls,
+        // which neither allocate nor take locks.
+        unsafe {
+            command.pre_exec(move || {
+                setrlimit(Resource::CPU, self.cpu_time, self.cpu_time)?;
+                setrlimit(Resource::AS, self.memory_usage, self.memory_usage)
+            });
+        }
+    }
+}
+
+impl From<&SierraToCasmCompilationConfig> for ResourceLimits {
+    fn from(config: &SierraToCasmCompilationConfig) -> Self {
+        Self { cpu_time: config.max_cpu_time, memory_usage: config.max_memory_usage }
+    }
+}
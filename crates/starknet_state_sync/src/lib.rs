@@ -14,6 +14,7 @@ use starknet_api::block::BlockNumber;
 use starknet_api::contract_class::{ContractClass, SierraVersion};
 use starknet_api::core::{ClassHash, ContractAddress, Nonce, BLOCK_HASH_TABLE_ADDRESS};
 use starknet_api::state::{StateNumber, StorageKey};
+use starknet_api::transaction::Transaction;
 use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
 use starknet_sequencer_infra::component_server::{LocalComponentServer, RemoteComponentServer};
 use starknet_state_sync_types::communication::{StateSyncRequest, StateSyncResponse};
@@ -85,6 +86,9 @@ impl StateSync {
         let Some(block_transaction_hashes) = txn.get_block_transaction_hashes(block_number)? else {
             return Ok(None);
         };
+        let Some(block_transactions) = txn.get_block_transactions(block_number)? else {
+            return Ok(None);
+        };
         let Some(thin_state_diff) = txn.get_state_diff(block_number)? else {
             return Ok(None);
         };
@@ -95,6 +99,13 @@ impl StateSync {
             state_diff: thin_state_diff,
             block_header_without_hash: block_header.block_header_without_hash,
             transaction_hashes: block_transaction_hashes,
+            consumed_l1_message_nonces: block_transactions
+                .iter()
+                .filter_map(|tx| match tx {
+                    Transaction::L1Handler(l1_handler_tx) => Some(l1_handler_tx.nonce),
+                    _ => None,
+                })
+                .collect(),
         }))
     }
 
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockHeaderWithoutHash;
+use starknet_api::core::Nonce;
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::TransactionHash;
 
@@ -18,4 +19,6 @@ pub struct SyncBlock {
     // TODO: decide if we want block hash, parent block hash and full classes here.
     pub transaction_hashes: Vec<TransactionHash>,
     pub block_header_without_hash: BlockHeaderWithoutHash,
+    /// The nonces of the L1 messages consumed by the L1 handler transactions of the block.
+    pub consumed_l1_message_nonces: Vec<Nonce>,
 }